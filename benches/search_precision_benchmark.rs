@@ -75,6 +75,11 @@ fn bench_query_latency(c: &mut Criterion) {
                         no_sketch: false,
                         acl_context: None,
                         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                        diversify: None,
+                        highlight: None,
+                        facets: Vec::new(),
+                        sort: memvid_core::types::SearchSort::Relevance,
+                        explain: false,
                     })
                     .unwrap();
                 total += start.elapsed();
@@ -110,6 +115,11 @@ fn bench_precision(c: &mut Criterion) {
                         no_sketch: false,
                         acl_context: None,
                         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                        diversify: None,
+                        highlight: None,
+                        facets: Vec::new(),
+                        sort: memvid_core::types::SearchSort::Relevance,
+                        explain: false,
                     })
                     .unwrap();
 
@@ -155,6 +165,11 @@ fn bench_result_count(c: &mut Criterion) {
                         no_sketch: false,
                         acl_context: None,
                         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                        diversify: None,
+                        highlight: None,
+                        facets: Vec::new(),
+                        sort: memvid_core::types::SearchSort::Relevance,
+                        explain: false,
                     })
                     .unwrap();
                 let _count = results.hits.len();
@@ -1,6 +1,7 @@
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
-use memvid_core::types::FrameId;
+use memvid_core::types::{FrameId, VectorCompression};
 use memvid_core::vec::{VecDocument, VecIndex, VecIndexBuilder};
+use memvid_core::{DEFAULT_RESCORE_OVERSAMPLE, IvfPqBuilder, IvfPqIndex, recall_at_k};
 
 fn generate_vectors(count: usize, dim: usize) -> Vec<Vec<f32>> {
     let mut vectors = Vec::with_capacity(count);
@@ -136,10 +137,139 @@ fn bench_search_100k(c: &mut Criterion) {
     group.finish();
 }
 
+/// Mean recall@k of `index` against brute-force search over `queries`.
+fn mean_recall(
+    index: &VecIndex,
+    baseline: &VecIndex,
+    queries: &[Vec<f32>],
+    k: usize,
+    oversample: usize,
+) -> f64 {
+    let total: f64 = queries
+        .iter()
+        .map(|query| {
+            let expected = baseline.search(query, k);
+            let actual = index.search_with_rescore(query, k, oversample);
+            recall_at_k(&expected, &actual, k)
+        })
+        .sum();
+    total / queries.len() as f64
+}
+
+fn bench_quantized_10k(c: &mut Criterion) {
+    let count = 10_000;
+    let dim = 128;
+    let k = 10;
+    let vectors = generate_vectors(count, dim);
+    let queries = generate_vectors(50, dim);
+
+    let documents: Vec<VecDocument> = vectors
+        .iter()
+        .enumerate()
+        .map(|(i, vec)| VecDocument {
+            frame_id: i as FrameId,
+            embedding: vec.clone(),
+        })
+        .collect();
+    let brute_index = VecIndex::Uncompressed { documents };
+
+    let mut group = c.benchmark_group("search_10k_quantized");
+
+    for (name, compression) in [
+        ("int8", VectorCompression::Int8),
+        ("binary", VectorCompression::Binary),
+    ] {
+        let mut builder = VecIndexBuilder::new().with_compression(compression);
+        for (i, vec) in vectors.iter().enumerate() {
+            builder.add_document(i as FrameId, vec.clone());
+        }
+        let artifact = builder.finish().expect("finish quantized");
+        let index = VecIndex::decode(&artifact.bytes).expect("decode quantized");
+
+        let raw = mean_recall(&index, &brute_index, &queries, k, 0);
+        let rescored = mean_recall(
+            &index,
+            &brute_index,
+            &queries,
+            k,
+            DEFAULT_RESCORE_OVERSAMPLE,
+        );
+        println!("{name}: recall@{k} = {raw:.3} (raw), {rescored:.3} (rescored)");
+
+        let query = &queries[0];
+        group.bench_function(format!("{name}_raw"), |b| {
+            b.iter(|| {
+                let _ = index.search_with_rescore(black_box(query), black_box(k), 0);
+            })
+        });
+        group.bench_function(format!("{name}_rescored"), |b| {
+            b.iter(|| {
+                let _ = index.search_with_rescore(
+                    black_box(query),
+                    black_box(k),
+                    DEFAULT_RESCORE_OVERSAMPLE,
+                );
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_ivf_pq_100k(c: &mut Criterion) {
+    let count = 100_000;
+    let dim = 128;
+    let k = 10;
+    let vectors = generate_vectors(count, dim);
+    let queries = generate_vectors(20, dim);
+
+    let documents: Vec<VecDocument> = vectors
+        .iter()
+        .enumerate()
+        .map(|(i, vec)| VecDocument {
+            frame_id: i as FrameId,
+            embedding: vec.clone(),
+        })
+        .collect();
+    let brute_index = VecIndex::Uncompressed { documents };
+
+    let mut builder = IvfPqBuilder::new().with_nlist(256).with_iterations(10, 10);
+    builder.train(&vectors[..10_000]).expect("train ivf-pq");
+    for (i, vec) in vectors.iter().enumerate() {
+        builder.add_document(i as FrameId, vec).expect("add ivf-pq");
+    }
+    let artifact = builder.finish().expect("finish ivf-pq");
+    let index = IvfPqIndex::from_bytes(artifact.bytes).expect("open ivf-pq");
+
+    let mut group = c.benchmark_group("search_100k_ivf_pq");
+    for nprobe in [4, 16, 64] {
+        let recall: f64 = queries
+            .iter()
+            .map(|query| {
+                let expected = brute_index.search(query, k);
+                recall_at_k(&expected, &index.search(query, k, nprobe), k)
+            })
+            .sum::<f64>()
+            / queries.len() as f64;
+        println!("ivf-pq nprobe={nprobe}: recall@{k} = {recall:.3}");
+
+        let query = &queries[0];
+        group.bench_function(format!("nprobe_{nprobe}"), |b| {
+            b.iter(|| {
+                let _ = index.search(black_box(query), black_box(k), nprobe);
+            })
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_search_10k,
     bench_search_50k,
-    bench_search_100k
+    bench_search_100k,
+    bench_quantized_10k,
+    bench_ivf_pq_100k
 );
 criterion_main!(benches);
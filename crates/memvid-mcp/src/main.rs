@@ -591,6 +591,13 @@ impl MemvidMcp {
                 no_sketch: request.no_sketch.unwrap_or(false),
                 #[cfg(feature = "temporal_track")]
                 temporal: None,
+                acl_context: None,
+                acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                diversify: None,
+                highlight: None,
+                facets: Vec::new(),
+                sort: memvid_core::types::SearchSort::Relevance,
+                explain: false,
             };
             let mut mem = Memvid::open_read_only(path).map_err(|err| err.to_string())?;
             let response = mem.search(search_request).map_err(|err| err.to_string())?;
@@ -864,6 +871,7 @@ fn frame_role_label(role: FrameRole) -> &'static str {
         FrameRole::Document => "document",
         FrameRole::DocumentChunk => "document_chunk",
         FrameRole::ExtractedImage => "extracted_image",
+        FrameRole::Attachment => "attachment",
     }
 }
 
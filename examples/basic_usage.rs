@@ -100,6 +100,11 @@ fn main() -> Result<()> {
         no_sketch: false,
         acl_context: None,
         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        diversify: None,
+        highlight: None,
+        facets: Vec::new(),
+        sort: memvid_core::types::SearchSort::Relevance,
+        explain: false,
     };
     let response = mem.search(request)?;
     println!("   Query: 'memvid'");
@@ -131,6 +136,11 @@ fn main() -> Result<()> {
         no_sketch: false,
         acl_context: None,
         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        diversify: None,
+        highlight: None,
+        facets: Vec::new(),
+        sort: memvid_core::types::SearchSort::Relevance,
+        explain: false,
     };
     let response = mem.search(request)?;
     println!("   Query: 'documentation' (scope: mv2://docs/)");
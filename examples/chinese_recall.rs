@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use memvid_core::{Memvid, PutOptions, Result, SearchRequest};
+use memvid_core::{LANGUAGE_METADATA_KEY, Memvid, PutOptions, Result, SearchRequest};
 
 const RESET: &str = "\x1b[0m";
 const RED: &str = "\x1b[31m";
@@ -443,6 +443,46 @@ fn main() -> Result<()> {
             labels: &["infra"],
             track: Some("eng"),
         },
+        Doc {
+            title: "東京オフィス移転",
+            uri: "mv2://ja/facilities/tokyo-office",
+            text: "東京オフィスは来年三月に移転します。新しいオフィスは渋谷駅の近くにあり、会議室が増えます。",
+            tags: &["facilities"],
+            labels: &["announcement"],
+            track: Some("biz"),
+        },
+        Doc {
+            title: "서울 데이터센터 점검",
+            uri: "mv2://ko/ops/seoul-maintenance",
+            text: "서울 데이터센터는 다음 주 토요일에 정기 점검을 진행합니다. 점검 중에는 서비스가 잠시 중단됩니다.",
+            tags: &["ops", "maintenance"],
+            labels: &["notice"],
+            track: Some("ops"),
+        },
+        Doc {
+            title: "Quartalsbericht Vertrieb",
+            uri: "mv2://de/sales/q3-report",
+            text: "Die Verkäufe im dritten Quartal sind deutlich gestiegen, vor allem bei den Kunden in Österreich und der Schweiz.",
+            tags: &["sales"],
+            labels: &["report"],
+            track: Some("biz"),
+        },
+        Doc {
+            title: "Réunions commerciales",
+            uri: "mv2://fr/sales/weekly-meetings",
+            text: "Les réunions hebdomadaires de l'équipe commerciale auront lieu le mardi matin dans la grande salle.",
+            tags: &["sales"],
+            labels: &["schedule"],
+            track: Some("biz"),
+        },
+        Doc {
+            title: "Facturas pendientes",
+            uri: "mv2://es/finance/pending-invoices",
+            text: "Las facturas pendientes deben enviarse al departamento de contabilidad antes del viernes por la tarde.",
+            tags: &["finance"],
+            labels: &["deadline"],
+            track: Some("biz"),
+        },
     ];
 
     for doc in &docs {
@@ -460,12 +500,14 @@ fn main() -> Result<()> {
     }
     mem.commit()?;
 
-    println!("Chinese recall demo (realistic mini corpus)");
+    println!("Multilingual recall harness (realistic mini corpus)");
     println!(
         "Indexed {} docs. Some queries are intentionally hard for debugging.\n",
         docs.len()
     );
-    println!("Note: default tokenizer is English + Jieba; Chinese recall can vary.\n");
+    println!(
+        "Note: frames are routed by detected language (en/de/fr/es stemmers, Jieba for zh, bigrams for ja/ko).\n"
+    );
     println!(
         "Colors: OK=green, MISS=red, KNOWN_MISS=yellow, alignment=green/red (MEMVID_COLOR=always|never|auto, NO_COLOR=1, FORCE_COLOR=1)\n"
     );
@@ -485,13 +527,19 @@ fn main() -> Result<()> {
                 doc.labels.join(", ")
             };
             let track = doc.track.unwrap_or("<none>");
+            let language = mem
+                .frame_by_uri(doc.uri)
+                .ok()
+                .and_then(|frame| frame.extra_metadata.get(LANGUAGE_METADATA_KEY).cloned())
+                .unwrap_or_else(|| "<default>".to_string());
             println!(
-                "  {}. {} (tags: {}; labels: {}; track: {})",
+                "  {}. {} (tags: {}; labels: {}; track: {}; language: {})",
                 idx + 1,
                 doc.title,
                 tags,
                 labels,
-                track
+                track,
+                language
             );
         }
         println!();
@@ -600,6 +648,48 @@ fn main() -> Result<()> {
             expectation: Expectation::ExpectNone,
             note: "中英文混用但语义分离。",
         },
+        Case {
+            query: "移転",
+            expected_titles: &["東京オフィス移転"],
+            expectation: Expectation::MustMatch,
+            note: "日本語: bigram match inside a kana/kanji run.",
+        },
+        Case {
+            query: "渋谷駅",
+            expected_titles: &["東京オフィス移転"],
+            expectation: Expectation::MustMatch,
+            note: "日本語: multi-bigram phrase.",
+        },
+        Case {
+            query: "점검",
+            expected_titles: &["서울 데이터센터 점검"],
+            expectation: Expectation::MustMatch,
+            note: "한국어: word followed by a particle (점검을).",
+        },
+        Case {
+            query: "데이터센터",
+            expected_titles: &["서울 데이터센터 점검"],
+            expectation: Expectation::MustMatch,
+            note: "한국어: compound noun with attached particle.",
+        },
+        Case {
+            query: "Kunde",
+            expected_titles: &["Quartalsbericht Vertrieb"],
+            expectation: Expectation::MustMatch,
+            note: "Deutsch: German stemming (Kunden -> kund).",
+        },
+        Case {
+            query: "réunion",
+            expected_titles: &["Réunions commerciales"],
+            expectation: Expectation::MustMatch,
+            note: "Français: plural folded by the French stemmer.",
+        },
+        Case {
+            query: "factura",
+            expected_titles: &["Facturas pendientes"],
+            expectation: Expectation::MustMatch,
+            note: "Español: plural folded by the Spanish stemmer.",
+        },
     ];
 
     let top_k = 5;
@@ -619,9 +709,15 @@ fn main() -> Result<()> {
             temporal: None,
             as_of_frame: None,
             as_of_ts: None,
-            no_sketch: false,
+            // Measure analyzer recall; the sketch pre-filter is a separate layer.
+            no_sketch: true,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            diversify: None,
+            highlight: None,
+            facets: Vec::new(),
+            sort: memvid_core::types::SearchSort::Relevance,
+            explain: false,
         };
         let response = mem.search(request)?;
         let hit_titles: Vec<&str> = response
@@ -863,5 +959,9 @@ fn main() -> Result<()> {
         avg_ms(summary.other_elapsed_ms, summary.other_total)
     );
 
+    // Non-zero exit so the harness can gate analyzer changes.
+    if summary.miss > 0 || summary.unexpected_hits > 0 {
+        std::process::exit(1);
+    }
     Ok(())
 }
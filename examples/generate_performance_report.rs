@@ -73,6 +73,11 @@ fn main() -> memvid_core::Result<()> {
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                diversify: None,
+                highlight: None,
+                facets: Vec::new(),
+                sort: memvid_core::types::SearchSort::Relevance,
+                explain: false,
             })?;
         }
 
@@ -98,6 +103,11 @@ fn main() -> memvid_core::Result<()> {
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                diversify: None,
+                highlight: None,
+                facets: Vec::new(),
+                sort: memvid_core::types::SearchSort::Relevance,
+                explain: false,
             })?;
 
             let terms: Vec<&str> = query.split_whitespace().collect();
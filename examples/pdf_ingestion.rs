@@ -115,6 +115,11 @@ fn main() -> Result<()> {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            diversify: None,
+            highlight: None,
+            facets: Vec::new(),
+            sort: memvid_core::types::SearchSort::Relevance,
+            explain: false,
         };
 
         let response = mem.search(request)?;
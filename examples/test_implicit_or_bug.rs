@@ -55,6 +55,11 @@ fn main() -> memvid_core::Result<()> {
         no_sketch: false,
         acl_context: None,
         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        diversify: None,
+        highlight: None,
+        facets: Vec::new(),
+        sort: memvid_core::types::SearchSort::Relevance,
+        explain: false,
     })?;
 
     println!("ACTUAL RESULTS: {} documents found", results.hits.len());
@@ -3,6 +3,10 @@
 //! This module provides cloud API embedding generation, enabling semantic search
 //! using external embedding services. Requires the `api_embed` feature.
 //!
+//! [`HttpEmbedder`] covers self-hosted servers: anything speaking the OpenAI
+//! `/v1/embeddings` schema (vLLM, llama.cpp server, LM Studio), plus the native
+//! Ollama and Text-Embeddings-Inference request shapes.
+//!
 //! # Example
 //!
 //! ```ignore
@@ -19,9 +23,12 @@
 
 use crate::error::{MemvidError, Result};
 use crate::types::embedding::EmbeddingProvider;
+use crate::types::{EmbeddingIdentity, VecEmbedder};
 use reqwest::blocking::Client;
-use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue};
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue, RETRY_AFTER};
 use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use std::sync::OnceLock;
 use std::time::Duration;
 
 // ============================================================================
@@ -460,6 +467,556 @@ impl EmbeddingProvider for OpenAIEmbedder {
     }
 }
 
+// ============================================================================
+// Generic HTTP Embedder (OpenAI-compatible, Ollama, TEI)
+// ============================================================================
+
+/// Request/response schema spoken by an embedding server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddingApiFormat {
+    /// OpenAI `/embeddings` schema (OpenAI, vLLM, llama.cpp server, LM Studio, ...).
+    OpenAI,
+    /// Ollama native `/api/embed`.
+    Ollama,
+    /// Hugging Face Text-Embeddings-Inference `/embed`.
+    Tei,
+}
+
+impl EmbeddingApiFormat {
+    fn endpoint(self) -> &'static str {
+        match self {
+            Self::OpenAI => "/embeddings",
+            Self::Ollama => "/api/embed",
+            Self::Tei => "/embed",
+        }
+    }
+
+    fn default_provider(self) -> &'static str {
+        match self {
+            Self::OpenAI => "openai-compatible",
+            Self::Ollama => "ollama",
+            Self::Tei => "tei",
+        }
+    }
+}
+
+/// Configuration for [`HttpEmbedder`].
+#[derive(Debug, Clone)]
+pub struct HttpEmbedderConfig {
+    /// Wire format of the server
+    pub format: EmbeddingApiFormat,
+    /// Server base URL; for the OpenAI format this includes the `/v1` prefix
+    pub base_url: String,
+    /// Model name sent to the server and recorded in the embedding identity
+    pub model: String,
+    /// Provider name recorded in the embedding identity
+    pub provider: String,
+    /// Environment variable holding a bearer token (`None` for unauthenticated servers)
+    pub api_key_env: Option<String>,
+    /// Known embedding dimension; detected from the first response when `None`
+    pub dimension: Option<usize>,
+    /// Whether the server returns L2-normalized vectors, if known
+    pub normalized: Option<bool>,
+    /// Request timeout in seconds
+    pub timeout_secs: u64,
+    /// Maximum retries on rate limit (429), server (5xx) and connection errors
+    pub max_retries: u32,
+    /// Initial backoff in milliseconds for exponential retry
+    pub initial_backoff_ms: u64,
+    /// Upper bound for a single backoff sleep in milliseconds
+    pub max_backoff_ms: u64,
+    /// Maximum texts per request
+    pub max_batch_size: usize,
+    /// Approximate token budget per request (estimated as 4 bytes per token)
+    pub max_batch_tokens: usize,
+}
+
+impl HttpEmbedderConfig {
+    fn new(format: EmbeddingApiFormat, base_url: String, model: String) -> Self {
+        Self {
+            format,
+            base_url,
+            model,
+            provider: format.default_provider().to_string(),
+            api_key_env: None,
+            dimension: None,
+            normalized: None,
+            timeout_secs: 30,
+            max_retries: 3,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 30_000,
+            max_batch_size: 256,
+            max_batch_tokens: 8192,
+        }
+    }
+
+    /// Any server implementing the OpenAI `/v1/embeddings` schema.
+    #[must_use]
+    pub fn openai_compatible(base_url: impl Into<String>, model: impl Into<String>) -> Self {
+        Self::new(EmbeddingApiFormat::OpenAI, base_url.into(), model.into())
+    }
+
+    /// A local Ollama server at `http://localhost:11434`.
+    #[must_use]
+    pub fn ollama(model: impl Into<String>) -> Self {
+        Self::new(
+            EmbeddingApiFormat::Ollama,
+            "http://localhost:11434".to_string(),
+            model.into(),
+        )
+    }
+
+    /// A Text-Embeddings-Inference server. TEI serves a single model, so
+    /// `model` is only recorded in the embedding identity.
+    #[must_use]
+    pub fn tei(base_url: impl Into<String>, model: impl Into<String>) -> Self {
+        Self::new(EmbeddingApiFormat::Tei, base_url.into(), model.into())
+    }
+
+    /// Set the server base URL
+    #[must_use]
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Set the provider name recorded in the embedding identity
+    #[must_use]
+    pub fn with_provider(mut self, provider: impl Into<String>) -> Self {
+        self.provider = provider.into();
+        self
+    }
+
+    /// Read a bearer token from this environment variable
+    #[must_use]
+    pub fn with_api_key_env(mut self, env_var: impl Into<String>) -> Self {
+        self.api_key_env = Some(env_var.into());
+        self
+    }
+
+    /// Declare the embedding dimension instead of detecting it
+    #[must_use]
+    pub fn with_dimension(mut self, dimension: usize) -> Self {
+        self.dimension = Some(dimension);
+        self
+    }
+
+    /// Declare whether the server returns normalized vectors
+    #[must_use]
+    pub fn with_normalized(mut self, normalized: bool) -> Self {
+        self.normalized = Some(normalized);
+        self
+    }
+
+    /// Set request timeout
+    #[must_use]
+    pub fn with_timeout(mut self, secs: u64) -> Self {
+        self.timeout_secs = secs;
+        self
+    }
+
+    /// Set retry count and initial backoff
+    #[must_use]
+    pub fn with_retries(mut self, max_retries: u32, initial_backoff_ms: u64) -> Self {
+        self.max_retries = max_retries;
+        self.initial_backoff_ms = initial_backoff_ms;
+        self
+    }
+
+    /// Set per-request limits on text count and estimated tokens
+    #[must_use]
+    pub fn with_batch_limits(mut self, max_batch_size: usize, max_batch_tokens: usize) -> Self {
+        self.max_batch_size = max_batch_size.max(1);
+        self.max_batch_tokens = max_batch_tokens.max(1);
+        self
+    }
+}
+
+#[derive(Deserialize)]
+struct CompatEmbeddingResponse {
+    data: Vec<CompatEmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct CompatEmbeddingData {
+    embedding: Vec<f32>,
+    #[serde(default)]
+    index: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct OllamaEmbeddingResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+/// Rough token estimate used for request batching (about 4 bytes per token).
+fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(4).max(1)
+}
+
+/// Split `texts` into consecutive batches of at most `max_items` texts and
+/// roughly `max_tokens` tokens. A text over the budget gets a batch of its own.
+fn plan_batches(texts: &[&str], max_items: usize, max_tokens: usize) -> Vec<Range<usize>> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut tokens = 0;
+    for (idx, text) in texts.iter().enumerate() {
+        let cost = estimate_tokens(text);
+        if idx > start && (idx - start >= max_items || tokens + cost > max_tokens) {
+            batches.push(start..idx);
+            start = idx;
+            tokens = 0;
+        }
+        tokens += cost;
+    }
+    if start < texts.len() {
+        batches.push(start..texts.len());
+    }
+    batches
+}
+
+/// Embedding provider for any HTTP embedding server.
+///
+/// Speaks the OpenAI `/embeddings` schema against any base URL, or the native
+/// Ollama and TEI request shapes. Requests are batched by text count and
+/// token budget and retried with exponential backoff. Every embedding carries
+/// an [`EmbeddingIdentity`] so frames and cache entries record which model
+/// produced them.
+///
+/// # Example
+///
+/// ```ignore
+/// use memvid_core::api_embed::{HttpEmbedder, HttpEmbedderConfig};
+///
+/// let embedder = HttpEmbedder::new(HttpEmbedderConfig::ollama("nomic-embed-text"))?;
+/// let dimension = embedder.detect_dimension()?;
+/// ```
+pub struct HttpEmbedder {
+    config: HttpEmbedderConfig,
+    client: Client,
+    api_key: Option<String>,
+    dimension: OnceLock<usize>,
+}
+
+impl HttpEmbedder {
+    /// Create a new HTTP embedder. No request is made until the first
+    /// embedding (or [`detect_dimension`](Self::detect_dimension)).
+    pub fn new(config: HttpEmbedderConfig) -> Result<Self> {
+        let api_key = match &config.api_key_env {
+            Some(env_var) => {
+                let key = std::env::var(env_var).map_err(|_| MemvidError::EmbeddingFailed {
+                    reason: format!("API key not found. Set the {env_var} environment variable.")
+                        .into(),
+                })?;
+                if key.is_empty() {
+                    return Err(MemvidError::EmbeddingFailed {
+                        reason: format!("{env_var} environment variable is empty").into(),
+                    });
+                }
+                Some(key)
+            }
+            None => None,
+        };
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| MemvidError::EmbeddingFailed {
+                reason: format!("Failed to create HTTP client: {e}").into(),
+            })?;
+
+        let dimension = OnceLock::new();
+        if let Some(known) = config.dimension {
+            let _ = dimension.set(known);
+        }
+
+        tracing::info!(
+            provider = %config.provider,
+            model = %config.model,
+            base_url = %config.base_url,
+            "HTTP embedder initialized"
+        );
+
+        Ok(Self {
+            config,
+            client,
+            api_key,
+            dimension,
+        })
+    }
+
+    /// Get the configuration
+    #[must_use]
+    pub fn config(&self) -> &HttpEmbedderConfig {
+        &self.config
+    }
+
+    /// Return the embedding dimension, probing the server once if unknown.
+    pub fn detect_dimension(&self) -> Result<usize> {
+        if let Some(dimension) = self.dimension.get() {
+            return Ok(*dimension);
+        }
+        let probe = self.request_embeddings(&["dimension probe"])?;
+        probe
+            .first()
+            .map(Vec::len)
+            .ok_or_else(|| MemvidError::EmbeddingFailed {
+                reason: "No embedding returned".into(),
+            })
+    }
+
+    /// Identity of the vector space produced by this embedder.
+    ///
+    /// Only a dimension declared in the config is recorded, so the identity
+    /// (and every cache key derived from it) stays the same before and after
+    /// the dimension is learned from a response.
+    #[must_use]
+    pub fn identity(&self) -> EmbeddingIdentity {
+        EmbeddingIdentity {
+            provider: Some(self.config.provider.to_ascii_lowercase().into_boxed_str()),
+            model: (!self.config.model.is_empty())
+                .then(|| self.config.model.clone().into_boxed_str()),
+            dimension: self
+                .config
+                .dimension
+                .and_then(|dim| u32::try_from(dim).ok()),
+            normalized: self.config.normalized,
+        }
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "{}{}",
+            self.config.base_url.trim_end_matches('/'),
+            self.config.format.endpoint()
+        )
+    }
+
+    fn request_body(&self, texts: &[&str]) -> serde_json::Value {
+        match self.config.format {
+            EmbeddingApiFormat::OpenAI => serde_json::json!({
+                "model": self.config.model,
+                "input": texts,
+                "encoding_format": "float",
+            }),
+            EmbeddingApiFormat::Ollama => serde_json::json!({
+                "model": self.config.model,
+                "input": texts,
+            }),
+            EmbeddingApiFormat::Tei => serde_json::json!({
+                "inputs": texts,
+                "truncate": true,
+            }),
+        }
+    }
+
+    fn parse_response(&self, body: &str) -> Result<Vec<Vec<f32>>> {
+        let parse_error = |e: serde_json::Error| MemvidError::EmbeddingFailed {
+            reason: format!("Failed to parse {} response: {e}", self.config.provider).into(),
+        };
+        match self.config.format {
+            EmbeddingApiFormat::OpenAI => {
+                let response: CompatEmbeddingResponse =
+                    serde_json::from_str(body).map_err(parse_error)?;
+                let mut data = response.data;
+                if data.iter().all(|d| d.index.is_some()) {
+                    data.sort_by_key(|d| d.index);
+                }
+                Ok(data.into_iter().map(|d| d.embedding).collect())
+            }
+            EmbeddingApiFormat::Ollama => {
+                let response: OllamaEmbeddingResponse =
+                    serde_json::from_str(body).map_err(parse_error)?;
+                Ok(response.embeddings)
+            }
+            EmbeddingApiFormat::Tei => serde_json::from_str(body).map_err(parse_error),
+        }
+    }
+
+    /// Embed one batch, checking the count and dimension of the result.
+    fn request_embeddings(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let body = self.post_with_retry(&self.request_body(texts))?;
+        let embeddings = self.parse_response(&body)?;
+
+        if embeddings.len() != texts.len() {
+            return Err(MemvidError::EmbeddingFailed {
+                reason: format!(
+                    "{} returned {} embeddings for {} inputs",
+                    self.config.provider,
+                    embeddings.len(),
+                    texts.len()
+                )
+                .into(),
+            });
+        }
+        if let Some(first) = embeddings.first() {
+            let expected = *self.dimension.get_or_init(|| first.len());
+            if let Some(bad) = embeddings.iter().find(|e| e.len() != expected) {
+                return Err(MemvidError::EmbeddingFailed {
+                    reason: format!(
+                        "{} returned a {}-dimensional embedding, expected {expected}",
+                        self.config.provider,
+                        bad.len()
+                    )
+                    .into(),
+                });
+            }
+        }
+
+        tracing::debug!(
+            texts = texts.len(),
+            provider = %self.config.provider,
+            "Generated HTTP embeddings"
+        );
+        Ok(embeddings)
+    }
+
+    /// POST `body`, retrying rate limits, server errors and connection
+    /// failures with exponential backoff (honouring `Retry-After` seconds).
+    fn post_with_retry(&self, body: &serde_json::Value) -> Result<String> {
+        let url = self.url();
+        let mut backoff_ms = self.config.initial_backoff_ms;
+        let mut last_error = None;
+
+        for attempt in 0..=self.config.max_retries {
+            if attempt > 0 {
+                tracing::warn!(
+                    attempt = attempt,
+                    backoff_ms = backoff_ms,
+                    provider = %self.config.provider,
+                    "Retrying embedding request"
+                );
+                std::thread::sleep(Duration::from_millis(backoff_ms));
+                backoff_ms = backoff_ms.saturating_mul(2).min(self.config.max_backoff_ms);
+            }
+
+            let mut request = self.client.post(&url).json(body);
+            if let Some(api_key) = &self.api_key {
+                request = request.bearer_auth(api_key);
+            }
+
+            match request.send() {
+                Ok(resp) => {
+                    let status = resp.status();
+                    if status.is_success() {
+                        return resp.text().map_err(|e| MemvidError::EmbeddingFailed {
+                            reason: format!("Failed to read response: {e}").into(),
+                        });
+                    }
+
+                    let retry_after_ms = resp
+                        .headers()
+                        .get(RETRY_AFTER)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| value.trim().parse::<u64>().ok())
+                        .map(|secs| secs.saturating_mul(1000));
+                    let error_text = resp.text().unwrap_or_default();
+                    let error = MemvidError::EmbeddingFailed {
+                        reason: format!(
+                            "{} embedding request failed ({status}): {error_text}",
+                            self.config.provider
+                        )
+                        .into(),
+                    };
+
+                    if status.as_u16() == 429 || status.is_server_error() {
+                        if let Some(wait) = retry_after_ms {
+                            backoff_ms = backoff_ms.max(wait).min(self.config.max_backoff_ms);
+                        }
+                        last_error = Some(error);
+                        continue;
+                    }
+                    return Err(error);
+                }
+                Err(e) => {
+                    let error = MemvidError::EmbeddingFailed {
+                        reason: format!("Request failed: {e}").into(),
+                    };
+                    if e.is_timeout() || e.is_connect() {
+                        last_error = Some(error);
+                        continue;
+                    }
+                    return Err(error);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| MemvidError::EmbeddingFailed {
+            reason: "Max retries exceeded".into(),
+        }))
+    }
+}
+
+impl std::fmt::Debug for HttpEmbedder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpEmbedder")
+            .field("config", &self.config)
+            .field("dimension", &self.dimension.get())
+            .field("api_key", &self.api_key.as_ref().map(|_| "[REDACTED]"))
+            .finish_non_exhaustive()
+    }
+}
+
+impl EmbeddingProvider for HttpEmbedder {
+    fn kind(&self) -> &str {
+        &self.config.provider
+    }
+
+    fn model(&self) -> &str {
+        &self.config.model
+    }
+
+    /// Known dimension, or 0 until the first response (see `init`).
+    fn dimension(&self) -> usize {
+        self.dimension.get().copied().unwrap_or(0)
+    }
+
+    fn embed_text(&self, text: &str) -> Result<Vec<f32>> {
+        self.request_embeddings(&[text])?
+            .into_iter()
+            .next()
+            .ok_or_else(|| MemvidError::EmbeddingFailed {
+                reason: "No embedding returned".into(),
+            })
+    }
+
+    fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let mut all_embeddings = Vec::with_capacity(texts.len());
+        for batch in plan_batches(
+            texts,
+            self.config.max_batch_size,
+            self.config.max_batch_tokens,
+        ) {
+            all_embeddings.extend(self.request_embeddings(&texts[batch])?);
+        }
+        Ok(all_embeddings)
+    }
+
+    fn init(&mut self) -> Result<()> {
+        self.detect_dimension().map(|_| ())
+    }
+}
+
+impl VecEmbedder for HttpEmbedder {
+    fn embed_query(&self, text: &str) -> Result<Vec<f32>> {
+        self.embed_text(text)
+    }
+
+    fn embed_chunks(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        self.embed_batch(texts)
+    }
+
+    /// Known dimension, or 0 until the first response. Never contacts the
+    /// server; call [`detect_dimension`](HttpEmbedder::detect_dimension) to probe.
+    fn embedding_dimension(&self) -> usize {
+        self.dimension.get().copied().unwrap_or(0)
+    }
+
+    fn embedding_identity(&self) -> Option<EmbeddingIdentity> {
+        Some(self.identity())
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -554,6 +1111,164 @@ mod tests {
         assert!(result.is_err());
     }
 
+    /// Minimal HTTP server answering each connection with the next canned
+    /// `(status, body)` and recording `(path, body)` of every request.
+    fn mock_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (
+        String,
+        std::sync::Arc<std::sync::Mutex<Vec<(String, String)>>>,
+    ) {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let addr = listener.local_addr().expect("local addr");
+        let requests = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = std::sync::Arc::clone(&requests);
+
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let Ok((stream, _)) = listener.accept() else {
+                    return;
+                };
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).expect("request line");
+                let path = request_line
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap_or_default()
+                    .to_string();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).expect("header line");
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap_or(0);
+                        }
+                    }
+                }
+                let mut request_body = vec![0u8; content_length];
+                reader.read_exact(&mut request_body).expect("request body");
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push((path, String::from_utf8_lossy(&request_body).into_owned()));
+
+                let mut stream = reader.into_inner();
+                let response = format!(
+                    "HTTP/1.1 {status} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream
+                    .write_all(response.as_bytes())
+                    .expect("write response");
+            }
+        });
+
+        (format!("http://{addr}"), requests)
+    }
+
+    #[test]
+    fn test_plan_batches_respects_token_budget() {
+        let long = "x".repeat(40); // ~10 tokens
+        let texts = vec!["ab", "cd", long.as_str(), "ef", "gh", "ij"];
+        assert_eq!(plan_batches(&texts, 10, 4), vec![0..2, 2..3, 3..6]);
+        assert_eq!(plan_batches(&texts, 2, 1000), vec![0..2, 2..4, 4..6]);
+        assert!(plan_batches(&[], 4, 4).is_empty());
+    }
+
+    #[test]
+    fn test_openai_compatible_detects_dimension() {
+        let (base_url, requests) = mock_server(vec![(
+            200,
+            r#"{"data":[{"embedding":[0.5,0.5,0.0],"index":0}],"model":"m"}"#,
+        )]);
+        let mut embedder =
+            HttpEmbedder::new(HttpEmbedderConfig::openai_compatible(base_url, "nomic")).unwrap();
+        assert_eq!(embedder.dimension(), 0);
+        let before = embedder.identity();
+        embedder.init().unwrap();
+        assert_eq!(embedder.dimension(), 3);
+
+        let identity = embedder.identity();
+        assert_eq!(
+            identity, before,
+            "learning the dimension keeps the identity"
+        );
+        assert_eq!(identity.provider.as_deref(), Some("openai-compatible"));
+        assert_eq!(identity.model.as_deref(), Some("nomic"));
+        assert_eq!(identity.dimension, None);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].0, "/embeddings");
+        assert!(requests[0].1.contains(r#""model":"nomic""#));
+    }
+
+    #[test]
+    fn test_ollama_and_tei_request_shapes() {
+        let (base_url, requests) = mock_server(vec![(
+            200,
+            r#"{"model":"m","embeddings":[[1.0,0.0],[0.0,1.0]]}"#,
+        )]);
+        let embedder =
+            HttpEmbedder::new(HttpEmbedderConfig::ollama("all-minilm").with_base_url(base_url))
+                .unwrap();
+        let embeddings = embedder.embed_batch(&["a", "b"]).unwrap();
+        assert_eq!(embeddings, vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+        {
+            let requests = requests.lock().unwrap();
+            assert_eq!(requests[0].0, "/api/embed");
+            assert!(requests[0].1.contains(r#""input":["a","b"]"#));
+        }
+
+        let (base_url, requests) = mock_server(vec![(200, "[[0.25,0.75]]")]);
+        let embedder = HttpEmbedder::new(HttpEmbedderConfig::tei(base_url, "bge")).unwrap();
+        assert_eq!(embedder.embed_text("hello").unwrap(), vec![0.25, 0.75]);
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].0, "/embed");
+        assert!(requests[0].1.contains(r#""inputs":["hello"]"#));
+    }
+
+    #[test]
+    fn test_http_embedder_retries_server_errors() {
+        let (base_url, requests) = mock_server(vec![
+            (503, r#"{"error":"loading model"}"#),
+            (429, r#"{"error":"busy"}"#),
+            (200, "[[1.0]]"),
+        ]);
+        let config = HttpEmbedderConfig::tei(base_url, "bge").with_retries(3, 1);
+        let embedder = HttpEmbedder::new(config).unwrap();
+        assert_eq!(embedder.embed_text("retry").unwrap(), vec![1.0]);
+        assert_eq!(requests.lock().unwrap().len(), 3);
+
+        let (base_url, _) = mock_server(vec![(400, r#"{"error":"bad input"}"#)]);
+        let embedder = HttpEmbedder::new(HttpEmbedderConfig::tei(base_url, "bge")).unwrap();
+        let err = embedder.embed_text("bad").unwrap_err();
+        assert!(format!("{err}").contains("400"));
+    }
+
+    #[test]
+    fn test_http_embedder_batches_requests() {
+        let (base_url, requests) = mock_server(vec![(200, "[[1.0],[2.0]]"), (200, "[[3.0]]")]);
+        let config = HttpEmbedderConfig::tei(base_url, "bge")
+            .with_batch_limits(2, 1000)
+            .with_dimension(1);
+        let embedder = HttpEmbedder::new(config).unwrap();
+        let embeddings = embedder.embed_chunks(&["a", "b", "c"]).unwrap();
+        assert_eq!(embeddings, vec![vec![1.0], vec![2.0], vec![3.0]]);
+        assert_eq!(requests.lock().unwrap().len(), 2);
+        assert_eq!(
+            embedder.embedding_identity().and_then(|id| id.dimension),
+            Some(1)
+        );
+    }
+
     /// Integration test - requires OPENAI_API_KEY to be set
     /// Run with: cargo test --features api_embed test_openai_integration -- --ignored
     #[test]
@@ -15,15 +15,15 @@
 //! - **Graceful Degradation**: Works without CLIP, just loses visual search capability.
 
 use blake3::hash;
-#[cfg(feature = "clip")]
+#[cfg(any(feature = "clip", feature = "ocr"))]
 use image::DynamicImage;
-#[cfg(all(feature = "clip", not(feature = "pdfium")))]
+#[cfg(all(any(feature = "clip", feature = "ocr"), not(feature = "pdfium")))]
 use image::{ImageBuffer, Luma, Rgb};
-#[cfg(all(feature = "clip", not(feature = "pdfium")))]
+#[cfg(all(any(feature = "clip", feature = "ocr"), not(feature = "pdfium")))]
 use lopdf::{Dictionary, Document, Object, ObjectId};
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
-#[cfg(all(feature = "clip", not(feature = "pdfium")))]
+#[cfg(all(any(feature = "clip", feature = "ocr"), not(feature = "pdfium")))]
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -1254,8 +1254,8 @@ mod model {
 #[cfg(feature = "clip")]
 pub use model::*;
 
-#[cfg(all(feature = "clip", feature = "pdfium"))]
-use pdfium_render::prelude::{PdfPageRenderRotation, PdfRenderConfig, Pdfium};
+#[cfg(all(any(feature = "clip", feature = "ocr"), feature = "pdfium"))]
+use pdfium_render::prelude::{PdfDocument, PdfPageRenderRotation, PdfRenderConfig, Pdfium};
 
 /// Render PDF pages to images suitable for CLIP embedding (feature-gated).
 #[cfg(all(feature = "clip", feature = "pdfium"))]
@@ -1264,17 +1264,47 @@ pub fn render_pdf_pages_for_clip(
     max_pages: usize,
     target_px: u32,
 ) -> Result<Vec<(u32, DynamicImage)>> {
-    let bindings = Pdfium::bind_to_system_library().map_err(|e| ClipError::InferenceError {
-        cause: format!("Failed to bind pdfium: {}", e),
-    })?;
-    let pdfium = Pdfium::new(bindings);
+    let pdfium = bind_pdfium()?;
     let document =
         pdfium
             .load_pdf_from_file(path, None)
             .map_err(|e| ClipError::InferenceError {
                 cause: format!("Failed to load PDF for CLIP rendering: {}", e),
             })?;
+    render_pdfium_pages(&document, max_pages, target_px)
+}
+
+/// Render the pages of an in-memory PDF, numbered from 1 (feature-gated).
+#[cfg(all(any(feature = "clip", feature = "ocr"), feature = "pdfium"))]
+pub fn render_pdf_pages_from_bytes(
+    bytes: &[u8],
+    max_pages: usize,
+    target_px: u32,
+) -> Result<Vec<(u32, DynamicImage)>> {
+    let pdfium = bind_pdfium()?;
+    let document =
+        pdfium
+            .load_pdf_from_byte_slice(bytes, None)
+            .map_err(|e| ClipError::InferenceError {
+                cause: format!("Failed to load PDF for rendering: {}", e),
+            })?;
+    render_pdfium_pages(&document, max_pages, target_px)
+}
+
+#[cfg(all(any(feature = "clip", feature = "ocr"), feature = "pdfium"))]
+fn bind_pdfium() -> Result<Pdfium> {
+    let bindings = Pdfium::bind_to_system_library().map_err(|e| ClipError::InferenceError {
+        cause: format!("Failed to bind pdfium: {}", e),
+    })?;
+    Ok(Pdfium::new(bindings))
+}
 
+#[cfg(all(any(feature = "clip", feature = "ocr"), feature = "pdfium"))]
+fn render_pdfium_pages(
+    document: &PdfDocument<'_>,
+    max_pages: usize,
+    target_px: u32,
+) -> Result<Vec<(u32, DynamicImage)>> {
     let render_config = PdfRenderConfig::new()
         .set_target_width(target_px as i32)
         .set_maximum_height(target_px as i32)
@@ -1304,6 +1334,27 @@ pub fn render_pdf_pages_for_clip(
     max_pages: usize,
     _target_px: u32,
 ) -> Result<Vec<(u32, DynamicImage)>> {
+    let doc = Document::load(path).map_err(|e| ClipError::InferenceError {
+        cause: format!("Failed to load PDF for image extraction: {}", e),
+    })?;
+    extract_pdf_page_images(&doc, max_pages)
+}
+
+/// Without pdfium, pages are not rasterized; their embedded images stand in.
+#[cfg(all(any(feature = "clip", feature = "ocr"), not(feature = "pdfium")))]
+pub fn render_pdf_pages_from_bytes(
+    bytes: &[u8],
+    max_pages: usize,
+    _target_px: u32,
+) -> Result<Vec<(u32, DynamicImage)>> {
+    let doc = Document::load_mem(bytes).map_err(|e| ClipError::InferenceError {
+        cause: format!("Failed to load PDF for image extraction: {}", e),
+    })?;
+    extract_pdf_page_images(&doc, max_pages)
+}
+
+#[cfg(all(any(feature = "clip", feature = "ocr"), not(feature = "pdfium")))]
+fn extract_pdf_page_images(doc: &Document, max_pages: usize) -> Result<Vec<(u32, DynamicImage)>> {
     fn extract_images_from_page(
         doc: &Document,
         page_id: ObjectId,
@@ -1454,10 +1505,6 @@ pub fn render_pdf_pages_for_clip(
         Ok(())
     }
 
-    let doc = Document::load(path).map_err(|e| ClipError::InferenceError {
-        cause: format!("Failed to load PDF for image extraction: {}", e),
-    })?;
-
     let mut remaining = max_pages;
     let mut pages: Vec<(u32, DynamicImage)> = Vec::new();
 
@@ -1466,7 +1513,7 @@ pub fn render_pdf_pages_for_clip(
             break;
         }
         let start_len = pages.len();
-        extract_images_from_page(&doc, page_id, &mut remaining, &mut pages)?;
+        extract_images_from_page(doc, page_id, &mut remaining, &mut pages)?;
         if pages.len() > start_len {
             for entry in pages.iter_mut().skip(start_len) {
                 entry.0 = page_num as u32;
@@ -11,7 +11,7 @@ use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
 use crate::error::Result;
-use crate::types::{EnrichmentTask, FrameId, VecEmbedder};
+use crate::types::{EmbeddingIdentity, EnrichmentTask, FrameId, VecEmbedder};
 
 /// Configuration for the enrichment worker.
 #[derive(Debug, Clone)]
@@ -247,6 +247,24 @@ impl<E: VecEmbedder> EmbeddingBatcher<E> {
         Ok(count)
     }
 
+    /// Like [`flush`](Self::flush), serving repeated texts from `memvid`'s
+    /// embedding cache and caching newly computed vectors.
+    pub(crate) fn flush_cached(&mut self, memvid: &mut crate::Memvid) -> Result<usize> {
+        if self.pending_texts.is_empty() {
+            return Ok(0);
+        }
+
+        let pending: Vec<_> = std::mem::take(&mut self.pending_texts);
+        let count = pending.len();
+        let texts: Vec<&str> = pending.iter().map(|(_, text)| text.as_str()).collect();
+        let embeddings = memvid.embed_texts_cached(&self.embedder, &texts)?;
+        for ((frame_id, _), embedding) in pending.into_iter().zip(embeddings) {
+            self.ready_embeddings.push((frame_id, embedding));
+        }
+
+        Ok(count)
+    }
+
     /// Take all ready embeddings.
     pub fn take_embeddings(&mut self) -> Vec<(FrameId, Vec<f32>)> {
         std::mem::take(&mut self.ready_embeddings)
@@ -256,6 +274,11 @@ impl<E: VecEmbedder> EmbeddingBatcher<E> {
     pub fn dimension(&self) -> usize {
         self.embedder.embedding_dimension()
     }
+
+    /// Get the embedding identity reported by the embedder.
+    pub fn embedding_identity(&self) -> Option<EmbeddingIdentity> {
+        self.embedder.embedding_identity()
+    }
 }
 
 /// Enrichment task processor (stateless, operates on Memvid instance).
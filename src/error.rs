@@ -91,6 +91,9 @@ pub enum MemvidError {
     #[error("Logic-Mesh is invalid: {reason}")]
     InvalidLogicMesh { reason: Cow<'static, str> },
 
+    #[error("Embedding cache is invalid: {reason}")]
+    InvalidEmbeddingCache { reason: Cow<'static, str> },
+
     #[error("Logic-Mesh is not enabled")]
     LogicMeshNotEnabled,
 
@@ -219,6 +222,23 @@ pub enum MemvidError {
 
     #[error("Schema validation failed: {reason}")]
     SchemaValidation { reason: String },
+
+    #[error("Directory sync failed: {reason}")]
+    Sync { reason: String },
+
+    #[error("Ingest stage '{stage}' rejected the document: {reason}")]
+    IngestRejected { stage: String, reason: String },
+
+    #[error(
+        "JSON Lines payloads hold one record per line; use Memvid::put_records to ingest one frame per record"
+    )]
+    RecordPayload,
+
+    #[error("PII policy is invalid: {reason}")]
+    InvalidPiiPolicy { reason: String },
+
+    #[error("Vector index has no embeddings to build from")]
+    EmptyVecIndex,
 }
 
 impl From<std::io::Error> for MemvidError {
@@ -377,13 +377,16 @@ pub fn extract_text_budgeted(
     })
 }
 
-/// Check if MIME type is an Office Open XML format (xlsx, docx, pptx).
+/// Check if MIME type is an Office Open XML format (xlsx, docx, pptx), or an
+/// OpenDocument or EPUB package that goes through the same readers.
 fn is_ooxml_mime(mime: Option<&str>) -> bool {
     let Some(m) = mime else { return false };
     let m = m.to_lowercase();
     m.contains("spreadsheetml")
         || m.contains("wordprocessingml")
         || m.contains("presentationml")
+        || m.starts_with("application/vnd.oasis.opendocument.")
+        || m == "application/epub+zip"
         || m == "application/vnd.ms-excel"
         || m == "application/msword"
         || m == "application/vnd.ms-powerpoint"
@@ -405,6 +408,10 @@ fn extract_ooxml_budgeted(
         Some(m) if m.contains("wordprocessingml") => Some(DocumentFormat::Docx),
         Some(m) if m.contains("presentationml") => Some(DocumentFormat::Pptx),
         Some("application/vnd.ms-excel") => Some(DocumentFormat::Xls),
+        Some("application/vnd.oasis.opendocument.text") => Some(DocumentFormat::Odt),
+        Some("application/vnd.oasis.opendocument.spreadsheet") => Some(DocumentFormat::Ods),
+        Some("application/vnd.oasis.opendocument.presentation") => Some(DocumentFormat::Odp),
+        Some("application/epub+zip") => Some(DocumentFormat::Epub),
         _ => {
             // Fall back to extension-based detection
             uri.and_then(|u| {
@@ -417,6 +424,14 @@ fn extract_ooxml_budgeted(
                     Some(DocumentFormat::Pptx)
                 } else if lower.ends_with(".xls") {
                     Some(DocumentFormat::Xls)
+                } else if lower.ends_with(".odt") {
+                    Some(DocumentFormat::Odt)
+                } else if lower.ends_with(".ods") {
+                    Some(DocumentFormat::Ods)
+                } else if lower.ends_with(".odp") {
+                    Some(DocumentFormat::Odp)
+                } else if lower.ends_with(".epub") {
+                    Some(DocumentFormat::Epub)
                 } else {
                     None
                 }
@@ -501,6 +516,10 @@ fn is_ooxml_by_extension(uri: Option<&str>) -> bool {
         || lower.ends_with(".doc")
         || lower.ends_with(".xls")
         || lower.ends_with(".ppt")
+        || lower.ends_with(".odt")
+        || lower.ends_with(".ods")
+        || lower.ends_with(".odp")
+        || lower.ends_with(".epub")
 }
 
 /// Check if bytes start with ZIP magic and extension indicates OOXML
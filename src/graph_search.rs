@@ -329,6 +329,11 @@ pub fn hybrid_search(memvid: &mut Memvid, plan: &QueryPlan) -> Result<Vec<Hybrid
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                diversify: None,
+                highlight: None,
+                facets: Vec::new(),
+                sort: crate::types::SearchSort::Relevance,
+                explain: false,
             };
             let response = memvid.search(request)?;
             Ok(response
@@ -395,6 +400,11 @@ pub fn hybrid_search(memvid: &mut Memvid, plan: &QueryPlan) -> Result<Vec<Hybrid
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    diversify: None,
+                    highlight: None,
+                    facets: Vec::new(),
+                    sort: crate::types::SearchSort::Relevance,
+                    explain: false,
                 };
                 let response = memvid.search(request)?;
                 return Ok(response
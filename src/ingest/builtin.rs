@@ -0,0 +1,68 @@
+//! Stages shipped with the crate.
+
+use crate::analysis::auto_tag::AutoTagger;
+use crate::error::Result;
+use crate::extract::ExtractedDocument;
+use crate::memvid::mutation::merge_unique;
+use crate::pii::{contains_pii, mask_pii};
+
+use super::stage::{BytesOutcome, IngestContext, IngestFrame, IngestStage, StageOutcome};
+
+/// Masks PII with [`mask_pii`] before it is stored: in text payloads, and in
+/// the extracted text of binary ones (whose stored bytes are left as they are).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PiiMaskStage;
+
+impl IngestStage for PiiMaskStage {
+    fn name(&self) -> &'static str {
+        "pii_mask"
+    }
+
+    fn on_bytes(&self, _ctx: &IngestContext, payload: &mut Vec<u8>) -> Result<BytesOutcome> {
+        if let Ok(text) = std::str::from_utf8(payload) {
+            if contains_pii(text) {
+                *payload = mask_pii(text).into_bytes();
+            }
+        }
+        Ok(BytesOutcome::Continue)
+    }
+
+    fn on_document(
+        &self,
+        _ctx: &IngestContext,
+        document: &mut ExtractedDocument,
+    ) -> Result<StageOutcome> {
+        if let Some(text) = document.text.as_mut() {
+            if contains_pii(text) {
+                *text = mask_pii(text);
+            }
+        }
+        Ok(StageOutcome::Continue)
+    }
+}
+
+/// Adds keyword tags, labels and, optionally, content dates from the frame's
+/// search text, as `PutOptions::auto_tag` does.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AutoTagStage {
+    pub extract_dates: bool,
+}
+
+impl IngestStage for AutoTagStage {
+    fn name(&self) -> &'static str {
+        "auto_tag"
+    }
+
+    fn on_frame(&self, _ctx: &IngestContext, frame: &mut IngestFrame) -> Result<StageOutcome> {
+        let Some(text) = frame.search_text.as_deref() else {
+            return Ok(StageOutcome::Continue);
+        };
+        let result = AutoTagger.analyse(text, self.extract_dates);
+        merge_unique(&mut frame.tags, result.tags);
+        merge_unique(&mut frame.labels, result.labels);
+        if self.extract_dates && frame.content_dates.is_empty() {
+            frame.content_dates = result.content_dates;
+        }
+        Ok(StageOutcome::Continue)
+    }
+}
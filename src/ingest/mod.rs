@@ -0,0 +1,16 @@
+//! Ingestion pipeline hooks for transforming documents before they are written.
+//!
+//! An [`IngestPipeline`] holds user-registered [`IngestStage`]s. Every `put`
+//! on a memory with a pipeline hands the document to each stage in turn at
+//! four points: the raw payload, the extracted document, the chunk plan and
+//! the final frame metadata. Stages can edit what they are given, reject the
+//! document, or (on the payload) split it into several frames.
+
+pub mod builtin;
+pub mod stage;
+
+pub use builtin::{AutoTagStage, PiiMaskStage};
+pub use stage::{
+    BytesOutcome, INGEST_SPLIT_METADATA_KEY, IngestChunk, IngestContext, IngestFrame, IngestPart,
+    IngestPipeline, IngestStage, StageOutcome,
+};
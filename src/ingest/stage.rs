@@ -0,0 +1,374 @@
+//! Ingest stage trait, the values stages work on, and the pipeline that runs them.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::error::{MemvidError, Result};
+use crate::extract::ExtractedDocument;
+use crate::types::PutOptions;
+
+/// What the document being ingested is, as far as the caller said.
+#[derive(Debug, Clone, Default)]
+pub struct IngestContext {
+    /// URI the frame will be stored under.
+    pub uri: Option<String>,
+    /// Title the frame will be stored with.
+    pub title: Option<String>,
+    /// MIME type hint from the put options, or the one extraction detected.
+    pub mime: Option<String>,
+}
+
+impl IngestContext {
+    pub(crate) fn from_options(options: &PutOptions) -> Self {
+        Self {
+            uri: options.uri.clone(),
+            title: options.title.clone(),
+            mime: options
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.mime.clone()),
+        }
+    }
+}
+
+/// Extra metadata key shared by the frames of one split payload; replacing the
+/// payload of any of them tombstones the rest.
+pub const INGEST_SPLIT_METADATA_KEY: &str = "memvid.ingest.split";
+
+/// One frame's worth of a split payload.
+#[derive(Debug, Clone, Default)]
+pub struct IngestPart {
+    pub payload: Vec<u8>,
+    /// URI of the part's frame; the document URI with `#<n>` appended when unset.
+    pub uri: Option<String>,
+    /// Title of the part's frame; the document title when unset.
+    pub title: Option<String>,
+    /// Added to the document's `extra_metadata` for this part.
+    pub extra_metadata: BTreeMap<String, String>,
+}
+
+impl IngestPart {
+    /// A part with only a payload.
+    #[must_use]
+    pub fn new(payload: impl Into<Vec<u8>>) -> Self {
+        Self {
+            payload: payload.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Fill what `self` leaves unset from the part it was split from.
+    fn inherit(mut self, parent: &Self) -> Self {
+        if self.uri.is_none() {
+            self.uri.clone_from(&parent.uri);
+        }
+        if self.title.is_none() {
+            self.title.clone_from(&parent.title);
+        }
+        for (key, value) in &parent.extra_metadata {
+            self.extra_metadata
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        self
+    }
+}
+
+/// One chunk of a chunked document.
+#[derive(Debug, Clone, Default)]
+pub struct IngestChunk {
+    /// Position of the chunk in the document.
+    pub index: usize,
+    pub text: String,
+    /// The chunk frame's own `extra_metadata`, on top of the document's.
+    pub extra_metadata: BTreeMap<String, String>,
+}
+
+/// Metadata of the frame about to be written.
+#[derive(Debug, Clone, Default)]
+pub struct IngestFrame {
+    pub uri: Option<String>,
+    pub title: Option<String>,
+    pub kind: Option<String>,
+    pub track: Option<String>,
+    /// Text indexed for the frame.
+    pub search_text: Option<String>,
+    pub tags: Vec<String>,
+    pub labels: Vec<String>,
+    pub extra_metadata: BTreeMap<String, String>,
+    pub content_dates: Vec<String>,
+}
+
+/// What a stage decided about the document it was handed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StageOutcome {
+    /// Go on with the (possibly edited) document.
+    Continue,
+    /// Drop the document; the put fails with [`MemvidError::IngestRejected`].
+    Reject { reason: String },
+}
+
+/// What a stage decided about a raw payload.
+#[derive(Debug, Clone)]
+pub enum BytesOutcome {
+    /// Go on with the (possibly edited) payload.
+    Continue,
+    /// Drop the document; the put fails with [`MemvidError::IngestRejected`].
+    Reject { reason: String },
+    /// Ingest each part as its own frame instead. Later stages see every part.
+    Split(Vec<IngestPart>),
+}
+
+/// A transform run on every document put into a memory.
+///
+/// Every hook defaults to passing its input through untouched, so a stage
+/// only implements the points it cares about.
+///
+/// A put's [`PiiPolicy`](crate::types::PiiPolicy) redacts the payload after
+/// `on_bytes` and extracted text after `on_document`, so those hooks can see
+/// raw values; `on_chunks` and `on_frame` see redacted text.
+///
+/// # Example
+///
+/// ```
+/// use memvid_core::ingest::{IngestContext, IngestFrame, IngestStage, StageOutcome};
+/// use memvid_core::Result;
+///
+/// struct Department(&'static str);
+///
+/// impl IngestStage for Department {
+///     fn name(&self) -> &str {
+///         "department"
+///     }
+///
+///     fn on_frame(&self, _ctx: &IngestContext, frame: &mut IngestFrame) -> Result<StageOutcome> {
+///         frame
+///             .extra_metadata
+///             .insert("department".to_string(), self.0.to_string());
+///         Ok(StageOutcome::Continue)
+///     }
+/// }
+/// ```
+pub trait IngestStage: Send + Sync {
+    /// Name reported when the stage rejects a document.
+    fn name(&self) -> &str;
+
+    /// Edit, reject or split the raw payload before anything reads it.
+    /// Payloads of frames updated without new bytes do not pass through here.
+    fn on_bytes(&self, _ctx: &IngestContext, _payload: &mut Vec<u8>) -> Result<BytesOutcome> {
+        Ok(BytesOutcome::Continue)
+    }
+
+    /// Edit or reject the extracted text and metadata. The stored payload is
+    /// left as it is; edited text is what gets chunked and indexed.
+    fn on_document(
+        &self,
+        _ctx: &IngestContext,
+        _document: &mut ExtractedDocument,
+    ) -> Result<StageOutcome> {
+        Ok(StageOutcome::Continue)
+    }
+
+    /// Edit or reject the chunks of a chunked document. Edits should not move
+    /// text between chunks; ones that change a chunk's length lay the chunk
+    /// ranges out again over the edited text.
+    fn on_chunks(&self, _ctx: &IngestContext, _chunks: &mut [IngestChunk]) -> Result<StageOutcome> {
+        Ok(StageOutcome::Continue)
+    }
+
+    /// Edit or reject the frame's metadata just before it is indexed and written.
+    fn on_frame(&self, _ctx: &IngestContext, _frame: &mut IngestFrame) -> Result<StageOutcome> {
+        Ok(StageOutcome::Continue)
+    }
+}
+
+/// Stages run, in registration order, on every put.
+#[derive(Clone, Default)]
+pub struct IngestPipeline {
+    stages: Vec<Arc<dyn IngestStage>>,
+}
+
+impl std::fmt::Debug for IngestPipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IngestPipeline")
+            .field("stages", &self.stage_names())
+            .finish()
+    }
+}
+
+impl IngestPipeline {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a stage after the ones already registered.
+    #[must_use]
+    pub fn with_stage(mut self, stage: impl IngestStage + 'static) -> Self {
+        self.push(stage);
+        self
+    }
+
+    /// Add a stage after the ones already registered.
+    pub fn push(&mut self, stage: impl IngestStage + 'static) {
+        self.stages.push(Arc::new(stage));
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Names of the registered stages, in order.
+    #[must_use]
+    pub fn stage_names(&self) -> Vec<&str> {
+        self.stages.iter().map(|stage| stage.name()).collect()
+    }
+
+    /// Run the payload stages, returning the parts to store as frames: just
+    /// the payload unless a stage split it.
+    pub(crate) fn run_bytes(
+        &self,
+        ctx: &IngestContext,
+        payload: Vec<u8>,
+    ) -> Result<Vec<IngestPart>> {
+        let mut parts = Vec::new();
+        // Parts still to run, with the stage they resume at
+        let mut pending = vec![(0, IngestPart::new(payload))];
+        while let Some((first, mut part)) = pending.pop() {
+            let mut split = None;
+            for (index, stage) in self.stages.iter().enumerate().skip(first) {
+                let part_ctx = IngestContext {
+                    uri: part.uri.clone().or_else(|| ctx.uri.clone()),
+                    title: part.title.clone().or_else(|| ctx.title.clone()),
+                    mime: ctx.mime.clone(),
+                };
+                match stage.on_bytes(&part_ctx, &mut part.payload)? {
+                    BytesOutcome::Continue => {}
+                    BytesOutcome::Reject { reason } => {
+                        return Err(rejected(stage.as_ref(), reason));
+                    }
+                    BytesOutcome::Split(children) if children.is_empty() => {
+                        return Err(rejected(stage.as_ref(), "split into no parts".to_string()));
+                    }
+                    BytesOutcome::Split(children) => {
+                        split = Some((index + 1, children));
+                        break;
+                    }
+                }
+            }
+            match split {
+                Some((next, children)) => {
+                    // Reversed so parts come off the stack in order
+                    for child in children.into_iter().rev() {
+                        pending.push((next, child.inherit(&part)));
+                    }
+                }
+                None => parts.push(part),
+            }
+        }
+        Ok(parts)
+    }
+
+    pub(crate) fn run_document(
+        &self,
+        ctx: &IngestContext,
+        document: &mut ExtractedDocument,
+    ) -> Result<()> {
+        for stage in &self.stages {
+            check(stage.as_ref(), stage.on_document(ctx, document)?)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn run_chunks(&self, ctx: &IngestContext, chunks: &mut [IngestChunk]) -> Result<()> {
+        for stage in &self.stages {
+            check(stage.as_ref(), stage.on_chunks(ctx, chunks)?)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn run_frame(&self, ctx: &IngestContext, frame: &mut IngestFrame) -> Result<()> {
+        for stage in &self.stages {
+            check(stage.as_ref(), stage.on_frame(ctx, frame)?)?;
+        }
+        Ok(())
+    }
+}
+
+fn check(stage: &dyn IngestStage, outcome: StageOutcome) -> Result<()> {
+    match outcome {
+        StageOutcome::Continue => Ok(()),
+        StageOutcome::Reject { reason } => Err(rejected(stage, reason)),
+    }
+}
+
+fn rejected(stage: &dyn IngestStage, reason: String) -> MemvidError {
+    MemvidError::IngestRejected {
+        stage: stage.name().to_string(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Splitter;
+
+    impl IngestStage for Splitter {
+        fn name(&self) -> &'static str {
+            "splitter"
+        }
+
+        fn on_bytes(&self, _ctx: &IngestContext, payload: &mut Vec<u8>) -> Result<BytesOutcome> {
+            let text = String::from_utf8_lossy(payload).into_owned();
+            if !text.contains("---") {
+                return Ok(BytesOutcome::Continue);
+            }
+            Ok(BytesOutcome::Split(
+                text.split("---")
+                    .map(|part| IngestPart::new(part.trim()))
+                    .collect(),
+            ))
+        }
+    }
+
+    struct Shout;
+
+    impl IngestStage for Shout {
+        fn name(&self) -> &'static str {
+            "shout"
+        }
+
+        fn on_bytes(&self, _ctx: &IngestContext, payload: &mut Vec<u8>) -> Result<BytesOutcome> {
+            if payload.is_empty() {
+                return Ok(BytesOutcome::Reject {
+                    reason: "empty".to_string(),
+                });
+            }
+            payload.make_ascii_uppercase();
+            Ok(BytesOutcome::Continue)
+        }
+    }
+
+    #[test]
+    fn split_parts_run_through_later_stages_in_order() {
+        let pipeline = IngestPipeline::new().with_stage(Splitter).with_stage(Shout);
+        let parts = pipeline
+            .run_bytes(&IngestContext::default(), b"one --- two --- three".to_vec())
+            .unwrap();
+        let payloads: Vec<&[u8]> = parts.iter().map(|part| part.payload.as_slice()).collect();
+        assert_eq!(payloads, [&b"ONE"[..], b"TWO", b"THREE"]);
+
+        let err = pipeline
+            .run_bytes(&IngestContext::default(), b"one --- ".to_vec())
+            .unwrap_err();
+        assert!(matches!(err, MemvidError::IngestRejected { stage, .. } if stage == "shout"));
+    }
+}
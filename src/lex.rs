@@ -188,12 +188,31 @@ impl LexIndex {
         uri_filter: Option<&str>,
         scope_filter: Option<&str>,
     ) -> Vec<LexMatch> {
-        if query_tokens.is_empty() {
+        let groups: Vec<Vec<String>> = query_tokens
+            .iter()
+            .map(|token| vec![token.clone()])
+            .collect();
+        self.compute_group_matches(&groups, uri_filter, scope_filter)
+    }
+
+    /// Sections containing at least one term of every group. Each group is
+    /// a query token followed by its alternatives (e.g. synonyms).
+    pub(crate) fn compute_group_matches(
+        &self,
+        groups: &[Vec<String>],
+        uri_filter: Option<&str>,
+        scope_filter: Option<&str>,
+    ) -> Vec<LexMatch> {
+        if groups.is_empty() {
             return Vec::new();
         }
 
         let mut hits = Vec::new();
-        let phrase = query_tokens.join(" ");
+        let phrase = groups
+            .iter()
+            .filter_map(|group| group.first().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ");
         for document in &self.documents {
             if let Some(uri) = uri_filter {
                 if !uri_matches(document.uri.as_deref(), uri) {
@@ -217,45 +236,26 @@ impl LexIndex {
                 }
 
                 let mut occurrences: Vec<(usize, usize)> = Vec::new();
-
-                if query_tokens.len() == 1 {
-                    let needle = &query_tokens[0];
-                    if needle.is_empty() {
-                        continue;
-                    }
-                    let mut start = 0usize;
-                    while let Some(idx) = haystack[start..].find(needle) {
-                        let local_start = start + idx;
-                        let local_end = local_start + needle.len();
-                        occurrences.push((local_start, local_end));
-                        start = local_end;
-                    }
-                } else {
-                    let mut all_occurrences = Vec::new();
-                    let mut all_present = true;
-                    for needle in query_tokens {
-                        if needle.is_empty() {
-                            all_present = false;
-                            break;
-                        }
+                let mut all_present = true;
+                for group in groups {
+                    let mut found_for_group = false;
+                    for needle in group.iter().filter(|needle| !needle.is_empty()) {
                         let mut start = 0usize;
-                        let mut found_for_token = false;
-                        while let Some(idx) = haystack[start..].find(needle) {
-                            found_for_token = true;
+                        while let Some(idx) = haystack[start..].find(needle.as_str()) {
+                            found_for_group = true;
                             let local_start = start + idx;
                             let local_end = local_start + needle.len();
-                            all_occurrences.push((local_start, local_end));
+                            occurrences.push((local_start, local_end));
                             start = local_end;
                         }
-                        if !found_for_token {
-                            all_present = false;
-                            break;
-                        }
                     }
-                    if !all_present {
-                        continue;
+                    if !found_for_group {
+                        all_present = false;
+                        break;
                     }
-                    occurrences = all_occurrences;
+                }
+                if !all_present {
+                    continue;
                 }
 
                 if occurrences.is_empty() {
@@ -696,6 +696,47 @@ mod tests {
         assert!(!hits[0].snippets.is_empty());
     }
 
+    #[test]
+    fn group_matches_accept_any_alternative() {
+        let mut builder = LexIndexBuilder::new();
+        builder.add_document(
+            0,
+            "mv2://ops/cluster",
+            None,
+            "kubernetes cluster upgraded",
+            &HashMap::new(),
+        );
+        builder.add_document(
+            1,
+            "mv2://ops/k8s",
+            None,
+            "k8s nodes drained",
+            &HashMap::new(),
+        );
+        builder.add_document(
+            2,
+            "mv2://ops/other",
+            None,
+            "cluster upgraded",
+            &HashMap::new(),
+        );
+        let artifact = builder.finish().expect("finish");
+        let index = LexIndex::decode(&artifact.bytes).expect("decode");
+
+        let groups = [
+            vec!["k8s".to_string(), "kubernetes".to_string()],
+            vec!["upgraded".to_string()],
+        ];
+        let matches = index.compute_group_matches(&groups, None, None);
+        let frames: Vec<FrameId> = matches.iter().map(|m| m.frame_id).collect();
+        assert_eq!(frames, [0]);
+
+        let matches = index.compute_group_matches(&groups[..1], None, None);
+        let mut frames: Vec<FrameId> = matches.iter().map(|m| m.frame_id).collect();
+        frames.sort_unstable();
+        assert_eq!(frames, [0, 1]);
+    }
+
     #[test]
     fn tokenizer_lowercases_and_filters() {
         let tokens = tokenize("Hello, Rust-lang!");
@@ -82,6 +82,7 @@ pub mod error;
 pub mod extract;
 pub mod extract_budgeted;
 pub mod footer;
+pub mod ingest;
 pub mod io;
 pub mod lex;
 mod lock;
@@ -99,7 +100,9 @@ pub mod text;
 mod toc;
 pub mod types;
 pub mod vec;
+pub mod vec_ivf;
 pub mod vec_pq;
+pub mod vec_sq;
 
 // SIMD-accelerated distance calculations
 pub mod simd;
@@ -121,6 +124,10 @@ pub mod clip;
 // Model inference requires the "whisper" feature
 pub mod whisper;
 
+// OCR module for scanned PDFs and images
+// Model inference requires the "ocr" feature
+pub mod ocr;
+
 // Replay module for time-travel debugging of agent sessions
 // Types are always available for serde compatibility
 // Full functionality requires the "replay" feature
@@ -183,13 +190,23 @@ pub use models::{
     ModelManifest, ModelManifestEntry, ModelVerification, ModelVerificationStatus,
     ModelVerifyOptions, verify_model_dir, verify_models,
 };
-#[cfg(feature = "excel")]
 pub use reader::{
-    DetectedTable, XlsxChunkingOptions, XlsxReader, XlsxStructuredDiagnostics, XlsxStructuredResult,
+    AUTHOR_METADATA_KEY, CANONICAL_URL_METADATA_KEY, CODE_LANGUAGE_METADATA_KEY,
+    CODE_LINES_METADATA_KEY, CODE_SIGNATURE_METADATA_KEY, CODE_SYMBOL_KIND_METADATA_KEY,
+    CODE_SYMBOLS_METADATA_KEY, ChatExport, CodeLanguage, CodeReader, CodeSegment, CodeSymbol,
+    DESCRIPTION_METADATA_KEY, DocumentFormat, DocumentReader, EMAIL_CC_METADATA_KEY,
+    EMAIL_DATE_METADATA_KEY, EMAIL_FROM_METADATA_KEY, EMAIL_IN_REPLY_TO_METADATA_KEY,
+    EMAIL_MESSAGE_ID_METADATA_KEY, EMAIL_SUBJECT_METADATA_KEY, EMAIL_TO_METADATA_KEY,
+    EmailAttachment, EmailMessage, EmailReader, EpubReader, HtmlReader, JsonlReader, MappedRecord,
+    OdpReader, OdtReader, PassthroughReader, PdfReader, RECORD_ID_METADATA_KEY, ReaderDiagnostics,
+    ReaderHint, ReaderOutput, ReaderRegistry, RecordMapping, SubtitleReader, SymbolKind,
+    THREAD_METADATA_KEY, TranscriptCue, identifier_terms, parse_subtitles, render_transcript,
+    split_identifier, write_webvtt,
 };
+#[cfg(feature = "excel")]
 pub use reader::{
-    DocumentFormat, DocumentReader, PassthroughReader, PdfReader, ReaderDiagnostics, ReaderHint,
-    ReaderOutput, ReaderRegistry,
+    DetectedTable, OdsReader, XlsxChunkingOptions, XlsxReader, XlsxStructuredDiagnostics,
+    XlsxStructuredResult,
 };
 pub use signature::{
     parse_ed25519_public_key_base64, verify_model_manifest, verify_ticket_signature,
@@ -199,20 +216,24 @@ pub use types::{
     ACL_POLICY_VERSION_KEY, ACL_READ_GROUPS_KEY, ACL_READ_PRINCIPALS_KEY, ACL_READ_ROLES_KEY,
     ACL_RESOURCE_ID_KEY, ACL_TENANT_ID_KEY, ACL_VISIBILITY_KEY, AclContext, AclEnforcementMode,
     AskCitation, AskMode, AskRequest, AskResponse, AskRetriever, AskStats, AudioSegmentMetadata,
-    AuditOptions, AuditReport, CanonicalEncoding, DOCTOR_PLAN_VERSION, DocAudioMetadata,
-    DocExifMetadata, DocGpsMetadata, DocMetadata, DoctorActionDetail, DoctorActionKind,
-    DoctorActionPlan, DoctorActionReport, DoctorActionStatus, DoctorFinding, DoctorFindingCode,
-    DoctorMetrics, DoctorOptions, DoctorPhaseDuration, DoctorPhaseKind, DoctorPhasePlan,
-    DoctorPhaseReport, DoctorPhaseStatus, DoctorPlan, DoctorReport, DoctorSeverity, DoctorStatus,
-    EmbeddingIdentity, EmbeddingIdentityCount, EmbeddingIdentitySummary, Frame, FrameId, FrameRole,
-    FrameStatus, Header, IndexManifests, LexIndexManifest, LexSegmentDescriptor,
+    AuditOptions, AuditReport, CanonicalEncoding, DOCTOR_PLAN_VERSION, DiversifyOptions,
+    DocAudioMetadata, DocExifMetadata, DocGpsMetadata, DocMetadata, DoctorActionDetail,
+    DoctorActionKind, DoctorActionPlan, DoctorActionReport, DoctorActionStatus, DoctorFinding,
+    DoctorFindingCode, DoctorMetrics, DoctorOptions, DoctorPhaseDuration, DoctorPhaseKind,
+    DoctorPhasePlan, DoctorPhaseReport, DoctorPhaseStatus, DoctorPlan, DoctorReport,
+    DoctorSeverity, DoctorStatus, EmbeddingIdentity, EmbeddingIdentityCount,
+    EmbeddingIdentitySummary, FacetBucket, FacetField, FacetRequest, FacetResult, Frame, FrameId,
+    FrameRole, FrameStatus, Header, HighlightFragment, HighlightOptions, HighlightSpan,
+    HistogramInterval, IndexManifests, LexIndexManifest, LexSegmentDescriptor,
     MEMVID_EMBEDDING_DIMENSION_KEY, MEMVID_EMBEDDING_MODEL_KEY, MEMVID_EMBEDDING_NORMALIZED_KEY,
     MEMVID_EMBEDDING_PROVIDER_KEY, MediaManifest, MemvidHandle, Open, PutManyOpts, PutOptions,
-    PutOptionsBuilder, Sealed, SearchEngineKind, SearchHit, SearchHitMetadata, SearchParams,
-    SearchRequest, SearchResponse, SegmentCatalog, SegmentCommon, SegmentCompression, SegmentMeta,
-    SegmentSpan, SourceSpan, Stats, TextChunkManifest, TextChunkRange, Ticket, TicketRef, Tier,
-    TimeIndexManifest, TimeSegmentDescriptor, TimelineEntry, TimelineQuery, TimelineQueryBuilder,
-    Toc, VecEmbedder, VecIndexManifest, VecSegmentDescriptor, VectorCompression, VerificationCheck,
+    PutOptionsBuilder, SYNC_HASH_METADATA_KEY, SYNC_MTIME_METADATA_KEY, SYNC_ROOT_METADATA_KEY,
+    SYNC_SIZE_METADATA_KEY, Sealed, SearchEngineKind, SearchHit, SearchHitMedia, SearchHitMetadata,
+    SearchHitRegion, SearchParams, SearchRequest, SearchResponse, SearchSort, SegmentCatalog,
+    SegmentCommon, SegmentCompression, SegmentMeta, SegmentSpan, SourceSpan, Stats, SyncOptions,
+    SyncReport, TextChunkManifest, TextChunkRange, Ticket, TicketRef, Tier, TimeIndexManifest,
+    TimeSegmentDescriptor, TimelineEntry, TimelineQuery, TimelineQueryBuilder, Toc, VecEmbedder,
+    VecIndexManifest, VecSegmentDescriptor, VectorCompression, VerificationCheck,
     VerificationReport, VerificationStatus,
 };
 #[cfg(feature = "temporal_track")]
@@ -241,6 +262,34 @@ pub use types::{
     hash_token, hash_token_u32, read_sketch_track, term_filter_maybe_contains, tokenize_for_sketch,
     write_sketch_track,
 };
+// Embedding cache track for persisted text embeddings
+pub use types::{
+    DEFAULT_EMBEDDING_CACHE_MAX_BYTES, DEFAULT_EMBEDDING_CACHE_MAX_ENTRIES, EMBEDDING_CACHE_MAGIC,
+    EMBEDDING_CACHE_VERSION, EmbeddingCacheConfig, EmbeddingCacheManifest, EmbeddingCacheStats,
+    EmbeddingCacheTrack, normalize_cache_text,
+};
+// Lexical search schema (title boost, typed metadata fields)
+pub use types::{
+    ContentLanguage, DEFAULT_TITLE_BOOST, LANGUAGE_METADATA_KEY, MAX_METADATA_FIELDS,
+    METADATA_FIELD_PREFIX, MetadataFieldType, SearchSchema,
+};
+// Search explanations: per-stage candidate counts and frame scoring
+pub use types::{
+    FrameExplanation, FrameVerdict, RrfContribution, ScoreExplanation, SearchExplanation,
+    SearchStage, StageTrace,
+};
+// User-defined synonyms and one-way query expansions
+pub use types::{MAX_SYNONYM_TERMS, QueryExpansion, SYNONYMS_METADATA_KEY, SynonymTrack};
+// Standing queries evaluated against newly committed frames
+pub use types::{
+    MAX_PERCOLATOR_MATCHES, MAX_STANDING_QUERIES, PercolatorDelivery, PercolatorTrack,
+    StandingQuery, StandingQueryMatch,
+};
+// Ingest-time PII policies and what they found
+pub use types::{
+    PII_FINDINGS_METADATA_KEY, PiiFindings, PiiFrameReport, PiiMode, PiiPattern, PiiPolicy,
+    PiiReport, PiiStats, PiiTrack,
+};
 // Schema types for predicate validation and type checking
 pub use types::{
     Cardinality, PredicateId, PredicateSchema, SchemaError, SchemaRegistry, ValueType,
@@ -257,6 +306,11 @@ pub use analysis::ner::{
 };
 // Enrichment engine types for extracting memory cards from frames
 pub use enrich::{EnrichmentContext, EnrichmentEngine, EnrichmentResult, RulesEngine};
+// Ingest pipeline stages run on every put
+pub use ingest::{
+    AutoTagStage, BytesOutcome, INGEST_SPLIT_METADATA_KEY, IngestChunk, IngestContext, IngestFrame,
+    IngestPart, IngestPipeline, IngestStage, PiiMaskStage, StageOutcome,
+};
 // Triplet extraction types for automatic SPO extraction
 pub use triplet::{ExtractionMode, ExtractionStats, TripletExtractor};
 // Graph-aware search for hybrid retrieval
@@ -273,10 +327,18 @@ pub use types::reranker::{
 #[cfg(feature = "parallel_segments")]
 pub use types::{IndexSegmentRef, SegmentKind, SegmentStats};
 pub use vec::{VecIndex, VecIndexArtifact, VecSearchHit};
+pub use vec_ivf::{
+    DEFAULT_IVF_TRAINING_SAMPLE, DEFAULT_NLIST, DEFAULT_NPROBE, IvfPqArtifact, IvfPqBuilder,
+    IvfPqIndex,
+};
 pub use vec_pq::{
     CompressionStats, ProductQuantizer, QuantizedVecIndex, QuantizedVecIndexArtifact,
     QuantizedVecIndexBuilder,
 };
+pub use vec_sq::{
+    BinaryQuantizer, BinaryVecIndex, DEFAULT_RESCORE_OVERSAMPLE, Int8VecIndex, ScalarQuantizer,
+    recall_at_k,
+};
 // Local text embedding provider - feature-gated
 #[cfg(feature = "vec")]
 pub use text_embed::{
@@ -286,8 +348,8 @@ pub use text_embed::{
 // API-based embedding providers - feature-gated
 #[cfg(feature = "api_embed")]
 pub use api_embed::{
-    OPENAI_MODELS, OpenAIConfig, OpenAIEmbedder, OpenAIModelInfo, default_openai_model_info,
-    get_openai_model_info,
+    EmbeddingApiFormat, HttpEmbedder, HttpEmbedderConfig, OPENAI_MODELS, OpenAIConfig,
+    OpenAIEmbedder, OpenAIModelInfo, default_openai_model_info, get_openai_model_info,
 };
 // CLIP visual embeddings - types always available for serde compatibility
 pub use clip::{
@@ -307,6 +369,16 @@ pub use whisper::{
 // Audio decoding and transcription require the "whisper" feature
 #[cfg(feature = "whisper")]
 pub use whisper::{WHISPER_SAMPLE_RATE, WhisperTranscriber, decode_audio_file};
+// OCR results - types always available
+pub use ocr::{
+    OCR_MAX_PAGES, OCR_RENDER_PX, OCR_WORDS_METADATA_KEY, OcrBox, OcrConfig, OcrDocument, OcrError,
+    OcrPage, OcrWord, decode_ocr_words, encode_ocr_words,
+};
+// Persisted word layout of OCR'd frames
+pub use types::{OCR_LAYOUT_MAGIC, OCR_LAYOUT_VERSION, OcrLayoutManifest, OcrLayoutTrack};
+// Text recognition requires the "ocr" feature
+#[cfg(feature = "ocr")]
+pub use ocr::OcrEngine;
 // Structure-aware chunking for preserving tables and code blocks
 pub use structure::{
     ChunkType, ChunkingOptions, ChunkingResult, StructuralChunker, StructuredChunk,
@@ -426,6 +498,24 @@ impl Memvid {
             self.lock.upgrade_to_exclusive()?;
             self.read_only = false;
         }
+        self.load_file_backed_indexes()
+    }
+
+    /// Copy index data still read from the file into memory, ahead of
+    /// writes that may overwrite the region it lives in.
+    pub(crate) fn load_file_backed_indexes(&mut self) -> Result<()> {
+        if let Some(index) = self.vec_index.as_mut() {
+            index.load_originals()?;
+        }
+        if self
+            .ivf_index
+            .as_ref()
+            .is_some_and(vec_ivf::IvfPqIndex::is_mapped)
+        {
+            if let Some(index) = self.ivf_index.take() {
+                self.ivf_index = Some(index.into_owned()?);
+            }
+        }
         Ok(())
     }
 
@@ -701,6 +791,11 @@ mod tests {
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                diversify: None,
+                highlight: None,
+                facets: Vec::new(),
+                sort: crate::types::SearchSort::Relevance,
+                explain: false,
             };
             let response = mem.search(request).expect("search");
             assert_eq!(response.hits.len(), 1);
@@ -722,6 +817,11 @@ mod tests {
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                diversify: None,
+                highlight: None,
+                facets: Vec::new(),
+                sort: crate::types::SearchSort::Relevance,
+                explain: false,
             };
             let response = reopened.search(request).expect("search reopened");
             assert_eq!(response.hits.len(), 1);
@@ -795,6 +895,11 @@ mod tests {
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    diversify: None,
+                    highlight: None,
+                    facets: Vec::new(),
+                    sort: crate::types::SearchSort::Relevance,
+                    explain: false,
                 })
                 .expect("search");
 
@@ -858,6 +963,11 @@ mod tests {
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    diversify: None,
+                    highlight: None,
+                    facets: Vec::new(),
+                    sort: crate::types::SearchSort::Relevance,
+                    explain: false,
                 })
                 .expect("search");
 
@@ -944,6 +1054,11 @@ mod tests {
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    diversify: None,
+                    highlight: None,
+                    facets: Vec::new(),
+                    sort: crate::types::SearchSort::Relevance,
+                    explain: false,
                 })
                 .expect("uri search");
             assert_eq!(uri_response.engine, SearchEngineKind::Tantivy);
@@ -969,6 +1084,11 @@ mod tests {
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    diversify: None,
+                    highlight: None,
+                    facets: Vec::new(),
+                    sort: crate::types::SearchSort::Relevance,
+                    explain: false,
                 })
                 .expect("scope search");
             assert_eq!(scope_response.engine, SearchEngineKind::Tantivy);
@@ -1024,6 +1144,11 @@ mod tests {
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    diversify: None,
+                    highlight: None,
+                    facets: Vec::new(),
+                    sort: crate::types::SearchSort::Relevance,
+                    explain: false,
                 })
                 .expect("page one");
             assert_eq!(first_page.engine, SearchEngineKind::Tantivy);
@@ -1049,6 +1174,11 @@ mod tests {
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    diversify: None,
+                    highlight: None,
+                    facets: Vec::new(),
+                    sort: crate::types::SearchSort::Relevance,
+                    explain: false,
                 })
                 .expect("page two");
             assert_eq!(second_page.engine, SearchEngineKind::Tantivy);
@@ -1092,6 +1222,11 @@ mod tests {
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    diversify: None,
+                    highlight: None,
+                    facets: Vec::new(),
+                    sort: crate::types::SearchSort::Relevance,
+                    explain: false,
                 })
                 .expect("search with tantivy");
 
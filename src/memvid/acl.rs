@@ -3,7 +3,7 @@ use std::collections::{BTreeMap, HashSet};
 use crate::memvid::lifecycle::Memvid;
 use crate::types::{
     ACL_READ_GROUPS_KEY, ACL_READ_PRINCIPALS_KEY, ACL_READ_ROLES_KEY, ACL_TENANT_ID_KEY,
-    ACL_VISIBILITY_KEY, AclContext, AclEnforcementMode, SearchHit,
+    ACL_VISIBILITY_KEY, AclContext, AclEnforcementMode, FrameId, SearchHit,
 };
 use crate::{MemvidError, Result};
 
@@ -134,6 +134,27 @@ impl Memvid {
 
         Ok(stats)
     }
+
+    /// Frame-id counterpart of [`Self::apply_acl_to_search_hits`], used where
+    /// results are aggregated rather than returned (e.g. facet counts).
+    pub(crate) fn retain_acl_allowed_frames(
+        &self,
+        frame_ids: &mut Vec<FrameId>,
+        acl_context: Option<&AclContext>,
+        acl_enforcement_mode: AclEnforcementMode,
+    ) -> Result<()> {
+        if acl_enforcement_mode == AclEnforcementMode::Audit {
+            return Ok(());
+        }
+        let normalized_context = validate_enforce_acl_context(acl_context)?;
+        frame_ids.retain(|&frame_id| match self.frame_by_id(frame_id) {
+            Ok(frame) => {
+                evaluate_acl_metadata(&frame.extra_metadata, Some(&normalized_context)).allowed
+            }
+            Err(_) => false,
+        });
+        Ok(())
+    }
 }
 
 fn validate_enforce_acl_context(context: Option<&AclContext>) -> Result<NormalizedAclContext> {
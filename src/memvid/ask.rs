@@ -6,6 +6,8 @@ use std::num::NonZeroU64;
 use std::time::Instant;
 
 use crate::memvid::lifecycle::Memvid;
+#[cfg(feature = "lex")]
+use crate::memvid::search::diversify::collapse_by_key;
 use crate::memvid::search::helpers::{build_context, reorder_hits_by_token_matches};
 #[cfg(feature = "temporal_track")]
 use crate::types::TemporalFilter;
@@ -16,7 +18,7 @@ use crate::types::{
 };
 use crate::{MemvidError, Result, VecEmbedder};
 
-const RRF_K: f32 = 60.0;
+pub(crate) const RRF_K: f32 = 60.0;
 
 #[cfg(feature = "lex")]
 impl Memvid {
@@ -107,13 +109,18 @@ impl Memvid {
             no_sketch: true,
             acl_context: request.acl_context.clone(),
             acl_enforcement_mode: request.acl_enforcement_mode,
+            diversify: None,
+            highlight: None,
+            facets: Vec::new(),
+            sort: crate::types::SearchSort::Relevance,
+            explain: false,
         };
 
         // Pre-compute the query embedding once so we can reuse it for vector recall and semantic re-rank
         let mut query_embedding: Option<Vec<f32>> = None;
         if let Some(embedder) = embedder {
             if self.vec_enabled || request.mode != AskMode::Lex {
-                query_embedding = Some(embedder.embed_query(&request.question)?);
+                query_embedding = Some(self.embed_query_cached(embedder, &request.question)?);
             }
         }
 
@@ -338,7 +345,12 @@ impl Memvid {
                 retrieval.hits.len(),
                 request.top_k
             );
-            diversify_hits_for_aggregation(&mut retrieval.hits, request.top_k);
+            let keys: Vec<String> = retrieval
+                .hits
+                .iter()
+                .map(|hit| self.document_key(hit))
+                .collect();
+            collapse_by_key(&mut retrieval.hits, &keys, request.top_k, true);
             retrieval.total_hits = retrieval.hits.len();
         }
 
@@ -368,6 +380,7 @@ impl Memvid {
         // This ensures user corrections override all other ranking signals
         promote_corrections(self, &mut retrieval.hits)?;
 
+        // Denied hits must not crowd out or suppress permitted ones
         self.apply_acl_to_search_hits(
             &mut retrieval.hits,
             request.acl_context.as_ref(),
@@ -377,6 +390,21 @@ impl Memvid {
             retrieval.total_hits = retrieval.hits.len();
         }
 
+        if let Some(options) = request
+            .diversify
+            .as_ref()
+            .filter(|options| options.is_active())
+        {
+            let limit = retrieval.hits.len();
+            self.diversify_hits(&mut retrieval.hits, options, limit)?;
+            retrieval.total_hits = retrieval.hits.len();
+        }
+
+        #[cfg(feature = "lex")]
+        if let Some(options) = request.highlight.as_ref() {
+            self.highlight_hits(&mut retrieval.hits, &search_request.query, options)?;
+        }
+
         retrieval.context = build_context(&retrieval.hits);
 
         let (answer, citations, synthesis_ms) = if request.context_only {
@@ -494,7 +522,7 @@ impl Memvid {
         let query_embedding_cow: Cow<'_, [f32]> = if let Some(existing) = query_embedding_hint {
             Cow::Borrowed(existing)
         } else {
-            let embedding = embedder.embed_query(&request.question)?;
+            let embedding = self.embed_query_cached(embedder, &request.question)?;
             if embedding.is_empty() {
                 return Ok(false);
             }
@@ -570,6 +598,10 @@ impl Memvid {
                 context: String::new(),
                 next_cursor: None,
                 engine: SearchEngineKind::LexFallback,
+                suggested_query: None,
+                facets: Vec::new(),
+                query_expansions: Vec::new(),
+                explanation: None,
                 elapsed_ms,
                 params: SearchParams {
                     top_k: request.top_k,
@@ -640,6 +672,7 @@ impl Memvid {
                 text: frame_text.clone(),
                 chunk_text: Some(frame_text.clone()),
                 metadata: None,
+                highlights: Vec::new(),
             });
         }
 
@@ -653,6 +686,10 @@ impl Memvid {
             context,
             next_cursor: None,
             engine: SearchEngineKind::LexFallback, // Mark as fallback
+            suggested_query: None,
+            facets: Vec::new(),
+            query_expansions: Vec::new(),
+            explanation: None,
             elapsed_ms,
             params: SearchParams {
                 top_k: request.top_k,
@@ -770,6 +807,11 @@ fn build_citations(hits: &[SearchHit], semantic_scores: &HashMap<u64, f32>) -> V
             uri: hit.uri.clone(),
             chunk_range: hit.chunk_range.or(Some(hit.range)),
             score: semantic_scores.get(&hit.frame_id).copied().or(hit.score),
+            spans: hit
+                .highlights
+                .iter()
+                .flat_map(|fragment| fragment.spans.iter().cloned())
+                .collect(),
         })
         .collect()
 }
@@ -1293,42 +1335,6 @@ fn build_recency_query(tokens: &[String]) -> String {
         .join(" OR ")
 }
 
-/// Diversify hits for aggregation questions by ensuring unique URIs/sessions.
-/// This helps find information scattered across multiple conversations.
-fn diversify_hits_for_aggregation(hits: &mut Vec<SearchHit>, target_unique: usize) {
-    if hits.len() <= target_unique {
-        return;
-    }
-
-    let mut seen_uris: HashSet<String> = HashSet::new();
-    let mut diversified: Vec<SearchHit> = Vec::new();
-    let mut remaining: Vec<SearchHit> = Vec::new();
-
-    // First pass: collect one hit per unique URI (base URI without fragment)
-    for hit in hits.drain(..) {
-        let base_uri = hit.uri.split('#').next().unwrap_or(&hit.uri).to_string();
-        if !seen_uris.contains(&base_uri) && diversified.len() < target_unique {
-            seen_uris.insert(base_uri);
-            diversified.push(hit);
-        } else {
-            remaining.push(hit);
-        }
-    }
-
-    // Second pass: fill remaining slots with best scoring remaining hits
-    let slots_left = target_unique.saturating_sub(diversified.len());
-    for hit in remaining.into_iter().take(slots_left) {
-        diversified.push(hit);
-    }
-
-    // Re-rank
-    for (idx, hit) in diversified.iter_mut().enumerate() {
-        hit.rank = idx + 1;
-    }
-
-    *hits = diversified;
-}
-
 /// Retrieve pure vector hits for fusion.
 fn vector_hits(
     memvid: &mut Memvid,
@@ -73,6 +73,8 @@ impl Memvid {
             adaptive: None,
             acl_context: None,
             acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+            diversify: None,
+            highlight: None,
         };
 
         let response = self.ask(request, embedder)?;
@@ -9,20 +9,36 @@
 //!
 //! Tables are split between rows with header propagation to ensure each chunk
 //! maintains context about the table structure.
+//!
+//! Source files are chunked per symbol instead, verbatim, with each chunk's
+//! symbol and line range kept for its frame metadata.
+//!
+//! Transcripts are chunked on cue boundaries, one cue per line, with each
+//! chunk's cue timings kept for its frame's audio metadata.
+
+use std::collections::BTreeMap;
 
 use crate::{
     normalize_text,
+    reader::{CodeLanguage, TranscriptCue, code_segments},
     structure::{ChunkingOptions, StructuralChunker, detect_structure},
-    types::{TextChunkManifest, TextChunkRange},
+    types::{AudioSegmentMetadata, TextChunkManifest, TextChunkRange},
 };
 
 pub(crate) const DEFAULT_CHUNK_CHARS: usize = 1_200;
 pub(crate) const CHUNK_MIN_CHARS: usize = DEFAULT_CHUNK_CHARS * 2;
+/// Functions run longer than prose paragraphs; splitting one loses its context.
+const CODE_CHUNK_CHARS: usize = DEFAULT_CHUNK_CHARS * 2;
 
 #[derive(Debug, Clone)]
 pub(crate) struct DocumentChunkPlan {
     pub manifest: TextChunkManifest,
     pub chunks: Vec<String>,
+    /// Per-chunk `extra_metadata`, parallel to `chunks`; empty for prose.
+    pub chunk_metadata: Vec<BTreeMap<String, String>>,
+    /// Per-chunk cue timings, one per line of the chunk; empty unless the
+    /// document is a transcript.
+    pub chunk_segments: Vec<Vec<AudioSegmentMetadata>>,
 }
 
 pub(crate) fn plan_document_chunks(raw: &[u8]) -> Option<DocumentChunkPlan> {
@@ -32,6 +48,85 @@ pub(crate) fn plan_document_chunks(raw: &[u8]) -> Option<DocumentChunkPlan> {
     plan_text_chunks(&text)
 }
 
+/// Plan one chunk per symbol of a source file. Returns `None` for files that
+/// are a single segment.
+pub(crate) fn plan_code_chunks(raw: &[u8], language: CodeLanguage) -> Option<DocumentChunkPlan> {
+    let text = std::str::from_utf8(raw).ok()?;
+    let segments = code_segments(text, language, CODE_CHUNK_CHARS);
+    if segments.len() <= 1 {
+        return None;
+    }
+    let mut start = 0;
+    let mut ranges = Vec::with_capacity(segments.len());
+    for segment in &segments {
+        let end = start + segment.text.chars().count();
+        ranges.push(TextChunkRange { start, end });
+        start = end;
+    }
+    Some(DocumentChunkPlan {
+        manifest: TextChunkManifest {
+            chunk_chars: CODE_CHUNK_CHARS,
+            chunks: ranges,
+        },
+        chunk_metadata: segments
+            .iter()
+            .map(|segment| segment.metadata(language))
+            .collect(),
+        chunks: segments.into_iter().map(|segment| segment.text).collect(),
+        chunk_segments: Vec::new(),
+    })
+}
+
+/// Plan transcript chunks that start and end on cue boundaries. Returns `None`
+/// for transcripts short enough to stay a single frame.
+pub(crate) fn plan_transcript_chunks(cues: &[TranscriptCue]) -> Option<DocumentChunkPlan> {
+    let lines: Vec<String> = cues.iter().map(TranscriptCue::line).collect();
+    let total: usize = lines.iter().map(|line| line.chars().count() + 1).sum();
+    if total < CHUNK_MIN_CHARS {
+        return None;
+    }
+
+    let mut chunks = Vec::new();
+    let mut chunk_segments = Vec::new();
+    let mut ranges = Vec::new();
+    let mut text = String::new();
+    let mut segments = Vec::new();
+    let mut start = 0;
+    for (idx, (cue, line)) in cues.iter().zip(&lines).enumerate() {
+        if !text.is_empty() && text.chars().count() + line.chars().count() > DEFAULT_CHUNK_CHARS {
+            let end = start + text.chars().count();
+            ranges.push(TextChunkRange { start, end });
+            start = end;
+            chunks.push(std::mem::take(&mut text));
+            chunk_segments.push(std::mem::take(&mut segments));
+        }
+        text.push_str(line);
+        // Chunks concatenate back to the whole transcript
+        if idx + 1 < lines.len() {
+            text.push('\n');
+        }
+        segments.push(cue.segment());
+    }
+    if !text.is_empty() {
+        let end = start + text.chars().count();
+        ranges.push(TextChunkRange { start, end });
+        chunks.push(text);
+        chunk_segments.push(segments);
+    }
+    if chunks.len() <= 1 {
+        return None;
+    }
+    Some(DocumentChunkPlan {
+        manifest: TextChunkManifest {
+            chunk_chars: DEFAULT_CHUNK_CHARS,
+            chunks: ranges,
+        },
+        chunks,
+        chunk_metadata: Vec::new(),
+        chunk_segments,
+    })
+}
+
 /// Plan chunks from already-extracted text (e.g., from PDF extraction).
 /// This is used when the raw payload isn't valid UTF-8 but we have extracted text.
 ///
@@ -82,7 +177,12 @@ fn plan_structural_chunks(
     // Build manifest with accurate character ranges
     let manifest = build_manifest_from_structural(&result.chunks, text);
 
-    Some(DocumentChunkPlan { manifest, chunks })
+    Some(DocumentChunkPlan {
+        manifest,
+        chunks,
+        chunk_metadata: Vec::new(),
+        chunk_segments: Vec::new(),
+    })
 }
 
 /// Build `TextChunkManifest` from structural chunks.
@@ -118,7 +218,12 @@ fn plan_naive_chunks(text: &str) -> Option<DocumentChunkPlan> {
         .iter()
         .map(|range| slice_text_range(text, range))
         .collect();
-    Some(DocumentChunkPlan { manifest, chunks })
+    Some(DocumentChunkPlan {
+        manifest,
+        chunks,
+        chunk_metadata: Vec::new(),
+        chunk_segments: Vec::new(),
+    })
 }
 
 fn build_chunk_manifest(text: &str, chunk_chars: usize) -> Option<TextChunkManifest> {
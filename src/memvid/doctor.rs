@@ -575,6 +575,23 @@ impl DoctorPlanner {
             return;
         };
 
+        #[cfg(feature = "lex")]
+        if toc
+            .segment_catalog
+            .tantivy_segments
+            .iter()
+            .any(|descriptor| {
+                descriptor.common.codec_version < crate::search::TANTIVY_CODEC_VERSION
+            })
+        {
+            probe.index.needs_lex = true;
+            probe.findings.push(DoctorFinding::warning(
+                DoctorFindingCode::TantivySnapshotOutdated,
+                "Tantivy segments were written by an older analyzer".to_string(),
+            ));
+            return;
+        }
+
         // CRITICAL FIX (Bug #10): Check for Tantivy segments first
         // Tantivy indexes have NO manifest but have lex_segments instead
         if !toc.indexes.lex_segments.is_empty() {
@@ -0,0 +1,109 @@
+//! Persistent embedding cache methods for Memvid.
+//!
+//! The cache track is opt-in: once enabled it is persisted on every commit and
+//! reloaded on open. Lookups need the embedder to report an
+//! [`EmbeddingIdentity`](crate::types::EmbeddingIdentity); embedders that don't
+//! bypass the cache entirely.
+
+use crate::error::Result;
+use crate::types::{EmbeddingCacheConfig, EmbeddingCacheStats, EmbeddingCacheTrack, VecEmbedder};
+
+use super::Memvid;
+
+impl Memvid {
+    /// Enable the persistent embedding cache, or update its limits when it is
+    /// already enabled.
+    pub fn enable_embedding_cache(&mut self, config: EmbeddingCacheConfig) {
+        match self.embedding_cache.as_mut() {
+            Some(cache) => cache.set_config(config),
+            None => self.embedding_cache = Some(EmbeddingCacheTrack::new(config)),
+        }
+    }
+
+    /// Disable the embedding cache. The track is dropped from the file on the
+    /// next commit.
+    pub fn disable_embedding_cache(&mut self) {
+        self.embedding_cache = None;
+    }
+
+    /// Drop all cached embeddings while keeping the cache enabled.
+    pub fn clear_embedding_cache(&mut self) {
+        if let Some(cache) = self.embedding_cache.as_mut() {
+            cache.clear();
+        }
+    }
+
+    /// Size and hit statistics, or `None` when the cache is disabled.
+    #[must_use]
+    pub fn embedding_cache_stats(&self) -> Option<EmbeddingCacheStats> {
+        self.embedding_cache
+            .as_ref()
+            .map(EmbeddingCacheTrack::stats)
+    }
+
+    /// Embed `texts`, serving repeated texts from the embedding cache and
+    /// storing newly computed vectors in it.
+    ///
+    /// Falls back to `embedder.embed_chunks` when the cache is disabled or the
+    /// embedder reports no identity.
+    pub fn embed_texts_cached<E>(&mut self, embedder: &E, texts: &[&str]) -> Result<Vec<Vec<f32>>>
+    where
+        E: VecEmbedder + ?Sized,
+    {
+        let (Some(cache), Some(identity)) =
+            (self.embedding_cache.as_mut(), embedder.embedding_identity())
+        else {
+            return embedder.embed_chunks(texts);
+        };
+
+        let mut results: Vec<Option<Vec<f32>>> = texts
+            .iter()
+            .map(|text| cache.get(&identity, text))
+            .collect();
+        let missing: Vec<usize> = results
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, cached)| cached.is_none().then_some(idx))
+            .collect();
+
+        if !missing.is_empty() {
+            let batch: Vec<&str> = missing.iter().map(|&idx| texts[idx]).collect();
+            let embeddings = embedder.embed_chunks(&batch)?;
+            for (&idx, embedding) in missing.iter().zip(embeddings) {
+                cache.insert(&identity, texts[idx], embedding.clone());
+                results[idx] = Some(embedding);
+            }
+        }
+
+        Ok(results.into_iter().map(Option::unwrap_or_default).collect())
+    }
+
+    /// Embed a single query through the embedding cache.
+    pub(crate) fn embed_query_cached<E>(&mut self, embedder: &E, text: &str) -> Result<Vec<f32>>
+    where
+        E: VecEmbedder + ?Sized,
+    {
+        let identity = match (self.embedding_cache.as_mut(), embedder.embedding_identity()) {
+            (Some(cache), Some(identity)) => {
+                if let Some(embedding) = cache.get(&identity, text) {
+                    return Ok(embedding);
+                }
+                identity
+            }
+            _ => return embedder.embed_query(text),
+        };
+        let embedding = embedder.embed_query(text)?;
+        if let Some(cache) = self.embedding_cache.as_mut() {
+            cache.insert(&identity, text, embedding.clone());
+        }
+        Ok(embedding)
+    }
+
+    /// Whether the cache has changes (or was disabled) since the last commit.
+    pub(crate) fn embedding_cache_dirty(&self) -> bool {
+        match &self.embedding_cache {
+            Some(cache) => cache.is_dirty(),
+            None => self.toc.embedding_cache.is_some(),
+        }
+    }
+}
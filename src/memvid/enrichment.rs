@@ -15,7 +15,9 @@ use crate::enrichment_worker::{
 };
 use crate::error::Result;
 use crate::extract_budgeted::ExtractionBudget;
-use crate::types::{EnrichmentState, EnrichmentTask, FrameId, FrameStatus, VecEmbedder};
+use crate::types::{
+    EmbeddingIdentity, EnrichmentState, EnrichmentTask, FrameId, FrameStatus, VecEmbedder,
+};
 use crate::vec::VecIndexBuilder;
 
 use super::Memvid;
@@ -397,6 +399,7 @@ impl Memvid {
         if let Err(err) = self.update_tantivy_for_enrichment(task.frame_id, &final_text) {
             result.error = Some(format!("Index update failed: {err}"));
         }
+        self.percolate_enriched(task.frame_id, &final_text);
 
         // Mark frame as enriched
         self.mark_frame_enriched(task.frame_id);
@@ -475,7 +478,7 @@ impl Memvid {
         let count = embeddings.len();
 
         // Build new vector index with existing + new embeddings
-        let mut builder = VecIndexBuilder::new();
+        let mut builder = VecIndexBuilder::new().with_compression(self.vec_compression.clone());
 
         // Add existing embeddings from current index
         if let Some(ref vec_index) = self.vec_index {
@@ -509,7 +512,11 @@ impl Memvid {
             bytes_offset: 0, // Will be set during commit
             bytes_length: artifact.bytes.len() as u64,
             checksum: artifact.checksum,
-            compression_mode: crate::types::VectorCompression::None,
+            compression_mode: if self.vec_compression.is_scalar_quantized() {
+                self.vec_compression.clone()
+            } else {
+                crate::types::VectorCompression::None
+            },
             model: self.vec_model.clone(),
         });
 
@@ -543,6 +550,7 @@ impl Memvid {
         let mut batcher = EmbeddingBatcher::new(embedder, batch_size);
         let mut frames_processed = 0;
         let mut embeddings_generated = 0;
+        let identity = batcher.embedding_identity();
 
         // Collect all tasks first to avoid borrow conflicts
         let tasks: Vec<_> = self.toc.enrichment_queue.tasks.clone();
@@ -572,6 +580,7 @@ impl Memvid {
             if let Err(err) = self.update_tantivy_for_enrichment(task.frame_id, &final_text) {
                 tracing::warn!(frame_id = task.frame_id, ?err, "tantivy update failed");
             }
+            self.percolate_enriched(task.frame_id, &final_text);
 
             // Queue for embedding if needed
             if needs_embedding && !final_text.trim().is_empty() {
@@ -579,13 +588,15 @@ impl Memvid {
 
                 // Flush batch if ready
                 if batcher.should_flush() {
-                    match batcher.flush() {
+                    match batcher.flush_cached(self) {
                         Ok(count) => {
                             embeddings_generated += count;
                             // Store ready embeddings
                             let ready = batcher.take_embeddings();
                             if !ready.is_empty() {
-                                if let Err(err) = self.add_embeddings(ready) {
+                                if let Err(err) =
+                                    self.store_enrichment_embeddings(identity.as_ref(), ready)
+                                {
                                     tracing::warn!(?err, "failed to add embeddings");
                                 }
                             }
@@ -613,12 +624,13 @@ impl Memvid {
 
         // Flush remaining batch
         if batcher.pending_count() > 0 {
-            match batcher.flush() {
+            match batcher.flush_cached(self) {
                 Ok(count) => {
                     embeddings_generated += count;
                     let ready = batcher.take_embeddings();
                     if !ready.is_empty() {
-                        if let Err(err) = self.add_embeddings(ready) {
+                        if let Err(err) = self.store_enrichment_embeddings(identity.as_ref(), ready)
+                        {
                             tracing::warn!(?err, "failed to add final embeddings");
                         }
                     }
@@ -638,6 +650,29 @@ impl Memvid {
         Ok((frames_processed, embeddings_generated))
     }
 
+    /// Add embeddings to the vector index and stamp the embedder identity on
+    /// each embedded frame.
+    fn store_enrichment_embeddings(
+        &mut self,
+        identity: Option<&EmbeddingIdentity>,
+        embeddings: Vec<(FrameId, Vec<f32>)>,
+    ) -> Result<usize> {
+        let frame_ids: Vec<FrameId> = embeddings.iter().map(|(frame_id, _)| *frame_id).collect();
+        let added = self.add_embeddings(embeddings)?;
+        if let Some(identity) = identity {
+            for frame_id in frame_ids {
+                if let Some(frame) = usize::try_from(frame_id)
+                    .ok()
+                    .and_then(|idx| self.toc.frames.get_mut(idx))
+                {
+                    identity.write_extra_metadata(&mut frame.extra_metadata);
+                    self.dirty = true;
+                }
+            }
+        }
+        Ok(added)
+    }
+
     /// Check if vector embeddings are enabled.
     #[must_use]
     pub fn has_embeddings(&self) -> bool {
@@ -389,7 +389,7 @@ impl Memvid {
     }
 
     pub(crate) fn frame_canonical_bytes(&mut self, frame: &Frame) -> Result<Vec<u8>> {
-        if frame.role == FrameRole::Document && frame.chunk_manifest.is_some() {
+        if frame.role.is_document() && frame.chunk_manifest.is_some() {
             let chunks = self.document_chunk_payloads(frame)?;
             let mut buffer = Vec::new();
             for (_, bytes) in chunks {
@@ -411,7 +411,7 @@ impl Memvid {
     }
 
     pub(crate) fn frame_canonical_text(&mut self, frame: &Frame) -> Result<String> {
-        if frame.role == FrameRole::Document && frame.chunk_manifest.is_some() {
+        if frame.role.is_document() && frame.chunk_manifest.is_some() {
             let bytes = self.frame_canonical_bytes(frame)?;
             return match String::from_utf8(bytes) {
                 Ok(text) => Ok(text),
@@ -492,7 +492,7 @@ impl Memvid {
         Ok(payloads)
     }
 
-    fn document_chunk_frames(&self, parent_id: FrameId) -> Vec<Frame> {
+    pub(crate) fn document_chunk_frames(&self, parent_id: FrameId) -> Vec<Frame> {
         let mut frames: Vec<Frame> = self
             .toc
             .frames
@@ -510,7 +510,7 @@ impl Memvid {
 
     pub(crate) fn resolve_chunk_context(&mut self, frame: &Frame) -> Result<ChunkInfo> {
         match frame.role {
-            FrameRole::Document => {
+            FrameRole::Document | FrameRole::Attachment => {
                 if frame.chunk_manifest.is_some() {
                     let payloads = self.document_chunk_payloads(frame)?;
                     if payloads.is_empty() {
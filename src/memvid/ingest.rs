@@ -0,0 +1,185 @@
+//! Ingest pipeline registration and the hooks `put` runs it at.
+//!
+//! Pipelines are not persisted; they apply to puts through this handle.
+
+use std::collections::BTreeMap;
+
+use crate::error::Result;
+use crate::ingest::{IngestChunk, IngestContext, IngestFrame, IngestPipeline};
+use crate::types::{PutOptions, TextChunkRange};
+
+use super::Memvid;
+use super::chunks::DocumentChunkPlan;
+
+impl Memvid {
+    /// Run `pipeline` on every subsequent put, replacing any pipeline set
+    /// before. A put whose payload a stage splits writes all of its parts or,
+    /// when one fails, none, and returns the first part's sequence. The parts
+    /// share an [`INGEST_SPLIT_METADATA_KEY`](crate::INGEST_SPLIT_METADATA_KEY)
+    /// value, and updating the payload of one tombstones the others.
+    pub fn set_ingest_pipeline(&mut self, pipeline: IngestPipeline) {
+        self.ingest_pipeline = (!pipeline.is_empty()).then_some(pipeline);
+    }
+
+    /// Stop running the ingest pipeline.
+    pub fn clear_ingest_pipeline(&mut self) {
+        self.ingest_pipeline = None;
+    }
+
+    /// The pipeline puts run through, if one is set.
+    #[must_use]
+    pub fn ingest_pipeline(&self) -> Option<&IngestPipeline> {
+        self.ingest_pipeline.as_ref()
+    }
+}
+
+/// Hand the chunks of `plan` to the chunk stages and take their edits back.
+/// Returns whether a stage changed any chunk's text. Edits that change a
+/// chunk's length re-lay the manifest ranges end to end, so they address the
+/// joined chunk text.
+pub(crate) fn run_chunk_stages(
+    pipeline: &IngestPipeline,
+    ctx: &IngestContext,
+    plan: &mut DocumentChunkPlan,
+) -> Result<bool> {
+    plan.chunk_metadata
+        .resize_with(plan.chunks.len(), BTreeMap::new);
+    let before: Vec<(usize, blake3::Hash)> = plan
+        .chunks
+        .iter()
+        .map(|text| (text.chars().count(), blake3::hash(text.as_bytes())))
+        .collect();
+    let mut chunks: Vec<IngestChunk> = plan
+        .chunks
+        .iter_mut()
+        .zip(&mut plan.chunk_metadata)
+        .enumerate()
+        .map(|(index, (text, extra_metadata))| IngestChunk {
+            index,
+            text: std::mem::take(text),
+            extra_metadata: std::mem::take(extra_metadata),
+        })
+        .collect();
+    pipeline.run_chunks(ctx, &mut chunks)?;
+    for (chunk, (text, extra_metadata)) in chunks
+        .into_iter()
+        .zip(plan.chunks.iter_mut().zip(&mut plan.chunk_metadata))
+    {
+        *text = chunk.text;
+        *extra_metadata = chunk.extra_metadata;
+    }
+
+    let lengths: Vec<usize> = plan
+        .chunks
+        .iter()
+        .map(|text| text.chars().count())
+        .collect();
+    if lengths
+        .iter()
+        .zip(&before)
+        .any(|(length, (previous, _))| length != previous)
+    {
+        let mut start = 0;
+        plan.manifest.chunks = lengths
+            .iter()
+            .map(|length| {
+                let range = TextChunkRange {
+                    start,
+                    end: start + length,
+                };
+                start = range.end;
+                range
+            })
+            .collect();
+    }
+    Ok(plan
+        .chunks
+        .iter()
+        .zip(&before)
+        .any(|(text, (_, hash))| blake3::hash(text.as_bytes()) != *hash))
+}
+
+/// Hand the frame's metadata to the frame stages and take their edits back.
+pub(crate) fn run_frame_stages(
+    pipeline: &IngestPipeline,
+    options: &mut PutOptions,
+    search_text: &mut Option<String>,
+    tags: &mut Vec<String>,
+    labels: &mut Vec<String>,
+    extra_metadata: &mut BTreeMap<String, String>,
+    content_dates: &mut Vec<String>,
+) -> Result<()> {
+    let ctx = IngestContext::from_options(options);
+    let mut frame = IngestFrame {
+        uri: options.uri.take(),
+        title: options.title.take(),
+        kind: options.kind.take(),
+        track: options.track.take(),
+        search_text: search_text.take(),
+        tags: std::mem::take(tags),
+        labels: std::mem::take(labels),
+        extra_metadata: std::mem::take(extra_metadata),
+        content_dates: std::mem::take(content_dates),
+    };
+    pipeline.run_frame(&ctx, &mut frame)?;
+    options.uri = frame.uri;
+    options.title = frame.title;
+    options.kind = frame.kind;
+    options.track = frame.track;
+    *search_text = frame.search_text;
+    *tags = frame.tags;
+    *labels = frame.labels;
+    *extra_metadata = frame.extra_metadata;
+    *content_dates = frame.content_dates;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::{IngestStage, StageOutcome};
+    use crate::memvid::chunks::plan_document_chunks;
+
+    struct Stamp;
+
+    impl IngestStage for Stamp {
+        fn name(&self) -> &'static str {
+            "stamp"
+        }
+
+        fn on_chunks(
+            &self,
+            _ctx: &IngestContext,
+            chunks: &mut [IngestChunk],
+        ) -> Result<StageOutcome> {
+            chunks[0].text.push_str(" [reviewed]");
+            Ok(StageOutcome::Continue)
+        }
+    }
+
+    #[test]
+    fn length_changing_chunk_edits_relay_the_manifest() {
+        let text = "Lorem ipsum dolor sit amet. ".repeat(200);
+        let mut plan = plan_document_chunks(text.as_bytes()).expect("chunk plan");
+        let ranges = plan.manifest.chunks.clone();
+
+        let unchanged =
+            run_chunk_stages(&IngestPipeline::new(), &IngestContext::default(), &mut plan);
+        assert!(!unchanged.unwrap());
+        assert_eq!(plan.manifest.chunks, ranges);
+
+        let pipeline = IngestPipeline::new().with_stage(Stamp);
+        assert!(run_chunk_stages(&pipeline, &IngestContext::default(), &mut plan).unwrap());
+        assert_eq!(plan.manifest.chunks.len(), plan.chunks.len());
+        let mut start = 0;
+        for (range, chunk) in plan.manifest.chunks.iter().zip(&plan.chunks) {
+            assert_eq!(range.start, start);
+            assert_eq!(range.end - range.start, chunk.chars().count());
+            start = range.end;
+        }
+        assert_eq!(
+            plan.manifest.chunks[1].start,
+            ranges[0].end + " [reviewed]".len()
+        );
+    }
+}
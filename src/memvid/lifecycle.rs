@@ -16,11 +16,13 @@ use std::sync::{Arc, RwLock};
 use crate::constants::{MAGIC, SPEC_VERSION, WAL_OFFSET, WAL_SIZE_TINY};
 use crate::error::{MemvidError, Result};
 use crate::footer::{FooterSlice, find_last_valid_footer};
+use crate::ingest::IngestPipeline;
 use crate::io::header::HeaderCodec;
 #[cfg(feature = "parallel_segments")]
 use crate::io::manifest_wal::ManifestWal;
 use crate::io::wal::EmbeddedWal;
 use crate::lock::{FileLock, LockMode};
+use crate::memvid::percolator::StandingQueryListener;
 #[cfg(feature = "lex")]
 use crate::search::{EmbeddedLexStorage, TantivyEngine};
 #[cfg(feature = "temporal_track")]
@@ -28,8 +30,9 @@ use crate::types::FrameId;
 #[cfg(feature = "parallel_segments")]
 use crate::types::IndexSegmentRef;
 use crate::types::{
-    FrameStatus, Header, IndexManifests, LogicMesh, MemoriesTrack, PutManyOpts, SchemaRegistry,
-    SegmentCatalog, SketchTrack, TicketRef, Tier, Toc, VectorCompression,
+    EmbeddingCacheTrack, FrameStatus, Header, IndexManifests, LogicMesh, MemoriesTrack,
+    PutManyOpts, SchemaRegistry, SegmentCatalog, SketchTrack, TicketRef, Tier, Toc,
+    VectorCompression,
 };
 #[cfg(feature = "temporal_track")]
 use crate::{TemporalTrack, temporal_track_read};
@@ -70,8 +73,12 @@ pub struct Memvid {
     pub(crate) lex_storage: Arc<RwLock<EmbeddedLexStorage>>,
     pub(crate) vec_enabled: bool,
     pub(crate) vec_compression: VectorCompression,
+    /// Candidate multiplier for rescoring int8/binary hits (0 disables).
+    pub(crate) vec_rescore_oversample: usize,
     pub(crate) vec_model: Option<String>,
     pub(crate) vec_index: Option<VecIndex>,
+    /// Memory-mapped IVF-PQ sidecar used by `search_vec` when attached.
+    pub(crate) ivf_index: Option<crate::vec_ivf::IvfPqIndex>,
     /// CLIP visual embeddings index (separate from vec due to different dimensions)
     pub(crate) clip_enabled: bool,
     pub(crate) clip_index: Option<crate::clip::ClipIndex>,
@@ -90,18 +97,31 @@ pub struct Memvid {
     pub(crate) logic_mesh: LogicMesh,
     /// In-memory sketch track for fast candidate generation.
     pub(crate) sketch_track: SketchTrack,
+    /// Persistent embedding cache; `None` when the cache is disabled.
+    pub(crate) embedding_cache: Option<EmbeddingCacheTrack>,
     /// Schema registry for predicate validation.
     pub(crate) schema_registry: SchemaRegistry,
     /// Whether to enforce strict schema validation on card insert.
     pub(crate) schema_strict: bool,
     /// Active batch mode options (set by `begin_batch`, cleared by `end_batch`).
     pub(crate) batch_opts: Option<PutManyOpts>,
+    /// Callbacks registered with `on_standing_query_match` (not persisted).
+    pub(crate) standing_query_listeners: Vec<StandingQueryListener>,
+    /// Logged standing query matches awaiting announcement after the next commit.
+    pub(crate) pending_standing_query_matches: Vec<crate::types::StandingQueryMatch>,
+    /// Recognized words of OCR'd frames, keyed by frame id.
+    pub(crate) ocr_layout: crate::types::OcrLayoutTrack,
+    /// Stages run on every put, set by `set_ingest_pipeline` (not persisted).
+    pub(crate) ingest_pipeline: Option<IngestPipeline>,
     /// Active replay session being recorded (if any).
     #[cfg(feature = "replay")]
     pub(crate) active_session: Option<crate::replay::ActiveSession>,
     /// Completed sessions stored in memory (until persisted to file).
     #[cfg(feature = "replay")]
     pub(crate) completed_sessions: Vec<crate::replay::ReplaySession>,
+    /// OCR engine for text-less payloads, set by `enable_ocr` (not persisted).
+    #[cfg(feature = "ocr")]
+    pub(crate) ocr_engine: Option<Arc<crate::ocr::OcrEngine>>,
 }
 
 /// Controls read-only open behaviour for `.mv2` memories.
@@ -197,8 +217,10 @@ impl Memvid {
             lex_storage,
             vec_enabled: cfg!(feature = "vec"), // Enable by default if feature is enabled
             vec_compression: VectorCompression::None,
+            vec_rescore_oversample: crate::vec_sq::DEFAULT_RESCORE_OVERSAMPLE,
             vec_model: None,
             vec_index: None,
+            ivf_index: None,
             clip_enabled: cfg!(feature = "clip"), // Enable by default if feature is enabled
             clip_index: None,
             dirty: false,
@@ -213,13 +235,20 @@ impl Memvid {
             memories_track: MemoriesTrack::new(),
             logic_mesh: LogicMesh::new(),
             sketch_track: SketchTrack::default(),
+            embedding_cache: None,
             schema_registry: SchemaRegistry::new(),
             schema_strict: false,
             batch_opts: None,
+            standing_query_listeners: Vec::new(),
+            pending_standing_query_matches: Vec::new(),
+            ocr_layout: crate::types::OcrLayoutTrack::default(),
+            ingest_pipeline: None,
             #[cfg(feature = "replay")]
             active_session: None,
             #[cfg(feature = "replay")]
             completed_sessions: Vec::new(),
+            #[cfg(feature = "ocr")]
+            ocr_engine: None,
         };
 
         #[cfg(feature = "lex")]
@@ -287,6 +316,39 @@ impl Memvid {
         &self.vec_compression
     }
 
+    /// Set how many candidates int8/binary searches re-rank against the
+    /// original f32 vectors, as a multiple of the requested limit.
+    ///
+    /// Defaults to [`crate::DEFAULT_RESCORE_OVERSAMPLE`]; 0 disables rescoring
+    /// and returns the quantized distances as-is.
+    pub fn set_vec_rescore_oversample(&mut self, oversample: usize) {
+        self.vec_rescore_oversample = oversample;
+    }
+
+    /// Int8/binary indexes are rebuilt from their retained originals on every
+    /// commit, so the mode must survive reopen or the next commit would
+    /// silently write an uncompressed index.
+    fn restore_scalar_quantization(&mut self) {
+        let persisted = self
+            .toc
+            .indexes
+            .vec
+            .as_ref()
+            .map(|manifest| &manifest.compression_mode)
+            .into_iter()
+            .chain(
+                self.toc
+                    .segment_catalog
+                    .vec_segments
+                    .iter()
+                    .map(|segment| &segment.vector_compression),
+            )
+            .find(|mode| mode.is_scalar_quantized());
+        if let Some(mode) = persisted {
+            self.vec_compression = mode.clone();
+        }
+    }
+
     /// Predict the next frame ID that would be assigned to a new insert.
     ///
     /// Frame IDs are dense indices into `toc.frames`. When a memory is mutable, inserts are first
@@ -379,8 +441,10 @@ impl Memvid {
             lex_storage,
             vec_enabled: false,
             vec_compression: VectorCompression::None,
+            vec_rescore_oversample: crate::vec_sq::DEFAULT_RESCORE_OVERSAMPLE,
             vec_model: None,
             vec_index: None,
+            ivf_index: None,
             clip_enabled: false,
             clip_index: None,
             dirty: false,
@@ -395,13 +459,20 @@ impl Memvid {
             memories_track: MemoriesTrack::new(),
             logic_mesh: LogicMesh::new(),
             sketch_track: SketchTrack::default(),
+            embedding_cache: None,
             schema_registry: SchemaRegistry::new(),
             schema_strict: false,
             batch_opts: None,
+            standing_query_listeners: Vec::new(),
+            pending_standing_query_matches: Vec::new(),
+            ocr_layout: crate::types::OcrLayoutTrack::default(),
+            ingest_pipeline: None,
             #[cfg(feature = "replay")]
             active_session: None,
             #[cfg(feature = "replay")]
             completed_sessions: Vec::new(),
+            #[cfg(feature = "ocr")]
+            ocr_engine: None,
         };
         memvid.data_end = compute_data_end(&memvid.toc, &memvid.header);
         // One-time O(n) scan to initialize cached_payload_end from existing frames
@@ -418,6 +489,7 @@ impl Memvid {
         memvid.vec_enabled =
             memvid.toc.indexes.vec.is_some() || !memvid.toc.segment_catalog.vec_segments.is_empty();
         if memvid.vec_enabled {
+            memvid.restore_scalar_quantization();
             memvid.load_vec_index_from_manifest()?;
         }
         memvid.clip_enabled = memvid.toc.indexes.clip.is_some();
@@ -433,6 +505,9 @@ impl Memvid {
         memvid.load_memories_track()?;
         memvid.load_logic_mesh()?;
         memvid.load_sketch_track()?;
+        memvid.load_embedding_cache()?;
+        memvid.load_ocr_layout()?;
+        memvid.load_ivf_index_from_manifest();
         if checksum_result.is_err() {
             memvid.toc.verify_checksum()?;
             if memvid.toc.toc_checksum != memvid.header.toc_checksum {
@@ -514,8 +589,10 @@ impl Memvid {
             lex_storage,
             vec_enabled: false,
             vec_compression: VectorCompression::None,
+            vec_rescore_oversample: crate::vec_sq::DEFAULT_RESCORE_OVERSAMPLE,
             vec_model: None,
             vec_index: None,
+            ivf_index: None,
             clip_enabled: false,
             clip_index: None,
             dirty: false,
@@ -530,13 +607,20 @@ impl Memvid {
             memories_track: MemoriesTrack::new(),
             logic_mesh: LogicMesh::new(),
             sketch_track: SketchTrack::default(),
+            embedding_cache: None,
             schema_registry: SchemaRegistry::new(),
             schema_strict: false,
             batch_opts: None,
+            standing_query_listeners: Vec::new(),
+            pending_standing_query_matches: Vec::new(),
+            ocr_layout: crate::types::OcrLayoutTrack::default(),
+            ingest_pipeline: None,
             #[cfg(feature = "replay")]
             active_session: None,
             #[cfg(feature = "replay")]
             completed_sessions: Vec::new(),
+            #[cfg(feature = "ocr")]
+            ocr_engine: None,
         };
 
         // Use consolidated helper for lex_enabled check
@@ -550,6 +634,7 @@ impl Memvid {
         memvid.vec_enabled =
             memvid.toc.indexes.vec.is_some() || !memvid.toc.segment_catalog.vec_segments.is_empty();
         if memvid.vec_enabled {
+            memvid.restore_scalar_quantization();
             memvid.load_vec_index_from_manifest()?;
         }
         memvid.clip_enabled = memvid.toc.indexes.clip.is_some();
@@ -560,6 +645,9 @@ impl Memvid {
         memvid.load_memories_track()?;
         memvid.load_logic_mesh()?;
         memvid.load_sketch_track()?;
+        memvid.load_embedding_cache()?;
+        memvid.load_ocr_layout()?;
+        memvid.load_ivf_index_from_manifest();
 
         memvid.bootstrap_segment_catalog();
         #[cfg(feature = "temporal_track")]
@@ -717,6 +805,67 @@ impl Memvid {
         Ok(())
     }
 
+    /// Load the OCR layout track from the manifest if present.
+    fn load_ocr_layout(&mut self) -> Result<()> {
+        let Some(manifest) = &self.toc.ocr_layout else {
+            return Ok(());
+        };
+
+        if manifest.bytes_length > crate::MAX_INDEX_BYTES {
+            return Err(MemvidError::InvalidToc {
+                reason: "OCR layout exceeds safety limit".into(),
+            });
+        }
+        // Safe: guarded by MAX_INDEX_BYTES check above
+        #[allow(clippy::cast_possible_truncation)]
+        let mut buf = vec![0u8; manifest.bytes_length as usize];
+        self.file
+            .seek(std::io::SeekFrom::Start(manifest.bytes_offset))?;
+        self.file.read_exact(&mut buf)?;
+
+        let actual_checksum: [u8; 32] = blake3::hash(&buf).into();
+        if actual_checksum != manifest.checksum {
+            return Err(MemvidError::InvalidToc {
+                reason: "OCR layout checksum mismatch".into(),
+            });
+        }
+
+        self.ocr_layout = crate::types::OcrLayoutTrack::deserialize(&buf)?;
+
+        Ok(())
+    }
+
+    /// Load the embedding cache track from the manifest if present.
+    fn load_embedding_cache(&mut self) -> Result<()> {
+        let manifest = match &self.toc.embedding_cache {
+            Some(m) => m,
+            None => return Ok(()),
+        };
+
+        if manifest.bytes_length > crate::MAX_INDEX_BYTES {
+            return Err(MemvidError::InvalidToc {
+                reason: "embedding cache exceeds safety limit".into(),
+            });
+        }
+        // Safe: guarded by MAX_INDEX_BYTES check above
+        #[allow(clippy::cast_possible_truncation)]
+        let mut buf = vec![0u8; manifest.bytes_length as usize];
+        self.file
+            .seek(std::io::SeekFrom::Start(manifest.bytes_offset))?;
+        self.file.read_exact(&mut buf)?;
+
+        let actual_checksum: [u8; 32] = blake3::hash(&buf).into();
+        if actual_checksum != manifest.checksum {
+            return Err(MemvidError::InvalidToc {
+                reason: "embedding cache checksum mismatch".into(),
+            });
+        }
+
+        self.embedding_cache = Some(EmbeddingCacheTrack::deserialize(&buf)?);
+
+        Ok(())
+    }
+
     #[cfg(feature = "temporal_track")]
     pub(crate) fn ensure_temporal_track_loaded(&mut self) -> Result<()> {
         if self.temporal_track.is_some() {
@@ -1191,6 +1340,14 @@ pub(crate) fn empty_toc() -> Toc {
         memory_binding: None,
         replay_manifest: None,
         enrichment_queue: crate::types::EnrichmentQueueManifest::default(),
+        embedding_cache: None,
+        search_schema: None,
+        synonyms: None,
+        percolator: None,
+        pii: None,
+        ivf_index: None,
+        percolator_delivery: None,
+        ocr_layout: None,
         merkle_root: [0u8; 32],
         toc_checksum: [0u8; 32],
     }
@@ -7,21 +7,28 @@ pub mod audit;
 pub mod builder;
 pub mod chunks;
 pub mod doctor;
+pub mod embedding_cache;
 pub mod enrichment;
 pub mod frame;
 mod helpers;
+mod ingest;
 pub mod lifecycle;
 pub mod maintenance;
 pub mod memory;
 pub mod mesh;
 pub mod mutation;
+mod ocr;
+mod percolator;
+mod pii;
 #[cfg(feature = "parallel_segments")]
 pub mod planner;
+mod records;
 #[cfg(feature = "replay")]
 pub mod replay_ops;
 pub mod search;
 mod segments;
 pub mod sketch;
+mod sync;
 pub mod ticket;
 pub mod timeline;
 #[cfg(feature = "parallel_segments")]
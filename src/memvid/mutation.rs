@@ -29,6 +29,7 @@ use zstd;
 use atomic_write_file::AtomicWriteFile;
 
 use tracing::instrument;
+use uuid::Uuid;
 
 #[cfg(feature = "parallel_segments")]
 use super::{
@@ -41,24 +42,36 @@ use crate::TemporalTrackManifest;
 use crate::analysis::auto_tag::AutoTagger;
 use crate::constants::{WAL_SIZE_LARGE, WAL_SIZE_MEDIUM};
 use crate::footer::CommitFooter;
+use crate::ingest::{INGEST_SPLIT_METADATA_KEY, IngestContext, IngestPipeline};
 use crate::io::wal::{EmbeddedWal, WalRecord};
-use crate::memvid::chunks::{plan_document_chunks, plan_text_chunks};
+use crate::memvid::chunks::{
+    DocumentChunkPlan, plan_code_chunks, plan_document_chunks, plan_text_chunks,
+    plan_transcript_chunks,
+};
+use crate::memvid::ingest::{run_chunk_stages, run_frame_stages};
 use crate::memvid::lifecycle::{Memvid, prepare_toc_bytes};
+use crate::memvid::ocr::apply_ocr_layout;
+use crate::memvid::pii::PiiRedactor;
+use crate::ocr::OCR_WORDS_METADATA_KEY;
 use crate::reader::{
-    DocumentFormat, DocumentReader, PassthroughReader, ReaderDiagnostics, ReaderHint, ReaderOutput,
-    ReaderRegistry,
+    CodeLanguage, DocumentFormat, DocumentReader, PassthroughReader, ReaderDiagnostics, ReaderHint,
+    ReaderOutput, ReaderRegistry, TranscriptCue, apply_code_metadata, apply_message_metadata,
+    apply_page_metadata, identifier_terms, looks_like_html, looks_like_subtitles, parse_subtitles,
+    transcript_audio_metadata,
 };
 #[cfg(feature = "lex")]
 use crate::search::{EmbeddedLexSegment, LexWalBatch, TantivySnapshot};
 use crate::triplet::TripletExtractor;
-#[cfg(feature = "lex")]
-use crate::types::TantivySegmentDescriptor;
 use crate::types::{
-    CanonicalEncoding, DocMetadata, Frame, FrameId, FrameRole, FrameStatus, PutManyOpts,
-    PutOptions, SegmentCommon, TextChunkManifest, Tier,
+    CanonicalEncoding, DocAudioMetadata, DocMetadata, Frame, FrameId, FrameRole, FrameStatus,
+    PII_FINDINGS_METADATA_KEY, PutManyOpts, PutOptions, SYNONYMS_METADATA_KEY, SegmentCommon,
+    SynonymTrack, TextChunkManifest, Tier,
 };
 #[cfg(feature = "parallel_segments")]
 use crate::types::{IndexSegmentRef, SegmentKind, SegmentSpan, SegmentStats};
+#[cfg(feature = "lex")]
+use crate::types::{LANGUAGE_METADATA_KEY, TantivySegmentDescriptor};
+use crate::whisper::TranscriptionResult;
 #[cfg(feature = "temporal_track")]
 use crate::{
     AnchorSource, TemporalAnchor, TemporalContext, TemporalMention, TemporalMentionFlags,
@@ -67,7 +80,8 @@ use crate::{
 };
 use crate::{
     DEFAULT_SEARCH_TEXT_LIMIT, ExtractedDocument, MemvidError, Result, TimeIndexEntry,
-    TimeIndexManifest, VecIndexManifest, normalize_text, time_index_append, wal_config,
+    TimeIndexManifest, VecIndexManifest, normalize_text, time_index_append,
+    truncate_at_grapheme_boundary, wal_config,
 };
 #[cfg(feature = "temporal_track")]
 use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
@@ -161,6 +175,8 @@ impl CommitStaging {
 #[derive(Debug, Default)]
 struct IngestionDelta {
     inserted_frames: Vec<FrameId>,
+    /// Inserted frames paired with the WAL sequence of their insert record.
+    inserted_frame_sequences: Vec<(FrameId, u64)>,
     inserted_embeddings: Vec<(FrameId, Vec<f32>)>,
     inserted_time_entries: Vec<TimeIndexEntry>,
     mutated_frames: bool,
@@ -255,6 +271,10 @@ fn infer_document_format(
             "text/plain" => Some(DocumentFormat::PlainText),
             "text/markdown" => Some(DocumentFormat::Markdown),
             "text/html" | "application/xhtml+xml" => Some(DocumentFormat::Html),
+            "application/x-ndjson" | "application/jsonl" | "application/jsonlines" => {
+                Some(DocumentFormat::Jsonl)
+            }
+            "message/rfc822" | "application/mbox" => Some(DocumentFormat::Email),
             "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => {
                 Some(DocumentFormat::Docx)
             }
@@ -265,6 +285,14 @@ fn infer_document_format(
             "application/vnd.openxmlformats-officedocument.presentationml.presentation" => {
                 Some(DocumentFormat::Pptx)
             }
+            "application/vnd.oasis.opendocument.text" => Some(DocumentFormat::Odt),
+            "application/vnd.oasis.opendocument.spreadsheet" => Some(DocumentFormat::Ods),
+            "application/vnd.oasis.opendocument.presentation" => Some(DocumentFormat::Odp),
+            "application/epub+zip" => Some(DocumentFormat::Epub),
+            "text/vtt" | "application/x-subrip" | "text/srt" | "application/x-srt" => {
+                Some(DocumentFormat::Subtitles)
+            }
+            other if CodeLanguage::from_mime(other).is_some() => Some(DocumentFormat::Code),
             other if other.starts_with("text/") => Some(DocumentFormat::PlainText),
             _ => None,
         };
@@ -273,8 +301,37 @@ fn infer_document_format(
         }
     }
 
-    // Fall back to extension-based detection
+    // Fall back to extension-based detection, then to sniffing for markup
+    // and subtitle cues
     infer_format_from_extension(uri)
+        .or_else(|| {
+            magic
+                .is_some_and(looks_like_html)
+                .then_some(DocumentFormat::Html)
+        })
+        .or_else(|| {
+            magic
+                .is_some_and(looks_like_subtitles)
+                .then_some(DocumentFormat::Subtitles)
+        })
+}
+
+/// `uri` without the `#<n>` a split payload appends to each part's URI.
+fn split_document_uri(uri: &str) -> &str {
+    uri.rsplit_once('#')
+        .filter(|(_, part)| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+        .map_or(uri, |(document, _)| document)
+}
+
+/// Refuse JSON Lines in the single-frame puts, which would merge every
+/// record into one document.
+fn reject_record_payload(payload: &[u8], options: &PutOptions) -> Result<()> {
+    let mime = options.metadata.as_ref().and_then(|m| m.mime.as_deref());
+    let magic = payload.get(..MAGIC_SNIFF_BYTES.min(payload.len()));
+    if infer_document_format(mime, magic, options.uri.as_deref()) == Some(DocumentFormat::Jsonl) {
+        return Err(MemvidError::RecordPayload);
+    }
+    Ok(())
 }
 
 /// Infer document format from file extension in URI/path
@@ -288,12 +345,20 @@ fn infer_format_from_extension(uri: Option<&str>) -> Option<DocumentFormat> {
         "xlsx" => Some(DocumentFormat::Xlsx),
         "xls" => Some(DocumentFormat::Xls),
         "pptx" => Some(DocumentFormat::Pptx),
+        "odt" => Some(DocumentFormat::Odt),
+        "ods" => Some(DocumentFormat::Ods),
+        "odp" => Some(DocumentFormat::Odp),
+        "epub" => Some(DocumentFormat::Epub),
         "txt" | "text" | "log" | "cfg" | "ini" | "json" | "yaml" | "yml" | "toml" | "csv"
-        | "tsv" | "rs" | "py" | "js" | "ts" | "tsx" | "jsx" | "c" | "h" | "cpp" | "hpp" | "go"
-        | "rb" | "php" | "css" | "scss" | "sh" | "bash" | "swift" | "kt" | "java" | "scala"
-        | "sql" => Some(DocumentFormat::PlainText),
+        | "tsv" | "php" | "css" | "scss" | "sh" | "bash" | "swift" | "kt" | "scala" | "sql" => {
+            Some(DocumentFormat::PlainText)
+        }
+        other if CodeLanguage::from_extension(other).is_some() => Some(DocumentFormat::Code),
         "md" | "markdown" => Some(DocumentFormat::Markdown),
         "html" | "htm" => Some(DocumentFormat::Html),
+        "jsonl" | "ndjson" => Some(DocumentFormat::Jsonl),
+        "eml" | "mbox" => Some(DocumentFormat::Email),
+        "srt" | "vtt" | "webvtt" => Some(DocumentFormat::Subtitles),
         _ => None,
     }
 }
@@ -702,6 +767,21 @@ impl Memvid {
                 track.bytes_offset += delta;
             }
         }
+        if let Some(cache) = self.toc.embedding_cache.as_mut() {
+            if cache.bytes_offset != 0 {
+                cache.bytes_offset += delta;
+            }
+        }
+        if let Some(ivf) = self.toc.ivf_index.as_mut() {
+            if ivf.bytes_offset != 0 {
+                ivf.bytes_offset += delta;
+            }
+        }
+        if let Some(layout) = self.toc.ocr_layout.as_mut() {
+            if layout.bytes_offset != 0 {
+                layout.bytes_offset += delta;
+            }
+        }
 
         let catalog = &mut self.toc.segment_catalog;
         for descriptor in &mut catalog.lex_segments {
@@ -743,7 +823,11 @@ impl Memvid {
         }
         let mode = options.mode;
         let records = self.wal.pending_records()?;
-        if records.is_empty() && !self.dirty && !self.tantivy_index_pending() {
+        if records.is_empty()
+            && !self.dirty
+            && !self.tantivy_index_pending()
+            && !self.embedding_cache_dirty()
+        {
             return Ok(());
         }
         self.with_staging_lock(move |mem| mem.commit_from_records(records, mode))
@@ -893,6 +977,10 @@ impl Memvid {
         self.toc.memories_track = None;
         self.toc.logic_mesh = None;
         self.toc.sketch_track = None;
+        self.toc.embedding_cache = None;
+        self.toc.ivf_index = None;
+        self.toc.ocr_layout = None;
+        self.ocr_layout.mark_dirty();
 
         self.rewrite_toc_footer()?;
         self.header.toc_checksum = self.toc.toc_checksum;
@@ -969,6 +1057,23 @@ impl Memvid {
             self.persist_sketch_track()?;
         }
 
+        // Persist embedding cache if it wasn't already persisted by rebuild_indexes
+        if !indexes_rebuilt
+            && (self.embedding_cache.is_some() || self.toc.embedding_cache.is_some())
+        {
+            self.persist_embedding_cache()?;
+        }
+
+        // Persist the OCR layout if it wasn't already persisted by rebuild_indexes
+        if !indexes_rebuilt && self.ocr_layout.is_dirty() {
+            self.persist_ocr_layout()?;
+        }
+
+        // Fold newly committed vectors into the IVF-PQ index
+        if !indexes_rebuilt && (self.ivf_index.is_some() || self.toc.ivf_index.is_some()) {
+            self.persist_ivf_index(&[])?;
+        }
+
         // flush_tantivy() and rebuild_indexes() have already set footer_offset correctly.
         // DO NOT overwrite it with catalog_data_end() as that would include orphaned segments.
 
@@ -985,7 +1090,9 @@ impl Memvid {
         }
         self.pending_frame_inserts = 0;
         self.dirty = false;
-        Ok(())
+        // Standing queries only see frames the TOC above made durable; their
+        // matches are logged with a second TOC write before listeners run.
+        self.percolate_committed(&delta.inserted_frame_sequences)
     }
 
     #[cfg(feature = "parallel_segments")]
@@ -1123,9 +1230,7 @@ impl Memvid {
                     .toc
                     .frames
                     .iter()
-                    .filter(|frame| {
-                        frame.status == FrameStatus::Active && frame.role == FrameRole::Document
-                    })
+                    .filter(|frame| frame.status == FrameStatus::Active && frame.role.is_document())
                     .map(|frame| TimeIndexEntry::new(frame.timestamp, frame.id))
                     .collect();
                 let (ti_offset, ti_length, ti_checksum) =
@@ -1182,6 +1287,21 @@ impl Memvid {
             self.persist_sketch_track()?;
         }
 
+        // Persist embedding cache if enabled (or drop a stale manifest)
+        if self.embedding_cache.is_some() || self.toc.embedding_cache.is_some() {
+            self.persist_embedding_cache()?;
+        }
+
+        // Persist the OCR layout when frames with recognized words were added
+        if self.ocr_layout.is_dirty() {
+            self.persist_ocr_layout()?;
+        }
+
+        // Fold newly committed vectors into the IVF-PQ index
+        if self.ivf_index.is_some() || self.toc.ivf_index.is_some() {
+            self.persist_ivf_index(&delta.inserted_embeddings)?;
+        }
+
         // flush_tantivy() has already set footer_offset correctly
         // DO NOT overwrite with catalog_data_end()
         self.rewrite_toc_footer()?;
@@ -1196,7 +1316,9 @@ impl Memvid {
         }
         self.pending_frame_inserts = 0;
         self.dirty = false;
-        Ok(())
+        // Standing queries only see frames the TOC above made durable; their
+        // matches are logged with a second TOC write before listeners run.
+        self.percolate_committed(&delta.inserted_frame_sequences)
     }
 
     pub(crate) fn recover_wal(&mut self) -> Result<()> {
@@ -1405,7 +1527,7 @@ impl Memvid {
                                     for &candidate_id in delta.inserted_frames.iter().rev() {
                                         if let Ok(idx) = usize::try_from(candidate_id) {
                                             if let Some(candidate) = self.toc.frames.get(idx) {
-                                                if candidate.role == FrameRole::Document
+                                                if candidate.role.is_document()
                                                     && candidate.chunk_manifest.is_some()
                                                 {
                                                     // Found a parent document - use it
@@ -1472,7 +1594,7 @@ impl Memvid {
                                 .push((frame_id, embedding.clone()));
                         }
 
-                        if entry.role == FrameRole::Document {
+                        if entry.role.is_document() {
                             delta
                                 .inserted_time_entries
                                 .push(TimeIndexEntry::new(entry.timestamp, frame_id));
@@ -1497,9 +1619,18 @@ impl Memvid {
                             self.mark_frame_superseded(predecessor, frame_id)?;
                         }
 
+                        // The word layout rides the WAL in extra metadata and
+                        // lives in the OCR layout track once applied
+                        if let Some(encoded) = frame.extra_metadata.remove(OCR_WORDS_METADATA_KEY) {
+                            self.ocr_layout.insert(frame_id, encoded);
+                        }
+
                         self.toc.frames.push(frame);
                         delta.inserted_frames.push(frame_id);
                         sequence_to_frame.insert(record.sequence, frame_id);
+                        delta
+                            .inserted_frame_sequences
+                            .push((frame_id, record.sequence));
                     }
                     FrameWalOp::Tombstone => {
                         let target = entry.target_frame_id.ok_or(MemvidError::InvalidFrame {
@@ -1530,7 +1661,7 @@ impl Memvid {
                 for candidate_id in (0..frame_id).rev() {
                     if let Ok(idx) = usize::try_from(candidate_id) {
                         if let Some(candidate) = self.toc.frames.get(idx) {
-                            if candidate.role == FrameRole::Document
+                            if candidate.role.is_document()
                                 && candidate.chunk_manifest.is_some()
                                 && candidate.status == FrameStatus::Active
                             {
@@ -2070,6 +2201,8 @@ impl Memvid {
         if self.toc.frames.is_empty() && !self.lex_enabled && !self.vec_enabled {
             return Ok(());
         }
+        // The old index region is about to be overwritten
+        self.load_file_backed_indexes()?;
 
         let payload_end = self.payload_region_end();
         self.data_end = payload_end;
@@ -2098,9 +2231,7 @@ impl Memvid {
             .toc
             .frames
             .iter()
-            .filter(|frame| {
-                frame.status == FrameStatus::Active && frame.role == FrameRole::Document
-            })
+            .filter(|frame| frame.status == FrameStatus::Active && frame.role.is_document())
             .map(|frame| TimeIndexEntry::new(frame.timestamp, frame.id))
             .collect();
         let (ti_offset, ti_length, ti_checksum) =
@@ -2332,6 +2463,11 @@ impl Memvid {
             });
         }
 
+        // Persist embedding cache if enabled
+        footer_offset = self.write_embedding_cache(footer_offset)?;
+        footer_offset = self.write_ocr_layout(footer_offset)?;
+        footer_offset = self.write_ivf_index(footer_offset, new_vec_docs)?;
+
         // This fires on every full rebuild (doctor/compaction); keep it informational to avoid noisy WARNs.
         tracing::info!(
             "rebuild_indexes: ti_offset={} ti_length={} computed_footer={} current_footer={} (before setting)",
@@ -2568,6 +2704,163 @@ impl Memvid {
         Ok(())
     }
 
+    /// Persist the embedding cache track after the current `footer_offset`.
+    fn persist_embedding_cache(&mut self) -> Result<()> {
+        self.header.footer_offset = self.write_embedding_cache(self.header.footer_offset)?;
+
+        // Ensure the file length covers the embedding cache
+        if self.file.metadata()?.len() < self.header.footer_offset {
+            self.file.set_len(self.header.footer_offset)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the embedding cache track at `offset` and update its manifest,
+    /// returning the end offset. Clears the manifest when the cache is
+    /// disabled or empty.
+    fn write_embedding_cache(&mut self, offset: u64) -> Result<u64> {
+        let Some(cache) = self.embedding_cache.as_mut() else {
+            self.toc.embedding_cache = None;
+            return Ok(offset);
+        };
+        cache.mark_clean();
+        if cache.is_empty() {
+            self.toc.embedding_cache = None;
+            return Ok(offset);
+        }
+        let entry_count = cache.len() as u64;
+        let cache_bytes = cache.serialize()?;
+        let cache_checksum: [u8; 32] = blake3::hash(&cache_bytes).into();
+
+        self.file.seek(SeekFrom::Start(offset))?;
+        self.file.write_all(&cache_bytes)?;
+
+        self.toc.embedding_cache = Some(crate::types::EmbeddingCacheManifest {
+            bytes_offset: offset,
+            bytes_length: cache_bytes.len() as u64,
+            entry_count,
+            checksum: cache_checksum,
+        });
+
+        tracing::debug!(
+            entries = entry_count,
+            offset,
+            "persisted embedding cache track"
+        );
+
+        Ok(offset + cache_bytes.len() as u64)
+    }
+
+    /// Persist the OCR layout track after the current `footer_offset`.
+    fn persist_ocr_layout(&mut self) -> Result<()> {
+        self.header.footer_offset = self.write_ocr_layout(self.header.footer_offset)?;
+
+        if self.file.metadata()?.len() < self.header.footer_offset {
+            self.file.set_len(self.header.footer_offset)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the OCR layout track at `offset` and update its manifest,
+    /// returning the end offset. Clears the manifest when no frame has
+    /// recognized words.
+    fn write_ocr_layout(&mut self, offset: u64) -> Result<u64> {
+        self.ocr_layout.mark_clean();
+        if self.ocr_layout.is_empty() {
+            self.toc.ocr_layout = None;
+            return Ok(offset);
+        }
+        let entry_count = self.ocr_layout.len() as u64;
+        let layout_bytes = self.ocr_layout.serialize()?;
+        let layout_checksum: [u8; 32] = blake3::hash(&layout_bytes).into();
+
+        self.file.seek(SeekFrom::Start(offset))?;
+        self.file.write_all(&layout_bytes)?;
+
+        self.toc.ocr_layout = Some(crate::types::OcrLayoutManifest {
+            bytes_offset: offset,
+            bytes_length: layout_bytes.len() as u64,
+            entry_count,
+            checksum: layout_checksum,
+        });
+
+        tracing::debug!(entries = entry_count, offset, "persisted OCR layout track");
+
+        Ok(offset + layout_bytes.len() as u64)
+    }
+
+    /// Persist the IVF-PQ index segment after the current `footer_offset`.
+    fn persist_ivf_index(&mut self, new_docs: &[(FrameId, Vec<f32>)]) -> Result<()> {
+        self.load_file_backed_indexes()?;
+        self.header.footer_offset = self.write_ivf_index(self.header.footer_offset, new_docs)?;
+
+        if self.file.metadata()?.len() < self.header.footer_offset {
+            self.file.set_len(self.header.footer_offset)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the IVF-PQ index at `offset` and update its manifest, returning
+    /// the end offset. Deleted frames are dropped from their lists and
+    /// vectors not yet indexed are added with the trained quantizers.
+    fn write_ivf_index(&mut self, offset: u64, new_docs: &[(FrameId, Vec<f32>)]) -> Result<u64> {
+        let Some(index) = self.ivf_index.as_ref() else {
+            self.toc.ivf_index = None;
+            return Ok(offset);
+        };
+        let dimension = index.dimension() as usize;
+        let mut builder = crate::vec_ivf::IvfPqBuilder::from_index(index, |frame_id| {
+            self.frame_is_active(frame_id)
+        })?;
+        let mut indexed = builder.frame_ids();
+        for (frame_id, embedding) in new_docs {
+            if embedding.len() == dimension
+                && self.frame_is_active(*frame_id)
+                && indexed.insert(*frame_id)
+            {
+                builder.add_document(*frame_id, embedding)?;
+            }
+        }
+        if let Some(vec_index) = self.vec_index.as_ref() {
+            for (frame_id, embedding) in vec_index.entries() {
+                if embedding.len() == dimension
+                    && self.frame_is_active(frame_id)
+                    && indexed.insert(frame_id)
+                {
+                    builder.add_document(frame_id, embedding)?;
+                }
+            }
+        }
+        let artifact = builder.finish()?;
+
+        self.file.seek(SeekFrom::Start(offset))?;
+        self.file.write_all(&artifact.bytes)?;
+
+        let length = artifact.bytes.len() as u64;
+        self.toc.ivf_index = Some(crate::types::IvfIndexManifest {
+            bytes_offset: offset,
+            bytes_length: length,
+            vector_count: artifact.vector_count,
+            dimension: artifact.dimension,
+            nlist: u32::try_from(artifact.nlist).unwrap_or(u32::MAX),
+            checksum: artifact.checksum,
+        });
+        self.ivf_index = Some(crate::vec_ivf::IvfPqIndex::from_bytes(
+            crate::vec_ivf::IvfBytes::Owned(artifact.bytes),
+        )?);
+
+        tracing::debug!(
+            vectors = self.toc.ivf_index.as_ref().map_or(0, |m| m.vector_count),
+            offset,
+            "persisted IVF-PQ index"
+        );
+
+        Ok(offset + length)
+    }
+
     #[cfg(feature = "lex")]
     fn apply_lex_wal(&mut self, batch: LexWalBatch) -> Result<()> {
         let LexWalBatch {
@@ -2655,15 +2948,14 @@ impl Memvid {
         let mut catalog_segments: Vec<TantivySegmentDescriptor> =
             Vec::with_capacity(embedded_segments.len());
         for segment in &embedded_segments {
-            let descriptor = TantivySegmentDescriptor::from_common(
-                SegmentCommon::new(
-                    next_segment_id,
-                    segment.bytes_offset,
-                    segment.bytes_length,
-                    segment.checksum,
-                ),
-                segment.path.clone(),
+            let mut common = SegmentCommon::new(
+                next_segment_id,
+                segment.bytes_offset,
+                segment.bytes_length,
+                segment.checksum,
             );
+            common.codec_version = crate::search::TANTIVY_CODEC_VERSION;
+            let descriptor = TantivySegmentDescriptor::from_common(common, segment.path.clone());
             catalog_segments.push(descriptor);
             next_segment_id = next_segment_id.saturating_add(1);
         }
@@ -2818,7 +3110,7 @@ impl Memvid {
         Ok(())
     }
 
-    fn ensure_mutation_allowed(&mut self) -> Result<()> {
+    pub(crate) fn ensure_mutation_allowed(&mut self) -> Result<()> {
         self.ensure_writable()?;
         if self.toc.ticket_ref.issuer == "free-tier" {
             return Ok(());
@@ -3087,25 +3379,43 @@ impl Memvid {
     }
 
     /// Append raw bytes as a document frame.
+    ///
+    /// JSON Lines payloads (by MIME type or `.jsonl`/`.ndjson` URI) are
+    /// rejected with [`MemvidError::RecordPayload`]; ingest them with
+    /// [`put_records`](Self::put_records) for one frame per record.
     pub fn put_bytes(&mut self, payload: &[u8]) -> Result<u64> {
-        self.put_internal(Some(payload), None, None, None, PutOptions::default(), None)
+        self.put_bytes_with_options(payload, PutOptions::default())
     }
 
     /// Append raw bytes with explicit metadata/options.
+    ///
+    /// Rejects JSON Lines payloads like [`put_bytes`](Self::put_bytes).
     pub fn put_bytes_with_options(&mut self, payload: &[u8], options: PutOptions) -> Result<u64> {
-        self.put_internal(Some(payload), None, None, None, options, None)
+        reject_record_payload(payload, &options)?;
+        self.put_internal(Some(payload), None, None, None, options, None, None)
+    }
+
+    /// Append a Whisper transcription as a transcript document, stored like an
+    /// ingested `WebVTT` file: one frame line per segment, with the segment
+    /// timings and the audio duration in the frame's audio metadata.
+    pub fn put_transcription(
+        &mut self,
+        transcription: &TranscriptionResult,
+        mut options: PutOptions,
+    ) -> Result<u64> {
+        let metadata = options.metadata.get_or_insert_with(DocMetadata::default);
+        metadata.mime.get_or_insert_with(|| "text/vtt".to_string());
+        let audio = metadata.audio.get_or_insert_with(DocAudioMetadata::default);
+        audio
+            .duration_secs
+            .get_or_insert(transcription.duration_secs);
+        let vtt = transcription.to_webvtt();
+        self.put_internal(Some(vtt.as_bytes()), None, None, None, options, None, None)
     }
 
     /// Append bytes and an existing embedding (bypasses on-device embedding).
     pub fn put_with_embedding(&mut self, payload: &[u8], embedding: Vec<f32>) -> Result<u64> {
-        self.put_internal(
-            Some(payload),
-            None,
-            Some(embedding),
-            None,
-            PutOptions::default(),
-            None,
-        )
+        self.put_with_embedding_and_options(payload, embedding, PutOptions::default())
     }
 
     pub fn put_with_embedding_and_options(
@@ -3114,7 +3424,16 @@ impl Memvid {
         embedding: Vec<f32>,
         options: PutOptions,
     ) -> Result<u64> {
-        self.put_internal(Some(payload), None, Some(embedding), None, options, None)
+        reject_record_payload(payload, &options)?;
+        self.put_internal(
+            Some(payload),
+            None,
+            Some(embedding),
+            None,
+            options,
+            None,
+            None,
+        )
     }
 
     /// Ingest a document with pre-computed embeddings for both parent and chunks.
@@ -3136,6 +3455,7 @@ impl Memvid {
         chunk_embeddings: Vec<Vec<f32>>,
         options: PutOptions,
     ) -> Result<u64> {
+        reject_record_payload(payload, &options)?;
         self.put_internal(
             Some(payload),
             None,
@@ -3143,6 +3463,7 @@ impl Memvid {
             Some(chunk_embeddings),
             options,
             None,
+            None,
         )
     }
 
@@ -3172,26 +3493,55 @@ impl Memvid {
         if options.kind.is_none() {
             options.kind = existing.kind.clone();
         }
+        // Metadata and search text describe the payload, so a new payload
+        // derives its own instead of keeping the old ones; likewise for tags
+        // and labels when the new payload is auto-tagged.
+        let keeps_payload = payload.is_none();
         if options.uri.is_none() {
-            options.uri = existing.uri.clone();
+            // A new payload replaces the whole split document, under its URI
+            let split = !keeps_payload
+                && existing
+                    .extra_metadata
+                    .contains_key(INGEST_SPLIT_METADATA_KEY);
+            options.uri = existing.uri.as_deref().map(|uri| {
+                if split {
+                    split_document_uri(uri).to_string()
+                } else {
+                    uri.to_string()
+                }
+            });
         }
         if options.title.is_none() {
             options.title = existing.title.clone();
         }
-        if options.metadata.is_none() {
+        if keeps_payload && options.metadata.is_none() {
             options.metadata = existing.metadata.clone();
         }
-        if options.search_text.is_none() {
+        if keeps_payload && options.search_text.is_none() {
             options.search_text = existing.search_text.clone();
         }
-        if options.tags.is_empty() {
+        let retags = !keeps_payload && options.auto_tag;
+        if !retags && options.tags.is_empty() {
             options.tags = existing.tags.clone();
         }
-        if options.labels.is_empty() {
+        if !retags && options.labels.is_empty() {
             options.labels = existing.labels.clone();
         }
         if options.extra_metadata.is_empty() {
             options.extra_metadata = existing.extra_metadata.clone();
+            // PII findings and the split belong to the old payload
+            if !keeps_payload {
+                options.extra_metadata.remove(PII_FINDINGS_METADATA_KEY);
+                options.extra_metadata.remove(INGEST_SPLIT_METADATA_KEY);
+            }
+        }
+        // The word layout carries over to the new frame with the payload
+        if keeps_payload && !options.extra_metadata.contains_key(OCR_WORDS_METADATA_KEY) {
+            if let Some(encoded) = self.ocr_layout.encoded(frame_id) {
+                options
+                    .extra_metadata
+                    .insert(OCR_WORDS_METADATA_KEY.to_string(), encoded.to_string());
+            }
         }
 
         let reuse_frame = if payload.is_none() {
@@ -3220,6 +3570,7 @@ impl Memvid {
             None, // No chunk embeddings for update
             options,
             Some(frame_id),
+            None,
         )?;
         info!(
             "frame_update frame_id={frame_id} seq={seq} reused_payload={reuse_flag} replaced_payload={replace_flag}"
@@ -3288,16 +3639,168 @@ impl Memvid {
 }
 
 impl Memvid {
-    fn put_internal(
+    pub(crate) fn put_internal(
         &mut self,
         payload: Option<&[u8]>,
         reuse_frame: Option<Frame>,
         embedding: Option<Vec<f32>>,
         chunk_embeddings: Option<Vec<Vec<f32>>>,
-        mut options: PutOptions,
+        options: PutOptions,
         supersedes: Option<FrameId>,
+        parent_sequence: Option<u64>,
     ) -> Result<u64> {
         self.ensure_mutation_allowed()?;
+        // Every part is staged before the first is written, so a part that
+        // fails leaves none of the others behind
+        let staged = match (payload, self.ingest_pipeline.clone()) {
+            // Payload stages see new bytes before anything reads them.
+            (Some(bytes), Some(pipeline)) => self.stage_parts(
+                &pipeline,
+                bytes,
+                reuse_frame,
+                embedding,
+                chunk_embeddings,
+                options,
+                supersedes,
+                parent_sequence,
+            )?,
+            _ => vec![self.stage_put(
+                payload,
+                reuse_frame,
+                embedding,
+                chunk_embeddings,
+                options,
+                supersedes,
+                parent_sequence,
+            )?],
+        };
+        // A new payload replaces every part the old one was split into
+        if let Some(frame_id) = supersedes.filter(|_| payload.is_some()) {
+            for sibling in self.split_siblings(frame_id) {
+                self.delete_frame(sibling)?;
+            }
+        }
+        let mut first_seq = None;
+        for put in staged {
+            let seq = self.write_staged(put)?;
+            first_seq.get_or_insert(seq);
+        }
+        Ok(first_seq.unwrap_or_default())
+    }
+
+    /// Stage each part the payload stages turn `bytes` into.
+    fn stage_parts(
+        &mut self,
+        pipeline: &IngestPipeline,
+        bytes: &[u8],
+        reuse_frame: Option<Frame>,
+        embedding: Option<Vec<f32>>,
+        chunk_embeddings: Option<Vec<Vec<f32>>>,
+        options: PutOptions,
+        supersedes: Option<FrameId>,
+        parent_sequence: Option<u64>,
+    ) -> Result<Vec<StagedPut>> {
+        let parts = pipeline.run_bytes(&IngestContext::from_options(&options), bytes.to_vec())?;
+
+        // Caller embeddings describe the whole payload, not its parts
+        let split = parts.len() > 1;
+        if split && (embedding.is_some() || chunk_embeddings.is_some()) {
+            tracing::warn!("ingest stage split the payload; dropping caller embeddings");
+        }
+        let split_id = split.then(|| Uuid::new_v4().to_string());
+        let mut embeddings = (!split).then_some((embedding, chunk_embeddings));
+        let mut staged = Vec::with_capacity(parts.len());
+        for (index, part) in parts.into_iter().enumerate() {
+            let mut part_options = options.clone();
+            part_options.uri = part.uri.or_else(|| {
+                let uri = options.uri.as_deref()?;
+                Some(if split {
+                    format!("{uri}#{}", index + 1)
+                } else {
+                    uri.to_string()
+                })
+            });
+            if part.title.is_some() {
+                part_options.title = part.title;
+            }
+            part_options.extra_metadata.extend(part.extra_metadata);
+            match &split_id {
+                Some(id) => part_options
+                    .extra_metadata
+                    .insert(INGEST_SPLIT_METADATA_KEY.to_string(), id.clone()),
+                None => part_options
+                    .extra_metadata
+                    .remove(INGEST_SPLIT_METADATA_KEY),
+            };
+            let (embedding, chunk_embeddings) = embeddings.take().unwrap_or_default();
+            staged.push(self.stage_put(
+                Some(&part.payload),
+                reuse_frame.clone(),
+                embedding,
+                chunk_embeddings,
+                part_options,
+                supersedes.filter(|_| index == 0),
+                parent_sequence,
+            )?);
+        }
+        Ok(staged)
+    }
+
+    /// Active frames other than `frame_id` split from the same payload.
+    fn split_siblings(&self, frame_id: FrameId) -> Vec<FrameId> {
+        let Some(split_id) = usize::try_from(frame_id)
+            .ok()
+            .and_then(|idx| self.toc.frames.get(idx))
+            .and_then(|frame| frame.extra_metadata.get(INGEST_SPLIT_METADATA_KEY))
+        else {
+            return Vec::new();
+        };
+        self.toc
+            .frames
+            .iter()
+            .filter(|frame| {
+                frame.id != frame_id
+                    && frame.status == FrameStatus::Active
+                    && frame.extra_metadata.get(INGEST_SPLIT_METADATA_KEY) == Some(split_id)
+            })
+            .map(|frame| frame.id)
+            .collect()
+    }
+
+    /// Run everything that can fail on a frame before any of it reaches the WAL.
+    fn stage_put(
+        &mut self,
+        payload: Option<&[u8]>,
+        reuse_frame: Option<Frame>,
+        embedding: Option<Vec<f32>>,
+        chunk_embeddings: Option<Vec<Vec<f32>>>,
+        mut options: PutOptions,
+        supersedes: Option<FrameId>,
+        parent_sequence: Option<u64>,
+    ) -> Result<StagedPut> {
+        self.ensure_mutation_allowed()?;
+        let pipeline = self.ingest_pipeline.clone();
+
+        // Redact text payloads before anything hashes, chunks or stores them
+        let mut pii = options
+            .pii
+            .take()
+            .map(|policy| self.pii_redactor(&policy))
+            .transpose()?;
+        let redacted_payload = match (pii.as_mut(), payload) {
+            (Some(redactor), Some(bytes)) => redactor.scan_bytes(bytes),
+            _ => None,
+        };
+        let payload = redacted_payload.as_deref().or(payload);
+        let mut pii_redacted = redacted_payload.is_some();
+        // Binary payloads cannot be rewritten, so a redacting policy keeps only
+        // their redacted text and the hash of the original bytes
+        if redacted_payload.is_none()
+            && pii.as_ref().is_some_and(PiiRedactor::redacts)
+            && payload.is_some_and(|bytes| std::str::from_utf8(bytes).is_err())
+        {
+            options.no_raw = true;
+        }
 
         // Deduplication: if enabled and we have payload, check if identical content exists
         if options.dedup {
@@ -3310,7 +3813,7 @@ impl Memvid {
                         "dedup: skipping ingestion, identical content already exists"
                     );
                     // Return existing frame's sequence number (which equals frame_id for committed frames)
-                    return Ok(existing_frame.id);
+                    return Ok(StagedPut::Existing(existing_frame.id));
                 }
             }
         }
@@ -3432,9 +3935,48 @@ impl Memvid {
             None
         };
 
+        // HTML, JSON Lines, email, EPUB, OpenDocument text and slides and
+        // subtitles are extracted and chunked through their readers, which
+        // also record titles, rather than as raw markup, records or MIME source.
+        let payload_format = payload_for_processing.and_then(|bytes| {
+            let mime_hint = options.metadata.as_ref().and_then(|m| m.mime.as_deref());
+            let magic = bytes.get(..MAGIC_SNIFF_BYTES.min(bytes.len()));
+            infer_document_format(mime_hint, magic, options.uri.as_deref())
+        });
+        let reader_text = matches!(
+            payload_format,
+            Some(
+                DocumentFormat::Html
+                    | DocumentFormat::Jsonl
+                    | DocumentFormat::Email
+                    | DocumentFormat::Epub
+                    | DocumentFormat::Odt
+                    | DocumentFormat::Odp
+                    | DocumentFormat::Subtitles
+            )
+        );
+
+        // Source files keep their text verbatim and are chunked per symbol.
+        let code_source = payload_for_processing
+            .filter(|_| payload_format == Some(DocumentFormat::Code))
+            .and_then(|bytes| std::str::from_utf8(bytes).ok())
+            .and_then(|text| {
+                let mime_hint = options.metadata.as_ref().and_then(|m| m.mime.as_deref());
+                CodeLanguage::detect(options.uri.as_deref(), mime_hint, text)
+                    .map(|language| (language, text))
+            });
+
+        // Subtitles are chunked on cue boundaries and keep the cue timings.
+        let transcript = payload_for_processing
+            .filter(|_| payload_format == Some(DocumentFormat::Subtitles))
+            .and_then(|bytes| parse_subtitles(&String::from_utf8_lossy(bytes)).ok());
+
         // Try to create a chunk plan from raw UTF-8 bytes first
         let raw_chunk_plan = match (payload, reuse_frame.as_ref()) {
-            (Some(bytes), None) => plan_document_chunks(bytes),
+            (Some(bytes), None) if !reader_text => match code_source {
+                Some((language, _)) => plan_code_chunks(bytes, language),
+                None => plan_document_chunks(bytes),
+            },
             _ => None,
         };
 
@@ -3507,13 +4049,15 @@ impl Memvid {
         let mut extraction_error = None;
         let mut is_skim_extraction = false; // Track if extraction was time-limited
 
-        let extracted = if run_extractor {
+        let mut extracted = if run_extractor {
             if let Some(bytes) = payload_for_processing {
                 let mime_hint = metadata.as_ref().and_then(|m| m.mime.as_deref());
                 let uri_hint = options.uri.as_deref();
 
-                // Use time-budgeted extraction for instant indexing with a budget
-                let use_budgeted = options.instant_index && options.extraction_budget_ms > 0;
+                // Use time-budgeted extraction for instant indexing with a budget.
+                // Formats with reader metadata always go through their readers.
+                let use_budgeted =
+                    options.instant_index && options.extraction_budget_ms > 0 && !reader_text;
 
                 if use_budgeted {
                     // Time-budgeted extraction for sub-second ingestion
@@ -3586,6 +4130,45 @@ impl Memvid {
             return Err(err);
         }
 
+        // Scanned PDFs and images have no text layer; read it off the pages.
+        let recognized = match (payload_for_processing, extracted.as_mut()) {
+            (Some(bytes), Some(doc))
+                if need_search_text
+                    && doc
+                        .text
+                        .as_deref()
+                        .is_none_or(|text| text.trim().is_empty()) =>
+            {
+                let recognized = self.recognize_text(bytes)?;
+                if let Some(document) = &recognized {
+                    doc.text = Some(document.text());
+                }
+                recognized
+            }
+            _ => None,
+        };
+
+        if let (Some(pipeline), Some(doc)) = (pipeline.as_ref(), extracted.as_mut()) {
+            let before = doc.text.clone();
+            pipeline.run_document(&IngestContext::from_options(&options), doc)?;
+            // Edited text is chunked afresh rather than as the raw payload
+            if doc.text != before && code_source.is_none() && transcript.is_none() {
+                chunk_plan = None;
+            }
+        }
+
+        if let (Some(redactor), Some(text)) = (
+            pii.as_mut(),
+            extracted.as_mut().and_then(|doc| doc.text.as_mut()),
+        ) {
+            if redactor.scan(text) {
+                pii_redacted = true;
+                if code_source.is_none() && transcript.is_none() {
+                    chunk_plan = None;
+                }
+            }
+        }
+
         if let Some(doc) = &extracted {
             if need_search_text {
                 if let Some(text) = &doc.text {
@@ -3600,11 +4183,28 @@ impl Memvid {
             // If we don't have a chunk plan from raw bytes (e.g., PDF), try to create one
             // from extracted text. This ensures large documents like PDFs get fully indexed.
             if chunk_plan.is_none() {
-                if let Some(text) = &doc.text {
+                if let Some(cues) = transcript.as_deref() {
+                    chunk_plan = plan_transcript_chunks(cues);
+                } else if let Some(text) = &doc.text {
                     chunk_plan = plan_text_chunks(text);
                 }
             }
 
+            match doc.mime_type.as_deref() {
+                Some(
+                    "text/html"
+                    | "application/epub+zip"
+                    | "application/vnd.oasis.opendocument.text"
+                    | "application/vnd.oasis.opendocument.presentation",
+                ) => {
+                    apply_page_metadata(&doc.metadata, &mut options.title, &mut extra_metadata);
+                }
+                Some("message/rfc822") => {
+                    apply_message_metadata(&doc.metadata, &mut options.title, &mut extra_metadata);
+                }
+                _ => {}
+            }
+
             if let Some(mime) = doc.mime_type.as_ref() {
                 if let Some(existing) = &mut metadata {
                     if existing.mime.is_none() {
@@ -3629,6 +4229,14 @@ impl Memvid {
             }
         }
 
+        if let Some((language, text)) = code_source {
+            apply_code_metadata(text, language, &mut extra_metadata);
+        }
+
+        if let Some(cues) = transcript.as_deref() {
+            apply_transcript_metadata(cues, chunk_plan.as_ref(), &mut metadata);
+        }
+
         if options.auto_tag {
             if let Some(ref text) = search_text {
                 if !text.trim().is_empty() {
@@ -3648,6 +4256,54 @@ impl Memvid {
             }
         }
 
+        let mut chunks_edited = false;
+        if let Some(pipeline) = pipeline.as_ref() {
+            if let Some(plan) = chunk_plan.as_mut() {
+                chunks_edited =
+                    run_chunk_stages(pipeline, &IngestContext::from_options(&options), plan)?;
+            }
+            run_frame_stages(
+                pipeline,
+                &mut options,
+                &mut search_text,
+                &mut tags,
+                &mut labels,
+                &mut extra_metadata,
+                &mut content_dates,
+            )?;
+        }
+
+        if let Some(redactor) = pii.as_mut() {
+            let fields = options
+                .title
+                .iter_mut()
+                .chain(search_text.iter_mut())
+                .chain(
+                    extra_metadata
+                        .iter_mut()
+                        .filter(|(key, _)| !key.starts_with("memvid."))
+                        .map(|(_, value)| value),
+                );
+            for text in fields {
+                pii_redacted |= redactor.scan(text);
+            }
+            self.enforce_pii_rejection(redactor)?;
+            if let Ok(findings) = serde_json::to_string(&redactor.findings) {
+                extra_metadata.insert(PII_FINDINGS_METADATA_KEY.to_string(), findings);
+            }
+        }
+
+        // Detect before the search text is augmented with field labels, and
+        // record after, so the language code is not itself indexed as text.
+        #[cfg(feature = "lex")]
+        let detected_language = if extra_metadata.contains_key(LANGUAGE_METADATA_KEY) {
+            None
+        } else {
+            search_text
+                .as_deref()
+                .and_then(crate::search::detect_language)
+        };
+
         let metadata_ref = metadata.as_ref();
         let mut search_text = augment_search_text(
             search_text,
@@ -3660,6 +4316,26 @@ impl Memvid {
             &content_dates,
             metadata_ref,
         );
+        #[cfg(feature = "lex")]
+        if let Some(language) = detected_language {
+            extra_metadata.insert(
+                LANGUAGE_METADATA_KEY.to_string(),
+                language.code().to_string(),
+            );
+        }
+        // Word boxes would keep the recognized values redaction or a chunk
+        // stage removed, and their offsets index the unedited text
+        if let Some(document) = recognized
+            .as_ref()
+            .filter(|_| !pii_redacted && !chunks_edited)
+        {
+            apply_ocr_layout(document, chunk_plan.as_mut(), &mut extra_metadata);
+        }
+        let index_synonyms = self
+            .toc
+            .synonyms
+            .clone()
+            .filter(|track| track.expand_at_index && !track.is_empty());
         let mut chunk_entries: Vec<WalEntryData> = Vec::new();
         let mut parent_chunk_manifest: Option<TextChunkManifest> = None;
         let mut parent_chunk_count: Option<u32> = None;
@@ -3699,7 +4375,23 @@ impl Memvid {
                     prepare_canonical_payload(chunk_text.as_bytes())?;
                 let chunk_search_text = normalize_text(chunk_text, DEFAULT_SEARCH_TEXT_LIMIT)
                     .map(|n| n.text)
-                    .filter(|text| !text.trim().is_empty());
+                    .filter(|text| !text.trim().is_empty())
+                    .map(|text| append_identifier_terms(text, code_source.is_some()));
+                let mut chunk_extra_metadata = chunk_extra_metadata.clone();
+                if let Some(symbol_metadata) = plan.chunk_metadata.get(idx) {
+                    chunk_extra_metadata.extend(symbol_metadata.clone());
+                }
+                record_index_synonyms(
+                    chunk_search_text.as_deref(),
+                    index_synonyms.as_ref(),
+                    &mut chunk_extra_metadata,
+                );
+                let mut chunk_metadata = chunk_metadata.clone();
+                if let Some(segments) = plan.chunk_segments.get(idx) {
+                    if let Some(audio) = chunk_metadata.as_mut().and_then(|m| m.audio.as_mut()) {
+                        audio.segments.clone_from(segments);
+                    }
+                }
 
                 let chunk_uri = uri_value
                     .as_ref()
@@ -3723,11 +4415,11 @@ impl Memvid {
                     title: chunk_title,
                     canonical_encoding: chunk_encoding,
                     canonical_length: chunk_length,
-                    metadata: chunk_metadata.clone(),
+                    metadata: chunk_metadata,
                     search_text: chunk_search_text,
                     tags: chunk_tags.clone(),
                     labels: chunk_labels.clone(),
-                    extra_metadata: chunk_extra_metadata.clone(),
+                    extra_metadata: chunk_extra_metadata,
                     content_dates: chunk_content_dates.clone(),
                     chunk_manifest: None,
                     role: FrameRole::DocumentChunk,
@@ -3749,9 +4441,12 @@ impl Memvid {
         let parent_uri = uri_value.clone();
         let parent_title = title_value.clone();
 
-        // Get parent_sequence from options.parent_id if provided
+        // Get parent_sequence from options.parent_id unless the caller already
+        // knows it (a parent appended earlier in the same batch)
         // We need the WAL sequence of the parent frame to link them
-        let parent_sequence = if let Some(parent_id) = options.parent_id {
+        let parent_sequence = if parent_sequence.is_some() {
+            parent_sequence
+        } else if let Some(parent_id) = options.parent_id {
             // Look up the parent frame to get its WAL sequence
             // Since frame.id corresponds to the array index, we need to find the sequence
             // For now, we'll use the frame_id + WAL_START_SEQUENCE as an approximation
@@ -3766,6 +4461,13 @@ impl Memvid {
 
         // Clone search_text for triplet extraction (before it's moved into WAL entry)
         let triplet_text = search_text.clone();
+        let search_text =
+            search_text.map(|text| append_identifier_terms(text, code_source.is_some()));
+        record_index_synonyms(
+            search_text.as_deref(),
+            index_synonyms.as_ref(),
+            &mut extra_metadata,
+        );
 
         // Capture values needed for instant indexing BEFORE they're moved into entry
         #[cfg(feature = "lex")]
@@ -3824,6 +4526,61 @@ impl Memvid {
             enrichment_state,
         };
 
+        Ok(StagedPut::Frame(Box::new(StagedFrame {
+            entry,
+            chunk_entries,
+            #[cfg(feature = "lex")]
+            options,
+            #[cfg(feature = "lex")]
+            supersedes,
+            timestamp,
+            #[cfg(feature = "lex")]
+            is_skim_extraction,
+            #[cfg(feature = "lex")]
+            needs_enrichment,
+            #[cfg(feature = "lex")]
+            instant_index_tags,
+            #[cfg(feature = "lex")]
+            instant_index_labels,
+            triplet_text,
+            triplet_uri,
+            triplet_title,
+            should_extract_triplets,
+            #[cfg(feature = "replay")]
+            replay_input: payload.map(<[u8]>::to_vec),
+        })))
+    }
+
+    /// Append a staged frame and its chunks to the WAL.
+    fn write_staged(&mut self, staged: StagedPut) -> Result<u64> {
+        let staged = match staged {
+            StagedPut::Existing(frame_id) => return Ok(frame_id),
+            StagedPut::Frame(staged) => *staged,
+        };
+        let StagedFrame {
+            entry,
+            chunk_entries,
+            #[cfg(feature = "lex")]
+            options,
+            #[cfg(feature = "lex")]
+            supersedes,
+            timestamp,
+            #[cfg(feature = "lex")]
+            is_skim_extraction,
+            #[cfg(feature = "lex")]
+            needs_enrichment,
+            #[cfg(feature = "lex")]
+            instant_index_tags,
+            #[cfg(feature = "lex")]
+            instant_index_labels,
+            triplet_text,
+            triplet_uri,
+            triplet_title,
+            should_extract_triplets,
+            #[cfg(feature = "replay")]
+            replay_input,
+        } = staged;
+
         let parent_bytes = encode_to_vec(WalEntry::Frame(entry), wal_config())?;
         let parent_seq = self.append_wal_entry(&parent_bytes)?;
         self.pending_frame_inserts = self.pending_frame_inserts.saturating_add(1);
@@ -3923,7 +4680,7 @@ impl Memvid {
 
         // Record the put action if a replay session is active
         #[cfg(feature = "replay")]
-        if let Some(input_bytes) = payload {
+        if let Some(input_bytes) = replay_input.as_deref() {
             self.record_put_action(parent_seq, input_bytes);
         }
 
@@ -3958,6 +4715,38 @@ impl Memvid {
     }
 }
 
+/// A put that passed every check and only has to be appended to the WAL.
+enum StagedPut {
+    /// Deduplicated against an existing frame; nothing to write.
+    Existing(FrameId),
+    Frame(Box<StagedFrame>),
+}
+
+/// WAL entries of a staged frame and what writing them needs besides.
+struct StagedFrame {
+    entry: WalEntryData,
+    chunk_entries: Vec<WalEntryData>,
+    #[cfg(feature = "lex")]
+    options: PutOptions,
+    #[cfg(feature = "lex")]
+    supersedes: Option<FrameId>,
+    timestamp: i64,
+    #[cfg(feature = "lex")]
+    is_skim_extraction: bool,
+    #[cfg(feature = "lex")]
+    needs_enrichment: bool,
+    #[cfg(feature = "lex")]
+    instant_index_tags: Vec<String>,
+    #[cfg(feature = "lex")]
+    instant_index_labels: Vec<String>,
+    triplet_text: Option<String>,
+    triplet_uri: Option<String>,
+    triplet_title: Option<String>,
+    should_extract_triplets: bool,
+    #[cfg(feature = "replay")]
+    replay_input: Option<Vec<u8>>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub(crate) enum FrameWalOp {
@@ -4123,7 +4912,12 @@ pub(crate) fn augment_search_text(
 
     if !extra_metadata.is_empty() {
         for (key, value) in extra_metadata {
-            if value.trim().is_empty() {
+            // The recognized word layout repeats the search text as JSON, and
+            // the PII findings record is bookkeeping rather than content
+            if value.trim().is_empty()
+                || key == OCR_WORDS_METADATA_KEY
+                || key == PII_FINDINGS_METADATA_KEY
+            {
                 continue;
             }
             segments.push(format!("{key}: {value}"));
@@ -4147,6 +4941,64 @@ pub(crate) fn augment_search_text(
     }
 }
 
+/// Record synonyms of terms in `text` under [`SYNONYMS_METADATA_KEY`] when
+/// the memory expands synonyms at index time. They are indexed and evaluated
+/// with the frame but kept out of its text, so snippets never show them.
+fn record_index_synonyms(
+    text: Option<&str>,
+    track: Option<&SynonymTrack>,
+    extra_metadata: &mut BTreeMap<String, String>,
+) {
+    let terms = match (text, track) {
+        (Some(text), Some(track)) => track.index_terms(text),
+        _ => Vec::new(),
+    };
+    if !terms.is_empty() {
+        extra_metadata.insert(SYNONYMS_METADATA_KEY.to_string(), terms.join(" | "));
+    }
+}
+
+/// Append the words of compound identifiers in source code, so `parse_query`
+/// and `parseQuery` are found by a search for "parse query". The result stays
+/// within [`DEFAULT_SEARCH_TEXT_LIMIT`]; terms past it are dropped.
+fn append_identifier_terms(text: String, code: bool) -> String {
+    let terms = if code {
+        identifier_terms(&text)
+    } else {
+        Vec::new()
+    };
+    if terms.is_empty() {
+        text
+    } else {
+        let mut text = format!("{text}\nidentifiers: {}", terms.join(" | "));
+        text.truncate(truncate_at_grapheme_boundary(
+            &text,
+            DEFAULT_SEARCH_TEXT_LIMIT,
+        ));
+        text
+    }
+}
+
+/// Record a transcript's cue timings as audio segments, one per line of the
+/// frame's text. A chunked transcript is searched by its first chunk, so the
+/// parent carries that chunk's segments; the duration covers every cue.
+fn apply_transcript_metadata(
+    cues: &[TranscriptCue],
+    plan: Option<&DocumentChunkPlan>,
+    metadata: &mut Option<DocMetadata>,
+) {
+    let mut transcript = transcript_audio_metadata(cues);
+    if let Some(first) = plan.and_then(|plan| plan.chunk_segments.first()) {
+        transcript.segments.clone_from(first);
+    }
+    let audio = metadata
+        .get_or_insert_with(DocMetadata::default)
+        .audio
+        .get_or_insert_with(DocAudioMetadata::default);
+    audio.duration_secs = audio.duration_secs.or(transcript.duration_secs);
+    audio.segments = transcript.segments;
+}
+
 pub(crate) fn merge_unique(target: &mut Vec<String>, additions: Vec<String>) {
     if additions.is_empty() {
         return;
@@ -4163,3 +5015,24 @@ pub(crate) fn merge_unique(target: &mut Vec<String>, additions: Vec<String>) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifier_terms_stay_within_search_text_limit() {
+        let line = "let parseQuery = build_index_writer(fooBar);\n";
+        let text = line.repeat(DEFAULT_SEARCH_TEXT_LIMIT / line.len());
+        let with_terms = append_identifier_terms(text.clone(), true);
+        assert!(with_terms.len() <= DEFAULT_SEARCH_TEXT_LIMIT);
+        assert!(with_terms.starts_with(&text));
+
+        let short = append_identifier_terms("fn parseQuery() {}".to_string(), true);
+        assert!(short.ends_with("identifiers: parse query"));
+        assert_eq!(
+            append_identifier_terms("parseQuery".to_string(), false),
+            "parseQuery"
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! OCR fallback for payloads without a text layer.
+//!
+//! Once an engine is enabled, `put` recognizes scanned PDFs and images whose
+//! readers return no text. The engine is not persisted; it has to be enabled
+//! again after open.
+
+use std::collections::BTreeMap;
+
+use crate::error::Result;
+use crate::ocr::{OCR_WORDS_METADATA_KEY, OcrDocument, OcrWord, encode_ocr_words};
+#[cfg(feature = "ocr")]
+use crate::ocr::{OcrConfig, OcrEngine};
+
+use super::Memvid;
+use super::chunks::DocumentChunkPlan;
+
+impl Memvid {
+    /// Load the configured OCR models and recognize text-less payloads on
+    /// subsequent puts.
+    #[cfg(feature = "ocr")]
+    pub fn enable_ocr(&mut self, config: OcrConfig) -> Result<()> {
+        self.ocr_engine = Some(std::sync::Arc::new(OcrEngine::load(config)?));
+        Ok(())
+    }
+
+    /// Stop recognizing text-less payloads.
+    #[cfg(feature = "ocr")]
+    pub fn disable_ocr(&mut self) {
+        self.ocr_engine = None;
+    }
+
+    /// Recognized text of `bytes`, or `None` without an OCR engine, for
+    /// payloads that are neither PDFs nor images, and when nothing was read.
+    pub(crate) fn recognize_text(&self, bytes: &[u8]) -> Result<Option<OcrDocument>> {
+        #[cfg(feature = "ocr")]
+        if let Some(engine) = self.ocr_engine.as_ref() {
+            return Ok(engine
+                .recognize_bytes(bytes)?
+                .filter(|document| !document.is_empty()));
+        }
+        let _ = bytes;
+        Ok(None)
+    }
+}
+
+/// Attach the recognized words of a document to the put's `extra_metadata`,
+/// from where commit moves them into the OCR layout track: each chunk gets
+/// the words that start within its range, and the parent gets the first
+/// chunk's words, or every word when the document is not chunked.
+pub(crate) fn apply_ocr_layout(
+    document: &OcrDocument,
+    plan: Option<&mut DocumentChunkPlan>,
+    extra_metadata: &mut BTreeMap<String, String>,
+) {
+    let Some(plan) = plan else {
+        let words: Vec<OcrWord> = document.words().cloned().collect();
+        extra_metadata.insert(OCR_WORDS_METADATA_KEY.to_string(), encode_ocr_words(&words));
+        return;
+    };
+
+    let offsets = document.word_offsets();
+
+    plan.chunk_metadata
+        .resize_with(plan.chunks.len(), BTreeMap::new);
+    for (range, chunk_metadata) in plan.manifest.chunks.iter().zip(&mut plan.chunk_metadata) {
+        let words: Vec<OcrWord> = offsets
+            .iter()
+            .filter(|(start, _)| (range.start..range.end).contains(start))
+            .map(|(_, word)| (*word).clone())
+            .collect();
+        chunk_metadata.insert(OCR_WORDS_METADATA_KEY.to_string(), encode_ocr_words(&words));
+    }
+    if let Some(first) = plan
+        .chunk_metadata
+        .first()
+        .and_then(|metadata| metadata.get(OCR_WORDS_METADATA_KEY))
+    {
+        extra_metadata.insert(OCR_WORDS_METADATA_KEY.to_string(), first.clone());
+    }
+}
@@ -0,0 +1,259 @@
+//! Standing queries (a percolator): saved queries checked against each newly
+//! committed frame instead of polled with `search`.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::search::{EvaluationContext, ParsedQuery, parse_query};
+use crate::types::{FrameId, FrameStatus, PercolatorDelivery, StandingQuery, StandingQueryMatch};
+
+/// Callback invoked for every standing query match, once it is durable.
+pub(crate) type StandingQueryListener = Box<dyn Fn(&StandingQueryMatch) + Send + Sync>;
+
+impl Memvid {
+    /// Registered standing queries.
+    #[must_use]
+    pub fn standing_queries(&self) -> Vec<StandingQuery> {
+        self.toc
+            .percolator
+            .as_ref()
+            .map(|track| track.queries.clone())
+            .unwrap_or_default()
+    }
+
+    /// Register `query`, replacing any standing query with the same id.
+    /// Returns whether one was replaced. Only frames committed afterwards are
+    /// checked. Persisted on the next commit.
+    pub fn register_standing_query(&mut self, query: StandingQuery) -> Result<bool> {
+        self.ensure_writable()?;
+        if !query.query.trim().is_empty() {
+            parse_query(&query.query)?;
+        }
+        let replaced = self
+            .toc
+            .percolator
+            .get_or_insert_with(Default::default)
+            .register(query)?;
+        self.dirty = true;
+        Ok(replaced)
+    }
+
+    /// Remove the standing query `id`, its logged matches and its delivered
+    /// markers. Returns whether it existed.
+    pub fn remove_standing_query(&mut self, id: &str) -> Result<bool> {
+        self.ensure_writable()?;
+        let Some(track) = self.toc.percolator.as_mut() else {
+            return Ok(false);
+        };
+        let removed = track.remove(id);
+        if track.is_empty() {
+            self.toc.percolator = None;
+        }
+        if removed {
+            if let Some(delivery) = self.toc.percolator_delivery.as_mut() {
+                delivery.forget(id);
+            }
+        }
+        self.dirty |= removed;
+        Ok(removed)
+    }
+
+    /// Logged matches recorded after match sequence `after_sequence`, oldest
+    /// first. Pass `0` for the whole log.
+    #[must_use]
+    pub fn standing_query_matches(&self, after_sequence: u64) -> Vec<StandingQueryMatch> {
+        self.toc
+            .percolator
+            .as_ref()
+            .map(|track| track.matches_after(after_sequence))
+            .unwrap_or_default()
+    }
+
+    /// Drop logged matches up to and including `sequence` once they have been
+    /// handled. Returns how many were dropped. The frames stay marked as
+    /// delivered, so they never match the same query again.
+    pub fn acknowledge_standing_query_matches(&mut self, sequence: u64) -> Result<usize> {
+        self.ensure_writable()?;
+        let dropped = self
+            .toc
+            .percolator
+            .as_mut()
+            .map_or(0, |track| track.acknowledge(sequence));
+        self.dirty |= dropped > 0;
+        Ok(dropped)
+    }
+
+    /// Call `listener` for every new match while this handle is open, once
+    /// the match is durable in the log. Listeners are not persisted; the match
+    /// log is.
+    pub fn on_standing_query_match<F>(&mut self, listener: F)
+    where
+        F: Fn(&StandingQueryMatch) + Send + Sync + 'static,
+    {
+        self.standing_query_listeners.push(Box::new(listener));
+    }
+
+    /// Check `frames` (frame id and WAL sequence) of a commit whose TOC is
+    /// already durable. New matches are persisted with a second TOC write;
+    /// then listeners hear of every match logged since the previous commit,
+    /// including those found during enrichment.
+    pub(crate) fn percolate_committed(&mut self, frames: &[(FrameId, u64)]) -> Result<()> {
+        if self.percolate_frames(frames, None) {
+            self.rewrite_toc_footer()?;
+            self.header.toc_checksum = self.toc.toc_checksum;
+            crate::persist_header(&mut self.file, &self.header)?;
+            self.file.sync_all()?;
+            self.dirty = false;
+        }
+        let announced = std::mem::take(&mut self.pending_standing_query_matches);
+        for entry in &announced {
+            for listener in &self.standing_query_listeners {
+                listener(entry);
+            }
+        }
+        Ok(())
+    }
+
+    /// Re-check `frame_id` against its enriched `text`, which only Tantivy
+    /// holds; the frame's stored search text is still the skim. Matches are
+    /// persisted and announced by the next commit.
+    pub(crate) fn percolate_enriched(&mut self, frame_id: FrameId, text: &str) {
+        let sequence = self.header.wal_sequence;
+        self.percolate_frames(&[(frame_id, sequence)], Some(text));
+    }
+
+    /// Log the matches among `frames` not delivered before and queue them
+    /// for announcement. Each frame is read once and tested against every
+    /// query; a query that fails to parse or a frame that fails to load is
+    /// skipped with a warning. Returns whether anything was logged.
+    fn percolate_frames(&mut self, frames: &[(FrameId, u64)], enriched_text: Option<&str>) -> bool {
+        let queries = match self.toc.percolator.as_ref() {
+            Some(track) if !track.queries.is_empty() && !frames.is_empty() => track.queries.clone(),
+            _ => return false,
+        };
+        let parsed: Vec<(&StandingQuery, Option<ParsedQuery>)> = queries
+            .iter()
+            .filter_map(|query| {
+                if query.query.trim().is_empty() {
+                    return Some((query, None));
+                }
+                match self.parse_standing_query(&query.query) {
+                    Ok(parsed) => Some((query, Some(parsed))),
+                    Err(err) => {
+                        tracing::warn!(query_id = %query.id, ?err, "standing query skipped");
+                        None
+                    }
+                }
+            })
+            .collect();
+        let needs_text = parsed.iter().any(|(_, parsed)| parsed.is_some());
+        let matched_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX));
+
+        let mut found = Vec::new();
+        for &(frame_id, wal_sequence) in frames {
+            let Some(frame) = usize::try_from(frame_id)
+                .ok()
+                .and_then(|idx| self.toc.frames.get(idx))
+                .filter(|frame| frame.status == FrameStatus::Active)
+                .cloned()
+            else {
+                continue;
+            };
+            let content_lower = match (needs_text, enriched_text) {
+                (false, _) => String::new(),
+                (true, Some(text)) => text.to_ascii_lowercase(),
+                (true, None) => match self.frame_search_text(&frame) {
+                    Ok(text) => text.to_ascii_lowercase(),
+                    Err(err) => {
+                        tracing::warn!(frame_id, ?err, "standing query check skipped frame");
+                        continue;
+                    }
+                },
+            };
+            let ctx = EvaluationContext {
+                frame: &frame,
+                content_lower: &content_lower,
+            };
+            let mut embedding = None;
+            for (query, parsed) in &parsed {
+                if parsed.as_ref().is_some_and(|parsed| !parsed.evaluate(&ctx)) {
+                    continue;
+                }
+                let similarity = match (&query.embedding, query.min_similarity) {
+                    (Some(vector), Some(threshold)) => {
+                        if embedding.is_none() {
+                            embedding =
+                                Some(self.frame_embedding(frame_id).unwrap_or_else(|err| {
+                                    tracing::warn!(frame_id, ?err, "frame embedding unavailable");
+                                    None
+                                }));
+                        }
+                        let Some(Some(frame_embedding)) = embedding.as_ref() else {
+                            continue;
+                        };
+                        let similarity = cosine_similarity(vector, frame_embedding);
+                        if similarity < threshold {
+                            continue;
+                        }
+                        Some(similarity)
+                    }
+                    _ => None,
+                };
+                found.push(StandingQueryMatch {
+                    query_id: query.id.clone(),
+                    frame_id,
+                    wal_sequence,
+                    similarity,
+                    matched_at,
+                });
+            }
+        }
+
+        let Some(track) = self.toc.percolator.as_mut() else {
+            return false;
+        };
+        let delivery = self
+            .toc
+            .percolator_delivery
+            .get_or_insert_with(|| PercolatorDelivery::from_log(track));
+        found.retain(|entry| delivery.mark(&entry.query_id, entry.frame_id));
+        if found.is_empty() {
+            return false;
+        }
+        for entry in &mut found {
+            entry.wal_sequence = delivery.next_sequence(entry.wal_sequence);
+            track.record(entry.clone());
+        }
+        self.pending_standing_query_matches.extend(found);
+        self.dirty = true;
+        true
+    }
+
+    /// Parse a standing query with synonyms applied as in `search`.
+    fn parse_standing_query(&self, query: &str) -> Result<ParsedQuery> {
+        #[cfg_attr(not(feature = "lex"), allow(unused_mut))]
+        let mut parsed = parse_query(query)?;
+        #[cfg(feature = "lex")]
+        if let Some(track) = self.toc.synonyms.as_ref() {
+            parsed.expand_synonyms(track);
+        }
+        Ok(parsed)
+    }
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
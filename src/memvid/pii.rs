@@ -0,0 +1,151 @@
+//! Ingest-time PII redaction and the report of what it found.
+
+use std::collections::BTreeMap;
+
+use uuid::Uuid;
+
+use crate::error::{MemvidError, Result};
+use crate::pii::{PiiDetector, redact_matches};
+use crate::types::{
+    FrameStatus, PII_FINDINGS_METADATA_KEY, PiiFindings, PiiFrameReport, PiiMode, PiiPolicy,
+    PiiReport, PiiStats, PiiTrack,
+};
+
+use super::Memvid;
+
+/// Applies one put's policy to each text of the document and tallies what
+/// it found across them.
+pub(crate) struct PiiRedactor {
+    detector: PiiDetector,
+    salt: Option<[u8; 32]>,
+    pub(crate) findings: PiiFindings,
+}
+
+impl PiiRedactor {
+    /// Redact `text` in place, returning whether anything was found. Text is
+    /// masked under [`PiiMode::Reject`] too, so that the texts derived from it
+    /// later in the put do not report the same values again.
+    pub(crate) fn scan(&mut self, text: &mut String) -> bool {
+        let matches = self.detector.detect(text);
+        for found in &matches {
+            self.findings.record(&found.kind);
+        }
+        if matches.is_empty() {
+            return false;
+        }
+        *text = redact_matches(text, &matches, self.salt.as_ref());
+        true
+    }
+
+    /// Whether the policy stores redacted values rather than refusing them.
+    pub(crate) fn redacts(&self) -> bool {
+        self.findings.mode != PiiMode::Reject
+    }
+
+    /// Redact UTF-8 `bytes`, returning the redacted copy when they changed.
+    /// Binary payloads are left to the scan of their extracted text.
+    pub(crate) fn scan_bytes(&mut self, bytes: &[u8]) -> Option<Vec<u8>> {
+        let mut text = std::str::from_utf8(bytes).ok()?.to_string();
+        self.scan(&mut text).then(|| text.into_bytes())
+    }
+}
+
+impl Memvid {
+    /// PII totals over active frames, or `None` when no frame was ingested
+    /// under a policy and no put was refused.
+    #[must_use]
+    pub fn pii_stats(&self) -> Option<PiiStats> {
+        let report = self.pii_report();
+        (report.stats != PiiStats::default()).then_some(report.stats)
+    }
+
+    /// What ingest-time PII policies found in active frames and in refused
+    /// puts. Chunk frames share their document's record and are not counted.
+    #[must_use]
+    pub fn pii_report(&self) -> PiiReport {
+        let mut report = PiiReport::default();
+        for frame in self
+            .toc
+            .frames
+            .iter()
+            .filter(|frame| frame.status == FrameStatus::Active && frame.role.is_document())
+        {
+            let Some(findings) = frame
+                .extra_metadata
+                .get(PII_FINDINGS_METADATA_KEY)
+                .and_then(|json| serde_json::from_str::<PiiFindings>(json).ok())
+            else {
+                continue;
+            };
+            report.stats.frames_scanned += 1;
+            if findings.counts.is_empty() {
+                continue;
+            }
+            report.stats.frames_with_findings += 1;
+            report.stats.redacted += findings.total();
+            for (kind, count) in &findings.counts {
+                *report.redacted_by_kind.entry(kind.clone()).or_default() += count;
+            }
+            report.frames.push(PiiFrameReport {
+                frame_id: frame.id,
+                uri: frame.uri.clone(),
+                findings,
+            });
+        }
+        if let Some(track) = &self.toc.pii {
+            report.stats.rejected_puts = track.rejected_puts;
+            report.rejected_by_kind.clone_from(&track.rejected_findings);
+        }
+        report
+    }
+
+    /// A redactor for one put under `policy`. Hashing creates the file's
+    /// salt on first use; it is persisted on the next commit.
+    pub(crate) fn pii_redactor(&mut self, policy: &PiiPolicy) -> Result<PiiRedactor> {
+        let detector = PiiDetector::new(&policy.custom_patterns)?;
+        let salt = (policy.mode == PiiMode::Hash).then(|| self.pii_track().salt);
+        Ok(PiiRedactor {
+            detector,
+            salt,
+            findings: PiiFindings {
+                mode: policy.mode,
+                counts: BTreeMap::new(),
+            },
+        })
+    }
+
+    /// Fail the put when a rejecting policy found something, recording the
+    /// refusal for [`pii_report`](Self::pii_report).
+    pub(crate) fn enforce_pii_rejection(&mut self, redactor: &PiiRedactor) -> Result<()> {
+        let findings = &redactor.findings;
+        if findings.mode != PiiMode::Reject || findings.counts.is_empty() {
+            return Ok(());
+        }
+        let track = self.pii_track();
+        track.rejected_puts += 1;
+        for (kind, count) in &findings.counts {
+            *track.rejected_findings.entry(kind.clone()).or_default() += count;
+        }
+        self.dirty = true;
+        let kinds: Vec<&str> = findings.counts.keys().map(String::as_str).collect();
+        Err(MemvidError::IngestRejected {
+            stage: "pii".to_string(),
+            reason: format!("found {}", kinds.join(", ")),
+        })
+    }
+
+    fn pii_track(&mut self) -> &mut PiiTrack {
+        if self.toc.pii.is_none() {
+            self.dirty = true;
+        }
+        self.toc.pii.get_or_insert_with(|| {
+            let mut salt = [0u8; 32];
+            salt[..16].copy_from_slice(Uuid::new_v4().as_bytes());
+            salt[16..].copy_from_slice(Uuid::new_v4().as_bytes());
+            PiiTrack {
+                salt,
+                ..PiiTrack::default()
+            }
+        })
+    }
+}
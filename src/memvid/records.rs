@@ -0,0 +1,249 @@
+//! Record ingestion: one frame per JSONL line, chat message or email.
+
+use std::collections::HashMap;
+
+use crate::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::reader::{
+    AUTHOR_METADATA_KEY, EMAIL_MESSAGE_ID_METADATA_KEY, EmailMessage, MappedRecord,
+    RECORD_ID_METADATA_KEY, RecordMapping, THREAD_METADATA_KEY,
+};
+use crate::types::{DocMetadata, FrameRole, PutManyOpts, PutOptions};
+
+const DEFAULT_RECORDS_URI: &str = "mv2://records";
+const DEFAULT_EMAIL_URI: &str = "mv2://email";
+
+impl Memvid {
+    /// Ingest every record in `bytes` (JSON Lines, or a JSON array or export
+    /// object) as its own frame, mapped through `mapping`.
+    ///
+    /// `options` is the template for each frame. Record URIs are
+    /// `{options.uri}/{thread}/{id}` (or `{options.uri}/{id}` outside a
+    /// thread), falling back to `mv2://records` and the 1-based record
+    /// position. Records run through batch mode, so nothing is indexed until
+    /// the next commit; each later record of a thread gets the thread's first
+    /// record in this call as `parent_id`. Returns the WAL sequence of each
+    /// frame, in record order.
+    pub fn put_records(
+        &mut self,
+        bytes: &[u8],
+        mapping: &RecordMapping,
+        options: PutOptions,
+    ) -> Result<Vec<u64>> {
+        self.ensure_mutation_allowed()?;
+        let records = mapping.parse(bytes)?;
+        self.in_record_batch(|memvid| memvid.put_mapped_records(records, &options))
+    }
+
+    /// Ingest every message of an `.eml` file or mbox mailbox as its own
+    /// frame, and each attachment as an [`FrameRole::Attachment`] child of
+    /// its message.
+    ///
+    /// `options` is the template for each message. Message URIs are
+    /// `{options.uri}/{message-id}` (default `mv2://email`, with the 1-based
+    /// message position when there is no `Message-ID`); the subject becomes
+    /// the title, the `Date` header the timestamp, and the headers the
+    /// `memvid.email.*` extra metadata. Messages are ingested oldest first,
+    /// and a reply gets the message named by its `In-Reply-To` or
+    /// `References` as `parent_id` when that message is in the same call.
+    /// Attachments keep their MIME type and are extracted through the reader
+    /// registry like any other payload. As with
+    /// [`put_records`](Self::put_records), frames are indexed on the next
+    /// commit. Returns the WAL sequence of each message frame, in mailbox
+    /// order.
+    pub fn put_emails(&mut self, bytes: &[u8], options: PutOptions) -> Result<Vec<u64>> {
+        self.ensure_mutation_allowed()?;
+        let messages = EmailMessage::parse_mailbox(bytes)?;
+        self.in_record_batch(|memvid| memvid.put_email_messages(messages, &options))
+    }
+
+    /// Run `put` in batch mode, opening and closing the batch unless the
+    /// caller already has one open.
+    pub(super) fn in_record_batch<T>(
+        &mut self,
+        put: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        let owns_batch = self.batch_opts.is_none();
+        if owns_batch {
+            self.begin_batch(PutManyOpts {
+                no_raw: false,
+                ..PutManyOpts::default()
+            })?;
+        }
+        let result = put(self);
+        if owns_batch {
+            self.end_batch()?;
+        }
+        result
+    }
+
+    fn put_mapped_records(
+        &mut self,
+        records: Vec<MappedRecord>,
+        template: &PutOptions,
+    ) -> Result<Vec<u64>> {
+        let base = base_uri(template, DEFAULT_RECORDS_URI);
+        let mut thread_roots: HashMap<String, u64> = HashMap::new();
+        let mut sequences = Vec::with_capacity(records.len());
+
+        for (idx, record) in records.into_iter().enumerate() {
+            let mut options = template.clone();
+            let id = record.id.clone().unwrap_or_else(|| (idx + 1).to_string());
+            options.uri = Some(match record.thread.as_deref() {
+                Some(thread) => format!("{base}/{}/{}", uri_segment(thread), uri_segment(&id)),
+                None => format!("{base}/{}", uri_segment(&id)),
+            });
+            if record.timestamp.is_some() {
+                options.timestamp = record.timestamp;
+            }
+            if record.title.is_some() {
+                options.title.clone_from(&record.title);
+            }
+            // Batched frames become searchable on commit.
+            options.instant_index = false;
+            for (key, value) in [
+                (AUTHOR_METADATA_KEY, record.author.as_ref()),
+                (THREAD_METADATA_KEY, record.thread.as_ref()),
+                (RECORD_ID_METADATA_KEY, record.id.as_ref()),
+            ] {
+                if let Some(value) = value {
+                    options
+                        .extra_metadata
+                        .insert(key.to_string(), value.clone());
+                }
+            }
+
+            let parent_sequence = record
+                .thread
+                .as_ref()
+                .and_then(|thread| thread_roots.get(thread).copied());
+            let seq = self.put_internal(
+                Some(record.text.as_bytes()),
+                None,
+                None,
+                None,
+                options,
+                None,
+                parent_sequence,
+            )?;
+            if let Some(thread) = record.thread {
+                thread_roots.entry(thread).or_insert(seq);
+            }
+            sequences.push(seq);
+        }
+        Ok(sequences)
+    }
+
+    fn put_email_messages(
+        &mut self,
+        messages: Vec<EmailMessage>,
+        template: &PutOptions,
+    ) -> Result<Vec<u64>> {
+        let base = base_uri(template, DEFAULT_EMAIL_URI);
+        let mut order: Vec<usize> = (0..messages.len()).collect();
+        order.sort_by_key(|&idx| messages[idx].timestamp.unwrap_or(i64::MIN));
+        let mut by_message_id: HashMap<&str, u64> = HashMap::new();
+        let mut sequences = vec![0; messages.len()];
+
+        for idx in order {
+            let message = &messages[idx];
+            let mut options = template.clone();
+            let id = message
+                .message_id
+                .clone()
+                .unwrap_or_else(|| (idx + 1).to_string());
+            let uri = format!("{base}/{}", uri_segment(&id));
+            options.uri = Some(uri.clone());
+            if message.timestamp.is_some() {
+                options.timestamp = message.timestamp;
+            }
+            if message.subject.is_some() {
+                options.title.clone_from(&message.subject);
+            }
+            options.instant_index = false;
+            options.extra_metadata.extend(message.metadata());
+
+            let text = if message.body.trim().is_empty() {
+                message.subject.clone().unwrap_or_default()
+            } else {
+                message.body.clone()
+            };
+            let parent_sequence = message
+                .reply_targets()
+                .find_map(|target| by_message_id.get(target).copied());
+            let seq = self.put_internal(
+                Some(text.as_bytes()),
+                None,
+                None,
+                None,
+                options,
+                None,
+                parent_sequence,
+            )?;
+            if let Some(message_id) = message.message_id.as_deref() {
+                by_message_id.entry(message_id).or_insert(seq);
+            }
+            sequences[idx] = seq;
+
+            for (position, attachment) in message.attachments.iter().enumerate() {
+                let name = attachment
+                    .filename
+                    .clone()
+                    .unwrap_or_else(|| format!("attachment-{}", position + 1));
+                let mut options = template.clone();
+                options.uri = Some(format!("{uri}/attachments/{}", uri_segment(&name)));
+                options.title = Some(name);
+                options.role = FrameRole::Attachment;
+                options.metadata = Some(DocMetadata {
+                    mime: Some(attachment.content_type.clone()),
+                    ..DocMetadata::default()
+                });
+                if message.timestamp.is_some() {
+                    options.timestamp = message.timestamp;
+                }
+                options.instant_index = false;
+                if let Some(message_id) = &message.message_id {
+                    options.extra_metadata.insert(
+                        EMAIL_MESSAGE_ID_METADATA_KEY.to_string(),
+                        message_id.clone(),
+                    );
+                }
+                self.put_internal(
+                    Some(&attachment.data),
+                    None,
+                    None,
+                    None,
+                    options,
+                    None,
+                    Some(seq),
+                )?;
+            }
+        }
+        Ok(sequences)
+    }
+}
+
+/// The template URI, or `default`, without a trailing slash.
+fn base_uri(template: &PutOptions, default: &str) -> String {
+    template
+        .uri
+        .as_deref()
+        .unwrap_or(default)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// `value` with characters that would split or break a URI path replaced.
+pub(super) fn uri_segment(value: &str) -> String {
+    value
+        .trim()
+        .chars()
+        .map(|ch| {
+            if ch.is_whitespace() || matches!(ch, '/' | '?' | '#' | '\\') {
+                '-'
+            } else {
+                ch
+            }
+        })
+        .collect()
+}
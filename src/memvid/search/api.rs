@@ -1,5 +1,5 @@
 #[cfg(feature = "lex")]
-use crate::search::{EmbeddedLexSegment, TantivyEngine};
+use crate::search::{EmbeddedLexSegment, TANTIVY_CODEC_VERSION, TantivyEngine};
 #[cfg(feature = "lex")]
 use std::fs::{self, File};
 #[cfg(feature = "lex")]
@@ -127,9 +127,32 @@ impl Memvid {
     }
 
     pub fn search_vec(&mut self, query: &[f32], limit: usize) -> Result<Vec<VecSearchHit>> {
+        self.search_vec_with_nprobe(query, limit, crate::vec_ivf::DEFAULT_NPROBE)
+    }
+
+    /// Like [`search_vec`](Self::search_vec), scanning the `nprobe` closest
+    /// lists when an IVF-PQ index is present. Higher `nprobe` trades latency
+    /// for recall; it is ignored without an IVF-PQ index.
+    pub fn search_vec_with_nprobe(
+        &mut self,
+        query: &[f32],
+        limit: usize,
+        nprobe: usize,
+    ) -> Result<Vec<VecSearchHit>> {
         if !self.vec_enabled {
             return Err(MemvidError::VecNotEnabled);
         }
+        if let Some(ivf) = self.ivf_index.as_ref() {
+            if query.len() != ivf.dimension() as usize {
+                return Err(MemvidError::VecDimensionMismatch {
+                    expected: ivf.dimension(),
+                    actual: query.len(),
+                });
+            }
+            return Ok(ivf.search_filtered(query, limit, nprobe, |frame_id| {
+                self.frame_is_active(frame_id)
+            }));
+        }
         let mut ensured_vec_index = false;
         let expected_dim = if let Some(dim) = self.effective_vec_index_dimension()? {
             dim
@@ -159,7 +182,89 @@ impl Memvid {
             self.ensure_vec_index()?;
         }
         let index = self.vec_index.as_ref().ok_or(MemvidError::VecNotEnabled)?;
-        Ok(index.search(query, limit))
+        Ok(index.search_with_rescore(query, limit, self.vec_rescore_oversample))
+    }
+
+    /// Train an IVF-PQ index over the current embeddings and use it for
+    /// [`search_vec`](Self::search_vec).
+    ///
+    /// The index is stored as a segment of this file on the next commit and
+    /// reattached on open, memory-mapped so only the probed lists are paged
+    /// in per query. Each commit adds newly committed vectors to their lists
+    /// and drops deleted frames; retrain when the data drifts from the
+    /// trained centroids.
+    pub fn build_ivf_index(
+        &mut self,
+        builder: crate::vec_ivf::IvfPqBuilder,
+        training_sample: usize,
+    ) -> Result<u64> {
+        if !self.vec_enabled {
+            return Err(MemvidError::VecNotEnabled);
+        }
+        self.ensure_writable()?;
+        self.ensure_vec_index()?;
+        let index = self.vec_index.as_ref().ok_or(MemvidError::EmptyVecIndex)?;
+        let entries: Vec<(FrameId, &[f32])> = index
+            .entries()
+            .filter(|(frame_id, _)| self.frame_is_active(*frame_id))
+            .collect();
+        if entries.is_empty() {
+            return Err(MemvidError::EmptyVecIndex);
+        }
+
+        // Evenly strided sample keeps training deterministic
+        let stride = entries.len().div_ceil(training_sample.max(1));
+        let training: Vec<Vec<f32>> = entries
+            .iter()
+            .step_by(stride)
+            .map(|(_, embedding)| embedding.to_vec())
+            .collect();
+
+        let mut builder = builder;
+        builder.train(&training)?;
+        for (frame_id, embedding) in &entries {
+            builder.add_document(*frame_id, embedding)?;
+        }
+        let artifact = builder.finish()?;
+        let vector_count = artifact.vector_count;
+        self.ivf_index = Some(crate::vec_ivf::IvfPqIndex::from_bytes(
+            crate::vec_ivf::IvfBytes::Owned(artifact.bytes),
+        )?);
+        self.dirty = true;
+        Ok(vector_count)
+    }
+
+    /// The IVF-PQ index used by [`search_vec`](Self::search_vec), if any.
+    #[must_use]
+    pub fn ivf_index(&self) -> Option<&crate::vec_ivf::IvfPqIndex> {
+        self.ivf_index.as_ref()
+    }
+
+    /// Remove the IVF-PQ index; the segment is dropped on the next commit.
+    pub fn drop_ivf_index(&mut self) -> Result<()> {
+        self.ensure_writable()?;
+        if self.ivf_index.take().is_some() {
+            self.dirty = true;
+        }
+        Ok(())
+    }
+
+    /// Map the IVF-PQ segment referenced by the TOC, if any.
+    pub(crate) fn load_ivf_index_from_manifest(&mut self) {
+        let Some(manifest) = self.toc.ivf_index.as_ref() else {
+            return;
+        };
+        match crate::vec_ivf::IvfPqIndex::map(
+            &self.file,
+            manifest.bytes_offset,
+            manifest.bytes_length,
+        ) {
+            Ok(index) => self.ivf_index = Some(index),
+            Err(err) => {
+                // Searches fall back to the regular vector index
+                tracing::warn!(error = %err, "failed to map IVF-PQ index segment");
+            }
+        }
     }
 
     /// Enable CLIP visual embeddings index.
@@ -343,7 +448,8 @@ impl Memvid {
         let vec_index = self.vec_index.as_ref().ok_or(MemvidError::VecNotEnabled)?;
 
         // Do pure vector search over entire index
-        let vec_hits = vec_index.search(query_embedding, top_k * 2);
+        let vec_hits =
+            vec_index.search_with_rescore(query_embedding, top_k * 2, self.vec_rescore_oversample);
 
         if vec_hits.is_empty() {
             let elapsed_ms = start_time.elapsed().as_millis();
@@ -360,6 +466,10 @@ impl Memvid {
                 context: build_context(&[]),
                 next_cursor: None,
                 engine: SearchEngineKind::Hybrid,
+                suggested_query: None,
+                facets: Vec::new(),
+                query_expansions: Vec::new(),
+                explanation: None,
             });
         }
 
@@ -420,6 +530,8 @@ impl Memvid {
                 content_dates: frame.content_dates.clone(),
                 entities: Vec::new(),
                 extra_metadata: frame.extra_metadata.clone(),
+                media: None,
+                region: None,
                 #[cfg(feature = "temporal_track")]
                 temporal: None,
             };
@@ -436,6 +548,7 @@ impl Memvid {
                 chunk_text: Some(snippet),
                 score: Some(similarity_score),
                 metadata: Some(metadata),
+                highlights: Vec::new(),
             });
 
             if hits.len() >= top_k {
@@ -447,6 +560,7 @@ impl Memvid {
 
         #[cfg(feature = "temporal_track")]
         super::helpers::attach_temporal_metadata(self, &mut hits)?;
+        super::helpers::attach_media_offsets(self, &mut hits, &[])?;
 
         self.apply_acl_to_search_hits(&mut hits, acl_context, acl_enforcement_mode)?;
         let context = build_context(&hits);
@@ -464,6 +578,10 @@ impl Memvid {
             context,
             next_cursor: None,
             engine: SearchEngineKind::Hybrid,
+            suggested_query: None,
+            facets: Vec::new(),
+            query_expansions: Vec::new(),
+            explanation: None,
         })
     }
 
@@ -849,11 +967,13 @@ impl Memvid {
             )
         };
 
+        let search_schema = self.toc.search_schema.clone().unwrap_or_default();
+        let mut reopen_failed = false;
         let mut engine = match segments {
             Some(segments) => {
                 match self
                     .materialize_tantivy_segments(&segments)
-                    .and_then(TantivyEngine::open_from_dir)
+                    .and_then(|dir| TantivyEngine::open_from_dir(dir, &search_schema))
                 {
                     Ok(engine) => engine,
                     Err(err) => {
@@ -861,11 +981,12 @@ impl Memvid {
                             "failed to open embedded Tantivy index: {}, rebuilding",
                             err
                         );
-                        TantivyEngine::create()?
+                        reopen_failed = true;
+                        TantivyEngine::create(&search_schema)?
                     }
                 }
             }
-            None => TantivyEngine::create()?,
+            None => TantivyEngine::create(&search_schema)?,
         };
 
         // Use consolidated helper for expected doc count
@@ -879,7 +1000,20 @@ impl Memvid {
         let actual_docs = engine.num_docs();
 
         let has_tantivy_segments = !self.toc.segment_catalog.tantivy_segments.is_empty();
-        let needs_rebuild = if has_tantivy_segments {
+        let outdated = self
+            .toc
+            .segment_catalog
+            .tantivy_segments
+            .iter()
+            .any(|descriptor| descriptor.common.codec_version < TANTIVY_CODEC_VERSION);
+        let needs_rebuild = if reopen_failed {
+            // Embedded segments were unreadable or built for another search schema
+            true
+        } else if outdated {
+            // Segments were analysed by an older tokenizer chain
+            tracing::debug!("rebuilding Tantivy index written with an older codec version");
+            true
+        } else if has_tantivy_segments {
             // Trust existing Tantivy segments, don't rebuild
             false
         } else {
@@ -4,7 +4,7 @@ use std::panic::{AssertUnwindSafe, catch_unwind};
 
 use crate::lex::{LexIndex, LexIndexArtifact, LexIndexBuilder};
 use crate::memvid::lifecycle::Memvid;
-use crate::types::{Frame, FrameId, FrameStatus, VectorCompression};
+use crate::types::{Frame, FrameId, FrameStatus};
 use crate::{MemvidError, Result, VecIndex, VecIndexArtifact};
 
 impl Memvid {
@@ -48,7 +48,7 @@ impl Memvid {
         if !self.vec_enabled {
             return Ok(None);
         }
-        let mut builder = VecIndexBuilder::new();
+        let mut builder = VecIndexBuilder::new().with_compression(self.vec_compression.clone());
         if let Some(index) = self.vec_index.as_ref() {
             for (frame_id, embedding) in index.entries() {
                 if self.frame_is_active(frame_id) {
@@ -133,15 +133,20 @@ impl Memvid {
                 return Ok(());
             }
 
-            let bytes =
-                if let Ok(bytes) = self.read_range(manifest.bytes_offset, manifest.bytes_length) {
-                    bytes
-                } else {
-                    self.vec_index = None;
-                    // Don't disable vec if loading fails - keep it enabled
-                    // self.vec_enabled = false;
-                    return Ok(());
-                };
+            let (offset, length) = (manifest.bytes_offset, manifest.bytes_length);
+            if let Some(result) = self.read_deferred_vec_index(offset, length) {
+                self.vec_index = result.ok();
+                return Ok(());
+            }
+
+            let bytes = if let Ok(bytes) = self.read_range(offset, length) {
+                bytes
+            } else {
+                self.vec_index = None;
+                // Don't disable vec if loading fails - keep it enabled
+                // self.vec_enabled = false;
+                return Ok(());
+            };
             match catch_unwind(AssertUnwindSafe(|| VecIndex::decode(&bytes))) {
                 Ok(Ok(index)) => self.vec_index = Some(index),
                 Ok(Err(_)) | Err(_) => {
@@ -158,6 +163,26 @@ impl Memvid {
         Ok(())
     }
 
+    /// Read only the codes of an int8/binary index at `offset`, leaving its
+    /// originals on disk; `None` when the blob is some other format.
+    fn read_deferred_vec_index(&mut self, offset: u64, length: u64) -> Option<Result<VecIndex>> {
+        let header_len = length.min(crate::vec_sq::HEADER_LEN as u64);
+        let header = self.read_range(offset, header_len).ok()?;
+        let prefix_len = match crate::vec_sq::hot_prefix_len(&header)? {
+            Ok(prefix_len) if prefix_len <= length => prefix_len,
+            Ok(_) => {
+                return Some(Err(MemvidError::InvalidToc {
+                    reason: "quantized vector index exceeds its manifest range".into(),
+                }));
+            }
+            Err(err) => return Some(Err(err)),
+        };
+        Some(
+            self.read_range(offset, prefix_len)
+                .and_then(|prefix| crate::vec_sq::decode_deferred(&prefix, &self.path, offset)),
+        )
+    }
+
     /// Load CLIP index from manifest.
     pub(crate) fn load_clip_index_from_manifest(&mut self) -> Result<()> {
         use crate::clip::ClipIndex;
@@ -216,7 +241,7 @@ impl Memvid {
     fn build_vec_index_from_segments(&mut self) -> Result<()> {
         use crate::vec::VecIndexBuilder;
 
-        let mut builder = VecIndexBuilder::new();
+        let mut builder = VecIndexBuilder::new().with_compression(self.vec_compression.clone());
 
         // Clone segments to avoid borrow checker issues
         let segments = self.toc.segment_catalog.vec_segments.clone();
@@ -269,7 +294,7 @@ impl Memvid {
         let artifact = builder.finish()?;
         if artifact.vector_count > 0 {
             let index =
-                VecIndex::decode_with_compression(&artifact.bytes, VectorCompression::None)?;
+                VecIndex::decode_with_compression(&artifact.bytes, self.vec_compression.clone())?;
             self.vec_index = Some(index);
         }
 
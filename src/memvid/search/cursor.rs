@@ -0,0 +1,223 @@
+//! Opaque search-after cursors.
+//!
+//! A cursor pins the match set to the frames that existed when the first
+//! page was served (`watermark`), so frames appended between pages neither
+//! shift nor duplicate results. Sorted searches resume strictly after the
+//! last hit's `(key, frame_id)`. Relevance searches re-rank a window covering
+//! every page served so far and resume after the last hit's
+//! `(score, frame_id, start)`, so deleting an earlier hit does not skip one.
+//! Final relevance scores include the recency boost, which Tantivy cannot
+//! bound, so a relevance page costs as much as ranking every page before it:
+//! deep relevance paging is O(offset) per page.
+//! Bare integers and older offset cursors are still accepted as relevance
+//! offsets.
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{FrameId, SearchHit, SearchSort};
+use crate::{MemvidError, Result};
+
+const CURSOR_PREFIX: &str = "c1.";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct SearchCursor {
+    pub sort: SearchSort,
+    /// Commit generation the first page was served from.
+    pub generation: u64,
+    /// Frames with ids at or above this were appended after the first page.
+    pub watermark: FrameId,
+    pub position: CursorPosition,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) enum CursorPosition {
+    /// Hits already returned, in relevance order.
+    Offset(usize),
+    /// Sort key and frame id of the last hit returned.
+    After { key: i64, frame_id: FrameId },
+    /// Last relevance hit returned (score bits, frame id, byte start) and the
+    /// number of hits served so far, which sizes the next window.
+    ScoreAfter {
+        score: Option<u32>,
+        frame_id: FrameId,
+        start: usize,
+        served: usize,
+    },
+}
+
+impl CursorPosition {
+    /// Relevance position after `hit`, with `served` hits returned in total.
+    pub(super) fn after_hit(hit: &SearchHit, served: usize) -> Self {
+        Self::ScoreAfter {
+            score: hit.score.map(f32::to_bits),
+            frame_id: hit.frame_id,
+            start: hit.range.0,
+            served,
+        }
+    }
+
+    /// Relevance hits returned before this position.
+    pub(super) fn served(self) -> usize {
+        match self {
+            Self::Offset(served) | Self::ScoreAfter { served, .. } => served,
+            Self::After { .. } => 0,
+        }
+    }
+}
+
+/// Drop the hits of a ranked `window` up to and including `position`.
+///
+/// The last served hit is looked up by frame and range; when it no longer
+/// matches, hits ranking at or above its score are dropped instead.
+pub(super) fn resume_ranked(window: &mut Vec<SearchHit>, position: CursorPosition) {
+    match position {
+        CursorPosition::Offset(offset) => {
+            window.drain(..offset.min(window.len()));
+        }
+        CursorPosition::ScoreAfter {
+            score,
+            frame_id,
+            start,
+            ..
+        } => {
+            if let Some(idx) = window
+                .iter()
+                .position(|hit| hit.frame_id == frame_id && hit.range.0 == start)
+            {
+                window.drain(..=idx);
+                return;
+            }
+            let score = score.map(f32::from_bits);
+            window.retain(|hit| {
+                let tie = (hit.frame_id, hit.range.0) > (frame_id, start);
+                match (hit.score, score) {
+                    (Some(hit_score), Some(score)) => {
+                        hit_score < score || (hit_score.total_cmp(&score).is_eq() && tie)
+                    }
+                    _ => tie,
+                }
+            });
+        }
+        CursorPosition::After { .. } => {}
+    }
+}
+
+impl SearchCursor {
+    /// Decode `token` for a request with `sort` against the current memory
+    /// state. Returns `None` for a first page.
+    pub(super) fn decode(
+        token: Option<&str>,
+        sort: SearchSort,
+        generation: u64,
+        frame_count: FrameId,
+    ) -> Result<Option<Self>> {
+        let Some(token) = token.map(str::trim).filter(|token| !token.is_empty()) else {
+            return Ok(None);
+        };
+
+        if let Ok(offset) = token.parse::<usize>() {
+            if sort != SearchSort::Relevance {
+                return Err(MemvidError::InvalidCursor {
+                    reason: "numeric cursors only apply to relevance sort",
+                });
+            }
+            return Ok(Some(Self {
+                sort,
+                generation,
+                watermark: frame_count,
+                position: CursorPosition::Offset(offset),
+            }));
+        }
+
+        let cursor = token
+            .strip_prefix(CURSOR_PREFIX)
+            .and_then(|payload| URL_SAFE_NO_PAD.decode(payload).ok())
+            .and_then(|bytes| {
+                bincode::serde::decode_from_slice::<Self, _>(&bytes, bincode::config::standard())
+                    .ok()
+            })
+            .map(|(cursor, _)| cursor)
+            .ok_or(MemvidError::InvalidCursor {
+                reason: "malformed cursor",
+            })?;
+
+        if cursor.sort != sort {
+            return Err(MemvidError::InvalidCursor {
+                reason: "cursor was issued for a different sort order",
+            });
+        }
+        let position_matches_sort = match cursor.position {
+            CursorPosition::Offset(_) | CursorPosition::ScoreAfter { .. } => {
+                sort == SearchSort::Relevance
+            }
+            CursorPosition::After { .. } => sort != SearchSort::Relevance,
+        };
+        if !position_matches_sort {
+            return Err(MemvidError::InvalidCursor {
+                reason: "malformed cursor",
+            });
+        }
+        if cursor.generation > generation {
+            return Err(MemvidError::InvalidCursor {
+                reason: "cursor is newer than this memory",
+            });
+        }
+        if cursor.watermark > frame_count {
+            return Err(MemvidError::InvalidCursor {
+                reason: "memory was rewritten since the cursor was issued",
+            });
+        }
+        Ok(Some(cursor))
+    }
+
+    pub(super) fn encode(&self) -> String {
+        // Serialising plain integers and unit enums cannot fail.
+        let bytes =
+            bincode::serde::encode_to_vec(self, bincode::config::standard()).unwrap_or_default();
+        format!("{CURSOR_PREFIX}{}", URL_SAFE_NO_PAD.encode(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_validates() {
+        let cursor = SearchCursor {
+            sort: SearchSort::TimestampDesc,
+            generation: 4,
+            watermark: 10,
+            position: CursorPosition::After {
+                key: -5,
+                frame_id: 7,
+            },
+        };
+        let token = cursor.encode();
+        assert_eq!(
+            SearchCursor::decode(Some(&token), SearchSort::TimestampDesc, 4, 12).unwrap(),
+            Some(cursor)
+        );
+
+        assert!(SearchCursor::decode(Some(&token), SearchSort::FrameId, 4, 12).is_err());
+        assert!(SearchCursor::decode(Some(&token), SearchSort::TimestampDesc, 3, 12).is_err());
+        assert!(SearchCursor::decode(Some(&token), SearchSort::TimestampDesc, 4, 9).is_err());
+        assert!(SearchCursor::decode(Some("c1.!!"), SearchSort::TimestampDesc, 4, 12).is_err());
+    }
+
+    #[test]
+    fn numeric_tokens_are_relevance_offsets() {
+        let cursor = SearchCursor::decode(Some("3"), SearchSort::Relevance, 2, 8)
+            .unwrap()
+            .unwrap();
+        assert_eq!(cursor.position, CursorPosition::Offset(3));
+        assert_eq!(cursor.watermark, 8);
+        assert!(SearchCursor::decode(Some("3"), SearchSort::FrameId, 2, 8).is_err());
+        assert_eq!(
+            SearchCursor::decode(None, SearchSort::Relevance, 2, 8).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,339 @@
+//! Result diversification: near-duplicate removal, collapse by document and
+//! maximal marginal relevance (MMR).
+//!
+//! Similarity between two hits is the cosine similarity of their stored
+//! embeddings when both have one, and `1 - hamming / 64` over their `SimHash`
+//! fingerprints otherwise (taken from the sketch track, or computed from the
+//! hit text when the frame has no sketch).
+
+use std::collections::HashSet;
+
+use crate::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{DiversifyOptions, SearchHit, SketchVariant, generate_sketch};
+
+/// How many extra candidates `search` fetches so diversification still has
+/// `top_k` hits left after dropping duplicates.
+pub(crate) const DIVERSIFY_OVERFETCH: usize = 3;
+
+/// Per-hit fingerprint used for pairwise similarity.
+struct HitSignature {
+    embedding: Option<Vec<f32>>,
+    simhash: u64,
+}
+
+impl HitSignature {
+    fn similarity(&self, other: &Self) -> f32 {
+        match (&self.embedding, &other.embedding) {
+            (Some(a), Some(b)) if a.len() == b.len() => cosine_similarity(a, b),
+            _ => {
+                let hamming = (self.simhash ^ other.simhash).count_ones();
+                1.0 - hamming as f32 / 64.0
+            }
+        }
+    }
+}
+
+impl Memvid {
+    /// Apply the enabled diversification stages to `hits`, keep at most
+    /// `limit` of them and renumber ranks.
+    pub(crate) fn diversify_hits(
+        &mut self,
+        hits: &mut Vec<SearchHit>,
+        options: &DiversifyOptions,
+        limit: usize,
+    ) -> Result<()> {
+        if options.collapse_by_document {
+            let keys: Vec<String> = hits.iter().map(|hit| self.document_key(hit)).collect();
+            collapse_by_key(hits, &keys, limit, false);
+        }
+
+        if options.mmr_lambda.is_some() || options.near_duplicate_threshold.is_some() {
+            if self.vec_enabled {
+                self.ensure_vec_index()?;
+            }
+            let signatures: Vec<HitSignature> =
+                hits.iter().map(|hit| self.hit_signature(hit)).collect();
+            let similarity = |a: usize, b: usize| signatures[a].similarity(&signatures[b]);
+
+            let mut order: Vec<usize> = (0..hits.len()).collect();
+            if let Some(threshold) = options.near_duplicate_threshold {
+                order = drop_near_duplicates(&order, threshold, &similarity);
+            }
+            if let Some(lambda) = options.mmr_lambda {
+                let relevance = relevance_scores(hits);
+                order = mmr_order(&order, &relevance, lambda, limit, &similarity);
+            }
+
+            let mut slots: Vec<Option<SearchHit>> = hits.drain(..).map(Some).collect();
+            hits.extend(order.into_iter().filter_map(|idx| slots[idx].take()));
+        }
+
+        hits.truncate(limit);
+        for (idx, hit) in hits.iter_mut().enumerate() {
+            hit.rank = idx + 1;
+        }
+        Ok(())
+    }
+
+    /// Base URI of the hit's root document (the parent frame for chunks).
+    pub(crate) fn document_key(&self, hit: &SearchHit) -> String {
+        let parent = usize::try_from(hit.frame_id)
+            .ok()
+            .and_then(|idx| self.toc.frames.get(idx))
+            .and_then(|frame| frame.parent_id)
+            .and_then(|parent_id| {
+                let idx = usize::try_from(parent_id).ok()?;
+                let frame = self.toc.frames.get(idx)?;
+                Some(
+                    frame
+                        .uri
+                        .clone()
+                        .unwrap_or_else(|| crate::default_uri(parent_id)),
+                )
+            });
+        let uri = parent.as_deref().unwrap_or(&hit.uri);
+        uri.split('#').next().unwrap_or(uri).to_string()
+    }
+
+    fn hit_signature(&self, hit: &SearchHit) -> HitSignature {
+        let embedding = self
+            .vec_index
+            .as_ref()
+            .and_then(|index| index.embedding_for(hit.frame_id))
+            .map(<[f32]>::to_vec);
+        let simhash = self.sketch_track.get(hit.frame_id).map_or_else(
+            || {
+                let text = hit.chunk_text.as_deref().unwrap_or(&hit.text);
+                generate_sketch(hit.frame_id, text, SketchVariant::Small, None).simhash
+            },
+            |entry| entry.simhash,
+        );
+        HitSignature { embedding, simhash }
+    }
+}
+
+/// Keep the first (best) hit per key. With `backfill`, leftover slots up to
+/// `limit` are refilled with the best remaining hits instead of dropping them.
+pub(crate) fn collapse_by_key(
+    hits: &mut Vec<SearchHit>,
+    keys: &[String],
+    limit: usize,
+    backfill: bool,
+) {
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut kept: Vec<SearchHit> = Vec::new();
+    let mut remaining: Vec<SearchHit> = Vec::new();
+
+    for (hit, key) in hits.drain(..).zip(keys) {
+        if kept.len() < limit && seen.insert(key.as_str()) {
+            kept.push(hit);
+        } else {
+            remaining.push(hit);
+        }
+    }
+
+    if backfill {
+        let slots_left = limit.saturating_sub(kept.len());
+        kept.extend(remaining.into_iter().take(slots_left));
+    }
+
+    for (idx, hit) in kept.iter_mut().enumerate() {
+        hit.rank = idx + 1;
+    }
+    *hits = kept;
+}
+
+/// Walk `order` best-first, dropping hits at least `threshold` similar to
+/// one already kept.
+fn drop_near_duplicates<F>(order: &[usize], threshold: f32, similarity: &F) -> Vec<usize>
+where
+    F: Fn(usize, usize) -> f32,
+{
+    let mut kept: Vec<usize> = Vec::with_capacity(order.len());
+    for &candidate in order {
+        if kept
+            .iter()
+            .all(|&existing| similarity(candidate, existing) < threshold)
+        {
+            kept.push(candidate);
+        }
+    }
+    kept
+}
+
+/// Greedy MMR: repeatedly pick the candidate maximising
+/// `lambda * relevance - (1 - lambda) * max_similarity_to_selected`.
+fn mmr_order<F>(
+    order: &[usize],
+    relevance: &[f32],
+    lambda: f32,
+    limit: usize,
+    similarity: &F,
+) -> Vec<usize>
+where
+    F: Fn(usize, usize) -> f32,
+{
+    let lambda = lambda.clamp(0.0, 1.0);
+    let mut candidates: Vec<usize> = order.to_vec();
+    let mut selected: Vec<usize> = Vec::with_capacity(limit.min(order.len()));
+
+    while selected.len() < limit && !candidates.is_empty() {
+        let mut best_pos = 0;
+        let mut best_score = f32::NEG_INFINITY;
+        for (pos, &candidate) in candidates.iter().enumerate() {
+            let redundancy = selected
+                .iter()
+                .map(|&chosen| similarity(candidate, chosen))
+                .fold(0.0f32, f32::max);
+            let score = lambda * relevance[candidate] - (1.0 - lambda) * redundancy;
+            if score > best_score {
+                best_score = score;
+                best_pos = pos;
+            }
+        }
+        selected.push(candidates.remove(best_pos));
+    }
+    selected
+}
+
+/// Relevance in `[0, 1]`: min-max normalised scores when every hit has one,
+/// otherwise derived from rank order.
+fn relevance_scores(hits: &[SearchHit]) -> Vec<f32> {
+    let scores: Option<Vec<f32>> = hits.iter().map(|hit| hit.score).collect();
+    match scores {
+        Some(scores) if !scores.is_empty() => {
+            let max = scores.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            let min = scores.iter().copied().fold(f32::INFINITY, f32::min);
+            let span = max - min;
+            scores
+                .iter()
+                .map(|score| {
+                    if span > f32::EPSILON {
+                        (score - min) / span
+                    } else {
+                        1.0
+                    }
+                })
+                .collect()
+        }
+        _ => {
+            let len = hits.len().max(1) as f32;
+            (0..hits.len()).map(|idx| 1.0 - idx as f32 / len).collect()
+        }
+    }
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a <= f32::EPSILON || norm_b <= f32::EPSILON {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(frame_id: u64, uri: &str, score: Option<f32>) -> SearchHit {
+        SearchHit {
+            rank: 0,
+            frame_id,
+            uri: uri.to_string(),
+            title: None,
+            range: (0, 0),
+            text: String::new(),
+            matches: 0,
+            chunk_range: None,
+            chunk_text: None,
+            score,
+            metadata: None,
+            highlights: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn collapse_keeps_best_per_key() {
+        let mut hits = vec![
+            hit(1, "mv2://a#1", None),
+            hit(2, "mv2://a#2", None),
+            hit(3, "mv2://b", None),
+            hit(4, "mv2://a#3", None),
+        ];
+        let keys = vec![
+            "a".to_string(),
+            "a".to_string(),
+            "b".to_string(),
+            "a".to_string(),
+        ];
+
+        let mut strict = hits.clone();
+        collapse_by_key(&mut strict, &keys, 3, false);
+        let ids: Vec<u64> = strict.iter().map(|h| h.frame_id).collect();
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(strict[1].rank, 2);
+
+        collapse_by_key(&mut hits, &keys, 3, true);
+        let ids: Vec<u64> = hits.iter().map(|h| h.frame_id).collect();
+        assert_eq!(ids, vec![1, 3, 2]);
+    }
+
+    #[test]
+    fn near_duplicates_are_dropped() {
+        // 0 and 1 are duplicates, 2 is distinct
+        let sim = |a: usize, b: usize| {
+            if a.min(b) == 0 && a.max(b) == 1 {
+                0.99
+            } else {
+                0.1
+            }
+        };
+        assert_eq!(drop_near_duplicates(&[0, 1, 2], 0.9, &sim), vec![0, 2]);
+        assert_eq!(drop_near_duplicates(&[0, 1, 2], 0.995, &sim), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn mmr_promotes_novel_hits() {
+        let relevance = [1.0, 0.95, 0.5];
+        let sim = |a: usize, b: usize| {
+            if a.min(b) == 0 && a.max(b) == 1 {
+                1.0
+            } else {
+                0.0
+            }
+        };
+        // Pure relevance keeps the original order
+        assert_eq!(
+            mmr_order(&[0, 1, 2], &relevance, 1.0, 3, &sim),
+            vec![0, 1, 2]
+        );
+        // Balanced MMR moves the redundant hit behind the novel one
+        assert_eq!(
+            mmr_order(&[0, 1, 2], &relevance, 0.5, 3, &sim),
+            vec![0, 2, 1]
+        );
+        assert_eq!(mmr_order(&[0, 1, 2], &relevance, 0.5, 2, &sim), vec![0, 2]);
+    }
+
+    #[test]
+    fn signature_similarity_prefers_embeddings() {
+        let a = HitSignature {
+            embedding: Some(vec![1.0, 0.0]),
+            simhash: 0,
+        };
+        let b = HitSignature {
+            embedding: Some(vec![1.0, 0.0]),
+            simhash: u64::MAX,
+        };
+        assert!((a.similarity(&b) - 1.0).abs() < 1e-6);
+
+        let c = HitSignature {
+            embedding: None,
+            simhash: 0b1111,
+        };
+        assert!((a.similarity(&c) - (1.0 - 4.0 / 64.0)).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,372 @@
+//! Search explanations: per-stage candidate counts collected while a search
+//! runs, and the scoring and drop reason for one frame.
+
+use std::collections::HashSet;
+
+use crate::memvid::ask::RRF_K;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{
+    AdaptiveConfig, FrameExplanation, FrameId, FrameStatus, FrameVerdict, RrfContribution,
+    ScoreExplanation, SearchEngineKind, SearchExplanation, SearchHit, SearchRequest, SearchSort,
+    SearchStage, StageTrace,
+};
+use crate::{MemvidError, Result};
+
+/// Deepest rank explanations look for a frame at, unless the requested page
+/// is deeper.
+const EXPLAIN_RANK_DEPTH: usize = 1_000;
+
+/// Stage counts gathered during one search, plus the first stage that
+/// removed the frame being explained.
+#[derive(Debug, Default)]
+pub(super) struct SearchTrace {
+    enabled: bool,
+    target: Option<FrameId>,
+    query_tree: String,
+    stages: Vec<StageTrace>,
+    dropped: Option<(SearchStage, String)>,
+}
+
+impl SearchTrace {
+    pub(super) fn new(enabled: bool, target: Option<FrameId>) -> Self {
+        Self {
+            enabled,
+            target,
+            ..Self::default()
+        }
+    }
+
+    pub(super) fn set_query_tree(&mut self, describe: impl FnOnce() -> String) {
+        if self.enabled {
+            self.query_tree = describe();
+        }
+    }
+
+    /// Record the candidate set left by a filtering stage (`None` = unrestricted).
+    pub(super) fn filter(
+        &mut self,
+        stage: SearchStage,
+        candidates: Option<&HashSet<FrameId>>,
+        reason: &str,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        self.stages.push(StageTrace {
+            stage,
+            candidates: candidates.map(HashSet::len),
+        });
+        if let (Some(target), Some(set)) = (self.target, candidates) {
+            if !set.contains(&target) {
+                self.drop_target(stage, reason);
+            }
+        }
+    }
+
+    /// Record a stage that keeps only frames below `bound`, applied on top of
+    /// `candidates` (`None` = unrestricted).
+    pub(super) fn bound(
+        &mut self,
+        stage: SearchStage,
+        candidates: Option<&HashSet<FrameId>>,
+        bound: FrameId,
+        reason: &str,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        let remaining = match candidates {
+            Some(set) => set.iter().filter(|id| **id < bound).count(),
+            None => usize::try_from(bound).unwrap_or(usize::MAX),
+        };
+        self.stages.push(StageTrace {
+            stage,
+            candidates: Some(remaining),
+        });
+        if self.target.is_some_and(|target| target >= bound) {
+            self.drop_target(stage, reason);
+        }
+    }
+
+    /// Record a stage that left no candidates at all.
+    pub(super) fn empty(&mut self, stage: SearchStage, reason: &str) {
+        self.filter(stage, Some(&HashSet::new()), reason);
+    }
+
+    /// Record a stage that narrowed a list of hits.
+    pub(super) fn hits(
+        &mut self,
+        stage: SearchStage,
+        before: &[FrameId],
+        after: &[SearchHit],
+        reason: &str,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        self.stages.push(StageTrace {
+            stage,
+            candidates: Some(after.len()),
+        });
+        if let Some(target) = self.target {
+            if before.contains(&target) && !after.iter().any(|hit| hit.frame_id == target) {
+                self.drop_target(stage, reason);
+            }
+        }
+    }
+
+    /// Record a stage by its candidate count alone.
+    pub(super) fn count(&mut self, stage: SearchStage, candidates: usize) {
+        if self.enabled {
+            self.stages.push(StageTrace {
+                stage,
+                candidates: Some(candidates),
+            });
+        }
+    }
+
+    fn drop_target(&mut self, stage: SearchStage, reason: &str) {
+        if self.dropped.is_none() {
+            self.dropped = Some((stage, reason.to_string()));
+        }
+    }
+
+    pub(super) fn into_explanation(self, engine: &SearchEngineKind) -> Option<SearchExplanation> {
+        self.enabled.then(|| SearchExplanation {
+            query_tree: self.query_tree,
+            engine: engine.clone(),
+            stages: self.stages,
+            frame: None,
+        })
+    }
+}
+
+impl Memvid {
+    /// Run `request` and explain what happened to `frame_id`: the stage that
+    /// dropped it (or its rank), its BM25 breakdown, and its rank among all
+    /// lexical matches.
+    pub fn explain_search(
+        &mut self,
+        request: SearchRequest,
+        frame_id: FrameId,
+    ) -> Result<SearchExplanation> {
+        self.explain_frame_search(request, frame_id, None)
+    }
+
+    /// [`Self::explain_search`] plus the frame's vector distance and rank for
+    /// `query_embedding`, and the reciprocal rank fusion terms `ask` would
+    /// add up for it in hybrid mode.
+    pub fn explain_hybrid_search(
+        &mut self,
+        request: SearchRequest,
+        frame_id: FrameId,
+        query_embedding: &[f32],
+    ) -> Result<SearchExplanation> {
+        self.explain_frame_search(request, frame_id, Some((query_embedding, None)))
+    }
+
+    /// [`Self::explain_hybrid_search`] with the vector list cut by adaptive
+    /// retrieval under `config`, as `ask` does when adaptive retrieval is on.
+    pub fn explain_adaptive_search(
+        &mut self,
+        request: SearchRequest,
+        frame_id: FrameId,
+        query_embedding: &[f32],
+        config: &AdaptiveConfig,
+    ) -> Result<SearchExplanation> {
+        self.explain_frame_search(request, frame_id, Some((query_embedding, Some(config))))
+    }
+
+    fn explain_frame_search(
+        &mut self,
+        mut request: SearchRequest,
+        frame_id: FrameId,
+        vector: Option<(&[f32], Option<&AdaptiveConfig>)>,
+    ) -> Result<SearchExplanation> {
+        let status = usize::try_from(frame_id)
+            .ok()
+            .and_then(|idx| self.toc.frames.get(idx))
+            .map(|frame| frame.status)
+            .ok_or(MemvidError::FrameNotFound { frame_id })?;
+
+        request.explain = true;
+        let mut trace = SearchTrace::new(true, Some(frame_id));
+        let response = self.search_traced(request.clone(), &mut trace)?;
+        let dropped = trace.dropped.take();
+        let depth = self.explain_rank_depth(&request);
+        let lexical_rank = self.lexical_rank(&request, frame_id, depth)?;
+        let bm25 = self.bm25_explanation(&request.query, frame_id)?;
+        let engine_stage = if response.engine == SearchEngineKind::LexFallback {
+            SearchStage::LexFallback
+        } else {
+            SearchStage::Engine
+        };
+
+        let mut verdict =
+            if let Some(hit) = response.hits.iter().find(|hit| hit.frame_id == frame_id) {
+                FrameVerdict::Returned { rank: hit.rank }
+            } else if let Some((stage, reason)) = dropped {
+                FrameVerdict::Dropped { stage, reason }
+            } else if status != FrameStatus::Active {
+                FrameVerdict::Dropped {
+                    stage: engine_stage,
+                    reason: if status == FrameStatus::Deleted {
+                        "frame is deleted".to_string()
+                    } else {
+                        "frame is superseded by a newer version".to_string()
+                    },
+                }
+            } else if let Some(rank) = lexical_rank {
+                FrameVerdict::Dropped {
+                    stage: SearchStage::Pagination,
+                    reason: format!("ranked {rank} among all matches, outside the requested page"),
+                }
+            } else if bm25.is_some() && depth < self.toc.frames.len() {
+                FrameVerdict::Dropped {
+                    stage: SearchStage::Pagination,
+                    reason: format!("not within the first {depth} matches"),
+                }
+            } else {
+                FrameVerdict::Dropped {
+                    stage: engine_stage,
+                    reason: "does not match the query or its URI/scope filter".to_string(),
+                }
+            };
+
+        let mut vector_distance = None;
+        let mut vector_rank = None;
+        let mut fused_vector_rank = None;
+        if let Some((embedding, adaptive)) = vector {
+            let nearest = self.search_vec(embedding, depth)?;
+            if let Some((idx, hit)) = nearest
+                .iter()
+                .enumerate()
+                .find(|(_, hit)| hit.frame_id == frame_id)
+            {
+                vector_distance = Some(hit.distance);
+                vector_rank = Some(idx + 1);
+            } else if let Some(frame_embedding) = self.frame_embedding(frame_id)? {
+                vector_distance = Some(crate::simd::l2_distance_simd(embedding, &frame_embedding));
+            }
+            fused_vector_rank = vector_rank;
+            if let Some(config) = adaptive.filter(|config| config.enabled) {
+                let kept = self.search_adaptive_acl(
+                    &request.query,
+                    embedding,
+                    config.clone(),
+                    request.snippet_chars,
+                    request.scope.as_deref(),
+                    request.acl_context.as_ref(),
+                    request.acl_enforcement_mode,
+                )?;
+                trace.count(SearchStage::AdaptiveCutoff, kept.stats.returned);
+                fused_vector_rank = kept
+                    .results
+                    .iter()
+                    .position(|hit| hit.frame_id == frame_id)
+                    .map(|idx| idx + 1);
+                // The vector list was the frame's only way into `ask`.
+                let lexically_unmatched = matches!(
+                    verdict,
+                    FrameVerdict::Dropped { stage, .. } if stage == engine_stage
+                );
+                if fused_vector_rank.is_none() && vector_rank.is_some() && lexically_unmatched {
+                    verdict = FrameVerdict::Dropped {
+                        stage: SearchStage::AdaptiveCutoff,
+                        reason: format!(
+                            "below the adaptive cutoff ({}), which kept {} of {} vector hits",
+                            kept.stats.triggered_by,
+                            kept.stats.returned,
+                            kept.stats.total_considered
+                        ),
+                    };
+                }
+            }
+        }
+
+        let rrf = if vector.is_some() {
+            [("lexical", lexical_rank), ("vector", fused_vector_rank)]
+                .into_iter()
+                .filter_map(|(list, rank)| {
+                    rank.map(|rank| RrfContribution {
+                        list: list.to_string(),
+                        rank,
+                        #[allow(clippy::cast_precision_loss)]
+                        contribution: 1.0 / (RRF_K + rank as f32),
+                    })
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let frame = FrameExplanation {
+            frame_id,
+            verdict,
+            lexical_rank,
+            bm25,
+            vector_distance,
+            vector_rank,
+            rrf,
+        };
+
+        let engine = response.engine.clone();
+        let mut explanation =
+            trace
+                .into_explanation(&engine)
+                .unwrap_or_else(|| SearchExplanation {
+                    query_tree: String::new(),
+                    engine,
+                    stages: Vec::new(),
+                    frame: None,
+                });
+        explanation.frame = Some(frame);
+        Ok(explanation)
+    }
+
+    /// How deep explanations look for a frame in the full lexical and vector
+    /// rankings: the requested page or [`EXPLAIN_RANK_DEPTH`], whichever is
+    /// larger, capped at the frame count.
+    fn explain_rank_depth(&self, request: &SearchRequest) -> usize {
+        request
+            .top_k
+            .max(EXPLAIN_RANK_DEPTH)
+            .min(self.toc.frames.len())
+            .max(1)
+    }
+
+    /// Rank of `frame_id` among the first `depth` matches of `request`,
+    /// ignoring paging, the sketch shortlist and diversification.
+    fn lexical_rank(
+        &mut self,
+        request: &SearchRequest,
+        frame_id: FrameId,
+        depth: usize,
+    ) -> Result<Option<usize>> {
+        let mut full = request.clone();
+        full.top_k = depth;
+        full.cursor = None;
+        full.no_sketch = true;
+        full.diversify = None;
+        full.highlight = None;
+        full.facets = Vec::new();
+        full.explain = false;
+        full.sort = SearchSort::Relevance;
+        let response = self.search(full)?;
+        Ok(response
+            .hits
+            .iter()
+            .position(|hit| hit.frame_id == frame_id)
+            .map(|idx| idx + 1))
+    }
+
+    fn bm25_explanation(&self, query: &str, frame_id: FrameId) -> Result<Option<ScoreExplanation>> {
+        let Some(engine) = self.tantivy.as_ref() else {
+            return Ok(None);
+        };
+        let mut parsed = crate::search::parse_query(query)?;
+        if let Some(track) = self.toc.synonyms.as_ref() {
+            parsed.expand_synonyms(track);
+        }
+        engine.explain_frame(&parsed, frame_id)
+    }
+}
@@ -0,0 +1,484 @@
+//! Facet counts over the full match set of a search.
+//!
+//! Hits are paged; facets are not. The match set is recomputed without the
+//! sketch pre-filter or `top_k` limits and filtered by ACL before grouping.
+//! When Tantivy served the query its `DocSetCollector` result is trusted for
+//! text terms and only field terms are re-checked against TOC metadata, so no
+//! payload is read; the lex index or a frame scan (which evaluate frame text)
+//! cover the other engines. Histograms read frame timestamps from the time
+//! index.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use chrono::{DateTime, Datelike, Months, NaiveDate};
+
+use super::helpers::{synonym_groups, timestamp_to_rfc3339};
+use super::tantivy::uri_matches;
+use crate::io::time_index::read_track as time_index_read;
+use crate::memvid::lifecycle::Memvid;
+use crate::search::{EvaluationContext, ParsedQuery, metadata_value};
+use crate::types::{
+    FacetBucket, FacetField, FacetRequest, FacetResult, Frame, FrameId, HistogramInterval,
+    MAX_HISTOGRAM_BUCKETS, SearchEngineKind, SearchRequest,
+};
+use crate::{MemvidError, Result};
+
+const HOUR_SECS: i64 = 3_600;
+const DAY_SECS: i64 = 86_400;
+const WEEK_SECS: i64 = 7 * DAY_SECS;
+
+pub(super) fn validate_facets(facets: &[FacetRequest]) -> Result<()> {
+    for facet in facets {
+        if let FacetField::Metadata { key } = &facet.field {
+            if key.trim().is_empty() {
+                return Err(MemvidError::InvalidQuery {
+                    reason: "metadata facet requires a key".into(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// One empty result per requested facet, for queries that cannot match.
+pub(super) fn empty_facets(facets: &[FacetRequest]) -> Vec<FacetResult> {
+    facets
+        .iter()
+        .map(|facet| FacetResult {
+            field: facet.field.clone(),
+            buckets: Vec::new(),
+            other: 0,
+            missing: 0,
+        })
+        .collect()
+}
+
+impl Memvid {
+    /// Count `request.facets` over every frame matching `parsed`.
+    ///
+    /// `candidate_filter` must not include the sketch pre-filter, which is a
+    /// ranking shortcut rather than part of the match set.
+    pub(super) fn compute_facets(
+        &mut self,
+        parsed: &ParsedQuery,
+        query_tokens: &[String],
+        request: &SearchRequest,
+        candidate_filter: Option<&HashSet<FrameId>>,
+        watermark: FrameId,
+        engine: &SearchEngineKind,
+    ) -> Result<Vec<FacetResult>> {
+        let mut frame_ids = self.facet_frame_ids(
+            parsed,
+            query_tokens,
+            request,
+            candidate_filter,
+            watermark,
+            engine,
+        )?;
+        self.retain_acl_allowed_frames(
+            &mut frame_ids,
+            request.acl_context.as_ref(),
+            request.acl_enforcement_mode,
+        )?;
+
+        let needs_timestamps = request
+            .facets
+            .iter()
+            .any(|facet| matches!(facet.field, FacetField::Timestamp { .. }));
+        let timestamps = if needs_timestamps {
+            self.facet_timestamps(&frame_ids)?
+        } else {
+            HashMap::new()
+        };
+
+        let frames: Vec<&Frame> = frame_ids
+            .iter()
+            .filter_map(|&frame_id| {
+                usize::try_from(frame_id)
+                    .ok()
+                    .and_then(|idx| self.toc.frames.get(idx))
+            })
+            .collect();
+        request
+            .facets
+            .iter()
+            .map(|facet| match facet.field {
+                FacetField::Timestamp { interval } => {
+                    let values: Vec<i64> = frame_ids
+                        .iter()
+                        .filter_map(|frame_id| timestamps.get(frame_id).copied())
+                        .collect();
+                    let missing = frame_ids.len() - values.len();
+                    histogram(facet, interval, &values, missing)
+                }
+                _ => Ok(count_terms(facet, &frames)),
+            })
+            .collect()
+    }
+
+    fn facet_frame_ids(
+        &mut self,
+        parsed: &ParsedQuery,
+        query_tokens: &[String],
+        request: &SearchRequest,
+        candidate_filter: Option<&HashSet<FrameId>>,
+        watermark: FrameId,
+        engine: &SearchEngineKind,
+    ) -> Result<Vec<FrameId>> {
+        let uri_filter = request.uri.as_deref();
+        let scope_filter = if uri_filter.is_some() {
+            None
+        } else {
+            request.scope.as_deref()
+        };
+
+        if *engine == SearchEngineKind::Tantivy {
+            if let Some(tantivy) = self.tantivy.as_ref() {
+                let frame_filter: Option<Vec<FrameId>> =
+                    candidate_filter.map(|set| set.iter().copied().collect());
+                let mut frame_ids = tantivy.matching_frame_ids(
+                    parsed,
+                    uri_filter,
+                    scope_filter,
+                    frame_filter.as_deref(),
+                    watermark,
+                )?;
+                // Same URI checks the hit path applies after Tantivy, plus field
+                // terms (e.g. `scope:`) that Tantivy matches loosely
+                frame_ids.retain(|&frame_id| {
+                    let Some(frame) = usize::try_from(frame_id)
+                        .ok()
+                        .and_then(|idx| self.toc.frames.get(idx))
+                    else {
+                        return false;
+                    };
+                    let uri = frame.uri.as_deref();
+                    let in_scope = match (uri_filter, scope_filter) {
+                        (Some(expected), _) => uri_matches(uri, expected),
+                        (None, Some(scope)) => uri.is_some_and(|uri| uri.starts_with(scope)),
+                        (None, None) => true,
+                    };
+                    in_scope && parsed.evaluate_fields(frame) != Some(false)
+                });
+                return Ok(frame_ids);
+            }
+        }
+
+        if !query_tokens.is_empty() {
+            if let Some(index) = self.lex_index.as_ref() {
+                let groups = synonym_groups(self, query_tokens);
+                let matches = index.compute_group_matches(&groups, uri_filter, scope_filter);
+                let mut frame_ids = Vec::new();
+                for matched in matches {
+                    if matched.frame_id >= watermark
+                        || candidate_filter
+                            .is_some_and(|filter| !filter.contains(&matched.frame_id))
+                    {
+                        continue;
+                    }
+                    let Some(frame) = usize::try_from(matched.frame_id)
+                        .ok()
+                        .and_then(|idx| self.toc.frames.get(idx))
+                    else {
+                        continue;
+                    };
+                    let content_lower = matched.content.to_ascii_lowercase();
+                    let ctx = EvaluationContext {
+                        frame,
+                        content_lower: &content_lower,
+                    };
+                    if parsed.evaluate(&ctx) {
+                        frame_ids.push(matched.frame_id);
+                    }
+                }
+                return Ok(frame_ids);
+            }
+        }
+
+        let frame_ids: Vec<FrameId> = self
+            .toc
+            .frames
+            .iter()
+            .map(|frame| frame.id)
+            .filter(|id| *id < watermark)
+            .filter(|id| candidate_filter.is_none_or(|filter| filter.contains(id)))
+            .collect();
+        self.evaluate_frames(parsed, frame_ids)
+    }
+
+    /// Keep the frames whose search text satisfies `parsed`.
+    fn evaluate_frames(
+        &mut self,
+        parsed: &ParsedQuery,
+        frame_ids: Vec<FrameId>,
+    ) -> Result<Vec<FrameId>> {
+        let mut matched = Vec::with_capacity(frame_ids.len());
+        for frame_id in frame_ids {
+            let Some(frame) = usize::try_from(frame_id)
+                .ok()
+                .and_then(|idx| self.toc.frames.get(idx))
+                .cloned()
+            else {
+                continue;
+            };
+            let search_text = match self.frame_search_text(&frame) {
+                Ok(text) => text,
+                Err(err) => {
+                    tracing::warn!("facets: unable to read frame {frame_id}: {err}");
+                    continue;
+                }
+            };
+            let content_lower = search_text.to_ascii_lowercase();
+            let ctx = EvaluationContext {
+                frame: &frame,
+                content_lower: &content_lower,
+            };
+            if parsed.evaluate(&ctx) {
+                matched.push(frame_id);
+            }
+        }
+        Ok(matched)
+    }
+
+    /// Timestamps from the time index, falling back to the TOC for frames
+    /// the index doesn't cover yet.
+    fn facet_timestamps(&mut self, frame_ids: &[FrameId]) -> Result<HashMap<FrameId, i64>> {
+        let wanted: HashSet<FrameId> = frame_ids.iter().copied().collect();
+        let mut timestamps = HashMap::with_capacity(wanted.len());
+        if let Some(manifest) = self.toc.time_index.clone() {
+            let entries =
+                time_index_read(&mut self.file, manifest.bytes_offset, manifest.bytes_length)?;
+            for entry in entries {
+                if wanted.contains(&entry.frame_id) {
+                    timestamps.insert(entry.frame_id, entry.timestamp);
+                }
+            }
+        }
+        for &frame_id in frame_ids {
+            if timestamps.contains_key(&frame_id) {
+                continue;
+            }
+            if let Some(frame) = usize::try_from(frame_id)
+                .ok()
+                .and_then(|idx| self.toc.frames.get(idx))
+            {
+                timestamps.insert(frame_id, frame.timestamp);
+            }
+        }
+        Ok(timestamps)
+    }
+}
+
+/// Values a frame contributes to a term facet; each counts once per frame.
+fn facet_values(field: &FacetField, frame: &Frame) -> BTreeSet<String> {
+    let non_empty = |value: &&String| !value.trim().is_empty();
+    match field {
+        FacetField::Tag => frame.tags.iter().filter(non_empty).cloned().collect(),
+        FacetField::Label => frame.labels.iter().filter(non_empty).cloned().collect(),
+        FacetField::Track => frame.track.iter().filter(non_empty).cloned().collect(),
+        FacetField::UriPrefix { depth } => frame
+            .uri
+            .as_deref()
+            .map(|uri| uri_prefix(uri, *depth).to_string())
+            .into_iter()
+            .collect(),
+        FacetField::Metadata { key } => metadata_value(frame, key.trim())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+            .into_iter()
+            .collect(),
+        FacetField::Timestamp { .. } => unreachable!("histograms are bucketed separately"),
+    }
+}
+
+fn count_terms(facet: &FacetRequest, frames: &[&Frame]) -> FacetResult {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut missing = 0;
+    for frame in frames {
+        let values = facet_values(&facet.field, frame);
+        if values.is_empty() {
+            missing += 1;
+        }
+        for value in values {
+            *counts.entry(value).or_default() += 1;
+        }
+    }
+
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let other = counts.iter().skip(facet.size).map(|(_, count)| count).sum();
+    counts.truncate(facet.size);
+    FacetResult {
+        field: facet.field.clone(),
+        buckets: counts
+            .into_iter()
+            .map(|(key, count)| FacetBucket {
+                key,
+                count,
+                start_ts: None,
+            })
+            .collect(),
+        other,
+        missing,
+    }
+}
+
+/// Contiguous buckets from the earliest to the latest timestamp, empty
+/// intervals included.
+fn histogram(
+    facet: &FacetRequest,
+    interval: HistogramInterval,
+    timestamps: &[i64],
+    missing: usize,
+) -> Result<FacetResult> {
+    let mut counts: BTreeMap<i64, usize> = BTreeMap::new();
+    for &ts in timestamps {
+        *counts.entry(bucket_start(ts, interval)).or_default() += 1;
+    }
+
+    let mut buckets = Vec::new();
+    if let (Some(&first), Some(&last)) = (counts.keys().next(), counts.keys().next_back()) {
+        let mut start = Some(first);
+        while let Some(current) = start.filter(|current| *current <= last) {
+            if buckets.len() == MAX_HISTOGRAM_BUCKETS {
+                return Err(MemvidError::InvalidQuery {
+                    reason: format!(
+                        "timestamp histogram exceeds {MAX_HISTOGRAM_BUCKETS} buckets; use a coarser interval"
+                    ),
+                });
+            }
+            buckets.push(FacetBucket {
+                key: timestamp_to_rfc3339(current).unwrap_or_else(|| current.to_string()),
+                count: counts.get(&current).copied().unwrap_or(0),
+                start_ts: Some(current),
+            });
+            start = next_bucket(current, interval);
+        }
+    }
+
+    Ok(FacetResult {
+        field: facet.field.clone(),
+        buckets,
+        other: 0,
+        missing,
+    })
+}
+
+/// Start of the UTC interval containing `ts`.
+fn bucket_start(ts: i64, interval: HistogramInterval) -> i64 {
+    match interval {
+        HistogramInterval::Hour => ts - ts.rem_euclid(HOUR_SECS),
+        HistogramInterval::Day => ts - ts.rem_euclid(DAY_SECS),
+        // 1970-01-01 was a Thursday, three days after a Monday.
+        HistogramInterval::Week => ts - ts.saturating_add(3 * DAY_SECS).rem_euclid(WEEK_SECS),
+        HistogramInterval::Month | HistogramInterval::Year => {
+            let Some(date) = DateTime::from_timestamp(ts, 0).map(|dt| dt.date_naive()) else {
+                return ts;
+            };
+            let month = if interval == HistogramInterval::Year {
+                1
+            } else {
+                date.month()
+            };
+            NaiveDate::from_ymd_opt(date.year(), month, 1)
+                .and_then(|first| first.and_hms_opt(0, 0, 0))
+                .map_or(ts, |start| start.and_utc().timestamp())
+        }
+    }
+}
+
+fn next_bucket(start: i64, interval: HistogramInterval) -> Option<i64> {
+    match interval {
+        HistogramInterval::Hour => start.checked_add(HOUR_SECS),
+        HistogramInterval::Day => start.checked_add(DAY_SECS),
+        HistogramInterval::Week => start.checked_add(WEEK_SECS),
+        HistogramInterval::Month | HistogramInterval::Year => {
+            let months = if interval == HistogramInterval::Year {
+                12
+            } else {
+                1
+            };
+            DateTime::from_timestamp(start, 0)
+                .and_then(|dt| dt.checked_add_months(Months::new(months)))
+                .map(|dt| dt.timestamp())
+        }
+    }
+}
+
+/// `uri` cut after `depth` path segments: `mv2://docs/a/b.md` gives
+/// `mv2://docs/` at depth 1 and `mv2://docs/a/` at depth 2. URIs with fewer
+/// segments are returned whole.
+fn uri_prefix(uri: &str, depth: usize) -> &str {
+    let mut end = uri.find("://").map_or(0, |idx| idx + 3);
+    for _ in 0..depth {
+        match uri[end..].find('/') {
+            Some(idx) => end += idx + 1,
+            None => return uri,
+        }
+    }
+    &uri[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(date: &str) -> i64 {
+        chrono::DateTime::parse_from_rfc3339(date)
+            .expect("rfc3339")
+            .timestamp()
+    }
+
+    #[test]
+    fn buckets_align_to_utc_intervals() {
+        let wednesday = ts("2024-03-13T15:42:10Z");
+        assert_eq!(
+            bucket_start(wednesday, HistogramInterval::Hour),
+            ts("2024-03-13T15:00:00Z")
+        );
+        assert_eq!(
+            bucket_start(wednesday, HistogramInterval::Week),
+            ts("2024-03-11T00:00:00Z")
+        );
+        assert_eq!(
+            bucket_start(wednesday, HistogramInterval::Month),
+            ts("2024-03-01T00:00:00Z")
+        );
+        assert_eq!(
+            bucket_start(wednesday, HistogramInterval::Year),
+            ts("2024-01-01T00:00:00Z")
+        );
+        assert_eq!(
+            next_bucket(ts("2024-01-01T00:00:00Z"), HistogramInterval::Month),
+            Some(ts("2024-02-01T00:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn histogram_fills_gaps() {
+        let facet = FacetRequest::new(FacetField::Timestamp {
+            interval: HistogramInterval::Day,
+        });
+        let values = [
+            ts("2024-03-01T08:00:00Z"),
+            ts("2024-03-01T20:00:00Z"),
+            ts("2024-03-04T01:00:00Z"),
+        ];
+        let result = histogram(&facet, HistogramInterval::Day, &values, 0).expect("histogram");
+        let counts: Vec<usize> = result.buckets.iter().map(|bucket| bucket.count).collect();
+        assert_eq!(counts, [2, 0, 0, 1]);
+        assert_eq!(result.buckets[1].start_ts, Some(ts("2024-03-02T00:00:00Z")));
+
+        let spread = [0, i64::from(u32::MAX)];
+        assert!(histogram(&facet, HistogramInterval::Hour, &spread, 0).is_err());
+    }
+
+    #[test]
+    fn uri_prefix_keeps_whole_segments() {
+        assert_eq!(uri_prefix("mv2://docs/a/b.md", 1), "mv2://docs/");
+        assert_eq!(uri_prefix("mv2://docs/a/b.md", 2), "mv2://docs/a/");
+        assert_eq!(uri_prefix("mv2://docs/a/b.md", 5), "mv2://docs/a/b.md");
+        assert_eq!(uri_prefix("notes/today", 1), "notes/");
+    }
+}
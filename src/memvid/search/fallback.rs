@@ -2,7 +2,9 @@
 
 #[cfg(feature = "temporal_track")]
 use super::helpers::attach_temporal_metadata;
-use super::helpers::{build_context, empty_search_response, parse_cursor, timestamp_to_rfc3339};
+use super::helpers::{
+    build_context, empty_search_response, parse_cursor, synonym_groups, timestamp_to_rfc3339,
+};
 use crate::lex::{LexMatch, compute_snippet_slices};
 use crate::memvid::lifecycle::Memvid;
 use crate::search::{EvaluationContext, ParsedQuery};
@@ -14,6 +16,7 @@ use crate::{MemvidError, Result};
 use std::collections::HashSet;
 use std::time::Instant;
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn search_with_lex_fallback(
     memvid: &mut Memvid,
     parsed: &ParsedQuery,
@@ -22,6 +25,7 @@ pub(super) fn search_with_lex_fallback(
     params: &SearchParams,
     start_time: Instant,
     candidate_filter: Option<&HashSet<FrameId>>,
+    watermark: FrameId,
 ) -> Result<SearchResponse> {
     let index = memvid
         .lex_index
@@ -34,12 +38,17 @@ pub(super) fn search_with_lex_fallback(
         request.scope.as_deref()
     };
 
-    let matches: Vec<LexMatch> = index.compute_matches(query_tokens, uri_filter, scope_filter);
+    // Synonyms widen each token to an OR; evaluation below applies the query
+    let groups = synonym_groups(memvid, query_tokens);
+    let matches: Vec<LexMatch> = index.compute_group_matches(&groups, uri_filter, scope_filter);
     let snippet_window = request.snippet_chars.max(80);
-    let max_snippets_per_doc = request.top_k.max(1);
+    let max_snippets_per_doc = params.top_k.max(1);
 
     let mut evaluated = Vec::new();
     for matched in &matches {
+        if matched.frame_id >= watermark {
+            continue;
+        }
         if let Some(filter) = candidate_filter {
             if !filter.contains(&matched.frame_id) {
                 continue;
@@ -141,6 +150,8 @@ pub(super) fn search_with_lex_fallback(
                 content_dates: frame_meta.content_dates.clone(),
                 entities: Vec::new(),
                 extra_metadata: frame_meta.extra_metadata.clone(),
+                media: None,
+                region: None,
                 #[cfg(feature = "temporal_track")]
                 temporal: None,
             };
@@ -175,6 +186,7 @@ pub(super) fn search_with_lex_fallback(
                 chunk_text: Some(chunk_text),
                 score: Some(matched.score),
                 metadata: Some(metadata),
+                highlights: Vec::new(),
             });
             produced += 1;
         }
@@ -200,6 +212,10 @@ pub(super) fn search_with_lex_fallback(
         context,
         next_cursor,
         engine: SearchEngineKind::LexFallback,
+        suggested_query: None,
+        facets: Vec::new(),
+        query_expansions: Vec::new(),
+        explanation: None,
     })
 }
 
@@ -210,20 +226,18 @@ pub(super) fn search_with_filters_only(
     params: &SearchParams,
     start_time: Instant,
     candidate_filter: Option<&HashSet<FrameId>>,
+    watermark: FrameId,
 ) -> Result<SearchResponse> {
     let mut matches = Vec::new();
     let snippet_limit = request.snippet_chars.max(80);
-    let frames: Vec<Frame> = if let Some(filter) = candidate_filter {
-        memvid
-            .toc
-            .frames
-            .iter()
-            .filter(|frame| filter.contains(&frame.id))
-            .cloned()
-            .collect()
-    } else {
-        memvid.toc.frames.clone()
-    };
+    let frames: Vec<Frame> = memvid
+        .toc
+        .frames
+        .iter()
+        .filter(|frame| frame.id < watermark)
+        .filter(|frame| candidate_filter.is_none_or(|filter| filter.contains(&frame.id)))
+        .cloned()
+        .collect();
 
     for frame in frames {
         let search_text = memvid.frame_search_text(&frame)?;
@@ -250,6 +264,10 @@ pub(super) fn search_with_filters_only(
             context: build_context(&[]),
             next_cursor: None,
             engine: SearchEngineKind::LexFallback,
+            suggested_query: None,
+            facets: Vec::new(),
+            query_expansions: Vec::new(),
+            explanation: None,
         });
     }
 
@@ -273,6 +291,8 @@ pub(super) fn search_with_filters_only(
             content_dates: frame.content_dates.clone(),
             entities: Vec::new(),
             extra_metadata: frame.extra_metadata.clone(),
+            media: None,
+            region: None,
             #[cfg(feature = "temporal_track")]
             temporal: None,
         };
@@ -298,6 +318,7 @@ pub(super) fn search_with_filters_only(
             chunk_text: Some(snippet),
             score: None,
             metadata: Some(metadata),
+            highlights: Vec::new(),
         });
         produced += 1;
     }
@@ -322,5 +343,9 @@ pub(super) fn search_with_filters_only(
         context,
         next_cursor,
         engine: SearchEngineKind::LexFallback,
+        suggested_query: None,
+        facets: Vec::new(),
+        query_expansions: Vec::new(),
+        explanation: None,
     })
 }
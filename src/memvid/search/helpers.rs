@@ -3,6 +3,7 @@
 use crate::MemvidError;
 use crate::Result;
 use crate::memvid::lifecycle::Memvid;
+use crate::ocr::OcrWord;
 #[cfg(not(feature = "temporal_track"))]
 #[allow(unused_imports)]
 use crate::types::FrameId;
@@ -10,7 +11,10 @@ use crate::types::FrameId;
 use crate::types::{
     FrameId, SearchHitTemporal, SearchHitTemporalAnchor, SearchHitTemporalMention, TemporalMention,
 };
-use crate::types::{SearchEngineKind, SearchHit, SearchHitMetadata, SearchParams, SearchResponse};
+use crate::types::{
+    SearchEngineKind, SearchHit, SearchHitMedia, SearchHitMetadata, SearchHitRegion, SearchParams,
+    SearchResponse,
+};
 #[cfg(feature = "temporal_track")]
 use std::collections::HashMap;
 #[cfg(feature = "temporal_track")]
@@ -33,6 +37,10 @@ pub(super) fn empty_search_response(
         context: String::new(),
         next_cursor: None,
         engine,
+        suggested_query: None,
+        facets: Vec::new(),
+        query_expansions: Vec::new(),
+        explanation: None,
     }
 }
 
@@ -180,6 +188,21 @@ fn render_hit(hit: &SearchHit) -> String {
     )
 }
 
+/// Lex index token groups: each query token with the terms that can stand in
+/// for it under the memory's synonyms.
+pub(super) fn synonym_groups(memvid: &Memvid, query_tokens: &[String]) -> Vec<Vec<String>> {
+    query_tokens
+        .iter()
+        .map(|token| {
+            let mut group = vec![token.clone()];
+            if let Some(track) = memvid.toc.synonyms.as_ref() {
+                group.extend(track.matching_terms(token));
+            }
+            group
+        })
+        .collect()
+}
+
 pub(super) fn collect_token_occurrences(
     content_lower: &str,
     tokens: &[String],
@@ -422,3 +445,135 @@ pub(super) fn enrich_hits_with_entities(hits: &mut [SearchHit], memvid: &Memvid)
         }
     }
 }
+
+/// Report the media offset of hits in transcript frames.
+///
+/// A transcript frame's text holds one cue per line and its audio metadata one
+/// segment per cue. The line with the most query terms picks the segment; hits
+/// without a matching line use the line their range starts on.
+pub(super) fn attach_media_offsets(
+    memvid: &mut Memvid,
+    hits: &mut [SearchHit],
+    query_tokens: &[String],
+) -> Result<()> {
+    for hit in hits.iter_mut() {
+        let Some(frame) = usize::try_from(hit.frame_id)
+            .ok()
+            .and_then(|idx| memvid.toc.frames.get(idx))
+            .filter(|frame| {
+                frame
+                    .metadata
+                    .as_ref()
+                    .and_then(|meta| meta.audio.as_ref())
+                    .is_some_and(|audio| !audio.segments.is_empty())
+            })
+            .cloned()
+        else {
+            continue;
+        };
+        // Hit ranges are relative to the chunk's parent document
+        let (text, offset) = match (&hit.chunk_text, hit.chunk_range) {
+            (Some(text), Some((start, _))) => (text.clone(), hit.range.0.saturating_sub(start)),
+            _ => (memvid.frame_content(&frame)?, hit.range.0),
+        };
+        let Some(segments) = frame
+            .metadata
+            .as_ref()
+            .and_then(|meta| meta.audio.as_ref())
+            .map(|audio| &audio.segments)
+        else {
+            continue;
+        };
+
+        let mut best: Option<(usize, usize)> = None;
+        for (line, content) in text.lines().take(segments.len()).enumerate() {
+            let content = content.to_lowercase();
+            let words: Vec<&str> = content
+                .split(|ch: char| !ch.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .collect();
+            // Prefix matching lets "lighthouse" find "lighthouses"
+            let score = query_tokens
+                .iter()
+                .filter(|token| words.iter().any(|word| word.starts_with(token.as_str())))
+                .count();
+            if score > 0 && best.is_none_or(|(_, top)| score > top) {
+                best = Some((line, score));
+            }
+        }
+        let line = best.map(|(line, _)| line).or_else(|| {
+            text.get(..offset.min(text.len()))
+                .map(|prefix| prefix.matches('\n').count())
+        });
+
+        if let Some(segment) = line.and_then(|line| segments.get(line)) {
+            let metadata = hit.metadata.get_or_insert_with(SearchHitMetadata::default);
+            metadata.media = Some(SearchHitMedia {
+                start_seconds: segment.start_seconds,
+                end_seconds: segment.end_seconds,
+                label: segment.label.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Attach the page region a hit matched on for frames with recognized text.
+pub(super) fn attach_ocr_regions(memvid: &Memvid, hits: &mut [SearchHit], query_tokens: &[String]) {
+    for hit in hits.iter_mut() {
+        let Some(words) = memvid.ocr_layout.words(hit.frame_id) else {
+            continue;
+        };
+
+        // Longest run of consecutive matching words on one page
+        let matches = |word: &OcrWord| {
+            let word = word.text.to_lowercase();
+            word.split(|ch: char| !ch.is_alphanumeric())
+                .filter(|part| !part.is_empty())
+                .any(|part| {
+                    query_tokens
+                        .iter()
+                        .any(|token| part.starts_with(token.as_str()))
+                })
+        };
+        let mut best: Option<(usize, usize)> = None;
+        let mut run_start = None;
+        for (idx, word) in words.iter().enumerate() {
+            let continues = run_start.is_some_and(|start: usize| words[start].page == word.page);
+            if !matches(word) {
+                run_start = None;
+                continue;
+            }
+            let start = if continues {
+                run_start.unwrap_or(idx)
+            } else {
+                idx
+            };
+            run_start = Some(start);
+            if best.is_none_or(|(from, to)| idx - start > to - from) {
+                best = Some((start, idx));
+            }
+        }
+
+        if let Some((from, to)) = best {
+            let run = &words[from..=to];
+            let bbox = run
+                .iter()
+                .skip(1)
+                .fold(run[0].bbox, |bbox, word| bbox.union(word.bbox));
+            let metadata = hit.metadata.get_or_insert_with(SearchHitMetadata::default);
+            metadata.region = Some(SearchHitRegion {
+                page: run[0].page,
+                x: bbox.x,
+                y: bbox.y,
+                width: bbox.width,
+                height: bbox.height,
+                text: run
+                    .iter()
+                    .map(|word| word.text.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            });
+        }
+    }
+}
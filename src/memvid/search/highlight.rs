@@ -0,0 +1,98 @@
+//! Attaches highlight fragments to search hits.
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use crate::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::search::{Highlighter, frame_language, parse_query};
+use crate::types::{
+    ContentLanguage, FrameId, FrameRole, HighlightFragment, HighlightOptions, SearchHit,
+};
+
+impl Memvid {
+    /// Fill `highlights` on each hit with its best fragments for `query`.
+    ///
+    /// Fragments are selected from the whole frame text that hit ranges index
+    /// into, so matches outside the returned chunk or snippet are considered
+    /// too. Each frame is decoded once even when several hits point into it.
+    pub(crate) fn highlight_hits(
+        &mut self,
+        hits: &mut [SearchHit],
+        query: &str,
+        options: &HighlightOptions,
+    ) -> Result<()> {
+        if hits.is_empty() {
+            return Ok(());
+        }
+        if self.tantivy.is_none() {
+            if !self.lex_enabled {
+                return Ok(());
+            }
+            self.init_tantivy()?;
+        }
+        // Queries the lexer rejects (e.g. rewritten ask questions) simply get no highlights
+        let Ok(parsed) = parse_query(query) else {
+            return Ok(());
+        };
+
+        let mut texts: HashMap<FrameId, (Option<ContentLanguage>, String)> = HashMap::new();
+        for hit in hits.iter() {
+            if let Entry::Vacant(entry) = texts.entry(hit.frame_id) {
+                entry.insert(self.highlight_text(hit.frame_id)?);
+            }
+        }
+
+        let Some(engine) = self.tantivy.as_ref() else {
+            return Ok(());
+        };
+        // Query terms are analysed like the frame text they are matched
+        // against, so each indexed language gets its own highlighter.
+        let mut highlighters: HashMap<Option<ContentLanguage>, Highlighter> = HashMap::new();
+        let mut fragments: HashMap<FrameId, Vec<HighlightFragment>> = HashMap::new();
+        for hit in hits.iter_mut() {
+            let highlights = fragments.entry(hit.frame_id).or_insert_with(|| {
+                let Some((language, text)) = texts.get(&hit.frame_id) else {
+                    return Vec::new();
+                };
+                let language = engine.indexed_language(*language);
+                let highlighter = highlighters.entry(language).or_insert_with(|| {
+                    Highlighter::new(&parsed, |text| engine.analyse_text_in(language, text))
+                });
+                if highlighter.is_empty() {
+                    return Vec::new();
+                }
+                let tokens = engine.analyse_with_offsets(language, text);
+                highlighter.fragments(text, &tokens, options, 0, 0)
+            });
+            hit.highlights.clone_from(highlights);
+        }
+        Ok(())
+    }
+
+    /// Frame text that hit ranges index into, with the frame's language.
+    fn highlight_text(&mut self, frame_id: FrameId) -> Result<(Option<ContentLanguage>, String)> {
+        let frame = self.frame_by_id(frame_id)?;
+        // Chunks of a manifest document are addressed within the joined chunk payloads
+        let parent = match (frame.role, frame.parent_id) {
+            (FrameRole::DocumentChunk, Some(parent_id)) => self
+                .frame_by_id(parent_id)
+                .ok()
+                .filter(|parent| parent.chunk_manifest.is_some()),
+            _ => None,
+        };
+        let text = match parent {
+            Some(parent) => {
+                let bytes: Vec<u8> = self
+                    .document_chunk_payloads(&parent)?
+                    .into_iter()
+                    .flat_map(|(_, bytes)| bytes)
+                    .collect();
+                String::from_utf8(bytes)
+                    .unwrap_or_else(|err| String::from_utf8_lossy(err.as_bytes()).into_owned())
+            }
+            None => self.frame_content(&frame)?,
+        };
+        Ok((frame_language(&frame, &text), text))
+    }
+}
@@ -11,15 +11,35 @@ use std::collections::{BTreeSet, HashSet};
 use std::time::Instant;
 
 use crate::memvid::lifecycle::Memvid;
+#[cfg(feature = "lex")]
+use crate::types::DiversifyOptions;
 use crate::types::{FrameId, SearchEngineKind, SearchParams, SearchRequest, SearchResponse};
+#[cfg(feature = "lex")]
+use crate::types::{SearchSort, SearchStage};
 use crate::{MemvidError, Result};
 
 mod api;
 mod builders;
 #[cfg(feature = "lex")]
+mod cursor;
+#[cfg(feature = "lex")]
+pub(crate) mod diversify;
+#[cfg(feature = "lex")]
+mod explain;
+#[cfg(feature = "lex")]
+mod facets;
+#[cfg(feature = "lex")]
 mod fallback;
 pub(crate) mod helpers;
 #[cfg(feature = "lex")]
+mod highlight;
+mod schema;
+#[cfg(feature = "lex")]
+mod sorted;
+#[cfg(feature = "lex")]
+mod suggest;
+mod synonyms;
+#[cfg(feature = "lex")]
 mod tantivy;
 #[cfg(any(feature = "lex", feature = "temporal_track"))]
 mod time_filter;
@@ -29,6 +49,10 @@ pub use api::{
     DEFAULT_MAX_INDEX_PAYLOAD, is_frame_text_indexable, is_text_indexable_mime, max_index_payload,
 };
 
+#[cfg(feature = "lex")]
+use cursor::{CursorPosition, SearchCursor, resume_ranked};
+#[cfg(feature = "lex")]
+use explain::SearchTrace;
 #[cfg(feature = "lex")]
 use fallback::{search_with_filters_only, search_with_lex_fallback};
 use helpers::{build_context, empty_search_response};
@@ -44,6 +68,18 @@ use time_filter::frame_ids_in_date_range;
 #[cfg(feature = "lex")]
 impl Memvid {
     pub fn search(&mut self, request: SearchRequest) -> Result<SearchResponse> {
+        let mut trace = SearchTrace::new(request.explain, None);
+        let mut response = self.search_traced(request, &mut trace)?;
+        response.explanation = trace.into_explanation(&response.engine);
+        Ok(response)
+    }
+
+    /// [`Self::search`], recording stage counts into `trace`.
+    fn search_traced(
+        &mut self,
+        request: SearchRequest,
+        trace: &mut SearchTrace,
+    ) -> Result<SearchResponse> {
         if !self.lex_enabled {
             return Err(MemvidError::LexNotEnabled);
         }
@@ -55,9 +91,106 @@ impl Memvid {
             self.init_tantivy()?;
         }
 
+        // Diversification needs spare candidates: over-fetch, then trim back to top_k.
+        if let Some(options) = request
+            .diversify
+            .clone()
+            .filter(DiversifyOptions::is_active)
+        {
+            let top_k = request.top_k.max(1);
+            let highlight = request.highlight.clone();
+            let frame_count = FrameId::try_from(self.toc.frames.len()).unwrap_or(FrameId::MAX);
+            let cursor = SearchCursor::decode(
+                request.cursor.as_deref(),
+                request.sort,
+                self.generation,
+                frame_count,
+            )?;
+            let mut widened = request;
+            widened.diversify = None;
+            widened.highlight = None;
+            widened.top_k = top_k.saturating_mul(diversify::DIVERSIFY_OVERFETCH);
+            let sort = widened.sort;
+            let mut response = self.search_traced(widened, trace)?;
+            let window: Vec<(FrameId, (usize, usize), Option<f32>)> = response
+                .hits
+                .iter()
+                .map(|hit| (hit.frame_id, hit.range, hit.score))
+                .collect();
+            let before: Vec<FrameId> = window.iter().map(|(frame_id, ..)| *frame_id).collect();
+
+            // Diversify the whole window so dropped duplicates can be told
+            // apart from hits that only fell past top_k.
+            self.diversify_hits(&mut response.hits, &options, window.len())?;
+            let duplicates = window.len() - response.hits.len();
+            let more_in_window = response.hits.len() > top_k;
+            response.hits.truncate(top_k);
+            trace.hits(
+                SearchStage::Diversify,
+                &before,
+                &response.hits,
+                "removed as a near-duplicate or by MMR",
+            );
+
+            // The next page resumes after the last window hit this page
+            // consumed, so no hit is served twice.
+            let consumed = response
+                .hits
+                .iter()
+                .filter_map(|hit| {
+                    window.iter().position(|(frame_id, range, _)| {
+                        (*frame_id, *range) == (hit.frame_id, hit.range)
+                    })
+                })
+                .max()
+                .map_or(0, |idx| idx + 1);
+            let has_more = more_in_window || response.next_cursor.is_some();
+            response.total_hits = response.total_hits.saturating_sub(duplicates);
+            response.next_cursor = if has_more && consumed > 0 {
+                let generation = cursor.as_ref().map_or(self.generation, |c| c.generation);
+                let watermark = cursor.as_ref().map_or(frame_count, |c| c.watermark);
+                let (frame_id, range, score) = window[consumed - 1];
+                let position = if sort == SearchSort::Relevance {
+                    let served = cursor.map_or(0, |c| c.position.served());
+                    Some(CursorPosition::ScoreAfter {
+                        score: score.map(f32::to_bits),
+                        frame_id,
+                        start: range.0,
+                        served: served + consumed,
+                    })
+                } else {
+                    usize::try_from(frame_id)
+                        .ok()
+                        .and_then(|idx| self.toc.frames.get(idx))
+                        .map(|frame| CursorPosition::After {
+                            key: sorted::sort_key(frame, sort),
+                            frame_id,
+                        })
+                };
+                position.map(|position| {
+                    SearchCursor {
+                        sort,
+                        generation,
+                        watermark,
+                        position,
+                    }
+                    .encode()
+                })
+            } else {
+                None
+            };
+
+            if let Some(highlight) = highlight {
+                self.highlight_hits(&mut response.hits, &response.query, &highlight)?;
+            }
+            response.params.top_k = top_k;
+            response.context = build_context(&response.hits);
+            return Ok(response);
+        }
+
         let start_time = Instant::now();
         // parse_query can return structured tokens; we only keep non-empty, lower-cased terms.
-        let parsed = crate::search::parse_query(&request.query)?;
+        let mut parsed = crate::search::parse_query(&request.query)?;
         let mut query_tokens = parsed.text_tokens();
         query_tokens.retain(|token| !token.trim().is_empty());
         query_tokens = query_tokens
@@ -75,34 +208,55 @@ impl Memvid {
             });
         }
 
+        facets::validate_facets(&request.facets)?;
+
+        // Synonyms widen the evaluated query; tokens stay the user's own and
+        // the lex index paths widen each one to an OR over its synonyms.
+        let query_expansions = self
+            .toc
+            .synonyms
+            .as_ref()
+            .map(|track| parsed.expand_synonyms(track))
+            .unwrap_or_default();
+        // Sketches only see frame text, not synonyms recorded at ingestion
+        let synonym_matched = !query_expansions.is_empty()
+            || self.toc.synonyms.as_ref().is_some_and(|track| {
+                query_tokens
+                    .iter()
+                    .any(|token| !track.matching_terms(token).is_empty())
+            });
+        trace.set_query_tree(|| parsed.describe());
+
         let params = SearchParams {
             top_k: request.top_k,
             snippet_chars: request.snippet_chars,
             cursor: request.cursor.clone(),
         };
 
+        // Pages after the first stay pinned to the frames that existed when it was served
+        let frame_count = FrameId::try_from(self.toc.frames.len()).unwrap_or(FrameId::MAX);
+        let cursor = SearchCursor::decode(
+            request.cursor.as_deref(),
+            request.sort,
+            self.generation,
+            frame_count,
+        )?;
+        let generation = cursor.as_ref().map_or(self.generation, |c| c.generation);
+        let watermark = cursor.as_ref().map_or(frame_count, |c| c.watermark);
+        let position = cursor.map(|c| c.position);
+
         let date_range = parsed.required_date_range();
         #[allow(unused_mut)]
         let mut candidate_filter: Option<HashSet<FrameId>> = if let Some(ref range) = date_range {
             if range.is_empty() {
-                let elapsed = start_time.elapsed().as_millis();
-                return Ok(empty_search_response(
-                    request.query.clone(),
-                    params.clone(),
-                    elapsed,
-                    SearchEngineKind::Tantivy,
-                ));
+                trace.empty(SearchStage::DateFilter, DATE_REASON);
+                return Ok(no_match_response(&request, &params, start_time));
             }
             match frame_ids_in_date_range(self, range)? {
                 Some(ids) => {
                     if ids.is_empty() {
-                        let elapsed = start_time.elapsed().as_millis();
-                        return Ok(empty_search_response(
-                            request.query.clone(),
-                            params.clone(),
-                            elapsed,
-                            SearchEngineKind::Tantivy,
-                        ));
+                        trace.empty(SearchStage::DateFilter, DATE_REASON);
+                        return Ok(no_match_response(&request, &params, start_time));
                     }
                     Some(ids.into_iter().collect())
                 }
@@ -111,6 +265,13 @@ impl Memvid {
         } else {
             None
         };
+        if date_range.is_some() {
+            trace.filter(
+                SearchStage::DateFilter,
+                candidate_filter.as_ref(),
+                DATE_REASON,
+            );
+        }
 
         #[cfg(feature = "temporal_track")]
         if let Some(ref temporal_filter) = request.temporal {
@@ -118,13 +279,8 @@ impl Memvid {
                 match time_filter::frame_ids_for_temporal_filter(self, temporal_filter)? {
                     Some(ids) => {
                         if ids.is_empty() {
-                            let elapsed = start_time.elapsed().as_millis();
-                            return Ok(empty_search_response(
-                                request.query.clone(),
-                                params.clone(),
-                                elapsed,
-                                SearchEngineKind::Tantivy,
-                            ));
+                            trace.empty(SearchStage::TemporalFilter, TEMPORAL_REASON);
+                            return Ok(no_match_response(&request, &params, start_time));
                         }
                         let new_set: HashSet<FrameId> = ids.into_iter().collect();
                         candidate_filter = match candidate_filter {
@@ -134,13 +290,8 @@ impl Memvid {
                                     .filter(|id| new_set.contains(id))
                                     .collect();
                                 if filtered.is_empty() {
-                                    let elapsed = start_time.elapsed().as_millis();
-                                    return Ok(empty_search_response(
-                                        request.query.clone(),
-                                        params.clone(),
-                                        elapsed,
-                                        SearchEngineKind::Tantivy,
-                                    ));
+                                    trace.empty(SearchStage::TemporalFilter, TEMPORAL_REASON);
+                                    return Ok(no_match_response(&request, &params, start_time));
                                 }
                                 Some(filtered)
                             }
@@ -149,6 +300,11 @@ impl Memvid {
                     }
                     None => {}
                 }
+                trace.filter(
+                    SearchStage::TemporalFilter,
+                    candidate_filter.as_ref(),
+                    TEMPORAL_REASON,
+                );
             }
         }
 
@@ -156,13 +312,8 @@ impl Memvid {
         if request.as_of_frame.is_some() || request.as_of_ts.is_some() {
             let replay_ids = self.get_replay_frame_ids(&request)?;
             if replay_ids.is_empty() {
-                let elapsed = start_time.elapsed().as_millis();
-                return Ok(empty_search_response(
-                    request.query.clone(),
-                    params.clone(),
-                    elapsed,
-                    SearchEngineKind::Tantivy,
-                ));
+                trace.empty(SearchStage::AsOf, AS_OF_REASON);
+                return Ok(no_match_response(&request, &params, start_time));
             }
             let replay_set: HashSet<FrameId> = replay_ids.into_iter().collect();
             candidate_filter = match candidate_filter {
@@ -172,25 +323,40 @@ impl Memvid {
                         .filter(|id| replay_set.contains(id))
                         .collect();
                     if filtered.is_empty() {
-                        let elapsed = start_time.elapsed().as_millis();
-                        return Ok(empty_search_response(
-                            request.query.clone(),
-                            params.clone(),
-                            elapsed,
-                            SearchEngineKind::Tantivy,
-                        ));
+                        trace.empty(SearchStage::AsOf, AS_OF_REASON);
+                        return Ok(no_match_response(&request, &params, start_time));
                     }
                     Some(filtered)
                 }
                 None => Some(replay_set),
             };
+            trace.filter(SearchStage::AsOf, candidate_filter.as_ref(), AS_OF_REASON);
+        }
+
+        // Engines take the watermark as a frame-id bound rather than a candidate set
+        if watermark < frame_count {
+            trace.bound(
+                SearchStage::Watermark,
+                candidate_filter.as_ref(),
+                watermark,
+                "appended after the first page was served",
+            );
         }
 
+        // Facets count the full match set, so they must not see the sketch shortlist
+        let facet_filter = if request.facets.is_empty() {
+            None
+        } else {
+            candidate_filter.clone()
+        };
+
         // SKETCH PRE-FILTER: Use sketch track for fast candidate generation if available
         // This dramatically reduces the number of documents sent to BM25/Tantivy
         if self.has_sketches()
+            && request.sort == SearchSort::Relevance
             && has_text_terms
             && !request.no_sketch
+            && !synonym_matched
             && !crate::search::contains_cjk(&request.query)
         {
             let sketch_start = Instant::now();
@@ -230,57 +396,141 @@ impl Memvid {
                     }
                     None => Some(sketch_set),
                 };
+                trace.filter(
+                    SearchStage::SketchPrefilter,
+                    candidate_filter.as_ref(),
+                    "not shortlisted by the sketch pre-filter (retry with no_sketch)",
+                );
             }
         }
 
-        let mut response = if let Some(response) = try_tantivy_search(
-            self,
-            &parsed,
-            &query_tokens,
-            &request,
-            &params,
-            start_time,
-            candidate_filter.as_ref(),
-        )? {
+        let mut response = if request.sort == SearchSort::Relevance {
+            // Rank a window covering every page served so far plus this one,
+            // then resume after the cursor's last hit within it.
+            let page_size = request.top_k.max(1);
+            let served = position.map_or(0, CursorPosition::served);
+            let mut window_request = request.clone();
+            window_request.cursor = None;
+            window_request.top_k = served.saturating_add(page_size);
+            let mut response = self.search_by_relevance(
+                &parsed,
+                &query_tokens,
+                &window_request,
+                &params,
+                start_time,
+                candidate_filter.as_ref(),
+                watermark,
+            )?;
+            if let Some(position) = position {
+                resume_ranked(&mut response.hits, position);
+            }
+            let has_more = response.hits.len() > page_size || response.next_cursor.is_some();
+            response.hits.truncate(page_size);
+            for (idx, hit) in response.hits.iter_mut().enumerate() {
+                hit.rank = idx + 1;
+            }
+            response.context = build_context(&response.hits);
+            let served = served + response.hits.len();
+            response.next_cursor = response.hits.last().filter(|_| has_more).map(|hit| {
+                SearchCursor {
+                    sort: request.sort,
+                    generation,
+                    watermark,
+                    position: CursorPosition::after_hit(hit, served),
+                }
+                .encode()
+            });
             response
         } else {
-            self.ensure_lex_index()?;
-            if has_text_terms {
-                search_with_lex_fallback(
-                    self,
-                    &parsed,
-                    &query_tokens,
-                    &request,
-                    &params,
-                    start_time,
-                    candidate_filter.as_ref(),
-                )?
-            } else {
-                search_with_filters_only(
-                    self,
-                    &parsed,
-                    &request,
-                    &params,
-                    start_time,
-                    candidate_filter.as_ref(),
-                )?
-            }
+            let after = match position {
+                Some(CursorPosition::After { key, frame_id }) => Some((key, frame_id)),
+                _ => None,
+            };
+            let (mut response, next_after) = sorted::search_sorted(
+                self,
+                &parsed,
+                &query_tokens,
+                &request,
+                &params,
+                start_time,
+                candidate_filter.as_ref(),
+                after,
+                watermark,
+            )?;
+            response.next_cursor = next_after.map(|(key, frame_id)| {
+                SearchCursor {
+                    sort: request.sort,
+                    generation,
+                    watermark,
+                    position: CursorPosition::After { key, frame_id },
+                }
+                .encode()
+            });
+            response
         };
 
-        self.apply_acl_to_search_hits(
+        let engine_stage = if response.engine == SearchEngineKind::LexFallback {
+            SearchStage::LexFallback
+        } else {
+            SearchStage::Engine
+        };
+        trace.count(engine_stage, response.total_hits);
+        let before_acl: Vec<FrameId> = response.hits.iter().map(|hit| hit.frame_id).collect();
+        let acl_stats = self.apply_acl_to_search_hits(
             &mut response.hits,
             request.acl_context.as_ref(),
             request.acl_enforcement_mode,
         )?;
-        if request.acl_enforcement_mode == crate::types::AclEnforcementMode::Enforce {
+        if request.acl_enforcement_mode == crate::types::AclEnforcementMode::Audit {
+            if request.acl_context.is_some() {
+                trace.count(SearchStage::AclAudit, acl_stats.allowed);
+            }
+        } else {
             response.total_hits = response.hits.len();
             response.context = build_context(&response.hits);
+            trace.hits(
+                SearchStage::Acl,
+                &before_acl,
+                &response.hits,
+                "denied by the ACL context",
+            );
+        }
+        trace.count(SearchStage::Pagination, response.hits.len());
+
+        if !request.facets.is_empty() {
+            response.facets = self.compute_facets(
+                &parsed,
+                &query_tokens,
+                &request,
+                facet_filter.as_ref(),
+                watermark,
+                &response.engine,
+            )?;
+        }
+
+        if let Some(options) = request.highlight.as_ref() {
+            self.highlight_hits(&mut response.hits, &request.query, options)?;
         }
 
+        // Offer a "did you mean" rewrite when the query found little
+        if has_text_terms && response.hits.len() < request.top_k.max(1) {
+            response.suggested_query = match self.suggest_query(&request.query) {
+                Ok(suggestion) => suggestion,
+                Err(err) => {
+                    tracing::warn!("query suggestion failed: {err}");
+                    None
+                }
+            };
+        }
+
+        response.query_expansions = query_expansions;
+
         // Enrich hits with Logic-Mesh entities if mesh is available
         if self.has_logic_mesh() {
             helpers::enrich_hits_with_entities(&mut response.hits, self);
         }
+        helpers::attach_media_offsets(self, &mut response.hits, &query_tokens)?;
+        helpers::attach_ocr_regions(self, &mut response.hits, &query_tokens);
 
         // Record the search action if a replay session is active
         #[cfg(feature = "replay")]
@@ -296,6 +546,78 @@ impl Memvid {
 
         Ok(response)
     }
+
+    /// Ranked search through Tantivy, falling back to the in-memory lex index.
+    fn search_by_relevance(
+        &mut self,
+        parsed: &crate::search::ParsedQuery,
+        query_tokens: &[String],
+        request: &SearchRequest,
+        params: &SearchParams,
+        start_time: Instant,
+        candidate_filter: Option<&HashSet<FrameId>>,
+        watermark: FrameId,
+    ) -> Result<SearchResponse> {
+        if let Some(response) = try_tantivy_search(
+            self,
+            parsed,
+            query_tokens,
+            request,
+            params,
+            start_time,
+            candidate_filter,
+            watermark,
+        )? {
+            return Ok(response);
+        }
+        self.ensure_lex_index()?;
+        if query_tokens.is_empty() {
+            search_with_filters_only(
+                self,
+                parsed,
+                request,
+                params,
+                start_time,
+                candidate_filter,
+                watermark,
+            )
+        } else {
+            search_with_lex_fallback(
+                self,
+                parsed,
+                query_tokens,
+                request,
+                params,
+                start_time,
+                candidate_filter,
+                watermark,
+            )
+        }
+    }
+}
+
+#[cfg(feature = "lex")]
+const DATE_REASON: &str = "outside the query's date range";
+#[cfg(all(feature = "lex", feature = "temporal_track"))]
+const TEMPORAL_REASON: &str = "excluded by the temporal filter";
+#[cfg(feature = "lex")]
+const AS_OF_REASON: &str = "outside the as_of time-travel view";
+
+/// Response for a query whose candidate set is empty before any engine runs.
+#[cfg(feature = "lex")]
+fn no_match_response(
+    request: &SearchRequest,
+    params: &SearchParams,
+    start_time: Instant,
+) -> SearchResponse {
+    let mut response = empty_search_response(
+        request.query.clone(),
+        params.clone(),
+        start_time.elapsed().as_millis(),
+        SearchEngineKind::Tantivy,
+    );
+    response.facets = facets::empty_facets(&request.facets);
+    response
 }
 
 #[cfg(not(feature = "lex"))]
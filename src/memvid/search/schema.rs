@@ -0,0 +1,45 @@
+//! Per-memory lexical search schema: title boost and typed metadata fields.
+
+use crate::Result;
+use crate::memvid::lifecycle::Memvid;
+#[cfg(feature = "lex")]
+use crate::search::TantivyEngine;
+use crate::types::SearchSchema;
+
+impl Memvid {
+    /// Lexical search schema in effect (the default when none was set).
+    #[must_use]
+    pub fn search_schema(&self) -> SearchSchema {
+        self.toc.search_schema.clone().unwrap_or_default()
+    }
+
+    /// Replace the lexical search schema.
+    ///
+    /// Declared metadata keys become `meta.<key>` Tantivy fields, so changing
+    /// them rebuilds the index from the stored frames; a boost-only change is
+    /// applied in place. Persisted on the next commit.
+    pub fn set_search_schema(&mut self, schema: SearchSchema) -> Result<()> {
+        self.ensure_writable()?;
+        schema.validate()?;
+        if self.search_schema() == schema {
+            return Ok(());
+        }
+        self.toc.search_schema = (schema != SearchSchema::default()).then(|| schema.clone());
+        self.dirty = true;
+
+        #[cfg(feature = "lex")]
+        if self.lex_enabled {
+            let reusable = self
+                .tantivy
+                .as_mut()
+                .is_some_and(|engine| engine.try_apply_schema(&schema));
+            if !reusable {
+                let mut engine = TantivyEngine::create(&schema)?;
+                self.rebuild_tantivy_engine(&mut engine)?;
+                self.tantivy = Some(engine);
+                self.tantivy_dirty = true;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,281 @@
+//! Timestamp and frame-id ordered search with search-after paging.
+//!
+//! Candidates come from Tantivy (or from the TOC when no Tantivy engine is
+//! loaded), are ordered by their TOC sort key and go through the same
+//! in-memory query evaluation as ranked hits. Every candidate is evaluated on
+//! each page so `total_hits` counts exactly what paging can return; the cost
+//! is linear in the candidate count, not in the page size. Each frame yields
+//! one hit, snippeted around its first match.
+
+use std::collections::HashSet;
+use std::time::Instant;
+
+#[cfg(feature = "temporal_track")]
+use super::helpers::attach_temporal_metadata;
+use super::helpers::{build_context, collect_token_occurrences, timestamp_to_rfc3339};
+use super::tantivy::uri_matches;
+use crate::Result;
+use crate::lex::compute_snippet_slices;
+use crate::memvid::frame::ChunkInfo;
+use crate::memvid::lifecycle::Memvid;
+use crate::search::{EvaluationContext, LanguageTokens, ParsedQuery};
+use crate::types::{
+    Frame, FrameId, FrameStatus, SearchEngineKind, SearchHit, SearchHitMetadata, SearchParams,
+    SearchRequest, SearchResponse, SearchSort,
+};
+
+/// Run a sorted search. Returns the page and, when more hits follow, the
+/// `(key, frame_id)` to resume after.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn search_sorted(
+    memvid: &mut Memvid,
+    parsed: &ParsedQuery,
+    query_tokens: &[String],
+    request: &SearchRequest,
+    params: &SearchParams,
+    start_time: Instant,
+    candidate_filter: Option<&HashSet<FrameId>>,
+    after: Option<(i64, FrameId)>,
+    watermark: FrameId,
+) -> Result<(SearchResponse, Option<(i64, FrameId)>)> {
+    let sort = request.sort;
+    let page_size = request.top_k.max(1);
+    let uri_filter = request.uri.as_deref();
+    let scope_filter = if uri_filter.is_some() {
+        None
+    } else {
+        request.scope.as_deref()
+    };
+    let descending = sort == SearchSort::TimestampDesc;
+
+    // Tantivy narrows the candidates; every candidate is then evaluated like
+    // a ranked hit, so the total counts exactly the frames pages can return.
+    let (engine, candidates, snippet_tokens) = if let Some(tantivy) = memvid.tantivy.as_ref() {
+        let frame_filter: Option<Vec<FrameId>> =
+            candidate_filter.map(|set| set.iter().copied().collect());
+        let ids = tantivy.matching_frame_ids(
+            parsed,
+            uri_filter,
+            scope_filter,
+            frame_filter.as_deref(),
+            watermark,
+        )?;
+        let candidates = toc_candidates(memvid, sort, watermark, Some(&ids.into_iter().collect()));
+        (
+            SearchEngineKind::Tantivy,
+            candidates,
+            tantivy.analyse_tokens(query_tokens),
+        )
+    } else {
+        (
+            SearchEngineKind::LexFallback,
+            toc_candidates(memvid, sort, watermark, candidate_filter),
+            LanguageTokens::unanalysed(query_tokens),
+        )
+    };
+
+    // The total covers pages before the cursor too.
+    let mut total_hits = 0;
+    let mut matched: Vec<(i64, Frame)> = Vec::new();
+    for (key, frame_id) in candidates {
+        let Some(frame) = matching_frame(memvid, frame_id, parsed, uri_filter, scope_filter) else {
+            continue;
+        };
+        total_hits += 1;
+        if matched.len() <= page_size && is_after((key, frame_id), after, descending) {
+            matched.push((key, frame));
+        }
+    }
+
+    let next_after = if matched.len() > page_size {
+        matched.truncate(page_size);
+        matched.last().map(|(key, frame)| (*key, frame.id))
+    } else {
+        None
+    };
+
+    let snippet_window = request.snippet_chars.max(80);
+    let mut hits = Vec::with_capacity(matched.len());
+    for (_, frame) in matched {
+        if let Some(hit) = sorted_hit(
+            memvid,
+            &frame,
+            &snippet_tokens,
+            snippet_window,
+            hits.len() + 1,
+        ) {
+            hits.push(hit);
+        }
+    }
+    #[cfg(feature = "temporal_track")]
+    attach_temporal_metadata(memvid, &mut hits)?;
+    let context = build_context(&hits);
+
+    let response = SearchResponse {
+        query: request.query.clone(),
+        elapsed_ms: start_time.elapsed().as_millis().max(1),
+        total_hits,
+        params: params.clone(),
+        hits,
+        context,
+        next_cursor: None,
+        engine,
+        suggested_query: None,
+        facets: Vec::new(),
+        query_expansions: Vec::new(),
+        explanation: None,
+    };
+    Ok((response, next_after))
+}
+
+/// Key `frame` is ordered by under `sort`, as stored in sorted cursors.
+pub(super) fn sort_key(frame: &Frame, sort: SearchSort) -> i64 {
+    match sort {
+        SearchSort::FrameId => i64::try_from(frame.id).unwrap_or(i64::MAX),
+        _ => frame.timestamp,
+    }
+}
+
+/// TOC frames below `watermark`, limited to `candidate_filter`, in sort order.
+fn toc_candidates(
+    memvid: &Memvid,
+    sort: SearchSort,
+    watermark: FrameId,
+    candidate_filter: Option<&HashSet<FrameId>>,
+) -> Vec<(i64, FrameId)> {
+    let descending = sort == SearchSort::TimestampDesc;
+    let mut candidates: Vec<(i64, FrameId)> = memvid
+        .toc
+        .frames
+        .iter()
+        .filter(|frame| frame.id < watermark)
+        .filter(|frame| candidate_filter.is_none_or(|filter| filter.contains(&frame.id)))
+        .map(|frame| (sort_key(frame, sort), frame.id))
+        .collect();
+    candidates.sort_unstable_by(|a, b| if descending { b.cmp(a) } else { a.cmp(b) });
+    candidates
+}
+
+/// Whether `candidate` sorts strictly after the cursor position `after`.
+fn is_after(candidate: (i64, FrameId), after: Option<(i64, FrameId)>, descending: bool) -> bool {
+    after.is_none_or(|after| {
+        if descending {
+            candidate < after
+        } else {
+            candidate > after
+        }
+    })
+}
+
+/// The active frame `frame_id` when it is in scope and satisfies `parsed`.
+fn matching_frame(
+    memvid: &mut Memvid,
+    frame_id: FrameId,
+    parsed: &ParsedQuery,
+    uri_filter: Option<&str>,
+    scope_filter: Option<&str>,
+) -> Option<Frame> {
+    let frame = usize::try_from(frame_id)
+        .ok()
+        .and_then(|idx| memvid.toc.frames.get(idx))
+        .cloned()?;
+    if frame.status != FrameStatus::Active {
+        return None;
+    }
+    let uri = frame.uri.as_deref();
+    let in_scope = match (uri_filter, scope_filter) {
+        (Some(expected), _) => uri_matches(uri, expected),
+        (None, Some(scope)) => uri.is_some_and(|uri| uri.starts_with(scope)),
+        (None, None) => true,
+    };
+    if !in_scope {
+        return None;
+    }
+    let search_text = match memvid.frame_search_text(&frame) {
+        Ok(text) => text,
+        Err(err) => {
+            tracing::warn!("sorted search: unable to read frame {frame_id}: {err}");
+            return None;
+        }
+    };
+    let content_lower = search_text.to_ascii_lowercase();
+    let ctx = EvaluationContext {
+        frame: &frame,
+        content_lower: &content_lower,
+    };
+    parsed.evaluate(&ctx).then_some(frame)
+}
+
+fn sorted_hit(
+    memvid: &mut Memvid,
+    frame: &Frame,
+    tokens: &LanguageTokens,
+    snippet_window: usize,
+    rank: usize,
+) -> Option<SearchHit> {
+    let ChunkInfo {
+        start: chunk_start,
+        end: chunk_end,
+        text: chunk_text,
+    } = match memvid.resolve_chunk_context(frame) {
+        Ok(info) => info,
+        Err(err) => {
+            tracing::warn!(
+                "unable to resolve chunk context for frame {}: {}",
+                frame.id,
+                err
+            );
+            return None;
+        }
+    };
+    let occurrences = collect_token_occurrences(
+        &chunk_text.to_ascii_lowercase(),
+        tokens.for_frame(frame, &chunk_text),
+    );
+    let (start, end) = compute_snippet_slices(&chunk_text, &occurrences, snippet_window, 1)
+        .first()
+        .copied()
+        .unwrap_or((0, 0));
+    let matches = occurrences
+        .iter()
+        .filter(|(s, e)| *s >= start && *e <= end)
+        .count()
+        .max(1);
+
+    let uri = frame
+        .uri
+        .clone()
+        .unwrap_or_else(|| crate::default_uri(frame.id));
+    let title = frame
+        .title
+        .clone()
+        .or_else(|| crate::infer_title_from_uri(&uri));
+    let metadata = SearchHitMetadata {
+        matches,
+        tags: frame.tags.clone(),
+        labels: frame.labels.clone(),
+        track: frame.track.clone(),
+        created_at: timestamp_to_rfc3339(frame.timestamp),
+        content_dates: frame.content_dates.clone(),
+        entities: Vec::new(),
+        extra_metadata: frame.extra_metadata.clone(),
+        media: None,
+        region: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+    };
+    Some(SearchHit {
+        rank,
+        frame_id: frame.id,
+        uri,
+        title,
+        range: (chunk_start + start, chunk_start + end),
+        text: chunk_text[start..end].to_string(),
+        matches,
+        chunk_range: Some((chunk_start, chunk_end)),
+        chunk_text: Some(chunk_text),
+        score: None,
+        metadata: Some(metadata),
+        highlights: Vec::new(),
+    })
+}
@@ -0,0 +1,25 @@
+//! "Did you mean" suggestions backed by the Tantivy engine's spelling dictionary.
+
+use crate::Result;
+use crate::memvid::lifecycle::Memvid;
+
+impl Memvid {
+    /// Suggest a spelling-corrected version of `query` using words that occur
+    /// in this memory, or `None` when every plain word is already known.
+    ///
+    /// The dictionary is updated at each commit and stored with the lexical
+    /// index. Field filters, phrases, wildcards and fuzzy terms are left as
+    /// typed.
+    pub fn suggest_query(&mut self, query: &str) -> Result<Option<String>> {
+        if self.tantivy.is_none() {
+            if !self.lex_enabled {
+                return Ok(None);
+            }
+            self.init_tantivy()?;
+        }
+        let Some(engine) = self.tantivy.as_mut() else {
+            return Ok(None);
+        };
+        Ok(engine.spelling_dictionary()?.suggest_query(query))
+    }
+}
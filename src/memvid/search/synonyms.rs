@@ -0,0 +1,69 @@
+//! Per-memory synonym groups and one-way query expansions.
+
+use crate::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::SynonymTrack;
+
+impl Memvid {
+    /// Synonyms in effect (empty when none were defined).
+    #[must_use]
+    pub fn synonyms(&self) -> SynonymTrack {
+        self.toc.synonyms.clone().unwrap_or_default()
+    }
+
+    /// Make `terms` interchangeable in `search` and `ask` queries. A group
+    /// sharing a term with an existing one is merged into it.
+    pub fn add_synonym_group<I, S>(&mut self, terms: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.update_synonyms(|track| track.add_group(terms))
+    }
+
+    /// Remove the synonym group containing `term`. Returns whether one existed.
+    pub fn remove_synonym_group(&mut self, term: &str) -> Result<bool> {
+        self.update_synonyms(|track| Ok(track.remove_group(term)))
+    }
+
+    /// Widen queries for `term` to also match `targets`, without the reverse.
+    pub fn add_query_expansion<I, S>(&mut self, term: &str, targets: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.update_synonyms(|track| track.add_expansion(term, targets))
+    }
+
+    /// Remove the one-way expansion for `term`. Returns whether one existed.
+    pub fn remove_query_expansion(&mut self, term: &str) -> Result<bool> {
+        self.update_synonyms(|track| Ok(track.remove_expansion(term)))
+    }
+
+    /// Also record synonyms of matched terms on frames ingested from now on,
+    /// so they are indexed with the frame without appearing in its text.
+    /// Existing frames are not rewritten.
+    pub fn set_synonym_index_expansion(&mut self, enabled: bool) -> Result<()> {
+        self.update_synonyms(|track| {
+            track.expand_at_index = enabled;
+            Ok(())
+        })
+    }
+
+    /// Apply `update` to a copy of the track and store it if it changed.
+    /// Persisted on the next commit.
+    fn update_synonyms<R>(
+        &mut self,
+        update: impl FnOnce(&mut SynonymTrack) -> Result<R>,
+    ) -> Result<R> {
+        self.ensure_writable()?;
+        let current = self.synonyms();
+        let mut track = current.clone();
+        let result = update(&mut track)?;
+        if track != current {
+            self.toc.synonyms = (track != SynonymTrack::default()).then_some(track);
+            self.dirty = true;
+        }
+        Ok(result)
+    }
+}
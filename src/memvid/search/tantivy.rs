@@ -19,6 +19,8 @@ use log::warn;
 use std::collections::HashSet;
 use std::time::Instant;
 
+/// Ranked search through Tantivy over frames below `watermark`.
+#[allow(clippy::too_many_arguments)]
 pub(super) fn try_tantivy_search(
     memvid: &mut Memvid,
     parsed: &ParsedQuery,
@@ -27,6 +29,7 @@ pub(super) fn try_tantivy_search(
     params: &SearchParams,
     start_time: Instant,
     candidate_filter: Option<&HashSet<FrameId>>,
+    watermark: FrameId,
 ) -> Result<Option<SearchResponse>> {
     let engine = match memvid.tantivy.as_ref() {
         Some(engine) => engine,
@@ -37,13 +40,9 @@ pub(super) fn try_tantivy_search(
 
     // Use stemmed tokens for evaluation to match what Tantivy indexed.
     // Tantivy stems during indexing (e.g., "technology" → "technolog"), so we need
-    // to search for stemmed forms in the content as well.
-    let mut stemmed_tokens = Vec::new();
-    for token in query_tokens {
-        let analyzed = engine.analyse_text(token);
-        stemmed_tokens.extend(analyzed);
-    }
-    let stemmed_tokens = stemmed_tokens;
+    // to search for stemmed forms in the content as well, stemmed by the
+    // analyzer of each frame's language.
+    let stemmed_tokens = engine.analyse_tokens(query_tokens);
 
     let offset_hint = request
         .cursor
@@ -71,6 +70,7 @@ pub(super) fn try_tantivy_search(
         uri_filter,
         scope_filter,
         frame_filter_slice,
+        watermark,
         doc_limit,
     ) {
         Ok(hits) => hits,
@@ -104,6 +104,7 @@ pub(super) fn try_tantivy_search(
                 params,
                 start_time,
                 candidate_filter,
+                watermark,
             )?));
         }
         // No lex fallback available, return empty Tantivy results
@@ -117,7 +118,8 @@ pub(super) fn try_tantivy_search(
     }
 
     let snippet_window = request.snippet_chars.max(80);
-    let max_snippets_per_doc = request.top_k.max(1);
+    // Sized by the page, not the widened window, so pages see the same hits
+    let max_snippets_per_doc = params.top_k.max(1);
     let mut evaluated = Vec::new();
     for hit in search_hits {
         let frame_meta = memvid
@@ -175,7 +177,10 @@ pub(super) fn try_tantivy_search(
             continue;
         }
         // Use frame's search text for token occurrence matching as well
-        let occurrences = collect_token_occurrences(&eval_text, &stemmed_tokens);
+        let occurrences = collect_token_occurrences(
+            &eval_text,
+            stemmed_tokens.for_frame(&frame_meta, &eval_text),
+        );
         let slices = compute_snippet_slices(
             &chunk_info.text,
             &occurrences,
@@ -242,6 +247,7 @@ pub(super) fn try_tantivy_search(
             params,
             start_time,
             candidate_filter,
+            watermark,
         )?));
     }
 
@@ -262,6 +268,7 @@ pub(super) fn try_tantivy_search(
             params,
             start_time,
             candidate_filter,
+            watermark,
         )?));
     }
 
@@ -327,6 +334,8 @@ pub(super) fn try_tantivy_search(
                 content_dates: frame_meta.content_dates.clone(),
                 entities: Vec::new(),
                 extra_metadata: frame_meta.extra_metadata.clone(),
+                media: None,
+                region: None,
                 #[cfg(feature = "temporal_track")]
                 temporal: None,
             };
@@ -349,6 +358,7 @@ pub(super) fn try_tantivy_search(
                 chunk_text: Some(chunk_text.clone()),
                 score: Some(hit.score),
                 metadata: Some(metadata),
+                highlights: Vec::new(),
             });
             produced += 1;
         }
@@ -373,10 +383,14 @@ pub(super) fn try_tantivy_search(
         context,
         next_cursor,
         engine: SearchEngineKind::Tantivy,
+        suggested_query: None,
+        facets: Vec::new(),
+        query_expansions: Vec::new(),
+        explanation: None,
     }))
 }
 
-fn uri_matches(candidate: Option<&str>, expected: &str) -> bool {
+pub(super) fn uri_matches(candidate: Option<&str>, expected: &str) -> bool {
     let Some(uri) = candidate else {
         return false;
     };
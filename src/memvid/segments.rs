@@ -118,7 +118,7 @@ impl Memvid {
             if frame.status != FrameStatus::Active {
                 continue;
             }
-            if frame.role != FrameRole::Document && frame.role != FrameRole::DocumentChunk {
+            if !frame.role.is_document() && frame.role != FrameRole::DocumentChunk {
                 continue;
             }
 
@@ -206,9 +206,10 @@ impl Memvid {
         };
 
         match effective_compression {
-            VectorCompression::None => {
-                // Uncompressed path - use regular VecIndexBuilder
-                let mut builder = VecIndexBuilder::new();
+            VectorCompression::None | VectorCompression::Int8 | VectorCompression::Binary => {
+                // Uncompressed or scalar-quantized path - use regular VecIndexBuilder
+                let mut builder =
+                    VecIndexBuilder::new().with_compression(effective_compression.clone());
                 for (frame_id, vector) in embeddings {
                     if vector.is_empty() {
                         continue;
@@ -234,7 +235,7 @@ impl Memvid {
                     vector_count,
                     dimension: artifact_dimension.max(dimension),
                     checksum,
-                    compression: VectorCompression::None,
+                    compression: effective_compression,
                     #[cfg(feature = "parallel_segments")]
                     bytes_uncompressed,
                 }))
@@ -536,12 +537,13 @@ impl Memvid {
         self.file.flush()?;
         self.data_end = offset + artifact.bytes.len() as u64;
 
-        let common = SegmentCommon::new(
+        let mut common = SegmentCommon::new(
             segment_id,
             offset,
             artifact.bytes.len() as u64,
             artifact.checksum,
         );
+        common.codec_version = crate::search::TANTIVY_CODEC_VERSION;
         Ok(TantivySegmentDescriptor::from_common(
             common,
             artifact.path.clone(),
@@ -0,0 +1,607 @@
+//! Directory and archive sync: one frame per file, rewritten only when the
+//! file changes.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Component, Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use flate2::read::GzDecoder;
+use glob::{MatchOptions, Pattern};
+use walkdir::WalkDir;
+
+use crate::memvid::lifecycle::Memvid;
+use crate::memvid::records::uri_segment;
+use crate::types::{
+    Frame, FrameRole, FrameStatus, SYNC_HASH_METADATA_KEY, SYNC_MTIME_METADATA_KEY,
+    SYNC_ROOT_METADATA_KEY, SYNC_SIZE_METADATA_KEY, SyncOptions, SyncReport,
+};
+use crate::{MemvidError, Result};
+
+const ARCHIVE_SUFFIXES: [&str; 4] = [".tar.gz", ".tgz", ".tar", ".zip"];
+
+/// A file found by the walk, named by its `/`-separated path under the root.
+struct SyncEntry {
+    relative: String,
+    size: u64,
+    mtime: Option<String>,
+    source: SyncSource,
+}
+
+/// Where an entry's bytes come from. Directory files and zip members are
+/// read only once their size or time differs from the frame's; tar members
+/// are read while the archive streams past, unless they exceed the size limit.
+enum SyncSource {
+    File(PathBuf),
+    ZipMember(usize),
+    Bytes(Vec<u8>),
+    Oversized,
+}
+
+type ZipReader = zip::ZipArchive<BufReader<File>>;
+
+/// The entries found under a sync root, and the zip archive their members
+/// are read from.
+struct SyncWalk {
+    entries: Vec<SyncEntry>,
+    zip: Option<ZipReader>,
+}
+
+impl SyncEntry {
+    /// Read the entry's bytes, leaving it without a source.
+    fn take_bytes(&mut self, zip: Option<&mut ZipReader>) -> Result<Vec<u8>> {
+        match std::mem::replace(&mut self.source, SyncSource::Oversized) {
+            SyncSource::File(path) => fs::read(&path).map_err(|source| MemvidError::Io {
+                source,
+                path: Some(path),
+            }),
+            SyncSource::ZipMember(index) => {
+                let archive = zip.ok_or_else(|| MemvidError::Sync {
+                    reason: format!("zip archive for '{}' is not open", self.relative),
+                })?;
+                let mut member = archive.by_index(index).map_err(zip_error)?;
+                let mut bytes = Vec::new();
+                member.read_to_end(&mut bytes)?;
+                Ok(bytes)
+            }
+            SyncSource::Bytes(bytes) => Ok(bytes),
+            SyncSource::Oversized => Err(MemvidError::Sync {
+                reason: format!(
+                    "'{}' exceeds the size limit and was not read",
+                    self.relative
+                ),
+            }),
+        }
+    }
+}
+
+fn zip_error(err: zip::result::ZipError) -> MemvidError {
+    MemvidError::Sync {
+        reason: format!("failed to read zip archive: {err}"),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveKind {
+    fn of(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_ascii_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+/// Include and exclude globs, matched against relative paths.
+struct SyncFilter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    include_hidden: bool,
+}
+
+impl SyncFilter {
+    fn new(options: &SyncOptions) -> Result<Self> {
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|pattern| {
+                    Pattern::new(pattern).map_err(|err| MemvidError::Sync {
+                        reason: format!("invalid glob '{pattern}': {err}"),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+        Ok(Self {
+            include: compile(&options.include)?,
+            exclude: compile(&options.exclude)?,
+            include_hidden: options.include_hidden,
+        })
+    }
+
+    fn matches(patterns: &[Pattern], relative: &str) -> bool {
+        let options = MatchOptions {
+            require_literal_leading_dot: false,
+            ..MatchOptions::default()
+        };
+        patterns
+            .iter()
+            .any(|pattern| pattern.matches_with(relative, options))
+    }
+
+    /// Whether a directory is walked; only `exclude` can prune one.
+    fn walks_dir(&self, relative: &str) -> bool {
+        !Self::matches(&self.exclude, relative)
+    }
+
+    fn accepts(&self, relative: &str) -> bool {
+        if !self.include_hidden && relative.split('/').any(|part| part.starts_with('.')) {
+            return false;
+        }
+        if !self.include.is_empty() && !Self::matches(&self.include, relative) {
+            return false;
+        }
+        // An excluded directory excludes everything below it, including
+        // archive members, which are not walked directory by directory.
+        !relative
+            .match_indices('/')
+            .map(|(end, _)| &relative[..end])
+            .chain([relative])
+            .any(|path| Self::matches(&self.exclude, path))
+    }
+}
+
+impl Memvid {
+    /// Mirror the files under `path` (a directory, or a `.zip`, `.tar`,
+    /// `.tar.gz` or `.tgz` archive) into this memory, one frame per file.
+    ///
+    /// Each file becomes a frame at `{uri_prefix}/{relative path}`, with `%`,
+    /// whitespace and URI delimiters in path segments percent-encoded, its
+    /// path in `source_path` and its size, modification time and BLAKE3 hash
+    /// in extra metadata. On a later sync with the same prefix, a file whose
+    /// size and time match its frame is not read; one whose hash still
+    /// matches is not rewritten, and gets its new time recorded so the next
+    /// sync skips the read; a changed file supersedes its frame through
+    /// [`update_frame`](Self::update_frame); and the frame of a file that is
+    /// gone is deleted unless `keep_deleted` is set. Hidden entries,
+    /// symlinks and this memory's own file are never synced.
+    ///
+    /// Writes run in batch mode and are indexed on the next commit; a sync
+    /// that finds nothing to change writes nothing.
+    pub fn sync_dir(&mut self, path: impl AsRef<Path>, options: SyncOptions) -> Result<SyncReport> {
+        self.ensure_mutation_allowed()?;
+        let root = path.as_ref();
+        let filter = SyncFilter::new(&options)?;
+        let prefix = sync_prefix(root, &options);
+        let SyncWalk { entries, mut zip } = match ArchiveKind::of(root) {
+            Some(kind) if root.is_file() => {
+                walk_archive(root, kind, &filter, options.max_file_bytes)?
+            }
+            _ => SyncWalk {
+                entries: walk_dir(root, &filter, &self.path)?,
+                zip: None,
+            },
+        };
+
+        let mut existing: HashMap<String, Frame> = self
+            .toc
+            .frames
+            .iter()
+            .filter(|frame| {
+                frame.status == FrameStatus::Active
+                    && frame.role == FrameRole::Document
+                    && frame.extra_metadata.get(SYNC_ROOT_METADATA_KEY) == Some(&prefix)
+            })
+            .filter_map(|frame| Some((frame.uri.clone()?, frame.clone())))
+            .collect();
+        let entries: Vec<(String, SyncEntry, Option<Frame>)> = entries
+            .into_iter()
+            .map(|entry| {
+                let uri = format!(
+                    "{prefix}/{}",
+                    entry
+                        .relative
+                        .split('/')
+                        .map(encode_path_segment)
+                        .collect::<Vec<_>>()
+                        .join("/")
+                );
+                let previous = existing.remove(&uri);
+                (uri, entry, previous)
+            })
+            .collect();
+        let mut removed: Vec<Frame> = if options.keep_deleted {
+            Vec::new()
+        } else {
+            existing.into_values().collect()
+        };
+        removed.sort_by(|a, b| a.uri.cmp(&b.uri));
+
+        let source_root = root.display().to_string();
+        let archive = ArchiveKind::of(root).is_some() && root.is_file();
+        let mut report = SyncReport::default();
+        // Each file is read at most once, and its bytes are both hashed and
+        // written; a sync that finds nothing to change writes nothing.
+        self.in_record_batch(|memvid| {
+            for (uri, mut entry, previous) in entries {
+                if options
+                    .max_file_bytes
+                    .is_some_and(|limit| entry.size > limit)
+                {
+                    report.skipped.push(uri);
+                    continue;
+                }
+                if let Some(frame) = &previous {
+                    if sync_value(frame, SYNC_SIZE_METADATA_KEY)
+                        == Some(entry.size.to_string().as_str())
+                        && entry.mtime.is_some()
+                        && sync_value(frame, SYNC_MTIME_METADATA_KEY) == entry.mtime.as_deref()
+                    {
+                        report.unchanged += 1;
+                        continue;
+                    }
+                }
+                let bytes = entry.take_bytes(zip.as_mut())?;
+                let digest = blake3::hash(&bytes).to_hex().to_string();
+                report.hashed += 1;
+                if let Some(frame) = previous.as_ref().filter(|frame| {
+                    sync_value(frame, SYNC_HASH_METADATA_KEY) == Some(digest.as_str())
+                }) {
+                    // Same content under a new time: record it so the next
+                    // sync skips the read.
+                    memvid.refresh_sync_stamp(frame.id, &entry);
+                    report.unchanged += 1;
+                    continue;
+                }
+
+                let mut put = options.put.clone();
+                put.uri = Some(uri.clone());
+                put.source_path = Some(if archive {
+                    format!("{source_root}!/{}", entry.relative)
+                } else {
+                    Path::new(&source_root)
+                        .join(&entry.relative)
+                        .display()
+                        .to_string()
+                });
+                // Batched frames become searchable on commit.
+                put.instant_index = false;
+                put.extra_metadata
+                    .insert(SYNC_ROOT_METADATA_KEY.to_string(), prefix.clone());
+                put.extra_metadata
+                    .insert(SYNC_SIZE_METADATA_KEY.to_string(), entry.size.to_string());
+                if let Some(mtime) = &entry.mtime {
+                    put.extra_metadata
+                        .insert(SYNC_MTIME_METADATA_KEY.to_string(), mtime.clone());
+                }
+                put.extra_metadata
+                    .insert(SYNC_HASH_METADATA_KEY.to_string(), digest);
+
+                if let Some(frame) = previous {
+                    memvid.delete_document_chunks(frame.id)?;
+                    memvid.update_frame(frame.id, Some(bytes), put, None)?;
+                    report.updated.push(uri);
+                } else {
+                    memvid.put_internal(Some(&bytes), None, None, None, put, None, None)?;
+                    report.added.push(uri);
+                }
+            }
+            for frame in removed {
+                memvid.delete_document_chunks(frame.id)?;
+                memvid.delete_frame(frame.id)?;
+                report.deleted.extend(frame.uri);
+            }
+            Ok(())
+        })?;
+        Ok(report)
+    }
+
+    /// Record `entry`'s size and time on the frame of an unchanged file.
+    fn refresh_sync_stamp(&mut self, frame_id: u64, entry: &SyncEntry) {
+        let Some(frame) = usize::try_from(frame_id)
+            .ok()
+            .and_then(|idx| self.toc.frames.get_mut(idx))
+        else {
+            return;
+        };
+        let metadata = &mut frame.extra_metadata;
+        metadata.insert(SYNC_SIZE_METADATA_KEY.to_string(), entry.size.to_string());
+        match &entry.mtime {
+            Some(mtime) => metadata.insert(SYNC_MTIME_METADATA_KEY.to_string(), mtime.clone()),
+            None => metadata.remove(SYNC_MTIME_METADATA_KEY),
+        };
+        self.dirty = true;
+    }
+
+    /// Tombstone the chunk frames of a document that is about to be replaced
+    /// or deleted, so the old text stops matching.
+    fn delete_document_chunks(&mut self, parent_id: u64) -> Result<()> {
+        for chunk in self.document_chunk_frames(parent_id) {
+            self.delete_frame(chunk.id)?;
+        }
+        Ok(())
+    }
+}
+
+fn sync_value<'a>(frame: &'a Frame, key: &str) -> Option<&'a str> {
+    frame.extra_metadata.get(key).map(String::as_str)
+}
+
+/// `segment` with `%`, whitespace, control characters and `?`, `#` and `\\`
+/// percent-encoded, so distinct file names never share a URI.
+fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for ch in segment.chars() {
+        if ch == '%' || ch.is_whitespace() || ch.is_control() || matches!(ch, '?' | '#' | '\\') {
+            let mut buf = [0u8; 4];
+            for byte in ch.encode_utf8(&mut buf).bytes() {
+                encoded.push_str(&format!("%{byte:02X}"));
+            }
+        } else {
+            encoded.push(ch);
+        }
+    }
+    encoded
+}
+
+/// `options.uri_prefix`, or `mv2://` and the directory or archive name.
+fn sync_prefix(root: &Path, options: &SyncOptions) -> String {
+    if let Some(prefix) = options.uri_prefix.as_deref() {
+        return prefix.trim_end_matches('/').to_string();
+    }
+    let name = root
+        .canonicalize()
+        .unwrap_or_else(|_| root.to_path_buf())
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let stem = ARCHIVE_SUFFIXES
+        .iter()
+        .find_map(|suffix| {
+            name.len()
+                .checked_sub(suffix.len())
+                .filter(|&cut| name[cut..].eq_ignore_ascii_case(suffix))
+                .map(|cut| &name[..cut])
+        })
+        .unwrap_or(&name);
+    format!("mv2://{}", uri_segment(stem))
+}
+
+/// Relative path of `path` under `root` with `/` separators, or `None` when
+/// a component is not a plain name.
+fn relative_path(path: &Path, root: &Path) -> Option<String> {
+    let parts = path
+        .strip_prefix(root)
+        .ok()?
+        .components()
+        .map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+fn walk_dir(root: &Path, filter: &SyncFilter, memory_path: &Path) -> Result<Vec<SyncEntry>> {
+    let memory_path = memory_path.canonicalize().ok();
+    let walker = WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            if entry.depth() == 0 || !entry.file_type().is_dir() {
+                return true;
+            }
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            (filter.include_hidden || !hidden)
+                && relative_path(entry.path(), root).is_some_and(|dir| filter.walks_dir(&dir))
+        });
+
+    let mut entries = Vec::new();
+    for entry in walker {
+        let entry = entry.map_err(|err| {
+            let path = err.path().map(Path::to_path_buf);
+            MemvidError::Io {
+                source: err.into(),
+                path,
+            }
+        })?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Some(relative) = relative_path(entry.path(), root) else {
+            continue;
+        };
+        if !filter.accepts(&relative) {
+            continue;
+        }
+        if memory_path.is_some() && entry.path().canonicalize().ok() == memory_path {
+            continue;
+        }
+        let metadata = entry.metadata().map_err(|err| MemvidError::Io {
+            source: err.into(),
+            path: Some(entry.path().to_path_buf()),
+        })?;
+        let mtime = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|elapsed| format!("{}.{:09}", elapsed.as_secs(), elapsed.subsec_nanos()));
+        entries.push(SyncEntry {
+            relative,
+            size: metadata.len(),
+            mtime,
+            source: SyncSource::File(entry.into_path()),
+        });
+    }
+    Ok(entries)
+}
+
+fn walk_archive(
+    path: &Path,
+    kind: ArchiveKind,
+    filter: &SyncFilter,
+    max_file_bytes: Option<u64>,
+) -> Result<SyncWalk> {
+    let file = File::open(path).map_err(|source| MemvidError::Io {
+        source,
+        path: Some(path.to_path_buf()),
+    })?;
+    let (mut entries, zip) = match kind {
+        ArchiveKind::Zip => {
+            let archive = zip::ZipArchive::new(BufReader::new(file)).map_err(zip_error)?;
+            let (entries, archive) = walk_zip(archive, filter)?;
+            (entries, Some(archive))
+        }
+        ArchiveKind::Tar => (
+            walk_tar(BufReader::new(file), filter, max_file_bytes)?,
+            None,
+        ),
+        ArchiveKind::TarGz => (
+            walk_tar(GzDecoder::new(BufReader::new(file)), filter, max_file_bytes)?,
+            None,
+        ),
+    };
+    // A path stored twice resolves to its last copy, as extraction leaves it:
+    // reversing first lets the stable sort put that copy ahead of the others.
+    entries.reverse();
+    entries.sort_by(|a, b| a.relative.cmp(&b.relative));
+    entries.dedup_by(|duplicate, kept| duplicate.relative == kept.relative);
+    Ok(SyncWalk { entries, zip })
+}
+
+/// Zip members are listed by index and read later, from the same archive.
+fn walk_zip(mut archive: ZipReader, filter: &SyncFilter) -> Result<(Vec<SyncEntry>, ZipReader)> {
+    let mut entries = Vec::new();
+    for index in 0..archive.len() {
+        let member = archive.by_index(index).map_err(zip_error)?;
+        if !member.is_file() || member.is_symlink() {
+            continue;
+        }
+        let Some(relative) = member
+            .enclosed_name()
+            .and_then(|name| relative_path(&name, Path::new("")))
+        else {
+            continue;
+        };
+        if !filter.accepts(&relative) {
+            continue;
+        }
+        entries.push(SyncEntry {
+            relative,
+            size: member.size(),
+            mtime: member.last_modified().map(|time| time.to_string()),
+            source: SyncSource::ZipMember(index),
+        });
+    }
+    Ok((entries, archive))
+}
+
+/// Tar members can only be read as the stream passes them, so those within
+/// `max_file_bytes` are kept in memory.
+fn walk_tar(
+    reader: impl Read,
+    filter: &SyncFilter,
+    max_file_bytes: Option<u64>,
+) -> Result<Vec<SyncEntry>> {
+    let archive_error = |err: std::io::Error| MemvidError::Sync {
+        reason: format!("failed to read tar archive: {err}"),
+    };
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = Vec::new();
+    for member in archive.entries().map_err(archive_error)? {
+        let mut member = member.map_err(archive_error)?;
+        if !member.header().entry_type().is_file() {
+            continue;
+        }
+        let Some(relative) = member
+            .path()
+            .ok()
+            .and_then(|name| relative_path(&name, Path::new("")))
+        else {
+            continue;
+        };
+        if !filter.accepts(&relative) {
+            continue;
+        }
+        let mtime = member.header().mtime().ok().map(|secs| secs.to_string());
+        let size = member.size();
+        let source = if max_file_bytes.is_some_and(|limit| size > limit) {
+            SyncSource::Oversized
+        } else {
+            let mut bytes = Vec::new();
+            member.read_to_end(&mut bytes).map_err(archive_error)?;
+            SyncSource::Bytes(bytes)
+        };
+        entries.push(SyncEntry {
+            relative,
+            size,
+            mtime,
+            source,
+        });
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(include: &[&str], exclude: &[&str]) -> SyncFilter {
+        SyncFilter::new(&SyncOptions {
+            include: include.iter().map(ToString::to_string).collect(),
+            exclude: exclude.iter().map(ToString::to_string).collect(),
+            ..SyncOptions::default()
+        })
+        .expect("valid globs")
+    }
+
+    #[test]
+    fn filter_applies_include_exclude_and_hidden() {
+        let filter = filter(&["*.md"], &["drafts", "*.tmp.md"]);
+        assert!(filter.accepts("README.md"));
+        assert!(filter.accepts("docs/guide/intro.md"));
+        assert!(!filter.accepts("src/main.rs"));
+        assert!(!filter.accepts("drafts/plan.md"));
+        assert!(!filter.accepts("docs/scratch.tmp.md"));
+        assert!(!filter.accepts(".github/notes.md"));
+        assert!(!filter.walks_dir("drafts"));
+    }
+
+    #[test]
+    fn prefix_defaults_to_directory_or_archive_name() {
+        let options = SyncOptions::default();
+        assert_eq!(
+            sync_prefix(Path::new("/data/team notes"), &options),
+            "mv2://team-notes"
+        );
+        assert_eq!(
+            sync_prefix(Path::new("/data/site.tar.gz"), &options),
+            "mv2://site"
+        );
+        let custom = SyncOptions {
+            uri_prefix: Some("mv2://kb/".to_string()),
+            ..SyncOptions::default()
+        };
+        assert_eq!(sync_prefix(Path::new("/data/x"), &custom), "mv2://kb");
+    }
+
+    #[test]
+    fn invalid_glob_is_rejected() {
+        let result = SyncFilter::new(&SyncOptions {
+            include: vec!["[".to_string()],
+            ..SyncOptions::default()
+        });
+        assert!(matches!(result, Err(MemvidError::Sync { .. })));
+    }
+}
@@ -119,6 +119,8 @@ impl Memvid {
             clip_image_count,
             lex_enabled: self.lex_enabled,
             vec_enabled: self.vec_enabled,
+            embedding_cache: self.embedding_cache_stats(),
+            pii: self.pii_stats(),
         })
     }
 
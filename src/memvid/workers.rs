@@ -288,9 +288,10 @@ fn build_vec_artifact(
     };
 
     match effective_compression {
-        VectorCompression::None => {
-            // Uncompressed path - use regular VecIndexBuilder
-            let mut builder = crate::vec::VecIndexBuilder::new();
+        VectorCompression::None | VectorCompression::Int8 | VectorCompression::Binary => {
+            // Uncompressed or scalar-quantized path - use regular VecIndexBuilder
+            let mut builder =
+                crate::vec::VecIndexBuilder::new().with_compression(effective_compression.clone());
             let mut vectors = 0usize;
             let mut dimension = 0u32;
 
@@ -326,7 +327,7 @@ fn build_vec_artifact(
                 vector_count: artifact.vector_count,
                 dimension: final_dimension,
                 checksum: artifact.checksum,
-                compression: VectorCompression::None,
+                compression: effective_compression,
                 #[cfg(feature = "parallel_segments")]
                 bytes_uncompressed: artifact.bytes_uncompressed,
             };
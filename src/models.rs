@@ -278,7 +278,7 @@ fn validate_entry(entry: &ModelManifestEntry) -> Result<()> {
     Ok(())
 }
 
-fn resolve_entry_path(base: &Path, relative: &str) -> Result<PathBuf> {
+pub(crate) fn resolve_entry_path(base: &Path, relative: &str) -> Result<PathBuf> {
     let path = Path::new(relative);
     if path.is_absolute() {
         return Err(MemvidError::ModelManifestInvalid {
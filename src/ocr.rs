@@ -0,0 +1,843 @@
+//! Optical character recognition for payloads without a text layer.
+//!
+//! Scanned PDFs and images get no text from the readers. With the `ocr`
+//! feature, [`OcrEngine`] rasterizes PDF pages (through the same plumbing as
+//! CLIP page rendering), finds text lines with a DBNet-style detection model
+//! and reads them with a CTC recognition model, both ONNX and run through
+//! `ort`. Model bundles are checked by [`crate::models::verify_model_dir`]
+//! before they load.
+//!
+//! Recognized words keep their page and bounding box. Ingestion stores them
+//! in the OCR layout track keyed by frame id, so search hits can report where
+//! on the page they matched. The result types and the
+//! detection and decoding post-processing are always compiled.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{MemvidError, normalize_text};
+
+/// Put-time `extra_metadata` key carrying a frame's recognized words, encoded
+/// by [`encode_ocr_words`], through the WAL. Committing moves them into the
+/// OCR layout track, so frames never keep the key.
+pub const OCR_WORDS_METADATA_KEY: &str = "memvid.ocr.words";
+
+/// Longest side, in pixels, PDF pages are rendered at for recognition.
+pub const OCR_RENDER_PX: u32 = 1600;
+
+/// Pages of a PDF recognized at most.
+pub const OCR_MAX_PAGES: usize = 64;
+
+/// Longest side of the image fed to the detection model (a multiple of 32).
+#[cfg(feature = "ocr")]
+const DETECT_MAX_SIDE: u32 = 960;
+
+/// Probability above which a detection map pixel is text.
+const DETECT_THRESHOLD: f32 = 0.3;
+
+/// Mean probability a text region needs to be kept.
+const DETECT_BOX_THRESHOLD: f32 = 0.6;
+
+/// Regions thinner than this many map pixels are noise.
+const DETECT_MIN_SIDE: usize = 3;
+
+/// How far regions grow past the shrunk text kernel DBNet predicts.
+const DETECT_UNCLIP_RATIO: f32 = 1.5;
+
+/// Height, in pixels, text lines are scaled to for recognition.
+#[cfg(feature = "ocr")]
+const RECOGNIZE_HEIGHT: u32 = 48;
+
+/// Widest line image fed to the recognition model.
+#[cfg(feature = "ocr")]
+const RECOGNIZE_MAX_WIDTH: u32 = 1280;
+
+/// Stored box coordinates are ten-thousandths of the page.
+const BOX_SCALE: f32 = 10_000.0;
+
+/// Where OCR model bundles live and how much of a document is recognized.
+#[derive(Debug, Clone)]
+pub struct OcrConfig {
+    /// Directory of `sha256-<digest>` model bundles, each with a
+    /// `manifest.json` listing files with the roles `detection`,
+    /// `recognition` and `charset`.
+    pub models_dir: PathBuf,
+    /// Bundle to load; the first bundle that verifies when unset.
+    pub model_digest: Option<String>,
+    /// Pages of a PDF recognized at most.
+    pub max_pages: usize,
+    /// Longest side, in pixels, PDF pages are rendered at.
+    pub render_px: u32,
+    /// Words recognized with a lower mean character probability are dropped.
+    pub min_confidence: f32,
+}
+
+impl Default for OcrConfig {
+    fn default() -> Self {
+        let models_dir = std::env::var("MEMVID_MODELS_DIR")
+            .ok()
+            .map(PathBuf::from)
+            .or_else(|| dirs_next::home_dir().map(|d| d.join(".memvid/models")))
+            .unwrap_or_else(|| PathBuf::from(".memvid/models"));
+
+        Self {
+            models_dir: models_dir.join("ocr"),
+            model_digest: std::env::var("MEMVID_OCR_MODEL").ok(),
+            max_pages: OCR_MAX_PAGES,
+            render_px: OCR_RENDER_PX,
+            min_confidence: 0.5,
+        }
+    }
+}
+
+/// OCR-specific errors
+#[derive(Debug, thiserror::Error)]
+pub enum OcrError {
+    /// No bundle in the models directory verified
+    #[error("No verified OCR model bundle in {dir:?}. {hint}")]
+    ModelNotFound { dir: PathBuf, hint: String },
+
+    /// The bundle's manifest does not list a file with a required role
+    #[error("OCR model bundle {dir:?} has no '{role}' file")]
+    MissingRole { dir: PathBuf, role: &'static str },
+
+    /// Image decode failed
+    #[error("Failed to decode image for OCR: {cause}")]
+    ImageDecodeError { cause: String },
+
+    /// ONNX runtime error
+    #[error("OCR inference error: {cause}")]
+    InferenceError { cause: String },
+}
+
+impl From<OcrError> for MemvidError {
+    fn from(err: OcrError) -> Self {
+        MemvidError::ExtractionFailed {
+            reason: err.to_string().into_boxed_str(),
+        }
+    }
+}
+
+/// Bounding box as fractions of the page (or image), origin top left.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct OcrBox {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl OcrBox {
+    /// Smallest box covering both boxes.
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Self {
+            x,
+            y,
+            width: (self.x + self.width).max(other.x + other.width) - x,
+            height: (self.y + self.height).max(other.y + other.height) - y,
+        }
+    }
+}
+
+/// A recognized word and where it is.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OcrWord {
+    pub text: String,
+    /// 1-based page number; 1 for images.
+    pub page: u32,
+    pub bbox: OcrBox,
+    /// Mean probability of the word's characters.
+    pub confidence: f32,
+}
+
+/// Recognized text of one page, in reading order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OcrPage {
+    /// 1-based page number; 1 for images.
+    pub page: u32,
+    /// Rendered size, in pixels.
+    pub width: u32,
+    pub height: u32,
+    pub lines: Vec<Vec<OcrWord>>,
+}
+
+/// Recognized text of a document.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OcrDocument {
+    pub pages: Vec<OcrPage>,
+}
+
+impl OcrDocument {
+    /// True when no word was recognized.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words().next().is_none()
+    }
+
+    /// Every word, in reading order.
+    pub fn words(&self) -> impl Iterator<Item = &OcrWord> {
+        self.pages
+            .iter()
+            .flat_map(|page| page.lines.iter().flatten())
+    }
+
+    /// The recognized text: words joined by spaces, one line per text line.
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.pages
+            .iter()
+            .flat_map(|page| page.lines.iter())
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                line.iter()
+                    .map(|word| word.text.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Each word with the char offset it starts at in [`text`](Self::text).
+    pub(crate) fn word_offsets(&self) -> Vec<(usize, &OcrWord)> {
+        let mut offsets = Vec::new();
+        let mut cursor = 0;
+        for line in self
+            .pages
+            .iter()
+            .flat_map(|page| page.lines.iter())
+            .filter(|line| !line.is_empty())
+        {
+            for word in line {
+                offsets.push((cursor, word));
+                // The word and the space or newline after it
+                cursor += word.text.chars().count() + 1;
+            }
+        }
+        offsets
+    }
+}
+
+/// Stored form of a word: page, box in ten-thousandths, confidence in
+/// percent and text.
+type EncodedWord<'a> = (u32, u16, u16, u16, u16, u8, std::borrow::Cow<'a, str>);
+
+/// Encode words compactly for the OCR layout track.
+#[must_use]
+pub fn encode_ocr_words(words: &[OcrWord]) -> String {
+    #[allow(clippy::cast_possible_truncation)]
+    let scaled = |value: f32| (value.clamp(0.0, 1.0) * BOX_SCALE).round() as u16;
+    #[allow(clippy::cast_possible_truncation)]
+    let percent = |value: f32| (value.clamp(0.0, 1.0) * 100.0).round() as u8;
+    let encoded: Vec<EncodedWord<'_>> = words
+        .iter()
+        .map(|word| {
+            (
+                word.page,
+                scaled(word.bbox.x),
+                scaled(word.bbox.y),
+                scaled(word.bbox.width),
+                scaled(word.bbox.height),
+                percent(word.confidence),
+                std::borrow::Cow::Borrowed(word.text.as_str()),
+            )
+        })
+        .collect();
+    serde_json::to_string(&encoded).unwrap_or_default()
+}
+
+/// Decode words stored by [`encode_ocr_words`]; malformed values decode to
+/// no words.
+#[must_use]
+pub fn decode_ocr_words(value: &str) -> Vec<OcrWord> {
+    let unscaled = |value: u16| f32::from(value) / BOX_SCALE;
+    serde_json::from_str::<Vec<EncodedWord<'_>>>(value)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(page, x, y, width, height, confidence, text)| OcrWord {
+            text: text.into_owned(),
+            page,
+            bbox: OcrBox {
+                x: unscaled(x),
+                y: unscaled(y),
+                width: unscaled(width),
+                height: unscaled(height),
+            },
+            confidence: f32::from(confidence) / 100.0,
+        })
+        .collect()
+}
+
+/// Text region in pixels of the detection map, `x1` and `y1` exclusive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(not(feature = "ocr"), allow(dead_code))]
+struct PixelBox {
+    x0: usize,
+    y0: usize,
+    x1: usize,
+    y1: usize,
+}
+
+/// Text regions of a `width` x `height` DBNet probability map: connected
+/// pixels above [`DETECT_THRESHOLD`] whose mean probability clears
+/// [`DETECT_BOX_THRESHOLD`], grown back out by [`DETECT_UNCLIP_RATIO`] and
+/// sorted into reading order.
+#[cfg_attr(not(feature = "ocr"), allow(dead_code))]
+#[allow(clippy::cast_possible_truncation)]
+fn detect_regions(probs: &[f32], width: usize, height: usize) -> Vec<PixelBox> {
+    let mut visited = vec![false; width * height];
+    let mut regions = Vec::new();
+    let mut stack = Vec::new();
+    for start in 0..(width * height).min(probs.len()) {
+        if visited[start] || probs[start] <= DETECT_THRESHOLD {
+            continue;
+        }
+        visited[start] = true;
+        stack.push(start);
+        let (mut x0, mut y0, mut x1, mut y1) = (width, height, 0, 0);
+        let (mut total, mut count) = (0.0_f32, 0_usize);
+        while let Some(index) = stack.pop() {
+            let (x, y) = (index % width, index / width);
+            x0 = x0.min(x);
+            y0 = y0.min(y);
+            x1 = x1.max(x + 1);
+            y1 = y1.max(y + 1);
+            total += probs[index];
+            count += 1;
+            let neighbours = [
+                (x > 0).then(|| index - 1),
+                (x + 1 < width).then(|| index + 1),
+                (y > 0).then(|| index - width),
+                (y + 1 < height).then(|| index + width),
+            ];
+            for next in neighbours.into_iter().flatten() {
+                if !visited[next] && probs[next] > DETECT_THRESHOLD {
+                    visited[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        let (w, h) = (x1 - x0, y1 - y0);
+        if w.min(h) < DETECT_MIN_SIDE || total / (count as f32) < DETECT_BOX_THRESHOLD {
+            continue;
+        }
+        // DBNet predicts a shrunk kernel; grow it by area * ratio / perimeter.
+        let grow = ((w * h) as f32 * DETECT_UNCLIP_RATIO / (2 * (w + h)) as f32).round() as usize;
+        regions.push(PixelBox {
+            x0: x0.saturating_sub(grow),
+            y0: y0.saturating_sub(grow),
+            x1: (x1 + grow).min(width),
+            y1: (y1 + grow).min(height),
+        });
+    }
+    reading_order(regions)
+}
+
+/// Sort regions top to bottom, and left to right among regions whose
+/// vertical centre falls within the first region of their row.
+#[cfg_attr(not(feature = "ocr"), allow(dead_code))]
+fn reading_order(mut regions: Vec<PixelBox>) -> Vec<PixelBox> {
+    regions.sort_by_key(|region| (region.y0 + region.y1, region.x0));
+    let mut ordered = Vec::with_capacity(regions.len());
+    let mut row: Vec<PixelBox> = Vec::new();
+    for region in regions {
+        let centre = usize::midpoint(region.y0, region.y1);
+        if row.first().is_some_and(|first| centre >= first.y1) {
+            row.sort_by_key(|region| region.x0);
+            ordered.append(&mut row);
+        }
+        row.push(region);
+    }
+    row.sort_by_key(|region| region.x0);
+    ordered.append(&mut row);
+    ordered
+}
+
+/// Words of a text line from `[steps, charset.len()]` recognition
+/// probabilities, by greedy CTC decoding; `charset[0]` is the blank. Each
+/// word comes with its start and end as fractions of the line width and its
+/// mean character probability.
+#[cfg_attr(not(feature = "ocr"), allow(dead_code))]
+fn decode_line(probs: &[f32], charset: &[String]) -> Vec<(String, f32, f32, f32)> {
+    let classes = charset.len();
+    if classes == 0 {
+        return Vec::new();
+    }
+    let steps = probs.len() / classes;
+    let mut words = Vec::new();
+    // Text, first step, last step, summed probability and character count
+    let mut current: Option<(String, usize, usize, f32, usize)> = None;
+    let mut previous = 0;
+    for step in 0..steps {
+        let row = &probs[step * classes..(step + 1) * classes];
+        let (class, prob) =
+            row.iter()
+                .copied()
+                .enumerate()
+                .fold((0, f32::MIN), |best, (class, prob)| {
+                    if prob > best.1 { (class, prob) } else { best }
+                });
+        let repeated = class == previous;
+        previous = class;
+        if class == 0 || repeated {
+            continue;
+        }
+        let symbol = &charset[class];
+        if symbol.trim().is_empty() {
+            words.extend(current.take());
+            continue;
+        }
+        let word = current.get_or_insert_with(|| (String::new(), step, step, 0.0, 0));
+        word.0.push_str(symbol);
+        word.2 = step;
+        word.3 += prob;
+        word.4 += 1;
+    }
+    words.extend(current);
+    words
+        .into_iter()
+        .map(|(text, first, last, total, count)| {
+            (
+                text,
+                first as f32 / steps as f32,
+                (last + 1) as f32 / steps as f32,
+                total / count as f32,
+            )
+        })
+        .collect()
+}
+
+/// Words of one recognized line, normalized like extracted text and placed on
+/// the page. `line` is the line's box; word spans are fractions of its width.
+#[cfg_attr(not(feature = "ocr"), allow(dead_code))]
+fn place_words(
+    decoded: Vec<(String, f32, f32, f32)>,
+    line: OcrBox,
+    page: u32,
+    min_confidence: f32,
+) -> Vec<OcrWord> {
+    decoded
+        .into_iter()
+        .filter(|(_, _, _, confidence)| *confidence >= min_confidence)
+        .filter_map(|(text, start, end, confidence)| {
+            let text = normalize_text(&text, usize::MAX)?.text;
+            (!text.contains(char::is_whitespace)).then_some(OcrWord {
+                text,
+                page,
+                bbox: OcrBox {
+                    x: line.x + start * line.width,
+                    y: line.y,
+                    width: (end - start) * line.width,
+                    height: line.height,
+                },
+                confidence,
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "ocr")]
+pub use engine::OcrEngine;
+
+#[cfg(feature = "ocr")]
+mod engine {
+    use std::path::Path;
+    use std::sync::Mutex;
+
+    use image::{DynamicImage, GenericImageView, imageops::FilterType};
+    use ndarray::Array4;
+    use ort::session::Session;
+    use ort::value::Tensor;
+
+    use super::{
+        DETECT_MAX_SIDE, OcrBox, OcrConfig, OcrDocument, OcrError, OcrPage, PixelBox,
+        RECOGNIZE_HEIGHT, RECOGNIZE_MAX_WIDTH, decode_line, detect_regions, place_words,
+    };
+    use crate::models::{
+        ModelManifest, ModelVerificationStatus, ModelVerifyOptions, resolve_entry_path,
+        verify_model_dir, verify_models,
+    };
+    use crate::{MemvidError, Result};
+
+    const DETECT_MEAN: [f32; 3] = [0.485, 0.456, 0.406];
+    const DETECT_STD: [f32; 3] = [0.229, 0.224, 0.225];
+
+    /// Detection and recognition sessions of a verified model bundle.
+    pub struct OcrEngine {
+        config: OcrConfig,
+        digest: String,
+        detector: Mutex<Session>,
+        recognizer: Mutex<Session>,
+        /// Recognition classes: the CTC blank, the bundle's characters, then a space.
+        charset: Vec<String>,
+    }
+
+    impl std::fmt::Debug for OcrEngine {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("OcrEngine")
+                .field("digest", &self.digest)
+                .field("classes", &self.charset.len())
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl OcrEngine {
+        /// Verify and load the configured model bundle.
+        pub fn load(config: OcrConfig) -> Result<Self> {
+            let options = ModelVerifyOptions::default();
+            let verification = match config.model_digest.as_deref() {
+                Some(digest) => {
+                    let hex = digest.trim_start_matches("sha256:");
+                    let dir = config.models_dir.join(format!("sha256-{hex}"));
+                    verify_model_dir(&dir, &options)?
+                }
+                None => verify_models(&config.models_dir, &options)?
+                    .into_iter()
+                    .find(|report| report.status != ModelVerificationStatus::Fail)
+                    .ok_or_else(|| OcrError::ModelNotFound {
+                        dir: config.models_dir.clone(),
+                        hint: "Install a bundle with detection, recognition and charset files."
+                            .to_string(),
+                    })?,
+            };
+            if verification.status == ModelVerificationStatus::Fail {
+                return Err(MemvidError::ModelIntegrity {
+                    reason: verification.errors.join("; ").into_boxed_str(),
+                });
+            }
+
+            let dir = verification.path;
+            let manifest: ModelManifest =
+                serde_json::from_str(&std::fs::read_to_string(dir.join("manifest.json"))?)
+                    .map_err(|err| MemvidError::ModelManifestInvalid {
+                        reason: err.to_string().into_boxed_str(),
+                    })?;
+            let file = |role: &'static str| {
+                manifest
+                    .files
+                    .iter()
+                    .find(|entry| entry.roles.iter().any(|candidate| candidate == role))
+                    .ok_or_else(|| OcrError::MissingRole {
+                        dir: dir.clone(),
+                        role,
+                    })
+                    .map_err(MemvidError::from)
+                    .and_then(|entry| resolve_entry_path(&dir, &entry.path))
+            };
+
+            let mut charset = vec![String::new()];
+            charset.extend(
+                std::fs::read_to_string(file("charset")?)?
+                    .lines()
+                    .map(str::to_string),
+            );
+            charset.push(" ".to_string());
+
+            Ok(Self {
+                detector: Mutex::new(load_session(&file("detection")?)?),
+                recognizer: Mutex::new(load_session(&file("recognition")?)?),
+                digest: verification.digest,
+                charset,
+                config,
+            })
+        }
+
+        /// Digest of the loaded model bundle.
+        #[must_use]
+        pub fn digest(&self) -> &str {
+            &self.digest
+        }
+
+        /// Recognize a PDF or an image; `None` for other payloads.
+        pub fn recognize_bytes(&self, bytes: &[u8]) -> Result<Option<OcrDocument>> {
+            if bytes.starts_with(b"%PDF") {
+                return self.recognize_pdf(bytes).map(Some);
+            }
+            if image::guess_format(bytes).is_err() {
+                return Ok(None);
+            }
+            let image =
+                image::load_from_memory(bytes).map_err(|err| OcrError::ImageDecodeError {
+                    cause: err.to_string(),
+                })?;
+            Ok(Some(OcrDocument {
+                pages: vec![self.recognize_image(&image, 1)?],
+            }))
+        }
+
+        /// Render up to `max_pages` pages of a PDF and recognize each.
+        pub fn recognize_pdf(&self, bytes: &[u8]) -> Result<OcrDocument> {
+            let rendered = crate::clip::render_pdf_pages_from_bytes(
+                bytes,
+                self.config.max_pages,
+                self.config.render_px,
+            )?;
+            let pages = rendered
+                .iter()
+                .map(|(page, image)| self.recognize_image(image, *page))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(OcrDocument { pages })
+        }
+
+        /// Recognize the text lines of one page image.
+        #[allow(clippy::cast_possible_truncation)]
+        pub fn recognize_image(&self, image: &DynamicImage, page: u32) -> Result<OcrPage> {
+            let (width, height) = image.dimensions();
+            let mut lines = Vec::new();
+            if width == 0 || height == 0 {
+                return Ok(OcrPage {
+                    page,
+                    width,
+                    height,
+                    lines,
+                });
+            }
+            for region in self.detect(image)? {
+                let (x, y) = (region.x0 as u32, region.y0 as u32);
+                let (w, h) = (
+                    (region.x1 - region.x0) as u32,
+                    (region.y1 - region.y0) as u32,
+                );
+                let line = OcrBox {
+                    x: x as f32 / width as f32,
+                    y: y as f32 / height as f32,
+                    width: w as f32 / width as f32,
+                    height: h as f32 / height as f32,
+                };
+                let decoded = self.recognize_line(&image.crop_imm(x, y, w, h))?;
+                let words = place_words(decoded, line, page, self.config.min_confidence);
+                if !words.is_empty() {
+                    lines.push(words);
+                }
+            }
+            Ok(OcrPage {
+                page,
+                width,
+                height,
+                lines,
+            })
+        }
+
+        /// Text regions of `image`, in image pixels and reading order.
+        #[allow(clippy::cast_possible_truncation)]
+        fn detect(&self, image: &DynamicImage) -> Result<Vec<PixelBox>> {
+            let (width, height) = image.dimensions();
+            let scale = (DETECT_MAX_SIDE as f32 / width.max(height) as f32).min(1.0);
+            let round32 = |side: u32| ((side as f32 * scale / 32.0).round() as u32).max(1) * 32;
+            let (map_w, map_h) = (round32(width), round32(height));
+            let resized = image
+                .resize_exact(map_w, map_h, FilterType::Triangle)
+                .to_rgb8();
+
+            let mut input = Array4::<f32>::zeros((1, 3, map_h as usize, map_w as usize));
+            for (x, y, pixel) in resized.enumerate_pixels() {
+                for channel in 0..3 {
+                    input[[0, channel, y as usize, x as usize]] =
+                        (f32::from(pixel[channel]) / 255.0 - DETECT_MEAN[channel])
+                            / DETECT_STD[channel];
+                }
+            }
+
+            let mut session = self
+                .detector
+                .lock()
+                .map_err(|_| MemvidError::Lock("Failed to lock OCR detection session".into()))?;
+            let (shape, probs) = run_session(&mut session, input)?;
+            let (out_h, out_w) = match shape.as_slice() {
+                [.., h, w] => (*h as usize, *w as usize),
+                _ => {
+                    return Err(OcrError::InferenceError {
+                        cause: format!("unexpected detection output shape {shape:?}"),
+                    }
+                    .into());
+                }
+            };
+
+            let (sx, sy) = (width as f32 / out_w as f32, height as f32 / out_h as f32);
+            Ok(detect_regions(&probs, out_w, out_h)
+                .into_iter()
+                .map(|region| PixelBox {
+                    x0: (region.x0 as f32 * sx) as usize,
+                    y0: (region.y0 as f32 * sy) as usize,
+                    x1: ((region.x1 as f32 * sx) as usize).min(width as usize),
+                    y1: ((region.y1 as f32 * sy) as usize).min(height as usize),
+                })
+                .filter(|region| region.x1 > region.x0 && region.y1 > region.y0)
+                .collect())
+        }
+
+        /// Decoded words of one line image.
+        #[allow(clippy::cast_possible_truncation)]
+        fn recognize_line(&self, line: &DynamicImage) -> Result<Vec<(String, f32, f32, f32)>> {
+            let (width, height) = line.dimensions();
+            let scaled_w = ((width as f32 * RECOGNIZE_HEIGHT as f32 / height as f32).ceil() as u32)
+                .clamp(RECOGNIZE_HEIGHT, RECOGNIZE_MAX_WIDTH);
+            let resized = line
+                .resize_exact(scaled_w, RECOGNIZE_HEIGHT, FilterType::Triangle)
+                .to_rgb8();
+
+            let mut input =
+                Array4::<f32>::zeros((1, 3, RECOGNIZE_HEIGHT as usize, scaled_w as usize));
+            for (x, y, pixel) in resized.enumerate_pixels() {
+                for channel in 0..3 {
+                    input[[0, channel, y as usize, x as usize]] =
+                        f32::from(pixel[channel]) / 127.5 - 1.0;
+                }
+            }
+
+            let mut session = self
+                .recognizer
+                .lock()
+                .map_err(|_| MemvidError::Lock("Failed to lock OCR recognition session".into()))?;
+            let (shape, probs) = run_session(&mut session, input)?;
+            if shape.last().copied() != Some(self.charset.len() as i64) {
+                return Err(OcrError::InferenceError {
+                    cause: format!(
+                        "recognition output {shape:?} does not match {} charset classes",
+                        self.charset.len()
+                    ),
+                }
+                .into());
+            }
+            Ok(decode_line(&probs, &self.charset))
+        }
+    }
+
+    fn load_session(path: &Path) -> Result<Session> {
+        let inference = |err: ort::Error| OcrError::InferenceError {
+            cause: format!("Failed to load {}: {err}", path.display()),
+        };
+        Ok(Session::builder()
+            .map_err(inference)?
+            .with_intra_threads(4)
+            .map_err(inference)?
+            .commit_from_file(path)
+            .map_err(inference)?)
+    }
+
+    /// Run a single-input session and copy out its first output.
+    fn run_session(session: &mut Session, input: Array4<f32>) -> Result<(Vec<i64>, Vec<f32>)> {
+        let inference = |err: ort::Error| OcrError::InferenceError {
+            cause: err.to_string(),
+        };
+        let input_name = session
+            .inputs
+            .first()
+            .map_or_else(|| "x".to_string(), |input| input.name.clone());
+        let tensor = Tensor::from_array(input).map_err(inference)?;
+        let outputs = session
+            .run(ort::inputs![input_name => tensor])
+            .map_err(inference)?;
+        let (shape, data) = outputs[0].try_extract_tensor::<f32>().map_err(inference)?;
+        Ok((shape.to_vec(), data.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, page: u32, x: f32) -> OcrWord {
+        OcrWord {
+            text: text.to_string(),
+            page,
+            bbox: OcrBox {
+                x,
+                y: 0.25,
+                width: 0.1,
+                height: 0.05,
+            },
+            confidence: 0.9,
+        }
+    }
+
+    #[test]
+    fn words_roundtrip_through_metadata_encoding() {
+        let words = vec![word("Termination", 2, 0.1), word("clause", 2, 0.22)];
+        let decoded = decode_ocr_words(&encode_ocr_words(&words));
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].text, "Termination");
+        assert_eq!(decoded[1].page, 2);
+        assert!((decoded[1].bbox.x - 0.22).abs() < 1e-4);
+        assert!((decoded[0].confidence - 0.9).abs() < 1e-2);
+        assert!(decode_ocr_words("not json").is_empty());
+    }
+
+    #[test]
+    fn word_offsets_follow_document_text() {
+        let document = OcrDocument {
+            pages: vec![
+                OcrPage {
+                    page: 1,
+                    lines: vec![vec![word("Master", 1, 0.1), word("Agreement", 1, 0.3)]],
+                    ..OcrPage::default()
+                },
+                OcrPage {
+                    page: 2,
+                    lines: vec![vec![word("Schedule", 2, 0.1)]],
+                    ..OcrPage::default()
+                },
+            ],
+        };
+        let text = document.text();
+        assert_eq!(text, "Master Agreement\nSchedule");
+        let offsets: Vec<usize> = document
+            .word_offsets()
+            .iter()
+            .map(|(offset, _)| *offset)
+            .collect();
+        assert_eq!(offsets, vec![0, 7, 17]);
+        assert!(text[17..].starts_with("Schedule"));
+    }
+
+    #[test]
+    fn detection_map_regions_come_out_in_reading_order() {
+        let (width, height) = (40, 20);
+        let mut probs = vec![0.0; width * height];
+        let mut fill = |x0: usize, y0: usize, x1: usize, y1: usize| {
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    probs[y * width + x] = 0.9;
+                }
+            }
+        };
+        // Second line, then two regions of the first line, right one first
+        fill(4, 13, 30, 17);
+        fill(24, 3, 36, 7);
+        fill(4, 3, 18, 7);
+        let regions = detect_regions(&probs, width, height);
+        assert_eq!(regions.len(), 3);
+        assert!(regions[0].x0 < regions[1].x0 && regions[0].y0 < 10);
+        assert!(regions[2].y0 > regions[0].y1);
+        assert!(regions[0].x0 < 4 && regions[0].x1 > 18);
+    }
+
+    #[test]
+    fn ctc_decoding_splits_words_with_their_spans() {
+        let charset: Vec<String> = ["", "a", "b", " "]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let step = |class: usize| {
+            let mut row = vec![0.0; 4];
+            row[class] = 1.0;
+            row
+        };
+        // "a a b" with blanks and repeats: a a _ b _ ' ' b
+        let probs: Vec<f32> = [1, 1, 0, 2, 0, 3, 2, 0]
+            .into_iter()
+            .flat_map(step)
+            .collect();
+        let words = decode_line(&probs, &charset);
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].0, "ab");
+        assert!((words[0].1 - 0.0).abs() < 1e-6);
+        assert!((words[0].2 - 0.5).abs() < 1e-6);
+        assert_eq!(words[1].0, "b");
+        assert!((words[1].1 - 0.75).abs() < 1e-6);
+    }
+}
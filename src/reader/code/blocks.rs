@@ -0,0 +1,321 @@
+//! Block parsing: groups scanned lines into symbols, by brace depth or
+//! indentation, with their doc comments and signatures.
+
+use super::lang::{Declaration, declaration};
+use super::scan::Line;
+use super::{CodeLanguage, CodeSymbol, MAX_DOC_CHARS, MAX_SIGNATURE_CHARS, SymbolKind};
+
+/// A symbol and the line indexes it spans, with members for containers.
+#[derive(Debug)]
+pub(super) struct Block {
+    pub(super) symbol: CodeSymbol,
+    /// First doc comment or attribute line.
+    pub(super) start: usize,
+    /// Last line, inclusive.
+    pub(super) end: usize,
+    pub(super) children: Vec<Block>,
+}
+
+pub(super) fn parse_blocks(lines: &[Line<'_>], language: CodeLanguage) -> Vec<Block> {
+    if language.indented() {
+        indent_blocks(lines, language, 0, lines.len(), None)
+    } else {
+        brace_blocks(lines, language, 0, lines.len(), 0, None)
+    }
+}
+
+fn brace_blocks(
+    lines: &[Line<'_>],
+    language: CodeLanguage,
+    lo: usize,
+    hi: usize,
+    depth: usize,
+    container: Option<&str>,
+) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut floor = lo;
+    let mut idx = lo;
+    while idx < hi {
+        let line = &lines[idx];
+        let declaration = (line.is_statement() && line.depth_before == depth)
+            .then(|| declaration(language, line.trimmed(), container.is_some()))
+            .flatten();
+        let Some(declaration) = declaration else {
+            idx += 1;
+            continue;
+        };
+        let Some(end) = brace_end(lines, idx, hi, depth, declaration.requires_body) else {
+            idx += 1;
+            continue;
+        };
+        let header_end = (idx..=end).find(|&at| lines[at].opens).unwrap_or(end);
+        let mut block = new_block(lines, language, floor, idx, end, container, declaration);
+        block.symbol.signature = signature(&lines[idx..=header_end], '{');
+        if block.symbol.kind.is_container() && end > idx {
+            let path = block.symbol.path.clone();
+            block.children = brace_blocks(lines, language, idx + 1, end, depth + 1, Some(&path));
+        }
+        blocks.push(block);
+        idx = end + 1;
+        floor = idx;
+    }
+    blocks
+}
+
+/// Last line of the declaration starting at `start`: where its body's braces
+/// close, or where the statement ends when it has no body.
+fn brace_end(
+    lines: &[Line<'_>],
+    start: usize,
+    hi: usize,
+    depth: usize,
+    requires_body: bool,
+) -> Option<usize> {
+    let mut opened = false;
+    for idx in start..hi {
+        let line = &lines[idx];
+        opened |= line.opens;
+        if opened {
+            if line.depth_after <= depth {
+                return Some(idx);
+            }
+        } else if line.nesting_after == 0
+            && !line.in_string
+            && !continues(line.trimmed(), lines.get(idx + 1).map(Line::trimmed))
+        {
+            return (!requires_body).then_some(idx);
+        }
+    }
+    opened.then_some(hi.saturating_sub(1).max(start))
+}
+
+/// The declaration carries on past this line.
+fn continues(line: &str, next: Option<&str>) -> bool {
+    let code = line.split("//").next().unwrap_or(line).trim_end();
+    [
+        ",", "(", "[", "=", "=>", "->", "|", "&&", "||", "+", ":", ".", "where",
+    ]
+    .iter()
+    .any(|suffix| code.ends_with(suffix))
+        || next.is_some_and(|next| {
+            [
+                "{", "where", "->", "=>", ":", ".", "+", "|", "&&", "||", "?",
+            ]
+            .iter()
+            .any(|prefix| next.starts_with(prefix))
+                || next.starts_with("throws ")
+                || next.starts_with("extends ")
+                || next.starts_with("implements ")
+        })
+}
+
+fn indent_blocks(
+    lines: &[Line<'_>],
+    language: CodeLanguage,
+    lo: usize,
+    hi: usize,
+    container: Option<&str>,
+) -> Vec<Block> {
+    let Some(level) = (lo..hi)
+        .find(|&idx| lines[idx].is_statement())
+        .map(|idx| lines[idx].indent)
+    else {
+        return Vec::new();
+    };
+    let mut blocks = Vec::new();
+    let mut floor = lo;
+    let mut idx = lo;
+    while idx < hi {
+        let line = &lines[idx];
+        let declaration = (line.is_statement() && line.indent == level)
+            .then(|| declaration(language, line.trimmed(), container.is_some()))
+            .flatten();
+        let Some(declaration) = declaration else {
+            idx += 1;
+            continue;
+        };
+        let header_end = (idx..hi)
+            .find(|&at| lines[at].nesting_after == 0)
+            .unwrap_or(idx);
+        let mut end = header_end;
+        for at in header_end + 1..hi {
+            let next = &lines[at];
+            if next.in_string || (next.has_code && next.nesting_before > 0) {
+                end = at;
+            } else if next.has_code && !next.in_comment {
+                if next.indent > level {
+                    end = at;
+                } else {
+                    if language == CodeLanguage::Ruby
+                        && next.indent == level
+                        && next.trimmed() == "end"
+                    {
+                        end = at;
+                    }
+                    break;
+                }
+            }
+        }
+        let mut block = new_block(lines, language, floor, idx, end, container, declaration);
+        block.symbol.signature = signature(&lines[idx..=header_end], ':');
+        if language == CodeLanguage::Python && block.symbol.doc.is_none() {
+            block.symbol.doc = docstring(&lines[header_end + 1..=end.max(header_end)]);
+        }
+        if block.symbol.kind.is_container() && end > header_end {
+            let path = block.symbol.path.clone();
+            block.children = indent_blocks(lines, language, header_end + 1, end + 1, Some(&path));
+        }
+        blocks.push(block);
+        idx = end + 1;
+        floor = idx;
+    }
+    blocks
+}
+
+/// Block for the declaration at `decl`, taking in doc lines above it down to
+/// `floor`. The signature is left for the caller.
+fn new_block(
+    lines: &[Line<'_>],
+    language: CodeLanguage,
+    floor: usize,
+    decl: usize,
+    end: usize,
+    container: Option<&str>,
+    declaration: Declaration,
+) -> Block {
+    let mut start = decl;
+    while start > floor && is_doc_line(language, &lines[start - 1]) {
+        start -= 1;
+    }
+    let doc = doc_text(language, &lines[start..decl]);
+    let owner = declaration.receiver.as_deref().or(container);
+    let kind = match (declaration.kind, owner) {
+        (SymbolKind::Function, Some(_)) => SymbolKind::Method,
+        (kind, _) => kind,
+    };
+    let path = match owner {
+        Some(owner) => format!("{owner}{}{}", language.separator(), declaration.name),
+        None => declaration.name.clone(),
+    };
+    Block {
+        symbol: CodeSymbol {
+            name: declaration.name,
+            path,
+            kind,
+            signature: String::new(),
+            doc,
+            start_line: start + 1,
+            end_line: end + 1,
+        },
+        start,
+        end,
+        children: Vec::new(),
+    }
+}
+
+fn is_doc_line(language: CodeLanguage, line: &Line<'_>) -> bool {
+    let text = line.trimmed();
+    if text.is_empty() || line.in_string {
+        return false;
+    }
+    if line.in_comment {
+        return true;
+    }
+    if text.starts_with("//!") {
+        return false;
+    }
+    match language {
+        CodeLanguage::Python => text.starts_with('#') || text.starts_with('@'),
+        CodeLanguage::Ruby => text.starts_with('#'),
+        CodeLanguage::Rust => {
+            text.starts_with("//") || text.starts_with("/*") || text.starts_with("#[")
+        }
+        CodeLanguage::C | CodeLanguage::Cpp => {
+            text.starts_with("//")
+                || text.starts_with("/*")
+                || text.starts_with("template")
+                || text.starts_with("[[")
+        }
+        CodeLanguage::JavaScript
+        | CodeLanguage::TypeScript
+        | CodeLanguage::Go
+        | CodeLanguage::Java => {
+            text.starts_with("//") || text.starts_with("/*") || text.starts_with('@')
+        }
+    }
+}
+
+/// Comment text of doc lines, without markers, attributes or decorators.
+fn doc_text(language: CodeLanguage, lines: &[Line<'_>]) -> Option<String> {
+    let mut words = Vec::new();
+    for line in lines {
+        let text = line.trimmed();
+        let comment = if language.indented() {
+            text.strip_prefix('#')
+        } else if line.in_comment {
+            Some(text.trim_start_matches('*'))
+        } else {
+            ["///", "//", "/**", "/*"]
+                .iter()
+                .find_map(|marker| text.strip_prefix(marker))
+        };
+        let Some(comment) = comment else {
+            continue;
+        };
+        let comment = comment.trim_end_matches("*/").trim();
+        if !comment.is_empty() {
+            words.push(comment);
+        }
+    }
+    collapse(&words.join(" "), MAX_DOC_CHARS)
+}
+
+/// A Python docstring at the top of a body.
+fn docstring(body: &[Line<'_>]) -> Option<String> {
+    let first = body.iter().position(|line| !line.trimmed().is_empty())?;
+    let opening = body[first].trimmed();
+    let quote = ["\"\"\"", "'''"]
+        .into_iter()
+        .find(|quote| opening.starts_with(quote))?;
+    let mut text = String::new();
+    for line in &body[first..] {
+        text.push_str(line.trimmed());
+        text.push(' ');
+        let so_far = text.trim_end();
+        if so_far.len() > quote.len() && so_far.ends_with(quote) {
+            break;
+        }
+    }
+    let text = text.trim();
+    let inner = text.strip_prefix(quote).unwrap_or(text);
+    collapse(inner.strip_suffix(quote).unwrap_or(inner), MAX_DOC_CHARS)
+}
+
+/// Declaration header, cut before the body opens at `body`.
+fn signature(lines: &[Line<'_>], body: char) -> String {
+    let mut header = String::new();
+    for line in lines {
+        let text = line.trimmed();
+        let text = text.split("//").next().unwrap_or(text);
+        header.push_str(text);
+        header.push(' ');
+    }
+    let header = header.trim();
+    let cut = if body == '{' {
+        header.find('{').map_or(header, |at| &header[..at])
+    } else {
+        header.strip_suffix(body).unwrap_or(header)
+    };
+    collapse(cut, MAX_SIGNATURE_CHARS).unwrap_or_default()
+}
+
+fn collapse(text: &str, limit: usize) -> Option<String> {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() {
+        return None;
+    }
+    Some(match collapsed.char_indices().nth(limit) {
+        Some((at, _)) => format!("{}…", &collapsed[..at]),
+        None => collapsed,
+    })
+}
@@ -0,0 +1,83 @@
+//! C and C++: namespaces, classes, structs, unions, enums and function
+//! definitions (prototypes and calls are skipped).
+
+use super::{Declaration, identifier, is_keyword, strip_words};
+use crate::reader::code::SymbolKind;
+
+pub(super) fn declaration(
+    line: &str,
+    member: bool,
+    found: impl Fn(SymbolKind, &str) -> Option<Declaration>,
+) -> Option<Declaration> {
+    let rest = strip_words(
+        line,
+        &[
+            "static",
+            "inline",
+            "extern",
+            "virtual",
+            "constexpr",
+            "explicit",
+            "export",
+        ],
+    );
+    if !line.ends_with(';') {
+        for (keyword, kind) in [
+            ("namespace ", SymbolKind::Module),
+            ("class ", SymbolKind::Class),
+            ("struct ", SymbolKind::Struct),
+            ("union ", SymbolKind::Struct),
+            ("enum class ", SymbolKind::Enum),
+            ("enum ", SymbolKind::Enum),
+        ] {
+            if let Some(rest) = rest.strip_prefix(keyword) {
+                let name = identifier(rest)?;
+                // `struct point origin = {...}` declares a variable.
+                let after = rest[name.len()..].trim_start();
+                if !(after.is_empty()
+                    || after.starts_with('{')
+                    || after.starts_with(':')
+                    || after.starts_with("final"))
+                {
+                    return None;
+                }
+                return found(kind, name);
+            }
+        }
+    }
+    function(rest, member)
+}
+/// A C-style function definition: `type name(...)`, with a body to follow.
+pub(super) fn function(line: &str, allow_untyped: bool) -> Option<Declaration> {
+    if line.ends_with(';') || line.starts_with('#') {
+        return None;
+    }
+    let (before, _) = line.split_once('(')?;
+    let before = before.trim_end();
+    if before.contains(['=', '.', '"', '\'', ',']) || before.contains("->") {
+        return None;
+    }
+    let name_start = before
+        .rfind(|ch: char| !(ch.is_alphanumeric() || matches!(ch, '_' | ':' | '~')))
+        .map_or(0, |at| at + 1);
+    let name = &before[name_start..];
+    let return_type = before[..name_start].trim();
+    let bare = name.rsplit("::").next().unwrap_or(name);
+    if bare.is_empty()
+        || bare.starts_with(|ch: char| ch.is_ascii_digit())
+        || is_keyword(bare)
+        || return_type
+            .split_whitespace()
+            .next()
+            .is_some_and(is_keyword)
+        || (return_type.is_empty() && !allow_untyped && !name.contains("::"))
+    {
+        return None;
+    }
+    Some(Declaration {
+        kind: SymbolKind::Function,
+        name: name.to_string(),
+        receiver: None,
+        requires_body: true,
+    })
+}
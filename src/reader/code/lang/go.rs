@@ -0,0 +1,33 @@
+//! Go functions, methods (with their receiver type) and type declarations.
+
+use super::{Declaration, identifier, skip_generics};
+use crate::reader::code::SymbolKind;
+
+pub(super) fn declaration(
+    line: &str,
+    found: impl Fn(SymbolKind, &str) -> Option<Declaration>,
+) -> Option<Declaration> {
+    if let Some(rest) = line.strip_prefix("func ") {
+        if let Some(receiver) = rest.strip_prefix('(') {
+            let (receiver, rest) = receiver.split_once(')')?;
+            let receiver_type = receiver.split_whitespace().last()?;
+            let receiver_type = identifier(receiver_type.trim_start_matches('*'))?;
+            let mut declaration = found(SymbolKind::Method, identifier(rest.trim_start())?)?;
+            declaration.receiver = Some(receiver_type.to_string());
+            return Some(declaration);
+        }
+        return found(SymbolKind::Function, identifier(rest)?);
+    }
+    let rest = line.strip_prefix("type ")?;
+    let name = identifier(rest)?;
+    let definition = rest[name.len()..].trim_start();
+    let definition = skip_generics(definition);
+    let kind = if definition.starts_with("struct") {
+        SymbolKind::Struct
+    } else if definition.starts_with("interface") {
+        SymbolKind::Interface
+    } else {
+        SymbolKind::Type
+    };
+    found(kind, name)
+}
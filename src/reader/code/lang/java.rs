@@ -0,0 +1,39 @@
+//! Java classes, interfaces, annotations, enums, records and methods.
+
+use super::{Declaration, c, identifier, strip_words};
+use crate::reader::code::SymbolKind;
+
+pub(super) fn declaration(
+    line: &str,
+    found: impl Fn(SymbolKind, &str) -> Option<Declaration>,
+) -> Option<Declaration> {
+    let rest = strip_words(
+        line,
+        &[
+            "public",
+            "private",
+            "protected",
+            "static",
+            "final",
+            "abstract",
+            "synchronized",
+            "native",
+            "default",
+            "sealed",
+            "non-sealed",
+            "strictfp",
+        ],
+    );
+    for (keyword, kind) in [
+        ("class ", SymbolKind::Class),
+        ("interface ", SymbolKind::Interface),
+        ("@interface ", SymbolKind::Interface),
+        ("enum ", SymbolKind::Enum),
+        ("record ", SymbolKind::Class),
+    ] {
+        if let Some(rest) = rest.strip_prefix(keyword) {
+            return found(kind, identifier(rest)?);
+        }
+    }
+    c::function(rest, true)
+}
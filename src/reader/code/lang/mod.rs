@@ -0,0 +1,127 @@
+//! Per-language declaration recognizers: given the first line of a
+//! statement, name the symbol it declares, if any.
+
+use super::{CodeLanguage, SymbolKind};
+
+mod c;
+mod go;
+mod java;
+mod python;
+mod ruby;
+mod rust;
+mod script;
+
+pub(super) struct Declaration {
+    pub(super) kind: SymbolKind,
+    pub(super) name: String,
+    /// Container named by the declaration itself (a Go method receiver).
+    pub(super) receiver: Option<String>,
+    /// Only a declaration if a body follows (C-style function definitions,
+    /// as opposed to calls and prototypes).
+    pub(super) requires_body: bool,
+}
+
+/// The symbol declared by the statement starting at `line` (trimmed).
+/// `member` is set inside a container, where methods need no keyword.
+pub(super) fn declaration(language: CodeLanguage, line: &str, member: bool) -> Option<Declaration> {
+    let found = |kind: SymbolKind, name: &str| {
+        Some(Declaration {
+            kind,
+            name: name.to_string(),
+            receiver: None,
+            requires_body: false,
+        })
+    };
+    match language {
+        CodeLanguage::Rust => rust::declaration(line, found),
+        CodeLanguage::Python => python::declaration(line, found),
+        CodeLanguage::Ruby => ruby::declaration(line, found),
+        CodeLanguage::Go => go::declaration(line, found),
+        CodeLanguage::JavaScript | CodeLanguage::TypeScript => {
+            script::declaration(line, member, found)
+        }
+        CodeLanguage::Java => java::declaration(line, found),
+        CodeLanguage::C | CodeLanguage::Cpp => c::declaration(line, member, found),
+    }
+}
+
+pub(super) fn is_keyword(word: &str) -> bool {
+    matches!(
+        word,
+        "if" | "else"
+            | "for"
+            | "while"
+            | "do"
+            | "switch"
+            | "case"
+            | "return"
+            | "catch"
+            | "sizeof"
+            | "new"
+            | "delete"
+            | "throw"
+            | "typeof"
+            | "await"
+            | "yield"
+            | "using"
+            | "synchronized"
+            | "function"
+            | "goto"
+    )
+}
+
+pub(super) fn strip_words<'a>(mut text: &'a str, words: &[&str]) -> &'a str {
+    'outer: loop {
+        for word in words {
+            if let Some(after) = text.strip_prefix(word) {
+                if after.starts_with(char::is_whitespace) {
+                    text = after.trim_start();
+                    continue 'outer;
+                }
+            }
+        }
+        return text;
+    }
+}
+
+pub(super) fn identifier(text: &str) -> Option<&str> {
+    let end = text
+        .find(|ch: char| !(ch.is_alphanumeric() || ch == '_' || ch == '$'))
+        .unwrap_or(text.len());
+    let name = &text[..end];
+    name.starts_with(|ch: char| ch.is_alphabetic() || ch == '_' || ch == '$')
+        .then_some(name)
+}
+
+/// An identifier with `::` path segments (`a::B`, `Outer::Inner`).
+pub(super) fn qualified_identifier(text: &str) -> Option<&str> {
+    let mut end = identifier(text)?.len();
+    while let Some(rest) = text[end..].strip_prefix("::") {
+        let Some(segment) = identifier(rest) else {
+            break;
+        };
+        end += 2 + segment.len();
+    }
+    Some(&text[..end])
+}
+
+/// Drop a leading `<...>` generic parameter list.
+pub(super) fn skip_generics(text: &str) -> &str {
+    if !text.starts_with('<') {
+        return text;
+    }
+    let mut depth = 0usize;
+    for (at, ch) in text.char_indices() {
+        match ch {
+            '<' => depth += 1,
+            '>' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return text[at + 1..].trim_start();
+                }
+            }
+            _ => {}
+        }
+    }
+    text
+}
@@ -0,0 +1,18 @@
+//! Python `def` and `class` statements.
+
+use super::{Declaration, identifier, strip_words};
+use crate::reader::code::SymbolKind;
+
+pub(super) fn declaration(
+    line: &str,
+    found: impl Fn(SymbolKind, &str) -> Option<Declaration>,
+) -> Option<Declaration> {
+    let rest = strip_words(line, &["async"]);
+    if let Some(rest) = rest.strip_prefix("def ") {
+        found(SymbolKind::Function, identifier(rest)?)
+    } else if let Some(rest) = rest.strip_prefix("class ") {
+        found(SymbolKind::Class, identifier(rest)?)
+    } else {
+        None
+    }
+}
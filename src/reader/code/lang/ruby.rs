@@ -0,0 +1,24 @@
+//! Ruby `def` (including `self.` singleton methods), `class` and `module`.
+
+use super::{Declaration, qualified_identifier};
+use crate::reader::code::SymbolKind;
+
+pub(super) fn declaration(
+    line: &str,
+    found: impl Fn(SymbolKind, &str) -> Option<Declaration>,
+) -> Option<Declaration> {
+    if let Some(rest) = line.strip_prefix("def ") {
+        let name: String = rest
+            .chars()
+            .take_while(|ch| !ch.is_whitespace() && *ch != '(' && *ch != ';')
+            .collect();
+        let name = name.strip_prefix("self.").unwrap_or(&name);
+        (!name.is_empty()).then(|| found(SymbolKind::Function, name))?
+    } else if let Some(rest) = line.strip_prefix("class ") {
+        found(SymbolKind::Class, qualified_identifier(rest)?)
+    } else if let Some(rest) = line.strip_prefix("module ") {
+        found(SymbolKind::Module, qualified_identifier(rest)?)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,72 @@
+//! Rust items: `fn`, types, traits, modules, constants, macros and `impl`
+//! blocks (named by their self type).
+
+use super::{Declaration, identifier, qualified_identifier, skip_generics, strip_words};
+use crate::reader::code::SymbolKind;
+
+pub(super) fn declaration(
+    line: &str,
+    found: impl Fn(SymbolKind, &str) -> Option<Declaration>,
+) -> Option<Declaration> {
+    let mut rest = line;
+    if let Some(after) = rest.strip_prefix("pub") {
+        if let Some(scoped) = after.strip_prefix('(') {
+            rest = scoped.split_once(')')?.1.trim_start();
+        } else if after.starts_with(char::is_whitespace) {
+            rest = after.trim_start();
+        }
+    }
+    loop {
+        let before = rest;
+        rest = strip_words(rest, &["async", "unsafe", "default"]);
+        if let Some(after) = rest.strip_prefix("extern ") {
+            rest = after.trim_start();
+            if rest.starts_with('"') {
+                rest = rest[1..].split_once('"')?.1.trim_start();
+            }
+        }
+        if let Some(after) = rest.strip_prefix("const ") {
+            if ["fn ", "unsafe ", "async ", "extern "]
+                .iter()
+                .any(|keyword| after.starts_with(keyword))
+            {
+                rest = after;
+            }
+        }
+        if rest == before {
+            break;
+        }
+    }
+    for (keyword, kind) in [
+        ("fn ", SymbolKind::Function),
+        ("struct ", SymbolKind::Struct),
+        ("enum ", SymbolKind::Enum),
+        ("union ", SymbolKind::Struct),
+        ("trait ", SymbolKind::Trait),
+        ("type ", SymbolKind::Type),
+        ("mod ", SymbolKind::Module),
+        ("const ", SymbolKind::Constant),
+        ("static ", SymbolKind::Constant),
+        ("macro_rules! ", SymbolKind::Macro),
+    ] {
+        if let Some(after) = rest.strip_prefix(keyword) {
+            if kind == SymbolKind::Module && line.ends_with(';') {
+                return None;
+            }
+            let after = after.strip_prefix("mut ").unwrap_or(after);
+            return found(kind, identifier(after)?);
+        }
+    }
+    let generics_and_type = rest.strip_prefix("impl")?;
+    if !(generics_and_type.starts_with('<') || generics_and_type.starts_with(' ')) {
+        return None;
+    }
+    let target = skip_generics(generics_and_type.trim_start());
+    let target = target.split(" where").next().unwrap_or(target);
+    let target = target.split('{').next().unwrap_or(target);
+    let target = target.rsplit(" for ").next().unwrap_or(target).trim();
+    let target = target.trim_start_matches(['&', '*']).trim_start();
+    let target = target.strip_prefix("dyn ").unwrap_or(target);
+    let name = qualified_identifier(target)?;
+    found(SymbolKind::Impl, name.rsplit("::").next().unwrap_or(name))
+}
@@ -0,0 +1,87 @@
+//! JavaScript and TypeScript: functions, classes, interfaces, enums,
+//! namespaces, type aliases, arrow-function bindings and class methods.
+
+use super::{Declaration, identifier, is_keyword, strip_words};
+use crate::reader::code::SymbolKind;
+
+pub(super) fn declaration(
+    line: &str,
+    member: bool,
+    found: impl Fn(SymbolKind, &str) -> Option<Declaration>,
+) -> Option<Declaration> {
+    let rest = strip_words(line, &["export", "default", "declare", "abstract", "async"]);
+    if let Some(after) = rest.strip_prefix("function") {
+        let after = after.trim_start_matches('*').trim_start();
+        return found(SymbolKind::Function, identifier(after)?);
+    }
+    for (keyword, kind) in [
+        ("class ", SymbolKind::Class),
+        ("interface ", SymbolKind::Interface),
+        ("enum ", SymbolKind::Enum),
+        ("namespace ", SymbolKind::Module),
+        ("type ", SymbolKind::Type),
+    ] {
+        if let Some(after) = rest.strip_prefix(keyword) {
+            return found(kind, identifier(after)?);
+        }
+    }
+    let assigned = if member {
+        strip_words(
+            rest,
+            &[
+                "public",
+                "private",
+                "protected",
+                "static",
+                "readonly",
+                "override",
+            ],
+        )
+    } else {
+        ["const ", "let ", "var "]
+            .iter()
+            .find_map(|keyword| rest.strip_prefix(keyword))?
+    };
+    let name = identifier(assigned.trim_start_matches('#'))?;
+    let after = assigned.trim_start_matches('#')[name.len()..].trim_start();
+    // `name = (...) => {` or `name = function (...) {`
+    if let Some(value) = after
+        .split_once('=')
+        .filter(|(annotation, _)| annotation.is_empty() || annotation.starts_with(':'))
+        .map(|(_, value)| value.trim_start())
+    {
+        if !value.starts_with('=')
+            && (value.contains("=>") || strip_words(value, &["async"]).starts_with("function"))
+        {
+            return found(SymbolKind::Function, name);
+        }
+        return None;
+    }
+    if !member {
+        return None;
+    }
+    let method = strip_words(
+        rest,
+        &[
+            "public",
+            "private",
+            "protected",
+            "static",
+            "override",
+            "async",
+            "get",
+            "set",
+        ],
+    );
+    let method = method.trim_start_matches(['*', '#']);
+    let name = identifier(method)?;
+    let after = method[name.len()..].trim_start();
+    (after.starts_with('(') || after.starts_with('<'))
+        .then(|| Declaration {
+            kind: SymbolKind::Method,
+            name: name.to_string(),
+            receiver: None,
+            requires_body: true,
+        })
+        .filter(|declaration| !is_keyword(&declaration.name))
+}
@@ -0,0 +1,694 @@
+//! Source code: language detection, symbol-level segmentation and an
+//! identifier tokenizer that splits `camelCase` and `snake_case` names.
+//!
+//! Symbols are found by a line scanner that tracks braces, brackets, strings
+//! and comments (indentation for Python and Ruby). It does not parse the
+//! language, so unusual formatting can merge or miss symbols; segments always
+//! cover the whole file, in order.
+//!
+//! `scan` classifies lines, `blocks` groups them into symbols, and
+//! `lang` holds one declaration recognizer per language.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::json;
+
+use crate::{
+    DocumentFormat, DocumentReader, ExtractedDocument, PassthroughReader, ReaderDiagnostics,
+    ReaderHint, ReaderOutput, Result,
+};
+
+mod blocks;
+mod lang;
+mod scan;
+
+use blocks::{Block, parse_blocks};
+use scan::{Line, scan};
+
+/// Frame `extra_metadata` key holding a source file's language.
+pub const CODE_LANGUAGE_METADATA_KEY: &str = "memvid.code.language";
+/// Frame `extra_metadata` key listing the symbols a file or chunk defines,
+/// comma separated and qualified by their container (`Parser::parse_query`).
+pub const CODE_SYMBOLS_METADATA_KEY: &str = "memvid.code.symbols";
+/// Frame `extra_metadata` key holding a chunk's symbol kind (`function`, `impl`, ...).
+pub const CODE_SYMBOL_KIND_METADATA_KEY: &str = "memvid.code.kind";
+/// Frame `extra_metadata` key holding a chunk's symbol signature.
+pub const CODE_SIGNATURE_METADATA_KEY: &str = "memvid.code.signature";
+/// Frame `extra_metadata` key holding a chunk's 1-based line range, `start-end`.
+pub const CODE_LINES_METADATA_KEY: &str = "memvid.code.lines";
+
+/// Gaps between symbols shorter than this are folded into a neighbouring
+/// segment rather than becoming a chunk of their own.
+const SMALL_GAP_CHARS: usize = 80;
+const MAX_SIGNATURE_CHARS: usize = 240;
+const MAX_DOC_CHARS: usize = 480;
+const MAX_IDENTIFIER_TERMS: usize = 256;
+
+/// Languages with symbol-level segmentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodeLanguage {
+    Rust,
+    Python,
+    JavaScript,
+    TypeScript,
+    Go,
+    Java,
+    C,
+    Cpp,
+    Ruby,
+}
+
+impl CodeLanguage {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::Python => "python",
+            Self::JavaScript => "javascript",
+            Self::TypeScript => "typescript",
+            Self::Go => "go",
+            Self::Java => "java",
+            Self::C => "c",
+            Self::Cpp => "cpp",
+            Self::Ruby => "ruby",
+        }
+    }
+
+    /// MIME type recorded on frames of this language.
+    #[must_use]
+    pub fn mime(self) -> &'static str {
+        match self {
+            Self::Rust => "text/x-rust",
+            Self::Python => "text/x-python",
+            Self::JavaScript => "text/javascript",
+            Self::TypeScript => "text/x-typescript",
+            Self::Go => "text/x-go",
+            Self::Java => "text/x-java",
+            Self::C => "text/x-c",
+            Self::Cpp => "text/x-c++",
+            Self::Ruby => "text/x-ruby",
+        }
+    }
+
+    #[must_use]
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "rs" => Some(Self::Rust),
+            "py" | "pyi" => Some(Self::Python),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "ts" | "tsx" | "mts" | "cts" => Some(Self::TypeScript),
+            "go" => Some(Self::Go),
+            "java" => Some(Self::Java),
+            "c" | "h" => Some(Self::C),
+            "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx" => Some(Self::Cpp),
+            "rb" => Some(Self::Ruby),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn from_mime(mime: &str) -> Option<Self> {
+        let mime = mime.split(';').next().unwrap_or(mime).trim();
+        match mime.to_ascii_lowercase().as_str() {
+            "text/x-rust" | "text/rust" => Some(Self::Rust),
+            "text/x-python" | "text/x-script.python" | "application/x-python" => Some(Self::Python),
+            "text/javascript" | "application/javascript" | "application/x-javascript" => {
+                Some(Self::JavaScript)
+            }
+            "text/x-typescript" | "application/typescript" | "application/x-typescript" => {
+                Some(Self::TypeScript)
+            }
+            "text/x-go" => Some(Self::Go),
+            "text/x-java" | "text/x-java-source" => Some(Self::Java),
+            "text/x-c" | "text/x-csrc" | "text/x-chdr" => Some(Self::C),
+            "text/x-c++" | "text/x-c++src" | "text/x-c++hdr" => Some(Self::Cpp),
+            "text/x-ruby" | "application/x-ruby" => Some(Self::Ruby),
+            _ => None,
+        }
+    }
+
+    /// Detect the language from a path or URI extension, then the MIME type,
+    /// then a `#!` interpreter line.
+    #[must_use]
+    pub fn detect(uri: Option<&str>, mime: Option<&str>, text: &str) -> Option<Self> {
+        uri.and_then(|uri| {
+            let path = uri.split(['?', '#']).next().unwrap_or(uri);
+            std::path::Path::new(path)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .and_then(Self::from_extension)
+        })
+        .or_else(|| mime.and_then(Self::from_mime))
+        .or_else(|| {
+            let first = text.trim_start_matches('\u{feff}').lines().next()?;
+            let interpreter = first.strip_prefix("#!")?;
+            let program = interpreter.split_whitespace().last()?;
+            let program = program.rsplit('/').next().unwrap_or(program);
+            if program.starts_with("python") {
+                Some(Self::Python)
+            } else if program.starts_with("node") || program == "deno" {
+                Some(Self::JavaScript)
+            } else if program.starts_with("ruby") {
+                Some(Self::Ruby)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn indented(self) -> bool {
+        matches!(self, Self::Python | Self::Ruby)
+    }
+
+    fn separator(self) -> &'static str {
+        match self {
+            Self::Rust | Self::Cpp => "::",
+            _ => ".",
+        }
+    }
+}
+
+/// What a symbol declares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    Function,
+    Method,
+    Class,
+    Struct,
+    Enum,
+    Trait,
+    Interface,
+    Impl,
+    Module,
+    Type,
+    Constant,
+    Macro,
+}
+
+impl SymbolKind {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Function => "function",
+            Self::Method => "method",
+            Self::Class => "class",
+            Self::Struct => "struct",
+            Self::Enum => "enum",
+            Self::Trait => "trait",
+            Self::Interface => "interface",
+            Self::Impl => "impl",
+            Self::Module => "module",
+            Self::Type => "type",
+            Self::Constant => "constant",
+            Self::Macro => "macro",
+        }
+    }
+
+    /// Kinds whose bodies are searched for member symbols.
+    fn is_container(self) -> bool {
+        !matches!(
+            self,
+            Self::Function | Self::Method | Self::Type | Self::Constant | Self::Macro
+        )
+    }
+}
+
+/// A declaration found in a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeSymbol {
+    pub name: String,
+    /// Name qualified by the enclosing symbols (`Parser::parse_query`).
+    pub path: String,
+    pub kind: SymbolKind,
+    /// Declaration up to its body, with whitespace collapsed.
+    pub signature: String,
+    /// Leading doc comment, or a Python docstring, as plain text.
+    pub doc: Option<String>,
+    /// 1-based line of the first doc comment or attribute line.
+    pub start_line: usize,
+    /// 1-based, inclusive.
+    pub end_line: usize,
+}
+
+/// A run of whole lines of a source file. The segments of a file,
+/// concatenated, reproduce it exactly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeSegment {
+    pub text: String,
+    /// 1-based line range, inclusive.
+    pub start_line: usize,
+    pub end_line: usize,
+    /// The symbol the segment belongs to; `None` for imports and other
+    /// top-level code between symbols. A symbol too long for one segment
+    /// spans several.
+    pub symbol: Option<CodeSymbol>,
+}
+
+impl CodeSegment {
+    /// `extra_metadata` recorded on the segment's chunk frame.
+    #[must_use]
+    pub fn metadata(&self, language: CodeLanguage) -> BTreeMap<String, String> {
+        let mut metadata = BTreeMap::new();
+        metadata.insert(
+            CODE_LANGUAGE_METADATA_KEY.to_string(),
+            language.label().to_string(),
+        );
+        metadata.insert(
+            CODE_LINES_METADATA_KEY.to_string(),
+            format!("{}-{}", self.start_line, self.end_line),
+        );
+        if let Some(symbol) = &self.symbol {
+            metadata.insert(CODE_SYMBOLS_METADATA_KEY.to_string(), symbol.path.clone());
+            metadata.insert(
+                CODE_SYMBOL_KIND_METADATA_KEY.to_string(),
+                symbol.kind.label().to_string(),
+            );
+            metadata.insert(
+                CODE_SIGNATURE_METADATA_KEY.to_string(),
+                symbol.signature.clone(),
+            );
+        }
+        metadata
+    }
+}
+
+/// Symbols declared in `text`, containers before their members.
+#[must_use]
+pub fn symbols(text: &str, language: CodeLanguage) -> Vec<CodeSymbol> {
+    fn flatten(blocks: Vec<Block>, out: &mut Vec<CodeSymbol>) {
+        for block in blocks {
+            out.push(block.symbol);
+            flatten(block.children, out);
+        }
+    }
+    let lines = scan(text, language);
+    let mut out = Vec::new();
+    flatten(parse_blocks(&lines, language), &mut out);
+    out
+}
+
+/// Split `text` into segments of one symbol each, keeping segments under
+/// `max_chars` where lines allow. Containers that fit stay whole; larger ones
+/// are split into their members.
+#[must_use]
+pub fn segments(text: &str, language: CodeLanguage, max_chars: usize) -> Vec<CodeSegment> {
+    let lines = scan(text, language);
+    let blocks = parse_blocks(&lines, language);
+    let mut builder = SegmentBuilder {
+        lines: &lines,
+        max_chars: max_chars.max(1),
+        out: Vec::new(),
+        prefix: None,
+    };
+    builder.emit(0, lines.len(), &blocks);
+    builder.finish()
+}
+
+/// Split an identifier into lowercase words at underscores, hyphens and case
+/// changes: `parseQuery`, `parse_query` and `ParseQuery` all give
+/// `["parse", "query"]`, and `HTTPServer` gives `["http", "server"]`.
+#[must_use]
+pub fn split_identifier(identifier: &str) -> Vec<String> {
+    let chars: Vec<char> = identifier.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for (idx, &ch) in chars.iter().enumerate() {
+        if !ch.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if let Some(&prev) = idx.checked_sub(1).and_then(|prev| chars.get(prev)) {
+            let next_lower = chars.get(idx + 1).is_some_and(|next| next.is_lowercase());
+            let boundary = ch.is_uppercase()
+                && (prev.is_lowercase()
+                    || prev.is_ascii_digit()
+                    || (prev.is_uppercase() && next_lower));
+            if boundary && !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.extend(ch.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Compound identifiers in `text`, each as its words joined by spaces, so a
+/// search for "parse query" matches `parse_query` and `parseQuery`.
+#[must_use]
+pub fn identifier_terms(text: &str) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+    let mut terms = Vec::new();
+    for identifier in text.split(|ch: char| !(ch.is_alphanumeric() || ch == '_')) {
+        if terms.len() >= MAX_IDENTIFIER_TERMS {
+            break;
+        }
+        let words = split_identifier(identifier);
+        if words.len() < 2 {
+            continue;
+        }
+        let term = words.join(" ");
+        if seen.insert(term.clone()) {
+            terms.push(term);
+        }
+    }
+    terms
+}
+
+/// Record a source file's language and symbols in `extra_metadata`.
+pub(crate) fn apply_code_metadata(
+    text: &str,
+    language: CodeLanguage,
+    extra_metadata: &mut BTreeMap<String, String>,
+) {
+    extra_metadata
+        .entry(CODE_LANGUAGE_METADATA_KEY.to_string())
+        .or_insert_with(|| language.label().to_string());
+    let paths: Vec<String> = symbols(text, language)
+        .into_iter()
+        .map(|symbol| symbol.path)
+        .collect();
+    if !paths.is_empty() {
+        extra_metadata
+            .entry(CODE_SYMBOLS_METADATA_KEY.to_string())
+            .or_insert_with(|| paths.join(", "));
+    }
+}
+
+/// Reader for source files. The text is kept verbatim; the language and
+/// symbol list go into the document metadata.
+pub struct CodeReader;
+
+impl CodeReader {
+    fn language(hint: &ReaderHint<'_>, text: &str) -> Option<CodeLanguage> {
+        CodeLanguage::detect(hint.uri, hint.mime, text)
+    }
+}
+
+impl DocumentReader for CodeReader {
+    fn name(&self) -> &'static str {
+        "code"
+    }
+
+    fn supports(&self, hint: &ReaderHint<'_>) -> bool {
+        matches!(hint.format, Some(DocumentFormat::Code))
+            || hint.mime.and_then(CodeLanguage::from_mime).is_some()
+    }
+
+    fn extract(&self, bytes: &[u8], hint: &ReaderHint<'_>) -> Result<ReaderOutput> {
+        let text = std::str::from_utf8(bytes)
+            .ok()
+            .map(|text| text.trim_start_matches('\u{feff}'));
+        let Some((text, language)) =
+            text.and_then(|text| Some((text, Self::language(hint, text)?)))
+        else {
+            let mut fallback = PassthroughReader.extract(bytes, hint)?;
+            fallback.reader_name = self.name().to_string();
+            fallback.diagnostics.mark_fallback();
+            fallback.diagnostics.record_warning(if text.is_none() {
+                "code reader expects UTF-8 source"
+            } else {
+                "code reader could not determine the language"
+            });
+            return Ok(fallback);
+        };
+        let names: Vec<String> = symbols(text, language)
+            .into_iter()
+            .map(|symbol| symbol.path)
+            .collect();
+        let mut document = ExtractedDocument::empty();
+        document.text = Some(text.to_string());
+        document.metadata = json!({
+            "language": language.label(),
+            "symbols": names,
+        });
+        document.mime_type = Some(language.mime().to_string());
+        Ok(ReaderOutput::new(document, self.name()).with_diagnostics(ReaderDiagnostics::default()))
+    }
+}
+
+struct SegmentBuilder<'a, 'b> {
+    lines: &'a [Line<'b>],
+    max_chars: usize,
+    out: Vec<CodeSegment>,
+    /// Short leading lines held for the first segment.
+    prefix: Option<(usize, usize)>,
+}
+
+impl SegmentBuilder<'_, '_> {
+    fn emit(&mut self, lo: usize, hi: usize, blocks: &[Block]) {
+        let mut cursor = lo;
+        for block in blocks {
+            self.gap(cursor, block.start);
+            self.block(block);
+            cursor = block.end + 1;
+        }
+        self.gap(cursor, hi);
+    }
+
+    fn block(&mut self, block: &Block) {
+        let size = self.chars(block.start, block.end + 1);
+        match block.children.first() {
+            Some(first) if size > self.max_chars => {
+                self.push(block.start, first.start, Some(&block.symbol));
+                self.emit(first.start, block.end + 1, &block.children);
+            }
+            _ => self.push(block.start, block.end + 1, Some(&block.symbol)),
+        }
+    }
+
+    fn gap(&mut self, lo: usize, hi: usize) {
+        if lo >= hi {
+            return;
+        }
+        let content: usize = self.lines[lo..hi]
+            .iter()
+            .map(|line| line.trimmed().chars().count())
+            .sum();
+        if content >= SMALL_GAP_CHARS {
+            self.push(lo, hi, None);
+        } else if let Some(last) = self.out.last_mut() {
+            for line in &self.lines[lo..hi] {
+                last.text.push_str(line.text);
+            }
+            last.end_line = hi;
+        } else {
+            let start = self.prefix.map_or(lo, |(start, _)| start);
+            self.prefix = Some((start, hi));
+        }
+    }
+
+    /// Push lines `lo..hi` as one or more segments of at most `max_chars`.
+    fn push(&mut self, lo: usize, hi: usize, symbol: Option<&CodeSymbol>) {
+        let lo = match self.prefix.take() {
+            Some((start, _)) => start,
+            None => lo,
+        };
+        let mut start = lo;
+        while start < hi {
+            let mut end = start;
+            let mut size = 0;
+            while end < hi && (end == start || size + self.lines[end].chars <= self.max_chars) {
+                size += self.lines[end].chars;
+                end += 1;
+            }
+            self.out.push(CodeSegment {
+                text: self.lines[start..end]
+                    .iter()
+                    .map(|line| line.text)
+                    .collect(),
+                start_line: start + 1,
+                end_line: end,
+                symbol: symbol.cloned(),
+            });
+            start = end;
+        }
+    }
+
+    fn chars(&self, lo: usize, hi: usize) -> usize {
+        self.lines[lo..hi].iter().map(|line| line.chars).sum()
+    }
+
+    fn finish(mut self) -> Vec<CodeSegment> {
+        if let Some((start, end)) = self.prefix.take() {
+            self.push(start, end, None);
+        }
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUST: &str = r#"//! Query parsing.
+
+use std::collections::HashMap;
+
+/// Parses a query string into terms.
+#[must_use]
+pub fn parse_query(input: &str) -> Vec<String> {
+    input.split(' ').map(|s| s.to_string()).collect()
+}
+
+pub struct Parser<'a> {
+    source: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    /// Creates a parser.
+    pub fn new(source: &'a str) -> Self {
+        let open = '{';
+        Self { source }
+    }
+
+    pub(crate) async fn next_token(
+        &mut self,
+        limit: usize,
+    ) -> Option<&'a str>
+    where
+        Self: Sized,
+    {
+        let text = "}";
+        None
+    }
+}
+"#;
+
+    #[test]
+    fn rust_symbols_and_members() {
+        let found = symbols(RUST, CodeLanguage::Rust);
+        let paths: Vec<&str> = found.iter().map(|symbol| symbol.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "parse_query",
+                "Parser",
+                "Parser",
+                "Parser::new",
+                "Parser::next_token"
+            ]
+        );
+        let parse = &found[0];
+        assert_eq!(parse.kind, SymbolKind::Function);
+        assert_eq!(
+            parse.signature,
+            "pub fn parse_query(input: &str) -> Vec<String>"
+        );
+        assert_eq!(
+            parse.doc.as_deref(),
+            Some("Parses a query string into terms.")
+        );
+        assert_eq!((parse.start_line, parse.end_line), (5, 9));
+        assert_eq!(found[2].kind, SymbolKind::Impl);
+        let next = &found[4];
+        assert_eq!(next.kind, SymbolKind::Method);
+        assert_eq!(next.end_line, 31);
+        assert!(next.signature.ends_with("where Self: Sized,"));
+    }
+
+    #[test]
+    fn segments_cover_the_file_and_split_large_containers() {
+        let whole = segments(RUST, CodeLanguage::Rust, 10_000);
+        assert_eq!(
+            whole.iter().map(|s| s.text.as_str()).collect::<String>(),
+            RUST
+        );
+        assert_eq!(whole.len(), 3);
+        assert_eq!(
+            whole[0].symbol.as_ref().map(|s| s.path.as_str()),
+            Some("parse_query")
+        );
+        assert_eq!(whole[0].start_line, 1);
+
+        let split = segments(RUST, CodeLanguage::Rust, 200);
+        assert_eq!(
+            split.iter().map(|s| s.text.as_str()).collect::<String>(),
+            RUST
+        );
+        let paths: Vec<Option<&str>> = split
+            .iter()
+            .map(|segment| segment.symbol.as_ref().map(|s| s.path.as_str()))
+            .collect();
+        assert!(paths.contains(&Some("Parser::new")));
+        assert!(paths.contains(&Some("Parser::next_token")));
+        let last = split.last().expect("segment");
+        assert!(last.text.ends_with("    }\n}\n"));
+        assert_eq!(
+            last.metadata(CodeLanguage::Rust)
+                .get(CODE_LINES_METADATA_KEY)
+                .map(String::as_str),
+            Some(format!("{}-32", last.start_line).as_str())
+        );
+    }
+
+    #[test]
+    fn python_classes_methods_and_docstrings() {
+        let source = "import os\n\n\nclass QueryParser:\n    \"\"\"Splits queries.\"\"\"\n\n    @staticmethod\n    def parse_query(\n        text,\n    ):\n        value = '''\nnot code: def fake():\n'''\n        return text.split()\n\n\nasync def main():\n    pass\n";
+        let found = symbols(source, CodeLanguage::Python);
+        let paths: Vec<&str> = found.iter().map(|symbol| symbol.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["QueryParser", "QueryParser.parse_query", "main"]
+        );
+        assert_eq!(found[0].doc.as_deref(), Some("Splits queries."));
+        assert_eq!(found[1].kind, SymbolKind::Method);
+        assert_eq!(found[1].signature, "def parse_query( text, )");
+        assert_eq!((found[1].start_line, found[1].end_line), (7, 14));
+    }
+
+    #[test]
+    fn script_go_and_c_declarations() {
+        let ts = "export async function loadUser(id: string) {\n  return id;\n}\nexport const fetchAll = async () => {\n  return [];\n};\nclass Store {\n  private items = [];\n  get size() {\n    return 0;\n  }\n  if (x) {\n  }\n}\n";
+        let paths: Vec<String> = symbols(ts, CodeLanguage::TypeScript)
+            .into_iter()
+            .map(|symbol| symbol.path)
+            .collect();
+        assert_eq!(paths, vec!["loadUser", "fetchAll", "Store", "Store.size"]);
+
+        let go = "func (s *Server) handleRequest(w http.ResponseWriter) {\n}\n\ntype Server struct {\n\tport int\n}\n";
+        let found = symbols(go, CodeLanguage::Go);
+        assert_eq!(found[0].path, "Server.handleRequest");
+        assert_eq!(found[0].kind, SymbolKind::Method);
+        assert_eq!(found[1].kind, SymbolKind::Struct);
+
+        let c = "#include <stdio.h>\nint helper(int x);\n\nstatic int helper(int x)\n{\n    return x;\n}\n";
+        let found = symbols(c, CodeLanguage::C);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "helper");
+        assert_eq!(found[0].start_line, 4);
+    }
+
+    #[test]
+    fn identifiers_split_on_case_and_underscores() {
+        assert_eq!(split_identifier("parse_query"), vec!["parse", "query"]);
+        assert_eq!(split_identifier("parseQuery"), vec!["parse", "query"]);
+        assert_eq!(split_identifier("HTTPServer"), vec!["http", "server"]);
+        assert_eq!(split_identifier("v2Api"), vec!["v2", "api"]);
+        assert_eq!(
+            identifier_terms("fn parse_query() { parseQuery(); let x = 1; }"),
+            vec!["parse query"]
+        );
+    }
+
+    #[test]
+    fn detects_language_from_uri_mime_and_shebang() {
+        assert_eq!(
+            CodeLanguage::detect(Some("mv2://repo/src/lib.rs"), None, ""),
+            Some(CodeLanguage::Rust)
+        );
+        assert_eq!(
+            CodeLanguage::detect(None, Some("text/x-python; charset=utf-8"), ""),
+            Some(CodeLanguage::Python)
+        );
+        assert_eq!(
+            CodeLanguage::detect(None, None, "#!/usr/bin/env node\nconsole.log(1)\n"),
+            Some(CodeLanguage::JavaScript)
+        );
+        assert_eq!(CodeLanguage::detect(Some("notes.txt"), None, "x"), None);
+    }
+}
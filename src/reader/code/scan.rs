@@ -0,0 +1,241 @@
+//! Line scanner: tracks strings, comments and bracket depth line by line,
+//! so block parsing can find statements without a language grammar.
+
+use super::CodeLanguage;
+
+/// Scanner state carried across lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Code,
+    BlockComment,
+    Str {
+        close: char,
+        triple: bool,
+        escapes: bool,
+        multiline: bool,
+    },
+    RawStr {
+        hashes: usize,
+    },
+}
+
+#[derive(Debug)]
+pub(super) struct Line<'a> {
+    /// Including the trailing newline.
+    pub(super) text: &'a str,
+    pub(super) chars: usize,
+    pub(super) indent: usize,
+    /// The line starts inside a block comment.
+    pub(super) in_comment: bool,
+    /// The line starts inside a string.
+    pub(super) in_string: bool,
+    pub(super) depth_before: usize,
+    pub(super) depth_after: usize,
+    /// Parentheses and brackets open before and after the line (braces too
+    /// for indentation-based languages).
+    pub(super) nesting_before: usize,
+    pub(super) nesting_after: usize,
+    /// The line opens a brace outside strings and comments.
+    pub(super) opens: bool,
+    /// The line has something other than comments and whitespace.
+    pub(super) has_code: bool,
+}
+
+impl Line<'_> {
+    pub(super) fn trimmed(&self) -> &str {
+        self.text.trim()
+    }
+
+    /// Starts a statement: not inside a string, comment or open bracket.
+    pub(super) fn is_statement(&self) -> bool {
+        self.has_code && !self.in_comment && !self.in_string && self.nesting_before == 0
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn scan(text: &str, language: CodeLanguage) -> Vec<Line<'_>> {
+    let line_comment = if language.indented() { "#" } else { "//" };
+    let block_comments = !language.indented();
+    let mut mode = Mode::Code;
+    let mut depth = 0usize;
+    let mut nesting = 0usize;
+    let mut lines = Vec::new();
+
+    for raw in text.split_inclusive('\n') {
+        let chars: Vec<char> = raw.trim_end_matches(['\n', '\r']).chars().collect();
+        let mut line = Line {
+            text: raw,
+            chars: raw.chars().count(),
+            indent: chars.iter().take_while(|ch| ch.is_whitespace()).count(),
+            in_comment: mode == Mode::BlockComment,
+            in_string: matches!(mode, Mode::Str { .. } | Mode::RawStr { .. }),
+            depth_before: depth,
+            depth_after: depth,
+            nesting_before: nesting,
+            nesting_after: nesting,
+            opens: false,
+            has_code: false,
+        };
+        let starts_with = |idx: usize, pattern: &str| {
+            pattern
+                .chars()
+                .enumerate()
+                .all(|(offset, ch)| chars.get(idx + offset) == Some(&ch))
+        };
+        let mut idx = 0;
+        while idx < chars.len() {
+            let ch = chars[idx];
+            match mode {
+                Mode::BlockComment => {
+                    if starts_with(idx, "*/") {
+                        mode = Mode::Code;
+                        idx += 1;
+                    }
+                }
+                Mode::Str {
+                    close,
+                    triple,
+                    escapes,
+                    ..
+                } => {
+                    line.has_code = true;
+                    if escapes && ch == '\\' {
+                        idx += 1;
+                    } else if ch == close {
+                        if !triple {
+                            mode = Mode::Code;
+                        } else if chars.get(idx + 1) == Some(&close)
+                            && chars.get(idx + 2) == Some(&close)
+                        {
+                            mode = Mode::Code;
+                            idx += 2;
+                        }
+                    }
+                }
+                Mode::RawStr { hashes } => {
+                    line.has_code = true;
+                    if ch == '"' && (1..=hashes).all(|offset| chars.get(idx + offset) == Some(&'#'))
+                    {
+                        mode = Mode::Code;
+                        idx += hashes;
+                    }
+                }
+                Mode::Code => {
+                    if starts_with(idx, line_comment) {
+                        break;
+                    }
+                    if block_comments && starts_with(idx, "/*") {
+                        mode = Mode::BlockComment;
+                        idx += 2;
+                        continue;
+                    }
+                    if !ch.is_whitespace() {
+                        line.has_code = true;
+                    }
+                    let after_ident =
+                        idx > 0 && (chars[idx - 1].is_alphanumeric() || chars[idx - 1] == '_');
+                    match ch {
+                        'r' if language == CodeLanguage::Rust && !after_ident => {
+                            let hashes = chars[idx + 1..]
+                                .iter()
+                                .take_while(|&&next| next == '#')
+                                .count();
+                            if chars.get(idx + 1 + hashes) == Some(&'"') {
+                                mode = Mode::RawStr { hashes };
+                                idx += hashes + 1;
+                            }
+                        }
+                        '"' | '\'' if language == CodeLanguage::Python => {
+                            let triple = starts_with(idx, if ch == '"' { "\"\"\"" } else { "'''" });
+                            mode = Mode::Str {
+                                close: ch,
+                                triple,
+                                escapes: true,
+                                multiline: triple,
+                            };
+                            if triple {
+                                idx += 2;
+                            }
+                        }
+                        '"' => {
+                            mode = Mode::Str {
+                                close: '"',
+                                triple: false,
+                                escapes: true,
+                                multiline: matches!(
+                                    language,
+                                    CodeLanguage::Rust | CodeLanguage::Ruby
+                                ),
+                            };
+                        }
+                        '\'' if matches!(
+                            language,
+                            CodeLanguage::JavaScript
+                                | CodeLanguage::TypeScript
+                                | CodeLanguage::Ruby
+                        ) =>
+                        {
+                            mode = Mode::Str {
+                                close: '\'',
+                                triple: false,
+                                escapes: true,
+                                multiline: language == CodeLanguage::Ruby,
+                            };
+                        }
+                        '\'' => {
+                            // Character literal, or a Rust lifetime.
+                            if chars.get(idx + 1) == Some(&'\\') {
+                                if let Some(end) =
+                                    chars[idx + 2..].iter().position(|&next| next == '\'')
+                                {
+                                    idx += end + 2;
+                                }
+                            } else if chars.get(idx + 2) == Some(&'\'') {
+                                idx += 2;
+                            }
+                        }
+                        '`' if matches!(
+                            language,
+                            CodeLanguage::JavaScript | CodeLanguage::TypeScript | CodeLanguage::Go
+                        ) =>
+                        {
+                            mode = Mode::Str {
+                                close: '`',
+                                triple: false,
+                                escapes: language != CodeLanguage::Go,
+                                multiline: true,
+                            };
+                        }
+                        '{' => {
+                            depth += 1;
+                            line.opens = true;
+                            if language.indented() {
+                                nesting += 1;
+                            }
+                        }
+                        '}' => {
+                            depth = depth.saturating_sub(1);
+                            if language.indented() {
+                                nesting = nesting.saturating_sub(1);
+                            }
+                        }
+                        '(' | '[' => nesting += 1,
+                        ')' | ']' => nesting = nesting.saturating_sub(1),
+                        _ => {}
+                    }
+                }
+            }
+            idx += 1;
+        }
+        if let Mode::Str {
+            multiline: false, ..
+        } = mode
+        {
+            mode = Mode::Code;
+        }
+        line.depth_after = depth;
+        line.nesting_after = nesting;
+        lines.push(line);
+    }
+    lines
+}
@@ -0,0 +1,737 @@
+//! RFC 5322 / MIME messages and mbox mailboxes: header unfolding and
+//! encoded-word decoding, multipart bodies, quoted-printable and base64
+//! transfer encodings, and a reader that renders a mailbox as one document.
+
+use std::collections::BTreeMap;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chrono::DateTime;
+use serde_json::{Map, Value, json};
+
+use crate::{
+    DocumentFormat, DocumentReader, ExtractedDocument, HtmlReader, MemvidError, PassthroughReader,
+    ReaderDiagnostics, ReaderHint, ReaderOutput, Result,
+};
+
+/// Frame `extra_metadata` key holding a message's `From` header.
+pub const EMAIL_FROM_METADATA_KEY: &str = "memvid.email.from";
+/// Frame `extra_metadata` key holding a message's `To` header.
+pub const EMAIL_TO_METADATA_KEY: &str = "memvid.email.to";
+/// Frame `extra_metadata` key holding a message's `Cc` header.
+pub const EMAIL_CC_METADATA_KEY: &str = "memvid.email.cc";
+/// Frame `extra_metadata` key holding a message's `Date` header.
+pub const EMAIL_DATE_METADATA_KEY: &str = "memvid.email.date";
+/// Frame `extra_metadata` key holding a message's `Subject` header.
+pub const EMAIL_SUBJECT_METADATA_KEY: &str = "memvid.email.subject";
+/// Frame `extra_metadata` key holding a message's `Message-ID`, without angle brackets.
+pub const EMAIL_MESSAGE_ID_METADATA_KEY: &str = "memvid.email.message_id";
+/// Frame `extra_metadata` key holding the `Message-ID` a message replies to.
+pub const EMAIL_IN_REPLY_TO_METADATA_KEY: &str = "memvid.email.in_reply_to";
+
+const EMAIL_MIME: &str = "message/rfc822";
+/// Multipart nesting deeper than this is treated as an opaque part.
+const MAX_MULTIPART_DEPTH: usize = 16;
+
+/// One parsed email message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EmailMessage {
+    /// `Message-ID` without angle brackets.
+    pub message_id: Option<String>,
+    pub in_reply_to: Option<String>,
+    /// `References`, oldest first.
+    pub references: Vec<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub cc: Option<String>,
+    pub subject: Option<String>,
+    /// `Date` header as written.
+    pub date: Option<String>,
+    /// `Date` as Unix seconds, when it parses as RFC 2822.
+    pub timestamp: Option<i64>,
+    /// Text of the message body. `text/plain` alternatives are preferred;
+    /// HTML-only bodies are converted through [`HtmlReader`].
+    pub body: String,
+    pub attachments: Vec<EmailAttachment>,
+}
+
+/// A decoded non-body part of a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailAttachment {
+    pub filename: Option<String>,
+    /// Lower-cased MIME type, without parameters.
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+impl EmailMessage {
+    /// Parse a single RFC 5322 message.
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let part = Part::split(bytes);
+        if part.headers.is_empty() {
+            return Err(MemvidError::ExtractionFailed {
+                reason: "email message has no headers".into(),
+            });
+        }
+
+        let mut content = Content::default();
+        content.walk(&part, 0);
+
+        let header = |name: &str| {
+            part.header(name)
+                .map(decode_header)
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let date = header("date");
+        Ok(Self {
+            message_id: part
+                .header("message-id")
+                .and_then(|value| message_ids(value).into_iter().next()),
+            in_reply_to: part
+                .header("in-reply-to")
+                .and_then(|value| message_ids(value).into_iter().next()),
+            references: part
+                .header("references")
+                .map(message_ids)
+                .unwrap_or_default(),
+            from: header("from"),
+            to: header("to"),
+            cc: header("cc"),
+            subject: header("subject"),
+            timestamp: date.as_deref().and_then(parse_date),
+            date,
+            body: content.texts.join("\n\n"),
+            attachments: content.attachments,
+        })
+    }
+
+    /// Parse an mbox mailbox, or a single message when `bytes` does not start
+    /// with an mbox `From ` separator line.
+    pub fn parse_mailbox(bytes: &[u8]) -> Result<Vec<Self>> {
+        if !bytes.starts_with(b"From ") {
+            return Ok(vec![Self::parse(bytes)?]);
+        }
+        split_mbox(bytes)
+            .iter()
+            .filter(|message| !message.iter().all(u8::is_ascii_whitespace))
+            .map(|message| Self::parse(message))
+            .collect()
+    }
+
+    /// Message ids this message answers, closest first: `In-Reply-To`, then
+    /// `References` from newest to oldest.
+    pub fn reply_targets(&self) -> impl Iterator<Item = &str> {
+        self.in_reply_to
+            .iter()
+            .chain(self.references.iter().rev())
+            .map(String::as_str)
+    }
+
+    /// Header values worth keeping as frame metadata, keyed by the
+    /// `memvid.email.*` constants.
+    #[must_use]
+    pub fn metadata(&self) -> BTreeMap<String, String> {
+        [
+            (EMAIL_FROM_METADATA_KEY, &self.from),
+            (EMAIL_TO_METADATA_KEY, &self.to),
+            (EMAIL_CC_METADATA_KEY, &self.cc),
+            (EMAIL_DATE_METADATA_KEY, &self.date),
+            (EMAIL_SUBJECT_METADATA_KEY, &self.subject),
+            (EMAIL_MESSAGE_ID_METADATA_KEY, &self.message_id),
+            (EMAIL_IN_REPLY_TO_METADATA_KEY, &self.in_reply_to),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.clone().map(|value| (key.to_string(), value)))
+        .collect()
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        for (name, value) in [
+            ("Subject", &self.subject),
+            ("From", &self.from),
+            ("To", &self.to),
+            ("Cc", &self.cc),
+            ("Date", &self.date),
+        ] {
+            if let Some(value) = value {
+                out.push_str(name);
+                out.push_str(": ");
+                out.push_str(value);
+                out.push('\n');
+            }
+        }
+        if !self.body.is_empty() {
+            out.push('\n');
+            out.push_str(&self.body);
+        }
+        out.trim_end().to_string()
+    }
+}
+
+/// Reader for `.eml` messages and mbox mailboxes ingested as a single
+/// document. Use [`Memvid::put_emails`](crate::Memvid::put_emails) for one
+/// frame per message with threading and attachments.
+pub struct EmailReader;
+
+impl DocumentReader for EmailReader {
+    fn name(&self) -> &'static str {
+        "email"
+    }
+
+    fn supports(&self, hint: &ReaderHint<'_>) -> bool {
+        matches!(hint.format, Some(DocumentFormat::Email))
+            || hint.mime.is_some_and(|mime| {
+                mime.eq_ignore_ascii_case(EMAIL_MIME)
+                    || mime.eq_ignore_ascii_case("application/mbox")
+            })
+    }
+
+    fn extract(&self, bytes: &[u8], hint: &ReaderHint<'_>) -> Result<ReaderOutput> {
+        match EmailMessage::parse_mailbox(bytes) {
+            Ok(messages) if !messages.is_empty() => {
+                let mut metadata = Map::new();
+                metadata.insert("messages".to_string(), json!(messages.len()));
+                if let [message] = messages.as_slice() {
+                    for (key, value) in message.metadata() {
+                        metadata.insert(key, Value::String(value));
+                    }
+                }
+                let mut document = ExtractedDocument::empty();
+                document.text = Some(
+                    messages
+                        .iter()
+                        .map(EmailMessage::render)
+                        .collect::<Vec<_>>()
+                        .join("\n\n"),
+                );
+                document.metadata = Value::Object(metadata);
+                document.mime_type = Some(EMAIL_MIME.to_string());
+                Ok(ReaderOutput::new(document, self.name())
+                    .with_diagnostics(ReaderDiagnostics::default()))
+            }
+            outcome => {
+                let mut fallback = PassthroughReader.extract(bytes, hint)?;
+                fallback.reader_name = self.name().to_string();
+                fallback.diagnostics.mark_fallback();
+                fallback.diagnostics.record_warning(match outcome {
+                    Err(err) => format!("email reader error: {err}"),
+                    Ok(_) => "email reader found no messages".to_string(),
+                });
+                Ok(fallback)
+            }
+        }
+    }
+}
+
+/// Copy the headers an [`EmailReader`] recorded for a single message into
+/// frame fields: the subject as title when none was given, and the
+/// `memvid.email.*` keys as extra metadata.
+pub(crate) fn apply_message_metadata(
+    metadata: &Value,
+    title: &mut Option<String>,
+    extra_metadata: &mut BTreeMap<String, String>,
+) {
+    let Some(fields) = metadata.as_object() else {
+        return;
+    };
+    if title.is_none() {
+        *title = fields
+            .get(EMAIL_SUBJECT_METADATA_KEY)
+            .and_then(Value::as_str)
+            .map(str::to_string);
+    }
+    for (key, value) in fields {
+        if let (true, Some(value)) = (key.starts_with("memvid.email."), value.as_str()) {
+            extra_metadata
+                .entry(key.clone())
+                .or_insert_with(|| value.to_string());
+        }
+    }
+}
+
+/// Body text and attachments gathered while walking a MIME tree.
+#[derive(Default)]
+struct Content {
+    texts: Vec<String>,
+    attachments: Vec<EmailAttachment>,
+    /// Whether any text came from a `text/plain` part.
+    plain: bool,
+}
+
+impl Content {
+    fn walk(&mut self, part: &Part<'_>, depth: usize) {
+        let (content_type, params) = part
+            .header("content-type")
+            .map_or_else(|| ("text/plain".to_string(), Vec::new()), parse_params);
+        let boundary = param(&params, "boundary");
+
+        if let (true, Some(boundary)) = (content_type.starts_with("multipart/"), boundary) {
+            if depth < MAX_MULTIPART_DEPTH {
+                let parts = split_multipart(part.body, boundary);
+                if content_type == "multipart/alternative" {
+                    self.walk_alternatives(&parts, depth);
+                } else {
+                    for sub in parts {
+                        self.walk(&Part::split(sub), depth + 1);
+                    }
+                }
+                return;
+            }
+        }
+
+        let disposition = part.header("content-disposition").map(parse_params);
+        let filename = disposition
+            .as_ref()
+            .and_then(|(_, params)| param(params, "filename"))
+            .or_else(|| param(&params, "name"))
+            .map(decode_header);
+        let is_attachment = disposition
+            .as_ref()
+            .is_some_and(|(kind, _)| kind == "attachment")
+            || !matches!(content_type.as_str(), "text/plain" | "text/html")
+            || (filename.is_some() && content_type != "text/plain");
+        let data = decode_transfer(
+            part.body,
+            part.header("content-transfer-encoding").unwrap_or_default(),
+        );
+
+        if is_attachment {
+            self.attachments.push(EmailAttachment {
+                filename,
+                content_type,
+                data,
+            });
+            return;
+        }
+
+        let text = decode_charset(&data, param(&params, "charset").unwrap_or("utf-8"));
+        let text = if content_type == "text/html" {
+            html_text(&text)
+        } else {
+            self.plain = true;
+            text.replace("\r\n", "\n").trim().to_string()
+        };
+        if !text.is_empty() {
+            self.texts.push(text);
+        }
+    }
+
+    /// Keep the first alternative with `text/plain` content, else the first
+    /// with any text; attachments from every alternative are kept.
+    fn walk_alternatives(&mut self, parts: &[&[u8]], depth: usize) {
+        let mut candidates: Vec<Content> = Vec::with_capacity(parts.len());
+        for sub in parts {
+            let mut content = Content::default();
+            content.walk(&Part::split(sub), depth + 1);
+            candidates.push(content);
+        }
+        let chosen = candidates
+            .iter()
+            .position(|content| content.plain && !content.texts.is_empty())
+            .or_else(|| {
+                candidates
+                    .iter()
+                    .position(|content| !content.texts.is_empty())
+            });
+        for (idx, content) in candidates.into_iter().enumerate() {
+            if Some(idx) == chosen {
+                self.plain |= content.plain;
+                self.texts.extend(content.texts);
+            }
+            self.attachments.extend(content.attachments);
+        }
+    }
+}
+
+/// Unfolded headers and the raw body of a message or MIME part.
+struct Part<'a> {
+    headers: Vec<(String, String)>,
+    body: &'a [u8],
+}
+
+impl<'a> Part<'a> {
+    fn split(bytes: &'a [u8]) -> Self {
+        let mut headers: Vec<(String, String)> = Vec::new();
+        let mut pos = 0;
+        while pos < bytes.len() {
+            let end = bytes[pos..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(bytes.len(), |offset| pos + offset);
+            let line = String::from_utf8_lossy(&bytes[pos..end]);
+            let line = line.trim_end_matches('\r');
+            pos = (end + 1).min(bytes.len());
+            if line.is_empty() {
+                break;
+            }
+            if line.starts_with([' ', '\t']) {
+                if let Some((_, value)) = headers.last_mut() {
+                    value.push(' ');
+                    value.push_str(line.trim());
+                }
+            } else if let Some((name, value)) = line.split_once(':') {
+                headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+            }
+        }
+        Self {
+            headers,
+            body: &bytes[pos..],
+        }
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Messages of an mbox mailbox, split on `From ` lines with `>From `
+/// quoting undone.
+fn split_mbox(bytes: &[u8]) -> Vec<Vec<u8>> {
+    let mut messages: Vec<Vec<u8>> = Vec::new();
+    for line in bytes.split_inclusive(|&b| b == b'\n') {
+        if line.starts_with(b"From ") {
+            messages.push(Vec::new());
+            continue;
+        }
+        let Some(current) = messages.last_mut() else {
+            continue;
+        };
+        let quoted = line.iter().take_while(|&&b| b == b'>').count();
+        if quoted > 0 && line[quoted..].starts_with(b"From ") {
+            current.extend_from_slice(&line[1..]);
+        } else {
+            current.extend_from_slice(line);
+        }
+    }
+    messages
+}
+
+/// Body parts between `--boundary` delimiter lines, without the preamble,
+/// the epilogue, or the line break that belongs to each delimiter.
+fn split_multipart<'a>(body: &'a [u8], boundary: &str) -> Vec<&'a [u8]> {
+    let delimiter = format!("--{boundary}");
+    let mut parts = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut pos = 0;
+    while pos < body.len() {
+        let end = body[pos..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(body.len(), |offset| pos + offset);
+        let next = (end + 1).min(body.len());
+        let line = body[pos..end].trim_ascii_end();
+        if let Some(rest) = line.strip_prefix(delimiter.as_bytes()) {
+            if rest.is_empty() || rest == b"--" {
+                if let Some(start) = start {
+                    parts.push(trim_line_break(&body[start..pos]));
+                }
+                if rest == b"--" {
+                    return parts;
+                }
+                start = Some(next);
+            }
+        }
+        pos = next;
+    }
+    if let Some(start) = start {
+        parts.push(&body[start..]);
+    }
+    parts
+}
+
+fn trim_line_break(bytes: &[u8]) -> &[u8] {
+    let bytes = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+    bytes.strip_suffix(b"\r").unwrap_or(bytes)
+}
+
+/// The lower-cased value before the first `;` and the `name=value`
+/// parameters after it, with quotes removed and RFC 2231 `name*=` values
+/// percent-decoded.
+fn parse_params(value: &str) -> (String, Vec<(String, String)>) {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for ch in value.chars() {
+        match ch {
+            '"' => {
+                quoted = !quoted;
+                current.push(ch);
+            }
+            ';' if !quoted => segments.push(std::mem::take(&mut current)),
+            _ => current.push(ch),
+        }
+    }
+    segments.push(current);
+
+    let mut segments = segments.into_iter();
+    let kind = segments
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let params = segments
+        .filter_map(|segment| {
+            let (name, value) = segment.split_once('=')?;
+            let name = name.trim().to_ascii_lowercase();
+            let value = value.trim().trim_matches('"');
+            Some(match name.strip_suffix('*') {
+                Some(name) => (name.to_string(), decode_extended_param(value)),
+                None => (name, value.to_string()),
+            })
+        })
+        .collect();
+    (kind, params)
+}
+
+fn param<'a>(params: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// RFC 2231 `charset'language'percent-encoded` parameter value.
+fn decode_extended_param(value: &str) -> String {
+    let mut pieces = value.splitn(3, '\'');
+    let (Some(charset), Some(_), Some(encoded)) = (pieces.next(), pieces.next(), pieces.next())
+    else {
+        return value.to_string();
+    };
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut iter = encoded.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let high = iter.next().and_then(hex_value);
+            let low = iter.next().and_then(hex_value);
+            if let (Some(high), Some(low)) = (high, low) {
+                bytes.push(high << 4 | low);
+                continue;
+            }
+        }
+        bytes.push(byte);
+    }
+    decode_charset(&bytes, charset)
+}
+
+/// Header value with RFC 2047 encoded words (`=?charset?B|Q?text?=`)
+/// decoded; whitespace between adjacent encoded words is dropped.
+fn decode_header(value: &str) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    let mut after_word = false;
+    while let Some(start) = rest.find("=?") {
+        let (before, candidate) = rest.split_at(start);
+        if let Some((decoded, consumed)) = decode_encoded_word(candidate) {
+            if !(after_word && before.trim().is_empty()) {
+                out.push_str(before);
+            }
+            out.push_str(&decoded);
+            rest = &candidate[consumed..];
+            after_word = true;
+        } else {
+            out.push_str(before);
+            out.push_str("=?");
+            rest = &candidate[2..];
+            after_word = false;
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The decoded text of the encoded word at the start of `word` and the
+/// number of bytes it spans.
+fn decode_encoded_word(word: &str) -> Option<(String, usize)> {
+    let inner = word.strip_prefix("=?")?;
+    let (charset, inner) = inner.split_once('?')?;
+    let (encoding, inner) = inner.split_once('?')?;
+    let end = inner.find("?=")?;
+    let text = &inner[..end];
+    let bytes = match encoding {
+        "B" | "b" => STANDARD.decode(text).ok()?,
+        "Q" | "q" => decode_quoted_printable(text.replace('_', " ").as_bytes()),
+        _ => return None,
+    };
+    let consumed = 2 + charset.len() + 1 + encoding.len() + 1 + end + 2;
+    let charset = charset.split('*').next().unwrap_or(charset);
+    Some((decode_charset(&bytes, charset), consumed))
+}
+
+fn decode_transfer(body: &[u8], encoding: &str) -> Vec<u8> {
+    match encoding.trim().to_ascii_lowercase().as_str() {
+        "base64" => {
+            let compact: Vec<u8> = body
+                .iter()
+                .copied()
+                .filter(|b| !b.is_ascii_whitespace())
+                .collect();
+            STANDARD.decode(&compact).unwrap_or_else(|_| body.to_vec())
+        }
+        "quoted-printable" => decode_quoted_printable(body),
+        _ => body.to_vec(),
+    }
+}
+
+fn decode_quoted_printable(input: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len());
+    let mut idx = 0;
+    while idx < input.len() {
+        if input[idx] != b'=' {
+            out.push(input[idx]);
+            idx += 1;
+            continue;
+        }
+        let rest = &input[idx + 1..];
+        if rest.starts_with(b"\r\n") {
+            idx += 3;
+        } else if rest.starts_with(b"\n") {
+            idx += 2;
+        } else if let (Some(high), Some(low)) = (
+            rest.first().copied().and_then(hex_value),
+            rest.get(1).copied().and_then(hex_value),
+        ) {
+            out.push(high << 4 | low);
+            idx += 3;
+        } else {
+            out.push(b'=');
+            idx += 1;
+        }
+    }
+    out
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    char::from(byte)
+        .to_digit(16)
+        .and_then(|digit| u8::try_from(digit).ok())
+}
+
+/// Text in `charset`. Latin-1 and Windows-1252 map byte for byte (the
+/// Windows-1252 punctuation in `0x80..0xA0` is not remapped); anything else
+/// is read as UTF-8.
+fn decode_charset(bytes: &[u8], charset: &str) -> String {
+    match charset.trim().to_ascii_lowercase().as_str() {
+        "iso-8859-1" | "iso8859-1" | "latin1" | "latin-1" | "windows-1252" | "cp1252" => {
+            bytes.iter().copied().map(char::from).collect()
+        }
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+fn html_text(html: &str) -> String {
+    let hint = ReaderHint::new(Some("text/html"), Some(DocumentFormat::Html));
+    HtmlReader
+        .extract(html.as_bytes(), &hint)
+        .ok()
+        .and_then(|output| output.document.text)
+        .unwrap_or_default()
+}
+
+/// Message ids in a `Message-ID`, `In-Reply-To` or `References` value,
+/// without angle brackets.
+fn message_ids(value: &str) -> Vec<String> {
+    let bracketed: Vec<String> = value
+        .split('<')
+        .skip(1)
+        .filter_map(|piece| piece.split_once('>'))
+        .map(|(id, _)| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect();
+    if !bracketed.is_empty() {
+        return bracketed;
+    }
+    value.split_whitespace().map(str::to_string).collect()
+}
+
+fn parse_date(value: &str) -> Option<i64> {
+    // Trailing comments such as `(UTC)` are not part of RFC 2822 dates.
+    let value = value.split('(').next().unwrap_or(value).trim();
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|date| date.timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MULTIPART: &str = "From: =?UTF-8?B?SsO2cmc=?= <jorg@example.com>\r\n\
+To: support@example.com\r\n\
+Subject: =?ISO-8859-1?Q?Caf=E9?=\r\n\
+\x20menu\r\n\
+Date: Tue, 3 Mar 2026 10:15:00 +0100 (CET)\r\n\
+Message-ID: <b@example.com>\r\n\
+In-Reply-To: <a@example.com>\r\n\
+References: <root@example.com> <a@example.com>\r\n\
+MIME-Version: 1.0\r\n\
+Content-Type: multipart/mixed; boundary=\"outer\"\r\n\
+\r\n\
+preamble\r\n\
+--outer\r\n\
+Content-Type: multipart/alternative; boundary=inner\r\n\
+\r\n\
+--inner\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+Content-Transfer-Encoding: quoted-printable\r\n\
+\r\n\
+The caf=C3=A9 opens at nine, soft=\r\n\
+\x20wrapped.\r\n\
+--inner\r\n\
+Content-Type: text/html\r\n\
+\r\n\
+<p>The caf&eacute; opens at nine</p>\r\n\
+--inner--\r\n\
+--outer\r\n\
+Content-Type: application/pdf; name=\"menu.pdf\"\r\n\
+Content-Disposition: attachment; filename*=UTF-8''men%C3%BC.pdf\r\n\
+Content-Transfer-Encoding: base64\r\n\
+\r\n\
+JVBERi0x\r\n\
+LjQ=\r\n\
+--outer--\r\n";
+
+    #[test]
+    fn parses_headers_alternatives_and_attachments() {
+        let message = EmailMessage::parse(MULTIPART.as_bytes()).expect("parse");
+        assert_eq!(message.from.as_deref(), Some("Jörg <jorg@example.com>"));
+        assert_eq!(message.subject.as_deref(), Some("Café menu"));
+        assert_eq!(message.message_id.as_deref(), Some("b@example.com"));
+        assert_eq!(
+            message.reply_targets().collect::<Vec<_>>(),
+            ["a@example.com", "a@example.com", "root@example.com"]
+        );
+        assert_eq!(message.timestamp, Some(1_772_529_300));
+        assert_eq!(message.body, "The café opens at nine, soft wrapped.");
+
+        assert_eq!(message.attachments.len(), 1);
+        let attachment = &message.attachments[0];
+        assert_eq!(attachment.filename.as_deref(), Some("menü.pdf"));
+        assert_eq!(attachment.content_type, "application/pdf");
+        assert_eq!(attachment.data, b"%PDF-1.4");
+    }
+
+    #[test]
+    fn splits_mbox_and_unquotes_from_lines() {
+        let mbox = b"From alice@example.com Mon Mar  2 09:00:00 2026\n\
+From: alice@example.com\n\
+Subject: first\n\
+\n\
+>From the top.\n\
+\n\
+From bob@example.com Mon Mar  2 10:00:00 2026\n\
+From: bob@example.com\n\
+Content-Type: text/html; charset=iso-8859-1\n\
+\n\
+<html><body><p>Gr\xfc\xdfe</p></body></html>\n";
+        let messages = EmailMessage::parse_mailbox(mbox).expect("mailbox");
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].body, "From the top.");
+        assert_eq!(messages[1].body, "Grüße");
+        assert!(EmailMessage::parse(b"not an email").is_err());
+    }
+}
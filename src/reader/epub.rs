@@ -0,0 +1,505 @@
+//! EPUB 2/3 publications: the package document's spine gives the reading
+//! order, the EPUB 3 navigation document (or the EPUB 2 NCX) gives chapter
+//! titles, and each chapter's XHTML goes through the HTML renderer.
+
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Seek};
+
+use quick_xml::Reader as XmlReader;
+use quick_xml::events::{BytesStart, Event};
+use serde_json::{Map, Value, json};
+use zip::ZipArchive;
+
+use super::html::render_body;
+use crate::{
+    DocumentFormat, DocumentReader, ExtractedDocument, MemvidError, PassthroughReader,
+    ReaderDiagnostics, ReaderHint, ReaderOutput, Result,
+};
+
+const EPUB_MIME: &str = "application/epub+zip";
+const CONTAINER_PATH: &str = "META-INF/container.xml";
+
+pub struct EpubReader;
+
+/// The parts of the OPF package document the reader uses.
+#[derive(Default)]
+struct Package {
+    title: Option<String>,
+    creator: Option<String>,
+    language: Option<String>,
+    /// Manifest items by id.
+    items: HashMap<String, ManifestItem>,
+    /// Manifest ids in reading order.
+    spine: Vec<String>,
+    /// Manifest id of the EPUB 2 NCX.
+    ncx: Option<String>,
+}
+
+struct ManifestItem {
+    href: String,
+    media_type: String,
+    properties: String,
+}
+
+/// A table-of-contents entry: the chapter path inside the archive (without
+/// fragment) and its title.
+struct TocEntry {
+    path: String,
+    title: String,
+}
+
+impl EpubReader {
+    fn extract_document(bytes: &[u8]) -> Result<ExtractedDocument> {
+        let mut archive =
+            ZipArchive::new(Cursor::new(bytes)).map_err(|err| MemvidError::ExtractionFailed {
+                reason: format!("failed to open epub archive: {err}").into(),
+            })?;
+
+        let container = read_entry(&mut archive, CONTAINER_PATH)?;
+        let opf_path = first_attr(&container, b"rootfile", b"full-path").ok_or_else(|| {
+            MemvidError::ExtractionFailed {
+                reason: "epub container.xml names no rootfile".into(),
+            }
+        })?;
+        let package = parse_package(&read_entry(&mut archive, &opf_path)?);
+        let toc = Self::table_of_contents(&mut archive, &package, &opf_path);
+
+        let mut chapters = Vec::new();
+        for idref in &package.spine {
+            let Some(item) = package.items.get(idref) else {
+                continue;
+            };
+            if !item.media_type.contains("html") {
+                continue;
+            }
+            let path = resolve_href(&opf_path, &item.href);
+            let Ok(xhtml) = read_entry(&mut archive, &path) else {
+                continue;
+            };
+            let mut text = render_body(&xhtml);
+            if let Some(entry) = toc.iter().find(|entry| entry.path == path) {
+                let first_line = text.lines().next().unwrap_or_default();
+                if !first_line.contains(&entry.title) {
+                    text = format!("# {}\n\n{text}", entry.title);
+                }
+            }
+            if !text.trim().is_empty() {
+                chapters.push(text);
+            }
+        }
+
+        let mut metadata = Map::new();
+        for (key, value) in [
+            ("title", &package.title),
+            ("author", &package.creator),
+            ("lang", &package.language),
+        ] {
+            if let Some(value) = value {
+                metadata.insert(key.to_string(), Value::String(value.clone()));
+            }
+        }
+        metadata.insert("chapters".to_string(), json!(chapters.len()));
+        metadata.insert(
+            "toc".to_string(),
+            json!(toc.iter().map(|entry| &entry.title).collect::<Vec<_>>()),
+        );
+
+        let mut document = ExtractedDocument::empty();
+        document.text = Some(chapters.join("\n\n"));
+        document.metadata = Value::Object(metadata);
+        document.mime_type = Some(EPUB_MIME.to_string());
+        Ok(document)
+    }
+
+    /// Entries of the EPUB 3 navigation document's `toc` nav, falling back
+    /// to the EPUB 2 NCX.
+    fn table_of_contents<R: Read + Seek>(
+        archive: &mut ZipArchive<R>,
+        package: &Package,
+        opf_path: &str,
+    ) -> Vec<TocEntry> {
+        let nav = package.items.values().find(|item| {
+            item.properties
+                .split_whitespace()
+                .any(|property| property == "nav")
+        });
+        if let Some(nav) = nav {
+            let path = resolve_href(opf_path, &nav.href);
+            if let Ok(xhtml) = read_entry(archive, &path) {
+                let entries = parse_nav(&xhtml, &path);
+                if !entries.is_empty() {
+                    return entries;
+                }
+            }
+        }
+        let ncx = package
+            .ncx
+            .as_ref()
+            .and_then(|id| package.items.get(id))
+            .or_else(|| {
+                package
+                    .items
+                    .values()
+                    .find(|item| item.media_type == "application/x-dtbncx+xml")
+            });
+        ncx.map(|ncx| resolve_href(opf_path, &ncx.href))
+            .and_then(|path| {
+                read_entry(archive, &path)
+                    .ok()
+                    .map(|xml| parse_ncx(&xml, &path))
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl DocumentReader for EpubReader {
+    fn name(&self) -> &'static str {
+        "epub"
+    }
+
+    fn supports(&self, hint: &ReaderHint<'_>) -> bool {
+        matches!(hint.format, Some(DocumentFormat::Epub))
+            || hint
+                .mime
+                .is_some_and(|mime| mime.eq_ignore_ascii_case(EPUB_MIME))
+    }
+
+    fn extract(&self, bytes: &[u8], hint: &ReaderHint<'_>) -> Result<ReaderOutput> {
+        match Self::extract_document(bytes) {
+            Ok(document) if document.text.as_ref().is_some_and(|text| !text.is_empty()) => {
+                Ok(ReaderOutput::new(document, self.name())
+                    .with_diagnostics(ReaderDiagnostics::default()))
+            }
+            outcome => {
+                let mut fallback = PassthroughReader.extract(bytes, hint)?;
+                fallback.reader_name = self.name().to_string();
+                fallback.diagnostics.mark_fallback();
+                fallback.diagnostics.record_warning(match outcome {
+                    Err(err) => format!("epub reader error: {err}"),
+                    Ok(_) => "epub reader produced empty text; falling back to default extractor"
+                        .to_string(),
+                });
+                Ok(fallback)
+            }
+        }
+    }
+}
+
+fn read_entry<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Result<String> {
+    let mut file = archive
+        .by_name(name)
+        .map_err(|err| MemvidError::ExtractionFailed {
+            reason: format!("epub missing {name}: {err}").into(),
+        })?;
+    let mut xml = String::new();
+    file.read_to_string(&mut xml)
+        .map_err(|err| MemvidError::ExtractionFailed {
+            reason: format!("failed to read {name}: {err}").into(),
+        })?;
+    Ok(xml)
+}
+
+fn attr(element: &BytesStart<'_>, name: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|attr| attr.key.local_name().as_ref() == name)
+        .and_then(|attr| attr.unescape_value().ok())
+        .map(std::borrow::Cow::into_owned)
+}
+
+/// `attribute` of the first `element` in `xml`.
+fn first_attr(xml: &str, element: &[u8], attribute: &[u8]) -> Option<String> {
+    let mut reader = XmlReader::from_str(xml);
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e) | Event::Empty(e)) if e.local_name().as_ref() == element => {
+                return attr(&e, attribute);
+            }
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+fn parse_package(xml: &str) -> Package {
+    let mut reader = XmlReader::from_str(xml);
+    reader.trim_text(true);
+    let mut buf = Vec::new();
+    let mut package = Package::default();
+    let mut field: Option<Vec<u8>> = None;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e) | Event::Empty(e)) => match e.local_name().as_ref() {
+                name @ (b"title" | b"creator" | b"language") => field = Some(name.to_vec()),
+                b"item" => {
+                    if let (Some(id), Some(href)) = (attr(&e, b"id"), attr(&e, b"href")) {
+                        package.items.insert(
+                            id,
+                            ManifestItem {
+                                href,
+                                media_type: attr(&e, b"media-type").unwrap_or_default(),
+                                properties: attr(&e, b"properties").unwrap_or_default(),
+                            },
+                        );
+                    }
+                }
+                b"itemref" => package.spine.extend(attr(&e, b"idref")),
+                b"spine" => package.ncx = attr(&e, b"toc"),
+                _ => {}
+            },
+            Ok(Event::Text(t)) => {
+                if let (Some(name), Ok(text)) = (field.take(), t.unescape()) {
+                    let slot = match name.as_slice() {
+                        b"title" => &mut package.title,
+                        b"creator" => &mut package.creator,
+                        _ => &mut package.language,
+                    };
+                    if slot.is_none() && !text.trim().is_empty() {
+                        *slot = Some(text.trim().to_string());
+                    }
+                }
+            }
+            Ok(Event::End(_)) => field = None,
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    package
+}
+
+/// Links of the `toc` nav in an EPUB 3 navigation document, or of its first
+/// nav when none is typed.
+fn parse_nav(xhtml: &str, nav_path: &str) -> Vec<TocEntry> {
+    let mut reader = XmlReader::from_str(xhtml);
+    let mut buf = Vec::new();
+    let mut typed: Vec<TocEntry> = Vec::new();
+    let mut untyped: Vec<TocEntry> = Vec::new();
+    // Whether the nav being read is typed `toc`, when inside one.
+    let mut nav: Option<bool> = None;
+    let mut link: Option<(String, String)> = None;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"nav" => {
+                    let kind = attr(&e, b"type").unwrap_or_default();
+                    nav = Some(kind.split_whitespace().any(|kind| kind == "toc"));
+                }
+                b"a" if nav.is_some() => {
+                    link = attr(&e, b"href").map(|href| (href, String::new()));
+                }
+                _ => {}
+            },
+            Ok(Event::Text(t)) => {
+                if let (Some((_, title)), Ok(text)) = (link.as_mut(), t.unescape()) {
+                    title.push_str(&text);
+                }
+            }
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"a" => {
+                    if let (Some((href, title)), Some(is_toc)) = (link.take(), nav) {
+                        let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+                        if !title.is_empty() {
+                            let entry = TocEntry {
+                                path: resolve_href(nav_path, &href),
+                                title,
+                            };
+                            if is_toc { &mut typed } else { &mut untyped }.push(entry);
+                        }
+                    }
+                }
+                b"nav" => {
+                    if nav == Some(true) {
+                        break;
+                    }
+                    nav = None;
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    if typed.is_empty() { untyped } else { typed }
+}
+
+/// `navPoint` labels and targets of an EPUB 2 NCX, in document order.
+fn parse_ncx(xml: &str, ncx_path: &str) -> Vec<TocEntry> {
+    let mut reader = XmlReader::from_str(xml);
+    reader.trim_text(true);
+    let mut buf = Vec::new();
+    let mut entries = Vec::new();
+    let mut in_label = false;
+    let mut label = String::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e) | Event::Empty(e)) => match e.local_name().as_ref() {
+                b"navLabel" => {
+                    in_label = true;
+                    label.clear();
+                }
+                b"content" => {
+                    if let Some(src) = attr(&e, b"src") {
+                        let title = label.trim().to_string();
+                        if !title.is_empty() {
+                            entries.push(TocEntry {
+                                path: resolve_href(ncx_path, &src),
+                                title,
+                            });
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Text(t)) if in_label => {
+                if let Ok(text) = t.unescape() {
+                    label.push_str(&text);
+                }
+            }
+            Ok(Event::End(e)) if e.local_name().as_ref() == b"navLabel" => in_label = false,
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    entries
+}
+
+/// Archive path of `href` relative to the document at `base`, with the
+/// fragment removed, percent-escapes decoded and `.`/`..` segments resolved.
+fn resolve_href(base: &str, href: &str) -> String {
+    let href = href.split('#').next().unwrap_or(href);
+    let href = percent_decode(href);
+    let mut segments: Vec<&str> = base.split('/').collect();
+    segments.pop();
+    for segment in href.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] == b'%' {
+            if let Some(byte) = value
+                .get(idx + 1..idx + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                out.push(byte);
+                idx += 3;
+                continue;
+            }
+        }
+        out.push(bytes[idx]);
+        idx += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use zip::write::SimpleFileOptions;
+
+    use super::*;
+
+    fn epub(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in files {
+            zip.start_file(*name, SimpleFileOptions::default())
+                .expect("start file");
+            zip.write_all(content.as_bytes()).expect("write file");
+        }
+        zip.finish().expect("finish").into_inner()
+    }
+
+    const CONTAINER: &str = r#"<?xml version="1.0"?>
+<container xmlns="urn:oasis:names:tc:opendocument:xmlns:container" version="1.0">
+  <rootfiles><rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/></rootfiles>
+</container>"#;
+
+    #[test]
+    fn follows_spine_and_titles_chapters_from_nav() {
+        let opf = r#"<?xml version="1.0"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:title>Field Notes</dc:title><dc:creator>R. Ames</dc:creator><dc:language>en</dc:language>
+  </metadata>
+  <manifest>
+    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+    <item id="c2" href="text/two.xhtml" media-type="application/xhtml+xml"/>
+    <item id="c1" href="text/one%20a.xhtml" media-type="application/xhtml+xml"/>
+  </manifest>
+  <spine><itemref idref="c1"/><itemref idref="c2"/></spine>
+</package>"#;
+        let nav = r#"<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops"><body>
+<nav epub:type="landmarks"><ol><li><a href="text/two.xhtml">Skip</a></li></ol></nav>
+<nav epub:type="toc"><ol>
+  <li><a href="text/one%20a.xhtml#start">Arrival</a></li>
+  <li><a href="text/two.xhtml">The Ridge</a></li>
+</ol></nav></body></html>"#;
+        let one = "<html><head><title>x</title></head><body><p>We reached camp at dusk.</p></body></html>";
+        let two = "<html><body><h1>The Ridge</h1><p>Wind all night.</p></body></html>";
+        let bytes = epub(&[
+            ("mimetype", "application/epub+zip"),
+            (CONTAINER_PATH, CONTAINER),
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/nav.xhtml", nav),
+            ("OEBPS/text/one a.xhtml", one),
+            ("OEBPS/text/two.xhtml", two),
+        ]);
+
+        let document = EpubReader::extract_document(&bytes).expect("epub");
+        assert_eq!(
+            document.text.as_deref(),
+            Some("# Arrival\n\nWe reached camp at dusk.\n\n# The Ridge\n\nWind all night.")
+        );
+        assert_eq!(document.metadata["title"], "Field Notes");
+        assert_eq!(document.metadata["author"], "R. Ames");
+        assert_eq!(document.metadata["toc"], json!(["Arrival", "The Ridge"]));
+    }
+
+    #[test]
+    fn falls_back_to_ncx_titles() {
+        let opf = r#"<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+  <metadata/>
+  <manifest>
+    <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
+    <item id="c1" href="ch1.html" media-type="application/xhtml+xml"/>
+  </manifest>
+  <spine toc="ncx"><itemref idref="c1"/></spine>
+</package>"#;
+        let ncx = r#"<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/"><navMap>
+  <navPoint id="p1" playOrder="1"><navLabel><text>Prologue</text></navLabel><content src="ch1.html"/></navPoint>
+</navMap></ncx>"#;
+        let bytes = epub(&[
+            (CONTAINER_PATH, CONTAINER),
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/toc.ncx", ncx),
+            (
+                "OEBPS/ch1.html",
+                "<html><body><p>It began.</p></body></html>",
+            ),
+        ]);
+
+        let document = EpubReader::extract_document(&bytes).expect("epub");
+        assert_eq!(document.text.as_deref(), Some("# Prologue\n\nIt began."));
+    }
+}
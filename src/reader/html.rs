@@ -0,0 +1,1133 @@
+//! HTML reader: drops scripts, styles and page chrome, picks the main content
+//! with a readability-style score, and renders headings, tables, code blocks
+//! and lists in the markdown form `detect_structure` recognises.
+
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+
+use crate::types::{ContentLanguage, LANGUAGE_METADATA_KEY};
+use crate::{
+    DocumentFormat, DocumentReader, ExtractedDocument, ReaderDiagnostics, ReaderHint, ReaderOutput,
+    Result,
+};
+
+/// Frame `extra_metadata` key holding the page's canonical URL.
+pub const CANONICAL_URL_METADATA_KEY: &str = "memvid.canonical_url";
+/// Frame `extra_metadata` key holding the page's meta description.
+pub const DESCRIPTION_METADATA_KEY: &str = "memvid.description";
+
+const HTML_MIME: &str = "text/html";
+
+/// Elements whose content is never text.
+const RAW_TEXT_TAGS: &[&str] = &["script", "style", "template", "textarea", "noscript"];
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+/// Elements dropped with their subtree before scoring.
+const CHROME_TAGS: &[&str] = &[
+    "nav", "aside", "footer", "form", "button", "select", "iframe", "svg", "canvas", "dialog",
+    "menu",
+];
+const BOILERPLATE_ROLES: &[&str] = &[
+    "navigation",
+    "banner",
+    "contentinfo",
+    "complementary",
+    "search",
+    "dialog",
+    "alert",
+];
+/// `class`/`id` tokens that mark page chrome rather than content.
+const BOILERPLATE_TOKENS: &[&str] = &[
+    "ad",
+    "ads",
+    "advert",
+    "advertisement",
+    "banner",
+    "breadcrumb",
+    "breadcrumbs",
+    "comment",
+    "comments",
+    "cookie",
+    "cookies",
+    "footer",
+    "masthead",
+    "menu",
+    "modal",
+    "nav",
+    "navbar",
+    "navigation",
+    "newsletter",
+    "popup",
+    "promo",
+    "related",
+    "share",
+    "sidebar",
+    "social",
+    "sponsor",
+    "subscribe",
+    "toolbar",
+];
+/// `class`/`id` tokens that keep an element even when it also looks like chrome.
+const CONTENT_TOKENS: &[&str] = &[
+    "article", "body", "content", "entry", "main", "post", "story",
+];
+const BLOCK_TAGS: &[&str] = &[
+    "address",
+    "article",
+    "blockquote",
+    "body",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "html",
+    "li",
+    "main",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "ul",
+];
+/// Paragraphs shorter than this do not count towards a container's score.
+const MIN_SCORED_PARAGRAPH_CHARS: usize = 25;
+
+/// Reader for `text/html` and `.html` payloads.
+pub struct HtmlReader;
+
+impl HtmlReader {
+    /// Main-content text and page metadata for `html`.
+    fn extract_document(html: &str) -> ExtractedDocument {
+        let mut root = parse(html);
+        let meta = PageMetadata::collect(&root);
+        prune(&mut root, false);
+        let content = main_content(&root).unwrap_or(&root);
+        let mut renderer = Renderer::default();
+        renderer.render_block(content);
+        let text = renderer.finish();
+
+        let mut document = ExtractedDocument::empty();
+        document.text = (!text.is_empty()).then_some(text);
+        document.metadata = meta.to_json();
+        document.mime_type = Some(HTML_MIME.to_string());
+        document
+    }
+}
+
+impl DocumentReader for HtmlReader {
+    fn name(&self) -> &'static str {
+        "html"
+    }
+
+    fn supports(&self, hint: &ReaderHint<'_>) -> bool {
+        matches!(hint.format, Some(DocumentFormat::Html))
+            || hint.mime.is_some_and(|mime| {
+                mime.eq_ignore_ascii_case(HTML_MIME)
+                    || mime.eq_ignore_ascii_case("application/xhtml+xml")
+            })
+    }
+
+    fn extract(&self, bytes: &[u8], _hint: &ReaderHint<'_>) -> Result<ReaderOutput> {
+        let html = String::from_utf8_lossy(bytes);
+        let mut diagnostics = ReaderDiagnostics::default();
+        let document = Self::extract_document(&html);
+        if document.text.is_none() {
+            diagnostics.record_warning("html reader found no main content");
+        }
+        Ok(ReaderOutput::new(document, self.name()).with_diagnostics(diagnostics))
+    }
+}
+
+/// Copy the page metadata an [`HtmlReader`] recorded into frame fields: the
+/// title when none was given, the language when it is one the search schema
+/// analyses, and the canonical URL and description as extra metadata. The
+/// EPUB and OpenDocument readers record their `title` and `lang` the same way.
+pub(crate) fn apply_page_metadata(
+    metadata: &Value,
+    title: &mut Option<String>,
+    extra_metadata: &mut BTreeMap<String, String>,
+) {
+    let field = |key: &str| metadata.get(key).and_then(Value::as_str);
+    if title.is_none() {
+        *title = field("title").map(str::to_string);
+    }
+    if let Some(language) = field("lang")
+        .and_then(|lang| lang.split(['-', '_']).next())
+        .and_then(ContentLanguage::from_code)
+    {
+        extra_metadata
+            .entry(LANGUAGE_METADATA_KEY.to_string())
+            .or_insert_with(|| language.code().to_string());
+    }
+    for (key, target) in [
+        ("canonical_url", CANONICAL_URL_METADATA_KEY),
+        ("description", DESCRIPTION_METADATA_KEY),
+    ] {
+        if let Some(value) = field(key) {
+            extra_metadata
+                .entry(target.to_string())
+                .or_insert_with(|| value.to_string());
+        }
+    }
+}
+
+/// Whether the first bytes of a payload look like an HTML document.
+pub(crate) fn looks_like_html(magic: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(magic);
+    let trimmed = text.trim_start_matches('\u{feff}').trim_start();
+    let prefix: String = trimmed.chars().take(14).collect::<String>();
+    let prefix = prefix.to_ascii_lowercase();
+    prefix.starts_with("<!doctype html") || prefix.starts_with("<html")
+}
+
+/// Markdown for the whole `<body>` of markup that carries no page chrome,
+/// such as an EPUB chapter: nothing is pruned and no main content is picked.
+pub(crate) fn render_body(html: &str) -> String {
+    let root = parse(html);
+    let mut renderer = Renderer::default();
+    renderer.render_block(root.find("body").unwrap_or(&root));
+    renderer.finish()
+}
+
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Node>,
+}
+
+#[derive(Debug)]
+enum Node {
+    Element(Element),
+    Text(String),
+}
+
+impl Element {
+    fn new(name: String, attrs: Vec<(String, String)>) -> Self {
+        Self {
+            name,
+            attrs,
+            children: Vec::new(),
+        }
+    }
+
+    fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|child| match child {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    /// First element named `name` in document order, including `self`.
+    fn find(&self, name: &str) -> Option<&Element> {
+        if self.name == name {
+            return Some(self);
+        }
+        self.elements().find_map(|child| child.find(name))
+    }
+
+    fn find_all<'a>(&'a self, name: &str, out: &mut Vec<&'a Element>) {
+        if self.name == name {
+            out.push(self);
+        }
+        for child in self.elements() {
+            child.find_all(name, out);
+        }
+    }
+
+    fn text_len(&self) -> usize {
+        self.children
+            .iter()
+            .map(|child| match child {
+                Node::Element(element) => element.text_len(),
+                Node::Text(text) => text.trim().chars().count(),
+            })
+            .sum()
+    }
+
+    fn link_text_len(&self) -> usize {
+        if self.name == "a" {
+            return self.text_len();
+        }
+        self.elements().map(Element::link_text_len).sum()
+    }
+
+    fn class_tokens(&self) -> impl Iterator<Item = String> + '_ {
+        ["class", "id"]
+            .into_iter()
+            .filter_map(|key| self.attr(key))
+            .flat_map(|value| value.split(|c: char| !c.is_ascii_alphanumeric()))
+            .filter(|token| !token.is_empty())
+            .map(str::to_ascii_lowercase)
+    }
+
+    /// Page chrome such as navigation, sidebars and cookie banners.
+    fn is_boilerplate(&self, in_article: bool) -> bool {
+        if CHROME_TAGS.contains(&self.name.as_str()) || (self.name == "header" && !in_article) {
+            return true;
+        }
+        if self
+            .attr("role")
+            .is_some_and(|role| BOILERPLATE_ROLES.contains(&role.to_ascii_lowercase().as_str()))
+        {
+            return true;
+        }
+        if matches!(self.name.as_str(), "html" | "body" | "main" | "article") {
+            return false;
+        }
+        let mut chrome = false;
+        for token in self.class_tokens() {
+            if CONTENT_TOKENS.contains(&token.as_str()) {
+                return false;
+            }
+            chrome |= BOILERPLATE_TOKENS.contains(&token.as_str());
+        }
+        chrome || self.attr("hidden").is_some() || self.attr("aria-hidden") == Some("true")
+    }
+}
+
+/// Parse `html` into a tree, tolerating unclosed and stray tags the way
+/// browsers do for the common cases (`<p>`, `<li>`, table cells).
+fn parse(html: &str) -> Element {
+    let mut stack: Vec<Element> = vec![Element::new("#root".to_string(), Vec::new())];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(lt) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+        if lt > 0 {
+            push_text(&mut stack, &rest[..lt]);
+            rest = &rest[lt..];
+        }
+
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.find("-->").map_or("", |end| &after[end + 3..]);
+            continue;
+        }
+        if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("</") {
+            let end = after.find('>').unwrap_or(after.len());
+            let name = tag_name(&after[..end]);
+            close_tag(&mut stack, &name);
+            rest = after.get(end + 1..).unwrap_or("");
+            continue;
+        }
+        if !rest[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            push_text(&mut stack, "<");
+            rest = &rest[1..];
+            continue;
+        }
+
+        let (name, attrs, self_closing, consumed) = parse_start_tag(rest);
+        rest = &rest[consumed..];
+        if RAW_TEXT_TAGS.contains(&name.as_str()) {
+            rest = skip_raw_text(rest, &name);
+            continue;
+        }
+        auto_close(&mut stack, &name);
+        let element = Element::new(name, attrs);
+        if self_closing || VOID_TAGS.contains(&element.name.as_str()) {
+            if let Some(parent) = stack.last_mut() {
+                parent.children.push(Node::Element(element));
+            }
+        } else {
+            stack.push(element);
+        }
+    }
+
+    while stack.len() > 1 {
+        pop_into_parent(&mut stack);
+    }
+    stack.pop().unwrap_or_default()
+}
+
+fn push_text(stack: &mut [Element], raw: &str) {
+    if let Some(parent) = stack.last_mut() {
+        let text = decode_entities(raw);
+        if let Some(Node::Text(previous)) = parent.children.last_mut() {
+            previous.push_str(&text);
+        } else {
+            parent.children.push(Node::Text(text));
+        }
+    }
+}
+
+fn pop_into_parent(stack: &mut Vec<Element>) {
+    if let Some(element) = stack.pop() {
+        if let Some(parent) = stack.last_mut() {
+            parent.children.push(Node::Element(element));
+        }
+    }
+}
+
+fn close_tag(stack: &mut Vec<Element>, name: &str) {
+    if let Some(pos) = stack.iter().rposition(|element| element.name == name) {
+        if pos > 0 {
+            while stack.len() > pos {
+                pop_into_parent(stack);
+            }
+        }
+    }
+}
+
+/// Close an open element that `name` implicitly ends, e.g. a `<li>` before
+/// the next `<li>`, without crossing the list or table that contains it.
+fn auto_close(stack: &mut Vec<Element>, name: &str) {
+    let (closes, boundaries): (&[&str], &[&str]) = match name {
+        "li" => (&["li"], &["ul", "ol", "menu"]),
+        "dt" | "dd" => (&["dt", "dd"], &["dl"]),
+        "tr" => (&["tr", "td", "th"], &["table", "thead", "tbody", "tfoot"]),
+        "td" | "th" => (&["td", "th"], &["tr", "table"]),
+        "thead" | "tbody" | "tfoot" => (&["thead", "tbody", "tfoot", "tr", "td", "th"], &["table"]),
+        "option" => (&["option"], &["select", "datalist"]),
+        _ if BLOCK_TAGS.contains(&name) => (&["p"], &["div", "td", "th", "li", "body"]),
+        _ => return,
+    };
+    let Some(pos) = stack
+        .iter()
+        .rposition(|element| closes.contains(&element.name.as_str()))
+    else {
+        return;
+    };
+    let crosses_boundary = stack[pos + 1..]
+        .iter()
+        .any(|element| boundaries.contains(&element.name.as_str()));
+    if pos > 0 && !crosses_boundary {
+        while stack.len() > pos {
+            pop_into_parent(stack);
+        }
+    }
+}
+
+fn tag_name(raw: &str) -> String {
+    raw.trim_start()
+        .split(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// Parse `<name attr=value ...>` at the start of `input`. Returns the tag
+/// name, attributes, whether it was self-closing, and the bytes consumed.
+fn parse_start_tag(input: &str) -> (String, Vec<(String, String)>, bool, usize) {
+    let bytes = input.as_bytes();
+    let mut pos = 1;
+    while pos < bytes.len()
+        && !bytes[pos].is_ascii_whitespace()
+        && !matches!(bytes[pos], b'>' | b'/')
+    {
+        pos += 1;
+    }
+    let name = input[1..pos].to_ascii_lowercase();
+    let mut attrs = Vec::new();
+    let mut self_closing = false;
+
+    loop {
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        match bytes.get(pos) {
+            None => break,
+            Some(b'>') => {
+                pos += 1;
+                break;
+            }
+            Some(b'/') => {
+                self_closing = bytes.get(pos + 1) == Some(&b'>');
+                pos += 1;
+                continue;
+            }
+            Some(_) => {}
+        }
+        let key_start = pos;
+        while pos < bytes.len()
+            && !bytes[pos].is_ascii_whitespace()
+            && !matches!(bytes[pos], b'=' | b'>' | b'/')
+        {
+            pos += 1;
+        }
+        let key = input[key_start..pos].to_ascii_lowercase();
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        let mut value = String::new();
+        if bytes.get(pos) == Some(&b'=') {
+            pos += 1;
+            while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            if let Some(&quote @ (b'"' | b'\'')) = bytes.get(pos) {
+                let start = pos + 1;
+                let end = input[start..]
+                    .find(char::from(quote))
+                    .map_or(input.len(), |offset| start + offset);
+                value = decode_entities(&input[start..end]);
+                pos = (end + 1).min(input.len());
+            } else {
+                let start = pos;
+                while pos < bytes.len() && !bytes[pos].is_ascii_whitespace() && bytes[pos] != b'>' {
+                    pos += 1;
+                }
+                value = decode_entities(&input[start..pos]);
+            }
+        }
+        if !key.is_empty() {
+            attrs.push((key, value));
+        } else if pos == key_start {
+            pos += 1;
+        }
+    }
+    (name, attrs, self_closing, pos)
+}
+
+/// Skip the body of a raw-text element up to and including its end tag.
+fn skip_raw_text<'a>(rest: &'a str, name: &str) -> &'a str {
+    let closing = format!("</{name}");
+    let lower = rest.to_ascii_lowercase();
+    match lower.find(&closing) {
+        Some(start) => rest[start..]
+            .find('>')
+            .map_or("", |end| &rest[start + end + 1..]),
+        None => "",
+    }
+}
+
+fn decode_entities(raw: &str) -> String {
+    if !raw.contains('&') {
+        return raw.to_string();
+    }
+    let mut out = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| decode_entity(&rest[1..=end]).map(|ch| (ch, end + 2)));
+        if let Some((ch, consumed)) = decoded {
+            out.push(ch);
+            rest = &rest[consumed..];
+        } else {
+            out.push('&');
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(numeric) = entity.strip_prefix('#') {
+        let code = match numeric.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => numeric.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    Some(match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "ndash" => '\u{2013}',
+        "mdash" => '\u{2014}',
+        "hellip" => '\u{2026}',
+        "lsquo" => '\u{2018}',
+        "rsquo" => '\u{2019}',
+        "ldquo" => '\u{201c}',
+        "rdquo" => '\u{201d}',
+        "copy" => '\u{a9}',
+        "reg" => '\u{ae}',
+        "trade" => '\u{2122}',
+        _ => return None,
+    })
+}
+
+/// Metadata read from `<html>` and `<head>`.
+#[derive(Debug, Default)]
+struct PageMetadata {
+    title: Option<String>,
+    canonical_url: Option<String>,
+    lang: Option<String>,
+    description: Option<String>,
+}
+
+impl PageMetadata {
+    fn collect(root: &Element) -> Self {
+        let mut meta = Self {
+            title: root
+                .find("title")
+                .map(|title| collapse_whitespace(&inline_text(title))),
+            lang: root
+                .find("html")
+                .and_then(|html| html.attr("lang").or_else(|| html.attr("xml:lang")))
+                .map(str::to_string),
+            ..Self::default()
+        };
+
+        let mut links = Vec::new();
+        root.find_all("link", &mut links);
+        meta.canonical_url = links
+            .iter()
+            .find(|link| {
+                link.attr("rel").is_some_and(|rel| {
+                    rel.split_whitespace()
+                        .any(|r| r.eq_ignore_ascii_case("canonical"))
+                })
+            })
+            .and_then(|link| link.attr("href"))
+            .map(str::to_string);
+
+        let mut metas = Vec::new();
+        root.find_all("meta", &mut metas);
+        let meta_content = |names: &[&str]| {
+            metas.iter().find_map(|tag| {
+                let key = tag.attr("name").or_else(|| tag.attr("property"))?;
+                names
+                    .iter()
+                    .any(|name| key.eq_ignore_ascii_case(name))
+                    .then(|| tag.attr("content"))
+                    .flatten()
+            })
+        };
+        meta.description = meta_content(&["description"])
+            .or_else(|| meta_content(&["og:description"]))
+            .map(collapse_whitespace);
+        if meta.title.as_deref().is_none_or(str::is_empty) {
+            meta.title = meta_content(&["og:title"]).map(collapse_whitespace);
+        }
+        if meta.canonical_url.is_none() {
+            meta.canonical_url = meta_content(&["og:url"]).map(str::to_string);
+        }
+        if meta.lang.is_none() {
+            meta.lang = meta_content(&["language", "og:locale"]).map(str::to_string);
+        }
+        meta
+    }
+
+    fn to_json(&self) -> Value {
+        let mut map = Map::new();
+        for (key, value) in [
+            ("title", &self.title),
+            ("canonical_url", &self.canonical_url),
+            ("lang", &self.lang),
+            ("description", &self.description),
+        ] {
+            if let Some(value) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+                map.insert(key.to_string(), Value::String(value.to_string()));
+            }
+        }
+        if map.is_empty() {
+            Value::Null
+        } else {
+            Value::Object(map)
+        }
+    }
+}
+
+/// Drop `<head>`, page chrome and hidden elements.
+fn prune(element: &mut Element, in_article: bool) {
+    let in_article = in_article || element.name == "article";
+    element.children.retain(|child| match child {
+        Node::Element(child) => child.name != "head" && !child.is_boilerplate(in_article),
+        Node::Text(_) => true,
+    });
+    for child in &mut element.children {
+        if let Node::Element(child) = child {
+            prune(child, in_article);
+        }
+    }
+}
+
+/// The element holding the page's main content: `<main>` when present, the
+/// longest `<article>` otherwise, and failing both the container whose
+/// paragraphs score highest, discounted by link density.
+fn main_content(root: &Element) -> Option<&Element> {
+    if let Some(main) = find_by(root, &|element| {
+        element.name == "main" || element.attr("role") == Some("main")
+    }) {
+        return Some(main);
+    }
+    let mut articles = Vec::new();
+    root.find_all("article", &mut articles);
+    if let Some(article) = articles
+        .into_iter()
+        .max_by_key(|article| article.text_len())
+    {
+        return Some(article);
+    }
+
+    let mut best: Option<(&Element, f64)> = None;
+    score_containers(root, &mut best);
+    best.map(|(element, _)| element)
+        .or_else(|| root.find("body"))
+}
+
+fn find_by<'a>(element: &'a Element, predicate: &dyn Fn(&Element) -> bool) -> Option<&'a Element> {
+    if predicate(element) {
+        return Some(element);
+    }
+    element
+        .elements()
+        .find_map(|child| find_by(child, predicate))
+}
+
+/// Score each container by the paragraphs directly inside it (and half of
+/// those of its children), keeping the best in `best`. Returns the
+/// container's own score for its parent.
+fn score_containers<'a>(element: &'a Element, best: &mut Option<(&'a Element, f64)>) -> f64 {
+    let mut own = 0.0;
+    let mut from_children = 0.0;
+    for child in element.elements() {
+        if matches!(child.name.as_str(), "p" | "pre" | "td" | "blockquote") {
+            own += paragraph_score(child);
+        }
+        from_children += score_containers(child, best);
+    }
+    if !matches!(element.name.as_str(), "div" | "section" | "td" | "body") {
+        return own;
+    }
+    let total = own + from_children / 2.0;
+    let text_len = element.text_len();
+    #[allow(clippy::cast_precision_loss)]
+    let link_density = if text_len == 0 {
+        0.0
+    } else {
+        element.link_text_len() as f64 / text_len as f64
+    };
+    let score = total * (1.0 - link_density);
+    if score > 0.0 && best.is_none_or(|(_, current)| score > current) {
+        *best = Some((element, score));
+    }
+    own
+}
+
+fn paragraph_score(paragraph: &Element) -> f64 {
+    let text = inline_text(paragraph);
+    let len = text.trim().chars().count();
+    if len < MIN_SCORED_PARAGRAPH_CHARS {
+        return 0.0;
+    }
+    let commas = text.matches([',', '\u{ff0c}', '\u{3001}']).count();
+    #[allow(clippy::cast_precision_loss)]
+    let score = 1.0 + commas as f64 + (len / 100).min(3) as f64;
+    score
+}
+
+/// Text of `element` with whitespace as written, inline code in backticks.
+fn inline_text(element: &Element) -> String {
+    let mut out = String::new();
+    collect_inline(element, &mut out);
+    out
+}
+
+fn collect_inline(element: &Element, out: &mut String) {
+    for child in &element.children {
+        match child {
+            Node::Text(text) => push_flowed(out, text),
+            Node::Element(child) => inline_element(child, out),
+        }
+    }
+}
+
+fn inline_element(element: &Element, out: &mut String) {
+    match element.name.as_str() {
+        "br" | "hr" => out.push('\n'),
+        "img" => {
+            if let Some(alt) = element.attr("alt").filter(|alt| !alt.trim().is_empty()) {
+                out.push(' ');
+                out.push_str(alt.trim());
+                out.push(' ');
+            }
+        }
+        "code" | "kbd" | "samp" => {
+            let code = collapse_whitespace(&inline_text(element));
+            if !code.is_empty() {
+                out.push('`');
+                out.push_str(&code);
+                out.push('`');
+            }
+        }
+        name => {
+            let block = BLOCK_TAGS.contains(&name);
+            if block {
+                out.push(' ');
+            }
+            collect_inline(element, out);
+            if block {
+                out.push(' ');
+            }
+        }
+    }
+}
+
+/// Append source text, folding its line breaks: only `<br>` ends a line.
+fn push_flowed(out: &mut String, text: &str) {
+    out.extend(
+        text.chars()
+            .map(|ch| if ch.is_whitespace() { ' ' } else { ch }),
+    );
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Renders content blocks as markdown: `#` headings, pipe tables, fenced
+/// code and `-`/`1.` lists, separated by blank lines.
+#[derive(Default)]
+struct Renderer {
+    blocks: Vec<String>,
+    inline: String,
+}
+
+impl Renderer {
+    fn finish(mut self) -> String {
+        self.flush_inline();
+        self.blocks.join("\n\n")
+    }
+
+    fn push_block(&mut self, block: String) {
+        self.flush_inline();
+        if !block.trim().is_empty() {
+            self.blocks.push(block);
+        }
+    }
+
+    fn flush_inline(&mut self) {
+        let text = std::mem::take(&mut self.inline);
+        let lines: Vec<String> = text
+            .lines()
+            .map(collapse_whitespace)
+            .filter(|line| !line.is_empty())
+            .collect();
+        if !lines.is_empty() {
+            self.blocks.push(lines.join("\n"));
+        }
+    }
+
+    fn render_block(&mut self, element: &Element) {
+        for child in &element.children {
+            match child {
+                Node::Text(text) => push_flowed(&mut self.inline, text),
+                Node::Element(child) => self.render_element(child),
+            }
+        }
+    }
+
+    fn render_element(&mut self, element: &Element) {
+        match element.name.as_str() {
+            name @ ("h1" | "h2" | "h3" | "h4" | "h5" | "h6") => {
+                let level = usize::from(name.as_bytes()[1] - b'0');
+                let text = collapse_whitespace(&inline_text(element));
+                if !text.is_empty() {
+                    self.push_block(format!("{} {text}", "#".repeat(level)));
+                }
+            }
+            "pre" => self.push_block(render_code_block(element)),
+            "table" => self.render_table(element),
+            "ul" | "ol" | "menu" => {
+                let mut lines = Vec::new();
+                render_list(element, 0, &mut lines);
+                self.push_block(lines.join("\n"));
+            }
+            "br" => self.inline.push('\n'),
+            "hr" => self.flush_inline(),
+            "img" | "code" | "kbd" | "samp" => inline_element(element, &mut self.inline),
+            name if BLOCK_TAGS.contains(&name) => {
+                self.flush_inline();
+                self.render_block(element);
+                self.flush_inline();
+            }
+            _ => {
+                if contains_block(element) {
+                    self.render_block(element);
+                } else {
+                    self.inline.push_str(&inline_text(element));
+                }
+            }
+        }
+    }
+
+    fn render_table(&mut self, table: &Element) {
+        let mut rows = Vec::new();
+        collect_rows(table, &mut rows);
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let nested = rows
+            .iter()
+            .flatten()
+            .any(|cell| cell.find("table").is_some());
+        if width < 2 || nested {
+            // Layout table: render its cells as ordinary blocks.
+            self.flush_inline();
+            for cell in rows.into_iter().flatten() {
+                self.render_block(cell);
+                self.flush_inline();
+            }
+            return;
+        }
+
+        let mut lines = Vec::with_capacity(rows.len() + 1);
+        for (idx, row) in rows.iter().enumerate() {
+            let mut cells: Vec<String> = row
+                .iter()
+                .map(|cell| collapse_whitespace(&inline_text(cell)).replace('|', "\u{a6}"))
+                .collect();
+            cells.resize(width, String::new());
+            lines.push(format!("| {} |", cells.join(" | ")));
+            if idx == 0 {
+                lines.push(format!("|{}", " --- |".repeat(width)));
+            }
+        }
+        if let Some(caption) = table.elements().find(|child| child.name == "caption") {
+            let caption = collapse_whitespace(&inline_text(caption));
+            if !caption.is_empty() {
+                self.push_block(caption);
+            }
+        }
+        self.push_block(lines.join("\n"));
+    }
+}
+
+fn contains_block(element: &Element) -> bool {
+    element
+        .elements()
+        .any(|child| BLOCK_TAGS.contains(&child.name.as_str()) || contains_block(child))
+}
+
+fn collect_rows<'a>(element: &'a Element, rows: &mut Vec<Vec<&'a Element>>) {
+    for child in element.elements() {
+        match child.name.as_str() {
+            "tr" => rows.push(
+                child
+                    .elements()
+                    .filter(|cell| matches!(cell.name.as_str(), "td" | "th"))
+                    .collect(),
+            ),
+            "thead" | "tbody" | "tfoot" => collect_rows(child, rows),
+            _ => {}
+        }
+    }
+    rows.retain(|row| !row.is_empty());
+}
+
+fn render_code_block(pre: &Element) -> String {
+    let code = pre.elements().find(|child| child.name == "code");
+    let language = code
+        .into_iter()
+        .chain(std::iter::once(pre))
+        .filter_map(|element| element.attr("class"))
+        .flat_map(str::split_whitespace)
+        .find_map(|class| {
+            class
+                .strip_prefix("language-")
+                .or_else(|| class.strip_prefix("lang-"))
+        })
+        .unwrap_or_default();
+    let mut body = String::new();
+    raw_text(pre, &mut body);
+    let body = body.trim_matches('\n').trim_end();
+    format!("```{language}\n{body}\n```")
+}
+
+/// Text with whitespace preserved, as inside `<pre>`.
+fn raw_text(element: &Element, out: &mut String) {
+    for child in &element.children {
+        match child {
+            Node::Text(text) => out.push_str(text),
+            Node::Element(child) if child.name == "br" => out.push('\n'),
+            Node::Element(child) => raw_text(child, out),
+        }
+    }
+}
+
+fn render_list(list: &Element, depth: usize, lines: &mut Vec<String>) {
+    let ordered = list.name == "ol";
+    let indent = "  ".repeat(depth);
+    let mut number = 0usize;
+    for item in list.elements() {
+        if matches!(item.name.as_str(), "ul" | "ol") {
+            render_list(item, depth + 1, lines);
+            continue;
+        }
+        if item.name != "li" {
+            continue;
+        }
+        number += 1;
+        let mut text = String::new();
+        let mut nested = Vec::new();
+        for child in &item.children {
+            match child {
+                Node::Element(child) if matches!(child.name.as_str(), "ul" | "ol") => {
+                    nested.push(child);
+                }
+                Node::Element(child) => {
+                    text.push(' ');
+                    inline_element(child, &mut text);
+                }
+                Node::Text(value) => text.push_str(value),
+            }
+        }
+        let text = collapse_whitespace(&text);
+        if !text.is_empty() {
+            let marker = if ordered {
+                format!("{number}.")
+            } else {
+                "-".to_string()
+            };
+            lines.push(format!("{indent}{marker} {text}"));
+        }
+        for child in nested {
+            render_list(child, depth + 1, lines);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structure::{ElementType, detect_structure};
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="de-DE">
+<head>
+  <title>Release notes &amp; changes</title>
+  <link rel="canonical" href="https://example.com/notes">
+  <meta name="description" content="What changed in 2.0">
+  <style>body { color: red; }</style>
+  <script>var tracking = "do not index";</script>
+</head>
+<body>
+  <nav><a href="/">Home</a> <a href="/docs">Docs</a></nav>
+  <div class="cookie-banner">We use cookies</div>
+  <main>
+    <h1>Version 2.0</h1>
+    <p>This release rewrites the storage layer, adds compaction, and fixes
+       several long-standing bugs.<br>See below.</p>
+    <h2>Benchmarks</h2>
+    <table>
+      <tr><th>Case</th><th>Before</th><th>After</th></tr>
+      <tr><td>insert</td><td>12 ms</td><td>4 ms</td>
+      <tr><td>query</td><td>8 ms</td><td>3 ms</td>
+    </table>
+    <pre><code class="language-rust">fn main() {
+    println!("a &lt; b");
+}</code></pre>
+    <ul>
+      <li>Faster <code>open</code>
+        <ol><li>cold start</li><li>warm start</li></ol>
+      <li>Smaller files
+    </ul>
+  </main>
+  <footer>Copyright</footer>
+</body>
+</html>"#;
+
+    #[test]
+    fn extracts_main_content_as_structured_text() {
+        let document = HtmlReader::extract_document(PAGE);
+        let text = document.text.expect("text");
+
+        for boilerplate in ["tracking", "color: red", "Home", "cookies", "Copyright"] {
+            assert!(!text.contains(boilerplate), "{boilerplate:?} in {text}");
+        }
+        assert!(text.starts_with("# Version 2.0\n\n"), "{text}");
+        assert!(text.contains("fixes several long-standing bugs.\nSee below."));
+        assert!(text.contains(
+            "| Case | Before | After |\n| --- | --- | --- |\n| insert | 12 ms | 4 ms |\n| query | 8 ms | 3 ms |"
+        ));
+        assert!(text.contains("```rust\nfn main() {\n    println!(\"a < b\");\n}\n```"));
+        assert!(
+            text.contains("- Faster `open`\n  1. cold start\n  2. warm start\n- Smaller files")
+        );
+
+        let structure = detect_structure(&text);
+        let types: Vec<ElementType> = structure
+            .elements
+            .iter()
+            .map(|element| element.element_type)
+            .collect();
+        assert!(types.contains(&ElementType::Heading));
+        assert!(types.contains(&ElementType::Table));
+        assert!(types.contains(&ElementType::CodeBlock));
+    }
+
+    #[test]
+    fn records_page_metadata() {
+        let document = HtmlReader::extract_document(PAGE);
+        assert_eq!(document.mime_type.as_deref(), Some("text/html"));
+
+        let mut title = None;
+        let mut extra = BTreeMap::new();
+        apply_page_metadata(&document.metadata, &mut title, &mut extra);
+        assert_eq!(title.as_deref(), Some("Release notes & changes"));
+        assert_eq!(
+            extra.get(LANGUAGE_METADATA_KEY).map(String::as_str),
+            Some("de")
+        );
+        assert_eq!(
+            extra.get(CANONICAL_URL_METADATA_KEY).map(String::as_str),
+            Some("https://example.com/notes")
+        );
+        assert_eq!(
+            extra.get(DESCRIPTION_METADATA_KEY).map(String::as_str),
+            Some("What changed in 2.0")
+        );
+    }
+
+    #[test]
+    fn scores_content_container_without_main() {
+        let html = r#"<html><body>
+            <div id="menu"><p>Products, pricing, about us, contact and more links here</p></div>
+            <div class="post-body">
+              <p>The first paragraph of the story, which is long enough to count.</p>
+              <p>A second paragraph, with commas, clauses, and more detail.</p>
+            </div>
+            <div><a href="/a">A long list of links that should not win at all</a></div>
+        </body></html>"#;
+        let text = HtmlReader::extract_document(html).text.expect("text");
+        assert!(text.starts_with("The first paragraph"), "{text}");
+        assert!(!text.contains("pricing"));
+        assert!(!text.contains("links"));
+        assert!(looks_like_html(b"  <!DOCTYPE html>"));
+        assert!(!looks_like_html(b"plain <b>text</b>"));
+    }
+}
@@ -0,0 +1,485 @@
+//! JSONL / NDJSON and chat-export records: field mapping from JSON values to
+//! frame fields, presets for common chat exports, and a reader that renders a
+//! whole log as `author: text` lines when it is ingested as one document.
+
+use std::cmp::Ordering;
+
+use chrono::{DateTime, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::{
+    DocumentFormat, DocumentReader, ExtractedDocument, MemvidError, PassthroughReader,
+    ReaderDiagnostics, ReaderHint, ReaderOutput, Result,
+};
+
+/// Frame `extra_metadata` key holding a record's author.
+pub const AUTHOR_METADATA_KEY: &str = "memvid.author";
+/// Frame `extra_metadata` key holding a record's thread id.
+pub const THREAD_METADATA_KEY: &str = "memvid.thread_id";
+/// Frame `extra_metadata` key holding a record's id in its source export.
+pub const RECORD_ID_METADATA_KEY: &str = "memvid.record_id";
+
+const JSONL_MIME: &str = "application/x-ndjson";
+
+/// Chat exports with a built-in [`RecordMapping`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatExport {
+    /// Slack workspace export: one JSON array of messages per channel and day.
+    Slack,
+    /// `DiscordChatExporter` JSON: a channel object with a `messages` array.
+    Discord,
+    /// `ChatGPT` `conversations.json`: conversations with a `mapping` of message nodes.
+    OpenAi,
+    /// Claude `conversations.json`: conversations with a `chat_messages` array.
+    Anthropic,
+}
+
+/// Where each frame field comes from in a JSON record.
+///
+/// Fields are dot paths (`author.name`, `content.parts`); numeric segments
+/// index arrays, and `a|b` takes the first alternative that is present.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordMapping {
+    /// Path to the records inside each top-level value (an array, or an
+    /// object whose values are records). `None` when every top-level value
+    /// is itself a record.
+    #[serde(default)]
+    pub records_path: Option<String>,
+    /// Frame text. Arrays are joined line by line, using each element's
+    /// `text` field when it is an object.
+    pub text_field: String,
+    /// Unix seconds or milliseconds, as a number or string, or RFC 3339.
+    #[serde(default)]
+    pub timestamp_field: Option<String>,
+    #[serde(default)]
+    pub author_field: Option<String>,
+    /// Records sharing a thread id are grouped under one URI prefix and
+    /// linked to the thread's first record through `parent_id`.
+    #[serde(default)]
+    pub thread_field: Option<String>,
+    #[serde(default)]
+    pub id_field: Option<String>,
+    /// Thread id taken from the enclosing value when `records_path` is set
+    /// and the record has none of its own.
+    #[serde(default)]
+    pub group_id_field: Option<String>,
+    /// Frame title taken from the enclosing value when `records_path` is set.
+    #[serde(default)]
+    pub group_title_field: Option<String>,
+}
+
+impl Default for RecordMapping {
+    fn default() -> Self {
+        Self {
+            records_path: None,
+            text_field: "text|content|message|body".to_string(),
+            timestamp_field: Some("timestamp|ts|created_at|time|date".to_string()),
+            author_field: Some("author|user|sender|role".to_string()),
+            thread_field: Some("thread_id|thread_ts|conversation_id".to_string()),
+            id_field: Some("id|uuid".to_string()),
+            group_id_field: None,
+            group_title_field: None,
+        }
+    }
+}
+
+impl RecordMapping {
+    /// Mapping that only reads `text_field`.
+    #[must_use]
+    pub fn new(text_field: impl Into<String>) -> Self {
+        Self {
+            records_path: None,
+            text_field: text_field.into(),
+            timestamp_field: None,
+            author_field: None,
+            thread_field: None,
+            id_field: None,
+            group_id_field: None,
+            group_title_field: None,
+        }
+    }
+
+    /// Mapping for a known chat export layout.
+    #[must_use]
+    pub fn chat_export(export: ChatExport) -> Self {
+        let field = |path: &str| Some(path.to_string());
+        match export {
+            ChatExport::Slack => Self {
+                timestamp_field: field("ts"),
+                author_field: field("user_profile.real_name|user_name|username|user"),
+                thread_field: field("thread_ts"),
+                id_field: field("client_msg_id|ts"),
+                ..Self::new("text")
+            },
+            ChatExport::Discord => Self {
+                records_path: field("messages"),
+                timestamp_field: field("timestamp"),
+                author_field: field("author.nickname|author.name"),
+                id_field: field("id"),
+                group_id_field: field("channel.id"),
+                group_title_field: field("channel.name"),
+                ..Self::new("content")
+            },
+            ChatExport::OpenAi => Self {
+                records_path: field("mapping"),
+                timestamp_field: field("message.create_time"),
+                author_field: field("message.author.role"),
+                id_field: field("message.id|id"),
+                group_id_field: field("conversation_id|id"),
+                group_title_field: field("title"),
+                ..Self::new("message.content.parts")
+            },
+            ChatExport::Anthropic => Self {
+                records_path: field("chat_messages"),
+                timestamp_field: field("created_at"),
+                author_field: field("sender"),
+                id_field: field("uuid"),
+                group_id_field: field("uuid"),
+                group_title_field: field("name"),
+                ..Self::new("text|content")
+            },
+        }
+    }
+
+    #[must_use]
+    pub fn with_records_path(mut self, path: impl Into<String>) -> Self {
+        self.records_path = Some(path.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_timestamp_field(mut self, path: impl Into<String>) -> Self {
+        self.timestamp_field = Some(path.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_author_field(mut self, path: impl Into<String>) -> Self {
+        self.author_field = Some(path.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_thread_field(mut self, path: impl Into<String>) -> Self {
+        self.thread_field = Some(path.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_id_field(mut self, path: impl Into<String>) -> Self {
+        self.id_field = Some(path.into());
+        self
+    }
+
+    /// Parse `bytes` (JSON Lines, or a single JSON array or object) into
+    /// records, in source order. Records without text are skipped.
+    pub fn parse(&self, bytes: &[u8]) -> Result<Vec<MappedRecord>> {
+        let mut records = Vec::new();
+        for item in parse_values(bytes)? {
+            let Some(path) = self.records_path.as_deref() else {
+                records.extend(self.map_record(&item, None));
+                continue;
+            };
+            let group_id = self
+                .group_id_field
+                .as_deref()
+                .and_then(|field| lookup(&item, field))
+                .and_then(scalar_string);
+            let group_title = self
+                .group_title_field
+                .as_deref()
+                .and_then(|field| lookup(&item, field))
+                .and_then(scalar_string);
+            let group = (group_id.as_deref(), group_title.as_deref());
+            match lookup(&item, path) {
+                Some(Value::Array(items)) => {
+                    records.extend(
+                        items
+                            .iter()
+                            .filter_map(|record| self.map_record(record, Some(group))),
+                    );
+                }
+                Some(Value::Object(nodes)) => {
+                    // Object keys carry no order; replay the nodes by time.
+                    let mut grouped: Vec<MappedRecord> = nodes
+                        .values()
+                        .filter_map(|record| self.map_record(record, Some(group)))
+                        .collect();
+                    grouped.sort_by(|a, b| match (a.timestamp, b.timestamp) {
+                        (Some(a), Some(b)) => a.cmp(&b),
+                        (Some(_), None) => Ordering::Less,
+                        (None, Some(_)) => Ordering::Greater,
+                        (None, None) => Ordering::Equal,
+                    });
+                    records.extend(grouped);
+                }
+                _ => {}
+            }
+        }
+        Ok(records)
+    }
+
+    fn map_record(
+        &self,
+        record: &Value,
+        group: Option<(Option<&str>, Option<&str>)>,
+    ) -> Option<MappedRecord> {
+        let field = |path: &Option<String>| path.as_deref().and_then(|path| lookup(record, path));
+        let text = lookup(record, &self.text_field).and_then(text_value)?;
+        let (group_id, group_title) = group.unwrap_or_default();
+        Some(MappedRecord {
+            text,
+            timestamp: field(&self.timestamp_field).and_then(timestamp_value),
+            author: field(&self.author_field).and_then(scalar_string),
+            thread: field(&self.thread_field)
+                .and_then(scalar_string)
+                .or_else(|| group_id.map(str::to_string)),
+            id: field(&self.id_field).and_then(scalar_string),
+            title: group_title.map(str::to_string),
+        })
+    }
+}
+
+/// One record mapped to frame fields.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MappedRecord {
+    pub text: String,
+    /// Unix seconds.
+    pub timestamp: Option<i64>,
+    pub author: Option<String>,
+    pub thread: Option<String>,
+    pub id: Option<String>,
+    /// Title of the enclosing conversation or channel.
+    pub title: Option<String>,
+}
+
+/// Reader for JSON Lines payloads ingested as a single document: each record
+/// becomes one `author: text` line. Use
+/// [`Memvid::put_records`](crate::Memvid::put_records) for one frame per record.
+pub struct JsonlReader;
+
+impl JsonlReader {
+    fn render(records: &[MappedRecord]) -> String {
+        records
+            .iter()
+            .map(|record| match &record.author {
+                Some(author) => format!("{author}: {}", record.text),
+                None => record.text.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl DocumentReader for JsonlReader {
+    fn name(&self) -> &'static str {
+        "jsonl"
+    }
+
+    fn supports(&self, hint: &ReaderHint<'_>) -> bool {
+        matches!(hint.format, Some(DocumentFormat::Jsonl))
+            || hint.mime.is_some_and(|mime| {
+                [JSONL_MIME, "application/jsonl", "application/jsonlines"]
+                    .iter()
+                    .any(|candidate| mime.eq_ignore_ascii_case(candidate))
+            })
+    }
+
+    fn extract(&self, bytes: &[u8], hint: &ReaderHint<'_>) -> Result<ReaderOutput> {
+        match RecordMapping::default().parse(bytes) {
+            Ok(records) if !records.is_empty() => {
+                let mut document = ExtractedDocument::empty();
+                document.text = Some(Self::render(&records));
+                document.metadata = json!({ "records": records.len() });
+                document.mime_type = Some(JSONL_MIME.to_string());
+                Ok(ReaderOutput::new(document, self.name())
+                    .with_diagnostics(ReaderDiagnostics::default()))
+            }
+            outcome => {
+                let mut fallback = PassthroughReader.extract(bytes, hint)?;
+                fallback.reader_name = self.name().to_string();
+                fallback.diagnostics.mark_fallback();
+                fallback.diagnostics.record_warning(match outcome {
+                    Err(err) => format!("jsonl reader error: {err}"),
+                    Ok(_) => "jsonl reader found no records with text".to_string(),
+                });
+                Ok(fallback)
+            }
+        }
+    }
+}
+
+/// Top-level values: the elements of a JSON array, a single JSON object, or
+/// one value per non-empty line.
+fn parse_values(bytes: &[u8]) -> Result<Vec<Value>> {
+    let text = std::str::from_utf8(bytes).map_err(|err| MemvidError::ExtractionFailed {
+        reason: format!("records are not valid UTF-8: {err}").into(),
+    })?;
+    let text = text.trim_start_matches('\u{feff}').trim();
+    if let Ok(value) = serde_json::from_str::<Value>(text) {
+        return Ok(match value {
+            Value::Array(items) => items,
+            other => vec![other],
+        });
+    }
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str(line).map_err(|err| MemvidError::ExtractionFailed {
+                reason: format!("invalid JSON on line {}: {err}", idx + 1).into(),
+            })
+        })
+        .collect()
+}
+
+/// Resolve a dot path with `|` alternatives against `value`.
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('|').find_map(|alternative| {
+        alternative
+            .trim()
+            .split('.')
+            .try_fold(value, |current, segment| match current {
+                Value::Object(map) => map.get(segment),
+                Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+                _ => None,
+            })
+            .filter(|found| !found.is_null())
+    })
+}
+
+fn scalar_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.trim().to_string()).filter(|text| !text.is_empty()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+fn text_value(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::Array(parts) => parts
+            .iter()
+            .filter_map(|part| match part {
+                Value::Object(_) => part.get("text").and_then(scalar_string),
+                other => scalar_string(other),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Value::Object(_) => value.get("text").and_then(scalar_string)?,
+        other => scalar_string(other)?,
+    };
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Unix seconds from epoch seconds or milliseconds (number or string), or an
+/// RFC 3339 / `YYYY-MM-DD HH:MM:SS` (UTC) string.
+fn timestamp_value(value: &Value) -> Option<i64> {
+    let epoch = match value {
+        Value::Number(number) => number.as_f64()?,
+        Value::String(text) => {
+            let text = text.trim();
+            match text.parse::<f64>() {
+                Ok(number) => number,
+                Err(_) => {
+                    return DateTime::parse_from_rfc3339(text)
+                        .map(|parsed| parsed.timestamp())
+                        .or_else(|_| {
+                            NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S")
+                                .map(|parsed| parsed.and_utc().timestamp())
+                        })
+                        .ok();
+                }
+            }
+        }
+        _ => return None,
+    };
+    if !epoch.is_finite() || epoch < 0.0 {
+        return None;
+    }
+    // Values past the year 5138 in seconds are taken to be milliseconds.
+    let seconds = if epoch >= 1e11 { epoch / 1000.0 } else { epoch };
+    #[allow(clippy::cast_possible_truncation)]
+    Some(seconds.trunc() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_jsonl_records_with_default_fields() {
+        let log =
+            br#"{"id": 1, "author": "ana", "text": "deploy is green", "timestamp": 1700000000}
+{"id": 2, "author": "bo", "content": "rolling back", "ts": "1700000060.25"}
+
+{"id": 3, "author": "cy", "text": ""}
+{"id": 4, "user": "di", "message": "all clear", "created_at": "2023-11-14T22:15:00Z"}"#;
+        let records = RecordMapping::default().parse(log).unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].author.as_deref(), Some("ana"));
+        assert_eq!(records[0].timestamp, Some(1_700_000_000));
+        assert_eq!(records[1].text, "rolling back");
+        assert_eq!(records[1].timestamp, Some(1_700_000_060));
+        assert_eq!(records[2].timestamp, Some(1_700_000_100));
+        assert_eq!(records[2].id.as_deref(), Some("4"));
+
+        let hint = ReaderHint::new(None, Some(DocumentFormat::Jsonl));
+        let output = JsonlReader.extract(log, &hint).unwrap();
+        assert_eq!(
+            output.document.text.as_deref(),
+            Some("ana: deploy is green\nbo: rolling back\ndi: all clear")
+        );
+
+        let err = RecordMapping::default()
+            .parse(b"{\"text\": \"ok\"}\n{broken")
+            .unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn chat_export_presets() {
+        let slack = br#"[
+            {"type": "message", "user": "U1", "user_profile": {"real_name": "Ana"},
+             "text": "release?", "ts": "1700000000.000100", "thread_ts": "1700000000.000100"},
+            {"type": "message", "user": "U2", "text": "tomorrow", "ts": "1700000100.000200",
+             "thread_ts": "1700000000.000100"}
+        ]"#;
+        let records = RecordMapping::chat_export(ChatExport::Slack)
+            .parse(slack)
+            .unwrap();
+        assert_eq!(records[0].author.as_deref(), Some("Ana"));
+        assert_eq!(records[1].author.as_deref(), Some("U2"));
+        assert_eq!(records[1].thread.as_deref(), Some("1700000000.000100"));
+
+        let openai = br#"[{"title": "Trip", "conversation_id": "c1", "mapping": {
+            "b": {"message": {"id": "m2", "author": {"role": "assistant"},
+                  "content": {"parts": ["Try Lisbon."]}, "create_time": 1700000010.5}},
+            "a": {"message": {"id": "m1", "author": {"role": "user"},
+                  "content": {"parts": ["Where to go?"]}, "create_time": 1700000000.0}},
+            "root": {"message": null}
+        }}]"#;
+        let records = RecordMapping::chat_export(ChatExport::OpenAi)
+            .parse(openai)
+            .unwrap();
+        let texts: Vec<&str> = records.iter().map(|r| r.text.as_str()).collect();
+        assert_eq!(texts, ["Where to go?", "Try Lisbon."]);
+        assert_eq!(records[0].thread.as_deref(), Some("c1"));
+        assert_eq!(records[0].title.as_deref(), Some("Trip"));
+
+        let anthropic = br#"[{"uuid": "conv", "name": "Notes", "chat_messages": [
+            {"uuid": "x1", "sender": "human", "created_at": "2024-01-01T10:00:00Z",
+             "content": [{"type": "text", "text": "Summarise this"}]}
+        ]}]"#;
+        let records = RecordMapping::chat_export(ChatExport::Anthropic)
+            .parse(anthropic)
+            .unwrap();
+        assert_eq!(records[0].text, "Summarise this");
+        assert_eq!(records[0].id.as_deref(), Some("x1"));
+        assert_eq!(records[0].thread.as_deref(), Some("conv"));
+    }
+}
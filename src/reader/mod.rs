@@ -1,9 +1,18 @@
 //! Document reader traits and registry for unified format ingestion.
 
+mod code;
 mod docx;
+mod email;
+mod epub;
+mod html;
+mod jsonl;
+mod odf;
+#[cfg(feature = "excel")]
+mod ods;
 mod passthrough;
 mod pdf;
 mod pptx;
+mod subtitle;
 #[cfg(feature = "excel")]
 mod xls;
 #[cfg(feature = "excel")]
@@ -17,10 +26,36 @@ pub(crate) mod xlsx_table_detect;
 
 use serde_json::Value;
 
+pub use code::{
+    CODE_LANGUAGE_METADATA_KEY, CODE_LINES_METADATA_KEY, CODE_SIGNATURE_METADATA_KEY,
+    CODE_SYMBOL_KIND_METADATA_KEY, CODE_SYMBOLS_METADATA_KEY, CodeLanguage, CodeReader,
+    CodeSegment, CodeSymbol, SymbolKind, identifier_terms, split_identifier,
+};
+pub(crate) use code::{apply_code_metadata, segments as code_segments};
 pub use docx::DocxReader;
+pub(crate) use email::apply_message_metadata;
+pub use email::{
+    EMAIL_CC_METADATA_KEY, EMAIL_DATE_METADATA_KEY, EMAIL_FROM_METADATA_KEY,
+    EMAIL_IN_REPLY_TO_METADATA_KEY, EMAIL_MESSAGE_ID_METADATA_KEY, EMAIL_SUBJECT_METADATA_KEY,
+    EMAIL_TO_METADATA_KEY, EmailAttachment, EmailMessage, EmailReader,
+};
+pub use epub::EpubReader;
+pub use html::{CANONICAL_URL_METADATA_KEY, DESCRIPTION_METADATA_KEY, HtmlReader};
+pub(crate) use html::{apply_page_metadata, looks_like_html};
+pub use jsonl::{
+    AUTHOR_METADATA_KEY, ChatExport, JsonlReader, MappedRecord, RECORD_ID_METADATA_KEY,
+    RecordMapping, THREAD_METADATA_KEY,
+};
+pub use odf::{OdpReader, OdtReader};
+#[cfg(feature = "excel")]
+pub use ods::OdsReader;
 pub use passthrough::PassthroughReader;
 pub use pdf::PdfReader;
 pub use pptx::PptxReader;
+pub use subtitle::{
+    SubtitleReader, TranscriptCue, parse_subtitles, render_transcript, write_webvtt,
+};
+pub(crate) use subtitle::{looks_like_subtitles, transcript_audio_metadata};
 #[cfg(feature = "excel")]
 pub use xls::XlsReader;
 #[cfg(feature = "excel")]
@@ -40,10 +75,17 @@ pub enum DocumentFormat {
     Xlsx,
     Xls,
     Pptx,
+    Odt,
+    Ods,
+    Odp,
+    Epub,
     PlainText,
     Markdown,
     Html,
     Jsonl,
+    Email,
+    Code,
+    Subtitles,
     Unknown,
 }
 
@@ -56,10 +98,17 @@ impl DocumentFormat {
             Self::Xlsx => "xlsx",
             Self::Xls => "xls",
             Self::Pptx => "pptx",
+            Self::Odt => "odt",
+            Self::Ods => "ods",
+            Self::Odp => "odp",
+            Self::Epub => "epub",
             Self::PlainText => "text",
             Self::Markdown => "markdown",
             Self::Html => "html",
             Self::Jsonl => "jsonl",
+            Self::Email => "email",
+            Self::Code => "code",
+            Self::Subtitles => "subtitles",
             Self::Unknown => "unknown",
         }
     }
@@ -226,6 +275,16 @@ impl Default for ReaderRegistry {
             registry.register(XlsReader);
         }
         registry.register(PptxReader);
+        registry.register(OdtReader);
+        #[cfg(feature = "excel")]
+        registry.register(OdsReader);
+        registry.register(OdpReader);
+        registry.register(EpubReader);
+        registry.register(HtmlReader);
+        registry.register(JsonlReader);
+        registry.register(EmailReader);
+        registry.register(CodeReader);
+        registry.register(SubtitleReader);
         registry.register(PassthroughReader);
         registry
     }
@@ -0,0 +1,443 @@
+//! OpenDocument text (ODT) and presentation (ODP) readers. Both read
+//! `content.xml` from the package: headings, paragraphs, lists and tables for
+//! text documents, and one block per slide for presentations.
+
+use std::io::{Cursor, Read};
+
+use quick_xml::Reader as XmlReader;
+use quick_xml::events::{BytesStart, Event};
+use serde_json::{Map, Value};
+use zip::ZipArchive;
+
+use crate::{
+    DocumentFormat, DocumentReader, ExtractedDocument, MemvidError, PassthroughReader,
+    ReaderDiagnostics, ReaderHint, ReaderOutput, Result,
+};
+
+const ODT_MIME: &str = "application/vnd.oasis.opendocument.text";
+const ODP_MIME: &str = "application/vnd.oasis.opendocument.presentation";
+const CONTENT_PATH: &str = "content.xml";
+const META_PATH: &str = "meta.xml";
+
+/// Elements whose text is not part of the document body: tracked deletions,
+/// comments, footnote markers and speaker notes.
+const SKIPPED_ELEMENTS: &[&[u8]] = &[
+    b"tracked-changes",
+    b"annotation",
+    b"note-citation",
+    b"notes",
+];
+
+pub struct OdtReader;
+
+pub struct OdpReader;
+
+impl OdtReader {
+    fn extract_document(bytes: &[u8]) -> Result<ExtractedDocument> {
+        read_package(bytes, "odt", ODT_MIME, false)
+    }
+}
+
+impl OdpReader {
+    fn extract_document(bytes: &[u8]) -> Result<ExtractedDocument> {
+        read_package(bytes, "odp", ODP_MIME, true)
+    }
+}
+
+impl DocumentReader for OdtReader {
+    fn name(&self) -> &'static str {
+        "odt"
+    }
+
+    fn supports(&self, hint: &ReaderHint<'_>) -> bool {
+        matches!(hint.format, Some(DocumentFormat::Odt))
+            || hint
+                .mime
+                .is_some_and(|mime| mime.eq_ignore_ascii_case(ODT_MIME))
+    }
+
+    fn extract(&self, bytes: &[u8], hint: &ReaderHint<'_>) -> Result<ReaderOutput> {
+        finish(self.name(), Self::extract_document(bytes), bytes, hint)
+    }
+}
+
+impl DocumentReader for OdpReader {
+    fn name(&self) -> &'static str {
+        "odp"
+    }
+
+    fn supports(&self, hint: &ReaderHint<'_>) -> bool {
+        matches!(hint.format, Some(DocumentFormat::Odp))
+            || hint
+                .mime
+                .is_some_and(|mime| mime.eq_ignore_ascii_case(ODP_MIME))
+    }
+
+    fn extract(&self, bytes: &[u8], hint: &ReaderHint<'_>) -> Result<ReaderOutput> {
+        finish(self.name(), Self::extract_document(bytes), bytes, hint)
+    }
+}
+
+/// Reader output for an extraction attempt, falling back to the default
+/// extractor when it failed or found no text.
+fn finish(
+    name: &'static str,
+    outcome: Result<ExtractedDocument>,
+    bytes: &[u8],
+    hint: &ReaderHint<'_>,
+) -> Result<ReaderOutput> {
+    match outcome {
+        Ok(document) if document.text.as_ref().is_some_and(|text| !text.is_empty()) => {
+            Ok(ReaderOutput::new(document, name).with_diagnostics(ReaderDiagnostics::default()))
+        }
+        outcome => {
+            let mut fallback = PassthroughReader.extract(bytes, hint)?;
+            fallback.reader_name = name.to_string();
+            fallback.diagnostics.mark_fallback();
+            fallback.diagnostics.record_warning(match outcome {
+                Err(err) => format!("{name} reader error: {err}"),
+                Ok(_) => {
+                    format!("{name} reader produced empty text; falling back to default extractor")
+                }
+            });
+            Ok(fallback)
+        }
+    }
+}
+
+fn read_package(bytes: &[u8], kind: &str, mime: &str, slides: bool) -> Result<ExtractedDocument> {
+    let mut archive =
+        ZipArchive::new(Cursor::new(bytes)).map_err(|err| MemvidError::ExtractionFailed {
+            reason: format!("failed to open {kind} archive: {err}").into(),
+        })?;
+    let mut content = String::new();
+    archive
+        .by_name(CONTENT_PATH)
+        .map_err(|err| MemvidError::ExtractionFailed {
+            reason: format!("{kind} missing content.xml: {err}").into(),
+        })?
+        .read_to_string(&mut content)
+        .map_err(|err| MemvidError::ExtractionFailed {
+            reason: format!("failed to read content.xml: {err}").into(),
+        })?;
+    let mut meta = String::new();
+    if let Ok(mut file) = archive.by_name(META_PATH) {
+        // Titles are optional; a missing or unreadable meta.xml is not an error.
+        let _ = file.read_to_string(&mut meta);
+    }
+
+    let mut document = ExtractedDocument::empty();
+    document.text = Some(render_content(&content, slides));
+    document.metadata = parse_meta(&meta);
+    document.mime_type = Some(mime.to_string());
+    Ok(document)
+}
+
+/// `title` and `lang` from `meta.xml`, keyed like the HTML reader's page
+/// metadata.
+fn parse_meta(xml: &str) -> Value {
+    let mut reader = XmlReader::from_str(xml);
+    reader.trim_text(true);
+    let mut buf = Vec::new();
+    let mut map = Map::new();
+    let mut field: Option<&str> = None;
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => {
+                field = match e.local_name().as_ref() {
+                    b"title" => Some("title"),
+                    b"language" => Some("lang"),
+                    _ => None,
+                };
+            }
+            Ok(Event::Text(t)) => {
+                if let (Some(key), Ok(text)) = (field.take(), t.unescape()) {
+                    if !text.trim().is_empty() {
+                        map.entry(key)
+                            .or_insert_with(|| Value::String(text.trim().to_string()));
+                    }
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    Value::Object(map)
+}
+
+/// Markdown-style text for an ODF `content.xml`: `#` headings by outline
+/// level, `-` list items, pipe tables, and for presentations a `Slide N:`
+/// block per page like the PPTX reader.
+fn render_content(xml: &str, slides: bool) -> String {
+    let mut reader = XmlReader::from_str(xml);
+    let mut buf = Vec::new();
+    let mut out = Content::default();
+    let mut skip_depth = 0usize;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => {
+                let name = e.local_name();
+                if skip_depth > 0 || SKIPPED_ELEMENTS.contains(&name.as_ref()) {
+                    skip_depth += 1;
+                } else {
+                    out.start(&e, slides);
+                }
+            }
+            Ok(Event::Empty(e)) => {
+                if skip_depth == 0 {
+                    out.empty(&e);
+                }
+            }
+            Ok(Event::Text(t)) => {
+                if let (0, Some(paragraph), Ok(text)) =
+                    (skip_depth, out.paragraphs.last_mut(), t.unescape())
+                {
+                    paragraph.text.push_str(&text);
+                }
+            }
+            Ok(Event::End(e)) => {
+                if skip_depth > 0 {
+                    skip_depth -= 1;
+                } else {
+                    out.end(e.local_name().as_ref(), slides);
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    out.blocks.join("\n\n")
+}
+
+#[derive(Default)]
+struct Content {
+    blocks: Vec<String>,
+    /// Open paragraphs and headings; text boxes can nest them.
+    paragraphs: Vec<Paragraph>,
+    list_depth: usize,
+    /// Rows of the open tables, innermost last.
+    tables: Vec<Vec<Vec<String>>>,
+    /// Index into `blocks` where the open slide starts.
+    slide_start: Option<usize>,
+    slide_count: usize,
+    /// Whether the last block is a list that further items extend.
+    in_list_block: bool,
+}
+
+struct Paragraph {
+    heading: Option<usize>,
+    text: String,
+}
+
+impl Content {
+    fn start(&mut self, element: &BytesStart<'_>, slides: bool) {
+        match element.local_name().as_ref() {
+            b"p" => self.paragraphs.push(Paragraph {
+                heading: None,
+                text: String::new(),
+            }),
+            b"h" => {
+                let level = attr(element, b"outline-level")
+                    .and_then(|level| level.parse::<usize>().ok())
+                    .unwrap_or(1)
+                    .clamp(1, 6);
+                self.paragraphs.push(Paragraph {
+                    heading: Some(level),
+                    text: String::new(),
+                });
+            }
+            b"list" => self.list_depth += 1,
+            b"table" => self.tables.push(Vec::new()),
+            b"table-row" => {
+                if let Some(rows) = self.tables.last_mut() {
+                    rows.push(Vec::new());
+                }
+            }
+            b"table-cell" | b"covered-table-cell" => self.push_cell(),
+            b"page" if slides => {
+                self.slide_count += 1;
+                self.slide_start = Some(self.blocks.len());
+            }
+            _ => self.empty(element),
+        }
+    }
+
+    /// Empty table cells and the inline elements that stand for whitespace.
+    fn empty(&mut self, element: &BytesStart<'_>) {
+        let name = element.local_name();
+        if matches!(name.as_ref(), b"table-cell" | b"covered-table-cell") {
+            self.push_cell();
+            return;
+        }
+        let Some(paragraph) = self.paragraphs.last_mut() else {
+            return;
+        };
+        match name.as_ref() {
+            b"s" => {
+                let count = attr(element, b"c")
+                    .and_then(|count| count.parse::<usize>().ok())
+                    .unwrap_or(1);
+                paragraph.text.push_str(&" ".repeat(count.min(64)));
+            }
+            b"tab" => paragraph.text.push('\t'),
+            b"line-break" => paragraph.text.push('\n'),
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, name: &[u8], slides: bool) {
+        match name {
+            b"p" | b"h" => {
+                let Some(paragraph) = self.paragraphs.pop() else {
+                    return;
+                };
+                let text = paragraph
+                    .text
+                    .lines()
+                    .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+                    .filter(|line| !line.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                if text.is_empty() {
+                    return;
+                }
+                if let Some(cell) = self
+                    .tables
+                    .last_mut()
+                    .and_then(|rows| rows.last_mut())
+                    .and_then(|row| row.last_mut())
+                {
+                    if !cell.is_empty() {
+                        cell.push(' ');
+                    }
+                    cell.push_str(&text.replace('\n', " "));
+                } else if let Some(level) = paragraph.heading {
+                    self.push_block(format!("{} {text}", "#".repeat(level)));
+                } else if self.list_depth > 0 {
+                    let item = format!("{}- {text}", "  ".repeat(self.list_depth - 1));
+                    match self.blocks.last_mut() {
+                        Some(list) if self.in_list_block => {
+                            list.push('\n');
+                            list.push_str(&item);
+                        }
+                        _ => self.blocks.push(item),
+                    }
+                    self.in_list_block = true;
+                } else {
+                    self.push_block(text);
+                }
+            }
+            b"list" => self.list_depth = self.list_depth.saturating_sub(1),
+            b"table" => {
+                if let Some(rows) = self.tables.pop() {
+                    self.push_table(rows);
+                }
+            }
+            b"page" if slides => {
+                if let Some(start) = self.slide_start.take() {
+                    let lines = self.blocks.split_off(start);
+                    self.push_block(format!("Slide {}:\n{}", self.slide_count, lines.join("\n")));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn push_block(&mut self, block: String) {
+        self.blocks.push(block);
+        self.in_list_block = false;
+    }
+
+    fn push_cell(&mut self) {
+        if let Some(row) = self.tables.last_mut().and_then(|rows| rows.last_mut()) {
+            row.push(String::new());
+        }
+    }
+
+    fn push_table(&mut self, mut rows: Vec<Vec<String>>) {
+        for row in &mut rows {
+            while row.last().is_some_and(String::is_empty) {
+                row.pop();
+            }
+        }
+        rows.retain(|row| !row.is_empty());
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if width == 0 {
+            return;
+        }
+        let mut lines = Vec::with_capacity(rows.len() + 1);
+        for (idx, row) in rows.iter_mut().enumerate() {
+            row.resize(width, String::new());
+            let cells: Vec<String> = row.iter().map(|cell| cell.replace('|', "\u{a6}")).collect();
+            lines.push(format!("| {} |", cells.join(" | ")));
+            if idx == 0 {
+                lines.push(format!("|{}", " --- |".repeat(width)));
+            }
+        }
+        // Tables inside a table cell flatten into that cell.
+        if let Some(cell) = self
+            .tables
+            .last_mut()
+            .and_then(|rows| rows.last_mut())
+            .and_then(|row| row.last_mut())
+        {
+            cell.push_str(&lines.join(" "));
+        } else {
+            self.push_block(lines.join("\n"));
+        }
+    }
+}
+
+fn attr(element: &BytesStart<'_>, name: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|attr| attr.key.local_name().as_ref() == name)
+        .and_then(|attr| attr.unescape_value().ok())
+        .map(std::borrow::Cow::into_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_text_document_structure() {
+        let xml = r#"<office:document-content xmlns:office="o" xmlns:text="t" xmlns:table="tb">
+<office:body><office:text>
+  <text:tracked-changes><text:changed-region><text:p>deleted</text:p></text:changed-region></text:tracked-changes>
+  <text:h text:outline-level="2">Scope</text:h>
+  <text:p>Covers<text:s text:c="2"/>all sites<text:note-citation>1</text:note-citation>.</text:p>
+  <text:list><text:list-item><text:p>North</text:p>
+    <text:list><text:list-item><text:p>Depot</text:p></text:list-item></text:list>
+  </text:list-item></text:list>
+  <table:table>
+    <table:table-row><table:table-cell><text:p>Site</text:p></table:table-cell><table:table-cell><text:p>Staff</text:p></table:table-cell></table:table-row>
+    <table:table-row><table:table-cell><text:p>North</text:p></table:table-cell><table:table-cell><text:p>12</text:p></table:table-cell><table:table-cell/></table:table-row>
+  </table:table>
+</office:text></office:body></office:document-content>"#;
+        assert_eq!(
+            render_content(xml, false),
+            "## Scope\n\nCovers all sites.\n\n- North\n  - Depot\n\n\
+             | Site | Staff |\n| --- | --- |\n| North | 12 |"
+        );
+    }
+
+    #[test]
+    fn renders_one_block_per_slide() {
+        let xml = r#"<office:document-content xmlns:office="o" xmlns:draw="d" xmlns:text="t" xmlns:presentation="p">
+<office:body><office:presentation>
+  <draw:page draw:name="one"><draw:frame><draw:text-box><text:p>Roadmap</text:p><text:p>Q3 goals</text:p></draw:text-box></draw:frame>
+    <presentation:notes><draw:frame><draw:text-box><text:p>say hello</text:p></draw:text-box></draw:frame></presentation:notes>
+  </draw:page>
+  <draw:page draw:name="two"><draw:frame><draw:text-box><text:p>Budget</text:p></draw:text-box></draw:frame></draw:page>
+</office:presentation></office:body></office:document-content>"#;
+        assert_eq!(
+            render_content(xml, true),
+            "Slide 1:\nRoadmap\nQ3 goals\n\nSlide 2:\nBudget"
+        );
+    }
+}
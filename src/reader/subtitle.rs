@@ -0,0 +1,355 @@
+//! Subtitles and transcripts: `SubRip` (`.srt`) and `WebVTT` (`.vtt`) cues.
+//!
+//! A transcript is stored as one line of text per cue, with the cue timings
+//! kept alongside as [`AudioSegmentMetadata`] in the frame's
+//! [`DocAudioMetadata`], so the n-th line of a frame's text was said during
+//! its n-th segment. Whisper transcriptions are stored the same way.
+
+use serde_json::json;
+
+use crate::{
+    DocumentFormat, DocumentReader, ExtractedDocument, MemvidError, PassthroughReader,
+    ReaderDiagnostics, ReaderHint, ReaderOutput, Result,
+    types::{AudioSegmentMetadata, DocAudioMetadata},
+};
+
+const WEBVTT_MIME: &str = "text/vtt";
+const SUBRIP_MIME: &str = "application/x-subrip";
+
+/// One timed cue of a subtitle file or transcription.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptCue {
+    pub start_seconds: f32,
+    pub end_seconds: f32,
+    /// Voice of a `WebVTT` `<v>` span, when the cue names one.
+    pub speaker: Option<String>,
+    /// Cue text on a single line, without markup.
+    pub text: String,
+}
+
+impl TranscriptCue {
+    /// The cue as it appears in a transcript frame's text.
+    #[must_use]
+    pub fn line(&self) -> String {
+        match &self.speaker {
+            Some(speaker) => format!("{speaker}: {}", self.text),
+            None => self.text.clone(),
+        }
+    }
+
+    #[must_use]
+    pub fn segment(&self) -> AudioSegmentMetadata {
+        AudioSegmentMetadata {
+            start_seconds: self.start_seconds,
+            end_seconds: self.end_seconds,
+            label: self.speaker.clone(),
+        }
+    }
+}
+
+/// Parse `SubRip` or `WebVTT` text into cues, in file order. Cues whose timing
+/// cannot be read or whose text is empty are skipped.
+///
+/// # Errors
+///
+/// Returns [`MemvidError::ExtractionFailed`] when no cue could be read.
+pub fn parse_subtitles(text: &str) -> Result<Vec<TranscriptCue>> {
+    let text = text.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let webvtt = is_webvtt(&text);
+    let mut cues = Vec::new();
+    for block in text.split("\n\n") {
+        let mut lines = block.lines().skip_while(|line| !line.contains("-->"));
+        let Some(timing) = lines.next() else {
+            continue;
+        };
+        let Some((start_seconds, end_seconds)) = parse_timing(timing) else {
+            continue;
+        };
+        let mut speaker = None;
+        let mut parts = Vec::new();
+        for line in lines {
+            let (voice, plain) = strip_markup(line, webvtt);
+            speaker = speaker.or(voice);
+            let plain = plain.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !plain.is_empty() {
+                parts.push(plain);
+            }
+        }
+        if parts.is_empty() {
+            continue;
+        }
+        cues.push(TranscriptCue {
+            start_seconds,
+            end_seconds: end_seconds.max(start_seconds),
+            speaker,
+            text: parts.join(" "),
+        });
+    }
+    if cues.is_empty() {
+        return Err(MemvidError::ExtractionFailed {
+            reason: "no subtitle cues found".into(),
+        });
+    }
+    Ok(cues)
+}
+
+/// Transcript text: one [`TranscriptCue::line`] per cue.
+#[must_use]
+pub fn render_transcript(cues: &[TranscriptCue]) -> String {
+    cues.iter()
+        .map(TranscriptCue::line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Serialise cues as a `WebVTT` file.
+#[must_use]
+pub fn write_webvtt(cues: &[TranscriptCue]) -> String {
+    let mut out = String::from("WEBVTT\n");
+    for cue in cues {
+        let text = escape_cue_text(&cue.text);
+        out.push('\n');
+        out.push_str(&format!(
+            "{} --> {}\n",
+            format_timestamp(cue.start_seconds),
+            format_timestamp(cue.end_seconds)
+        ));
+        match &cue.speaker {
+            Some(speaker) => out.push_str(&format!("<v {}>{text}\n", escape_cue_text(speaker))),
+            None => out.push_str(&format!("{text}\n")),
+        }
+    }
+    out
+}
+
+/// Audio metadata for a transcript: its duration and one segment per cue.
+pub(crate) fn transcript_audio_metadata(cues: &[TranscriptCue]) -> DocAudioMetadata {
+    DocAudioMetadata {
+        duration_secs: cues.iter().map(|cue| cue.end_seconds).reduce(f32::max),
+        segments: cues.iter().map(TranscriptCue::segment).collect(),
+        ..DocAudioMetadata::default()
+    }
+}
+
+/// True when the leading bytes look like a subtitle file: a `WEBVTT` header,
+/// or a numbered `SubRip` cue followed by its timing line.
+pub(crate) fn looks_like_subtitles(magic: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(magic);
+    let text = text.trim_start_matches('\u{feff}');
+    if is_webvtt(text) {
+        return true;
+    }
+    let mut lines = text
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| line.is_empty());
+    let numbered = lines
+        .next()
+        .is_some_and(|line| !line.is_empty() && line.bytes().all(|b| b.is_ascii_digit()));
+    numbered
+        && lines
+            .next()
+            .is_some_and(|line| parse_timing(line).is_some())
+}
+
+fn is_webvtt(text: &str) -> bool {
+    text.strip_prefix("WEBVTT")
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t', '\n', '\r']))
+}
+
+/// Start and end of a `00:01:02,500 --> 00:01:04.000 align:start` line.
+fn parse_timing(line: &str) -> Option<(f32, f32)> {
+    let (start, rest) = line.split_once("-->")?;
+    let end = rest.split_whitespace().next()?;
+    Some((parse_timestamp(start.trim())?, parse_timestamp(end)?))
+}
+
+/// `hh:mm:ss.ttt` or `mm:ss.ttt`, with a comma as the decimal separator in `SubRip`.
+fn parse_timestamp(value: &str) -> Option<f32> {
+    let mut parts = value.rsplit(':');
+    let seconds: f32 = parts.next()?.replace(',', ".").parse().ok()?;
+    let minutes: u16 = parts.next()?.parse().ok()?;
+    let hours: u16 = match parts.next() {
+        Some(hours) => hours.parse().ok()?,
+        None => 0,
+    };
+    if parts.next().is_some() || !(0.0..60.0).contains(&seconds) {
+        return None;
+    }
+    Some(f32::from(hours) * 3600.0 + f32::from(minutes) * 60.0 + seconds)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_timestamp(seconds: f32) -> String {
+    // Non-negative and far below u64::MAX for any real recording.
+    let millis = (f64::from(seconds.max(0.0)) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+/// Drop `<tags>` (and `SubRip` `{\an8}` overrides), returning the voice named
+/// by a `WebVTT` `<v Name>` span and the plain text.
+fn strip_markup(line: &str, webvtt: bool) -> (Option<String>, String) {
+    let mut speaker = None;
+    let mut plain = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(open) = rest.find(['<', '{']) {
+        plain.push_str(&rest[..open]);
+        let close = if rest[open..].starts_with('<') {
+            '>'
+        } else {
+            '}'
+        };
+        let Some(len) = rest[open..].find(close) else {
+            plain.push_str(&rest[open..]);
+            rest = "";
+            break;
+        };
+        let tag = &rest[open + 1..open + len];
+        if close == '}' && !tag.starts_with('\\') {
+            plain.push_str(&rest[open..=open + len]);
+        } else if let Some(voice) = tag.strip_prefix('v').filter(|_| webvtt) {
+            // `<v Name>` or `<v.class Name>`
+            if let Some((_, name)) = voice.split_once([' ', '\t']) {
+                let name = name.trim();
+                if !name.is_empty() && speaker.is_none() {
+                    speaker = Some(decode_entities(name));
+                }
+            }
+        }
+        rest = &rest[open + len + 1..];
+    }
+    plain.push_str(rest);
+    let plain = if webvtt {
+        decode_entities(&plain)
+    } else {
+        plain
+    };
+    (speaker, plain)
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&lrm;", "")
+        .replace("&rlm;", "")
+        .replace("&amp;", "&")
+}
+
+fn escape_cue_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Reader for `SubRip` and `WebVTT` files. The text is the rendered transcript;
+/// cue timings are recorded by ingestion as audio segments.
+pub struct SubtitleReader;
+
+impl DocumentReader for SubtitleReader {
+    fn name(&self) -> &'static str {
+        "subtitle"
+    }
+
+    fn supports(&self, hint: &ReaderHint<'_>) -> bool {
+        matches!(hint.format, Some(DocumentFormat::Subtitles))
+    }
+
+    fn extract(&self, bytes: &[u8], hint: &ReaderHint<'_>) -> Result<ReaderOutput> {
+        let text = String::from_utf8_lossy(bytes);
+        let cues = match parse_subtitles(&text) {
+            Ok(cues) => cues,
+            Err(err) => {
+                let mut fallback = PassthroughReader.extract(bytes, hint)?;
+                fallback.reader_name = self.name().to_string();
+                fallback.diagnostics.mark_fallback();
+                fallback.diagnostics.record_warning(err.to_string());
+                return Ok(fallback);
+            }
+        };
+        let webvtt = is_webvtt(text.trim_start_matches('\u{feff}'));
+        let mut document = ExtractedDocument::empty();
+        document.text = Some(render_transcript(&cues));
+        document.metadata = json!({
+            "format": if webvtt { "webvtt" } else { "srt" },
+            "cues": cues.len(),
+            "duration_secs": cues.iter().map(|cue| cue.end_seconds).reduce(f32::max),
+        });
+        document.mime_type = Some(if webvtt { WEBVTT_MIME } else { SUBRIP_MIME }.to_string());
+        Ok(ReaderOutput::new(document, self.name()).with_diagnostics(ReaderDiagnostics::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRT: &str = "1\r\n00:00:01,000 --> 00:00:03,500\r\nWelcome back to\r\n<i>the show</i>\r\n\r\n2\r\n00:00:04,000 --> 00:00:06,250\r\n{\\an8}Tonight: tides\r\n";
+
+    const VTT: &str = "WEBVTT - episode 12
+
+NOTE speaker names come from the script
+
+STYLE
+::cue { color: yellow }
+
+intro
+00:01.000 --> 00:03.500 align:start
+<v.host Ada Lovelace>Engines &amp; numbers</v>
+
+01:00:04.000 --> 01:00:06.000
+<c.loud>Plain</c> text
+";
+
+    #[test]
+    fn parses_srt_cues() {
+        let cues = parse_subtitles(SRT).expect("srt");
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].text, "Welcome back to the show");
+        assert!((cues[0].start_seconds - 1.0).abs() < 1e-3);
+        assert!((cues[0].end_seconds - 3.5).abs() < 1e-3);
+        assert_eq!(cues[1].text, "Tonight: tides");
+        assert_eq!(cues[1].speaker, None);
+    }
+
+    #[test]
+    fn parses_webvtt_cues_with_voices() {
+        let cues = parse_subtitles(VTT).expect("vtt");
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].speaker.as_deref(), Some("Ada Lovelace"));
+        assert_eq!(cues[0].text, "Engines & numbers");
+        assert_eq!(cues[0].line(), "Ada Lovelace: Engines & numbers");
+        assert!((cues[1].start_seconds - 3604.0).abs() < 1e-2);
+        assert_eq!(cues[1].text, "Plain text");
+        assert_eq!(
+            render_transcript(&cues),
+            "Ada Lovelace: Engines & numbers\nPlain text"
+        );
+    }
+
+    #[test]
+    fn webvtt_round_trips() {
+        let cues = parse_subtitles(VTT).expect("vtt");
+        let written = write_webvtt(&cues);
+        assert!(written.starts_with("WEBVTT\n\n00:00:01.000 --> 00:00:03.500\n"));
+        assert_eq!(parse_subtitles(&written).expect("reparse"), cues);
+    }
+
+    #[test]
+    fn sniffs_subtitles() {
+        assert!(looks_like_subtitles(SRT.as_bytes()));
+        assert!(looks_like_subtitles(VTT.as_bytes()));
+        assert!(!looks_like_subtitles(b"WEBVTTX\n"));
+        assert!(!looks_like_subtitles(b"1\nplain text\n"));
+        assert!(parse_subtitles("no cues here").is_err());
+    }
+}
@@ -39,46 +39,7 @@ impl XlsxReader {
             Xlsx::new(cursor).map_err(|err| crate::MemvidError::ExtractionFailed {
                 reason: format!("failed to read xlsx workbook: {err}").into(),
             })?;
-
-        let sheet_names: Vec<String> = workbook.sheet_names().clone();
-        let mut grids = Vec::new();
-
-        for sheet_name in &sheet_names {
-            let Some(Ok(range)) = workbook.worksheet_range(sheet_name) else {
-                continue;
-            };
-
-            let mut grid = SheetGrid::new(sheet_name.clone());
-            #[allow(clippy::cast_possible_truncation)]
-            let num_rows = range.height() as u32;
-            #[allow(clippy::cast_possible_truncation)]
-            let num_cols = range.width() as u32;
-
-            for row in range.rows() {
-                let cells: Vec<CellValue> = row
-                    .iter()
-                    .map(|cell| match cell {
-                        DataType::String(s) => CellValue::Text(s.clone()),
-                        DataType::Float(v) => CellValue::Number(*v),
-                        DataType::Int(v) => CellValue::Integer(*v),
-                        DataType::Bool(b) => CellValue::Boolean(*b),
-                        DataType::DateTime(v) => CellValue::Number(*v),
-                        DataType::DateTimeIso(s) => CellValue::DateTime(s.clone()),
-                        DataType::Duration(v) => CellValue::Number(*v),
-                        DataType::DurationIso(s) => CellValue::Text(s.clone()),
-                        DataType::Error(e) => CellValue::Error(format!("#{e:?}")),
-                        DataType::Empty => CellValue::Empty,
-                    })
-                    .collect();
-                grid.rows.push(cells);
-            }
-
-            grid.num_rows = num_rows;
-            grid.num_cols = num_cols;
-            grids.push(grid);
-        }
-
-        Ok(grids)
+        Ok(sheet_grids(&mut workbook))
     }
 
     /// Extract structured data from XLSX bytes with default options.
@@ -93,43 +54,7 @@ impl XlsxReader {
     ) -> Result<XlsxStructuredResult> {
         let grids = Self::build_grids(bytes)?;
         let metadata = parse_ooxml_metadata(bytes).unwrap_or_default();
-
-        let mut all_tables = Vec::new();
-        let mut warnings = Vec::new();
-
-        for grid in &grids {
-            let sheet_merged = metadata
-                .merged_regions
-                .get(&grid.sheet_name)
-                .cloned()
-                .unwrap_or_default();
-            let sheet_ooxml_tables: Vec<_> = metadata
-                .table_defs
-                .iter()
-                .filter(|t| t.sheet_name == grid.sheet_name)
-                .cloned()
-                .collect();
-
-            let tables = detect_tables(grid, &sheet_ooxml_tables, &sheet_merged);
-            if tables.is_empty() {
-                warnings.push(format!("No tables detected in sheet '{}'", grid.sheet_name));
-            }
-            all_tables.extend(tables);
-        }
-
-        let chunks = chunk_workbook(&grids, &all_tables, &metadata, &options);
-        let text = generate_flat_text(&grids, &all_tables, &metadata);
-
-        // Merge chunker warnings
-        warnings.extend(chunks.warnings.iter().cloned());
-
-        Ok(XlsxStructuredResult {
-            text,
-            tables: all_tables,
-            chunks,
-            metadata,
-            diagnostics: XlsxStructuredDiagnostics { warnings },
-        })
+        Ok(structure_grids(&grids, metadata, &options))
     }
 
     fn extract_text(bytes: &[u8]) -> Result<String> {
@@ -226,3 +151,95 @@ impl DocumentReader for XlsxReader {
         }
     }
 }
+
+/// One `SheetGrid` per readable worksheet of a calamine workbook.
+pub(crate) fn sheet_grids<RS, R>(workbook: &mut R) -> Vec<SheetGrid>
+where
+    RS: std::io::Read + std::io::Seek,
+    R: CalamineReader<RS>,
+{
+    let sheet_names: Vec<String> = workbook.sheet_names();
+    let mut grids = Vec::new();
+
+    for sheet_name in &sheet_names {
+        let Some(Ok(range)) = workbook.worksheet_range(sheet_name) else {
+            continue;
+        };
+
+        let mut grid = SheetGrid::new(sheet_name.clone());
+        #[allow(clippy::cast_possible_truncation)]
+        let num_rows = range.height() as u32;
+        #[allow(clippy::cast_possible_truncation)]
+        let num_cols = range.width() as u32;
+
+        for row in range.rows() {
+            let cells: Vec<CellValue> = row
+                .iter()
+                .map(|cell| match cell {
+                    DataType::String(s) => CellValue::Text(s.clone()),
+                    DataType::Float(v) => CellValue::Number(*v),
+                    DataType::Int(v) => CellValue::Integer(*v),
+                    DataType::Bool(b) => CellValue::Boolean(*b),
+                    DataType::DateTime(v) => CellValue::Number(*v),
+                    DataType::DateTimeIso(s) => CellValue::DateTime(s.clone()),
+                    DataType::Duration(v) => CellValue::Number(*v),
+                    DataType::DurationIso(s) => CellValue::Text(s.clone()),
+                    DataType::Error(e) => CellValue::Error(format!("#{e:?}")),
+                    DataType::Empty => CellValue::Empty,
+                })
+                .collect();
+            grid.rows.push(cells);
+        }
+
+        grid.num_rows = num_rows;
+        grid.num_cols = num_cols;
+        grids.push(grid);
+    }
+
+    grids
+}
+
+/// Detect tables in `grids` and chunk them, shared by every spreadsheet
+/// format that can be read into `SheetGrid`s.
+pub(crate) fn structure_grids(
+    grids: &[SheetGrid],
+    metadata: OoxmlMetadata,
+    options: &XlsxChunkingOptions,
+) -> XlsxStructuredResult {
+    let mut all_tables = Vec::new();
+    let mut warnings = Vec::new();
+
+    for grid in grids {
+        let sheet_merged = metadata
+            .merged_regions
+            .get(&grid.sheet_name)
+            .cloned()
+            .unwrap_or_default();
+        let sheet_ooxml_tables: Vec<_> = metadata
+            .table_defs
+            .iter()
+            .filter(|t| t.sheet_name == grid.sheet_name)
+            .cloned()
+            .collect();
+
+        let tables = detect_tables(grid, &sheet_ooxml_tables, &sheet_merged);
+        if tables.is_empty() {
+            warnings.push(format!("No tables detected in sheet '{}'", grid.sheet_name));
+        }
+        all_tables.extend(tables);
+    }
+
+    let chunks = chunk_workbook(grids, &all_tables, &metadata, options);
+    let text = generate_flat_text(grids, &all_tables, &metadata);
+
+    // Merge chunker warnings
+    warnings.extend(chunks.warnings.iter().cloned());
+
+    XlsxStructuredResult {
+        text,
+        tables: all_tables,
+        chunks,
+        metadata,
+        diagnostics: XlsxStructuredDiagnostics { warnings },
+    }
+}
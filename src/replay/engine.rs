@@ -238,6 +238,11 @@ impl<'a> ReplayEngine<'a> {
                             no_sketch: false,
                             acl_context: None,
                             acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                            diversify: None,
+                            highlight: None,
+                            facets: Vec::new(),
+                            sort: crate::types::SearchSort::Relevance,
+                            explain: false,
                         };
                         match self.mem.search(search_request) {
                             Ok(response) => {
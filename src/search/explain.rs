@@ -0,0 +1,101 @@
+//! Compact rendering of the parsed query tree for search explanations.
+
+use std::fmt::{self, Display, Formatter};
+
+use super::parser::{Expr, FieldTerm, ParsedQuery, Term, TextTerm};
+
+impl ParsedQuery {
+    /// Prefix-notation rendering, e.g. `AND(word:k8s, NOT(tag:draft))`.
+    pub(crate) fn describe(&self) -> String {
+        self.expr.to_string()
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Or(children) => write_group(f, "OR", children),
+            Expr::And(children) => write_group(f, "AND", children),
+            Expr::Not(inner) => write!(f, "NOT({inner})"),
+            Expr::Term(Term::Text(term)) => write_text_term(f, term),
+            Expr::Term(Term::Field(term)) => write_field_term(f, term),
+        }
+    }
+}
+
+fn write_group(f: &mut Formatter<'_>, op: &str, children: &[Expr]) -> fmt::Result {
+    write!(f, "{op}(")?;
+    for (idx, child) in children.iter().enumerate() {
+        if idx > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{child}")?;
+    }
+    f.write_str(")")
+}
+
+fn write_text_term(f: &mut Formatter<'_>, term: &TextTerm) -> fmt::Result {
+    match term {
+        TextTerm::Word(word) => write!(f, "word:{word}"),
+        TextTerm::Phrase(phrase) => write!(f, "phrase:\"{phrase}\""),
+        TextTerm::Wildcard(pattern) => write!(f, "wildcard:{}", pattern.raw),
+        TextTerm::Prefix(prefix) => write!(f, "prefix:{prefix}*"),
+        TextTerm::Fuzzy(fuzzy) => write!(f, "fuzzy:{}~{}", fuzzy.term, fuzzy.distance),
+        TextTerm::Proximity(proximity) => {
+            let op = if proximity.unordered {
+                "near"
+            } else {
+                "proximity"
+            };
+            write!(
+                f,
+                "{op}:\"{}\"~{}",
+                proximity.words.join(" "),
+                proximity.slop
+            )
+        }
+    }
+}
+
+fn write_field_term(f: &mut Formatter<'_>, term: &FieldTerm) -> fmt::Result {
+    let bound = |value: Option<&str>| value.unwrap_or("*").to_string();
+    match term {
+        FieldTerm::Uri(value) => write!(f, "uri:{value}"),
+        FieldTerm::Scope(value) => write!(f, "scope:{value}"),
+        FieldTerm::Track(value) => write!(f, "track:{value}"),
+        FieldTerm::Tag(value) => write!(f, "tag:{value}"),
+        FieldTerm::Label(value) => write!(f, "label:{value}"),
+        FieldTerm::Title(value) => write!(f, "title:{value}"),
+        FieldTerm::DateRange(range) => write!(
+            f,
+            "date:[{} TO {}]",
+            range
+                .start
+                .map_or_else(|| "*".to_string(), |ts| ts.to_string()),
+            range
+                .end
+                .map_or_else(|| "*".to_string(), |ts| ts.to_string())
+        ),
+        FieldTerm::Meta { key, value } => write!(f, "meta.{key}:{value}"),
+        FieldTerm::MetaRange { key, start, end } => write!(
+            f,
+            "meta.{key}:[{} TO {}]",
+            bound(start.as_deref()),
+            bound(end.as_deref())
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::search::parse_query;
+
+    #[test]
+    fn describes_nested_queries() {
+        let parsed = parse_query("(alpha OR \"beta gamma\") AND NOT tag:draft").unwrap();
+        assert_eq!(
+            parsed.describe(),
+            "AND(OR(word:alpha, phrase:\"beta gamma\"), NOT(tag:draft))"
+        );
+    }
+}
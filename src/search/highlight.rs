@@ -0,0 +1,342 @@
+//! Multi-fragment highlighting over analysed tokens.
+//!
+//! Frame text is run through the index analyzer so matches line up with what
+//! Tantivy scored: `approvals` finds `approval` through the shared stem and
+//! Jieba segments CJK text into whole words. Prefix, fuzzy and wildcard terms
+//! are compared against the lowercased surface form instead, mirroring the
+//! query planner. Terms under `NOT` are never highlighted.
+
+use std::collections::{BTreeSet, HashSet};
+
+use regex::Regex;
+
+use super::bounded_edit_distance;
+use super::parser::{Expr, FuzzyTerm, ParsedQuery, Term, TextTerm};
+use crate::types::{HighlightFragment, HighlightOptions, HighlightSpan};
+
+/// Bonus per repeated occurrence, kept below one distinct term.
+const REPEAT_BONUS: f32 = 0.1;
+
+/// Token produced by the analyzer with its byte offsets in the source text.
+#[derive(Debug, Clone)]
+pub(crate) struct AnalysedToken {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Positive query terms, ready to be matched against analysed tokens.
+#[derive(Debug, Default)]
+pub(crate) struct Highlighter {
+    exact: HashSet<String>,
+    prefixes: Vec<String>,
+    fuzzy: Vec<FuzzyTerm>,
+    wildcards: Vec<Regex>,
+}
+
+struct Match {
+    start: usize,
+    end: usize,
+    term: String,
+}
+
+impl Highlighter {
+    /// Collect highlightable terms; `analyse` must be the indexing analyzer.
+    pub(crate) fn new(parsed: &ParsedQuery, analyse: impl Fn(&str) -> Vec<String>) -> Self {
+        let mut highlighter = Self::default();
+        highlighter.collect(&parsed.expr, &analyse);
+        highlighter
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.exact.is_empty()
+            && self.prefixes.is_empty()
+            && self.fuzzy.is_empty()
+            && self.wildcards.is_empty()
+    }
+
+    fn collect(&mut self, expr: &Expr, analyse: &impl Fn(&str) -> Vec<String>) {
+        match expr {
+            Expr::Or(children) | Expr::And(children) => {
+                for child in children {
+                    self.collect(child, analyse);
+                }
+            }
+            Expr::Not(_) | Expr::Term(Term::Field(_)) => {}
+            Expr::Term(Term::Text(text)) => match text {
+                TextTerm::Word(value) | TextTerm::Phrase(value) => {
+                    self.exact.extend(analyse(value));
+                }
+                TextTerm::Proximity(proximity) => {
+                    self.exact.extend(analyse(&proximity.words.join(" ")));
+                }
+                TextTerm::Prefix(prefix) => self.prefixes.push(prefix.clone()),
+                TextTerm::Fuzzy(fuzzy) => self.fuzzy.push(fuzzy.clone()),
+                TextTerm::Wildcard(pattern) => self.wildcards.push(pattern.regex.clone()),
+            },
+        }
+    }
+
+    /// Query term matched by `token`, if any.
+    fn matched_term(&self, token: &AnalysedToken, text: &str) -> Option<String> {
+        if self.exact.contains(&token.text) {
+            return Some(token.text.clone());
+        }
+        let surface = text.get(token.start..token.end)?.to_lowercase();
+        if let Some(prefix) = self
+            .prefixes
+            .iter()
+            .find(|p| surface.starts_with(p.as_str()))
+        {
+            return Some(format!("{prefix}*"));
+        }
+        if let Some(fuzzy) = self.fuzzy.iter().find(|fuzzy| {
+            bounded_edit_distance(&surface, &fuzzy.term, usize::from(fuzzy.distance)).is_some()
+        }) {
+            return Some(format!("{}~{}", fuzzy.term, fuzzy.distance));
+        }
+        self.wildcards
+            .iter()
+            .find(|regex| regex.is_match(&surface))
+            .map(|regex| regex.as_str().to_string())
+    }
+
+    /// Up to `options.max_fragments` non-overlapping fragments of `text`,
+    /// best first. `byte_base`/`char_base` locate `text` inside the frame text.
+    pub(crate) fn fragments(
+        &self,
+        text: &str,
+        tokens: &[AnalysedToken],
+        options: &HighlightOptions,
+        byte_base: usize,
+        char_base: usize,
+    ) -> Vec<HighlightFragment> {
+        let matches: Vec<Match> = tokens
+            .iter()
+            .filter_map(|token| {
+                self.matched_term(token, text).map(|term| Match {
+                    start: token.start,
+                    end: token.end,
+                    term,
+                })
+            })
+            .collect();
+        if matches.is_empty() || options.max_fragments == 0 {
+            return Vec::new();
+        }
+
+        let chars = CharIndex::new(text);
+        let fragment_chars = options.fragment_chars.max(1);
+
+        // One candidate window per match, anchored a quarter-fragment before it.
+        let mut candidates: Vec<(f32, usize, usize)> = matches
+            .iter()
+            .map(|anchor| {
+                let anchor_char = chars.char_at(anchor.start);
+                let start_char = anchor_char.saturating_sub(fragment_chars / 4);
+                let end_char = (start_char + fragment_chars)
+                    .max(chars.char_at(anchor.end))
+                    .min(chars.len());
+                let (start, end) = snap_to_words(
+                    text,
+                    chars.byte_at(start_char),
+                    chars.byte_at(end_char),
+                    anchor,
+                );
+                let inside: Vec<&Match> = matches
+                    .iter()
+                    .filter(|m| m.start >= start && m.end <= end)
+                    .collect();
+                let distinct: BTreeSet<&str> = inside.iter().map(|m| m.term.as_str()).collect();
+                #[allow(clippy::cast_precision_loss)]
+                let score =
+                    distinct.len() as f32 + REPEAT_BONUS * (inside.len() - distinct.len()) as f32;
+                (score, start, end)
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        let mut chosen: Vec<(f32, usize, usize)> = Vec::new();
+        for candidate in candidates {
+            if chosen.len() == options.max_fragments {
+                break;
+            }
+            let overlaps = chosen
+                .iter()
+                .any(|(_, start, end)| candidate.1 < *end && *start < candidate.2);
+            if !overlaps {
+                chosen.push(candidate);
+            }
+        }
+
+        chosen
+            .into_iter()
+            .map(|(score, start, end)| {
+                let start_char = chars.char_at(start);
+                let spans = matches
+                    .iter()
+                    .filter(|m| m.start >= start && m.end <= end)
+                    .map(|m| {
+                        let (m_start_char, m_end_char) =
+                            (chars.char_at(m.start), chars.char_at(m.end));
+                        HighlightSpan {
+                            term: m.term.clone(),
+                            fragment_range: (m.start - start, m.end - start),
+                            fragment_char_range: (
+                                m_start_char - start_char,
+                                m_end_char - start_char,
+                            ),
+                            range: (byte_base + m.start, byte_base + m.end),
+                            char_range: (char_base + m_start_char, char_base + m_end_char),
+                        }
+                    })
+                    .collect();
+                HighlightFragment {
+                    text: text[start..end].to_string(),
+                    range: (byte_base + start, byte_base + end),
+                    char_range: (char_base + start_char, char_base + chars.char_at(end)),
+                    score,
+                    spans,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Byte offset of every character boundary, for byte/char conversions.
+struct CharIndex {
+    boundaries: Vec<usize>,
+}
+
+impl CharIndex {
+    fn new(text: &str) -> Self {
+        let boundaries = text
+            .char_indices()
+            .map(|(idx, _)| idx)
+            .chain(std::iter::once(text.len()))
+            .collect();
+        Self { boundaries }
+    }
+
+    /// Number of characters in the text.
+    fn len(&self) -> usize {
+        self.boundaries.len() - 1
+    }
+
+    fn char_at(&self, byte: usize) -> usize {
+        self.boundaries.partition_point(|&boundary| boundary < byte)
+    }
+
+    fn byte_at(&self, char_idx: usize) -> usize {
+        self.boundaries[char_idx.min(self.len())]
+    }
+}
+
+/// Pull window edges in to whitespace so fragments don't start or end
+/// mid-word, without cutting into the anchoring match.
+fn snap_to_words(text: &str, start: usize, end: usize, anchor: &Match) -> (usize, usize) {
+    let mut start = start;
+    if start > 0 && !text[..start].ends_with(char::is_whitespace) {
+        if let Some(space) = text[start..anchor.start].find(char::is_whitespace) {
+            start += space;
+        }
+        start += text[start..anchor.start].len() - text[start..anchor.start].trim_start().len();
+    }
+    let mut end = end;
+    if end < text.len() && !text[end..].starts_with(char::is_whitespace) {
+        if let Some(space) = text[anchor.end..end].rfind(char::is_whitespace) {
+            end = anchor.end + space;
+        }
+    }
+    let trimmed = text[start..end].trim_end().len();
+    (start, (start + trimmed).max(anchor.end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::parse_query;
+
+    /// Lowercased alphanumeric words with a crude plural stemmer, standing in
+    /// for the Tantivy analyzer.
+    fn analyse(text: &str) -> Vec<AnalysedToken> {
+        let mut tokens = Vec::new();
+        let mut start = None;
+        for (idx, ch) in text
+            .char_indices()
+            .chain(std::iter::once((text.len(), ' ')))
+        {
+            match (start, ch.is_alphanumeric()) {
+                (None, true) => start = Some(idx),
+                (Some(from), false) => {
+                    let word = text[from..idx].to_lowercase();
+                    tokens.push(AnalysedToken {
+                        text: word.strip_suffix('s').unwrap_or(&word).to_string(),
+                        start: from,
+                        end: idx,
+                    });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        tokens
+    }
+
+    fn highlighter(query: &str) -> Highlighter {
+        let parsed = parse_query(query).expect("parse");
+        Highlighter::new(&parsed, |text| {
+            analyse(text).into_iter().map(|token| token.text).collect()
+        })
+    }
+
+    #[test]
+    fn marks_stemmed_and_prefix_matches() {
+        let text = "Budget approvals are due. Nothing else here. The budget was approved.";
+        let options = HighlightOptions {
+            max_fragments: 2,
+            fragment_chars: 30,
+        };
+        let fragments = highlighter("budget approval approv* NOT else").fragments(
+            text,
+            &analyse(text),
+            &options,
+            100,
+            50,
+        );
+        assert_eq!(fragments.len(), 2);
+
+        let best = &fragments[0];
+        assert_eq!(best.text, "Budget approvals are due.");
+        let marked: Vec<&str> = best
+            .spans
+            .iter()
+            .map(|span| &best.text[span.fragment_range.0..span.fragment_range.1])
+            .collect();
+        assert_eq!(marked, ["Budget", "approvals"]);
+        assert_eq!(best.spans[1].range, (107, 116));
+        assert_eq!(best.spans[1].char_range, (57, 66));
+
+        assert!(
+            fragments
+                .iter()
+                .all(|fragment| !fragment.text.contains("else"))
+        );
+        assert!(highlighter("tag:x NOT budget").is_empty());
+    }
+
+    #[test]
+    fn char_offsets_follow_multibyte_text() {
+        let text = "café budget";
+        let fragments = highlighter("budget").fragments(
+            text,
+            &analyse(text),
+            &HighlightOptions::default(),
+            0,
+            0,
+        );
+        let span = &fragments[0].spans[0];
+        assert_eq!(span.range, (6, 12));
+        assert_eq!(span.char_range, (5, 11));
+    }
+}
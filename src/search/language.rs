@@ -0,0 +1,82 @@
+//! Content language detection used to route frames to per-language analyzers.
+
+use whatlang::Lang;
+
+use crate::types::{ContentLanguage, Frame, LANGUAGE_METADATA_KEY};
+
+/// Leading characters inspected; more text rarely changes the verdict.
+const DETECTION_SAMPLE_CHARS: usize = 2_000;
+
+/// Language of `text` when it has a dedicated analyzer. Latin-script guesses
+/// are only trusted when the detector considers them reliable; CJK scripts
+/// are distinctive enough to trust outright.
+pub(crate) fn detect_language(text: &str) -> Option<ContentLanguage> {
+    let sample = text
+        .char_indices()
+        .nth(DETECTION_SAMPLE_CHARS)
+        .map_or(text, |(end, _)| &text[..end]);
+    let info = whatlang::detect(sample)?;
+    let language = match info.lang() {
+        Lang::Eng => ContentLanguage::English,
+        Lang::Deu => ContentLanguage::German,
+        Lang::Fra => ContentLanguage::French,
+        Lang::Spa => ContentLanguage::Spanish,
+        Lang::Jpn => return Some(ContentLanguage::Japanese),
+        Lang::Kor => return Some(ContentLanguage::Korean),
+        Lang::Cmn => return Some(ContentLanguage::Chinese),
+        _ => return None,
+    };
+    info.is_reliable().then_some(language)
+}
+
+/// Language recorded on `frame`, falling back to detection over `content`
+/// for frames ingested before languages were recorded.
+pub(crate) fn frame_language(frame: &Frame, content: &str) -> Option<ContentLanguage> {
+    match frame.extra_metadata.get(LANGUAGE_METADATA_KEY) {
+        Some(code) => ContentLanguage::from_code(code),
+        None => detect_language(content),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_supported_languages() {
+        let cases = [
+            (
+                "The quarterly report shows that revenue grew faster than expected this year.",
+                ContentLanguage::English,
+            ),
+            (
+                "Die Bundesregierung hat heute neue Maßnahmen für den Klimaschutz beschlossen.",
+                ContentLanguage::German,
+            ),
+            (
+                "Le gouvernement a annoncé de nouvelles mesures pour protéger les forêts du pays.",
+                ContentLanguage::French,
+            ),
+            (
+                "Las facturas pendientes deben enviarse al departamento de contabilidad antes del viernes por la tarde.",
+                ContentLanguage::Spanish,
+            ),
+            (
+                "東京都は新しい交通計画を発表しました。",
+                ContentLanguage::Japanese,
+            ),
+            (
+                "서울시는 새로운 교통 계획을 발표했습니다.",
+                ContentLanguage::Korean,
+            ),
+            (
+                "北京市政府今天发布了新的交通规划。",
+                ContentLanguage::Chinese,
+            ),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(detect_language(text), Some(expected), "{text}");
+        }
+        assert_eq!(detect_language("   "), None);
+    }
+}
@@ -1,18 +1,37 @@
+#[cfg(feature = "lex")]
+mod explain;
+#[cfg(feature = "lex")]
+mod highlight;
+#[cfg(feature = "lex")]
+mod language;
 mod parser;
+#[cfg(feature = "lex")]
+mod spelling;
+#[cfg(feature = "lex")]
+mod synonyms;
 
 #[cfg(feature = "lex")]
 mod tantivy;
 
-use crate::types::Frame;
+use std::cmp::Ordering;
+
+use crate::types::{Frame, SYNONYMS_METADATA_KEY};
 use parser::{Expr, FieldTerm, Term, TextTerm};
 
+#[cfg(feature = "lex")]
+pub(crate) use highlight::{AnalysedToken, Highlighter};
+#[cfg(feature = "lex")]
+pub(crate) use language::{detect_language, frame_language};
 pub(crate) use parser::parse_query;
 pub(crate) use parser::{DateRange, ParsedQuery};
+#[cfg(feature = "lex")]
+pub(crate) use spelling::{SpellingDelta, SpellingDictionary};
 
 #[cfg(feature = "lex")]
 #[allow(unused_imports)]
 pub(crate) use tantivy::{
-    EmbeddedLexSegment, EmbeddedLexStorage, LexWalBatch, TantivyEngine, TantivySnapshot,
+    EmbeddedLexSegment, EmbeddedLexStorage, LanguageTokens, LexWalBatch, TANTIVY_CODEC_VERSION,
+    TantivyEngine, TantivySnapshot,
 };
 
 pub struct EvaluationContext<'a> {
@@ -25,6 +44,16 @@ impl ParsedQuery {
         self.expr.evaluate(ctx)
     }
 
+    /// Evaluate field terms against `frame` metadata alone, leaving text terms
+    /// undecided. `Some(false)` means no frame text could make the query match.
+    pub(crate) fn evaluate_fields(&self, frame: &Frame) -> Option<bool> {
+        let ctx = EvaluationContext {
+            frame,
+            content_lower: "",
+        };
+        self.expr.evaluate_fields(&ctx)
+    }
+
     pub fn text_tokens(&self) -> Vec<String> {
         self.expr.collect_tokens()
     }
@@ -50,8 +79,72 @@ impl TextTerm {
                 haystack.contains(&needle)
             }
             TextTerm::Wildcard(pattern) => pattern.regex.is_match(haystack),
+            TextTerm::Prefix(prefix) => haystack.match_indices(prefix.as_str()).any(|(pos, _)| {
+                haystack[..pos]
+                    .chars()
+                    .next_back()
+                    .is_none_or(|ch| !ch.is_alphanumeric())
+            }),
+            TextTerm::Fuzzy(fuzzy) => {
+                let max = usize::from(fuzzy.distance);
+                haystack
+                    .split(|ch: char| !ch.is_alphanumeric())
+                    .filter(|word| !word.is_empty())
+                    .any(|word| bounded_edit_distance(word, &fuzzy.term, max).is_some())
+            }
+            TextTerm::Proximity(proximity) => proximity_matches(haystack, proximity),
+        }
+    }
+}
+
+/// Whether every word of `proximity` occurs as a whole word inside a window
+/// of `words + slop` positions. Order is not checked here; the Tantivy phrase
+/// query already enforced it for ordered slop. CJK text has no word
+/// boundaries to count, so only presence is checked.
+fn proximity_matches(haystack: &str, proximity: &parser::ProximityTerm) -> bool {
+    if proximity.words.iter().any(|word| contains_cjk(word)) {
+        return proximity
+            .words
+            .iter()
+            .all(|word| haystack.contains(word.as_str()));
+    }
+    let words: Vec<&str> = haystack
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    // (position, index of the query word it matches), in position order
+    let mut hits: Vec<(usize, usize)> = Vec::new();
+    for (position, word) in words.iter().enumerate() {
+        for (index, needle) in proximity.words.iter().enumerate() {
+            if *word == needle.as_str() {
+                hits.push((position, index));
+            }
+        }
+    }
+
+    let wanted = proximity.words.len();
+    let span = wanted + proximity.slop as usize;
+    let mut counts = vec![0usize; wanted];
+    let mut covered = 0;
+    let mut start = 0;
+    for &(end_position, index) in &hits {
+        if counts[index] == 0 {
+            covered += 1;
+        }
+        counts[index] += 1;
+        while hits[start].0 + span <= end_position {
+            let dropped = hits[start].1;
+            counts[dropped] -= 1;
+            if counts[dropped] == 0 {
+                covered -= 1;
+            }
+            start += 1;
+        }
+        if covered == wanted {
+            return true;
         }
     }
+    false
 }
 
 impl FieldTerm {
@@ -82,11 +175,58 @@ impl FieldTerm {
                 .labels
                 .iter()
                 .any(|value| value.eq_ignore_ascii_case(label)),
+            FieldTerm::Title(title) => ctx
+                .frame
+                .title
+                .as_deref()
+                .is_some_and(|value| value.to_lowercase().contains(title.as_str())),
             FieldTerm::DateRange(range) => range.matches(ctx.frame),
+            FieldTerm::Meta { key, value } => {
+                metadata_value(ctx.frame, key).is_some_and(|actual| {
+                    actual.to_lowercase().contains(value.as_str())
+                        || compare_metadata(actual, value) == Some(Ordering::Equal)
+                })
+            }
+            FieldTerm::MetaRange { key, start, end } => {
+                metadata_value(ctx.frame, key).is_some_and(|actual| {
+                    let above = start.as_deref().is_none_or(|start| {
+                        compare_metadata(actual, start).is_some_and(Ordering::is_ge)
+                    });
+                    let below = end.as_deref().is_none_or(|end| {
+                        compare_metadata(actual, end).is_some_and(Ordering::is_le)
+                    });
+                    above && below
+                })
+            }
         }
     }
 }
 
+/// Value of `key` in a frame's `extra_metadata`, matched case-insensitively.
+pub(crate) fn metadata_value<'a>(frame: &'a Frame, key: &str) -> Option<&'a str> {
+    frame
+        .extra_metadata
+        .iter()
+        .find(|(candidate, _)| candidate.eq_ignore_ascii_case(key))
+        .map(|(_, value)| value.as_str())
+}
+
+/// Order a metadata value against a query bound: numerically when both parse
+/// as numbers, then as dates, otherwise as lowercase strings.
+fn compare_metadata(actual: &str, bound: &str) -> Option<Ordering> {
+    let actual = actual.trim();
+    if let (Ok(lhs), Ok(rhs)) = (actual.parse::<f64>(), bound.parse::<f64>()) {
+        return lhs.partial_cmp(&rhs);
+    }
+    if let (Some(lhs), Some(rhs)) = (
+        parser::parse_date_value(actual),
+        parser::parse_date_value(bound),
+    ) {
+        return Some(lhs.cmp(&rhs));
+    }
+    Some(actual.to_lowercase().as_str().cmp(bound))
+}
+
 impl DateRange {
     fn matches(&self, frame: &Frame) -> bool {
         if self.start.is_none() && self.end.is_none() {
@@ -154,6 +294,41 @@ impl Expr {
         }
     }
 
+    /// Three-valued evaluation where text terms are unknown (`None`).
+    fn evaluate_fields(&self, ctx: &EvaluationContext<'_>) -> Option<bool> {
+        match self {
+            Expr::Or(children) => {
+                let results: Vec<Option<bool>> = children
+                    .iter()
+                    .map(|child| child.evaluate_fields(ctx))
+                    .collect();
+                if results.contains(&Some(true)) {
+                    Some(true)
+                } else if results.iter().all(|result| *result == Some(false)) {
+                    Some(false)
+                } else {
+                    None
+                }
+            }
+            Expr::And(children) => {
+                let results: Vec<Option<bool>> = children
+                    .iter()
+                    .map(|child| child.evaluate_fields(ctx))
+                    .collect();
+                if results.contains(&Some(false)) {
+                    Some(false)
+                } else if results.iter().all(|result| *result == Some(true)) {
+                    Some(true)
+                } else {
+                    None
+                }
+            }
+            Expr::Not(child) => child.evaluate_fields(ctx).map(|result| !result),
+            Expr::Term(Term::Field(field)) => Some(field.matches(ctx)),
+            Expr::Term(Term::Text(_)) => None,
+        }
+    }
+
     fn collect_tokens(&self) -> Vec<String> {
         let mut tokens = Vec::new();
         self.collect_into(&mut tokens);
@@ -169,7 +344,11 @@ impl Expr {
             }
             Expr::Not(child) => child.collect_into(tokens),
             Expr::Term(Term::Text(text)) => match text {
-                TextTerm::Word(word) | TextTerm::Phrase(word) => tokens.push(word.clone()),
+                TextTerm::Word(word) | TextTerm::Phrase(word) | TextTerm::Prefix(word) => {
+                    tokens.push(word.clone());
+                }
+                TextTerm::Fuzzy(fuzzy) => tokens.push(fuzzy.term.clone()),
+                TextTerm::Proximity(proximity) => tokens.extend(proximity.words.iter().cloned()),
                 TextTerm::Wildcard(pattern) => {
                     if let Some(seed) = pattern.seed() {
                         tokens.push(seed);
@@ -214,7 +393,15 @@ impl Expr {
 impl Term {
     fn evaluate(&self, ctx: &EvaluationContext<'_>) -> bool {
         match self {
-            Term::Text(text) => text.matches(ctx.content_lower),
+            // Synonyms recorded at ingestion match like the frame's own text
+            Term::Text(text) => {
+                text.matches(ctx.content_lower)
+                    || ctx
+                        .frame
+                        .extra_metadata
+                        .get(SYNONYMS_METADATA_KEY)
+                        .is_some_and(|synonyms| text.matches(synonyms))
+            }
             Term::Field(field) => field.matches(ctx),
         }
     }
@@ -224,11 +411,47 @@ impl Term {
     }
 }
 
+/// Optimal string alignment distance between `a` and `b` (adjacent transpositions
+/// count as one edit), or `None` once it exceeds `max`.
+pub(crate) fn bounded_edit_distance(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+    let width = b.len() + 1;
+    let mut rows = vec![vec![0usize; width]; 3];
+    for (j, cell) in rows[1].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        let (cur, prev, prev2) = ((i + 1) % 3, i % 3, (i + 2) % 3);
+        rows[cur][0] = i;
+        let mut row_min = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut value = (rows[prev][j] + 1)
+                .min(rows[cur][j - 1] + 1)
+                .min(rows[prev][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                value = value.min(rows[prev2][j - 2] + 1);
+            }
+            rows[cur][j] = value;
+            row_min = row_min.min(value);
+        }
+        if row_min > max {
+            return None;
+        }
+    }
+    let distance = rows[(a.len() + 1) % 3][b.len()];
+    (distance <= max).then_some(distance)
+}
+
 pub(crate) fn contains_cjk(text: &str) -> bool {
     text.chars().any(is_cjk_char)
 }
 
-fn is_cjk_char(ch: char) -> bool {
+pub(crate) fn is_cjk_char(ch: char) -> bool {
     matches!(
         ch,
         '\u{3400}'..='\u{4DBF}'
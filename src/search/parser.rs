@@ -1,6 +1,7 @@
 // Safe unwrap/expect: regex patterns from validated input strings.
 #![allow(clippy::unwrap_used, clippy::expect_used)]
 use crate::error::MemvidError;
+use crate::types::METADATA_FIELD_PREFIX;
 use regex::Regex;
 use std::convert::TryFrom;
 use time::{Date, Month, OffsetDateTime};
@@ -37,6 +38,36 @@ pub(crate) enum TextTerm {
     Word(String),
     Phrase(String),
     Wildcard(WildcardPattern),
+    /// `term*` with a single trailing star; matched via the term dictionary, not a regex.
+    Prefix(String),
+    /// `term~N`: matches terms within `distance` edits (transpositions count as one).
+    Fuzzy(FuzzyTerm),
+    /// `"a b"~N` or `a NEAR/N b`: words within `slop` positions of each other.
+    Proximity(ProximityTerm),
+}
+
+/// Maximum edit distance accepted by `term~N` (bounded by Tantivy's Levenshtein automata).
+pub(crate) const MAX_FUZZY_DISTANCE: u8 = 2;
+/// Edit distance used when `term~` carries no explicit number (matches Lucene).
+pub(crate) const DEFAULT_FUZZY_DISTANCE: u8 = 2;
+
+/// Largest slop accepted by `"..."~N` and `NEAR/N`.
+pub(crate) const MAX_PROXIMITY_SLOP: u32 = 64;
+
+#[derive(Debug, Clone)]
+pub(crate) struct ProximityTerm {
+    /// Lowercased words in query order.
+    pub words: Vec<String>,
+    /// Extra positions allowed between the words (Tantivy phrase slop).
+    pub slop: u32,
+    /// `NEAR/N` also matches the operands in reverse order.
+    pub unordered: bool,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct FuzzyTerm {
+    pub term: String,
+    pub distance: u8,
 }
 
 #[derive(Debug, Clone)]
@@ -52,7 +83,19 @@ pub(crate) enum FieldTerm {
     Track(String),
     Tag(String),
     Label(String),
+    Title(String),
     DateRange(DateRange),
+    /// `meta.<key>:value` against a frame's `extra_metadata`.
+    Meta {
+        key: String,
+        value: String,
+    },
+    /// `meta.<key>:[lo TO hi]`; `*` leaves a side open.
+    MetaRange {
+        key: String,
+        start: Option<String>,
+        end: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, Default)]
@@ -65,13 +108,15 @@ pub(crate) struct DateRange {
 enum Token {
     Word(String),
     Phrase(String),
+    SloppyPhrase(String, u32),
     Field(String, String),
-    DateRange(String, String, String),
+    Range(String, String, String),
     LParen,
     RParen,
     And,
     Or,
     Not,
+    Near(u32),
 }
 
 struct Lexer<'a> {
@@ -108,7 +153,10 @@ impl<'a> Lexer<'a> {
                 }
                 '"' => {
                     let phrase = self.read_quoted()?;
-                    tokens.push(Token::Phrase(phrase));
+                    match self.read_slop()? {
+                        Some(slop) => tokens.push(Token::SloppyPhrase(phrase, slop)),
+                        None => tokens.push(Token::Phrase(phrase)),
+                    }
                 }
                 _ => {
                     if let Some(token) = self.read_field_or_word()? {
@@ -122,7 +170,11 @@ impl<'a> Lexer<'a> {
 
     /// Known field names that should be treated as field queries when followed by `:`
     const KNOWN_FIELDS: &'static [&'static str] =
-        &["uri", "scope", "track", "tag", "label", "date"];
+        &["uri", "scope", "track", "tag", "label", "date", "title"];
+
+    fn is_field_name(name: &str) -> bool {
+        Self::KNOWN_FIELDS.contains(&name) || metadata_key(name).is_some()
+    }
 
     fn read_field_or_word(&mut self) -> Result<Option<Token>, MemvidError> {
         let start = self.index;
@@ -144,7 +196,7 @@ impl<'a> Lexer<'a> {
             let potential_field: String = self.chars[start..colon_idx].iter().collect();
             let potential_field_lower = potential_field.to_ascii_lowercase();
 
-            if Self::KNOWN_FIELDS.contains(&potential_field_lower.as_str()) {
+            if Self::is_field_name(&potential_field_lower) {
                 // Reset index to just after the colon and parse as field
                 self.index = colon_idx + 1;
                 return self.read_field(start);
@@ -160,7 +212,19 @@ impl<'a> Lexer<'a> {
             "AND" | "and" => Ok(Some(Token::And)),
             "OR" | "or" => Ok(Some(Token::Or)),
             "NOT" | "not" => Ok(Some(Token::Not)),
-            _ => Ok(Some(Token::Word(word))),
+            _ => {
+                let near = word
+                    .get(..5)
+                    .filter(|head| head.eq_ignore_ascii_case("NEAR/"))
+                    .map(|_| &word[5..])
+                    .filter(|digits| {
+                        !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+                    });
+                match near {
+                    Some(distance) => Ok(Some(Token::Near(parse_slop(distance)?))),
+                    None => Ok(Some(Token::Word(word))),
+                }
+            }
         }
     }
 
@@ -173,10 +237,10 @@ impl<'a> Lexer<'a> {
                 let value = self.read_until_quote()?;
                 Token::Field(field, value)
             }
-            Some('[') if field == "date" => {
+            Some('[') if field == "date" || metadata_key(&field).is_some() => {
                 self.index += 1; // skip '['
-                let (start, end) = self.read_date_range()?;
-                Token::DateRange(field, start, end)
+                let (start, end) = self.read_range()?;
+                Token::Range(field, start, end)
             }
             _ => {
                 let value_start = self.index;
@@ -199,6 +263,20 @@ impl<'a> Lexer<'a> {
         Ok(value)
     }
 
+    /// Optional `~N` directly after a closing quote.
+    fn read_slop(&mut self) -> Result<Option<u32>, MemvidError> {
+        if self.peek() != Some('~') {
+            return Ok(None);
+        }
+        self.index += 1; // skip '~'
+        let start = self.index;
+        while self.peek().is_some_and(|ch| ch.is_ascii_digit()) {
+            self.index += 1;
+        }
+        let digits: String = self.chars[start..self.index].iter().collect();
+        parse_slop(&digits).map(Some)
+    }
+
     fn read_until_quote(&mut self) -> Result<String, MemvidError> {
         let start = self.index;
         while let Some(ch) = self.peek() {
@@ -214,7 +292,7 @@ impl<'a> Lexer<'a> {
         })
     }
 
-    fn read_date_range(&mut self) -> Result<(String, String), MemvidError> {
+    fn read_range(&mut self) -> Result<(String, String), MemvidError> {
         let start_pos = self.index;
         while let Some(ch) = self.peek() {
             if ch == ']' {
@@ -224,7 +302,7 @@ impl<'a> Lexer<'a> {
                 let parts: Vec<_> = contents.split_whitespace().collect();
                 if parts.len() != 3 || !parts[1].eq_ignore_ascii_case("TO") {
                     return Err(MemvidError::InvalidQuery {
-                        reason: "date range must be in format [start TO end]".into(),
+                        reason: "range must be in format [start TO end]".into(),
                     });
                 }
                 return Ok((parts[0].to_string(), parts[2].to_string()));
@@ -232,7 +310,7 @@ impl<'a> Lexer<'a> {
             self.index += 1;
         }
         Err(MemvidError::InvalidQuery {
-            reason: "unterminated date range".into(),
+            reason: "unterminated range".into(),
         })
     }
 
@@ -304,7 +382,29 @@ impl Parser {
             let inner = self.parse_factor()?;
             return Ok(Expr::Not(Box::new(inner)));
         }
-        self.parse_primary()
+        self.parse_proximity()
+    }
+
+    /// `NEAR/N` binds tighter than AND/OR and joins two words or phrases.
+    fn parse_proximity(&mut self) -> Result<Expr, MemvidError> {
+        let left = self.parse_primary()?;
+        let Some(Token::Near(slop)) = self.tokens.get(self.position).cloned() else {
+            return Ok(left);
+        };
+        self.position += 1;
+        let right = self.parse_primary()?;
+        if matches!(self.tokens.get(self.position), Some(Token::Near(_))) {
+            return Err(MemvidError::InvalidQuery {
+                reason: "NEAR cannot be chained; group clauses with AND instead".into(),
+            });
+        }
+        let mut words = near_operand_words(&left)?;
+        words.extend(near_operand_words(&right)?);
+        Ok(Expr::Term(Term::Text(TextTerm::Proximity(ProximityTerm {
+            words,
+            slop,
+            unordered: true,
+        }))))
     }
 
     fn parse_primary(&mut self) -> Result<Expr, MemvidError> {
@@ -314,16 +414,29 @@ impl Parser {
             return Ok(expr);
         }
         match self.advance() {
-            Some(Token::Word(word)) => Ok(Expr::Term(Term::Text(TextTerm::from_word(word)))),
+            Some(Token::Word(word)) => Ok(Expr::Term(Term::Text(TextTerm::from_token(word)?))),
             Some(Token::Phrase(phrase)) => Ok(Expr::Term(Term::Text(TextTerm::Phrase(
                 phrase.to_ascii_lowercase(),
             )))),
+            Some(Token::SloppyPhrase(phrase, slop)) => {
+                let lower = phrase.to_ascii_lowercase();
+                let words = phrase_words(&lower);
+                // A single word has nothing to be near; treat it as a plain phrase.
+                if slop == 0 || (words.len() < 2 && !super::contains_cjk(&lower)) {
+                    return Ok(Expr::Term(Term::Text(TextTerm::Phrase(lower))));
+                }
+                Ok(Expr::Term(Term::Text(TextTerm::Proximity(ProximityTerm {
+                    words,
+                    slop,
+                    unordered: false,
+                }))))
+            }
             Some(Token::Field(field, value)) => {
                 let term = FieldTerm::from_pair(&field, &value)?;
                 Ok(Expr::Term(Term::Field(term)))
             }
-            Some(Token::DateRange(field, start, end)) => {
-                let term = FieldTerm::from_date_range(&field, &start, &end)?;
+            Some(Token::Range(field, start, end)) => {
+                let term = FieldTerm::from_range(&field, &start, &end)?;
                 Ok(Expr::Term(Term::Field(term)))
             }
             Some(token) => Err(MemvidError::InvalidQuery {
@@ -398,7 +511,75 @@ impl TokenKind {
     }
 }
 
+fn parse_slop(digits: &str) -> Result<u32, MemvidError> {
+    match digits.parse::<u32>() {
+        Ok(slop) if slop <= MAX_PROXIMITY_SLOP => Ok(slop),
+        _ => Err(MemvidError::InvalidQuery {
+            reason: format!(
+                "proximity distance must be a number between 0 and {MAX_PROXIMITY_SLOP}"
+            ),
+        }),
+    }
+}
+
+/// Words of a phrase as the in-memory matcher sees them.
+fn phrase_words(phrase: &str) -> Vec<String> {
+    phrase
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn near_operand_words(expr: &Expr) -> Result<Vec<String>, MemvidError> {
+    match expr {
+        Expr::Term(Term::Text(TextTerm::Word(word))) if !word.is_empty() => Ok(vec![word.clone()]),
+        Expr::Term(Term::Text(TextTerm::Phrase(phrase))) if !phrase_words(phrase).is_empty() => {
+            Ok(phrase_words(phrase))
+        }
+        _ => Err(MemvidError::InvalidQuery {
+            reason: "NEAR operands must be words or quoted phrases".into(),
+        }),
+    }
+}
+
 impl TextTerm {
+    /// Parse a bare word token, recognising the `term~N` fuzzy suffix before the
+    /// usual punctuation cleanup strips it.
+    fn from_token(word: String) -> Result<Self, MemvidError> {
+        let Some((term, suffix)) = word.rsplit_once('~') else {
+            return Ok(Self::from_word(word));
+        };
+        let distance = match suffix {
+            "" => DEFAULT_FUZZY_DISTANCE,
+            digits if digits.chars().all(|c| c.is_ascii_digit()) => match digits.parse::<u8>() {
+                Ok(distance) if distance <= MAX_FUZZY_DISTANCE => distance,
+                _ => {
+                    return Err(MemvidError::InvalidQuery {
+                        reason: format!(
+                            "fuzzy edit distance must be between 0 and {MAX_FUZZY_DISTANCE}"
+                        ),
+                    });
+                }
+            },
+            // Not a fuzzy suffix (e.g. "a~b"): keep the old word handling.
+            _ => return Ok(Self::from_word(word)),
+        };
+        let lower = term.to_ascii_lowercase();
+        let cleaned = lower.trim_matches(|c: char| !c.is_alphanumeric());
+        if cleaned.is_empty() {
+            return Ok(TextTerm::Word(String::new()));
+        }
+        if distance == 0 {
+            return Ok(TextTerm::Word(cleaned.to_string()));
+        }
+        Ok(TextTerm::Fuzzy(FuzzyTerm {
+            term: cleaned.to_string(),
+            distance,
+        }))
+    }
+
     fn from_word(word: String) -> Self {
         // Strip trailing question marks - they're punctuation, not wildcards
         // Users type "What is machine?" as a question, not a wildcard pattern
@@ -408,6 +589,16 @@ impl TextTerm {
         // Strip leading/trailing punctuation that won't tokenize well
         let cleaned = trimmed.trim_matches(|c: char| !c.is_alphanumeric() && c != '*' && c != '?');
 
+        // A lone trailing star is a plain prefix query and skips the regex machinery
+        if let Some(prefix) = cleaned.strip_suffix('*') {
+            if !prefix.is_empty()
+                && !prefix.contains(['*', '?'])
+                && prefix.chars().any(char::is_alphanumeric)
+            {
+                return TextTerm::Prefix(prefix.to_string());
+            }
+        }
+
         // Only treat * or ? as wildcards when they're NOT at the end
         // (i.e., "mach?ne" or "mach*" are wildcards, but "machine?" is just "machine")
         if cleaned.contains('*') || cleaned.contains('?') {
@@ -431,26 +622,51 @@ impl FieldTerm {
             "track" => Ok(FieldTerm::Track(normalized)),
             "tag" => Ok(FieldTerm::Tag(normalized)),
             "label" => Ok(FieldTerm::Label(normalized)),
-            _ => Err(MemvidError::InvalidQuery {
-                reason: format!("unsupported field: {field}"),
-            }),
+            "title" => Ok(FieldTerm::Title(normalized)),
+            _ => match metadata_key(field) {
+                Some(key) => Ok(FieldTerm::Meta {
+                    key: key.to_string(),
+                    value: normalized,
+                }),
+                None => Err(MemvidError::InvalidQuery {
+                    reason: format!("unsupported field: {field}"),
+                }),
+            },
         }
     }
 
-    fn from_date_range(field: &str, start: &str, end: &str) -> Result<Self, MemvidError> {
-        if field != "date" {
+    fn from_range(field: &str, start: &str, end: &str) -> Result<Self, MemvidError> {
+        if field == "date" {
+            let range = DateRange {
+                start: parse_date_value(start),
+                end: parse_date_value(end),
+            };
+            return Ok(FieldTerm::DateRange(range));
+        }
+        let Some(key) = metadata_key(field) else {
             return Err(MemvidError::InvalidQuery {
-                reason: format!("unexpected field for date range: {field}"),
+                reason: format!("unexpected field for range: {field}"),
             });
-        }
-        let range = DateRange {
-            start: parse_date_value(start),
-            end: parse_date_value(end),
         };
-        Ok(FieldTerm::DateRange(range))
+        let bound = |value: &str| {
+            let trimmed = value.trim_matches('"');
+            (!trimmed.is_empty() && trimmed != "*").then(|| trimmed.to_ascii_lowercase())
+        };
+        Ok(FieldTerm::MetaRange {
+            key: key.to_string(),
+            start: bound(start),
+            end: bound(end),
+        })
     }
 }
 
+/// Metadata key addressed by a `meta.<key>` field name.
+pub(crate) fn metadata_key(field: &str) -> Option<&str> {
+    field
+        .strip_prefix(METADATA_FIELD_PREFIX)
+        .filter(|key| !key.is_empty())
+}
+
 pub(crate) fn parse_date_value(value: &str) -> Option<i64> {
     let trimmed = value.trim_matches('"');
     if trimmed.is_empty() || trimmed == "*" {
@@ -539,6 +755,35 @@ mod tests {
         parse_query("date:[2024-01-01 TO 2024-12-31] AND rust").expect("parse");
     }
 
+    #[test]
+    fn parses_title_and_metadata_fields() {
+        let parsed =
+            parse_query("title:Roadmap meta.customer:ACME meta.priority:[3 TO *]").expect("parse");
+        let Expr::And(terms) = parsed.expr else {
+            panic!("expected conjunction");
+        };
+        assert!(
+            matches!(&terms[0], Expr::Term(Term::Field(FieldTerm::Title(value))) if value == "roadmap")
+        );
+        assert!(matches!(
+            &terms[1],
+            Expr::Term(Term::Field(FieldTerm::Meta { key, value })) if key == "customer" && value == "acme"
+        ));
+        assert!(matches!(
+            &terms[2],
+            Expr::Term(Term::Field(FieldTerm::MetaRange { key, start: Some(start), end: None }))
+                if key == "priority" && start == "3"
+        ));
+
+        // A bare `meta.` prefix is not a field.
+        let parsed = parse_query("meta.:x").expect("parse");
+        assert!(matches!(parsed.expr, Expr::Term(Term::Text(_))));
+        assert!(
+            parse_query("tag:[a TO b]").is_ok(),
+            "ranges only apply to date and meta.*"
+        );
+    }
+
     #[test]
     fn unknown_field_colon_treated_as_word() {
         // "IRR:" should NOT be treated as a field query - it's just text with a colon
@@ -581,6 +826,39 @@ mod tests {
         assert!(parse_query("LP IRR - year 1").is_ok());
     }
 
+    #[test]
+    fn parses_phrase_slop_and_near() {
+        let parsed = parse_query("\"budget approval\"~5").expect("parse");
+        assert!(matches!(
+            parsed.expr,
+            Expr::Term(Term::Text(TextTerm::Proximity(ProximityTerm { ref words, slop: 5, unordered: false })))
+                if words == &["budget", "approval"]
+        ));
+
+        let parsed = parse_query("budget NEAR/3 \"final approval\" AND q3").expect("parse");
+        let Expr::And(terms) = parsed.expr else {
+            panic!("NEAR binds tighter than AND");
+        };
+        assert!(matches!(
+            &terms[0],
+            Expr::Term(Term::Text(TextTerm::Proximity(ProximityTerm { words, slop: 3, unordered: true })))
+                if words == &["budget", "final", "approval"]
+        ));
+
+        assert!(matches!(
+            parse_query("\"budget\"~2").expect("parse").expr,
+            Expr::Term(Term::Text(TextTerm::Phrase(_)))
+        ));
+        assert!(matches!(
+            parse_query("near/far").expect("parse").expr,
+            Expr::Term(Term::Text(TextTerm::Word(_)))
+        ));
+        assert!(parse_query("\"a b\"~").is_err());
+        assert!(parse_query("a NEAR/500 b").is_err());
+        assert!(parse_query("a NEAR/2 b NEAR/2 c").is_err());
+        assert!(parse_query("tag:x NEAR/2 b").is_err());
+    }
+
     #[test]
     fn text_term_filters_punctuation() {
         // Punctuation-only words should produce empty Word
@@ -691,6 +969,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_fuzzy_terms() {
+        let parsed = parse_query("kubernets~1").expect("parse");
+        match parsed.expr {
+            Expr::Term(Term::Text(TextTerm::Fuzzy(fuzzy))) => {
+                assert_eq!(fuzzy.term, "kubernets");
+                assert_eq!(fuzzy.distance, 1);
+            }
+            other => panic!("expected fuzzy term, got {other:?}"),
+        }
+
+        match parse_query("Roam~").expect("parse").expr {
+            Expr::Term(Term::Text(TextTerm::Fuzzy(fuzzy))) => {
+                assert_eq!(fuzzy.term, "roam");
+                assert_eq!(fuzzy.distance, DEFAULT_FUZZY_DISTANCE);
+            }
+            other => panic!("expected fuzzy term, got {other:?}"),
+        }
+
+        assert!(matches!(
+            parse_query("exact~0").expect("parse").expr,
+            Expr::Term(Term::Text(TextTerm::Word(ref word))) if word == "exact"
+        ));
+        assert!(parse_query("kubernets~3").is_err());
+    }
+
+    #[test]
+    fn trailing_star_is_prefix_not_wildcard() {
+        assert!(matches!(
+            TextTerm::from_word("Kube*".to_string()),
+            TextTerm::Prefix(ref prefix) if prefix == "kube"
+        ));
+        assert!(matches!(
+            TextTerm::from_word("ku*e*".to_string()),
+            TextTerm::Wildcard(_)
+        ));
+        assert!(matches!(
+            TextTerm::from_word("mach?ne".to_string()),
+            TextTerm::Wildcard(_)
+        ));
+    }
+
     #[test]
     fn parentheses_preserve_implicit_and() {
         // (machine learning) python actually flattens to And([machine, learning, python])
@@ -0,0 +1,374 @@
+//! Per-memory spelling dictionary for "did you mean" query suggestions.
+//!
+//! Uses the SymSpell symmetric-delete scheme: every dictionary word is indexed
+//! under all of its deletions up to [`MAX_SUGGEST_DISTANCE`], so a lookup only
+//! needs the deletions of the misspelled word instead of a scan. The optional
+//! `symspell` crate ships fixed English dictionaries; this one is built from the
+//! memory's own text so product names and jargon are suggested too.
+//!
+//! Word counts are updated from each commit's added and deleted documents and
+//! persisted next to the lexical index; the deletion index is rebuilt in
+//! memory on the first lookup.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::{bounded_edit_distance, contains_cjk};
+use crate::{MemvidError, Result};
+
+/// Largest edit distance considered when suggesting a correction.
+pub(crate) const MAX_SUGGEST_DISTANCE: usize = 2;
+/// Words shorter than this are never corrected (too many neighbours).
+const MIN_SUGGEST_LEN: usize = 3;
+/// Words up to this length only get single-edit corrections.
+const SHORT_WORD_LEN: usize = 5;
+/// Upper bound on distinct dictionary words; the least frequent are dropped
+/// when the dictionary is saved, and new words are ignored while it is full.
+const MAX_DICTIONARY_WORDS: usize = 100_000;
+/// Longer tokens are almost always identifiers or hashes, not words.
+const MAX_WORD_LEN: usize = 32;
+/// Upper bound on allocations while decoding a saved dictionary.
+const SPELLING_DECODE_LIMIT: usize = 64 * 1024 * 1024;
+
+/// Word-frequency dictionary with a deletion index for fast lookups.
+#[derive(Debug, Default)]
+pub(crate) struct SpellingDictionary {
+    words: Vec<(String, u64)>,
+    ids: HashMap<String, u32>,
+    deletes: OnceLock<HashMap<String, Vec<u32>>>,
+}
+
+/// Word count changes from documents added and deleted since the last commit.
+#[derive(Debug, Default)]
+pub(crate) struct SpellingDelta {
+    counts: HashMap<String, i64>,
+}
+
+impl SpellingDelta {
+    pub(crate) fn add_text(&mut self, text: &str) {
+        for word in dictionary_words(text) {
+            *self.counts.entry(word).or_default() += 1;
+        }
+    }
+
+    pub(crate) fn remove_text(&mut self, text: &str) {
+        for word in dictionary_words(text) {
+            *self.counts.entry(word).or_default() -= 1;
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.counts.clear();
+    }
+}
+
+impl SpellingDictionary {
+    /// Build a dictionary from raw document texts.
+    #[cfg(test)]
+    pub(crate) fn from_texts<'a>(texts: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut delta = SpellingDelta::default();
+        for text in texts {
+            delta.add_text(text);
+        }
+        let mut dictionary = Self::default();
+        dictionary.apply(&mut delta);
+        dictionary
+    }
+
+    fn from_counts(mut words: Vec<(String, u64)>) -> Self {
+        words.retain(|(_, count)| *count > 0);
+        words.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        words.truncate(MAX_DICTIONARY_WORDS);
+        let ids = words
+            .iter()
+            .enumerate()
+            .filter_map(|(id, (word, _))| Some((word.clone(), u32::try_from(id).ok()?)))
+            .collect();
+        Self {
+            words,
+            ids,
+            deletes: OnceLock::new(),
+        }
+    }
+
+    /// Fold `delta` into the word counts, leaving it empty.
+    pub(crate) fn apply(&mut self, delta: &mut SpellingDelta) {
+        for (word, change) in delta.counts.drain() {
+            if let Some(&id) = self.ids.get(&word) {
+                let count = &mut self.words[id as usize].1;
+                *count = count.saturating_add_signed(change);
+                continue;
+            }
+            let Ok(count) = u64::try_from(change) else {
+                continue;
+            };
+            if count == 0 || self.words.len() >= MAX_DICTIONARY_WORDS {
+                continue;
+            }
+            let Ok(id) = u32::try_from(self.words.len()) else {
+                continue;
+            };
+            if let Some(deletes) = self.deletes.get_mut() {
+                for variant in deletions(&word, MAX_SUGGEST_DISTANCE) {
+                    deletes.entry(variant).or_default().push(id);
+                }
+            }
+            self.ids.insert(word.clone(), id);
+            self.words.push((word, count));
+        }
+    }
+
+    /// Serialize the word counts, most frequent first.
+    pub(crate) fn encode(&self) -> Result<Vec<u8>> {
+        let mut words: Vec<(&str, u64)> = self
+            .words
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(word, count)| (word.as_str(), *count))
+            .collect();
+        words.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        words.truncate(MAX_DICTIONARY_WORDS);
+        bincode::serde::encode_to_vec(&words, bincode::config::standard()).map_err(|err| {
+            MemvidError::Tantivy {
+                reason: format!("failed to encode spelling dictionary: {err}"),
+            }
+        })
+    }
+
+    pub(crate) fn decode(bytes: &[u8]) -> Result<Self> {
+        let config = bincode::config::standard().with_limit::<SPELLING_DECODE_LIMIT>();
+        let (words, _): (Vec<(String, u64)>, _) = bincode::serde::decode_from_slice(bytes, config)
+            .map_err(|err| MemvidError::Tantivy {
+                reason: format!("failed to decode spelling dictionary: {err}"),
+            })?;
+        Ok(Self::from_counts(words))
+    }
+
+    /// Whether `word` (lowercase) occurs in the indexed text.
+    pub(crate) fn contains(&self, word: &str) -> bool {
+        self.ids
+            .get(word)
+            .is_some_and(|&id| self.words[id as usize].1 > 0)
+    }
+
+    fn deletes(&self) -> &HashMap<String, Vec<u32>> {
+        self.deletes.get_or_init(|| {
+            let mut deletes: HashMap<String, Vec<u32>> = HashMap::new();
+            for (id, (word, _)) in self.words.iter().enumerate() {
+                let Ok(id) = u32::try_from(id) else { break };
+                for variant in deletions(word, MAX_SUGGEST_DISTANCE) {
+                    deletes.entry(variant).or_default().push(id);
+                }
+            }
+            deletes
+        })
+    }
+
+    /// Best correction for `word`: smallest edit distance first, then the most
+    /// frequent candidate. Returns `None` for known or uncorrectable words.
+    pub(crate) fn suggest(&self, word: &str) -> Option<&str> {
+        let length = word.chars().count();
+        if length < MIN_SUGGEST_LEN || self.contains(word) {
+            return None;
+        }
+        let max = if length <= SHORT_WORD_LEN {
+            1
+        } else {
+            MAX_SUGGEST_DISTANCE
+        };
+
+        let deletes = self.deletes();
+        let mut best: Option<(usize, u64, u32)> = None;
+        for variant in deletions(word, max) {
+            let Some(candidates) = deletes.get(&variant) else {
+                continue;
+            };
+            for &id in candidates {
+                let (candidate, count) = &self.words[id as usize];
+                if *count == 0 {
+                    continue;
+                }
+                let Some(distance) = bounded_edit_distance(word, candidate, max) else {
+                    continue;
+                };
+                let better = match best {
+                    None => true,
+                    Some((best_distance, best_count, best_id)) => {
+                        (distance, std::cmp::Reverse(*count), candidate)
+                            < (
+                                best_distance,
+                                std::cmp::Reverse(best_count),
+                                &self.words[best_id as usize].0,
+                            )
+                    }
+                };
+                if better {
+                    best = Some((distance, *count, id));
+                }
+            }
+        }
+        best.map(|(_, _, id)| self.words[id as usize].0.as_str())
+    }
+
+    /// Rewrite the plain words of `query` with their corrections.
+    ///
+    /// Operators (including `NEAR/N`), field filters, quoted phrases, wildcards and fuzzy terms are
+    /// left untouched. Returns `None` when nothing would change.
+    pub(crate) fn suggest_query(&self, query: &str) -> Option<String> {
+        let mut changed = false;
+        let mut in_phrase = false;
+        let mut rewritten = Vec::new();
+        for token in query.split_whitespace() {
+            let quotes = token.matches('"').count();
+            let skip = in_phrase
+                || quotes > 0
+                || token.contains([':', '*', '?', '~'])
+                || matches!(token, "AND" | "and" | "OR" | "or" | "NOT" | "not")
+                || token
+                    .get(..5)
+                    .is_some_and(|head| head.eq_ignore_ascii_case("NEAR/"));
+            if quotes % 2 == 1 {
+                in_phrase = !in_phrase;
+            }
+            if skip {
+                rewritten.push(token.to_string());
+                continue;
+            }
+
+            let start = token
+                .find(|ch: char| ch.is_alphanumeric())
+                .unwrap_or(token.len());
+            let end = token
+                .char_indices()
+                .rev()
+                .find(|(_, ch)| ch.is_alphanumeric())
+                .map_or(start, |(pos, ch)| pos + ch.len_utf8());
+            let core = &token[start..end];
+            match self.suggest(&core.to_lowercase()) {
+                Some(correction) => {
+                    changed = true;
+                    rewritten.push(format!("{}{correction}{}", &token[..start], &token[end..]));
+                }
+                None => rewritten.push(token.to_string()),
+            }
+        }
+        changed.then(|| rewritten.join(" "))
+    }
+}
+
+/// Lowercase words from `text` that are worth keeping in the dictionary.
+fn dictionary_words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| {
+            let length = word.chars().count();
+            (2..=MAX_WORD_LEN).contains(&length)
+                && word.chars().any(char::is_alphabetic)
+                && !contains_cjk(word)
+        })
+        .map(str::to_lowercase)
+}
+
+/// `word` plus every string reachable by deleting up to `max` characters.
+fn deletions(word: &str, max: usize) -> Vec<String> {
+    let mut all = vec![word.to_string()];
+    let mut frontier = vec![word.to_string()];
+    for _ in 0..max {
+        let mut next = Vec::new();
+        for current in &frontier {
+            let chars: Vec<char> = current.chars().collect();
+            if chars.len() <= 1 {
+                continue;
+            }
+            for skip in 0..chars.len() {
+                let variant: String = chars
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(idx, ch)| (idx != skip).then_some(*ch))
+                    .collect();
+                next.push(variant);
+            }
+        }
+        next.sort_unstable();
+        next.dedup();
+        all.extend(next.iter().cloned());
+        frontier = next;
+    }
+    all.sort_unstable();
+    all.dedup();
+    all
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary() -> SpellingDictionary {
+        SpellingDictionary::from_texts([
+            "Kubernetes schedules pods onto nodes.",
+            "Kubernetes clusters run containers; kubectl talks to the API server.",
+            "Rust ownership and borrowing rules.",
+        ])
+    }
+
+    #[test]
+    fn edit_distance_counts_transpositions_once() {
+        assert_eq!(bounded_edit_distance("kubernets", "kubernetes", 2), Some(1));
+        assert_eq!(bounded_edit_distance("rsut", "rust", 2), Some(1));
+        assert_eq!(bounded_edit_distance("", "abc", 3), Some(3));
+        assert_eq!(bounded_edit_distance("container", "kubectl", 2), None);
+    }
+
+    #[test]
+    fn suggests_closest_known_word() {
+        let dict = dictionary();
+        assert_eq!(dict.suggest("kubernets"), Some("kubernetes"));
+        assert_eq!(dict.suggest("contianers"), Some("containers"));
+        assert_eq!(dict.suggest("kubernetes"), None, "known words are kept");
+        assert_eq!(dict.suggest("xyzzyplugh"), None);
+        assert_eq!(dict.suggest("ru"), None, "short words are not corrected");
+    }
+
+    #[test]
+    fn suggest_query_keeps_syntax() {
+        let dict = dictionary();
+        assert_eq!(
+            dict.suggest_query("kubernets AND tag:ops \"rust ownershp\" pods? NEAR/2 nodes"),
+            Some("kubernetes AND tag:ops \"rust ownershp\" pods? NEAR/2 nodes".to_string())
+        );
+        assert_eq!(
+            dict.suggest_query("(Ownershp) borrowing"),
+            Some("(ownership) borrowing".to_string())
+        );
+        assert_eq!(dict.suggest_query("rust pods"), None);
+    }
+
+    #[test]
+    fn deltas_update_counts_and_survive_encoding() {
+        let mut dict = dictionary();
+        assert_eq!(dict.suggest("contianers"), Some("containers"));
+
+        let mut delta = SpellingDelta::default();
+        delta.remove_text("Kubernetes clusters run containers; kubectl talks to the API server.");
+        delta.add_text("Terraform provisions clusters.");
+        dict.apply(&mut delta);
+        assert!(delta.is_empty());
+        assert_eq!(
+            dict.suggest("contianers"),
+            None,
+            "deleted words are forgotten"
+        );
+        assert_eq!(dict.suggest("terrafrom"), Some("terraform"));
+        assert!(
+            dict.contains("kubernetes"),
+            "still used by another document"
+        );
+
+        let decoded = SpellingDictionary::decode(&dict.encode().unwrap()).unwrap();
+        assert!(!decoded.contains("containers"));
+        assert_eq!(decoded.suggest("terrafrom"), Some("terraform"));
+    }
+}
@@ -0,0 +1,90 @@
+//! Query-time synonym expansion over the parsed query AST.
+
+use super::parser::{Expr, ParsedQuery, Term, TextTerm};
+use crate::types::{QueryExpansion, SynonymTrack};
+
+impl ParsedQuery {
+    /// Replace each word or phrase that has synonyms with an OR over the
+    /// original and its synonyms. Returns the rewrites in query order, one
+    /// per distinct term.
+    pub(crate) fn expand_synonyms(&mut self, track: &SynonymTrack) -> Vec<QueryExpansion> {
+        let mut expansions = Vec::new();
+        if !track.is_empty() {
+            expand_expr(&mut self.expr, track, &mut expansions);
+        }
+        expansions
+    }
+}
+
+fn expand_expr(expr: &mut Expr, track: &SynonymTrack, expansions: &mut Vec<QueryExpansion>) {
+    match expr {
+        Expr::Or(children) | Expr::And(children) => {
+            for child in children {
+                expand_expr(child, track, expansions);
+            }
+        }
+        Expr::Not(inner) => expand_expr(inner, track, expansions),
+        Expr::Term(Term::Text(TextTerm::Word(term) | TextTerm::Phrase(term))) => {
+            let synonyms = track.expansions_for(term);
+            if synonyms.is_empty() {
+                return;
+            }
+            let term = term.to_lowercase();
+            if !expansions.iter().any(|expansion| expansion.term == term) {
+                expansions.push(QueryExpansion {
+                    term,
+                    expanded_to: synonyms.clone(),
+                });
+            }
+            let mut alternatives = Vec::with_capacity(synonyms.len() + 1);
+            alternatives.push(std::mem::replace(expr, Expr::Or(Vec::new())));
+            alternatives.extend(synonyms.into_iter().map(|synonym| {
+                let term = if synonym.contains(' ') {
+                    TextTerm::Phrase(synonym)
+                } else {
+                    TextTerm::Word(synonym)
+                };
+                Expr::Term(Term::Text(term))
+            }));
+            *expr = Expr::Or(alternatives);
+        }
+        Expr::Term(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::parse_query;
+
+    #[test]
+    fn expands_words_and_phrases_into_alternatives() {
+        let mut track = SynonymTrack::default();
+        track.add_group(["k8s", "kubernetes"]).unwrap();
+        track.add_expansion("pto", ["paid time off"]).unwrap();
+
+        let mut parsed = parse_query("k8s AND pto NOT \"k8s\" budget").unwrap();
+        let expansions = parsed.expand_synonyms(&track);
+        assert_eq!(
+            expansions,
+            [
+                QueryExpansion {
+                    term: "k8s".into(),
+                    expanded_to: vec!["kubernetes".into()],
+                },
+                QueryExpansion {
+                    term: "pto".into(),
+                    expanded_to: vec!["paid time off".into()],
+                },
+            ]
+        );
+
+        let mut tokens = parsed.text_tokens();
+        tokens.sort();
+        tokens.dedup();
+        assert_eq!(
+            tokens,
+            ["budget", "k8s", "kubernetes", "paid time off", "pto"]
+        );
+    }
+}
@@ -1,14 +1,31 @@
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Bound;
+
 use super::query;
-use super::schema::{build_schema, initialise_tokenizer};
+use super::schema::{
+    build_schema, initialise_tokenizer, language_field_name, language_tokenizer,
+    metadata_field_name,
+};
 use super::util::to_search_value;
-use crate::search::parser::ParsedQuery;
-use crate::types::{Frame, FrameId};
+use crate::search::parser::{ParsedQuery, parse_date_value};
+use crate::search::{
+    AnalysedToken, SpellingDelta, SpellingDictionary, frame_language, metadata_value,
+};
+use crate::types::{
+    ContentLanguage, Frame, FrameId, METADATA_FIELD_PREFIX, MetadataFieldType,
+    SYNONYMS_METADATA_KEY, ScoreExplanation, SearchSchema,
+};
 use crate::{MemvidError, Result};
 use blake3::{Hasher, hash};
-use tantivy::collector::TopDocs;
+use tantivy::collector::{DocSetCollector, TopDocs};
+use tantivy::columnar::Column;
 use tantivy::indexer::IndexWriter;
-use tantivy::schema::{Field, OwnedValue, Schema, TantivyDocument};
-use tantivy::{Index, IndexReader, Term, doc};
+use tantivy::query::{BooleanQuery, ConstScoreQuery, Occur, Query, RangeQuery, TermQuery};
+use tantivy::schema::{
+    Field, FieldEntry, FieldType, IndexRecordOption, OwnedValue, Schema, TantivyDocument,
+};
+use tantivy::{DateTime, Index, IndexReader, Term, doc};
 use tempfile::TempDir;
 
 /// Tantivy-backed search index used when the `lex` feature is enabled.
@@ -17,6 +34,23 @@ pub struct TantivyEngine {
     pub(super) index: Index,
     pub(super) _schema: Schema,
     pub(super) content: Field,
+    /// Content fields for languages with their own analyzer. Empty in indexes
+    /// written before language routing; those index everything into
+    /// `content` until the next rebuild.
+    pub(super) languages: Vec<(ContentLanguage, Field)>,
+    /// Absent in indexes written before titles were indexed; those still match
+    /// `title:` through in-memory evaluation until the next rebuild.
+    pub(super) title: Option<Field>,
+    pub(super) title_boost: f32,
+    /// Unstemmed content words for prefix queries. Absent in indexes written
+    /// before it was added; those match prefixes against the stemmed content
+    /// fields until the next rebuild.
+    pub(super) words: Option<Field>,
+    /// Synonyms recorded on frames at ingestion. Absent in indexes written
+    /// before it was added.
+    pub(super) synonyms: Option<Field>,
+    /// Declared `extra_metadata` keys and the fields they are indexed into.
+    pub(super) metadata: BTreeMap<String, (Field, MetadataFieldType)>,
     pub(super) tags: Field,
     pub(super) labels: Field,
     pub(super) track: Field,
@@ -26,8 +60,19 @@ pub struct TantivyEngine {
     pub(super) index_writer: Option<IndexWriter>,
     pub(super) reader: IndexReader,
     pub(super) tokenizer: Option<String>,
+    /// "Did you mean" dictionary over committed documents. `None` for
+    /// indexes saved without one until it is first needed.
+    pub(super) spelling: Option<SpellingDictionary>,
+    /// Word count changes from documents added or deleted since the last commit.
+    pub(super) spelling_delta: SpellingDelta,
+    /// Whether `spelling` differs from the copy in the work directory.
+    pub(super) spelling_unsaved: bool,
 }
 
+/// Work-directory file holding the spelling dictionary, so it is embedded
+/// and restored together with the index segments.
+const SPELLING_FILE: &str = "memvid-spelling.bin";
+
 /// Search hit returned from Tantivy queries.
 pub struct TantivyDocHit {
     pub frame_id: u64,
@@ -51,30 +96,48 @@ pub struct TantivySegmentBlob {
 }
 
 impl TantivyEngine {
-    pub fn create() -> Result<Self> {
+    pub fn create(search_schema: &SearchSchema) -> Result<Self> {
         let dir = TempDir::new().map_err(|err| MemvidError::Tantivy {
             reason: format!("failed to allocate Tantivy work directory: {err}"),
         })?;
-        let schema = build_schema();
+        let schema = build_schema(search_schema);
         let index = Index::create_in_dir(dir.path(), schema.clone()).map_err(|err| {
             MemvidError::Tantivy {
                 reason: err.to_string(),
             }
         })?;
         initialise_tokenizer(&index);
-        Self::from_parts(dir, index, schema)
+        Self::from_parts(
+            dir,
+            index,
+            schema,
+            search_schema,
+            Some(SpellingDictionary::default()),
+        )
     }
 
-    pub fn open_from_dir(dir: TempDir) -> Result<Self> {
+    /// Open a materialised index. Fails when its fields do not match
+    /// `search_schema`, so the caller can rebuild it from frames.
+    pub fn open_from_dir(dir: TempDir, search_schema: &SearchSchema) -> Result<Self> {
         let index = Index::open_in_dir(dir.path()).map_err(|err| MemvidError::Tantivy {
             reason: err.to_string(),
         })?;
         initialise_tokenizer(&index);
         let schema = index.schema();
-        Self::from_parts(dir, index, schema)
+        // A missing or unreadable dictionary is rebuilt from the index on first use
+        let spelling = std::fs::read(dir.path().join(SPELLING_FILE))
+            .ok()
+            .and_then(|bytes| SpellingDictionary::decode(&bytes).ok());
+        Self::from_parts(dir, index, schema, search_schema, spelling)
     }
 
-    fn from_parts(dir: TempDir, index: Index, schema: Schema) -> Result<Self> {
+    fn from_parts(
+        dir: TempDir,
+        index: Index,
+        schema: Schema,
+        search_schema: &SearchSchema,
+        spelling: Option<SpellingDictionary>,
+    ) -> Result<Self> {
         let content = schema
             .get_field("content")
             .map_err(|err| MemvidError::Tantivy {
@@ -110,6 +173,44 @@ impl TantivyEngine {
             .map_err(|err| MemvidError::Tantivy {
                 reason: err.to_string(),
             })?;
+        let title = schema.get_field("title").ok();
+        let words = schema.get_field("content_words").ok();
+        let synonyms = schema.get_field("synonyms").ok();
+        let languages = ContentLanguage::ALL
+            .into_iter()
+            .filter(|&language| language != ContentLanguage::English)
+            .filter_map(|language| {
+                schema
+                    .get_field(language_field_name(language))
+                    .ok()
+                    .map(|field| (language, field))
+            })
+            .collect();
+
+        let mut metadata = BTreeMap::new();
+        for (key, &kind) in &search_schema.metadata_fields {
+            let field = schema.get_field(&metadata_field_name(key)).map_err(|err| {
+                MemvidError::Tantivy {
+                    reason: err.to_string(),
+                }
+            })?;
+            if !field_has_type(schema.get_field_entry(field), kind) {
+                return Err(MemvidError::Tantivy {
+                    reason: format!("metadata field `{key}` is not indexed as {}", kind.as_str()),
+                });
+            }
+            metadata.insert(key.clone(), (field, kind));
+        }
+        if let Some((_, entry)) = schema.fields().find(|(_, entry)| {
+            entry
+                .name()
+                .strip_prefix(METADATA_FIELD_PREFIX)
+                .is_some_and(|key| !search_schema.metadata_fields.contains_key(key))
+        }) {
+            return Err(MemvidError::Tantivy {
+                reason: format!("index has undeclared metadata field `{}`", entry.name()),
+            });
+        }
 
         let writer = index
             .writer(50_000_000)
@@ -125,6 +226,12 @@ impl TantivyEngine {
             index,
             _schema: schema,
             content,
+            languages,
+            title,
+            title_boost: search_schema.title_boost,
+            words,
+            synonyms,
+            metadata,
             tags,
             labels,
             track,
@@ -134,6 +241,9 @@ impl TantivyEngine {
             index_writer: Some(writer),
             reader,
             tokenizer: Some("memvid_default".to_string()),
+            spelling,
+            spelling_delta: SpellingDelta::default(),
+            spelling_unsaved: false,
         })
     }
 
@@ -158,12 +268,37 @@ impl TantivyEngine {
             })
     }
 
+    /// Adopt `search_schema` without reindexing when the index already has the
+    /// fields it needs (only the title boost differs). Returns `false` when the
+    /// index must be rebuilt.
+    pub(crate) fn try_apply_schema(&mut self, search_schema: &SearchSchema) -> bool {
+        let same_fields = self.title.is_some()
+            && self.words.is_some()
+            && self.synonyms.is_some()
+            && self.languages.len() == ContentLanguage::ALL.len() - 1
+            && self.metadata.len() == search_schema.metadata_fields.len()
+            && self
+                .metadata
+                .iter()
+                .all(|(key, (_, kind))| search_schema.metadata_field(key) == Some(*kind));
+        if same_fields {
+            self.title_boost = search_schema.title_boost;
+        }
+        same_fields
+    }
+
     pub fn add_frame(&mut self, frame: &Frame, content: &str) -> Result<()> {
         if content.trim().is_empty() {
             return Ok(());
         }
+        if self.spelling.is_some() {
+            self.spelling_delta.add_text(content);
+        }
+        let content_field = frame_language(frame, content)
+            .and_then(|language| self.language_field(language))
+            .unwrap_or(self.content);
         let mut document = doc!(
-            self.content => content,
+            content_field => content,
             self.timestamp => frame.timestamp,
             self.frame_id => frame.id,
         );
@@ -179,6 +314,43 @@ impl TantivyEngine {
         if let Some(uri) = &frame.uri {
             document.add_text(self.uri, to_search_value(uri));
         }
+        if let Some(field) = self.words {
+            document.add_text(field, content);
+        }
+        if let (Some(field), Some(synonyms)) = (
+            self.synonyms,
+            frame.extra_metadata.get(SYNONYMS_METADATA_KEY),
+        ) {
+            document.add_text(field, synonyms);
+        }
+        if let (Some(field), Some(title)) = (self.title, &frame.title) {
+            document.add_text(field, title);
+        }
+        for (key, &(field, kind)) in &self.metadata {
+            let Some(value) = metadata_value(frame, key).map(str::trim) else {
+                continue;
+            };
+            // Values that do not parse as the declared type are left unindexed.
+            match kind {
+                MetadataFieldType::Keyword => document.add_text(field, to_search_value(value)),
+                MetadataFieldType::Text => document.add_text(field, value),
+                MetadataFieldType::I64 => {
+                    if let Ok(number) = value.parse::<i64>() {
+                        document.add_i64(field, number);
+                    }
+                }
+                MetadataFieldType::F64 => {
+                    if let Ok(number) = value.parse::<f64>() {
+                        document.add_f64(field, number);
+                    }
+                }
+                MetadataFieldType::Date => {
+                    if let Some(timestamp) = parse_date_value(value) {
+                        document.add_date(field, DateTime::from_timestamp_secs(timestamp));
+                    }
+                }
+            }
+        }
         self.writer_mut()?
             .add_document(document)
             .map_err(|err| MemvidError::Tantivy {
@@ -188,6 +360,11 @@ impl TantivyEngine {
     }
 
     pub fn delete_frame(&mut self, frame_id: FrameId) -> Result<()> {
+        if self.spelling.is_some() {
+            if let Some(text) = self.committed_content(frame_id)? {
+                self.spelling_delta.remove_text(&text);
+            }
+        }
         let term = Term::from_field_u64(self.frame_id, frame_id);
         if let Some(writer) = self.index_writer.as_mut() {
             writer.delete_term(term);
@@ -209,7 +386,7 @@ impl TantivyEngine {
         self.reader.reload().map_err(|err| MemvidError::Tantivy {
             reason: err.to_string(),
         })?;
-        Ok(())
+        self.commit_spelling()
     }
 
     /// Soft commit that makes documents searchable immediately without waiting for merge.
@@ -225,9 +402,54 @@ impl TantivyEngine {
         self.reader.reload().map_err(|err| MemvidError::Tantivy {
             reason: err.to_string(),
         })?;
+        self.commit_spelling()
+    }
+
+    /// Fold this commit's word count changes into the spelling dictionary and
+    /// save it to the work directory.
+    fn commit_spelling(&mut self) -> Result<()> {
+        let Some(spelling) = self.spelling.as_mut() else {
+            self.spelling_delta.clear();
+            return Ok(());
+        };
+        if !self.spelling_delta.is_empty() {
+            spelling.apply(&mut self.spelling_delta);
+            self.spelling_unsaved = true;
+        }
+        if self.spelling_unsaved {
+            let path = self.work_dir.path().join(SPELLING_FILE);
+            std::fs::write(&path, spelling.encode()?).map_err(|err| MemvidError::Tantivy {
+                reason: format!("failed to write {}: {err}", path.display()),
+            })?;
+            self.spelling_unsaved = false;
+        }
         Ok(())
     }
 
+    /// Stored text of `frame_id`'s committed document, if any.
+    fn committed_content(&self, frame_id: FrameId) -> Result<Option<String>> {
+        let searcher = self.reader.searcher();
+        let by_id = TermQuery::new(
+            Term::from_field_u64(self.frame_id, frame_id),
+            IndexRecordOption::Basic,
+        );
+        let Some((_, address)) = searcher
+            .search(&by_id, &TopDocs::with_limit(1))
+            .map_err(|err| MemvidError::Tantivy {
+                reason: err.to_string(),
+            })?
+            .into_iter()
+            .next()
+        else {
+            return Ok(None);
+        };
+        let document: TantivyDocument =
+            searcher.doc(address).map_err(|err| MemvidError::Tantivy {
+                reason: err.to_string(),
+            })?;
+        Ok(self.stored_content(&document))
+    }
+
     /// Add frame and make it searchable immediately via soft commit.
     /// Returns Ok(true) if the frame was indexed, Ok(false) if skipped (empty content).
     #[allow(dead_code)]
@@ -241,6 +463,9 @@ impl TantivyEngine {
     }
 
     pub fn reset(&mut self) -> Result<()> {
+        self.spelling = Some(SpellingDictionary::default());
+        self.spelling_delta.clear();
+        self.spelling_unsaved = true;
         let mut writer = self.take_writer()?;
         writer
             .delete_all_documents()
@@ -259,7 +484,7 @@ impl TantivyEngine {
         self.reader.reload().map_err(|err| MemvidError::Tantivy {
             reason: err.to_string(),
         })?;
-        Ok(())
+        self.commit_spelling()
     }
 
     pub fn search_documents(
@@ -268,15 +493,15 @@ impl TantivyEngine {
         uri_filter: Option<&str>,
         scope_filter: Option<&str>,
         frame_filter: Option<&[u64]>,
+        watermark: FrameId,
         limit: usize,
     ) -> Result<Vec<TantivyDocHit>> {
-        if let Some(ids) = frame_filter {
-            if ids.is_empty() {
-                return Ok(Vec::new());
-            }
+        if watermark == 0 || frame_filter.is_some_and(<[u64]>::is_empty) {
+            return Ok(Vec::new());
         }
 
-        let query = query::build_root_query(self, parsed, uri_filter, scope_filter, frame_filter)?;
+        let root = query::build_root_query(self, parsed, uri_filter, scope_filter, frame_filter)?;
+        let query = self.scored_below_watermark(root, watermark);
         let doc_limit = limit.max(1);
         let searcher = self.reader.searcher();
         let top_docs = searcher
@@ -290,28 +515,8 @@ impl TantivyEngine {
                 searcher.doc(address).map_err(|err| MemvidError::Tantivy {
                     reason: err.to_string(),
                 })?;
-            let frame_id = match document.get_first(self.frame_id) {
-                Some(value) => match OwnedValue::from(value) {
-                    OwnedValue::U64(id) => id,
-                    _ => {
-                        return Err(MemvidError::Tantivy {
-                            reason: "tantivy doc missing frame_id".into(),
-                        });
-                    }
-                },
-                None => {
-                    return Err(MemvidError::Tantivy {
-                        reason: "tantivy doc missing frame_id".into(),
-                    });
-                }
-            };
-            let content = match document.get_first(self.content) {
-                Some(value) => match OwnedValue::from(value) {
-                    OwnedValue::Str(text) => text,
-                    _ => String::new(),
-                },
-                None => String::new(),
-            };
+            let frame_id = self.stored_frame_id(&document)?;
+            let content = self.stored_content(&document).unwrap_or_default();
             results.push(TantivyDocHit {
                 frame_id,
                 score,
@@ -321,6 +526,114 @@ impl TantivyEngine {
         Ok(results)
     }
 
+    /// Frame ids below `watermark` of every document matching `parsed`,
+    /// unranked and deduplicated. Facets count over this set rather than a
+    /// page of hits.
+    pub(crate) fn matching_frame_ids(
+        &self,
+        parsed: &ParsedQuery,
+        uri_filter: Option<&str>,
+        scope_filter: Option<&str>,
+        frame_filter: Option<&[u64]>,
+        watermark: FrameId,
+    ) -> Result<Vec<FrameId>> {
+        if watermark == 0 || frame_filter.is_some_and(<[u64]>::is_empty) {
+            return Ok(Vec::new());
+        }
+        let root = query::build_root_query(self, parsed, uri_filter, scope_filter, frame_filter)?;
+        let query = self.scored_below_watermark(root, watermark);
+        let searcher = self.reader.searcher();
+        let addresses =
+            searcher
+                .search(&query, &DocSetCollector)
+                .map_err(|err| MemvidError::Tantivy {
+                    reason: err.to_string(),
+                })?;
+        // Read ids from the fast field; stored documents carry the full text
+        let mut columns: HashMap<u32, Column<u64>> = HashMap::new();
+        let mut frame_ids = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            let column = match columns.entry(address.segment_ord) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(
+                    searcher
+                        .segment_reader(address.segment_ord)
+                        .fast_fields()
+                        .u64("frame_id")
+                        .map_err(|err| MemvidError::Tantivy {
+                            reason: err.to_string(),
+                        })?,
+                ),
+            };
+            if let Some(frame_id) = column.first(address.doc_id) {
+                frame_ids.push(frame_id);
+            }
+        }
+        frame_ids.sort_unstable();
+        frame_ids.dedup();
+        Ok(frame_ids)
+    }
+
+    /// BM25 breakdown of `frame_id`'s document for `parsed`, or `None` when
+    /// the frame is not indexed or its document does not match.
+    pub(crate) fn explain_frame(
+        &self,
+        parsed: &ParsedQuery,
+        frame_id: FrameId,
+    ) -> Result<Option<ScoreExplanation>> {
+        let searcher = self.reader.searcher();
+        let by_id = TermQuery::new(
+            Term::from_field_u64(self.frame_id, frame_id),
+            IndexRecordOption::Basic,
+        );
+        let Some((_, address)) = searcher
+            .search(&by_id, &TopDocs::with_limit(1))
+            .map_err(|err| MemvidError::Tantivy {
+                reason: err.to_string(),
+            })?
+            .into_iter()
+            .next()
+        else {
+            return Ok(None);
+        };
+        let query = query::build_root_query(self, parsed, None, None, None)?;
+        // Tantivy reports a non-matching document as an error
+        let Ok(explanation) = query.explain(&searcher, address) else {
+            return Ok(None);
+        };
+        let value = serde_json::to_value(&explanation).map_err(|err| MemvidError::Tantivy {
+            reason: err.to_string(),
+        })?;
+        serde_json::from_value(value)
+            .map(Some)
+            .map_err(|err| MemvidError::Tantivy {
+                reason: err.to_string(),
+            })
+    }
+
+    /// `root` restricted to frames below `watermark`, with `root`'s scores.
+    fn scored_below_watermark(&self, root: Box<dyn Query>, watermark: FrameId) -> BooleanQuery {
+        let bound: Box<dyn Query> =
+            Box::new(ConstScoreQuery::new(self.below_watermark(watermark), 0.0));
+        BooleanQuery::new(vec![(Occur::Must, root), (Occur::Must, bound)])
+    }
+
+    fn below_watermark(&self, watermark: FrameId) -> Box<dyn Query> {
+        Box::new(RangeQuery::new(
+            Bound::Unbounded,
+            Bound::Excluded(Term::from_field_u64(self.frame_id, watermark)),
+        ))
+    }
+
+    fn stored_frame_id(&self, document: &TantivyDocument) -> Result<FrameId> {
+        match document.get_first(self.frame_id).map(OwnedValue::from) {
+            Some(OwnedValue::U64(id)) => Ok(id),
+            _ => Err(MemvidError::Tantivy {
+                reason: "tantivy doc missing frame_id".into(),
+            }),
+        }
+    }
+
     pub fn snapshot_segments(&self) -> Result<TantivySnapshot> {
         let entries =
             std::fs::read_dir(self.work_dir.path()).map_err(|err| MemvidError::Tantivy {
@@ -384,15 +697,20 @@ impl TantivyEngine {
     }
 
     pub(crate) fn analyse_text(&self, text: &str) -> Vec<String> {
-        if let Some(name) = &self.tokenizer {
-            if let Some(mut analyzer) = self.index.tokenizers().get(name) {
-                let mut stream = analyzer.token_stream(text);
-                let mut tokens = Vec::new();
-                while stream.advance() {
-                    tokens.push(stream.token().text.to_string());
-                }
-                return tokens;
-            }
+        self.analyse_text_in(None, text)
+    }
+
+    /// Tokens of `text` under the analyzer that indexed frames in `language`.
+    pub(crate) fn analyse_text_in(
+        &self,
+        language: Option<ContentLanguage>,
+        text: &str,
+    ) -> Vec<String> {
+        if let Some(tokens) = self
+            .analyzer_for(language)
+            .and_then(|name| self.analyse_with(name, text))
+        {
+            return tokens;
         }
         if text.trim().is_empty() {
             Vec::new()
@@ -401,7 +719,215 @@ impl TantivyEngine {
         }
     }
 
+    /// Tokens of `text` under the registered analyzer `name`.
+    pub(super) fn analyse_with(&self, name: &str, text: &str) -> Option<Vec<String>> {
+        let mut analyzer = self.index.tokenizers().get(name)?;
+        let mut stream = analyzer.token_stream(text);
+        let mut tokens = Vec::new();
+        while stream.advance() {
+            tokens.push(stream.token().text.to_string());
+        }
+        Some(tokens)
+    }
+
+    /// Analyzer name for text in `language`; the default analyzer when the
+    /// index has no field of its own for it.
+    fn analyzer_for(&self, language: Option<ContentLanguage>) -> Option<&str> {
+        match self.indexed_language(language) {
+            Some(language) => Some(language_tokenizer(language)),
+            None => self.tokenizer.as_deref(),
+        }
+    }
+
+    /// Language whose field text in `language` is routed to, or `None` when
+    /// it goes to the default `content` field.
+    pub(crate) fn indexed_language(
+        &self,
+        language: Option<ContentLanguage>,
+    ) -> Option<ContentLanguage> {
+        language.filter(|&language| self.language_field(language).is_some())
+    }
+
+    /// `tokens` analysed once per content analyzer, for finding them in
+    /// frame text in whichever language it was indexed.
+    pub(crate) fn analyse_tokens(&self, tokens: &[String]) -> LanguageTokens {
+        let analyse = |language: Option<ContentLanguage>| -> Vec<String> {
+            tokens
+                .iter()
+                .flat_map(|token| self.analyse_text_in(language, token))
+                .collect()
+        };
+        let default = analyse(None);
+        // Stemmers that fold diacritics ("gebäude" -> "gebaud") yield forms
+        // that never occur verbatim, so the default forms are kept alongside.
+        let languages = self
+            .languages
+            .iter()
+            .map(|&(language, _)| {
+                let mut forms = analyse(Some(language));
+                for form in &default {
+                    if !forms.contains(form) {
+                        forms.push(form.clone());
+                    }
+                }
+                (language, forms)
+            })
+            .collect();
+        LanguageTokens { default, languages }
+    }
+
+    /// `content`, every per-language content field and the synonyms field,
+    /// with their analyzers.
+    pub(super) fn content_fields(&self) -> impl Iterator<Item = (Field, &'static str)> + '_ {
+        let default = language_tokenizer(ContentLanguage::English);
+        std::iter::once((self.content, default))
+            .chain(
+                self.languages
+                    .iter()
+                    .map(|&(language, field)| (field, language_tokenizer(language))),
+            )
+            .chain(self.synonyms.map(|field| (field, default)))
+    }
+
+    /// Content fields holding committed documents. `content` is always
+    /// searched; language fields no frame was routed to (and an empty
+    /// synonyms field) are skipped, so query terms do not fan out across
+    /// languages the memory does not contain.
+    pub(super) fn populated_content_fields(&self) -> Vec<(Field, &'static str)> {
+        let searcher = self.reader.searcher();
+        self.content_fields()
+            .filter(|&(field, _)| {
+                field == self.content
+                    || searcher.segment_readers().iter().any(|segment| {
+                        segment
+                            .inverted_index(field)
+                            .is_ok_and(|index| index.terms().num_terms() > 0)
+                    })
+            })
+            .collect()
+    }
+
+    fn language_field(&self, language: ContentLanguage) -> Option<Field> {
+        self.languages
+            .iter()
+            .find(|(candidate, _)| *candidate == language)
+            .map(|&(_, field)| field)
+    }
+
+    /// Stored text of a document, whichever content field it was routed to.
+    fn stored_content(&self, document: &TantivyDocument) -> Option<String> {
+        self.content_fields().find_map(|(field, _)| {
+            match document.get_first(field).map(OwnedValue::from) {
+                Some(OwnedValue::Str(text)) => Some(text),
+                _ => None,
+            }
+        })
+    }
+
+    /// Tokens of `text` with byte offsets, using the analyzer that indexed
+    /// frames in `language`.
+    pub(crate) fn analyse_with_offsets(
+        &self,
+        language: Option<ContentLanguage>,
+        text: &str,
+    ) -> Vec<AnalysedToken> {
+        let Some(mut analyzer) = self
+            .analyzer_for(language)
+            .and_then(|name| self.index.tokenizers().get(name))
+        else {
+            return Vec::new();
+        };
+        let mut stream = analyzer.token_stream(text);
+        let mut tokens = Vec::new();
+        while stream.advance() {
+            let token = stream.token();
+            tokens.push(AnalysedToken {
+                text: token.text.clone(),
+                start: token.offset_from,
+                end: token.offset_to,
+            });
+        }
+        tokens
+    }
+
+    /// Spelling dictionary over the committed documents. Indexes saved
+    /// without one are scanned once; the result is saved on the next commit.
+    pub(crate) fn spelling_dictionary(&mut self) -> Result<&SpellingDictionary> {
+        if self.spelling.is_none() {
+            let searcher = self.reader.searcher();
+            let mut delta = SpellingDelta::default();
+            for segment in searcher.segment_readers() {
+                let store = segment
+                    .get_store_reader(1)
+                    .map_err(|err| MemvidError::Tantivy {
+                        reason: err.to_string(),
+                    })?;
+                for document in store.iter::<TantivyDocument>(segment.alive_bitset()) {
+                    let document = document.map_err(|err| MemvidError::Tantivy {
+                        reason: err.to_string(),
+                    })?;
+                    if let Some(text) = self.stored_content(&document) {
+                        delta.add_text(&text);
+                    }
+                }
+            }
+            let mut spelling = SpellingDictionary::default();
+            spelling.apply(&mut delta);
+            self.spelling = Some(spelling);
+            self.spelling_unsaved = true;
+        }
+        Ok(self.spelling.get_or_insert_default())
+    }
+
     pub fn num_docs(&self) -> u64 {
         self.reader.searcher().num_docs()
     }
 }
+
+/// Query tokens in the analysed forms of each content analyzer.
+pub(crate) struct LanguageTokens {
+    default: Vec<String>,
+    languages: Vec<(ContentLanguage, Vec<String>)>,
+}
+
+impl LanguageTokens {
+    /// Tokens used as-is for every frame, when no analyzer is available.
+    pub(crate) fn unanalysed(tokens: &[String]) -> Self {
+        Self {
+            default: tokens.to_vec(),
+            languages: Vec::new(),
+        }
+    }
+
+    /// Tokens in the forms the analyzer for `frame` indexed `content` with.
+    pub(crate) fn for_frame(&self, frame: &Frame, content: &str) -> &[String] {
+        if self.languages.is_empty() {
+            return &self.default;
+        }
+        let language = frame_language(frame, content);
+        self.languages
+            .iter()
+            .find(|(candidate, _)| Some(*candidate) == language)
+            .map_or(&self.default, |(_, tokens)| tokens)
+    }
+}
+
+/// Whether an existing index field was built for a metadata key of type `kind`.
+fn field_has_type(entry: &FieldEntry, kind: MetadataFieldType) -> bool {
+    match (entry.field_type(), kind) {
+        (FieldType::Str(options), MetadataFieldType::Keyword | MetadataFieldType::Text) => {
+            let expected = if kind == MetadataFieldType::Keyword {
+                "raw"
+            } else {
+                "memvid_default"
+            };
+            options
+                .get_indexing_options()
+                .is_some_and(|indexing| indexing.tokenizer() == expected)
+        }
+        (FieldType::I64(_), MetadataFieldType::I64)
+        | (FieldType::F64(_), MetadataFieldType::F64)
+        | (FieldType::Date(_), MetadataFieldType::Date) => true,
+        _ => false,
+    }
+}
@@ -7,8 +7,10 @@ mod storage;
 mod util;
 mod wal;
 
+pub(crate) use engine::LanguageTokens;
 #[allow(unused_imports)]
 pub use engine::{TantivyDocHit, TantivyEngine, TantivySnapshot};
+pub(crate) use schema::TANTIVY_CODEC_VERSION;
 #[allow(unused_imports)]
 pub(crate) use storage::{EmbeddedLexSegment, EmbeddedLexStorage};
 #[allow(unused_imports)]
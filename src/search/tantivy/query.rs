@@ -1,18 +1,20 @@
-// Safe unwrap: single-element vector pop after length check.
-#![allow(clippy::unwrap_used)]
 use std::ops::Bound;
 
 use super::engine::TantivyEngine;
 use super::util::{combine_should_queries, to_search_value};
 use crate::search::contains_cjk;
-use crate::search::parser::{Expr, FieldTerm, ParsedQuery, Term as ParsedTerm, TextTerm};
+use crate::search::parser::{
+    Expr, FieldTerm, FuzzyTerm, ParsedQuery, ProximityTerm, Term as ParsedTerm, TextTerm,
+    parse_date_value,
+};
+use crate::types::MetadataFieldType;
 use crate::{MemvidError, Result};
-use tantivy::Term;
 use tantivy::query::{
-    AllQuery, BooleanQuery, Occur, PhraseQuery, Query, RangeQuery, RegexQuery, TermQuery,
-    TermSetQuery,
+    AllQuery, BooleanQuery, BoostQuery, FuzzyTermQuery, Occur, PhraseQuery, Query, RangeQuery,
+    RegexQuery, TermQuery, TermSetQuery,
 };
-use tantivy::schema::IndexRecordOption;
+use tantivy::schema::{Field, IndexRecordOption};
+use tantivy::{DateTime, Term};
 
 pub(super) fn build_root_query(
     engine: &TantivyEngine,
@@ -21,11 +23,17 @@ pub(super) fn build_root_query(
     scope_filter: Option<&str>,
     frame_filter: Option<&[u64]>,
 ) -> Result<Box<dyn Query>> {
-    QueryPlanner { engine }.build_root_query(parsed, uri_filter, scope_filter, frame_filter)
+    QueryPlanner {
+        engine,
+        content_fields: engine.populated_content_fields(),
+    }
+    .build_root_query(parsed, uri_filter, scope_filter, frame_filter)
 }
 
 struct QueryPlanner<'a> {
     engine: &'a TantivyEngine,
+    /// Content fields worth searching, with their analyzers.
+    content_fields: Vec<(Field, &'static str)>,
 }
 
 impl QueryPlanner<'_> {
@@ -61,10 +69,11 @@ impl QueryPlanner<'_> {
         }
 
         if clauses.len() == 1 {
-            Ok(clauses.into_iter().next().unwrap().1)
-        } else {
-            Ok(Box::new(BooleanQuery::new(clauses)))
+            if let Some((_, query)) = clauses.pop() {
+                return Ok(query);
+            }
         }
+        Ok(Box::new(BooleanQuery::new(clauses)))
     }
 
     fn build_expr_query(&self, expr: &Expr) -> Result<Box<dyn Query>> {
@@ -116,15 +125,96 @@ impl QueryPlanner<'_> {
             TextTerm::Phrase(phrase) => self.build_phrase_query(phrase),
             TextTerm::Wildcard(pattern) => {
                 let regex = pattern.regex.as_str().to_ascii_lowercase();
-                let query =
-                    RegexQuery::from_pattern(&regex, self.engine.content).map_err(|err| {
+                let mut queries: Vec<Box<dyn Query>> = Vec::new();
+                for &(field, _) in &self.content_fields {
+                    let query = RegexQuery::from_pattern(&regex, field).map_err(|err| {
                         MemvidError::Tantivy {
                             reason: err.to_string(),
                         }
                     })?;
-                Ok(Box::new(query))
+                    queries.push(Box::new(query));
+                }
+                Ok(combine_should_queries(queries))
+            }
+            TextTerm::Prefix(prefix) => {
+                // A zero-distance prefix automaton walks the term dictionary directly.
+                // Stemmed terms can be shorter than the typed prefix ("runn*" vs
+                // "run"), so match against unstemmed words when they are indexed.
+                let prefix_query = |field: Field| -> Box<dyn Query> {
+                    let term = Term::from_field_text(field, &prefix.to_lowercase());
+                    Box::new(FuzzyTermQuery::new_prefix(term, 0, true))
+                };
+                if let Some(field) = self.engine.words {
+                    return Ok(prefix_query(field));
+                }
+                let queries = self
+                    .content_fields
+                    .iter()
+                    .map(|&(field, _)| prefix_query(field))
+                    .collect();
+                Ok(combine_should_queries(queries))
+            }
+            TextTerm::Fuzzy(fuzzy) => Ok(self.build_fuzzy_query(fuzzy)),
+            TextTerm::Proximity(proximity) => Ok(self.build_proximity_query(proximity)),
+        }
+    }
+
+    fn build_proximity_query(&self, proximity: &ProximityTerm) -> Box<dyn Query> {
+        // Analyse the words together so Jieba can segment CJK text into positions.
+        let text = proximity.words.join(" ");
+        let mut queries = Vec::new();
+        for &(field, analyzer) in &self.content_fields {
+            let tokens = self
+                .engine
+                .analyse_with(analyzer, &text)
+                .unwrap_or_default();
+            match tokens.len() {
+                0 => continue,
+                1 => {
+                    queries.push(analysed_query(field, &tokens));
+                    continue;
+                }
+                _ => {}
+            }
+            let sloppy_phrase = |tokens: &[String]| -> Box<dyn Query> {
+                let terms: Vec<Term> = tokens
+                    .iter()
+                    .map(|token| Term::from_field_text(field, token))
+                    .collect();
+                let mut query = PhraseQuery::new(terms);
+                query.set_slop(proximity.slop);
+                Box::new(query)
+            };
+            queries.push(sloppy_phrase(&tokens));
+            if proximity.unordered {
+                let reversed: Vec<String> = tokens.iter().rev().cloned().collect();
+                queries.push(sloppy_phrase(&reversed));
             }
         }
+        combine_should_queries(queries)
+    }
+
+    fn build_fuzzy_query(&self, fuzzy: &FuzzyTerm) -> Box<dyn Query> {
+        // Content terms are stemmed, so fuzz around the stemmed form when the word
+        // analyses to a single token and around the raw form otherwise.
+        let queries = self
+            .content_fields
+            .iter()
+            .map(|&(field, analyzer)| -> Box<dyn Query> {
+                let tokens = self
+                    .engine
+                    .analyse_with(analyzer, &fuzzy.term)
+                    .unwrap_or_default();
+                let text = if let [token] = tokens.as_slice() {
+                    token
+                } else {
+                    &fuzzy.term
+                };
+                let term = Term::from_field_text(field, text);
+                Box::new(FuzzyTermQuery::new(term, fuzzy.distance, true))
+            })
+            .collect();
+        combine_should_queries(queries)
     }
 
     fn build_field_query(&self, field: &FieldTerm) -> Result<Box<dyn Query>> {
@@ -167,9 +257,72 @@ impl QueryPlanner<'_> {
                 });
                 Ok(Box::new(RangeQuery::new(lower, upper)))
             }
+            FieldTerm::Title(value) => {
+                let tokens = self.engine.analyse_text(value);
+                match self.engine.title {
+                    Some(field) if !tokens.is_empty() => Ok(analysed_query(field, &tokens)),
+                    _ => Ok(Box::new(AllQuery)),
+                }
+            }
+            FieldTerm::Meta { key, value } => self.build_metadata_query(key, value),
+            FieldTerm::MetaRange { key, start, end } => {
+                self.build_metadata_range_query(key, start.as_deref(), end.as_deref())
+            }
         }
     }
 
+    /// Boosted title clause added alongside content matches.
+    fn title_query(&self, tokens: &[String]) -> Option<Box<dyn Query>> {
+        let field = self.engine.title?;
+        Some(Box::new(BoostQuery::new(
+            analysed_query(field, tokens),
+            self.engine.title_boost,
+        )))
+    }
+
+    fn build_metadata_query(&self, key: &str, value: &str) -> Result<Box<dyn Query>> {
+        // Undeclared keys are answered by in-memory evaluation over extra_metadata.
+        let Some(&(field, kind)) = self.engine.metadata.get(key) else {
+            return Ok(Box::new(AllQuery));
+        };
+        if kind == MetadataFieldType::Text {
+            let tokens = self.engine.analyse_text(value);
+            if tokens.is_empty() {
+                return Ok(Box::new(AllQuery));
+            }
+            return Ok(analysed_query(field, &tokens));
+        }
+        Ok(Box::new(TermQuery::new(
+            metadata_term(field, kind, key, value)?,
+            IndexRecordOption::Basic,
+        )))
+    }
+
+    fn build_metadata_range_query(
+        &self,
+        key: &str,
+        start: Option<&str>,
+        end: Option<&str>,
+    ) -> Result<Box<dyn Query>> {
+        let Some(&(field, kind)) = self.engine.metadata.get(key) else {
+            return Ok(Box::new(AllQuery));
+        };
+        if kind == MetadataFieldType::Text {
+            return Err(MemvidError::InvalidQuery {
+                reason: format!("meta.{key} is a text field and does not support ranges"),
+            });
+        }
+        if start.is_none() && end.is_none() {
+            return Ok(Box::new(AllQuery));
+        }
+        let bound = |value: Option<&str>| -> Result<Bound<Term>> {
+            value.map_or(Ok(Bound::Unbounded), |value| {
+                metadata_term(field, kind, key, value).map(Bound::Included)
+            })
+        };
+        Ok(Box::new(RangeQuery::new(bound(start)?, bound(end)?)))
+    }
+
     fn build_word_query(&self, word: &str) -> Result<Box<dyn Query>> {
         // Handle empty words gracefully (from punctuation-only tokens like "-")
         if word.is_empty() {
@@ -184,30 +337,24 @@ impl QueryPlanner<'_> {
             return Ok(Box::new(AllQuery));
         }
         let mut queries: Vec<Box<dyn Query>> = Vec::new();
-        if tokens.len() == 1 {
-            queries.push(Box::new(TermQuery::new(
-                Term::from_field_text(self.engine.content, &tokens[0]),
-                IndexRecordOption::WithFreqsAndPositions,
-            )));
-        } else if contains_cjk(word) {
-            for token in &tokens {
-                queries.push(Box::new(TermQuery::new(
-                    Term::from_field_text(self.engine.content, token),
-                    IndexRecordOption::WithFreqsAndPositions,
-                )));
+        for &(field, analyzer) in &self.content_fields {
+            let mut field_tokens = self.engine.analyse_with(analyzer, word).unwrap_or_default();
+            field_tokens.retain(|token| token.chars().any(char::is_alphanumeric));
+            if field_tokens.is_empty() {
+                continue;
+            }
+            if field_tokens.len() > 1 && contains_cjk(word) {
+                // Segmentation may disagree with the author's; match any segment too.
+                for token in &field_tokens {
+                    queries.push(Box::new(TermQuery::new(
+                        Term::from_field_text(field, token),
+                        IndexRecordOption::WithFreqsAndPositions,
+                    )));
+                }
             }
-            let terms: Vec<Term> = tokens
-                .iter()
-                .map(|token| Term::from_field_text(self.engine.content, token))
-                .collect();
-            queries.push(Box::new(PhraseQuery::new(terms)));
-        } else {
-            let terms: Vec<Term> = tokens
-                .iter()
-                .map(|token| Term::from_field_text(self.engine.content, token))
-                .collect();
-            queries.push(Box::new(PhraseQuery::new(terms)));
+            queries.push(analysed_query(field, &field_tokens));
         }
+        queries.extend(self.title_query(&tokens));
 
         let normalized = to_search_value(word);
         queries.push(Box::new(TermQuery::new(
@@ -242,18 +389,16 @@ impl QueryPlanner<'_> {
             return Ok(Box::new(AllQuery));
         }
         let mut queries: Vec<Box<dyn Query>> = Vec::new();
-        if tokens.len() == 1 {
-            queries.push(Box::new(TermQuery::new(
-                Term::from_field_text(self.engine.content, &tokens[0]),
-                IndexRecordOption::WithFreqsAndPositions,
-            )));
-        } else {
-            let terms: Vec<Term> = tokens
-                .iter()
-                .map(|token| Term::from_field_text(self.engine.content, token))
-                .collect();
-            queries.push(Box::new(PhraseQuery::new(terms)));
+        for &(field, analyzer) in &self.content_fields {
+            let field_tokens = self
+                .engine
+                .analyse_with(analyzer, phrase)
+                .unwrap_or_default();
+            if !field_tokens.is_empty() {
+                queries.push(analysed_query(field, &field_tokens));
+            }
         }
+        queries.extend(self.title_query(&tokens));
 
         let normalized = to_search_value(phrase);
         queries.push(Box::new(TermQuery::new(
@@ -276,3 +421,42 @@ impl QueryPlanner<'_> {
         Ok(combine_should_queries(queries))
     }
 }
+
+/// Term query for a single analysed token, phrase query for several.
+fn analysed_query(field: Field, tokens: &[String]) -> Box<dyn Query> {
+    if let [token] = tokens {
+        return Box::new(TermQuery::new(
+            Term::from_field_text(field, token),
+            IndexRecordOption::WithFreqsAndPositions,
+        ));
+    }
+    let terms: Vec<Term> = tokens
+        .iter()
+        .map(|token| Term::from_field_text(field, token))
+        .collect();
+    Box::new(PhraseQuery::new(terms))
+}
+
+/// Typed term for a keyword or numeric metadata value.
+fn metadata_term(field: Field, kind: MetadataFieldType, key: &str, value: &str) -> Result<Term> {
+    let invalid = || MemvidError::InvalidQuery {
+        reason: format!("`{value}` is not a valid {} for meta.{key}", kind.as_str()),
+    };
+    let value = value.trim();
+    match kind {
+        MetadataFieldType::Keyword | MetadataFieldType::Text => {
+            Ok(Term::from_field_text(field, &to_search_value(value)))
+        }
+        MetadataFieldType::I64 => value
+            .parse()
+            .map(|number| Term::from_field_i64(field, number))
+            .map_err(|_| invalid()),
+        MetadataFieldType::F64 => value
+            .parse()
+            .map(|number| Term::from_field_f64(field, number))
+            .map_err(|_| invalid()),
+        MetadataFieldType::Date => parse_date_value(value)
+            .map(|timestamp| Term::from_field_date(field, DateTime::from_timestamp_secs(timestamp)))
+            .ok_or_else(invalid),
+    }
+}
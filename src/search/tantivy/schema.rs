@@ -1,16 +1,34 @@
 use tantivy::Index;
-use tantivy::schema::{IndexRecordOption, NumericOptions, STRING, Schema, TEXT, TextFieldIndexing};
+use tantivy::schema::{
+    DateOptions, IndexRecordOption, NumericOptions, STRING, Schema, TEXT, TextFieldIndexing,
+};
 use tantivy::tokenizer::{
     Language, LowerCaser, RawTokenizer, Stemmer, TextAnalyzer, Token, TokenFilter, TokenStream,
     Tokenizer,
 };
 use tantivy_jieba::JiebaTokenizer;
 
+use crate::search::is_cjk_char;
+use crate::types::{ContentLanguage, METADATA_FIELD_PREFIX, MetadataFieldType, SearchSchema};
+
+/// Codec version stamped on embedded Tantivy segments. Bump it whenever a
+/// change to analysis alters indexed terms or positions; segments written
+/// with an older version are rebuilt on open and flagged by doctor.
+///
+/// 2: punctuation tokens no longer occupy positions (phrase slop).
+/// 3: unstemmed `content_words` field for prefix queries.
+/// 4: `frame_id` is a fast field, so match sets skip stored documents.
+/// 5: unstored `synonyms` field for index-time synonym expansion.
+pub(crate) const TANTIVY_CODEC_VERSION: u16 = 5;
+
 #[derive(Clone)]
 struct AlnumTokenFilter;
 
+/// Drops punctuation and whitespace tokens and renumbers the survivors, so
+/// words separated by a space stay adjacent for phrase and slop queries.
 struct AlnumTokenStream<T> {
     tail: T,
+    next_position: usize,
 }
 
 impl TokenFilter for AlnumTokenFilter {
@@ -30,6 +48,7 @@ impl<T: Tokenizer> Tokenizer for AlnumTokenFilterWrapper<T> {
     fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
         AlnumTokenStream {
             tail: self.0.token_stream(text),
+            next_position: 0,
         }
     }
 }
@@ -38,6 +57,8 @@ impl<T: TokenStream> TokenStream for AlnumTokenStream<T> {
     fn advance(&mut self) -> bool {
         while self.tail.advance() {
             if self.tail.token().text.chars().any(char::is_alphanumeric) {
+                self.tail.token_mut().position = self.next_position;
+                self.next_position += 1;
                 return true;
             }
         }
@@ -53,26 +74,212 @@ impl<T: TokenStream> TokenStream for AlnumTokenStream<T> {
     }
 }
 
+/// Splits runs of CJK characters into overlapping bigrams (a lone character
+/// stays a unigram) and keeps other alphanumeric runs as whole words. Used
+/// for Japanese and Korean, where whitespace does not delimit words.
+///
+/// This is not a morphological analyzer: no MeCab-style dictionary (IPADIC,
+/// ko-dic) is bundled, since one adds tens of megabytes to the crate. Bigrams
+/// give good recall, but they do not reduce inflected forms to a lemma, and
+/// they can match across word boundaries.
+#[derive(Clone, Default)]
+struct CjkBigramTokenizer {
+    tokens: Vec<Token>,
+}
+
+struct CjkBigramTokenStream<'a> {
+    tokens: &'a mut [Token],
+    next: usize,
+}
+
+impl Tokenizer for CjkBigramTokenizer {
+    type TokenStream<'a> = CjkBigramTokenStream<'a>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        self.tokens.clear();
+        let push = |tokens: &mut Vec<Token>, from: usize, to: usize| {
+            let position = tokens.len();
+            tokens.push(Token {
+                offset_from: from,
+                offset_to: to,
+                position,
+                text: text[from..to].to_string(),
+                position_length: 1,
+            });
+        };
+        let mut cjk_run: Vec<(usize, usize)> = Vec::new();
+        let mut word_start: Option<usize> = None;
+        let flush_cjk = |tokens: &mut Vec<Token>, run: &mut Vec<(usize, usize)>| {
+            match run.as_slice() {
+                [] => {}
+                [(from, to)] => push(tokens, *from, *to),
+                chars => {
+                    for pair in chars.windows(2) {
+                        push(tokens, pair[0].0, pair[1].1);
+                    }
+                }
+            }
+            run.clear();
+        };
+        for (offset, ch) in text.char_indices() {
+            let end = offset + ch.len_utf8();
+            if is_cjk_char(ch) {
+                if let Some(start) = word_start.take() {
+                    push(&mut self.tokens, start, offset);
+                }
+                cjk_run.push((offset, end));
+            } else {
+                flush_cjk(&mut self.tokens, &mut cjk_run);
+                if ch.is_alphanumeric() {
+                    word_start.get_or_insert(offset);
+                } else if let Some(start) = word_start.take() {
+                    push(&mut self.tokens, start, offset);
+                }
+            }
+        }
+        flush_cjk(&mut self.tokens, &mut cjk_run);
+        if let Some(start) = word_start {
+            push(&mut self.tokens, start, text.len());
+        }
+        CjkBigramTokenStream {
+            tokens: &mut self.tokens,
+            next: 0,
+        }
+    }
+}
+
+impl TokenStream for CjkBigramTokenStream<'_> {
+    fn advance(&mut self) -> bool {
+        if self.next < self.tokens.len() {
+            self.next += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.tokens[self.next - 1]
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.tokens[self.next - 1]
+    }
+}
+
 pub(super) fn initialise_tokenizer(index: &Index) {
-    let mut tokenizer = JiebaTokenizer::new();
-    tokenizer.set_ordinal_position_mode(true);
-    let analyzer = TextAnalyzer::builder(tokenizer)
+    for language in ContentLanguage::ALL {
+        index
+            .tokenizers()
+            .register(language_tokenizer(language), language_analyzer(language));
+    }
+    index.tokenizers().register("raw", RawTokenizer::default());
+    index
+        .tokenizers()
+        .register(WORDS_TOKENIZER, words_analyzer());
+}
+
+/// Analyzer for the unstemmed `content_words` field.
+pub(super) const WORDS_TOKENIZER: &str = "memvid_words";
+
+/// Lowercased words without stemming, so prefixes match what was typed.
+fn words_analyzer() -> TextAnalyzer {
+    TextAnalyzer::builder(JiebaTokenizer::with_ordinal_position_mode(true))
         .filter(AlnumTokenFilter)
         .filter(LowerCaser)
-        .filter(Stemmer::new(Language::English))
-        .build();
-    index.tokenizers().register("memvid_default", analyzer);
-    index.tokenizers().register("raw", RawTokenizer::default());
+        .build()
+}
+
+fn language_analyzer(language: ContentLanguage) -> TextAnalyzer {
+    let stemmer = match language {
+        ContentLanguage::English => Language::English,
+        ContentLanguage::German => Language::German,
+        ContentLanguage::French => Language::French,
+        ContentLanguage::Spanish => Language::Spanish,
+        ContentLanguage::Japanese | ContentLanguage::Korean => {
+            return TextAnalyzer::builder(CjkBigramTokenizer::default())
+                .filter(LowerCaser)
+                .build();
+        }
+        ContentLanguage::Chinese => {
+            return TextAnalyzer::builder(JiebaTokenizer::with_ordinal_position_mode(true))
+                .filter(AlnumTokenFilter)
+                .filter(LowerCaser)
+                .build();
+        }
+    };
+    TextAnalyzer::builder(JiebaTokenizer::with_ordinal_position_mode(true))
+        .filter(AlnumTokenFilter)
+        .filter(LowerCaser)
+        .filter(Stemmer::new(stemmer))
+        .build()
+}
+
+/// Analyzer registered for `language`. English text, and text in languages
+/// without a dedicated analyzer, uses `memvid_default`.
+pub(super) fn language_tokenizer(language: ContentLanguage) -> &'static str {
+    match language {
+        ContentLanguage::English => "memvid_default",
+        ContentLanguage::German => "memvid_de",
+        ContentLanguage::French => "memvid_fr",
+        ContentLanguage::Spanish => "memvid_es",
+        ContentLanguage::Japanese => "memvid_ja",
+        ContentLanguage::Korean => "memvid_ko",
+        ContentLanguage::Chinese => "memvid_zh",
+    }
 }
 
-pub(super) fn build_schema() -> Schema {
+/// Content field `language` is routed to; English shares `content`.
+pub(super) fn language_field_name(language: ContentLanguage) -> &'static str {
+    match language {
+        ContentLanguage::English => "content",
+        ContentLanguage::German => "content_de",
+        ContentLanguage::French => "content_fr",
+        ContentLanguage::Spanish => "content_es",
+        ContentLanguage::Japanese => "content_ja",
+        ContentLanguage::Korean => "content_ko",
+        ContentLanguage::Chinese => "content_zh",
+    }
+}
+
+/// Tantivy field name for a declared metadata key.
+pub(super) fn metadata_field_name(key: &str) -> String {
+    format!("{METADATA_FIELD_PREFIX}{key}")
+}
+
+pub(super) fn build_schema(search_schema: &SearchSchema) -> Schema {
     let mut schema_builder = tantivy::schema::SchemaBuilder::default();
 
     let content_options = TextFieldIndexing::default()
         .set_tokenizer("memvid_default")
         .set_index_option(IndexRecordOption::WithFreqsAndPositions);
-    let content_field = TEXT.set_stored().set_indexing_options(content_options);
+    let content_field = TEXT
+        .set_stored()
+        .set_indexing_options(content_options.clone());
     schema_builder.add_text_field("content", content_field);
+    schema_builder.add_text_field("title", TEXT.set_indexing_options(content_options.clone()));
+    let words_indexing = TextFieldIndexing::default()
+        .set_tokenizer(WORDS_TOKENIZER)
+        .set_index_option(IndexRecordOption::Basic);
+    schema_builder.add_text_field("content_words", TEXT.set_indexing_options(words_indexing));
+    // Index-time synonyms are searchable but never stored, so they stay out
+    // of snippets and hit text.
+    schema_builder.add_text_field(
+        "synonyms",
+        TEXT.set_indexing_options(content_options.clone()),
+    );
+    for language in ContentLanguage::ALL {
+        if language == ContentLanguage::English {
+            continue;
+        }
+        let indexing = TextFieldIndexing::default()
+            .set_tokenizer(language_tokenizer(language))
+            .set_index_option(IndexRecordOption::WithFreqsAndPositions);
+        schema_builder.add_text_field(
+            language_field_name(language),
+            TEXT.set_stored().set_indexing_options(indexing),
+        );
+    }
 
     let keyword_indexing = TextFieldIndexing::default()
         .set_tokenizer("memvid_default")
@@ -90,13 +297,41 @@ pub(super) fn build_schema() -> Schema {
     let uri_field = STRING.set_stored().set_indexing_options(uri_indexing);
     schema_builder.add_text_field("uri", uri_field);
 
+    for (key, kind) in &search_schema.metadata_fields {
+        let name = metadata_field_name(key);
+        match kind {
+            MetadataFieldType::Keyword => {
+                let indexing = TextFieldIndexing::default()
+                    .set_tokenizer("raw")
+                    .set_index_option(IndexRecordOption::Basic);
+                schema_builder.add_text_field(&name, STRING.set_indexing_options(indexing));
+            }
+            MetadataFieldType::Text => {
+                schema_builder
+                    .add_text_field(&name, TEXT.set_indexing_options(content_options.clone()));
+            }
+            MetadataFieldType::I64 => {
+                schema_builder.add_i64_field(&name, NumericOptions::default().set_indexed());
+            }
+            MetadataFieldType::F64 => {
+                schema_builder.add_f64_field(&name, NumericOptions::default().set_indexed());
+            }
+            MetadataFieldType::Date => {
+                schema_builder.add_date_field(&name, DateOptions::default().set_indexed());
+            }
+        }
+    }
+
     let timestamp_options = NumericOptions::default()
         .set_indexed()
         .set_fast()
         .set_stored();
     schema_builder.add_i64_field("timestamp", timestamp_options);
 
-    let frame_id_options = NumericOptions::default().set_indexed().set_stored();
+    let frame_id_options = NumericOptions::default()
+        .set_indexed()
+        .set_stored()
+        .set_fast();
     schema_builder.add_u64_field("frame_id", frame_id_options);
 
     schema_builder.build()
@@ -1,7 +1,8 @@
 //! SIMD-accelerated distance calculations for vector search.
 //!
 //! This module provides optimized L2 (Euclidean) distance functions using
-//! the `wide` crate for portable SIMD across `x86_64` and aarch64.
+//! the `wide` crate for portable SIMD across `x86_64` and aarch64, plus a
+//! popcount-based Hamming distance for binary-quantized vectors.
 
 #[cfg(feature = "simd")]
 use wide::f32x8;
@@ -89,10 +90,46 @@ pub fn l2_distance_simd(a: &[f32], b: &[f32]) -> f32 {
     l2_distance_squared_simd(a, b).sqrt()
 }
 
+/// Compute the Hamming distance between two packed bit vectors.
+///
+/// A scalar loop over four independent accumulators. `count_ones` becomes a
+/// hardware popcount only where the target has one enabled (`CNT` on
+/// aarch64, `POPCNT` on `x86_64` built with `-C target-feature=+popcnt` or a
+/// matching `target-cpu`); default `x86_64` builds use a software popcount.
+#[must_use]
+pub fn hamming_distance(a: &[u64], b: &[u64]) -> u32 {
+    debug_assert_eq!(a.len(), b.len(), "bit vectors must have same length");
+
+    let chunks = a.len() / 4;
+    let mut acc = [0u32; 4];
+
+    for i in 0..chunks {
+        let offset = i * 4;
+        for lane in 0..4 {
+            acc[lane] += (a[offset + lane] ^ b[offset + lane]).count_ones();
+        }
+    }
+
+    let mut total: u32 = acc.iter().sum();
+    for i in chunks * 4..a.len() {
+        total += (a[i] ^ b[i]).count_ones();
+    }
+
+    total
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_hamming_distance() {
+        let a = [0u64, u64::MAX, 0b1011, 0, 1];
+        let b = [0u64, 0, 0b0001, 0, 0];
+        assert_eq!(hamming_distance(&a, &b), 64 + 2 + 1);
+        assert_eq!(hamming_distance(&a, &a), 0);
+    }
+
     #[test]
     fn test_l2_distance_squared_basic() {
         let a = [0.0, 0.0, 0.0];
@@ -139,6 +139,7 @@ fn store_table_impl(
         dedup: false,
         instant_index: false,    // Tables are batch operations, commit at end
         extraction_budget_ms: 0, // No budget for table metadata
+        pii: None,
     };
 
     let meta_frame_id = mem.next_frame_id();
@@ -211,13 +212,14 @@ fn store_table_impl(
             dedup: false,
             instant_index: false, // Tables are batch operations, commit at end
             extraction_budget_ms: 0, // No budget for table rows
+            pii: None,
         };
 
         let should_embed = embed_rows && embedder.is_some();
         if should_embed {
             let embedder = embedder.expect("checked above");
             let text = row_options.search_text.as_deref().unwrap_or_default();
-            let embedding = embedder.embed_query(text)?;
+            let embedding = mem.embed_query_cached(embedder, text)?;
 
             if let Some(identity) = embedding_identity {
                 if let Some(provider) = identity.provider.as_deref() {
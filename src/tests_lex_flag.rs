@@ -71,6 +71,11 @@ mod tests {
                         no_sketch: false,
                         acl_context: None,
                         acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                        diversify: None,
+                        highlight: None,
+                        facets: Vec::new(),
+                        sort: crate::types::SearchSort::Relevance,
+                        explain: false,
                     })
                     .expect("search must succeed");
 
@@ -150,6 +155,11 @@ mod tests {
                         no_sketch: false,
                         acl_context: None,
                         acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                        diversify: None,
+                        highlight: None,
+                        facets: Vec::new(),
+                        sort: crate::types::SearchSort::Relevance,
+                        explain: false,
                     })
                     .expect("search must succeed through mutex wrapper");
 
@@ -204,6 +214,11 @@ mod tests {
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    diversify: None,
+                    highlight: None,
+                    facets: Vec::new(),
+                    sort: crate::types::SearchSort::Relevance,
+                    explain: false,
                 })
                 .expect("search must succeed");
 
@@ -24,6 +24,7 @@
 //! ```
 
 use crate::types::embedding::EmbeddingProvider;
+use crate::types::{EmbeddingIdentity, VecEmbedder};
 use crate::{MemvidError, Result};
 use ndarray::Array;
 use ort::session::{Session, builder::GraphOptimizationLevel};
@@ -912,6 +913,30 @@ impl EmbeddingProvider for LocalTextEmbedder {
     }
 }
 
+impl VecEmbedder for LocalTextEmbedder {
+    fn embed_query(&self, text: &str) -> Result<Vec<f32>> {
+        self.encode_text(text)
+    }
+
+    fn embed_chunks(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        self.encode_batch(texts)
+    }
+
+    fn embedding_dimension(&self) -> usize {
+        self.model_info.dims as usize
+    }
+
+    /// Outputs are always L2-normalized.
+    fn embedding_identity(&self) -> Option<EmbeddingIdentity> {
+        Some(EmbeddingIdentity {
+            provider: Some("local".into()),
+            model: Some(self.model_info.name.into()),
+            dimension: Some(self.model_info.dims),
+            normalized: Some(true),
+        })
+    }
+}
+
 // ============================================================================
 // Utilities
 // ============================================================================
@@ -945,6 +970,16 @@ mod tests {
         assert!(default_model.is_default);
     }
 
+    #[test]
+    fn identity_names_model_and_dimension() {
+        let embedder = LocalTextEmbedder::new(TextEmbedConfig::default()).unwrap();
+        let identity = embedder.embedding_identity().unwrap();
+        assert_eq!(identity.provider.as_deref(), Some("local"));
+        assert_eq!(identity.model.as_deref(), Some("bge-small-en-v1.5"));
+        assert_eq!(identity.dimension, Some(384));
+        assert_eq!(embedder.embedding_dimension(), 384);
+    }
+
     #[test]
     fn test_get_model_info() {
         let bge_small = get_text_model_info("bge-small-en-v1.5");
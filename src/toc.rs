@@ -56,6 +56,30 @@ struct LegacyTocV2 {
     pub toc_checksum: [u8; 32],
 }
 
+/// Legacy TOC format ending at `enrichment_queue` (pre-embedding_cache).
+/// Used for files created before the embedding cache, search schema, synonyms,
+/// percolator, PII, IVF and OCR layout tracks, which all arrived together.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct LegacyTocV3 {
+    pub toc_version: u64,
+    pub segments: Vec<SegmentMeta>,
+    pub frames: Vec<Frame>,
+    pub indexes: IndexManifests,
+    pub time_index: Option<TimeIndexManifest>,
+    pub temporal_track: Option<TemporalTrackManifest>,
+    pub memories_track: Option<crate::types::MemoriesTrackManifest>,
+    pub logic_mesh: Option<crate::types::LogicMeshManifest>,
+    pub sketch_track: Option<crate::types::SketchTrackManifest>,
+    pub segment_catalog: SegmentCatalog,
+    pub ticket_ref: TicketRef,
+    pub memory_binding: Option<MemoryBinding>,
+    pub replay_manifest: Option<crate::replay::ReplayManifest>,
+    pub enrichment_queue: crate::types::EnrichmentQueueManifest,
+    // Note: embedding_cache and every later track NOT present in this version
+    pub merkle_root: [u8; 32],
+    pub toc_checksum: [u8; 32],
+}
+
 impl From<LegacyTocV1> for Toc {
     fn from(legacy: LegacyTocV1) -> Self {
         Toc {
@@ -73,6 +97,14 @@ impl From<LegacyTocV1> for Toc {
             memory_binding: legacy.memory_binding,
             replay_manifest: None,                // Default for legacy files
             enrichment_queue: Default::default(), // Default for legacy files
+            embedding_cache: None,                // Default for legacy files
+            search_schema: None,                  // Default for legacy files
+            synonyms: None,                       // Default for legacy files
+            percolator: None,                     // Default for legacy files
+            pii: None,                            // Default for pre-PII-policy files
+            ivf_index: None,                      // Default for pre-IVF files
+            percolator_delivery: None,
+            ocr_layout: None,
             merkle_root: legacy.merkle_root,
             toc_checksum: legacy.toc_checksum,
         }
@@ -96,6 +128,45 @@ impl From<LegacyTocV2> for Toc {
             memory_binding: legacy.memory_binding,
             replay_manifest: None, // Default for pre-replay files
             enrichment_queue: Default::default(), // Default for legacy files
+            embedding_cache: None, // Default for legacy files
+            search_schema: None,   // Default for legacy files
+            synonyms: None,        // Default for legacy files
+            percolator: None,      // Default for legacy files
+            pii: None,             // Default for pre-PII-policy files
+            ivf_index: None,       // Default for pre-IVF files
+            percolator_delivery: None,
+            ocr_layout: None,
+            merkle_root: legacy.merkle_root,
+            toc_checksum: legacy.toc_checksum,
+        }
+    }
+}
+
+impl From<LegacyTocV3> for Toc {
+    fn from(legacy: LegacyTocV3) -> Self {
+        Toc {
+            toc_version: legacy.toc_version,
+            segments: legacy.segments,
+            frames: legacy.frames,
+            indexes: legacy.indexes,
+            time_index: legacy.time_index,
+            temporal_track: legacy.temporal_track,
+            memories_track: legacy.memories_track,
+            logic_mesh: legacy.logic_mesh,
+            sketch_track: legacy.sketch_track,
+            segment_catalog: legacy.segment_catalog,
+            ticket_ref: legacy.ticket_ref,
+            memory_binding: legacy.memory_binding,
+            replay_manifest: legacy.replay_manifest,
+            enrichment_queue: legacy.enrichment_queue,
+            embedding_cache: None, // Defaults for pre-embedding-cache files
+            search_schema: None,
+            synonyms: None,
+            percolator: None,
+            pii: None,
+            ivf_index: None,
+            percolator_delivery: None,
+            ocr_layout: None,
             merkle_root: legacy.merkle_root,
             toc_checksum: legacy.toc_checksum,
         }
@@ -121,6 +192,19 @@ impl Toc {
             return Ok(toc);
         }
 
+        // Try V3 format (without embedding_cache)
+        if let Ok((legacy, bytes_read)) =
+            decode_from_slice::<LegacyTocV3, _>(bytes, canonical_config())
+        {
+            if bytes_read != bytes.len() {
+                return Err(MemvidError::InvalidToc {
+                    reason: "unexpected trailing bytes in V3 format".into(),
+                });
+            }
+            tracing::debug!("Decoded TOC V3 format (pre-embedding_cache)");
+            return Ok(legacy.into());
+        }
+
         // Try V2 format (with memories_track/logic_mesh, without replay_manifest)
         if let Ok((legacy, bytes_read)) =
             decode_from_slice::<LegacyTocV2, _>(bytes, canonical_config())
@@ -156,6 +240,11 @@ impl Toc {
         if let Ok((toc, _)) = decode_from_slice::<Toc, _>(bytes, canonical_config()) {
             return Ok(toc);
         }
+        // Try V3 format (without embedding_cache)
+        if let Ok((legacy, _)) = decode_from_slice::<LegacyTocV3, _>(bytes, canonical_config()) {
+            tracing::debug!("Decoded TOC V3 format (pre-embedding_cache) in lenient mode");
+            return Ok(legacy.into());
+        }
         // Try V2 format (with memories_track/logic_mesh, without replay_manifest)
         if let Ok((legacy, _)) = decode_from_slice::<LegacyTocV2, _>(bytes, canonical_config()) {
             tracing::debug!("Decoded TOC V2 format (pre-replay_manifest) in lenient mode");
@@ -172,6 +261,13 @@ impl Toc {
     }
 }
 
+impl LegacyTocV3 {
+    /// Encode V3 TOC format for checksum verification.
+    fn encode(&self) -> Result<Vec<u8>> {
+        Ok(encode_to_vec(self, canonical_config())?)
+    }
+}
+
 impl LegacyTocV1 {
     /// Encode legacy TOC format for checksum verification.
     fn encode(&self) -> Result<Vec<u8>> {
@@ -207,6 +303,43 @@ impl Toc {
             return Ok(());
         }
 
+        // Try V3 format (without embedding_cache)
+        // Only try if every later track is None (indicates pre-embedding-cache origin)
+        if self.embedding_cache.is_none()
+            && self.search_schema.is_none()
+            && self.synonyms.is_none()
+            && self.percolator.is_none()
+            && self.pii.is_none()
+            && self.ivf_index.is_none()
+            && self.percolator_delivery.is_none()
+            && self.ocr_layout.is_none()
+        {
+            let legacy_v3 = LegacyTocV3 {
+                toc_version: self.toc_version,
+                segments: self.segments.clone(),
+                frames: self.frames.clone(),
+                indexes: self.indexes.clone(),
+                time_index: self.time_index.clone(),
+                temporal_track: self.temporal_track.clone(),
+                memories_track: self.memories_track.clone(),
+                logic_mesh: self.logic_mesh.clone(),
+                sketch_track: self.sketch_track.clone(),
+                segment_catalog: self.segment_catalog.clone(),
+                ticket_ref: self.ticket_ref.clone(),
+                memory_binding: self.memory_binding.clone(),
+                replay_manifest: self.replay_manifest.clone(),
+                enrichment_queue: self.enrichment_queue.clone(),
+                merkle_root: self.merkle_root,
+                toc_checksum: [0u8; 32],
+            };
+            let v3_bytes = legacy_v3.encode()?;
+            let v3_digest = Self::calculate_checksum(&v3_bytes);
+            if v3_digest == self.toc_checksum {
+                tracing::debug!("TOC checksum verified using V3 format (pre-embedding_cache)");
+                return Ok(());
+            }
+        }
+
         // Try V2 format (with memories_track/logic_mesh, without replay_manifest)
         // Only try if replay_manifest is None (indicates pre-replay origin)
         if self.replay_manifest.is_none() {
@@ -369,6 +502,14 @@ mod tests {
             memory_binding: None,
             replay_manifest: None,
             enrichment_queue: Default::default(),
+            embedding_cache: None,
+            search_schema: None,
+            synonyms: None,
+            percolator: None,
+            pii: None,
+            ivf_index: None,
+            percolator_delivery: None,
+            ocr_layout: None,
             merkle_root: [0x55; 32],
             toc_checksum: [0u8; 32],
         }
@@ -407,4 +548,35 @@ mod tests {
         let err = Toc::decode(&bytes).expect_err("should reject");
         matches!(err, MemvidError::InvalidToc { .. });
     }
+
+    #[test]
+    fn decode_pre_embedding_cache_format() {
+        let toc = sample_toc();
+        let mut legacy = LegacyTocV3 {
+            toc_version: toc.toc_version,
+            segments: toc.segments.clone(),
+            frames: toc.frames.clone(),
+            indexes: toc.indexes.clone(),
+            time_index: toc.time_index.clone(),
+            temporal_track: toc.temporal_track.clone(),
+            memories_track: None,
+            logic_mesh: None,
+            sketch_track: None,
+            segment_catalog: toc.segment_catalog.clone(),
+            ticket_ref: toc.ticket_ref.clone(),
+            memory_binding: None,
+            replay_manifest: None,
+            enrichment_queue: Default::default(),
+            merkle_root: toc.merkle_root,
+            toc_checksum: [0u8; 32],
+        };
+        let unstamped = legacy.encode().expect("encode legacy");
+        legacy.toc_checksum = Toc::calculate_checksum(&unstamped);
+        let bytes = legacy.encode().expect("encode legacy");
+
+        let decoded = Toc::decode(&bytes).expect("decode legacy toc");
+        assert!(decoded.embedding_cache.is_none());
+        assert_eq!(decoded.frames.len(), toc.frames.len());
+        decoded.verify_checksum().expect("legacy checksum matches");
+    }
 }
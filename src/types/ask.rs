@@ -3,9 +3,10 @@ use serde::{Deserialize, Serialize};
 use super::acl::{AclContext, AclEnforcementMode};
 use super::adaptive::AdaptiveConfig;
 use super::common::FrameId;
+use super::embedding_identity::EmbeddingIdentity;
 #[cfg(feature = "temporal_track")]
 use super::search::SearchHitTemporal;
-use super::search::SearchResponse;
+use super::search::{DiversifyOptions, HighlightOptions, HighlightSpan, SearchResponse};
 #[cfg(feature = "temporal_track")]
 use super::temporal::TemporalFilter;
 use crate::Result;
@@ -81,6 +82,12 @@ pub struct AskRequest {
     #[serde(default)]
     /// ACL evaluation mode (`audit` or `enforce`).
     pub acl_enforcement_mode: AclEnforcementMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Optional diversification applied to the final context hits.
+    pub diversify: Option<DiversifyOptions>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Highlight the final context hits; citations then carry the matched spans.
+    pub highlight: Option<HighlightOptions>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -103,6 +110,9 @@ pub struct AskCitation {
     pub chunk_range: Option<(usize, usize)>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub score: Option<f32>,
+    /// Matched term spans in the cited frame, when highlighting was requested.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spans: Vec<HighlightSpan>,
 }
 
 /// Fragment of retrieval context sent to a synthesizer (with ranges and optional temporal info).
@@ -168,4 +178,10 @@ pub trait VecEmbedder {
     }
 
     fn embedding_dimension(&self) -> usize;
+
+    /// Identity of the embedding space, used to key the persistent embedding
+    /// cache. Embedders that return `None` bypass the cache.
+    fn embedding_identity(&self) -> Option<EmbeddingIdentity> {
+        None
+    }
 }
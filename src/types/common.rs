@@ -122,6 +122,17 @@ pub enum FrameRole {
     DocumentChunk,
     /// Extracted image from a document (e.g., PDF page image for CLIP)
     ExtractedImage,
+    /// File attached to its parent frame (e.g. an email attachment); stored,
+    /// chunked and indexed like a document.
+    Attachment,
+}
+
+impl FrameRole {
+    /// Whether frames with this role carry a document's own content.
+    #[must_use]
+    pub fn is_document(self) -> bool {
+        matches!(self, Self::Document | Self::Attachment)
+    }
 }
 
 /// Enrichment state for progressive ingestion.
@@ -0,0 +1,459 @@
+//! Embedding cache track: text embeddings persisted inside `.mv2`.
+//!
+//! Entries are keyed by the BLAKE3 hash of the normalized chunk text together
+//! with the embedding identity (provider, model, dimension, normalization), so
+//! vectors from different models never mix. The cache survives process
+//! restarts, letting re-ingestion, enrichment and repeated `ask` queries skip
+//! the embedder for text it has already seen.
+//!
+//! Size is bounded by an entry count and a byte budget; when either is
+//! exceeded the least recently used entries are evicted.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::embedding_identity::EmbeddingIdentity;
+use crate::{MemvidError, Result};
+
+/// Magic bytes for the embedding cache blob.
+pub const EMBEDDING_CACHE_MAGIC: &[u8; 4] = b"MVEC";
+
+/// Current schema version.
+pub const EMBEDDING_CACHE_VERSION: u16 = 1;
+
+/// Default maximum number of cached embeddings.
+pub const DEFAULT_EMBEDDING_CACHE_MAX_ENTRIES: u64 = 50_000;
+
+/// Default byte budget for cached embeddings (64 MiB).
+pub const DEFAULT_EMBEDDING_CACHE_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Fixed per-entry overhead counted against the byte budget (text hash + clock).
+const ENTRY_OVERHEAD_BYTES: u64 = 40;
+
+/// Upper bound on allocations while decoding a persisted cache track.
+#[allow(clippy::cast_possible_truncation)]
+const CACHE_DECODE_LIMIT: usize = crate::MAX_INDEX_BYTES as usize;
+
+/// Size limits for the embedding cache track.
+///
+/// The whole track is rewritten on every commit, so keep `max_bytes` modest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddingCacheConfig {
+    /// Maximum number of cached embeddings.
+    pub max_entries: u64,
+    /// Maximum bytes of cached embeddings (vectors plus keys).
+    pub max_bytes: u64,
+}
+
+impl Default for EmbeddingCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: DEFAULT_EMBEDDING_CACHE_MAX_ENTRIES,
+            max_bytes: DEFAULT_EMBEDDING_CACHE_MAX_BYTES,
+        }
+    }
+}
+
+impl EmbeddingCacheConfig {
+    #[must_use]
+    pub fn with_max_entries(mut self, max_entries: u64) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+}
+
+/// Snapshot of embedding cache size and hit counters.
+///
+/// Hit, miss and eviction counters cover the current session only.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddingCacheStats {
+    pub entries: u64,
+    pub bytes: u64,
+    pub max_entries: u64,
+    pub max_bytes: u64,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+impl EmbeddingCacheStats {
+    /// Fraction of lookups served from the cache.
+    #[must_use]
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct CacheKey {
+    text_hash: [u8; 32],
+    identity: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    embedding: Vec<f32>,
+    last_used: u64,
+}
+
+impl CacheEntry {
+    fn size_bytes(&self, key: &CacheKey) -> u64 {
+        ENTRY_OVERHEAD_BYTES + key.identity.len() as u64 + self.embedding.len() as u64 * 4
+    }
+}
+
+/// On-disk form: entries sorted by key for deterministic output.
+#[derive(Serialize, Deserialize)]
+struct PersistedCache {
+    config: EmbeddingCacheConfig,
+    clock: u64,
+    entries: Vec<(CacheKey, CacheEntry)>,
+}
+
+/// LRU cache of text embeddings persisted as a track in the `.mv2` file.
+#[derive(Debug, Clone, Default)]
+pub struct EmbeddingCacheTrack {
+    config: EmbeddingCacheConfig,
+    entries: HashMap<CacheKey, CacheEntry>,
+    clock: u64,
+    bytes: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+    dirty: bool,
+}
+
+impl EmbeddingCacheTrack {
+    #[must_use]
+    pub fn new(config: EmbeddingCacheConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn config(&self) -> EmbeddingCacheConfig {
+        self.config
+    }
+
+    /// Replace the size limits, evicting entries that no longer fit.
+    pub fn set_config(&mut self, config: EmbeddingCacheConfig) {
+        if self.config != config {
+            self.config = config;
+            self.dirty = true;
+            self.evict_to(config.max_entries, config.max_bytes);
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether the cache changed since it was last loaded or persisted.
+    #[must_use]
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub(crate) fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Look up the embedding of `text` under `identity`, counting a hit or miss.
+    pub fn get(&mut self, identity: &EmbeddingIdentity, text: &str) -> Option<Vec<f32>> {
+        let key = cache_key(identity, text);
+        self.clock += 1;
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.last_used = self.clock;
+            self.hits += 1;
+            Some(entry.embedding.clone())
+        } else {
+            self.misses += 1;
+            None
+        }
+    }
+
+    /// Store the embedding of `text` under `identity`, evicting the least
+    /// recently used entries when the cache is over its limits.
+    pub fn insert(&mut self, identity: &EmbeddingIdentity, text: &str, embedding: Vec<f32>) {
+        if self.config.max_entries == 0 || embedding.is_empty() {
+            return;
+        }
+        let key = cache_key(identity, text);
+        self.clock += 1;
+        let entry = CacheEntry {
+            embedding,
+            last_used: self.clock,
+        };
+        let size = entry.size_bytes(&key);
+        if size > self.config.max_bytes {
+            return;
+        }
+        if let Some(previous) = self.entries.get(&key) {
+            self.bytes -= previous.size_bytes(&key);
+        }
+        self.bytes += size;
+        self.entries.insert(key, entry);
+        self.dirty = true;
+
+        if self.entries.len() as u64 > self.config.max_entries || self.bytes > self.config.max_bytes
+        {
+            // Evict down to 90% so bulk inserts at capacity don't evict on every call
+            self.evict_to(
+                self.config.max_entries - self.config.max_entries / 10,
+                self.config.max_bytes - self.config.max_bytes / 10,
+            );
+        }
+    }
+
+    /// Drop every cached embedding.
+    pub fn clear(&mut self) {
+        if !self.entries.is_empty() {
+            self.dirty = true;
+        }
+        self.entries.clear();
+        self.bytes = 0;
+    }
+
+    #[must_use]
+    pub fn stats(&self) -> EmbeddingCacheStats {
+        EmbeddingCacheStats {
+            entries: self.entries.len() as u64,
+            bytes: self.bytes,
+            max_entries: self.config.max_entries,
+            max_bytes: self.config.max_bytes,
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+        }
+    }
+
+    fn evict_to(&mut self, max_entries: u64, max_bytes: u64) {
+        if self.entries.len() as u64 <= max_entries && self.bytes <= max_bytes {
+            return;
+        }
+        let mut by_age: Vec<(u64, CacheKey)> = self
+            .entries
+            .iter()
+            .map(|(key, entry)| (entry.last_used, key.clone()))
+            .collect();
+        by_age.sort_unstable_by_key(|(last_used, _)| *last_used);
+
+        for (_, key) in by_age {
+            if self.entries.len() as u64 <= max_entries && self.bytes <= max_bytes {
+                break;
+            }
+            if let Some(entry) = self.entries.remove(&key) {
+                self.bytes -= entry.size_bytes(&key);
+                self.evictions += 1;
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Serialize the cache to bytes with magic header.
+    pub fn serialize(&self) -> Result<Vec<u8>> {
+        let mut entries: Vec<(CacheKey, CacheEntry)> = self
+            .entries
+            .iter()
+            .map(|(key, entry)| (key.clone(), entry.clone()))
+            .collect();
+        entries.sort_by(|(a, _), (b, _)| {
+            (a.identity.as_str(), a.text_hash).cmp(&(b.identity.as_str(), b.text_hash))
+        });
+        let persisted = PersistedCache {
+            config: self.config,
+            clock: self.clock,
+            entries,
+        };
+
+        let config = bincode::config::standard()
+            .with_fixed_int_encoding()
+            .with_little_endian();
+        let payload = bincode::serde::encode_to_vec(&persisted, config).map_err(|e| {
+            MemvidError::InvalidEmbeddingCache {
+                reason: format!("serialization failed: {e}").into(),
+            }
+        })?;
+
+        let mut buf = Vec::with_capacity(14 + payload.len());
+        buf.extend_from_slice(EMBEDDING_CACHE_MAGIC);
+        buf.extend_from_slice(&EMBEDDING_CACHE_VERSION.to_le_bytes());
+        buf.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+        buf.extend(payload);
+        Ok(buf)
+    }
+
+    /// Deserialize a cache from bytes.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < 14 {
+            return Err(MemvidError::InvalidEmbeddingCache {
+                reason: "blob too short".into(),
+            });
+        }
+        if &bytes[0..4] != EMBEDDING_CACHE_MAGIC {
+            return Err(MemvidError::InvalidEmbeddingCache {
+                reason: "invalid magic bytes".into(),
+            });
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version > EMBEDDING_CACHE_VERSION {
+            return Err(MemvidError::InvalidEmbeddingCache {
+                reason: format!("unsupported version: {version}").into(),
+            });
+        }
+        let mut len_bytes = [0u8; 8];
+        len_bytes.copy_from_slice(&bytes[6..14]);
+        let payload_len = usize::try_from(u64::from_le_bytes(len_bytes)).unwrap_or(usize::MAX);
+        let payload = bytes
+            .get(14..)
+            .and_then(|rest| rest.get(..payload_len))
+            .ok_or_else(|| MemvidError::InvalidEmbeddingCache {
+                reason: "truncated blob".into(),
+            })?;
+
+        let config = bincode::config::standard()
+            .with_fixed_int_encoding()
+            .with_little_endian()
+            .with_limit::<CACHE_DECODE_LIMIT>();
+        let (persisted, _): (PersistedCache, _) =
+            bincode::serde::decode_from_slice(payload, config).map_err(|e| {
+                MemvidError::InvalidEmbeddingCache {
+                    reason: format!("deserialization failed: {e}").into(),
+                }
+            })?;
+
+        let mut track = Self::new(persisted.config);
+        track.clock = persisted.clock;
+        for (key, entry) in persisted.entries {
+            track.bytes += entry.size_bytes(&key);
+            track.entries.insert(key, entry);
+        }
+        Ok(track)
+    }
+}
+
+/// Collapse whitespace runs and trim, so formatting-only differences share
+/// a cache entry.
+#[must_use]
+pub fn normalize_cache_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn cache_key(identity: &EmbeddingIdentity, text: &str) -> CacheKey {
+    let normalized = normalize_cache_text(text);
+    CacheKey {
+        text_hash: *blake3::hash(normalized.as_bytes()).as_bytes(),
+        identity: identity_key(identity),
+    }
+}
+
+fn identity_key(identity: &EmbeddingIdentity) -> String {
+    format!(
+        "{}|{}|{}|{}",
+        identity.provider.as_deref().unwrap_or(""),
+        identity.model.as_deref().unwrap_or(""),
+        identity.dimension.unwrap_or(0),
+        identity
+            .normalized
+            .map_or("", |n| if n { "1" } else { "0" }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(model: &str) -> EmbeddingIdentity {
+        EmbeddingIdentity {
+            provider: Some("test".into()),
+            model: Some(model.into()),
+            dimension: Some(2),
+            normalized: None,
+        }
+    }
+
+    #[test]
+    fn lookup_is_keyed_by_identity_and_normalized_text() {
+        let mut cache = EmbeddingCacheTrack::new(EmbeddingCacheConfig::default());
+        cache.insert(&identity("a"), "hello   world\n", vec![1.0, 2.0]);
+
+        assert_eq!(
+            cache.get(&identity("a"), " hello world"),
+            Some(vec![1.0, 2.0])
+        );
+        assert_eq!(cache.get(&identity("b"), "hello world"), None);
+        assert_eq!(cache.get(&identity("a"), "Hello world"), None);
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 2);
+        assert_eq!(stats.entries, 1);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let config = EmbeddingCacheConfig::default().with_max_entries(3);
+        let mut cache = EmbeddingCacheTrack::new(config);
+        let id = identity("a");
+        cache.insert(&id, "one", vec![1.0]);
+        cache.insert(&id, "two", vec![2.0]);
+        cache.insert(&id, "three", vec![3.0]);
+        assert!(cache.get(&id, "one").is_some());
+        cache.insert(&id, "four", vec![4.0]);
+
+        assert!(cache.len() <= 3);
+        assert!(cache.get(&id, "one").is_some());
+        assert!(cache.get(&id, "four").is_some());
+        assert!(cache.get(&id, "two").is_none());
+        assert!(cache.stats().evictions >= 1);
+    }
+
+    #[test]
+    fn byte_budget_is_enforced() {
+        let config = EmbeddingCacheConfig::default().with_max_bytes(200);
+        let mut cache = EmbeddingCacheTrack::new(config);
+        let id = identity("a");
+        for idx in 0..10 {
+            cache.insert(&id, &format!("text {idx}"), vec![0.5; 8]);
+        }
+        assert!(cache.stats().bytes <= 200);
+        assert!(cache.get(&id, "text 9").is_some());
+    }
+
+    #[test]
+    fn serialize_roundtrip() {
+        let mut cache =
+            EmbeddingCacheTrack::new(EmbeddingCacheConfig::default().with_max_entries(7));
+        cache.insert(&identity("a"), "alpha", vec![0.25, -1.0]);
+        cache.insert(&identity("b"), "beta", vec![3.0, 4.0]);
+
+        let bytes = cache.serialize().unwrap();
+        let mut restored = EmbeddingCacheTrack::deserialize(&bytes).unwrap();
+        assert_eq!(restored.config().max_entries, 7);
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored.stats().bytes, cache.stats().bytes);
+        assert!(!restored.is_dirty());
+        assert_eq!(restored.get(&identity("b"), "beta"), Some(vec![3.0, 4.0]));
+
+        assert!(EmbeddingCacheTrack::deserialize(&bytes[..10]).is_err());
+    }
+}
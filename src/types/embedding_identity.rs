@@ -66,6 +66,32 @@ impl EmbeddingIdentity {
             normalized,
         })
     }
+
+    /// Write this identity into a frame's `extra_metadata`, the inverse of
+    /// [`from_extra_metadata`](Self::from_extra_metadata).
+    pub fn write_extra_metadata(&self, extra: &mut BTreeMap<String, String>) {
+        if let Some(provider) = &self.provider {
+            extra.insert(
+                MEMVID_EMBEDDING_PROVIDER_KEY.to_string(),
+                provider.to_string(),
+            );
+        }
+        if let Some(model) = &self.model {
+            extra.insert(MEMVID_EMBEDDING_MODEL_KEY.to_string(), model.to_string());
+        }
+        if let Some(dimension) = self.dimension {
+            extra.insert(
+                MEMVID_EMBEDDING_DIMENSION_KEY.to_string(),
+                dimension.to_string(),
+            );
+        }
+        if let Some(normalized) = self.normalized {
+            extra.insert(
+                MEMVID_EMBEDDING_NORMALIZED_KEY.to_string(),
+                normalized.to_string(),
+            );
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
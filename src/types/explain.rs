@@ -0,0 +1,118 @@
+//! Search explanations: which stages narrowed the candidate set, how the
+//! engine scored a frame, and why a frame was left out of the results.
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+use super::search::SearchEngineKind;
+
+/// A step of the search pipeline that can narrow or drop candidates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchStage {
+    /// `date:` ranges in the query, resolved through the time index.
+    DateFilter,
+    /// [`SearchRequest::temporal`](super::SearchRequest) filters.
+    TemporalFilter,
+    /// `as_of_frame` / `as_of_ts` time-travel views.
+    AsOf,
+    /// Frames appended after the first page of a paged search.
+    Watermark,
+    /// Sketch-track shortlist ahead of BM25.
+    SketchPrefilter,
+    /// Ranking engine, including URI/scope filters and query evaluation.
+    Engine,
+    /// Legacy lex index ranking, used when Tantivy is unavailable or left no
+    /// hits; replaces [`Self::Engine`].
+    LexFallback,
+    /// ACL filtering in `enforce` mode.
+    Acl,
+    /// ACL decisions in `audit` mode: frames the ACL allows. Denied hits are
+    /// still returned.
+    AclAudit,
+    /// Near-duplicate removal, collapsing and MMR.
+    Diversify,
+    /// `top_k` and cursor paging.
+    Pagination,
+    /// Score-distribution cutoff of adaptive vector retrieval, as `ask` applies it.
+    AdaptiveCutoff,
+}
+
+/// Candidate count after one stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageTrace {
+    pub stage: SearchStage,
+    /// Frames still eligible after the stage; `None` when the stage placed
+    /// no restriction (every frame remained a candidate).
+    pub candidates: Option<usize>,
+}
+
+/// Score breakdown as reported by Tantivy's `explain`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreExplanation {
+    pub value: f32,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<ScoreExplanation>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context: Vec<String>,
+}
+
+/// What happened to the explained frame.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum FrameVerdict {
+    /// Present in the response at `rank` (1-based).
+    Returned { rank: usize },
+    /// Removed by `stage`.
+    Dropped { stage: SearchStage, reason: String },
+}
+
+/// One ranked list's share of a frame's reciprocal rank fusion score.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RrfContribution {
+    /// `lexical` or `vector`.
+    pub list: String,
+    /// 1-based rank of the frame in that list.
+    pub rank: usize,
+    /// `1 / (k + rank)`.
+    pub contribution: f32,
+}
+
+/// Scoring details and verdict for one frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameExplanation {
+    pub frame_id: FrameId,
+    pub verdict: FrameVerdict,
+    /// Rank among the lexical matches, beyond the requested page; `None` past
+    /// the explain ranking depth.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lexical_rank: Option<usize>,
+    /// BM25 breakdown; `None` when Tantivy is unavailable or the frame does
+    /// not match the query.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bm25: Option<ScoreExplanation>,
+    /// L2 distance to the query embedding, when one was supplied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_distance: Option<f32>,
+    /// Rank among the nearest vectors, when one was supplied; `None` past
+    /// the explain ranking depth.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_rank: Option<usize>,
+    /// Hybrid fusion terms, when a query embedding was supplied.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rrf: Vec<RrfContribution>,
+}
+
+/// Structured trace of one search.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchExplanation {
+    /// Parsed query after synonym expansion.
+    pub query_tree: String,
+    pub engine: SearchEngineKind,
+    /// Candidate counts in pipeline order. Stages that did not run are absent.
+    pub stages: Vec<StageTrace>,
+    /// Present for [`Memvid::explain_search`](crate::Memvid::explain_search).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame: Option<FrameExplanation>,
+}
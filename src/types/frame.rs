@@ -13,7 +13,9 @@ use super::search::SearchHitTemporal;
 use super::temporal::TemporalFilter;
 use super::{
     common::{CanonicalEncoding, FrameId, FrameRole, FrameStatus, Tier},
+    embedding_cache::EmbeddingCacheStats,
     metadata::{DocMetadata, TextChunkManifest},
+    pii::PiiStats,
 };
 
 // Note: AnchorSource is always defined (not feature-gated) to maintain binary compatibility
@@ -142,6 +144,12 @@ pub struct Stats {
     /// Whether the vec (vector/semantic) search engine is enabled at runtime.
     #[serde(default)]
     pub vec_enabled: bool,
+    /// Persistent embedding cache size and session hit counters (`None` when disabled).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_cache: Option<EmbeddingCacheStats>,
+    /// Ingest-time PII findings and refusals (`None` when no policy was used).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pii: Option<PiiStats>,
 }
 
 /// Entry returned by `timeline` queries, carrying a lightweight preview.
@@ -704,7 +704,18 @@ pub struct LexSegmentManifest {
 pub enum VectorCompression {
     #[default]
     None, // Full f32 vectors (1,536 bytes for 384 dims)
-    Pq96, // Product quantization with 96 subspaces (96 bytes)
+    Pq96,   // Product quantization with 96 subspaces (96 bytes)
+    Int8,   // Per-dimension int8 scalar quantization (384 bytes), originals kept for rescoring
+    Binary, // One bit per dimension, Hamming search (48 bytes), originals kept for rescoring
+}
+
+impl VectorCompression {
+    /// Whether this mode is int8/binary quantization, which keeps the original
+    /// f32 vectors and can therefore be rebuilt losslessly.
+    #[must_use]
+    pub fn is_scalar_quantized(&self) -> bool {
+        matches!(self, Self::Int8 | Self::Binary)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -788,6 +799,30 @@ pub struct Toc {
     /// Tracks frames needing background Phase 2 work (full extraction + embeddings).
     #[serde(default)]
     pub enrichment_queue: EnrichmentQueueManifest,
+    /// Persistent embedding cache track.
+    #[serde(default)]
+    pub embedding_cache: Option<EmbeddingCacheManifest>,
+    /// Lexical schema: title boost and typed metadata fields.
+    #[serde(default)]
+    pub search_schema: Option<super::SearchSchema>,
+    /// User-defined synonym groups and query expansions.
+    #[serde(default)]
+    pub synonyms: Option<super::SynonymTrack>,
+    /// Standing queries and their match log.
+    #[serde(default)]
+    pub percolator: Option<super::PercolatorTrack>,
+    /// Hash salt and refusal counts of ingest-time PII policies.
+    #[serde(default)]
+    pub pii: Option<super::PiiTrack>,
+    /// IVF-PQ index segment, rewritten on each commit.
+    #[serde(default)]
+    pub ivf_index: Option<IvfIndexManifest>,
+    /// Standing query match sequence and delivered markers.
+    #[serde(default)]
+    pub percolator_delivery: Option<super::PercolatorDelivery>,
+    /// Recognized word layout of OCR'd frames.
+    #[serde(default)]
+    pub ocr_layout: Option<OcrLayoutManifest>,
     pub merkle_root: [u8; 32],
     pub toc_checksum: [u8; 32],
 }
@@ -859,6 +894,44 @@ pub struct SketchTrackManifest {
     pub checksum: [u8; 32],
 }
 
+/// Manifest for the embedding cache track.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EmbeddingCacheManifest {
+    /// Offset to the embedding cache data in the file.
+    pub bytes_offset: u64,
+    /// Length of the embedding cache data.
+    pub bytes_length: u64,
+    /// Number of cached embeddings.
+    pub entry_count: u64,
+    /// BLAKE3 checksum of the track data.
+    pub checksum: [u8; 32],
+}
+
+/// Manifest for the OCR layout track.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OcrLayoutManifest {
+    /// Offset to the OCR layout data in the file.
+    pub bytes_offset: u64,
+    /// Length of the OCR layout data.
+    pub bytes_length: u64,
+    /// Number of frames with recognized words.
+    pub entry_count: u64,
+    /// BLAKE3 checksum of the track data.
+    pub checksum: [u8; 32],
+}
+
+/// Manifest for the IVF-PQ index segment.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IvfIndexManifest {
+    pub bytes_offset: u64,
+    pub bytes_length: u64,
+    pub vector_count: u64,
+    pub dimension: u32,
+    pub nlist: u32,
+    /// BLAKE3 checksum of the segment.
+    pub checksum: [u8; 32],
+}
+
 /// Manifest for the enrichment queue (progressive ingestion).
 ///
 /// Tracks frames that need background enrichment (Phase 2: full extraction + embeddings).
@@ -7,7 +7,9 @@ pub mod audit;
 pub mod binding;
 pub mod common;
 pub mod embedding;
+pub mod embedding_cache;
 pub mod embedding_identity;
+pub mod explain;
 pub mod frame;
 pub mod graph_query;
 pub mod logic_mesh;
@@ -15,12 +17,18 @@ pub mod manifest;
 pub mod memories_track;
 pub mod memory_card;
 pub mod metadata;
+pub mod ocr_layout;
 pub mod options;
+pub mod percolator;
+pub mod pii;
 pub mod reranker;
 pub mod schema;
 pub mod search;
+pub mod search_schema;
 pub mod sketch_track;
 pub mod structure;
+pub mod sync;
+pub mod synonyms;
 #[cfg(feature = "temporal_track")]
 pub mod temporal;
 pub mod ticket;
@@ -36,6 +44,10 @@ pub use common::{
     CanonicalEncoding, EnrichmentState, EnrichmentTask, FrameId, FrameRole, FrameStatus,
     MemvidHandle, Open, Sealed, Tier,
 };
+pub use explain::{
+    FrameExplanation, FrameVerdict, RrfContribution, ScoreExplanation, SearchExplanation,
+    SearchStage, StageTrace,
+};
 // AnchorSource always exported - not feature-gated to maintain binary compatibility
 pub use frame::AnchorSource;
 pub use frame::{Frame, Stats, TimelineEntry, TimelineQuery, TimelineQueryBuilder};
@@ -43,11 +55,12 @@ pub use frame::{Frame, Stats, TimelineEntry, TimelineQuery, TimelineQueryBuilder
 pub use manifest::TemporalSegmentDescriptor;
 pub use manifest::TemporalTrackManifest;
 pub use manifest::{
-    EnrichmentQueueManifest, Header, IndexManifests, IndexSegmentRef, LexIndexManifest,
-    LexSegmentDescriptor, LexSegmentManifest, LogicMeshManifest, MemoriesTrackManifest,
-    SegmentCatalog, SegmentCommon, SegmentCompression, SegmentKind, SegmentMeta, SegmentSpan,
-    SegmentStats, SketchTrackManifest, TantivySegmentDescriptor, TimeIndexManifest,
-    TimeSegmentDescriptor, Toc, VecIndexManifest, VecSegmentDescriptor, VectorCompression,
+    EmbeddingCacheManifest, EnrichmentQueueManifest, Header, IndexManifests, IndexSegmentRef,
+    IvfIndexManifest, LexIndexManifest, LexSegmentDescriptor, LexSegmentManifest,
+    LogicMeshManifest, MemoriesTrackManifest, OcrLayoutManifest, SegmentCatalog, SegmentCommon,
+    SegmentCompression, SegmentKind, SegmentMeta, SegmentSpan, SegmentStats, SketchTrackManifest,
+    TantivySegmentDescriptor, TimeIndexManifest, TimeSegmentDescriptor, Toc, VecIndexManifest,
+    VecSegmentDescriptor, VectorCompression,
 };
 // Logic-Mesh types for entity-relationship graph traversal
 pub use logic_mesh::{
@@ -59,12 +72,32 @@ pub use metadata::{
     MediaManifest, TextChunkManifest, TextChunkRange,
 };
 pub use options::{PutManyOpts, PutOptions, PutOptionsBuilder, PutRequest};
+pub use percolator::{
+    MAX_PERCOLATOR_MATCHES, MAX_STANDING_QUERIES, PercolatorDelivery, PercolatorTrack,
+    StandingQuery, StandingQueryMatch,
+};
+pub use pii::{
+    PII_FINDINGS_METADATA_KEY, PiiFindings, PiiFrameReport, PiiMode, PiiPattern, PiiPolicy,
+    PiiReport, PiiStats, PiiTrack,
+};
 pub use search::{
-    SearchEngineKind, SearchHit, SearchHitEntity, SearchHitMetadata, SearchParams, SearchRequest,
-    SearchResponse,
+    DEFAULT_FACET_SIZE, DEFAULT_HIGHLIGHT_FRAGMENT_CHARS, DEFAULT_HIGHLIGHT_FRAGMENTS,
+    DiversifyOptions, FacetBucket, FacetField, FacetRequest, FacetResult, HighlightFragment,
+    HighlightOptions, HighlightSpan, HistogramInterval, MAX_HISTOGRAM_BUCKETS, SearchEngineKind,
+    SearchHit, SearchHitEntity, SearchHitMedia, SearchHitMetadata, SearchHitRegion, SearchParams,
+    SearchRequest, SearchResponse, SearchSort,
 };
 #[cfg(feature = "temporal_track")]
 pub use search::{SearchHitTemporal, SearchHitTemporalAnchor, SearchHitTemporalMention};
+pub use search_schema::{
+    ContentLanguage, DEFAULT_TITLE_BOOST, LANGUAGE_METADATA_KEY, MAX_METADATA_FIELDS,
+    METADATA_FIELD_PREFIX, MetadataFieldType, SearchSchema,
+};
+pub use sync::{
+    SYNC_HASH_METADATA_KEY, SYNC_MTIME_METADATA_KEY, SYNC_ROOT_METADATA_KEY,
+    SYNC_SIZE_METADATA_KEY, SyncOptions, SyncReport,
+};
+pub use synonyms::{MAX_SYNONYM_TERMS, QueryExpansion, SYNONYMS_METADATA_KEY, SynonymTrack};
 #[cfg(feature = "temporal_track")]
 pub use temporal::{
     TEMPORAL_TRACK_FLAG_HAS_ANCHORS, TEMPORAL_TRACK_FLAG_HAS_MENTIONS, TemporalAnchor,
@@ -93,11 +126,17 @@ pub use embedding::{
     BatchEmbeddingResult, EmbeddingConfig, EmbeddingProvider, EmbeddingProviderKind,
     EmbeddingResult,
 };
+pub use embedding_cache::{
+    DEFAULT_EMBEDDING_CACHE_MAX_BYTES, DEFAULT_EMBEDDING_CACHE_MAX_ENTRIES, EMBEDDING_CACHE_MAGIC,
+    EMBEDDING_CACHE_VERSION, EmbeddingCacheConfig, EmbeddingCacheStats, EmbeddingCacheTrack,
+    normalize_cache_text,
+};
 pub use embedding_identity::{
     EmbeddingIdentity, EmbeddingIdentityCount, EmbeddingIdentitySummary,
     MEMVID_EMBEDDING_DIMENSION_KEY, MEMVID_EMBEDDING_MODEL_KEY, MEMVID_EMBEDDING_NORMALIZED_KEY,
     MEMVID_EMBEDDING_PROVIDER_KEY,
 };
+pub use ocr_layout::{OCR_LAYOUT_MAGIC, OCR_LAYOUT_VERSION, OcrLayoutTrack};
 // Structure-aware chunking types for preserving tables and code blocks
 pub use structure::{
     ChunkType, ChunkingOptions, ChunkingResult, CodeChunkingStrategy, DocumentElement, ElementData,
@@ -0,0 +1,170 @@
+//! OCR layout track: the recognized words of frames with OCR text, keyed by
+//! frame id and persisted as one segment inside `.mv2`.
+//!
+//! Words reach commit in the WAL entry's `extra_metadata` under
+//! [`OCR_WORDS_METADATA_KEY`](crate::OCR_WORDS_METADATA_KEY); applying the
+//! entry moves them here, so frames and the TOC stay free of the layout.
+
+use std::collections::BTreeMap;
+
+use super::FrameId;
+use crate::ocr::{OcrWord, decode_ocr_words};
+use crate::{MemvidError, Result};
+
+/// Magic bytes for the OCR layout blob.
+pub const OCR_LAYOUT_MAGIC: &[u8; 4] = b"MVOL";
+
+/// Current schema version.
+pub const OCR_LAYOUT_VERSION: u16 = 1;
+
+/// Upper bound on allocations while decoding a persisted layout track.
+#[allow(clippy::cast_possible_truncation)]
+const LAYOUT_DECODE_LIMIT: usize = crate::MAX_INDEX_BYTES as usize;
+
+/// Recognized words per frame, in the compact form of
+/// [`encode_ocr_words`](crate::encode_ocr_words).
+#[derive(Debug, Clone, Default)]
+pub struct OcrLayoutTrack {
+    words: BTreeMap<FrameId, String>,
+    dirty: bool,
+}
+
+impl OcrLayoutTrack {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Whether entries changed since the track was loaded or last persisted.
+    #[must_use]
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Force a rewrite on the next commit, e.g. after the segment was dropped.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Store the encoded words of `frame_id`, replacing earlier ones.
+    pub fn insert(&mut self, frame_id: FrameId, encoded: String) {
+        self.words.insert(frame_id, encoded);
+        self.dirty = true;
+    }
+
+    /// Encoded words of `frame_id`.
+    #[must_use]
+    pub fn encoded(&self, frame_id: FrameId) -> Option<&str> {
+        self.words.get(&frame_id).map(String::as_str)
+    }
+
+    /// Decoded words of `frame_id`.
+    #[must_use]
+    pub fn words(&self, frame_id: FrameId) -> Option<Vec<OcrWord>> {
+        self.encoded(frame_id).map(decode_ocr_words)
+    }
+
+    /// Serialize the track to bytes.
+    pub fn serialize(&self) -> Result<Vec<u8>> {
+        let config = bincode::config::standard()
+            .with_fixed_int_encoding()
+            .with_little_endian();
+        let payload = bincode::serde::encode_to_vec(&self.words, config).map_err(|e| {
+            MemvidError::InvalidToc {
+                reason: format!("OCR layout serialization failed: {e}").into(),
+            }
+        })?;
+
+        let mut buf = Vec::with_capacity(14 + payload.len());
+        buf.extend_from_slice(OCR_LAYOUT_MAGIC);
+        buf.extend_from_slice(&OCR_LAYOUT_VERSION.to_le_bytes());
+        buf.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+        buf.extend(payload);
+        Ok(buf)
+    }
+
+    /// Deserialize a track from bytes.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self> {
+        let invalid = |reason: String| MemvidError::InvalidToc {
+            reason: reason.into(),
+        };
+        if bytes.len() < 14 {
+            return Err(invalid("OCR layout blob too short".to_string()));
+        }
+        if &bytes[0..4] != OCR_LAYOUT_MAGIC {
+            return Err(invalid("OCR layout has invalid magic bytes".to_string()));
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version > OCR_LAYOUT_VERSION {
+            return Err(invalid(format!(
+                "unsupported OCR layout version: {version}"
+            )));
+        }
+        let mut len_bytes = [0u8; 8];
+        len_bytes.copy_from_slice(&bytes[6..14]);
+        let payload_len = usize::try_from(u64::from_le_bytes(len_bytes)).unwrap_or(usize::MAX);
+        let payload = bytes
+            .get(14..)
+            .and_then(|rest| rest.get(..payload_len))
+            .ok_or_else(|| invalid("OCR layout blob truncated".to_string()))?;
+
+        let config = bincode::config::standard()
+            .with_fixed_int_encoding()
+            .with_little_endian()
+            .with_limit::<LAYOUT_DECODE_LIMIT>();
+        let (words, _): (BTreeMap<FrameId, String>, _) =
+            bincode::serde::decode_from_slice(payload, config)
+                .map_err(|e| invalid(format!("OCR layout deserialization failed: {e}")))?;
+
+        Ok(Self {
+            words,
+            dirty: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ocr::{OcrBox, encode_ocr_words};
+
+    #[test]
+    fn serialize_roundtrip() {
+        let word = OcrWord {
+            text: "Invoice".to_string(),
+            page: 2,
+            bbox: OcrBox {
+                x: 0.1,
+                y: 0.2,
+                width: 0.3,
+                height: 0.05,
+            },
+            confidence: 0.9,
+        };
+        let mut track = OcrLayoutTrack::new();
+        track.insert(7, encode_ocr_words(std::slice::from_ref(&word)));
+        assert!(track.is_dirty());
+
+        let restored = OcrLayoutTrack::deserialize(&track.serialize().unwrap()).unwrap();
+        assert!(!restored.is_dirty());
+        assert_eq!(restored.len(), 1);
+        let words = restored.words(7).unwrap();
+        assert_eq!(words[0].text, "Invoice");
+        assert_eq!(words[0].page, 2);
+        assert!(restored.words(8).is_none());
+    }
+}
@@ -6,7 +6,9 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use super::common::{FrameId, FrameRole};
+use super::embedding_identity::EmbeddingIdentity;
 use super::metadata::DocMetadata;
+use super::pii::PiiPolicy;
 
 fn default_true() -> bool {
     true
@@ -71,6 +73,12 @@ pub struct PutOptions {
     /// Default: 350ms (optimized for sub-second total ingestion).
     #[serde(default = "default_extraction_budget_ms")]
     pub extraction_budget_ms: u64,
+    /// Redact or refuse PII before the frame is stored and indexed.
+    /// Text payloads are rewritten. Binary payloads cannot be, so under the
+    /// mask and hash modes they are stored as with `no_raw`: only their
+    /// redacted extracted text and the hash of the original bytes are kept.
+    #[serde(default)]
+    pub pii: Option<PiiPolicy>,
 }
 
 fn default_extraction_budget_ms() -> u64 {
@@ -101,6 +109,7 @@ impl Default for PutOptions {
             dedup: false,
             instant_index: true, // Instant searchability by default
             extraction_budget_ms: default_extraction_budget_ms(),
+            pii: None,
         }
     }
 }
@@ -150,6 +159,13 @@ impl PutOptionsBuilder {
         self
     }
 
+    /// Record the embedding identity used for this frame's vectors.
+    #[must_use]
+    pub fn embedding_identity(mut self, identity: &EmbeddingIdentity) -> Self {
+        identity.write_extra_metadata(&mut self.inner.extra_metadata);
+        self
+    }
+
     #[must_use]
     pub fn metadata(mut self, metadata: DocMetadata) -> Self {
         self.inner.metadata = Some(metadata);
@@ -253,6 +269,13 @@ impl PutOptionsBuilder {
         self
     }
 
+    /// Redact or refuse PII found in the document under `policy`.
+    #[must_use]
+    pub fn pii(mut self, policy: PiiPolicy) -> Self {
+        self.inner.pii = Some(policy);
+        self
+    }
+
     #[must_use]
     pub fn build(self) -> PutOptions {
         self.inner
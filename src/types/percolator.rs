@@ -0,0 +1,316 @@
+//! Standing queries evaluated against newly committed frames.
+//!
+//! A standing query is a saved lexical query, an embedding with a similarity
+//! threshold, or both (a frame must then satisfy each). Every commit checks
+//! the frames it adds, enrichment re-checks frames whose text was upgraded,
+//! and each match is appended to a bounded log keyed by a strictly increasing
+//! match sequence.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+use crate::error::{MemvidError, Result};
+
+/// Matches retained in the log; the oldest are dropped first.
+pub const MAX_PERCOLATOR_MATCHES: usize = 10_000;
+
+/// Maximum number of registered standing queries.
+pub const MAX_STANDING_QUERIES: usize = 1_024;
+
+/// A saved query that fires on newly ingested frames.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StandingQuery {
+    /// Caller-chosen identifier, unique within the memory.
+    pub id: String,
+    /// Query in `search` syntax; empty for vector-only queries.
+    pub query: String,
+    /// Embedding compared against each frame's embedding.
+    #[serde(default)]
+    pub embedding: Option<Vec<f32>>,
+    /// Minimum cosine similarity for `embedding` to match.
+    #[serde(default)]
+    pub min_similarity: Option<f32>,
+}
+
+impl StandingQuery {
+    #[must_use]
+    pub fn new(id: impl Into<String>, query: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            query: query.into(),
+            embedding: None,
+            min_similarity: None,
+        }
+    }
+
+    /// Also require the frame embedding to be within `min_similarity` of `embedding`.
+    #[must_use]
+    pub fn with_vector(mut self, embedding: Vec<f32>, min_similarity: f32) -> Self {
+        self.embedding = Some(embedding);
+        self.min_similarity = Some(min_similarity);
+        self
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.id.trim().is_empty() {
+            return Err(MemvidError::InvalidQuery {
+                reason: "standing query id is empty".to_string(),
+            });
+        }
+        if self.query.trim().is_empty() && self.embedding.is_none() {
+            return Err(MemvidError::InvalidQuery {
+                reason: format!(
+                    "standing query `{}` has neither a query nor a vector",
+                    self.id
+                ),
+            });
+        }
+        if let Some(embedding) = &self.embedding {
+            if embedding.is_empty() {
+                return Err(MemvidError::InvalidQuery {
+                    reason: format!("standing query `{}` has an empty vector", self.id),
+                });
+            }
+            if !self
+                .min_similarity
+                .is_some_and(|threshold| (-1.0..=1.0).contains(&threshold))
+            {
+                return Err(MemvidError::InvalidQuery {
+                    reason: format!(
+                        "standing query `{}` needs a similarity threshold between -1 and 1",
+                        self.id
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One frame that satisfied a standing query.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StandingQueryMatch {
+    pub query_id: String,
+    pub frame_id: FrameId,
+    /// Match sequence: the WAL sequence of the record that inserted the
+    /// frame, raised above every earlier match so that sequences strictly
+    /// increase. Enrichment matches are numbered after the latest match.
+    pub wal_sequence: u64,
+    /// Cosine similarity, for queries with a vector.
+    #[serde(default)]
+    pub similarity: Option<f32>,
+    /// Unix timestamp (seconds) when the match was recorded.
+    pub matched_at: i64,
+}
+
+/// Registered standing queries and their match log.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PercolatorTrack {
+    pub queries: Vec<StandingQuery>,
+    /// Matches in recording order, capped at [`MAX_PERCOLATOR_MATCHES`].
+    pub matches: Vec<StandingQueryMatch>,
+}
+
+impl PercolatorTrack {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty() && self.matches.is_empty()
+    }
+
+    /// Register `query`, replacing any query with the same id. Returns
+    /// whether one was replaced.
+    pub fn register(&mut self, query: StandingQuery) -> Result<bool> {
+        query.validate()?;
+        if let Some(existing) = self.queries.iter_mut().find(|q| q.id == query.id) {
+            *existing = query;
+            return Ok(true);
+        }
+        if self.queries.len() >= MAX_STANDING_QUERIES {
+            return Err(MemvidError::InvalidQuery {
+                reason: format!("at most {MAX_STANDING_QUERIES} standing queries"),
+            });
+        }
+        self.queries.push(query);
+        Ok(false)
+    }
+
+    /// Remove the query `id` and its logged matches. Returns whether it existed.
+    pub fn remove(&mut self, id: &str) -> bool {
+        let before = self.queries.len();
+        self.queries.retain(|query| query.id != id);
+        if self.queries.len() == before {
+            return false;
+        }
+        self.matches.retain(|entry| entry.query_id != id);
+        true
+    }
+
+    /// Whether `query_id` already matched `frame_id`.
+    #[must_use]
+    pub fn has_match(&self, query_id: &str, frame_id: FrameId) -> bool {
+        self.matches
+            .iter()
+            .any(|entry| entry.frame_id == frame_id && entry.query_id == query_id)
+    }
+
+    /// Append `entry`, dropping the oldest matches beyond the cap.
+    pub fn record(&mut self, entry: StandingQueryMatch) {
+        self.matches.push(entry);
+        if self.matches.len() > MAX_PERCOLATOR_MATCHES {
+            let excess = self.matches.len() - MAX_PERCOLATOR_MATCHES;
+            self.matches.drain(..excess);
+        }
+    }
+
+    /// Logged matches with a WAL sequence greater than `sequence`.
+    #[must_use]
+    pub fn matches_after(&self, sequence: u64) -> Vec<StandingQueryMatch> {
+        self.matches
+            .iter()
+            .filter(|entry| entry.wal_sequence > sequence)
+            .cloned()
+            .collect()
+    }
+
+    /// Drop logged matches up to and including `sequence`. Returns how many.
+    pub fn acknowledge(&mut self, sequence: u64) -> usize {
+        let before = self.matches.len();
+        self.matches.retain(|entry| entry.wal_sequence > sequence);
+        before - self.matches.len()
+    }
+}
+
+/// Delivery state kept beside the match log. Acknowledging matches drops
+/// log entries, not these markers, so a frame fires a query at most once.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PercolatorDelivery {
+    /// Highest match sequence handed out.
+    pub last_sequence: u64,
+    /// Frames each query has matched, by query id.
+    pub delivered: BTreeMap<String, BTreeSet<FrameId>>,
+}
+
+impl PercolatorDelivery {
+    /// Seed delivery state from a match log written before it was tracked.
+    #[must_use]
+    pub fn from_log(track: &PercolatorTrack) -> Self {
+        let mut delivery = Self::default();
+        for entry in &track.matches {
+            delivery.last_sequence = delivery.last_sequence.max(entry.wal_sequence);
+            delivery.mark(&entry.query_id, entry.frame_id);
+        }
+        delivery
+    }
+
+    /// Whether `query_id` already fired for `frame_id`.
+    #[must_use]
+    pub fn is_delivered(&self, query_id: &str, frame_id: FrameId) -> bool {
+        self.delivered
+            .get(query_id)
+            .is_some_and(|frames| frames.contains(&frame_id))
+    }
+
+    /// Mark `frame_id` as delivered to `query_id`. Returns whether it was new.
+    pub fn mark(&mut self, query_id: &str, frame_id: FrameId) -> bool {
+        self.delivered
+            .entry(query_id.to_string())
+            .or_default()
+            .insert(frame_id)
+    }
+
+    /// Sequence for a match on a frame inserted at `wal_sequence`: that
+    /// sequence, or one past the previous match if it is not greater.
+    pub fn next_sequence(&mut self, wal_sequence: u64) -> u64 {
+        let sequence = wal_sequence.max(self.last_sequence.saturating_add(1));
+        self.last_sequence = sequence;
+        sequence
+    }
+
+    /// Drop the markers of a removed query.
+    pub fn forget(&mut self, query_id: &str) {
+        self.delivered.remove(query_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(query_id: &str, frame_id: FrameId, wal_sequence: u64) -> StandingQueryMatch {
+        StandingQueryMatch {
+            query_id: query_id.to_string(),
+            frame_id,
+            wal_sequence,
+            similarity: None,
+            matched_at: 0,
+        }
+    }
+
+    #[test]
+    fn register_validates_and_replaces_by_id() {
+        let mut track = PercolatorTrack::default();
+        assert!(
+            !track
+                .register(StandingQuery::new("inc", "INC-1234"))
+                .unwrap()
+        );
+        assert!(
+            track
+                .register(StandingQuery::new("inc", "INC-9999"))
+                .unwrap()
+        );
+        assert_eq!(track.queries.len(), 1);
+        assert_eq!(track.queries[0].query, "INC-9999");
+
+        assert!(track.register(StandingQuery::new(" ", "x")).is_err());
+        assert!(track.register(StandingQuery::new("empty", "")).is_err());
+        let out_of_range = StandingQuery::new("vec", "").with_vector(vec![1.0], 1.5);
+        assert!(track.register(out_of_range).is_err());
+        let vector_only = StandingQuery::new("vec", "").with_vector(vec![1.0], 0.8);
+        assert!(!track.register(vector_only).unwrap());
+    }
+
+    #[test]
+    fn match_log_is_keyed_by_sequence_and_capped() {
+        let mut track = PercolatorTrack::default();
+        track.register(StandingQuery::new("a", "alpha")).unwrap();
+        track.record(entry("a", 0, 2));
+        track.record(entry("a", 1, 3));
+        assert!(track.has_match("a", 1));
+        assert_eq!(track.matches_after(2), [entry("a", 1, 3)]);
+        assert_eq!(track.acknowledge(2), 1);
+        assert_eq!(track.matches, [entry("a", 1, 3)]);
+
+        for frame_id in 0..=MAX_PERCOLATOR_MATCHES as FrameId {
+            track.record(entry("a", frame_id, frame_id + 10));
+        }
+        assert_eq!(track.matches.len(), MAX_PERCOLATOR_MATCHES);
+        assert_eq!(track.matches[0].frame_id, 1);
+
+        assert!(track.remove("a"));
+        assert!(track.is_empty());
+    }
+
+    #[test]
+    fn delivery_sequences_increase_and_survive_acknowledge() {
+        let mut track = PercolatorTrack::default();
+        track.register(StandingQuery::new("a", "alpha")).unwrap();
+        track.record(entry("a", 0, 4));
+        let mut delivery = PercolatorDelivery::from_log(&track);
+        assert_eq!(delivery.last_sequence, 4);
+        assert!(delivery.is_delivered("a", 0));
+
+        assert_eq!(delivery.next_sequence(4), 5);
+        assert_eq!(delivery.next_sequence(9), 9);
+        assert_eq!(delivery.next_sequence(2), 10);
+
+        assert_eq!(track.acknowledge(4), 1);
+        assert!(delivery.is_delivered("a", 0));
+        assert!(!delivery.mark("a", 0));
+        assert!(delivery.mark("a", 1));
+        delivery.forget("a");
+        assert!(!delivery.is_delivered("a", 1));
+    }
+}
@@ -0,0 +1,177 @@
+//! Ingest-time PII policy, the per-file state it keeps in the TOC, and the
+//! findings it records on frames.
+//!
+//! A policy set on [`PutOptions`](super::PutOptions) scans the payload, the
+//! extracted text, the title and any caller search text before they are stored
+//! or indexed. Every frame written under a policy carries a findings record in
+//! its `extra_metadata`, so an audit can tell scanned-and-clean frames apart
+//! from frames ingested without one.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+
+/// `extra_metadata` key holding a frame's [`PiiFindings`] as JSON.
+pub const PII_FINDINGS_METADATA_KEY: &str = "memvid.pii.findings";
+
+/// What to do with PII found in a document.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiMode {
+    /// Replace each finding with a placeholder such as `[EMAIL]`.
+    #[default]
+    Mask,
+    /// Replace each finding with a placeholder carrying a salted hash of the
+    /// value, such as `[EMAIL:3f9a0c12d4e7]`. Equal values hash alike within
+    /// a file, so they can still be correlated, but not across files.
+    ///
+    /// This is linkable pseudonymisation, not protection: the salt is stored
+    /// in the file's [`PiiTrack`] and the placeholder keeps 48 bits of the
+    /// hash, so anyone holding the file can confirm a guessed value, and
+    /// enumerate values from a small space such as SSNs, phone numbers or
+    /// card numbers with a known prefix. Use [`PiiMode::Mask`] when the values
+    /// themselves must not be recoverable.
+    Hash,
+    /// Fail the put with [`MemvidError::IngestRejected`](crate::MemvidError::IngestRejected).
+    Reject,
+}
+
+impl PiiMode {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Mask => "mask",
+            Self::Hash => "hash",
+            Self::Reject => "reject",
+        }
+    }
+}
+
+/// A caller-defined detector.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PiiPattern {
+    /// Kind reported in findings; also the placeholder, uppercased.
+    pub name: String,
+    /// Regular expression matching the sensitive value.
+    pub pattern: String,
+}
+
+impl PiiPattern {
+    #[must_use]
+    pub fn new(name: impl Into<String>, pattern: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            pattern: pattern.into(),
+        }
+    }
+}
+
+/// How a put treats PII.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PiiPolicy {
+    pub mode: PiiMode,
+    /// Detectors run alongside the built-in ones.
+    #[serde(default)]
+    pub custom_patterns: Vec<PiiPattern>,
+}
+
+impl PiiPolicy {
+    #[must_use]
+    pub fn mask() -> Self {
+        Self::with_mode(PiiMode::Mask)
+    }
+
+    #[must_use]
+    pub fn hash() -> Self {
+        Self::with_mode(PiiMode::Hash)
+    }
+
+    #[must_use]
+    pub fn reject() -> Self {
+        Self::with_mode(PiiMode::Reject)
+    }
+
+    #[must_use]
+    pub fn with_mode(mode: PiiMode) -> Self {
+        Self {
+            mode,
+            custom_patterns: Vec::new(),
+        }
+    }
+
+    /// Also detect `pattern`, reporting it as `name`.
+    #[must_use]
+    pub fn with_pattern(mut self, name: impl Into<String>, pattern: impl Into<String>) -> Self {
+        self.custom_patterns.push(PiiPattern::new(name, pattern));
+        self
+    }
+}
+
+/// Per-file PII state persisted in the TOC.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PiiTrack {
+    /// Key for [`PiiMode::Hash`] placeholders, generated with the track. It
+    /// is stored unencrypted, so it only keeps hashes from linking across
+    /// files.
+    pub salt: [u8; 32],
+    /// Puts refused under [`PiiMode::Reject`].
+    pub rejected_puts: u64,
+    /// Findings of the refused puts, by kind.
+    #[serde(default)]
+    pub rejected_findings: BTreeMap<String, u64>,
+}
+
+/// What a policy found in one frame, stored under [`PII_FINDINGS_METADATA_KEY`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PiiFindings {
+    pub mode: PiiMode,
+    /// Number of findings by kind; empty when the frame was clean.
+    #[serde(default)]
+    pub counts: BTreeMap<String, u64>,
+}
+
+impl PiiFindings {
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    pub(crate) fn record(&mut self, kind: &str) {
+        *self.counts.entry(kind.to_string()).or_default() += 1;
+    }
+}
+
+/// PII totals reported in [`Stats`](super::Stats).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PiiStats {
+    /// Active frames ingested under a policy.
+    pub frames_scanned: u64,
+    /// Of those, frames where something was found.
+    pub frames_with_findings: u64,
+    /// Findings masked or hashed in active frames.
+    pub redacted: u64,
+    /// Puts refused under [`PiiMode::Reject`].
+    pub rejected_puts: u64,
+}
+
+/// One frame's entry in a [`PiiReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PiiFrameReport {
+    pub frame_id: FrameId,
+    pub uri: Option<String>,
+    pub findings: PiiFindings,
+}
+
+/// What ingest-time PII policies found and did across a memory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PiiReport {
+    pub stats: PiiStats,
+    /// Findings in active frames, by kind.
+    pub redacted_by_kind: BTreeMap<String, u64>,
+    /// Findings in refused puts, by kind.
+    pub rejected_by_kind: BTreeMap<String, u64>,
+    /// Active frames with findings, in frame order.
+    pub frames: Vec<PiiFrameReport>,
+}
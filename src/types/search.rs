@@ -4,8 +4,10 @@ use serde::{Deserialize, Serialize};
 
 use super::acl::{AclContext, AclEnforcementMode};
 use super::common::FrameId;
+use super::explain::SearchExplanation;
 #[cfg(feature = "temporal_track")]
 use super::frame::AnchorSource;
+use super::synonyms::QueryExpansion;
 #[cfg(feature = "temporal_track")]
 use super::temporal::{TemporalFilter, TemporalMentionFlags, TemporalMentionKind};
 
@@ -72,6 +74,229 @@ pub struct SearchRequest {
     #[serde(default)]
     /// ACL evaluation mode (`audit` or `enforce`).
     pub acl_enforcement_mode: AclEnforcementMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Optional diversification (near-duplicate removal, collapse, MMR).
+    pub diversify: Option<DiversifyOptions>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Return the best matching fragments of each hit with term offsets.
+    pub highlight: Option<HighlightOptions>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    /// Counts over the full match set (not just this page), after ACL filtering.
+    pub facets: Vec<FacetRequest>,
+    #[serde(default)]
+    /// Result ordering. Sorted (non-relevance) searches return one hit per frame.
+    pub sort: SearchSort,
+    #[serde(default)]
+    /// Attach a [`SearchExplanation`] with per-stage candidate counts.
+    pub explain: bool,
+}
+
+/// Result ordering for [`SearchRequest::sort`].
+///
+/// Every ordering pages with opaque search-after cursors that pin the match
+/// set to the frames present when the first page was served, so frames
+/// appended between pages don't shift results. Cursors fix the position, not
+/// the cost: a relevance page re-ranks every hit already served, and a sorted
+/// page evaluates every candidate to keep `total_hits` exact.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchSort {
+    /// BM25 with recency boost.
+    #[default]
+    Relevance,
+    /// Oldest first; ties broken by ascending frame id.
+    TimestampAsc,
+    /// Newest first; ties broken by descending frame id.
+    TimestampDesc,
+    /// Ascending frame id, i.e. ingestion order.
+    FrameId,
+}
+
+impl SearchSort {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Relevance => "relevance",
+            Self::TimestampAsc => "timestamp_asc",
+            Self::TimestampDesc => "timestamp_desc",
+            Self::FrameId => "frame_id",
+        }
+    }
+}
+
+/// Default number of fragments returned per hit.
+pub const DEFAULT_HIGHLIGHT_FRAGMENTS: usize = 3;
+/// Default fragment length in characters.
+pub const DEFAULT_HIGHLIGHT_FRAGMENT_CHARS: usize = 160;
+
+/// Multi-fragment highlighting. Terms are located with the same analyzer
+/// chain used for indexing (stemming, Jieba segmentation), so a query for
+/// `approvals` marks `approval` and CJK words are matched whole.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighlightOptions {
+    /// Maximum fragments per hit, best first.
+    pub max_fragments: usize,
+    /// Target fragment length in characters (grown to fit a long term).
+    pub fragment_chars: usize,
+}
+
+impl Default for HighlightOptions {
+    fn default() -> Self {
+        Self {
+            max_fragments: DEFAULT_HIGHLIGHT_FRAGMENTS,
+            fragment_chars: DEFAULT_HIGHLIGHT_FRAGMENT_CHARS,
+        }
+    }
+}
+
+/// A fragment of the hit's frame text containing one or more matched terms.
+///
+/// `range`/`char_range` are offsets into the frame text, in the same
+/// coordinates as [`SearchHit::range`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighlightFragment {
+    pub text: String,
+    pub range: (usize, usize),
+    pub char_range: (usize, usize),
+    /// Distinct matched terms, plus a small bonus for repeats.
+    pub score: f32,
+    pub spans: Vec<HighlightSpan>,
+}
+
+/// One matched term inside a [`HighlightFragment`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighlightSpan {
+    /// Query term after analysis (e.g. the English stem).
+    pub term: String,
+    /// Byte offsets within the fragment text.
+    pub fragment_range: (usize, usize),
+    /// Character offsets within the fragment text.
+    pub fragment_char_range: (usize, usize),
+    /// Byte offsets within the frame text.
+    pub range: (usize, usize),
+    /// Character offsets within the frame text.
+    pub char_range: (usize, usize),
+}
+
+/// Default number of buckets returned per term facet.
+pub const DEFAULT_FACET_SIZE: usize = 10;
+/// Upper bound on buckets in a timestamp histogram, gaps included.
+pub const MAX_HISTOGRAM_BUCKETS: usize = 10_000;
+
+/// A facet to count over every frame matching the query.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FacetRequest {
+    pub field: FacetField,
+    /// Maximum buckets returned, by descending count. Ignored by histograms.
+    #[serde(default = "default_facet_size")]
+    pub size: usize,
+}
+
+fn default_facet_size() -> usize {
+    DEFAULT_FACET_SIZE
+}
+
+impl FacetRequest {
+    #[must_use]
+    pub fn new(field: FacetField) -> Self {
+        Self {
+            field,
+            size: DEFAULT_FACET_SIZE,
+        }
+    }
+
+    #[must_use]
+    pub fn with_size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+}
+
+/// Frame attribute a facet groups matches by.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FacetField {
+    Tag,
+    Label,
+    Track,
+    /// URI truncated after `depth` path segments, e.g. `mv2://docs/` for depth 1.
+    UriPrefix {
+        depth: usize,
+    },
+    /// Value of an `extra_metadata` key (matched case-insensitively).
+    Metadata {
+        key: String,
+    },
+    /// Hits per interval, by frame timestamp. Empty intervals between the
+    /// first and last bucket are included so the series can be plotted as is.
+    Timestamp {
+        interval: HistogramInterval,
+    },
+}
+
+/// Bucket width of a timestamp histogram. Buckets are aligned in UTC; weeks
+/// start on Monday.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistogramInterval {
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+/// Counts for one [`FacetRequest`], in request order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FacetResult {
+    pub field: FacetField,
+    /// Term buckets by descending count, or histogram buckets in time order.
+    pub buckets: Vec<FacetBucket>,
+    /// Values left out of `buckets` by the size limit.
+    #[serde(default)]
+    pub other: usize,
+    /// Matching frames without a value for this field.
+    #[serde(default)]
+    pub missing: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FacetBucket {
+    /// Facet value; RFC 3339 bucket start for histograms.
+    pub key: String,
+    /// Matching frames in this bucket.
+    pub count: usize,
+    /// Bucket start as a Unix timestamp (histograms only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_ts: Option<i64>,
+}
+
+/// Post-retrieval diversification so repeated chunks of one document or
+/// near-identical frames don't crowd out `top_k`.
+///
+/// Similarity uses stored embeddings when both hits have one, and `SimHash`
+/// Hamming similarity (from the sketch track or the hit text) otherwise.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiversifyOptions {
+    /// MMR trade-off between relevance (1.0) and novelty (0.0). `None` disables MMR.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmr_lambda: Option<f32>,
+    /// Drop hits at least this similar (0.0-1.0) to a higher ranked hit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub near_duplicate_threshold: Option<f32>,
+    /// Keep only the best hit per parent document / base URI.
+    #[serde(default)]
+    pub collapse_by_document: bool,
+}
+
+impl DiversifyOptions {
+    /// Whether any diversification stage is enabled.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.mmr_lambda.is_some()
+            || self.near_duplicate_threshold.is_some()
+            || self.collapse_by_document
+    }
 }
 
 /// A single ranked hit with snippet metadata.
@@ -93,6 +318,9 @@ pub struct SearchHit {
     pub score: Option<f32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<SearchHitMetadata>,
+    /// Best matching fragments, filled when [`SearchRequest::highlight`] is set.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<HighlightFragment>,
 }
 
 /// Entity reference in search hit metadata.
@@ -107,6 +335,31 @@ pub struct SearchHitEntity {
     pub confidence: Option<f32>,
 }
 
+/// Media offset of a hit in a transcript: the cue the match falls in.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SearchHitMedia {
+    /// Seconds from the start of the recording.
+    pub start_seconds: f32,
+    pub end_seconds: f32,
+    /// Speaker of the cue, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Page region of a hit in recognized text: the words the match falls on.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SearchHitRegion {
+    /// 1-based page number; 1 for images.
+    pub page: u32,
+    /// Bounding box as fractions of the page, origin top left.
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// The matched words.
+    pub text: String,
+}
+
 /// Optional per-hit metadata (tags, labels, dates, temporal context).
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SearchHitMetadata {
@@ -128,6 +381,12 @@ pub struct SearchHitMetadata {
     /// Custom user-defined metadata stored with the frame via `PutOptions.extra_metadata`.
     #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
     pub extra_metadata: std::collections::BTreeMap<String, String>,
+    /// Where in the recording the hit was said, for transcript frames.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media: Option<SearchHitMedia>,
+    /// Where on the page the hit was read, for frames with recognized text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<SearchHitRegion>,
     #[cfg(feature = "temporal_track")]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub temporal: Option<SearchHitTemporal>,
@@ -176,7 +435,8 @@ pub struct SearchResponse {
     pub query: String,
     /// Milliseconds spent satisfying the request.
     pub elapsed_ms: u128,
-    /// Total hits found (without pagination applied).
+    /// Total hits found (without pagination applied). Sorted searches report
+    /// the frames the index matched, before in-memory query evaluation.
     pub total_hits: usize,
     /// Parameters used for this request, including cursors.
     pub params: SearchParams,
@@ -185,9 +445,21 @@ pub struct SearchResponse {
     /// Concatenated snippets or context paragraphs.
     pub context: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    /// Cursor for fetching the next page, if any.
+    /// Opaque cursor for fetching the next page, if any.
     pub next_cursor: Option<String>,
     #[serde(default)]
     /// Engine responsible for the results.
     pub engine: SearchEngineKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// "Did you mean" rewrite of the query, offered when results are empty or sparse.
+    pub suggested_query: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    /// One entry per requested facet.
+    pub facets: Vec<FacetResult>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    /// Query terms widened by the memory's synonyms, in query order.
+    pub query_expansions: Vec<QueryExpansion>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Pipeline trace, when [`SearchRequest::explain`] was set.
+    pub explanation: Option<SearchExplanation>,
 }
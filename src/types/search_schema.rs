@@ -0,0 +1,229 @@
+//! Per-memory lexical schema: title boost and typed `extra_metadata` fields.
+//!
+//! Declared metadata keys become dedicated Tantivy fields that the query
+//! language addresses as `meta.<key>:value` or `meta.<key>:[lo TO hi]`.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MemvidError, Result};
+
+/// Boost applied to title matches when no schema has been configured.
+pub const DEFAULT_TITLE_BOOST: f32 = 2.0;
+/// Prefix used for metadata fields in queries and in the Tantivy schema.
+pub const METADATA_FIELD_PREFIX: &str = "meta.";
+/// Maximum number of declared metadata fields per memory.
+pub const MAX_METADATA_FIELDS: usize = 64;
+/// `extra_metadata` key holding the detected (or caller-supplied) content
+/// language as an ISO 639-1 code.
+pub const LANGUAGE_METADATA_KEY: &str = "memvid.language";
+
+/// How an `extra_metadata` value is indexed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataFieldType {
+    /// Exact, case-insensitive match on the whole value.
+    Keyword,
+    /// Tokenised and stemmed like frame content.
+    Text,
+    /// Signed integer; supports range clauses.
+    I64,
+    /// Floating point number; supports range clauses.
+    F64,
+    /// RFC 3339 or `YYYY[-MM[-DD]]` date stored as a Unix timestamp; supports range clauses.
+    Date,
+}
+
+impl MetadataFieldType {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Keyword => "keyword",
+            Self::Text => "text",
+            Self::I64 => "i64",
+            Self::F64 => "f64",
+            Self::Date => "date",
+        }
+    }
+
+    /// Whether `meta.<key>:[lo TO hi]` is meaningful for this type.
+    #[must_use]
+    pub fn supports_range(self) -> bool {
+        matches!(self, Self::I64 | Self::F64 | Self::Date)
+    }
+}
+
+/// Content languages with a dedicated analyzer. Text in any other language
+/// is indexed with the default (English) analyzer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentLanguage {
+    /// Jieba segmentation with English stemming; also the default analyzer.
+    English,
+    German,
+    French,
+    Spanish,
+    /// Overlapping character bigrams over kana and kanji runs. There is no
+    /// morphological analysis, so inflected forms are not lemmatized.
+    Japanese,
+    /// Overlapping character bigrams over Hangul runs. There is no
+    /// morphological analysis, so particles and endings are not split off.
+    Korean,
+    /// Jieba segmentation without stemming.
+    Chinese,
+}
+
+impl ContentLanguage {
+    pub const ALL: [Self; 7] = [
+        Self::English,
+        Self::German,
+        Self::French,
+        Self::Spanish,
+        Self::Japanese,
+        Self::Korean,
+        Self::Chinese,
+    ];
+
+    /// ISO 639-1 code, as stored under [`LANGUAGE_METADATA_KEY`].
+    #[must_use]
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::German => "de",
+            Self::French => "fr",
+            Self::Spanish => "es",
+            Self::Japanese => "ja",
+            Self::Korean => "ko",
+            Self::Chinese => "zh",
+        }
+    }
+
+    /// Parse an ISO 639-1 code (case-insensitive).
+    #[must_use]
+    pub fn from_code(code: &str) -> Option<Self> {
+        let code = code.trim();
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code().eq_ignore_ascii_case(code))
+    }
+}
+
+/// Lexical search schema persisted in the TOC.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchSchema {
+    /// Score multiplier for matches in frame titles.
+    pub title_boost: f32,
+    /// Indexed `extra_metadata` keys (lowercase) and their types.
+    pub metadata_fields: BTreeMap<String, MetadataFieldType>,
+}
+
+impl Default for SearchSchema {
+    fn default() -> Self {
+        Self {
+            title_boost: DEFAULT_TITLE_BOOST,
+            metadata_fields: BTreeMap::new(),
+        }
+    }
+}
+
+impl SearchSchema {
+    #[must_use]
+    pub fn with_title_boost(mut self, boost: f32) -> Self {
+        self.title_boost = boost;
+        self
+    }
+
+    /// Declare `key` as an indexed metadata field. Keys are matched
+    /// case-insensitively and stored lowercase.
+    #[must_use]
+    pub fn with_metadata_field(mut self, key: impl AsRef<str>, kind: MetadataFieldType) -> Self {
+        self.metadata_fields
+            .insert(key.as_ref().trim().to_ascii_lowercase(), kind);
+        self
+    }
+
+    /// Type declared for `key`, if any.
+    #[must_use]
+    pub fn metadata_field(&self, key: &str) -> Option<MetadataFieldType> {
+        self.metadata_fields.get(key).copied()
+    }
+
+    /// Check the boost and metadata keys before the schema is applied.
+    pub fn validate(&self) -> Result<()> {
+        if !self.title_boost.is_finite() || self.title_boost <= 0.0 {
+            return Err(MemvidError::SchemaValidation {
+                reason: format!("title boost must be positive, got {}", self.title_boost),
+            });
+        }
+        if self.metadata_fields.len() > MAX_METADATA_FIELDS {
+            return Err(MemvidError::SchemaValidation {
+                reason: format!(
+                    "at most {MAX_METADATA_FIELDS} metadata fields can be indexed, got {}",
+                    self.metadata_fields.len()
+                ),
+            });
+        }
+        for key in self.metadata_fields.keys() {
+            let valid = !key.is_empty()
+                && key
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "_-.".contains(c));
+            if !valid {
+                return Err(MemvidError::SchemaValidation {
+                    reason: format!(
+                        "metadata field `{key}` must be lowercase ASCII letters, digits, '_', '-' or '.'"
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_codes_round_trip() {
+        for language in ContentLanguage::ALL {
+            assert_eq!(ContentLanguage::from_code(language.code()), Some(language));
+        }
+        assert_eq!(
+            ContentLanguage::from_code(" DE "),
+            Some(ContentLanguage::German)
+        );
+        assert_eq!(ContentLanguage::from_code("pt"), None);
+    }
+
+    #[test]
+    fn builder_normalises_keys() {
+        let schema = SearchSchema::default()
+            .with_title_boost(3.0)
+            .with_metadata_field(" Customer ", MetadataFieldType::Keyword)
+            .with_metadata_field("priority", MetadataFieldType::I64);
+        assert_eq!(
+            schema.metadata_field("customer"),
+            Some(MetadataFieldType::Keyword)
+        );
+        assert!(schema.metadata_field("priority").unwrap().supports_range());
+        schema.validate().expect("valid schema");
+    }
+
+    #[test]
+    fn validate_rejects_bad_input() {
+        assert!(
+            SearchSchema::default()
+                .with_title_boost(0.0)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            SearchSchema::default()
+                .with_metadata_field("has space", MetadataFieldType::Text)
+                .validate()
+                .is_err()
+        );
+    }
+}
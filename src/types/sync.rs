@@ -0,0 +1,69 @@
+//! Options and summary for mirroring a directory or archive into a memory.
+//!
+//! Each synced file becomes one frame at `{uri_prefix}/{relative path}`. The
+//! file's size, modification time and BLAKE3 hash are kept in its frame's
+//! extra metadata, so a later sync only reads files whose size or time
+//! changed and only rewrites those whose content did.
+
+use serde::{Deserialize, Serialize};
+
+use super::options::PutOptions;
+
+/// Frame `extra_metadata` key holding the URI prefix of the sync that wrote the frame.
+pub const SYNC_ROOT_METADATA_KEY: &str = "memvid.sync.root";
+/// Frame `extra_metadata` key holding the synced file's size in bytes.
+pub const SYNC_SIZE_METADATA_KEY: &str = "memvid.sync.size";
+/// Frame `extra_metadata` key holding the synced file's modification time.
+pub const SYNC_MTIME_METADATA_KEY: &str = "memvid.sync.mtime";
+/// Frame `extra_metadata` key holding the BLAKE3 hash of the synced file, in hex.
+pub const SYNC_HASH_METADATA_KEY: &str = "memvid.sync.blake3";
+
+/// What [`Memvid::sync_dir`](crate::Memvid::sync_dir) walks and how it names frames.
+#[derive(Debug, Clone, Default)]
+pub struct SyncOptions {
+    /// Prefix of every frame URI; defaults to `mv2://` and the directory or
+    /// archive name.
+    pub uri_prefix: Option<String>,
+    /// Glob patterns a file's relative path must match one of; empty matches
+    /// every file. `*` also matches `/`, so `*.md` selects Markdown at any depth.
+    pub include: Vec<String>,
+    /// Glob patterns of relative paths to leave out, applied after `include`;
+    /// a pattern matching a directory leaves out everything below it.
+    pub exclude: Vec<String>,
+    /// Also sync files and directories whose name starts with a dot.
+    pub include_hidden: bool,
+    /// Leave out files larger than this many bytes.
+    pub max_file_bytes: Option<u64>,
+    /// Keep the frames of files that are no longer present instead of
+    /// deleting them.
+    pub keep_deleted: bool,
+    /// Template for every frame written; `uri`, `source_path` and the sync
+    /// keys of `extra_metadata` are set per file.
+    pub put: PutOptions,
+}
+
+/// Outcome of a sync, by frame URI.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncReport {
+    /// Files without a frame, now added.
+    pub added: Vec<String>,
+    /// Files whose content changed; their frames were superseded.
+    pub updated: Vec<String>,
+    /// Frames whose file is gone; they were deleted.
+    pub deleted: Vec<String>,
+    /// Files whose frame is current.
+    pub unchanged: usize,
+    /// Files read and hashed because their size or time differed from the
+    /// frame's. Unchanged ones among them get the new size and time recorded.
+    pub hashed: usize,
+    /// Files left out by `max_file_bytes`.
+    pub skipped: Vec<String>,
+}
+
+impl SyncReport {
+    /// True when no frame was added, superseded or deleted.
+    #[must_use]
+    pub fn is_unchanged(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.deleted.is_empty()
+    }
+}
@@ -0,0 +1,268 @@
+//! User-defined synonyms persisted in the TOC and applied to lexical queries.
+//!
+//! A synonym group makes its terms interchangeable: a query for any one of
+//! them also matches the others. A one-way expansion only widens queries for
+//! its source term, so `pto -> vacation` does not make `vacation` match `pto`.
+//! Terms are lowercased with whitespace collapsed; multi-word terms expand as
+//! phrases.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MemvidError, Result};
+
+/// Maximum number of terms in one synonym group or expansion target list.
+pub const MAX_SYNONYM_TERMS: usize = 64;
+
+/// `extra_metadata` key listing (`" | "`-separated) the synonyms recorded
+/// for a frame's text when it was ingested with index-time expansion.
+pub const SYNONYMS_METADATA_KEY: &str = "memvid.synonyms";
+
+/// Synonym groups and one-way expansions for a memory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SynonymTrack {
+    /// Sets of interchangeable terms. A term belongs to at most one group.
+    pub groups: Vec<Vec<String>>,
+    /// Source term to the additional terms a query for it should match.
+    pub expansions: BTreeMap<String, Vec<String>>,
+    /// Also record the synonyms of terms found in ingested text on the frame
+    /// (under [`SYNONYMS_METADATA_KEY`]), so the frame matches them even
+    /// where queries are not expanded. They are indexed, not added to the text.
+    pub expand_at_index: bool,
+}
+
+/// How one query term was widened by the synonym track.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryExpansion {
+    /// Term as it appeared in the query (normalised).
+    pub term: String,
+    /// Terms that were OR-ed in alongside it.
+    pub expanded_to: Vec<String>,
+}
+
+impl SynonymTrack {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty() && self.expansions.is_empty()
+    }
+
+    /// Lowercase `term` and collapse internal whitespace; `None` when blank.
+    #[must_use]
+    pub fn normalize_term(term: &str) -> Option<String> {
+        let normalized = term
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(" ");
+        (!normalized.is_empty()).then_some(normalized)
+    }
+
+    /// Make `terms` interchangeable. Groups sharing a term are merged.
+    pub fn add_group<I, S>(&mut self, terms: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut merged = normalize_terms(terms)?;
+        if merged.len() < 2 {
+            return Err(MemvidError::InvalidQuery {
+                reason: "a synonym group needs at least two distinct terms".to_string(),
+            });
+        }
+        self.groups.retain(|group| {
+            if group.iter().any(|term| merged.contains(term)) {
+                merged.extend(group.iter().cloned());
+                false
+            } else {
+                true
+            }
+        });
+        merged.sort();
+        merged.dedup();
+        check_size(&merged)?;
+        self.groups.push(merged);
+        Ok(())
+    }
+
+    /// Remove the group containing `term`. Returns whether one was removed.
+    pub fn remove_group(&mut self, term: &str) -> bool {
+        let Some(term) = Self::normalize_term(term) else {
+            return false;
+        };
+        let before = self.groups.len();
+        self.groups.retain(|group| !group.contains(&term));
+        self.groups.len() != before
+    }
+
+    /// Widen queries for `term` to also match `targets`, adding to any
+    /// expansion already defined for it.
+    pub fn add_expansion<I, S>(&mut self, term: &str, targets: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let Some(source) = Self::normalize_term(term) else {
+            return Err(MemvidError::InvalidQuery {
+                reason: "expansion source term is empty".to_string(),
+            });
+        };
+        let mut targets = normalize_terms(targets)?;
+        targets.retain(|target| *target != source);
+        if targets.is_empty() {
+            return Err(MemvidError::InvalidQuery {
+                reason: format!("expansion for `{source}` needs at least one other term"),
+            });
+        }
+        let entry = self.expansions.entry(source).or_default();
+        entry.extend(targets);
+        entry.sort();
+        entry.dedup();
+        check_size(entry)
+    }
+
+    /// Remove the expansion for `term`. Returns whether one was removed.
+    pub fn remove_expansion(&mut self, term: &str) -> bool {
+        Self::normalize_term(term).is_some_and(|term| self.expansions.remove(&term).is_some())
+    }
+
+    /// Terms a query for `term` should also match, excluding `term` itself.
+    #[must_use]
+    pub fn expansions_for(&self, term: &str) -> Vec<String> {
+        let Some(term) = Self::normalize_term(term) else {
+            return Vec::new();
+        };
+        let mut terms: Vec<String> = self
+            .groups
+            .iter()
+            .filter(|group| group.contains(&term))
+            .flatten()
+            .chain(self.expansions.get(&term).into_iter().flatten())
+            .filter(|candidate| **candidate != term)
+            .cloned()
+            .collect();
+        terms.sort();
+        terms.dedup();
+        terms
+    }
+
+    /// Terms whose presence can satisfy a query for `term`: its expansions
+    /// and, with index-time expansion, the one-way sources recorded as
+    /// expanding to it. Excludes `term` itself.
+    #[must_use]
+    pub fn matching_terms(&self, term: &str) -> Vec<String> {
+        let mut terms = self.expansions_for(term);
+        if !self.expand_at_index {
+            return terms;
+        }
+        let Some(term) = Self::normalize_term(term) else {
+            return terms;
+        };
+        terms.extend(
+            self.expansions
+                .iter()
+                .filter(|(source, targets)| **source != term && targets.contains(&term))
+                .map(|(source, _)| source.clone()),
+        );
+        terms.sort();
+        terms.dedup();
+        terms
+    }
+
+    /// Synonyms of the group and expansion terms that occur in `text`, minus
+    /// those already present, for appending to a frame's search text.
+    #[must_use]
+    pub fn index_terms(&self, text: &str) -> Vec<String> {
+        let lower = text.to_lowercase();
+        let words: BTreeSet<&str> = lower
+            .split(|ch: char| !ch.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect();
+        let present = |term: &str| {
+            if term.contains(' ') {
+                lower.contains(term)
+            } else {
+                words.contains(term)
+            }
+        };
+        let mut terms: Vec<String> = self
+            .groups
+            .iter()
+            .flatten()
+            .chain(self.expansions.keys())
+            .filter(|source| present(source))
+            .flat_map(|source| self.expansions_for(source))
+            .filter(|synonym| !present(synonym))
+            .collect();
+        terms.sort();
+        terms.dedup();
+        terms
+    }
+}
+
+fn normalize_terms<I, S>(terms: I) -> Result<Vec<String>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut normalized: Vec<String> = terms
+        .into_iter()
+        .filter_map(|term| SynonymTrack::normalize_term(term.as_ref()))
+        .collect();
+    normalized.sort();
+    normalized.dedup();
+    check_size(&normalized)?;
+    Ok(normalized)
+}
+
+fn check_size(terms: &[String]) -> Result<()> {
+    if terms.len() > MAX_SYNONYM_TERMS {
+        return Err(MemvidError::InvalidQuery {
+            reason: format!(
+                "at most {MAX_SYNONYM_TERMS} synonym terms per entry, got {}",
+                terms.len()
+            ),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_merge_and_expansions_are_one_way() {
+        let mut track = SynonymTrack::default();
+        track.add_group(["K8s", "kubernetes"]).unwrap();
+        track.add_group(["kube", " K8S "]).unwrap();
+        assert_eq!(track.groups, [vec!["k8s", "kube", "kubernetes"]]);
+        assert_eq!(track.expansions_for("Kubernetes"), ["k8s", "kube"]);
+
+        track
+            .add_expansion("PTO", ["vacation", "paid  time off"])
+            .unwrap();
+        assert_eq!(track.expansions_for("pto"), ["paid time off", "vacation"]);
+        assert!(track.expansions_for("vacation").is_empty());
+
+        assert!(track.add_group(["solo", "SOLO"]).is_err());
+        assert!(track.add_expansion("pto", ["pto"]).is_err());
+
+        assert_eq!(
+            track.index_terms("Moving the cluster to Kubernetes; PTO starts Monday."),
+            ["k8s", "kube", "paid time off", "vacation"]
+        );
+        assert_eq!(track.index_terms("k8s is short for kubernetes"), ["kube"]);
+
+        assert_eq!(track.matching_terms("vacation"), Vec::<String>::new());
+        track.expand_at_index = true;
+        assert_eq!(track.matching_terms("Vacation"), ["pto"]);
+        assert_eq!(track.matching_terms("kube"), ["k8s", "kubernetes"]);
+        track.expand_at_index = false;
+
+        assert!(track.remove_group("kube"));
+        assert!(!track.remove_group("kube"));
+        assert!(track.remove_expansion("PTO"));
+        assert!(track.is_empty());
+    }
+}
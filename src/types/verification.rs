@@ -87,6 +87,7 @@ pub enum DoctorFindingCode {
     VecIndexCorrupt,
     TantivySnapshotMissing,
     TantivySnapshotCorrupt,
+    TantivySnapshotOutdated,
     MerkleMismatch,
     SegmentCatalogInconsistent,
     VacuumIncomplete,
@@ -36,6 +36,7 @@ pub struct VecDocument {
 #[derive(Default)]
 pub struct VecIndexBuilder {
     documents: Vec<VecDocument>,
+    compression: crate::VectorCompression,
 }
 
 impl VecIndexBuilder {
@@ -44,6 +45,16 @@ impl VecIndexBuilder {
         Self::default()
     }
 
+    /// Encode the finished index with int8 or binary quantization.
+    ///
+    /// Other modes build the regular uncompressed/HNSW index; PQ has its own
+    /// builder in `vec_pq` because it needs an explicit training step.
+    #[must_use]
+    pub fn with_compression(mut self, compression: crate::VectorCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
     pub fn add_document<I>(&mut self, frame_id: FrameId, embedding: I)
     where
         I: Into<Vec<f32>>,
@@ -55,6 +66,10 @@ impl VecIndexBuilder {
     }
 
     pub fn finish(self) -> Result<VecIndexArtifact> {
+        if self.compression.is_scalar_quantized() && !self.documents.is_empty() {
+            return crate::vec_sq::build_artifact(&self.documents, &self.compression);
+        }
+
         #[cfg(any(feature = "vec", feature = "hnsw_bench"))]
         if self.documents.len() >= HNSW_THRESHOLD {
             return self.finish_hnsw();
@@ -131,6 +146,8 @@ pub enum VecIndex {
         documents: Vec<VecDocument>,
     },
     Compressed(crate::vec_pq::QuantizedVecIndex),
+    Int8(crate::vec_sq::Int8VecIndex),
+    Binary(crate::vec_sq::BinaryVecIndex),
     #[cfg(any(feature = "vec", feature = "hnsw_bench"))]
     Hnsw(HnswVecIndex),
 }
@@ -148,10 +165,16 @@ impl VecIndex {
     /// This is necessary because `MIN_VECTORS_FOR_PQ` threshold (100 vectors)
     /// causes most segments to be stored as uncompressed even when Pq96 is requested.
     /// Falls back to PQ format for true compressed segments.
+    /// Int8/binary indexes carry a magic prefix and are recognised up front.
     pub fn decode_with_compression(
         bytes: &[u8],
         _compression: crate::VectorCompression,
     ) -> Result<Self> {
+        if let Some(result) = crate::vec_sq::decode_tagged(bytes) {
+            tracing::debug!(bytes_len = bytes.len(), "decoded as scalar quantized");
+            return result;
+        }
+
         // Try uncompressed format first, regardless of compression flag.
         // This is necessary because MIN_VECTORS_FOR_PQ threshold (100 vectors)
         // causes most segments to be stored as uncompressed even when Pq96 is requested.
@@ -230,6 +253,20 @@ impl VecIndex {
 
     #[must_use]
     pub fn search(&self, query: &[f32], limit: usize) -> Vec<VecSearchHit> {
+        self.search_with_rescore(query, limit, crate::vec_sq::DEFAULT_RESCORE_OVERSAMPLE)
+    }
+
+    /// Search, re-ranking the best `limit * oversample` quantized candidates
+    /// against the retained f32 originals.
+    ///
+    /// Only int8/binary indexes rescore; `oversample` of 0 disables it.
+    #[must_use]
+    pub fn search_with_rescore(
+        &self,
+        query: &[f32],
+        limit: usize,
+        oversample: usize,
+    ) -> Vec<VecSearchHit> {
         if query.is_empty() {
             return Vec::new();
         }
@@ -254,6 +291,8 @@ impl VecIndex {
                 hits
             }
             VecIndex::Compressed(quantized) => quantized.search(query, limit),
+            VecIndex::Int8(index) => index.search(query, limit, oversample),
+            VecIndex::Binary(index) => index.search(query, limit, oversample),
             #[cfg(any(feature = "vec", feature = "hnsw_bench"))]
             VecIndex::Hnsw(index) => index.search(query, limit),
         }
@@ -271,6 +310,8 @@ impl VecIndex {
                 // Compressed vectors don't have direct f32 access
                 Box::new(std::iter::empty())
             }
+            VecIndex::Int8(index) => Box::new(index.entries()),
+            VecIndex::Binary(index) => Box::new(index.entries()),
             #[cfg(any(feature = "vec", feature = "hnsw_bench"))]
             VecIndex::Hnsw(_) => {
                 // HNSW graph doesn't easily iterate all embeddings
@@ -290,6 +331,8 @@ impl VecIndex {
                 // Compressed vectors don't have direct f32 access
                 None
             }
+            VecIndex::Int8(index) => index.embedding_for(frame_id),
+            VecIndex::Binary(index) => index.embedding_for(frame_id),
             #[cfg(any(feature = "vec", feature = "hnsw_bench"))]
             VecIndex::Hnsw(_) => {
                 // HNSW storage is internal, would need traversal to find exact embedding
@@ -299,6 +342,18 @@ impl VecIndex {
         }
     }
 
+    /// Read int8/binary originals still on disk into memory.
+    ///
+    /// Called before the file is written, since writes can move or overwrite
+    /// the section a deferred load reads from.
+    pub(crate) fn load_originals(&mut self) -> Result<()> {
+        match self {
+            VecIndex::Int8(index) => index.load_originals(),
+            VecIndex::Binary(index) => index.load_originals(),
+            _ => Ok(()),
+        }
+    }
+
     pub fn remove(&mut self, frame_id: FrameId) {
         match self {
             VecIndex::Uncompressed { documents } => {
@@ -307,6 +362,8 @@ impl VecIndex {
             VecIndex::Compressed(_quantized) => {
                 // Compressed indices are immutable
             }
+            VecIndex::Int8(index) => index.remove(frame_id),
+            VecIndex::Binary(index) => index.remove(frame_id),
             #[cfg(any(feature = "vec", feature = "hnsw_bench"))]
             VecIndex::Hnsw(_) => {
                 // HNSW indices are immutable in this implementation
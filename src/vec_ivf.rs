@@ -0,0 +1,693 @@
+//! Inverted-file index with residual product quantization (IVF-PQ)
+//!
+//! Flat PQ ([`crate::QuantizedVecIndex`]) shrinks vectors but still scans every
+//! code per query. IVF-PQ partitions the space with a k-means coarse quantizer
+//! and only scans the `nprobe` lists closest to the query, which keeps query
+//! time roughly proportional to `N * nprobe / nlist`.
+//!
+//! **Algorithm**:
+//! 1. Train `nlist` coarse centroids with k-means on a sample of vectors
+//! 2. Train a [`ProductQuantizer`] on the residuals (vector - nearest centroid)
+//! 3. Each vector is stored in its centroid's list as `frame_id` + PQ codes
+//! 4. Search ranks centroids, then scans the closest `nprobe` lists with an
+//!    ADC table built from the query residual for that list
+//!
+//! **Layout** (little-endian, no padding, readable straight from an mmap):
+//! ```text
+//! header      magic "MVIVFPQ1", version u32, dimension u32, nlist u32,
+//!             num_subspaces u32, vector_count u64, quantizer_len u64,
+//!             max_frame_id u64
+//! centroids   nlist * dimension * f32
+//! quantizer   bincode-encoded ProductQuantizer (quantizer_len bytes)
+//! directory   nlist * (offset u64, count u64), offsets relative to lists
+//! lists       per list: count * frame_id u64, then count * num_subspaces codes
+//! ```
+//!
+//! Only the header, centroids, codebooks and directory are decoded on open;
+//! list bytes are read in place, so a memory-mapped index only pages in the
+//! lists a query actually probes.
+//!
+//! A memory stores the index as a TOC-referenced segment of its own file
+//! ([`IvfIndexManifest`](crate::types::IvfIndexManifest)), mapped on open and
+//! rewritten on each commit with the newly committed vectors added to their
+//! lists.
+
+use std::collections::HashSet;
+use std::fs::File;
+
+use blake3::hash;
+use memmap2::{Mmap, MmapOptions};
+
+use crate::vec::VecSearchHit;
+use crate::vec_pq::{ProductQuantizer, kmeans, nearest_centroid};
+use crate::{MemvidError, Result, types::FrameId};
+
+const IVF_MAGIC: [u8; 8] = *b"MVIVFPQ1";
+const IVF_VERSION: u32 = 1;
+const HEADER_LEN: usize = 48;
+const DIRECTORY_ENTRY_LEN: usize = 16;
+
+/// Default number of coarse lists.
+pub const DEFAULT_NLIST: usize = 1024;
+/// Default number of lists scanned per query.
+pub const DEFAULT_NPROBE: usize = 16;
+/// Default number of vectors used to train the coarse quantizer and PQ codebooks.
+pub const DEFAULT_IVF_TRAINING_SAMPLE: usize = 65_536;
+
+fn quantizer_config() -> impl bincode::config::Config {
+    bincode::config::standard()
+        .with_fixed_int_encoding()
+        .with_little_endian()
+}
+
+#[allow(clippy::cast_possible_truncation)]
+const VEC_DECODE_LIMIT: usize = crate::MAX_INDEX_BYTES as usize;
+
+/// Builder for an IVF-PQ index.
+///
+/// Train once on a sample, then stream documents through
+/// [`add_document`](Self::add_document); only codes are kept in memory.
+#[derive(Debug, Clone)]
+pub struct IvfPqBuilder {
+    nlist: usize,
+    coarse_iterations: usize,
+    pq_iterations: usize,
+    centroids: Vec<Vec<f32>>,
+    quantizer: Option<ProductQuantizer>,
+    /// Per list: (`frame_ids`, flat codes)
+    lists: Vec<(Vec<FrameId>, Vec<u8>)>,
+    vector_count: u64,
+    max_frame_id: FrameId,
+}
+
+impl Default for IvfPqBuilder {
+    fn default() -> Self {
+        Self {
+            nlist: DEFAULT_NLIST,
+            coarse_iterations: 10,
+            pq_iterations: 25,
+            centroids: Vec::new(),
+            quantizer: None,
+            lists: Vec::new(),
+            vector_count: 0,
+            max_frame_id: 0,
+        }
+    }
+}
+
+impl IvfPqBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of coarse lists (clamped to the training set size).
+    #[must_use]
+    pub fn with_nlist(mut self, nlist: usize) -> Self {
+        self.nlist = nlist.max(1);
+        self
+    }
+
+    /// k-means iterations for the coarse quantizer and the PQ codebooks.
+    #[must_use]
+    pub fn with_iterations(mut self, coarse: usize, pq: usize) -> Self {
+        self.coarse_iterations = coarse;
+        self.pq_iterations = pq;
+        self
+    }
+
+    /// Train the coarse quantizer and residual PQ on sample vectors.
+    pub fn train(&mut self, training_vectors: &[Vec<f32>]) -> Result<()> {
+        let Some(first) = training_vectors.first() else {
+            return Err(MemvidError::InvalidQuery {
+                reason: "Cannot train IVF-PQ with empty training set".to_string(),
+            });
+        };
+        let dimension = u32::try_from(first.len()).map_err(|_| MemvidError::InvalidQuery {
+            reason: "IVF-PQ training vector dimension too large".to_string(),
+        })?;
+        // Fails early on dimensions PQ can't split into subspaces
+        let mut quantizer = ProductQuantizer::new(dimension)?;
+
+        let nlist = self.nlist.min(training_vectors.len());
+        let centroids = kmeans(training_vectors, nlist, self.coarse_iterations)?;
+
+        let residuals: Vec<Vec<f32>> = training_vectors
+            .iter()
+            .map(|vector| {
+                if vector.len() == first.len() {
+                    residual(vector, &centroids[nearest_centroid(vector, &centroids)])
+                } else {
+                    // Let PQ training report the mismatch
+                    vector.clone()
+                }
+            })
+            .collect();
+        quantizer.train(&residuals, self.pq_iterations)?;
+
+        self.lists = vec![(Vec::new(), Vec::new()); centroids.len()];
+        self.centroids = centroids;
+        self.quantizer = Some(quantizer);
+        self.vector_count = 0;
+        self.max_frame_id = 0;
+        Ok(())
+    }
+
+    /// Assign a vector to its list and store its residual PQ codes.
+    pub fn add_document(&mut self, frame_id: FrameId, embedding: &[f32]) -> Result<()> {
+        let quantizer = self
+            .quantizer
+            .as_ref()
+            .ok_or_else(|| MemvidError::InvalidQuery {
+                reason: "IVF-PQ not trained. Call train first".to_string(),
+            })?;
+        if embedding.len() != quantizer.dimension() as usize {
+            return Err(MemvidError::VecDimensionMismatch {
+                expected: quantizer.dimension(),
+                actual: embedding.len(),
+            });
+        }
+
+        let list = nearest_centroid(embedding, &self.centroids);
+        let codes = quantizer.encode(&residual(embedding, &self.centroids[list]))?;
+        let (frame_ids, list_codes) = &mut self.lists[list];
+        frame_ids.push(frame_id);
+        list_codes.extend_from_slice(&codes);
+        self.vector_count += 1;
+        self.max_frame_id = self.max_frame_id.max(frame_id);
+        Ok(())
+    }
+
+    /// Reopen a built index for additions, keeping its training and the
+    /// stored vectors `keep` accepts.
+    pub fn from_index<B: AsRef<[u8]>>(
+        index: &IvfPqIndex<B>,
+        keep: impl Fn(FrameId) -> bool,
+    ) -> Result<Self> {
+        let mut lists = Vec::with_capacity(index.nlist());
+        let mut vector_count = 0u64;
+        let mut max_frame_id = 0;
+        for list in 0..index.nlist() {
+            let (frame_bytes, codes) = index.list(list);
+            let mut kept_ids = Vec::new();
+            let mut kept_codes = Vec::new();
+            for (frame_bytes, code) in frame_bytes
+                .chunks_exact(8)
+                .zip(codes.chunks_exact(index.num_subspaces))
+            {
+                let frame_id = read_u64(frame_bytes, 0);
+                if keep(frame_id) {
+                    kept_ids.push(frame_id);
+                    kept_codes.extend_from_slice(code);
+                    max_frame_id = max_frame_id.max(frame_id);
+                }
+            }
+            vector_count += kept_ids.len() as u64;
+            lists.push((kept_ids, kept_codes));
+        }
+        Ok(Self {
+            centroids: index
+                .centroids
+                .chunks_exact(index.dimension)
+                .map(<[f32]>::to_vec)
+                .collect(),
+            quantizer: Some(index.quantizer.clone()),
+            lists,
+            vector_count,
+            max_frame_id,
+            ..Self::default()
+        })
+    }
+
+    /// Frame ids already added.
+    #[must_use]
+    pub fn frame_ids(&self) -> HashSet<FrameId> {
+        self.lists
+            .iter()
+            .flat_map(|(frame_ids, _)| frame_ids.iter().copied())
+            .collect()
+    }
+
+    pub fn finish(self) -> Result<IvfPqArtifact> {
+        let quantizer = self.quantizer.ok_or_else(|| MemvidError::InvalidQuery {
+            reason: "IVF-PQ not trained".to_string(),
+        })?;
+        let quantizer_bytes = bincode::serde::encode_to_vec(&quantizer, quantizer_config())?;
+        let dimension = quantizer.dimension();
+        let nlist = self.centroids.len();
+        let list_bytes: usize = self
+            .lists
+            .iter()
+            .map(|(frame_ids, codes)| frame_ids.len() * 8 + codes.len())
+            .sum();
+
+        let mut bytes = Vec::with_capacity(
+            HEADER_LEN
+                + nlist * dimension as usize * 4
+                + quantizer_bytes.len()
+                + nlist * DIRECTORY_ENTRY_LEN
+                + list_bytes,
+        );
+        bytes.extend_from_slice(&IVF_MAGIC);
+        bytes.extend_from_slice(&IVF_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&dimension.to_le_bytes());
+        bytes.extend_from_slice(&u32::try_from(nlist).unwrap_or(u32::MAX).to_le_bytes());
+        bytes.extend_from_slice(
+            &u32::try_from(quantizer.num_subspaces())
+                .unwrap_or(u32::MAX)
+                .to_le_bytes(),
+        );
+        bytes.extend_from_slice(&self.vector_count.to_le_bytes());
+        bytes.extend_from_slice(&(quantizer_bytes.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.max_frame_id.to_le_bytes());
+
+        for centroid in &self.centroids {
+            for value in centroid {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        bytes.extend_from_slice(&quantizer_bytes);
+
+        let mut offset = 0u64;
+        for (frame_ids, codes) in &self.lists {
+            bytes.extend_from_slice(&offset.to_le_bytes());
+            bytes.extend_from_slice(&(frame_ids.len() as u64).to_le_bytes());
+            offset += (frame_ids.len() * 8 + codes.len()) as u64;
+        }
+        for (frame_ids, codes) in &self.lists {
+            for frame_id in frame_ids {
+                bytes.extend_from_slice(&frame_id.to_le_bytes());
+            }
+            bytes.extend_from_slice(codes);
+        }
+
+        let checksum = *hash(&bytes).as_bytes();
+        Ok(IvfPqArtifact {
+            bytes,
+            vector_count: self.vector_count,
+            dimension,
+            nlist,
+            checksum,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IvfPqArtifact {
+    pub bytes: Vec<u8>,
+    pub vector_count: u64,
+    pub dimension: u32,
+    pub nlist: usize,
+    pub checksum: [u8; 32],
+}
+
+/// Bytes behind an attached index: mapped from the memory file, or owned
+/// once the handle is about to write to the file.
+#[derive(Debug)]
+pub enum IvfBytes {
+    Mapped(Mmap),
+    Owned(Vec<u8>),
+}
+
+impl AsRef<[u8]> for IvfBytes {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::Mapped(map) => map,
+            Self::Owned(bytes) => bytes,
+        }
+    }
+}
+
+/// Read-only IVF-PQ index over any byte buffer (typically an mmap).
+#[derive(Debug)]
+pub struct IvfPqIndex<B = IvfBytes> {
+    bytes: B,
+    dimension: usize,
+    num_subspaces: usize,
+    vector_count: u64,
+    max_frame_id: FrameId,
+    centroids: Vec<f32>,
+    quantizer: ProductQuantizer,
+    directory_offset: usize,
+    lists_offset: usize,
+}
+
+impl IvfPqIndex<IvfBytes> {
+    /// Memory-map the index stored at `offset..offset + length` of `file`.
+    pub fn map(file: &File, offset: u64, length: u64) -> Result<Self> {
+        let length = usize::try_from(length).map_err(|_| invalid("IVF-PQ segment too large"))?;
+        // Safety: read-only mapping of a segment the TOC references. The
+        // owning handle holds the file lock and copies the index into memory
+        // (see `into_owned`) before it writes to the file.
+        let map = unsafe { MmapOptions::new().offset(offset).len(length).map(file)? };
+        Self::from_bytes(IvfBytes::Mapped(map))
+    }
+
+    /// Copy a mapped index into memory so the file can be rewritten.
+    pub fn into_owned(self) -> Result<Self> {
+        match self.bytes {
+            IvfBytes::Owned(_) => Ok(self),
+            IvfBytes::Mapped(map) => Self::from_bytes(IvfBytes::Owned(map.to_vec())),
+        }
+    }
+
+    #[must_use]
+    pub fn is_mapped(&self) -> bool {
+        matches!(self.bytes, IvfBytes::Mapped(_))
+    }
+}
+
+impl<B: AsRef<[u8]>> IvfPqIndex<B> {
+    /// Validate the header and directory and decode the small in-memory parts.
+    pub fn from_bytes(bytes: B) -> Result<Self> {
+        let data = bytes.as_ref();
+        if data.len() < HEADER_LEN || data[..8] != IVF_MAGIC {
+            return Err(invalid("missing IVF-PQ header"));
+        }
+        if read_u32(data, 8) != IVF_VERSION {
+            return Err(invalid("unsupported IVF-PQ version"));
+        }
+        let dimension = read_u32(data, 12) as usize;
+        let nlist = read_u32(data, 16) as usize;
+        let num_subspaces = read_u32(data, 20) as usize;
+        let vector_count = read_u64(data, 24);
+        let quantizer_len =
+            usize::try_from(read_u64(data, 32)).map_err(|_| invalid("quantizer too large"))?;
+        let max_frame_id = read_u64(data, 40);
+
+        let centroids_len = nlist
+            .checked_mul(dimension)
+            .and_then(|n| n.checked_mul(4))
+            .ok_or_else(|| invalid("centroid table overflow"))?;
+        let quantizer_offset = HEADER_LEN + centroids_len;
+        let directory_offset = quantizer_offset
+            .checked_add(quantizer_len)
+            .ok_or_else(|| invalid("quantizer range overflow"))?;
+        let lists_offset = directory_offset
+            .checked_add(nlist * DIRECTORY_ENTRY_LEN)
+            .ok_or_else(|| invalid("directory range overflow"))?;
+        if lists_offset > data.len() {
+            return Err(invalid("IVF-PQ index truncated"));
+        }
+
+        let centroids = data[HEADER_LEN..quantizer_offset]
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+
+        let config = bincode::config::standard()
+            .with_fixed_int_encoding()
+            .with_little_endian()
+            .with_limit::<VEC_DECODE_LIMIT>();
+        let (quantizer, read): (ProductQuantizer, usize) =
+            bincode::serde::decode_from_slice(&data[quantizer_offset..directory_offset], config)?;
+        if read != quantizer_len
+            || quantizer.dimension() as usize != dimension
+            || quantizer.num_subspaces() != num_subspaces
+        {
+            return Err(invalid("IVF-PQ quantizer does not match header"));
+        }
+
+        // Every list must fit inside the buffer so search can slice unchecked
+        let lists_len = data.len() - lists_offset;
+        let mut total = 0u64;
+        for list in 0..nlist {
+            let entry = directory_offset + list * DIRECTORY_ENTRY_LEN;
+            let offset = read_u64(data, entry);
+            let count = read_u64(data, entry + 8);
+            let end = count
+                .checked_mul(8 + num_subspaces as u64)
+                .and_then(|len| len.checked_add(offset))
+                .ok_or_else(|| invalid("IVF-PQ list overflow"))?;
+            if end > lists_len as u64 {
+                return Err(invalid("IVF-PQ list out of bounds"));
+            }
+            total += count;
+        }
+        if total != vector_count {
+            return Err(invalid("IVF-PQ list counts do not match header"));
+        }
+
+        Ok(Self {
+            bytes,
+            dimension,
+            num_subspaces,
+            vector_count,
+            max_frame_id,
+            centroids,
+            quantizer,
+            directory_offset,
+            lists_offset,
+        })
+    }
+
+    #[must_use]
+    pub fn dimension(&self) -> u32 {
+        u32::try_from(self.dimension).unwrap_or(u32::MAX)
+    }
+
+    #[must_use]
+    pub fn nlist(&self) -> usize {
+        self.centroids.len() / self.dimension.max(1)
+    }
+
+    #[must_use]
+    pub fn vector_count(&self) -> u64 {
+        self.vector_count
+    }
+
+    /// Largest frame id stored in the index; newer frames are not covered.
+    #[must_use]
+    pub fn max_frame_id(&self) -> FrameId {
+        self.max_frame_id
+    }
+
+    /// A list's frame id bytes and codes.
+    fn list(&self, list: usize) -> (&[u8], &[u8]) {
+        let data = self.bytes.as_ref();
+        let entry = self.directory_offset + list * DIRECTORY_ENTRY_LEN;
+        #[allow(clippy::cast_possible_truncation)]
+        let offset = self.lists_offset + read_u64(data, entry) as usize;
+        #[allow(clippy::cast_possible_truncation)]
+        let count = read_u64(data, entry + 8) as usize;
+        let codes_offset = offset + count * 8;
+        (
+            &data[offset..codes_offset],
+            &data[codes_offset..codes_offset + count * self.num_subspaces],
+        )
+    }
+
+    /// Approximate nearest neighbours scanning the `nprobe` closest lists.
+    #[must_use]
+    pub fn search(&self, query: &[f32], limit: usize, nprobe: usize) -> Vec<VecSearchHit> {
+        self.search_filtered(query, limit, nprobe, |_| true)
+    }
+
+    /// Like [`search`](Self::search), skipping frames rejected by `filter`
+    /// (e.g. deleted frames) before they take a slot in the top `limit`.
+    pub fn search_filtered<F>(
+        &self,
+        query: &[f32],
+        limit: usize,
+        nprobe: usize,
+        filter: F,
+    ) -> Vec<VecSearchHit>
+    where
+        F: Fn(FrameId) -> bool,
+    {
+        if query.len() != self.dimension || limit == 0 {
+            return Vec::new();
+        }
+        let mut probes: Vec<(usize, f32)> = self
+            .centroids
+            .chunks_exact(self.dimension)
+            .map(|centroid| crate::simd::l2_distance_squared_simd(query, centroid))
+            .enumerate()
+            .collect();
+        let nprobe = nprobe.clamp(1, probes.len().max(1));
+        if nprobe < probes.len() {
+            probes.select_nth_unstable_by(nprobe - 1, |a, b| a.1.total_cmp(&b.1));
+            probes.truncate(nprobe);
+        }
+
+        let mut hits = Vec::new();
+        for (list, _) in probes {
+            let (frame_ids, codes) = self.list(list);
+            if frame_ids.is_empty() {
+                continue;
+            }
+
+            let centroid = &self.centroids[list * self.dimension..(list + 1) * self.dimension];
+            let Some(table) = self.quantizer.distance_table(&residual(query, centroid)) else {
+                continue;
+            };
+
+            for (frame_bytes, code) in frame_ids
+                .chunks_exact(8)
+                .zip(codes.chunks_exact(self.num_subspaces))
+            {
+                let frame_id = read_u64(frame_bytes, 0);
+                if !filter(frame_id) {
+                    continue;
+                }
+                hits.push(VecSearchHit {
+                    frame_id,
+                    distance: ProductQuantizer::table_distance_squared(&table, code),
+                });
+            }
+        }
+
+        if hits.len() > limit {
+            hits.select_nth_unstable_by(limit - 1, |a, b| a.distance.total_cmp(&b.distance));
+            hits.truncate(limit);
+        }
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        for hit in &mut hits {
+            hit.distance = hit.distance.sqrt();
+        }
+        hits
+    }
+}
+
+fn residual(vector: &[f32], centroid: &[f32]) -> Vec<f32> {
+    vector.iter().zip(centroid).map(|(v, c)| v - c).collect()
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    let mut buf = [0u8; 4];
+    buf.copy_from_slice(&data[offset..offset + 4]);
+    u32::from_le_bytes(buf)
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&data[offset..offset + 8]);
+    u64::from_le_bytes(buf)
+}
+
+fn invalid(reason: &'static str) -> MemvidError {
+    MemvidError::InvalidToc {
+        reason: reason.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Clustered vectors: `clusters` well separated centres plus small noise.
+    fn clustered(count: usize, dim: usize, clusters: usize) -> Vec<Vec<f32>> {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % 10_000) as f32 / 10_000.0
+        };
+        let centres: Vec<Vec<f32>> = (0..clusters)
+            .map(|_| (0..dim).map(|_| next() * 10.0).collect())
+            .collect();
+        (0..count)
+            .map(|i| {
+                centres[i % clusters]
+                    .iter()
+                    .map(|c| c + next() * 0.5)
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn build(vectors: &[Vec<f32>], nlist: usize) -> IvfPqArtifact {
+        let mut builder = IvfPqBuilder::new().with_nlist(nlist).with_iterations(8, 8);
+        builder.train(vectors).unwrap();
+        for (i, vector) in vectors.iter().enumerate() {
+            builder.add_document(i as FrameId, vector).unwrap();
+        }
+        builder.finish().unwrap()
+    }
+
+    #[test]
+    fn ivf_search_finds_exact_match() {
+        let vectors = clustered(600, 32, 12);
+        let artifact = build(&vectors, 12);
+        assert_eq!(artifact.vector_count, 600);
+        assert_eq!(artifact.nlist, 12);
+
+        let index = IvfPqIndex::from_bytes(artifact.bytes).unwrap();
+        assert_eq!(index.dimension(), 32);
+        assert_eq!(index.nlist(), 12);
+        assert_eq!(index.max_frame_id(), 599);
+
+        let hits = index.search(&vectors[42], 5, 2);
+        assert_eq!(hits.len(), 5);
+        assert_eq!(hits[0].frame_id, 42);
+        assert!(hits.windows(2).all(|w| w[0].distance <= w[1].distance));
+        // Everything returned comes from the query's own cluster
+        assert!(hits.iter().all(|hit| hit.frame_id % 12 == 42 % 12));
+    }
+
+    #[test]
+    fn ivf_nprobe_and_filter() {
+        let vectors = clustered(400, 16, 8);
+        let index = IvfPqIndex::from_bytes(build(&vectors, 8).bytes).unwrap();
+
+        // Probing every list sees every vector
+        let all = index.search(&vectors[0], 1000, 8);
+        assert_eq!(all.len(), 400);
+        let one = index.search(&vectors[0], 1000, 1);
+        assert!(one.len() < 400);
+
+        let hits = index.search_filtered(&vectors[3], 3, 2, |frame_id| frame_id != 3);
+        assert!(hits.iter().all(|hit| hit.frame_id != 3));
+        assert_eq!(hits.len(), 3);
+
+        assert!(index.search(&vectors[0][..8], 5, 2).is_empty());
+    }
+
+    #[test]
+    fn ivf_rejects_corrupt_bytes() {
+        let vectors = clustered(100, 8, 4);
+        let bytes = build(&vectors, 4).bytes;
+
+        assert!(IvfPqIndex::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        assert!(IvfPqIndex::from_bytes(bad_magic).is_err());
+        assert!(IvfPqIndex::from_bytes(&bytes[..]).is_ok());
+    }
+
+    #[test]
+    fn ivf_maps_segment_and_reopens_for_additions() {
+        let vectors = clustered(200, 8, 4);
+        let artifact = build(&vectors[..150], 4);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memory.bin");
+        let mut file_bytes = vec![0u8; 1000];
+        file_bytes.extend_from_slice(&artifact.bytes);
+        std::fs::write(&path, &file_bytes).unwrap();
+
+        let file = File::open(&path).unwrap();
+        let index = IvfPqIndex::map(&file, 1000, artifact.bytes.len() as u64).unwrap();
+        assert!(index.is_mapped());
+        assert_eq!(index.vector_count(), 150);
+        assert_eq!(index.search(&vectors[7], 1, 1)[0].frame_id, 7);
+
+        let index = index.into_owned().unwrap();
+        assert!(!index.is_mapped());
+        let mut builder = IvfPqBuilder::from_index(&index, |frame_id| frame_id != 7).unwrap();
+        assert_eq!(builder.frame_ids().len(), 149);
+        for (i, vector) in vectors.iter().enumerate().skip(150) {
+            builder.add_document(i as FrameId, vector).unwrap();
+        }
+        let index = IvfPqIndex::from_bytes(builder.finish().unwrap().bytes).unwrap();
+        assert_eq!(index.vector_count(), 199);
+        assert_eq!(index.max_frame_id(), 199);
+        assert_eq!(index.search(&vectors[180], 1, 1)[0].frame_id, 180);
+        assert!(
+            index
+                .search(&vectors[7], 200, 4)
+                .iter()
+                .all(|hit| hit.frame_id != 7)
+        );
+    }
+}
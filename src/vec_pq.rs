@@ -2,6 +2,8 @@
 //!
 //! Compresses 384-dim f32 vectors from 1,536 bytes to 96 bytes (16x compression)
 //! while maintaining ~95% search accuracy using codebook-based quantization.
+//! Other dimensions work as long as they are a multiple of 4 (one code byte per
+//! 4 dimensions).
 //!
 //! **Algorithm**:
 //! 1. Split 384-dim vector into 96 subspaces of 4 dimensions each
@@ -26,8 +28,8 @@ const VEC_DECODE_LIMIT: usize = crate::MAX_INDEX_BYTES as usize;
 
 /// Product Quantization parameters
 const NUM_SUBSPACES: usize = 96; // 384 dims / 4 dims per subspace
-const SUBSPACE_DIM: usize = 4; // Dimensions per subspace
-const NUM_CENTROIDS: usize = 256; // 2^8 centroids (encoded as u8)
+pub(crate) const SUBSPACE_DIM: usize = 4; // Dimensions per subspace
+pub(crate) const NUM_CENTROIDS: usize = 256; // 2^8 centroids (encoded as u8)
 const TOTAL_DIM: usize = NUM_SUBSPACES * SUBSPACE_DIM; // 384
 
 /// Codebook for one subspace: 256 centroids, each with `SUBSPACE_DIM` dimensions
@@ -90,18 +92,33 @@ pub struct ProductQuantizer {
 impl ProductQuantizer {
     /// Create uninitialized quantizer
     pub fn new(dimension: u32) -> Result<Self> {
-        if dimension as usize != TOTAL_DIM {
+        let dim = dimension as usize;
+        if dim == 0 || dim % SUBSPACE_DIM != 0 {
             return Err(MemvidError::InvalidQuery {
-                reason: format!("PQ only supports {TOTAL_DIM}-dim vectors, got {dimension}"),
+                reason: format!(
+                    "PQ requires a dimension that is a multiple of {SUBSPACE_DIM}, got {dimension}"
+                ),
             });
         }
 
         Ok(Self {
-            codebooks: vec![SubspaceCodebook::new(); NUM_SUBSPACES],
+            codebooks: vec![SubspaceCodebook::new(); dim / SUBSPACE_DIM],
             dimension,
         })
     }
 
+    /// Vector dimension this quantizer was created for
+    #[must_use]
+    pub fn dimension(&self) -> u32 {
+        self.dimension
+    }
+
+    /// Number of subspaces, i.e. bytes per encoded vector
+    #[must_use]
+    pub fn num_subspaces(&self) -> usize {
+        self.codebooks.len()
+    }
+
     /// Train codebooks using k-means on sample vectors
     pub fn train(&mut self, training_vectors: &[Vec<f32>], max_iterations: usize) -> Result<()> {
         if training_vectors.is_empty() {
@@ -111,12 +128,13 @@ impl ProductQuantizer {
         }
 
         // Verify all vectors have correct dimension
+        let dim = self.dimension as usize;
         for vec in training_vectors {
-            if vec.len() != TOTAL_DIM {
+            if vec.len() != dim {
                 return Err(MemvidError::InvalidQuery {
                     reason: format!(
                         "Training vector has wrong dimension: expected {}, got {}",
-                        TOTAL_DIM,
+                        dim,
                         vec.len()
                     ),
                 });
@@ -124,7 +142,7 @@ impl ProductQuantizer {
         }
 
         // Train each subspace independently
-        for subspace_idx in 0..NUM_SUBSPACES {
+        for subspace_idx in 0..self.num_subspaces() {
             let start_dim = subspace_idx * SUBSPACE_DIM;
             let end_dim = start_dim + SUBSPACE_DIM;
 
@@ -147,21 +165,21 @@ impl ProductQuantizer {
         Ok(())
     }
 
-    /// Encode a vector into PQ codes (96 bytes)
+    /// Encode a vector into PQ codes (96 bytes for 384 dims)
     pub fn encode(&self, vector: &[f32]) -> Result<Vec<u8>> {
-        if vector.len() != TOTAL_DIM {
+        if vector.len() != self.dimension as usize {
             return Err(MemvidError::InvalidQuery {
                 reason: format!(
                     "Vector dimension mismatch: expected {}, got {}",
-                    TOTAL_DIM,
+                    self.dimension,
                     vector.len()
                 ),
             });
         }
 
-        let mut codes = Vec::with_capacity(NUM_SUBSPACES);
+        let mut codes = Vec::with_capacity(self.num_subspaces());
 
-        for subspace_idx in 0..NUM_SUBSPACES {
+        for subspace_idx in 0..self.num_subspaces() {
             let start_dim = subspace_idx * SUBSPACE_DIM;
             let end_dim = start_dim + SUBSPACE_DIM;
             let subspace = &vector[start_dim..end_dim];
@@ -175,17 +193,17 @@ impl ProductQuantizer {
 
     /// Decode PQ codes back to approximate vector (for debugging/verification)
     pub fn decode(&self, codes: &[u8]) -> Result<Vec<f32>> {
-        if codes.len() != NUM_SUBSPACES {
+        if codes.len() != self.num_subspaces() {
             return Err(MemvidError::InvalidQuery {
                 reason: format!(
                     "Invalid PQ codes length: expected {}, got {}",
-                    NUM_SUBSPACES,
+                    self.num_subspaces(),
                     codes.len()
                 ),
             });
         }
 
-        let mut vector = Vec::with_capacity(TOTAL_DIM);
+        let mut vector = Vec::with_capacity(self.dimension as usize);
 
         for (subspace_idx, &code) in codes.iter().enumerate() {
             let centroid = self.codebooks[subspace_idx].get_centroid(code);
@@ -199,13 +217,13 @@ impl ProductQuantizer {
     /// Uses precomputed lookup tables for efficiency
     #[must_use]
     pub fn asymmetric_distance(&self, query: &[f32], codes: &[u8]) -> f32 {
-        if query.len() != TOTAL_DIM || codes.len() != NUM_SUBSPACES {
+        if query.len() != self.dimension as usize || codes.len() != self.num_subspaces() {
             return f32::INFINITY;
         }
 
         let mut total_dist_sq = 0.0f32;
 
-        for subspace_idx in 0..NUM_SUBSPACES {
+        for subspace_idx in 0..self.num_subspaces() {
             let start_dim = subspace_idx * SUBSPACE_DIM;
             let end_dim = start_dim + SUBSPACE_DIM;
             let query_subspace = &query[start_dim..end_dim];
@@ -218,6 +236,36 @@ impl ProductQuantizer {
 
         total_dist_sq.sqrt()
     }
+
+    /// Precompute the ADC lookup table for a query: squared distances from each
+    /// query subspace to all 256 centroids of that subspace, laid out as
+    /// `table[subspace * 256 + code]`. Returns `None` on dimension mismatch.
+    #[must_use]
+    pub fn distance_table(&self, query: &[f32]) -> Option<Vec<f32>> {
+        if query.len() != self.dimension as usize {
+            return None;
+        }
+
+        let mut table = Vec::with_capacity(self.num_subspaces() * NUM_CENTROIDS);
+        for (codebook, query_subspace) in
+            self.codebooks.iter().zip(query.chunks_exact(SUBSPACE_DIM))
+        {
+            for centroid in codebook.centroids.chunks_exact(SUBSPACE_DIM) {
+                table.push(l2_distance_squared(query_subspace, centroid));
+            }
+        }
+        Some(table)
+    }
+
+    /// Squared distance for PQ codes using a table from [`Self::distance_table`].
+    #[must_use]
+    pub fn table_distance_squared(table: &[f32], codes: &[u8]) -> f32 {
+        codes
+            .iter()
+            .enumerate()
+            .map(|(subspace_idx, &code)| table[subspace_idx * NUM_CENTROIDS + code as usize])
+            .sum()
+    }
 }
 
 /// Compressed vector document
@@ -343,7 +391,7 @@ impl QuantizedVecIndex {
         // Convert old format to new format
         let quantizer = ProductQuantizer {
             codebooks: old_quantizer.codebooks,
-            dimension: u32::try_from(NUM_SUBSPACES * SUBSPACE_DIM).unwrap_or(u32::MAX),
+            dimension: u32::try_from(TOTAL_DIM).unwrap_or(u32::MAX),
         };
 
         Ok(Self {
@@ -388,16 +436,19 @@ impl QuantizedVecIndex {
     /// Get compression statistics
     #[must_use]
     pub fn compression_stats(&self) -> CompressionStats {
-        let original_bytes = self.documents.len() * TOTAL_DIM * std::mem::size_of::<f32>();
-        let compressed_bytes = self.documents.len() * NUM_SUBSPACES; // 96 bytes per vector
+        let num_subspaces = self.quantizer.num_subspaces();
+        let original_bytes =
+            self.documents.len() * self.quantizer.dimension as usize * std::mem::size_of::<f32>();
+        let compressed_bytes = self.documents.len() * num_subspaces; // 96 bytes per 384-dim vector
         let codebook_bytes =
-            NUM_SUBSPACES * NUM_CENTROIDS * SUBSPACE_DIM * std::mem::size_of::<f32>();
+            num_subspaces * NUM_CENTROIDS * SUBSPACE_DIM * std::mem::size_of::<f32>();
 
         CompressionStats {
             vector_count: self.documents.len() as u64,
             original_bytes: original_bytes as u64,
             compressed_bytes: compressed_bytes as u64,
             codebook_bytes: codebook_bytes as u64,
+            rescore_bytes: 0,
             total_bytes: (compressed_bytes + codebook_bytes) as u64,
             compression_ratio: original_bytes as f64 / (compressed_bytes + codebook_bytes) as f64,
         }
@@ -410,12 +461,19 @@ pub struct CompressionStats {
     pub original_bytes: u64,
     pub compressed_bytes: u64,
     pub codebook_bytes: u64,
+    /// Full-precision vectors kept beside the codes for rescoring (int8 and
+    /// binary indexes only).
+    pub rescore_bytes: u64,
     pub total_bytes: u64,
     pub compression_ratio: f64,
 }
 
-/// K-means clustering for a single subspace
-fn kmeans(vectors: &[Vec<f32>], k: usize, max_iterations: usize) -> Result<Vec<Vec<f32>>> {
+/// K-means clustering, used for PQ subspaces and the IVF coarse quantizer
+pub(crate) fn kmeans(
+    vectors: &[Vec<f32>],
+    k: usize,
+    max_iterations: usize,
+) -> Result<Vec<Vec<f32>>> {
     if vectors.is_empty() {
         return Err(MemvidError::InvalidQuery {
             reason: "Cannot run k-means on empty vector set".to_string(),
@@ -428,42 +486,30 @@ fn kmeans(vectors: &[Vec<f32>], k: usize, max_iterations: usize) -> Result<Vec<V
     let mut centroids = kmeans_plus_plus_init(vectors, k)?;
 
     for _iteration in 0..max_iterations {
-        // Assignment step: assign each vector to nearest centroid
-        let mut assignments = vec![Vec::new(); k];
+        // Assignment step: accumulate each vector into its nearest centroid
+        let mut sums = vec![vec![0.0f32; dim]; k];
+        let mut counts = vec![0usize; k];
 
         for vec in vectors {
-            let mut best_cluster = 0;
-            let mut best_dist = f32::INFINITY;
-
-            for (cluster_idx, centroid) in centroids.iter().enumerate() {
-                let dist = l2_distance_squared(vec, centroid);
-                if dist < best_dist {
-                    best_dist = dist;
-                    best_cluster = cluster_idx;
-                }
+            let best_cluster = nearest_centroid(vec, &centroids);
+            for (sum, &val) in sums[best_cluster].iter_mut().zip(vec) {
+                *sum += val;
             }
-
-            assignments[best_cluster].push(vec.clone());
+            counts[best_cluster] += 1;
         }
 
         // Update step: recompute centroids
         let mut changed = false;
-        for (cluster_idx, cluster_vecs) in assignments.iter().enumerate() {
-            if cluster_vecs.is_empty() {
+        for (cluster_idx, (mut new_centroid, count)) in sums.into_iter().zip(counts).enumerate() {
+            if count == 0 {
                 // Empty cluster: reinitialize with random vector
                 centroids[cluster_idx] = vectors[cluster_idx % vectors.len()].clone();
                 changed = true;
                 continue;
             }
 
-            let mut new_centroid = vec![0.0f32; dim];
-            for vec in cluster_vecs {
-                for (i, &val) in vec.iter().enumerate() {
-                    new_centroid[i] += val;
-                }
-            }
             for val in &mut new_centroid {
-                *val /= cluster_vecs.len() as f32;
+                *val /= count as f32;
             }
 
             // Check if centroid changed
@@ -482,6 +528,22 @@ fn kmeans(vectors: &[Vec<f32>], k: usize, max_iterations: usize) -> Result<Vec<V
     Ok(centroids)
 }
 
+/// Index of the centroid closest to `vector`
+pub(crate) fn nearest_centroid(vector: &[f32], centroids: &[Vec<f32>]) -> usize {
+    let mut best_cluster = 0;
+    let mut best_dist = f32::INFINITY;
+
+    for (cluster_idx, centroid) in centroids.iter().enumerate() {
+        let dist = l2_distance_squared(vector, centroid);
+        if dist < best_dist {
+            best_dist = dist;
+            best_cluster = cluster_idx;
+        }
+    }
+
+    best_cluster
+}
+
 /// K-means++ initialization for better initial centroids
 fn kmeans_plus_plus_init(vectors: &[Vec<f32>], k: usize) -> Result<Vec<Vec<f32>>> {
     if vectors.is_empty() || k == 0 {
@@ -490,25 +552,20 @@ fn kmeans_plus_plus_init(vectors: &[Vec<f32>], k: usize) -> Result<Vec<Vec<f32>>
         });
     }
 
-    let mut centroids = Vec::new();
+    let mut centroids = Vec::with_capacity(k);
 
     // Choose first centroid randomly (use first vector for determinism)
     centroids.push(vectors[0].clone());
 
+    // Distance from each vector to its nearest chosen centroid, updated
+    // incrementally so initialization stays O(n * k)
+    let mut distances: Vec<f32> = vectors
+        .iter()
+        .map(|vec| l2_distance_squared(vec, &vectors[0]))
+        .collect();
+
     // Choose remaining k-1 centroids
     for _ in 1..k {
-        let mut distances = Vec::new();
-
-        // Compute distance to nearest existing centroid for each vector
-        for vec in vectors {
-            let mut min_dist = f32::INFINITY;
-            for centroid in &centroids {
-                let dist = l2_distance_squared(vec, centroid);
-                min_dist = min_dist.min(dist);
-            }
-            distances.push(min_dist);
-        }
-
         // Choose vector with maximum distance as next centroid
         let max_idx = distances
             .iter()
@@ -516,7 +573,11 @@ fn kmeans_plus_plus_init(vectors: &[Vec<f32>], k: usize) -> Result<Vec<Vec<f32>>
             .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
             .map_or(0, |(idx, _)| idx);
 
-        centroids.push(vectors[max_idx].clone());
+        let next = vectors[max_idx].clone();
+        for (distance, vec) in distances.iter_mut().zip(vectors) {
+            *distance = distance.min(l2_distance_squared(vec, &next));
+        }
+        centroids.push(next);
     }
 
     Ok(centroids)
@@ -615,6 +676,30 @@ mod tests {
         assert_eq!(hits[0].frame_id, 1); // Should find exact match first
     }
 
+    #[test]
+    fn test_distance_table_matches_asymmetric_distance() {
+        // Any multiple of SUBSPACE_DIM is accepted, not just 384
+        assert!(ProductQuantizer::new(30).is_err());
+        let mut pq = ProductQuantizer::new(16).unwrap();
+        assert_eq!(pq.num_subspaces(), 4);
+
+        let training_vecs: Vec<Vec<f32>> = (0..64)
+            .map(|i| (0..16).map(|j| ((i * 7 + j * 3) % 11) as f32).collect())
+            .collect();
+        pq.train(&training_vecs, 5).unwrap();
+
+        let query = &training_vecs[3];
+        let table = pq.distance_table(query).unwrap();
+        assert_eq!(table.len(), 4 * NUM_CENTROIDS);
+        for vec in &training_vecs {
+            let codes = pq.encode(vec).unwrap();
+            let direct = pq.asymmetric_distance(query, &codes);
+            let tabled = ProductQuantizer::table_distance_squared(&table, &codes).sqrt();
+            assert!((direct - tabled).abs() < 1e-4);
+        }
+        assert!(pq.distance_table(&query[..8]).is_none());
+    }
+
     #[test]
     fn test_kmeans_simple() {
         let vectors = vec![
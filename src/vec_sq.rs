@@ -0,0 +1,1063 @@
+//! Scalar (int8) and binary quantization for vector compression
+//!
+//! Lighter-weight alternatives to PQ that need no k-means training and work at
+//! any segment size:
+//!
+//! - **Int8**: every dimension is mapped linearly from its observed `[min, max]`
+//!   range onto 256 levels (4x smaller than f32).
+//! - **Binary**: every dimension is reduced to one bit, set when the value is
+//!   above the per-dimension mean (32x smaller than f32). Candidates are ranked
+//!   by Hamming distance using hardware popcount.
+//!
+//! **Rescoring**: both indexes keep the original f32 vectors in a separate cold
+//! section that the candidate scan never touches. With rescoring enabled the
+//! best `limit * oversample` candidates are re-ranked with exact L2 distance
+//! against those originals. The originals are also what lets the index be
+//! rebuilt losslessly when frames are added or removed.
+//!
+//! A blob is the magic, the length of the hot section (quantizer, frame ids,
+//! codes and the originals' checksum), the hot section, then the originals.
+//! Opening a memory reads only up to the end of the hot section; the
+//! originals are read on first use, and before anything writes to the file.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use blake3::hash;
+use serde::{Deserialize, Serialize};
+
+use crate::types::VectorCompression;
+use crate::vec::{VecDocument, VecIndex, VecIndexArtifact, VecSearchHit};
+use crate::{MemvidError, Result, types::FrameId};
+
+fn vec_config() -> impl bincode::config::Config {
+    bincode::config::standard()
+        .with_fixed_int_encoding()
+        .with_little_endian()
+}
+
+#[allow(clippy::cast_possible_truncation)]
+const VEC_DECODE_LIMIT: usize = crate::MAX_INDEX_BYTES as usize;
+
+/// Magic prefix for int8 scalar-quantized indexes.
+const INT8_MAGIC: [u8; 4] = *b"MVQ8";
+/// Magic prefix for binary-quantized indexes.
+const BINARY_MAGIC: [u8; 4] = *b"MVQ1";
+/// Magic plus the little-endian `u64` length of the hot section.
+pub(crate) const HEADER_LEN: usize = 12;
+
+/// Default candidate multiplier used when rescoring against the originals.
+pub const DEFAULT_RESCORE_OVERSAMPLE: usize = 4;
+
+/// Number of int8 levels per dimension.
+const INT8_LEVELS: f32 = 255.0;
+
+/// Per-dimension linear quantizer mapping f32 values to u8 codes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScalarQuantizer {
+    mins: Vec<f32>,
+    /// Width of one quantization step per dimension (`(max - min) / 255`).
+    steps: Vec<f32>,
+}
+
+impl ScalarQuantizer {
+    /// Learn per-dimension ranges from the vectors to be encoded.
+    pub fn train(vectors: &[Vec<f32>]) -> Result<Self> {
+        let dimension = training_dimension(vectors)?;
+        let mut mins = vec![f32::INFINITY; dimension];
+        let mut maxs = vec![f32::NEG_INFINITY; dimension];
+        for vector in vectors {
+            for (i, &value) in vector.iter().enumerate() {
+                mins[i] = mins[i].min(value);
+                maxs[i] = maxs[i].max(value);
+            }
+        }
+        let steps = mins
+            .iter()
+            .zip(&maxs)
+            .map(|(min, max)| {
+                let step = (max - min) / INT8_LEVELS;
+                if step > f32::EPSILON { step } else { 1.0 }
+            })
+            .collect();
+        Ok(Self { mins, steps })
+    }
+
+    #[must_use]
+    pub fn dimension(&self) -> usize {
+        self.mins.len()
+    }
+
+    /// Encode a vector into one byte per dimension.
+    pub fn encode(&self, vector: &[f32]) -> Result<Vec<u8>> {
+        check_dimension(self.dimension(), vector.len())?;
+        Ok(vector
+            .iter()
+            .zip(self.mins.iter().zip(&self.steps))
+            .map(|(&value, (&min, &step))| {
+                #[allow(clippy::cast_possible_truncation)]
+                let code = ((value - min) / step).round().clamp(0.0, INT8_LEVELS) as u8;
+                code
+            })
+            .collect())
+    }
+
+    /// Reconstruct the approximate vector for a code (for debugging/verification).
+    pub fn decode(&self, codes: &[u8]) -> Result<Vec<f32>> {
+        check_dimension(self.dimension(), codes.len())?;
+        Ok(codes
+            .iter()
+            .zip(self.mins.iter().zip(&self.steps))
+            .map(|(&code, (&min, &step))| min + f32::from(code) * step)
+            .collect())
+    }
+
+    /// Project a query into code space once so each candidate costs one pass.
+    fn prepare_query(&self, query: &[f32]) -> Vec<f32> {
+        query
+            .iter()
+            .zip(self.mins.iter().zip(&self.steps))
+            .map(|(&value, (&min, &step))| (value - min) / step)
+            .collect()
+    }
+
+    /// L2 distance between a prepared query and an encoded vector.
+    fn prepared_distance(&self, prepared: &[f32], codes: &[u8]) -> f32 {
+        let mut total = 0.0f32;
+        for ((&q, &code), &step) in prepared.iter().zip(codes).zip(&self.steps) {
+            let diff = (q - f32::from(code)) * step;
+            total += diff * diff;
+        }
+        total.sqrt()
+    }
+}
+
+/// Per-dimension threshold quantizer producing one bit per dimension.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinaryQuantizer {
+    /// Bits are set when a value is above its dimension's mean.
+    thresholds: Vec<f32>,
+}
+
+impl BinaryQuantizer {
+    /// Learn per-dimension means from the vectors to be encoded.
+    pub fn train(vectors: &[Vec<f32>]) -> Result<Self> {
+        let dimension = training_dimension(vectors)?;
+        let mut sums = vec![0.0f64; dimension];
+        for vector in vectors {
+            for (sum, &value) in sums.iter_mut().zip(vector) {
+                *sum += f64::from(value);
+            }
+        }
+        let count = vectors.len() as f64;
+        #[allow(clippy::cast_possible_truncation)]
+        let thresholds = sums.into_iter().map(|sum| (sum / count) as f32).collect();
+        Ok(Self { thresholds })
+    }
+
+    #[must_use]
+    pub fn dimension(&self) -> usize {
+        self.thresholds.len()
+    }
+
+    /// Number of `u64` words used per encoded vector.
+    #[must_use]
+    pub fn words_per_vector(&self) -> usize {
+        self.dimension().div_ceil(64)
+    }
+
+    /// Encode a vector into packed sign bits.
+    pub fn encode(&self, vector: &[f32]) -> Result<Vec<u64>> {
+        check_dimension(self.dimension(), vector.len())?;
+        let mut words = vec![0u64; self.words_per_vector()];
+        for (i, (&value, &threshold)) in vector.iter().zip(&self.thresholds).enumerate() {
+            if value > threshold {
+                words[i / 64] |= 1u64 << (i % 64);
+            }
+        }
+        Ok(words)
+    }
+
+    /// Map a Hamming distance to an approximate L2 distance between unit vectors.
+    ///
+    /// The fraction of differing bits estimates the angle between the vectors
+    /// (`theta ~= pi * h / d`), which gives `l2 = 2 * sin(theta / 2)`.
+    fn estimated_l2(&self, hamming: u32) -> f32 {
+        let dimension = self.dimension().max(1) as f32;
+        let theta = std::f32::consts::PI * hamming as f32 / dimension;
+        2.0 * (theta / 2.0).sin()
+    }
+}
+
+/// Original f32 vectors kept alongside quantized codes for rescoring.
+///
+/// Stored as one flat array in document order after the codes, so the
+/// candidate scan never touches it; only the handful of rescored candidates
+/// are read. An index opened from a file leaves them there until the first
+/// rescore or rebuild needs them.
+#[derive(Debug, Clone)]
+struct RescoreVectors {
+    dimension: usize,
+    /// Loaded values; holds `None` once a deferred load has failed.
+    values: OnceLock<Option<Vec<f32>>>,
+    deferred: Option<ColdSection>,
+}
+
+/// Where a deferred index's originals live in its memory file.
+#[derive(Debug, Clone)]
+struct ColdSection {
+    path: PathBuf,
+    offset: u64,
+    length: u64,
+    checksum: [u8; 32],
+}
+
+impl ColdSection {
+    fn read(&self) -> Result<Vec<f32>> {
+        if self.length > crate::MAX_INDEX_BYTES {
+            return Err(MemvidError::InvalidToc {
+                reason: "quantized vector originals exceed index size limit".into(),
+            });
+        }
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(self.offset))?;
+        // Safe: length is checked against MAX_INDEX_BYTES above
+        #[allow(clippy::cast_possible_truncation)]
+        let mut bytes = vec![0u8; self.length as usize];
+        file.read_exact(&mut bytes)?;
+        if *hash(&bytes).as_bytes() != self.checksum {
+            return Err(MemvidError::InvalidToc {
+                reason: "quantized vector originals checksum mismatch".into(),
+            });
+        }
+        Ok(bytes_to_values(&bytes))
+    }
+}
+
+impl RescoreVectors {
+    fn from_documents(documents: &[VecDocument], dimension: usize) -> Self {
+        let mut values = Vec::with_capacity(documents.len() * dimension);
+        for doc in documents {
+            values.extend_from_slice(&doc.embedding);
+        }
+        Self {
+            dimension,
+            values: OnceLock::from(Some(values)),
+            deferred: None,
+        }
+    }
+
+    /// Originals stored inline after the hot section of a blob.
+    fn from_bytes(
+        bytes: &[u8],
+        dimension: usize,
+        count: usize,
+        checksum: [u8; 32],
+    ) -> Result<Self> {
+        if bytes.len() != count * dimension * std::mem::size_of::<f32>()
+            || *hash(bytes).as_bytes() != checksum
+        {
+            return Err(MemvidError::InvalidToc {
+                reason: "quantized vector originals do not match their index".into(),
+            });
+        }
+        Ok(Self {
+            dimension,
+            values: OnceLock::from(Some(bytes_to_values(bytes))),
+            deferred: None,
+        })
+    }
+
+    fn deferred(cold: ColdSection, dimension: usize) -> Self {
+        Self {
+            dimension,
+            values: OnceLock::new(),
+            deferred: Some(cold),
+        }
+    }
+
+    fn values(&self) -> Option<&[f32]> {
+        self.values
+            .get_or_init(|| {
+                let cold = self.deferred.as_ref()?;
+                cold.read()
+                    .map_err(|err| {
+                        tracing::warn!(error = %err, "failed to load quantized vector originals");
+                    })
+                    .ok()
+            })
+            .as_deref()
+    }
+
+    fn vector(&self, position: usize) -> Option<&[f32]> {
+        self.values()?
+            .get(position * self.dimension..(position + 1) * self.dimension)
+    }
+
+    fn remove(&mut self, position: usize) {
+        let dim = self.dimension;
+        self.values();
+        if let Some(Some(values)) = self.values.get_mut() {
+            values.drain(position * dim..(position + 1) * dim);
+        }
+    }
+
+    /// Read deferred originals into memory, failing if they can't be.
+    fn load(&mut self) -> Result<()> {
+        if self.deferred.is_none() {
+            return Ok(());
+        }
+        if self.values().is_none() {
+            return Err(MemvidError::InvalidToc {
+                reason: "quantized vector originals are unreadable".into(),
+            });
+        }
+        self.deferred = None;
+        Ok(())
+    }
+
+    fn is_loaded(&self) -> bool {
+        matches!(self.values.get(), Some(Some(_)))
+    }
+
+    fn encode(&self) -> Result<Vec<u8>> {
+        let values = self.values().ok_or_else(|| MemvidError::InvalidToc {
+            reason: "quantized vector originals are unreadable".into(),
+        })?;
+        Ok(values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect())
+    }
+}
+
+fn bytes_to_values(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+/// Hot section of an int8 blob; the originals follow it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Int8Repr {
+    quantizer: ScalarQuantizer,
+    frame_ids: Vec<FrameId>,
+    codes: Vec<u8>,
+    originals_checksum: [u8; 32],
+}
+
+/// Hot section of a binary blob; the originals follow it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BinaryRepr {
+    quantizer: BinaryQuantizer,
+    frame_ids: Vec<FrameId>,
+    codes: Vec<u64>,
+    originals_checksum: [u8; 32],
+}
+
+/// Int8 scalar-quantized vector index.
+#[derive(Debug, Clone)]
+pub struct Int8VecIndex {
+    quantizer: ScalarQuantizer,
+    frame_ids: Vec<FrameId>,
+    /// Flat codes: `codes[i*dim..(i+1)*dim]` belongs to `frame_ids[i]`.
+    codes: Vec<u8>,
+    originals: RescoreVectors,
+}
+
+impl Int8VecIndex {
+    fn build(documents: &[VecDocument]) -> Result<Self> {
+        let training: Vec<Vec<f32>> = documents.iter().map(|d| d.embedding.clone()).collect();
+        let quantizer = ScalarQuantizer::train(&training)?;
+        let dimension = quantizer.dimension();
+        let mut codes = Vec::with_capacity(documents.len() * dimension);
+        for doc in documents {
+            codes.extend(quantizer.encode(&doc.embedding)?);
+        }
+        Ok(Self {
+            quantizer,
+            frame_ids: documents.iter().map(|d| d.frame_id).collect(),
+            codes,
+            originals: RescoreVectors::from_documents(documents, dimension),
+        })
+    }
+
+    /// Search with approximate distances, optionally rescoring against originals.
+    ///
+    /// `oversample` of 0 disables rescoring.
+    #[must_use]
+    pub fn search(&self, query: &[f32], limit: usize, oversample: usize) -> Vec<VecSearchHit> {
+        let dimension = self.quantizer.dimension();
+        if query.len() != dimension || limit == 0 {
+            return Vec::new();
+        }
+        let prepared = self.quantizer.prepare_query(query);
+        let candidates: Vec<(usize, f32)> = self
+            .codes
+            .chunks_exact(dimension)
+            .enumerate()
+            .map(|(pos, codes)| (pos, self.quantizer.prepared_distance(&prepared, codes)))
+            .collect();
+        finish_search(
+            candidates,
+            &self.frame_ids,
+            &self.originals,
+            query,
+            limit,
+            oversample,
+        )
+    }
+
+    fn position(&self, frame_id: FrameId) -> Option<usize> {
+        self.frame_ids.iter().position(|id| *id == frame_id)
+    }
+
+    pub fn remove(&mut self, frame_id: FrameId) {
+        if let Some(pos) = self.position(frame_id) {
+            let dim = self.quantizer.dimension();
+            self.frame_ids.remove(pos);
+            self.codes.drain(pos * dim..(pos + 1) * dim);
+            self.originals.remove(pos);
+        }
+    }
+
+    pub(crate) fn entries(&self) -> impl Iterator<Item = (FrameId, &[f32])> + '_ {
+        self.frame_ids
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, id)| Some((*id, self.originals.vector(pos)?)))
+    }
+
+    pub(crate) fn embedding_for(&self, frame_id: FrameId) -> Option<&[f32]> {
+        self.position(frame_id)
+            .and_then(|pos| self.originals.vector(pos))
+    }
+
+    pub(crate) fn load_originals(&mut self) -> Result<()> {
+        self.originals.load()
+    }
+
+    /// Whether the originals are in memory rather than still on disk.
+    #[must_use]
+    pub fn originals_loaded(&self) -> bool {
+        self.originals.is_loaded()
+    }
+
+    /// Get compression statistics, counting the originals kept for rescoring.
+    #[must_use]
+    pub fn compression_stats(&self) -> crate::vec_pq::CompressionStats {
+        let dimension = self.quantizer.dimension();
+        // min + step per dimension
+        let codebook_bytes = dimension * 2 * std::mem::size_of::<f32>();
+        compression_stats(
+            self.frame_ids.len(),
+            dimension,
+            self.codes.len(),
+            codebook_bytes,
+        )
+    }
+
+    fn encode(&self) -> Result<Vec<u8>> {
+        let originals = self.originals.encode()?;
+        let repr = Int8Repr {
+            quantizer: self.quantizer.clone(),
+            frame_ids: self.frame_ids.clone(),
+            codes: self.codes.clone(),
+            originals_checksum: *hash(&originals).as_bytes(),
+        };
+        encode_blob(INT8_MAGIC, &repr, &originals)
+    }
+
+    fn from_repr(repr: Int8Repr, originals: RescoreVectors) -> Result<Self> {
+        let dimension = repr.quantizer.dimension();
+        if repr.codes.len() != repr.frame_ids.len() * dimension {
+            return Err(MemvidError::InvalidToc {
+                reason: "int8 vector index payload length mismatch".into(),
+            });
+        }
+        Ok(Self {
+            quantizer: repr.quantizer,
+            frame_ids: repr.frame_ids,
+            codes: repr.codes,
+            originals,
+        })
+    }
+}
+
+/// Binary-quantized vector index searched by Hamming distance.
+#[derive(Debug, Clone)]
+pub struct BinaryVecIndex {
+    quantizer: BinaryQuantizer,
+    frame_ids: Vec<FrameId>,
+    /// Flat packed bits: `words_per_vector` words per document.
+    codes: Vec<u64>,
+    originals: RescoreVectors,
+}
+
+impl BinaryVecIndex {
+    fn build(documents: &[VecDocument]) -> Result<Self> {
+        let training: Vec<Vec<f32>> = documents.iter().map(|d| d.embedding.clone()).collect();
+        let quantizer = BinaryQuantizer::train(&training)?;
+        let mut codes = Vec::with_capacity(documents.len() * quantizer.words_per_vector());
+        for doc in documents {
+            codes.extend(quantizer.encode(&doc.embedding)?);
+        }
+        Ok(Self {
+            frame_ids: documents.iter().map(|d| d.frame_id).collect(),
+            originals: RescoreVectors::from_documents(documents, quantizer.dimension()),
+            quantizer,
+            codes,
+        })
+    }
+
+    /// Search by Hamming distance, optionally rescoring against originals.
+    ///
+    /// Without rescoring, distances are angle-based L2 estimates derived from
+    /// the Hamming distance. `oversample` of 0 disables rescoring.
+    #[must_use]
+    pub fn search(&self, query: &[f32], limit: usize, oversample: usize) -> Vec<VecSearchHit> {
+        if query.len() != self.quantizer.dimension() || limit == 0 {
+            return Vec::new();
+        }
+        let Ok(query_bits) = self.quantizer.encode(query) else {
+            return Vec::new();
+        };
+        let candidates: Vec<(usize, f32)> = self
+            .codes
+            .chunks_exact(self.quantizer.words_per_vector())
+            .enumerate()
+            .map(|(pos, bits)| {
+                let hamming = crate::simd::hamming_distance(&query_bits, bits);
+                (pos, self.quantizer.estimated_l2(hamming))
+            })
+            .collect();
+        finish_search(
+            candidates,
+            &self.frame_ids,
+            &self.originals,
+            query,
+            limit,
+            oversample,
+        )
+    }
+
+    fn position(&self, frame_id: FrameId) -> Option<usize> {
+        self.frame_ids.iter().position(|id| *id == frame_id)
+    }
+
+    pub fn remove(&mut self, frame_id: FrameId) {
+        if let Some(pos) = self.position(frame_id) {
+            let words = self.quantizer.words_per_vector();
+            self.frame_ids.remove(pos);
+            self.codes.drain(pos * words..(pos + 1) * words);
+            self.originals.remove(pos);
+        }
+    }
+
+    pub(crate) fn entries(&self) -> impl Iterator<Item = (FrameId, &[f32])> + '_ {
+        self.frame_ids
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, id)| Some((*id, self.originals.vector(pos)?)))
+    }
+
+    pub(crate) fn embedding_for(&self, frame_id: FrameId) -> Option<&[f32]> {
+        self.position(frame_id)
+            .and_then(|pos| self.originals.vector(pos))
+    }
+
+    pub(crate) fn load_originals(&mut self) -> Result<()> {
+        self.originals.load()
+    }
+
+    /// Whether the originals are in memory rather than still on disk.
+    #[must_use]
+    pub fn originals_loaded(&self) -> bool {
+        self.originals.is_loaded()
+    }
+
+    /// Get compression statistics, counting the originals kept for rescoring.
+    #[must_use]
+    pub fn compression_stats(&self) -> crate::vec_pq::CompressionStats {
+        let dimension = self.quantizer.dimension();
+        let code_bytes = self.codes.len() * std::mem::size_of::<u64>();
+        let codebook_bytes = dimension * std::mem::size_of::<f32>();
+        compression_stats(self.frame_ids.len(), dimension, code_bytes, codebook_bytes)
+    }
+
+    fn encode(&self) -> Result<Vec<u8>> {
+        let originals = self.originals.encode()?;
+        let repr = BinaryRepr {
+            quantizer: self.quantizer.clone(),
+            frame_ids: self.frame_ids.clone(),
+            codes: self.codes.clone(),
+            originals_checksum: *hash(&originals).as_bytes(),
+        };
+        encode_blob(BINARY_MAGIC, &repr, &originals)
+    }
+
+    fn from_repr(repr: BinaryRepr, originals: RescoreVectors) -> Result<Self> {
+        if repr.codes.len() != repr.frame_ids.len() * repr.quantizer.words_per_vector() {
+            return Err(MemvidError::InvalidToc {
+                reason: "binary vector index payload length mismatch".into(),
+            });
+        }
+        Ok(Self {
+            quantizer: repr.quantizer,
+            frame_ids: repr.frame_ids,
+            codes: repr.codes,
+            originals,
+        })
+    }
+}
+
+/// Build an int8 or binary index artifact from full-precision documents.
+pub(crate) fn build_artifact(
+    documents: &[VecDocument],
+    compression: &VectorCompression,
+) -> Result<VecIndexArtifact> {
+    let bytes = match compression {
+        VectorCompression::Int8 => Int8VecIndex::build(documents)?.encode()?,
+        VectorCompression::Binary => BinaryVecIndex::build(documents)?.encode()?,
+        other => {
+            return Err(MemvidError::InvalidQuery {
+                reason: format!("{other:?} is not a scalar quantization mode"),
+            });
+        }
+    };
+    let checksum = *hash(&bytes).as_bytes();
+    let dimension = documents
+        .first()
+        .map_or(0, |doc| u32::try_from(doc.embedding.len()).unwrap_or(0));
+    #[cfg(feature = "parallel_segments")]
+    let bytes_uncompressed = documents
+        .iter()
+        .map(|doc| doc.embedding.len() * std::mem::size_of::<f32>())
+        .sum::<usize>() as u64;
+    Ok(VecIndexArtifact {
+        bytes,
+        vector_count: documents.len() as u64,
+        dimension,
+        checksum,
+        #[cfg(feature = "parallel_segments")]
+        bytes_uncompressed,
+    })
+}
+
+/// Lay out a blob as magic, hot-section length, hot section, then originals.
+fn encode_blob(magic: [u8; 4], hot: &impl Serialize, originals: &[u8]) -> Result<Vec<u8>> {
+    let hot = bincode::serde::encode_to_vec(hot, vec_config())?;
+    let mut bytes = Vec::with_capacity(HEADER_LEN + hot.len() + originals.len());
+    bytes.extend_from_slice(&magic);
+    bytes.extend_from_slice(&(hot.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&hot);
+    bytes.extend_from_slice(originals);
+    Ok(bytes)
+}
+
+/// The magic and hot-section length of a scalar-quantized blob, or `None`
+/// when `bytes` carries neither of our magic prefixes.
+fn parse_header(bytes: &[u8]) -> Option<Result<([u8; 4], usize)>> {
+    let magic: [u8; 4] = bytes.get(..4)?.try_into().ok()?;
+    if magic != INT8_MAGIC && magic != BINARY_MAGIC {
+        return None;
+    }
+    let hot_len = bytes
+        .get(4..HEADER_LEN)
+        .and_then(|len| <[u8; 8]>::try_from(len).ok())
+        .and_then(|len| usize::try_from(u64::from_le_bytes(len)).ok())
+        .filter(|&len| len <= VEC_DECODE_LIMIT);
+    Some(
+        hot_len
+            .map(|len| (magic, len))
+            .ok_or_else(|| MemvidError::InvalidToc {
+                reason: "quantized vector index header is truncated".into(),
+            }),
+    )
+}
+
+/// Decode a hot section, taking the originals from `originals` given the
+/// dimension, vector count and recorded checksum.
+fn decode_index(
+    magic: [u8; 4],
+    hot: &[u8],
+    originals: impl FnOnce(usize, usize, [u8; 32]) -> Result<RescoreVectors>,
+) -> Result<VecIndex> {
+    let config = bincode::config::standard()
+        .with_fixed_int_encoding()
+        .with_little_endian()
+        .with_limit::<VEC_DECODE_LIMIT>();
+    if magic == INT8_MAGIC {
+        let (repr, _) = bincode::serde::decode_from_slice::<Int8Repr, _>(hot, config)?;
+        let originals = originals(
+            repr.quantizer.dimension(),
+            repr.frame_ids.len(),
+            repr.originals_checksum,
+        )?;
+        Int8VecIndex::from_repr(repr, originals).map(VecIndex::Int8)
+    } else {
+        let (repr, _) = bincode::serde::decode_from_slice::<BinaryRepr, _>(hot, config)?;
+        let originals = originals(
+            repr.quantizer.dimension(),
+            repr.frame_ids.len(),
+            repr.originals_checksum,
+        )?;
+        BinaryVecIndex::from_repr(repr, originals).map(VecIndex::Binary)
+    }
+}
+
+/// Decode a scalar-quantized index if `bytes` carries one of our magic prefixes.
+pub(crate) fn decode_tagged(bytes: &[u8]) -> Option<Result<VecIndex>> {
+    let (magic, hot_len) = match parse_header(bytes)? {
+        Ok(header) => header,
+        Err(err) => return Some(Err(err)),
+    };
+    let Some(hot) = bytes.get(HEADER_LEN..HEADER_LEN + hot_len) else {
+        return Some(Err(MemvidError::InvalidToc {
+            reason: "quantized vector index payload is truncated".into(),
+        }));
+    };
+    let cold = &bytes[HEADER_LEN + hot_len..];
+    Some(decode_index(magic, hot, |dimension, count, checksum| {
+        RescoreVectors::from_bytes(cold, dimension, count, checksum)
+    }))
+}
+
+/// Bytes a deferred load must read from the start of a blob whose first
+/// [`HEADER_LEN`] bytes are `header`: the header and the hot section.
+/// `None` when the blob is not scalar-quantized.
+pub(crate) fn hot_prefix_len(header: &[u8]) -> Option<Result<u64>> {
+    Some(parse_header(header)?.map(|(_, hot_len)| (HEADER_LEN + hot_len) as u64))
+}
+
+/// Decode the codes of a blob stored at `offset` in the file at `path` from
+/// its `prefix` (see [`hot_prefix_len`]), leaving the originals on disk.
+pub(crate) fn decode_deferred(prefix: &[u8], path: &Path, offset: u64) -> Result<VecIndex> {
+    let (magic, hot_len) = parse_header(prefix).ok_or_else(|| MemvidError::InvalidToc {
+        reason: "not a quantized vector index".into(),
+    })??;
+    let hot = prefix
+        .get(HEADER_LEN..HEADER_LEN + hot_len)
+        .ok_or_else(|| MemvidError::InvalidToc {
+            reason: "quantized vector index payload is truncated".into(),
+        })?;
+    decode_index(magic, hot, |dimension, count, checksum| {
+        let cold = ColdSection {
+            path: path.to_path_buf(),
+            offset: offset + prefix.len() as u64,
+            length: (count * dimension * std::mem::size_of::<f32>()) as u64,
+            checksum,
+        };
+        Ok(RescoreVectors::deferred(cold, dimension))
+    })
+}
+
+/// Fraction of the baseline top-k that also appears in the candidate top-k.
+///
+/// Used to compare a quantized index against uncompressed brute-force search.
+#[must_use]
+pub fn recall_at_k(baseline: &[VecSearchHit], candidate: &[VecSearchHit], k: usize) -> f64 {
+    let expected: std::collections::HashSet<FrameId> =
+        baseline.iter().take(k).map(|hit| hit.frame_id).collect();
+    if expected.is_empty() {
+        return 1.0;
+    }
+    let found = candidate
+        .iter()
+        .take(k)
+        .filter(|hit| expected.contains(&hit.frame_id))
+        .count();
+    found as f64 / expected.len() as f64
+}
+
+/// Rank approximate candidates and optionally re-rank the best ones exactly.
+fn finish_search(
+    mut candidates: Vec<(usize, f32)>,
+    frame_ids: &[FrameId],
+    originals: &RescoreVectors,
+    query: &[f32],
+    limit: usize,
+    oversample: usize,
+) -> Vec<VecSearchHit> {
+    let keep = if oversample == 0 {
+        limit
+    } else {
+        limit.saturating_mul(oversample)
+    };
+    sort_candidates(&mut candidates);
+    candidates.truncate(keep);
+
+    // Unreadable originals leave the quantized distances in place
+    if oversample > 0 && originals.values().is_some() {
+        for (pos, distance) in &mut candidates {
+            if let Some(original) = originals.vector(*pos) {
+                *distance = crate::simd::l2_distance_simd(query, original);
+            }
+        }
+        sort_candidates(&mut candidates);
+    }
+    candidates.truncate(limit);
+
+    candidates
+        .into_iter()
+        .map(|(pos, distance)| VecSearchHit {
+            frame_id: frame_ids[pos],
+            distance,
+        })
+        .collect()
+}
+
+fn sort_candidates(candidates: &mut [(usize, f32)]) {
+    candidates.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+}
+
+fn compression_stats(
+    count: usize,
+    dimension: usize,
+    compressed_bytes: usize,
+    codebook_bytes: usize,
+) -> crate::vec_pq::CompressionStats {
+    let original_bytes = count * dimension * std::mem::size_of::<f32>();
+    // The originals are kept in full for rescoring and rebuilds
+    let rescore_bytes = original_bytes;
+    let total_bytes = compressed_bytes + codebook_bytes + rescore_bytes;
+    crate::vec_pq::CompressionStats {
+        vector_count: count as u64,
+        original_bytes: original_bytes as u64,
+        compressed_bytes: compressed_bytes as u64,
+        codebook_bytes: codebook_bytes as u64,
+        rescore_bytes: rescore_bytes as u64,
+        total_bytes: total_bytes as u64,
+        compression_ratio: if total_bytes == 0 {
+            0.0
+        } else {
+            original_bytes as f64 / total_bytes as f64
+        },
+    }
+}
+
+fn training_dimension(vectors: &[Vec<f32>]) -> Result<usize> {
+    let dimension = vectors.first().map_or(0, Vec::len);
+    if dimension == 0 {
+        return Err(MemvidError::InvalidQuery {
+            reason: "Cannot train scalar quantizer with empty training set".to_string(),
+        });
+    }
+    for vector in vectors {
+        check_dimension(dimension, vector.len())?;
+    }
+    Ok(dimension)
+}
+
+fn check_dimension(expected: usize, actual: usize) -> Result<()> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(MemvidError::VecDimensionMismatch {
+            expected: u32::try_from(expected).unwrap_or(u32::MAX),
+            actual,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn documents(count: usize, dim: usize) -> Vec<VecDocument> {
+        (0..count)
+            .map(|i| VecDocument {
+                frame_id: i as FrameId,
+                embedding: (0..dim)
+                    .map(|j| (((i * 31 + j * 17) % 97) as f32 / 97.0) - 0.5)
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn brute_force(docs: &[VecDocument], query: &[f32], k: usize) -> Vec<VecSearchHit> {
+        VecIndex::Uncompressed {
+            documents: docs.to_vec(),
+        }
+        .search(query, k)
+    }
+
+    #[test]
+    fn scalar_quantizer_roundtrip() {
+        let docs = documents(20, 16);
+        let training: Vec<Vec<f32>> = docs.iter().map(|d| d.embedding.clone()).collect();
+        let quantizer = ScalarQuantizer::train(&training).unwrap();
+        let codes = quantizer.encode(&training[3]).unwrap();
+        let decoded = quantizer.decode(&codes).unwrap();
+        let error = crate::simd::l2_distance_simd(&training[3], &decoded);
+        assert!(error < 0.05, "reconstruction error too large: {}", error);
+    }
+
+    #[test]
+    fn binary_quantizer_sets_bits_above_mean() {
+        let quantizer = BinaryQuantizer::train(&[vec![0.0, 0.0], vec![1.0, 1.0]]).unwrap();
+        assert_eq!(quantizer.encode(&[0.9, 0.1]).unwrap(), vec![0b01]);
+        assert_eq!(quantizer.words_per_vector(), 1);
+    }
+
+    #[test]
+    fn int8_index_roundtrip_and_recall() {
+        let docs = documents(300, 32);
+        let artifact = build_artifact(&docs, &VectorCompression::Int8).unwrap();
+        assert_eq!(artifact.vector_count, 300);
+        assert_eq!(artifact.dimension, 32);
+
+        let index = VecIndex::decode(&artifact.bytes).unwrap();
+        assert!(matches!(index, VecIndex::Int8(_)));
+
+        let query = &docs[42].embedding;
+        let hits = index.search(query, 10);
+        assert_eq!(hits[0].frame_id, 42);
+        let recall = recall_at_k(&brute_force(&docs, query, 10), &hits, 10);
+        assert!(recall >= 0.9, "int8 recall {} should be >= 0.9", recall);
+    }
+
+    #[test]
+    fn binary_rescoring_recovers_exact_order() {
+        let docs = documents(300, 64);
+        let artifact = build_artifact(&docs, &VectorCompression::Binary).unwrap();
+        let VecIndex::Binary(index) = VecIndex::decode(&artifact.bytes).unwrap() else {
+            panic!("expected binary index");
+        };
+
+        let query = &docs[7].embedding;
+        let baseline = brute_force(&docs, query, 10);
+        let rescored = index.search(query, 10, 8);
+        assert_eq!(rescored[0].frame_id, 7);
+        assert!(rescored[0].distance < 1e-6);
+        let rescored_recall = recall_at_k(&baseline, &rescored, 10);
+        let raw_recall = recall_at_k(&baseline, &index.search(query, 10, 0), 10);
+        assert!(rescored_recall >= raw_recall);
+        assert!(
+            rescored_recall >= 0.8,
+            "rescored recall {} should be >= 0.8",
+            rescored_recall
+        );
+    }
+
+    #[test]
+    fn binary_recall_stays_above_floor() {
+        // Unstructured vectors from a fixed xorshift stream
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 40) as f32 / (1u64 << 24) as f32 - 0.5
+        };
+        let docs: Vec<VecDocument> = (0..500)
+            .map(|i| VecDocument {
+                frame_id: i,
+                embedding: (0..64).map(|_| next()).collect(),
+            })
+            .collect();
+        let queries: Vec<Vec<f32>> = (0..20).map(|_| (0..64).map(|_| next()).collect()).collect();
+        let artifact = build_artifact(&docs, &VectorCompression::Binary).unwrap();
+        let VecIndex::Binary(index) = VecIndex::decode(&artifact.bytes).unwrap() else {
+            panic!("expected binary index");
+        };
+
+        let mean_recall = |oversample: usize| {
+            queries
+                .iter()
+                .map(|query| {
+                    let baseline = brute_force(&docs, query, 10);
+                    recall_at_k(&baseline, &index.search(query, 10, oversample), 10)
+                })
+                .sum::<f64>()
+                / queries.len() as f64
+        };
+        let raw = mean_recall(0);
+        let rescored = mean_recall(DEFAULT_RESCORE_OVERSAMPLE);
+        // Measured 0.30 raw and 0.63 rescored; one bit per dimension of
+        // unstructured vectors is the hard case
+        assert!(raw >= 0.25, "raw binary recall {raw} below 0.25");
+        assert!(
+            rescored >= 0.55 && rescored > raw,
+            "rescored binary recall {rescored} below 0.55"
+        );
+    }
+
+    #[test]
+    fn quantized_index_keeps_originals_for_rebuilds() {
+        let docs = documents(10, 8);
+        let artifact = build_artifact(&docs, &VectorCompression::Binary).unwrap();
+        let mut index = VecIndex::decode(&artifact.bytes).unwrap();
+        assert_eq!(index.embedding_for(3), Some(docs[3].embedding.as_slice()));
+
+        index.remove(3);
+        assert_eq!(index.entries().count(), 9);
+        assert!(index.embedding_for(3).is_none());
+        assert!(
+            index
+                .search(&docs[3].embedding, 10)
+                .iter()
+                .all(|h| h.frame_id != 3)
+        );
+    }
+
+    #[test]
+    fn deferred_originals_load_on_first_rescore() {
+        let docs = documents(50, 16);
+        let artifact = build_artifact(&docs, &VectorCompression::Int8).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.bin");
+        let offset = 100u64;
+        let mut file_bytes = vec![0u8; 100];
+        file_bytes.extend_from_slice(&artifact.bytes);
+        std::fs::write(&path, &file_bytes).unwrap();
+
+        let prefix_len = hot_prefix_len(&artifact.bytes[..HEADER_LEN])
+            .unwrap()
+            .unwrap();
+        let prefix = &artifact.bytes[..usize::try_from(prefix_len).unwrap()];
+        let VecIndex::Int8(index) = decode_deferred(prefix, &path, offset).unwrap() else {
+            panic!("expected int8 index");
+        };
+        assert!(!index.originals_loaded());
+        // Unrescored searches only touch the codes
+        assert_eq!(index.search(&docs[9].embedding, 1, 0)[0].frame_id, 9);
+        assert!(!index.originals_loaded());
+
+        let hits = index.search(&docs[9].embedding, 1, 4);
+        assert!(index.originals_loaded());
+        assert!(hits[0].distance < 1e-6);
+        assert_eq!(index.entries().count(), 50);
+
+        // Overwritten originals fail their checksum rather than rescoring with garbage
+        let last = file_bytes.len() - 1;
+        file_bytes[last] ^= 0xff;
+        std::fs::write(&path, &file_bytes).unwrap();
+        let VecIndex::Int8(mut index) = decode_deferred(prefix, &path, offset).unwrap() else {
+            panic!("expected int8 index");
+        };
+        assert_eq!(index.search(&docs[9].embedding, 1, 4)[0].frame_id, 9);
+        assert!(index.load_originals().is_err());
+    }
+
+    #[test]
+    fn compression_stats_count_originals() {
+        let docs = documents(100, 64);
+        let artifact = build_artifact(&docs, &VectorCompression::Int8).unwrap();
+        let VecIndex::Int8(index) = VecIndex::decode(&artifact.bytes).unwrap() else {
+            panic!("expected int8 index");
+        };
+        let stats = index.compression_stats();
+        assert_eq!(stats.compressed_bytes, 100 * 64);
+        // The originals kept for rescoring cost as much as the uncompressed index
+        assert_eq!(stats.rescore_bytes, stats.original_bytes);
+        assert_eq!(
+            stats.total_bytes,
+            stats.compressed_bytes + stats.codebook_bytes + stats.rescore_bytes
+        );
+        assert!(stats.compression_ratio < 1.0);
+    }
+}
@@ -12,6 +12,7 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 use crate::MemvidError;
+use crate::reader::{TranscriptCue, write_webvtt};
 
 // These are only used when whisper feature is enabled
 #[cfg(feature = "whisper")]
@@ -277,6 +278,50 @@ pub struct TranscriptionSegment {
     pub text: String,
 }
 
+impl TranscriptionResult {
+    /// The transcription as cues, in the representation used for ingested
+    /// subtitles. Without segments the whole text becomes a single cue.
+    #[must_use]
+    pub fn cues(&self) -> Vec<TranscriptCue> {
+        let segments = if self.segments.is_empty() {
+            vec![TranscriptionSegment {
+                start: 0.0,
+                end: self.duration_secs,
+                text: self.text.clone(),
+            }]
+        } else {
+            self.segments.clone()
+        };
+        segments
+            .iter()
+            .map(TranscriptCue::from)
+            .filter(|cue| !cue.text.is_empty())
+            .collect()
+    }
+
+    /// The transcription as a `WebVTT` file, ready for `put_bytes` with a
+    /// `text/vtt` MIME type.
+    #[must_use]
+    pub fn to_webvtt(&self) -> String {
+        write_webvtt(&self.cues())
+    }
+}
+
+impl From<&TranscriptionSegment> for TranscriptCue {
+    fn from(segment: &TranscriptionSegment) -> Self {
+        Self {
+            start_seconds: segment.start,
+            end_seconds: segment.end.max(segment.start),
+            speaker: None,
+            text: segment
+                .text
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+}
+
 // ============================================================================
 // Audio Decoding (Feature-gated)
 // ============================================================================
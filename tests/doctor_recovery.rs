@@ -72,6 +72,11 @@ fn doctor_rebuilds_tantivy_index() {
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                diversify: None,
+                highlight: None,
+                facets: Vec::new(),
+                sort: memvid_core::types::SearchSort::Relevance,
+                explain: false,
             })
             .unwrap();
 
@@ -125,6 +130,11 @@ fn doctor_rebuilds_tantivy_index() {
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                diversify: None,
+                highlight: None,
+                facets: Vec::new(),
+                sort: memvid_core::types::SearchSort::Relevance,
+                explain: false,
             })
             .unwrap();
 
@@ -278,6 +288,11 @@ fn open_file_with_tantivy_segments_enables_lex() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            diversify: None,
+            highlight: None,
+            facets: Vec::new(),
+            sort: memvid_core::types::SearchSort::Relevance,
+            explain: false,
         });
 
         assert!(
@@ -371,6 +386,11 @@ fn doctor_rebuild_produces_searchable_index() {
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                diversify: None,
+                highlight: None,
+                facets: Vec::new(),
+                sort: memvid_core::types::SearchSort::Relevance,
+                explain: false,
             })
             .unwrap();
 
@@ -400,6 +420,11 @@ fn doctor_rebuild_produces_searchable_index() {
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                diversify: None,
+                highlight: None,
+                facets: Vec::new(),
+                sort: memvid_core::types::SearchSort::Relevance,
+                explain: false,
             })
             .unwrap();
 
@@ -1,8 +1,12 @@
 //! Integration tests for Memvid lifecycle operations.
 //! Tests: create, open, open_read_only, commit, stats, verify
 
-use memvid_core::{Memvid, PutOptions, VerificationStatus};
+use memvid_core::{
+    EmbeddingCacheConfig, EmbeddingIdentity, Memvid, PutOptions, Result, VecEmbedder,
+    VerificationStatus,
+};
 use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use tempfile::TempDir;
 
 /// Test basic create and open lifecycle.
@@ -265,3 +269,114 @@ fn commit_without_changes() {
         "Commit without changes should not significantly change file size"
     );
 }
+
+struct CountingEmbedder {
+    calls: AtomicUsize,
+}
+
+impl VecEmbedder for CountingEmbedder {
+    fn embed_query(&self, text: &str) -> Result<Vec<f32>> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(vec![text.len() as f32, 1.0])
+    }
+
+    fn embedding_dimension(&self) -> usize {
+        2
+    }
+
+    fn embedding_identity(&self) -> Option<EmbeddingIdentity> {
+        Some(EmbeddingIdentity {
+            provider: Some("test".into()),
+            model: Some("counting".into()),
+            dimension: Some(2),
+            normalized: None,
+        })
+    }
+}
+
+/// Test that the embedding cache track survives reopen and serves hits.
+#[test]
+fn embedding_cache_persists_across_reopen() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    let embedder = CountingEmbedder {
+        calls: AtomicUsize::new(0),
+    };
+
+    {
+        let mut mem = Memvid::create(&path).unwrap();
+        mem.enable_embedding_cache(EmbeddingCacheConfig::default());
+        let embeddings = mem
+            .embed_texts_cached(&embedder, &["alpha", "beta gamma"])
+            .unwrap();
+        assert_eq!(embeddings[1], vec![10.0, 1.0]);
+        mem.put_bytes(b"alpha").unwrap();
+        mem.commit().unwrap();
+    }
+    assert_eq!(embedder.calls.load(Ordering::SeqCst), 2);
+
+    {
+        let mut mem = Memvid::open(&path).unwrap();
+        let embeddings = mem
+            .embed_texts_cached(&embedder, &["alpha", "beta  gamma", "delta"])
+            .unwrap();
+        assert_eq!(embeddings[1], vec![10.0, 1.0]);
+        // Only "delta" reaches the embedder
+        assert_eq!(embedder.calls.load(Ordering::SeqCst), 3);
+
+        let stats = mem.stats().unwrap();
+        let cache = stats.embedding_cache.expect("cache stats");
+        assert_eq!(cache.entries, 3);
+        assert_eq!(cache.hits, 2);
+        assert_eq!(cache.misses, 1);
+
+        mem.disable_embedding_cache();
+        mem.commit().unwrap();
+    }
+
+    {
+        let mem = Memvid::open(&path).unwrap();
+        assert!(mem.embedding_cache_stats().is_none());
+    }
+    let report = Memvid::verify(&path, false).unwrap();
+    assert_eq!(report.overall_status, VerificationStatus::Passed);
+}
+
+/// Test that a file written by the baseline on-disk format opens, verifies
+/// and accepts new frames.
+#[test]
+fn opens_file_written_by_baseline_format() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("baseline.mv2");
+    let fixture =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/baseline.mv2");
+    fs::copy(&fixture, &path).unwrap();
+
+    let report = Memvid::verify(&path, false).unwrap();
+    assert_eq!(report.overall_status, VerificationStatus::Passed);
+
+    {
+        let mut mem = Memvid::open_read_only(&path).unwrap();
+        assert_eq!(mem.frame_count(), 3);
+        let frame = mem.frame_by_uri("mv2://docs/gamma.md").unwrap();
+        assert_eq!(frame.title.as_deref(), Some("Gamma"));
+        let text = mem.frame_text_by_id(frame.id).unwrap();
+        assert!(text.contains("incident retrospective"));
+    }
+
+    {
+        let mut mem = Memvid::open(&path).unwrap();
+        let opts = PutOptions {
+            uri: Some("mv2://docs/delta.md".to_string()),
+            ..Default::default()
+        };
+        mem.put_bytes_with_options(b"Delta notes about the vendor audit.", opts)
+            .unwrap();
+        mem.commit().unwrap();
+    }
+
+    let mem = Memvid::open_read_only(&path).unwrap();
+    assert_eq!(mem.stats().unwrap().frame_count, 4);
+    let report = Memvid::verify(&path, false).unwrap();
+    assert_eq!(report.overall_status, VerificationStatus::Passed);
+}
@@ -46,6 +46,11 @@ fn replay_save_does_not_corrupt_lex_index() {
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                diversify: None,
+                highlight: None,
+                facets: Vec::new(),
+                sort: memvid_core::types::SearchSort::Relevance,
+                explain: false,
             })
             .unwrap();
         mem.end_session().unwrap();
@@ -73,6 +78,11 @@ fn replay_save_does_not_corrupt_lex_index() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            diversify: None,
+            highlight: None,
+            facets: Vec::new(),
+            sort: memvid_core::types::SearchSort::Relevance,
+            explain: false,
         })
         .unwrap();
 
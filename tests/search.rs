@@ -1,7 +1,7 @@
 //! Integration tests for Memvid search operations.
 //! Tests: search (lex), timeline queries
 
-use memvid_core::{Memvid, PutOptions, SearchRequest, TimelineQuery};
+use memvid_core::{DiversifyOptions, Memvid, PutOptions, SearchRequest, TimelineQuery};
 use std::num::NonZeroU64;
 use tempfile::TempDir;
 
@@ -78,6 +78,11 @@ fn search_basic_query() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            diversify: None,
+            highlight: None,
+            facets: Vec::new(),
+            sort: memvid_core::types::SearchSort::Relevance,
+            explain: false,
         })
         .unwrap();
 
@@ -115,6 +120,11 @@ fn search_multiple_results() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            diversify: None,
+            highlight: None,
+            facets: Vec::new(),
+            sort: memvid_core::types::SearchSort::Relevance,
+            explain: false,
         })
         .unwrap();
 
@@ -169,6 +179,11 @@ fn search_respects_top_k() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            diversify: None,
+            highlight: None,
+            facets: Vec::new(),
+            sort: memvid_core::types::SearchSort::Relevance,
+            explain: false,
         })
         .unwrap();
 
@@ -202,6 +217,11 @@ fn search_with_scope() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            diversify: None,
+            highlight: None,
+            facets: Vec::new(),
+            sort: memvid_core::types::SearchSort::Relevance,
+            explain: false,
         })
         .unwrap();
 
@@ -239,6 +259,11 @@ fn search_returns_snippets() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            diversify: None,
+            highlight: None,
+            facets: Vec::new(),
+            sort: memvid_core::types::SearchSort::Relevance,
+            explain: false,
         })
         .unwrap();
 
@@ -274,6 +299,11 @@ fn search_no_results() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            diversify: None,
+            highlight: None,
+            facets: Vec::new(),
+            sort: memvid_core::types::SearchSort::Relevance,
+            explain: false,
         })
         .unwrap();
 
@@ -309,6 +339,11 @@ fn search_empty_memory() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            diversify: None,
+            highlight: None,
+            facets: Vec::new(),
+            sort: memvid_core::types::SearchSort::Relevance,
+            explain: false,
         })
         .unwrap();
 
@@ -482,3 +517,1374 @@ fn timeline_respects_limit() {
         "Timeline should return exactly limit entries"
     );
 }
+
+/// Test diversification drops near-identical frames before filling top_k.
+#[test]
+#[cfg(feature = "lex")]
+fn search_diversify_drops_near_duplicates() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    let repeated = "standup notes: deploy the billing service on friday after review";
+    for i in 0..4 {
+        let opts = PutOptions {
+            uri: Some(format!("mv2://chat/{i}")),
+            search_text: Some(repeated.to_string()),
+            ..Default::default()
+        };
+        mem.put_bytes_with_options(repeated.as_bytes(), opts)
+            .unwrap();
+    }
+    let distinct = "billing dashboard shows a spike in failed invoices this week";
+    let opts = PutOptions {
+        uri: Some("mv2://chat/other".to_string()),
+        search_text: Some(distinct.to_string()),
+        ..Default::default()
+    };
+    mem.put_bytes_with_options(distinct.as_bytes(), opts)
+        .unwrap();
+    mem.commit().unwrap();
+
+    let request = |diversify| SearchRequest {
+        query: "billing".to_string(),
+        top_k: 2,
+        snippet_chars: 200,
+        uri: None,
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch: true,
+        acl_context: None,
+        acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        diversify,
+        highlight: None,
+        facets: Vec::new(),
+        sort: memvid_core::types::SearchSort::Relevance,
+        explain: false,
+    };
+
+    let plain = mem.search(request(None)).unwrap();
+    assert_eq!(plain.hits.len(), 2);
+
+    let diversified = mem
+        .search(request(Some(DiversifyOptions {
+            near_duplicate_threshold: Some(0.9),
+            ..Default::default()
+        })))
+        .unwrap();
+    assert_eq!(diversified.hits.len(), 2);
+    let uris: Vec<&str> = diversified.hits.iter().map(|h| h.uri.as_str()).collect();
+    assert!(uris.contains(&"mv2://chat/other"), "got {uris:?}");
+    assert_eq!(diversified.hits[1].rank, 2);
+    // Paging reflects the caller's top_k, not the over-fetched window.
+    assert_eq!(diversified.params.top_k, 2);
+    assert_eq!(diversified.total_hits, 2, "duplicates are not counted");
+    assert!(diversified.next_cursor.is_none());
+}
+
+#[test]
+#[cfg(feature = "lex")]
+fn search_fuzzy_prefix_and_suggestions() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    let docs = [
+        ("mv2://ops/cluster", "kubernetes cluster upgrade checklist"),
+        (
+            "mv2://ops/deploy",
+            "deployment pipeline for the kubernetes operator",
+        ),
+        ("mv2://notes/rust", "rust ownership notes"),
+    ];
+    for (uri, text) in docs {
+        let opts = PutOptions {
+            uri: Some(uri.to_string()),
+            search_text: Some(text.to_string()),
+            ..Default::default()
+        };
+        mem.put_bytes_with_options(text.as_bytes(), opts).unwrap();
+    }
+    mem.commit().unwrap();
+
+    let request = |query: &str| SearchRequest {
+        query: query.to_string(),
+        top_k: 10,
+        snippet_chars: 200,
+        uri: None,
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch: true,
+        acl_context: None,
+        acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        diversify: None,
+        highlight: None,
+        facets: Vec::new(),
+        sort: memvid_core::types::SearchSort::Relevance,
+        explain: false,
+    };
+
+    let fuzzy = mem.search(request("kubernettes~1")).unwrap();
+    assert_eq!(fuzzy.hits.len(), 2, "fuzzy term should tolerate one typo");
+
+    let prefix = mem.search(request("deploy*")).unwrap();
+    assert_eq!(prefix.hits.len(), 1);
+    assert_eq!(prefix.hits[0].uri, "mv2://ops/deploy");
+
+    // "deployment" stems to "deploy"; the prefix must match the word as written.
+    let unstemmed = mem.search(request("Deploym*")).unwrap();
+    assert_eq!(unstemmed.hits.len(), 1);
+    assert_eq!(unstemmed.hits[0].uri, "mv2://ops/deploy");
+
+    let typo = mem.search(request("ownrship")).unwrap();
+    assert!(typo.hits.is_empty());
+    assert_eq!(typo.suggested_query.as_deref(), Some("ownership"));
+
+    assert_eq!(
+        mem.suggest_query("kubernetes clustr").unwrap().as_deref(),
+        Some("kubernetes cluster")
+    );
+    assert_eq!(mem.suggest_query("rust notes").unwrap(), None);
+}
+
+/// Test title and typed metadata clauses, and that the schema survives reopen.
+#[test]
+#[cfg(feature = "lex")]
+fn search_title_and_metadata_fields() {
+    use memvid_core::{MetadataFieldType, SearchSchema};
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    let docs = [
+        (
+            "Quarterly roadmap",
+            "acme",
+            "5",
+            "planning notes for the launch",
+        ),
+        (
+            "Incident review",
+            "acme",
+            "2",
+            "roadmap slipped after the outage",
+        ),
+        ("Hiring plan", "globex", "4", "roadmap for new teams"),
+    ];
+    for (title, customer, priority, text) in docs {
+        let mut opts = PutOptions {
+            title: Some(title.to_string()),
+            search_text: Some(text.to_string()),
+            ..Default::default()
+        };
+        opts.extra_metadata
+            .insert("Customer".to_string(), customer.to_string());
+        opts.extra_metadata
+            .insert("priority".to_string(), priority.to_string());
+        mem.put_bytes_with_options(text.as_bytes(), opts).unwrap();
+    }
+    mem.commit().unwrap();
+
+    let schema = SearchSchema::default()
+        .with_title_boost(4.0)
+        .with_metadata_field("customer", MetadataFieldType::Keyword)
+        .with_metadata_field("priority", MetadataFieldType::I64);
+    mem.set_search_schema(schema.clone()).unwrap();
+    mem.commit().unwrap();
+    drop(mem);
+
+    let mut mem = Memvid::open(&path).unwrap();
+    assert_eq!(mem.search_schema(), schema);
+
+    let request = |query: &str| SearchRequest {
+        query: query.to_string(),
+        top_k: 10,
+        snippet_chars: 200,
+        uri: None,
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch: true,
+        acl_context: None,
+        acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        diversify: None,
+        highlight: None,
+        facets: Vec::new(),
+        sort: memvid_core::types::SearchSort::Relevance,
+        explain: false,
+    };
+    let titles = |query: &str, mem: &mut Memvid| -> Vec<String> {
+        let response = mem.search(request(query)).unwrap();
+        let mut titles: Vec<String> = response
+            .hits
+            .into_iter()
+            .filter_map(|hit| hit.title)
+            .collect();
+        titles.sort();
+        titles
+    };
+
+    assert_eq!(titles("title:roadmap", &mut mem), ["Quarterly roadmap"]);
+    assert_eq!(
+        titles("meta.customer:ACME", &mut mem),
+        ["Incident review", "Quarterly roadmap"]
+    );
+    assert_eq!(
+        titles("meta.priority:[3 TO 5]", &mut mem),
+        ["Hiring plan", "Quarterly roadmap"]
+    );
+    assert_eq!(
+        titles("roadmap AND meta.priority:[* TO 4]", &mut mem),
+        ["Hiring plan", "Incident review"]
+    );
+
+    let filtered = mem.search(request("meta.priority:[3 TO 5]")).unwrap();
+    assert_eq!(
+        filtered.engine,
+        memvid_core::types::SearchEngineKind::Tantivy
+    );
+
+    let boosted = mem.search(request("roadmap")).unwrap();
+    assert_eq!(
+        boosted.hits[0].title.as_deref(),
+        Some("Quarterly roadmap"),
+        "title matches rank first"
+    );
+}
+
+/// Test phrase slop and the NEAR/n operator, including CJK text.
+#[test]
+#[cfg(feature = "lex")]
+fn search_proximity_operators() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    let docs = [
+        (
+            "mv2://notes/close",
+            "the budget got final approval on monday",
+        ),
+        (
+            "mv2://notes/far",
+            "budget talks dragged on for weeks before anyone mentioned an approval",
+        ),
+        ("mv2://notes/reversed", "approval of the budget is pending"),
+        // "approvals" shares a prefix with "approval" but is a different word
+        ("mv2://notes/plural", "approvals for the budget are due"),
+        ("mv2://notes/cjk", "会议决定预算需要经理审批后执行"),
+    ];
+    for (uri, text) in docs {
+        let opts = PutOptions {
+            uri: Some(uri.to_string()),
+            search_text: Some(text.to_string()),
+            // Keep auto-generated keyword lines out of the positions under test
+            auto_tag: false,
+            ..Default::default()
+        };
+        mem.put_bytes_with_options(text.as_bytes(), opts).unwrap();
+    }
+    mem.commit().unwrap();
+
+    let request = |query: &str| SearchRequest {
+        query: query.to_string(),
+        top_k: 10,
+        snippet_chars: 200,
+        uri: None,
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch: true,
+        acl_context: None,
+        acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        diversify: None,
+        highlight: None,
+        facets: Vec::new(),
+        sort: memvid_core::types::SearchSort::Relevance,
+        explain: false,
+    };
+    let uris = |query: &str, mem: &mut Memvid| -> Vec<String> {
+        let mut uris: Vec<String> = mem
+            .search(request(query))
+            .unwrap()
+            .hits
+            .into_iter()
+            .map(|hit| hit.uri)
+            .collect();
+        uris.sort();
+        uris
+    };
+
+    assert!(uris("\"budget approval\"", &mut mem).is_empty());
+    assert_eq!(
+        uris("\"budget approval\"~3", &mut mem),
+        ["mv2://notes/close"]
+    );
+    assert_eq!(
+        uris("budget NEAR/3 approval", &mut mem),
+        ["mv2://notes/close", "mv2://notes/reversed"]
+    );
+    assert_eq!(
+        uris(
+            "budget NEAR/3 approval OR budget NEAR/12 mentioned",
+            &mut mem
+        ),
+        [
+            "mv2://notes/close",
+            "mv2://notes/far",
+            "mv2://notes/reversed"
+        ]
+    );
+    assert_eq!(uris("预算 NEAR/3 审批", &mut mem), ["mv2://notes/cjk"]);
+    assert!(uris("预算 NEAR/1 执行", &mut mem).is_empty());
+}
+
+/// Test multi-fragment highlighting with analyzer-aligned offsets.
+#[test]
+#[cfg(feature = "lex")]
+fn search_highlight_fragments() {
+    use memvid_core::HighlightOptions;
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    let text = "Budget approvals stalled in March. The team discussed hiring, offsites and \
+                travel at length without reaching any decision. Finance finally approved \
+                the budget in May after a second review.";
+    let cjk = "会议决定预算需要经理审批后执行";
+    for (uri, body) in [("mv2://notes/budget", text), ("mv2://notes/cjk", cjk)] {
+        let opts = PutOptions {
+            uri: Some(uri.to_string()),
+            search_text: Some(body.to_string()),
+            auto_tag: false,
+            ..Default::default()
+        };
+        mem.put_bytes_with_options(body.as_bytes(), opts).unwrap();
+    }
+    mem.commit().unwrap();
+
+    let mut request = SearchRequest {
+        query: "budget approval".to_string(),
+        top_k: 1,
+        snippet_chars: 400,
+        uri: Some("mv2://notes/budget".to_string()),
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch: true,
+        acl_context: None,
+        acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        diversify: None,
+        highlight: Some(HighlightOptions {
+            max_fragments: 2,
+            fragment_chars: 60,
+        }),
+        facets: Vec::new(),
+        sort: memvid_core::types::SearchSort::Relevance,
+        explain: false,
+    };
+    let response = mem.search(request.clone()).unwrap();
+    let hit = &response.hits[0];
+    let frame_text = hit.chunk_text.as_deref().unwrap();
+    assert_eq!(hit.highlights.len(), 2);
+    for fragment in &hit.highlights {
+        assert!(!fragment.spans.is_empty());
+        for span in &fragment.spans {
+            let in_fragment = &fragment.text[span.fragment_range.0..span.fragment_range.1];
+            let in_frame = &frame_text[span.range.0..span.range.1];
+            assert_eq!(in_fragment, in_frame);
+            assert!(
+                in_frame.to_lowercase().starts_with("budget") || in_frame.starts_with("approv")
+            );
+        }
+    }
+    let marked: Vec<&str> = hit
+        .highlights
+        .iter()
+        .flat_map(|fragment| {
+            fragment
+                .spans
+                .iter()
+                .map(|s| &frame_text[s.range.0..s.range.1])
+        })
+        .collect();
+    assert!(marked.contains(&"approvals"), "stemmed match: {marked:?}");
+    assert!(marked.contains(&"approved"), "stemmed match: {marked:?}");
+
+    request.query = "审批".to_string();
+    request.uri = Some("mv2://notes/cjk".to_string());
+    let response = mem.search(request).unwrap();
+    let span = &response.hits[0].highlights[0].spans[0];
+    assert_eq!(span.char_range, (10, 12));
+    assert_eq!(&cjk[span.range.0..span.range.1], "审批");
+}
+
+/// Highlights of a chunk hit are selected from the whole document text.
+#[test]
+#[cfg(feature = "lex")]
+fn search_highlight_uses_whole_document() {
+    use memvid_core::HighlightOptions;
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    let body = format!(
+        "Budget approval arrived early. {}The budget closed.",
+        "Filler sentence about unrelated topics. ".repeat(120)
+    );
+    let opts = PutOptions {
+        uri: Some("mv2://notes/long".to_string()),
+        auto_tag: false,
+        ..Default::default()
+    };
+    mem.put_bytes_with_options(body.as_bytes(), opts).unwrap();
+    mem.commit().unwrap();
+
+    let request = SearchRequest {
+        query: "budget".to_string(),
+        top_k: 10,
+        snippet_chars: 40,
+        uri: None,
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch: true,
+        acl_context: None,
+        acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        diversify: None,
+        highlight: Some(HighlightOptions {
+            max_fragments: 1,
+            fragment_chars: 60,
+        }),
+        facets: Vec::new(),
+        sort: memvid_core::types::SearchSort::Relevance,
+        explain: false,
+    };
+    let response = mem.search(request).unwrap();
+    let late = response
+        .hits
+        .iter()
+        .find(|hit| hit.chunk_range.is_some_and(|(start, _)| start > 0))
+        .expect("hit in a later chunk");
+    let fragment = &late.highlights[0];
+    // Equal-scoring fragments prefer the earliest, which lies in the first chunk
+    assert_eq!(fragment.spans.len(), 1);
+    assert!(fragment.range.1 <= late.chunk_range.unwrap().0);
+    assert_eq!(&body[fragment.range.0..fragment.range.1], fragment.text);
+}
+
+/// Facets count every match, not just the returned page, and honour ACLs.
+#[test]
+#[cfg(feature = "lex")]
+fn search_facets_cover_full_match_set() {
+    use memvid_core::types::{
+        ACL_TENANT_ID_KEY, ACL_VISIBILITY_KEY, AclContext, AclEnforcementMode,
+    };
+    use memvid_core::{FacetField, FacetRequest, HistogramInterval};
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    // (uri, track, tenant, visibility, team, timestamp, text)
+    let docs = [
+        (
+            "mv2://slack/general/1",
+            "slack",
+            "acme",
+            "public",
+            Some("finance"),
+            1_709_510_400,
+            "budget review thread",
+        ),
+        (
+            "mv2://slack/random/2",
+            "slack",
+            "acme",
+            "public",
+            Some("finance"),
+            1_709_683_200,
+            "budget follow up",
+        ),
+        (
+            "mv2://email/inbox/3",
+            "email",
+            "acme",
+            "restricted",
+            None,
+            1_710_892_800,
+            "budget sign off",
+        ),
+        (
+            "mv2://email/inbox/4",
+            "email",
+            "globex",
+            "public",
+            None,
+            1_710_979_200,
+            "budget from another tenant",
+        ),
+        (
+            "mv2://slack/general/5",
+            "slack",
+            "acme",
+            "public",
+            None,
+            1_710_979_200,
+            "lunch plans",
+        ),
+    ];
+    for (uri, track, tenant, visibility, team, timestamp, text) in docs {
+        let mut opts = PutOptions {
+            uri: Some(uri.to_string()),
+            track: Some(track.to_string()),
+            timestamp: Some(timestamp),
+            search_text: Some(text.to_string()),
+            auto_tag: false,
+            ..Default::default()
+        };
+        opts.extra_metadata
+            .insert(ACL_TENANT_ID_KEY.to_string(), tenant.to_string());
+        opts.extra_metadata
+            .insert(ACL_VISIBILITY_KEY.to_string(), visibility.to_string());
+        if let Some(team) = team {
+            opts.extra_metadata
+                .insert("Team".to_string(), team.to_string());
+        }
+        mem.put_bytes_with_options(text.as_bytes(), opts).unwrap();
+    }
+    mem.commit().unwrap();
+
+    let mut request = SearchRequest {
+        query: "budget".to_string(),
+        top_k: 1,
+        snippet_chars: 200,
+        uri: None,
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch: true,
+        acl_context: None,
+        acl_enforcement_mode: AclEnforcementMode::Audit,
+        diversify: None,
+        highlight: None,
+        facets: vec![
+            FacetRequest::new(FacetField::Track),
+            FacetRequest::new(FacetField::UriPrefix { depth: 1 }).with_size(1),
+            FacetRequest::new(FacetField::Metadata {
+                key: "team".to_string(),
+            }),
+            FacetRequest::new(FacetField::Timestamp {
+                interval: HistogramInterval::Week,
+            }),
+        ],
+        sort: memvid_core::types::SearchSort::Relevance,
+        explain: false,
+    };
+    let counts = |result: &memvid_core::FacetResult| -> Vec<(String, usize)> {
+        result
+            .buckets
+            .iter()
+            .map(|bucket| (bucket.key.clone(), bucket.count))
+            .collect()
+    };
+
+    let response = mem.search(request.clone()).unwrap();
+    assert_eq!(response.hits.len(), 1);
+    assert_eq!(response.facets.len(), 4);
+    assert_eq!(
+        counts(&response.facets[0]),
+        [("email".to_string(), 2), ("slack".to_string(), 2)]
+    );
+    assert_eq!(
+        counts(&response.facets[1]),
+        [("mv2://email/".to_string(), 2)]
+    );
+    assert_eq!(response.facets[1].other, 2);
+    assert_eq!(counts(&response.facets[2]), [("finance".to_string(), 2)]);
+    assert_eq!(response.facets[2].missing, 2);
+    let weekly: Vec<usize> = response.facets[3]
+        .buckets
+        .iter()
+        .map(|bucket| bucket.count)
+        .collect();
+    assert_eq!(weekly, [2, 0, 2]);
+    assert_eq!(response.facets[3].buckets[0].key, "2024-03-04T00:00:00Z");
+
+    request.scope = Some("mv2://slack/".to_string());
+    let scoped = mem.search(request.clone()).unwrap();
+    assert_eq!(counts(&scoped.facets[0]), [("slack".to_string(), 2)]);
+    request.scope = None;
+
+    // Field terms that Tantivy matches loosely are re-checked against frame metadata
+    request.query = "budget AND scope:mv2://email/".to_string();
+    let scoped = mem.search(request.clone()).unwrap();
+    assert_eq!(counts(&scoped.facets[0]), [("email".to_string(), 2)]);
+    request.query = "budget".to_string();
+
+    request.acl_enforcement_mode = AclEnforcementMode::Enforce;
+    request.acl_context = Some(AclContext {
+        tenant_id: Some("acme".to_string()),
+        ..Default::default()
+    });
+    let response = mem.search(request).unwrap();
+    assert_eq!(counts(&response.facets[0]), [("slack".to_string(), 2)]);
+    let weekly: Vec<usize> = response.facets[3]
+        .buckets
+        .iter()
+        .map(|bucket| bucket.count)
+        .collect();
+    assert_eq!(weekly, [2]);
+}
+
+#[test]
+#[cfg(feature = "lex")]
+fn search_sorted_pages_are_stable_under_appends() {
+    use memvid_core::types::SearchSort;
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    let put = |mem: &mut Memvid, i: usize, timestamp: i64, text: &str| {
+        let opts = PutOptions {
+            uri: Some(format!("mv2://notes/{i}")),
+            timestamp: Some(timestamp),
+            search_text: Some(text.to_string()),
+            auto_tag: false,
+            ..Default::default()
+        };
+        mem.put_bytes_with_options(text.as_bytes(), opts).unwrap();
+    };
+    // Frame ids follow insertion order; frames 1 and 3 share a timestamp.
+    let timestamps = [1_700_000_300, 1_700_000_100, 1_700_000_500, 1_700_000_100];
+    for (i, ts) in timestamps.into_iter().enumerate() {
+        put(&mut mem, i, ts, "quarterly report draft");
+    }
+    put(&mut mem, 4, 1_700_000_200, "holiday schedule");
+    put(&mut mem, 5, 1_700_000_400, "final report");
+    mem.commit().unwrap();
+
+    let request = |sort, cursor| SearchRequest {
+        query: "report".to_string(),
+        top_k: 2,
+        snippet_chars: 200,
+        uri: None,
+        scope: None,
+        cursor,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch: true,
+        acl_context: None,
+        acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        diversify: None,
+        highlight: None,
+        facets: Vec::new(),
+        sort,
+        explain: false,
+    };
+    let page_ids = |response: &memvid_core::SearchResponse| -> Vec<u64> {
+        response.hits.iter().map(|hit| hit.frame_id).collect()
+    };
+
+    let first = mem
+        .search(request(SearchSort::TimestampDesc, None))
+        .unwrap();
+    assert_eq!(page_ids(&first), [2, 5]);
+    assert_eq!(first.total_hits, 5);
+    assert!(first.hits.iter().all(|hit| hit.score.is_none()));
+
+    // Newer matching frames appended between pages must not shift the listing.
+    put(&mut mem, 6, 1_700_000_900, "late report");
+    mem.commit().unwrap();
+
+    let second = mem
+        .search(request(
+            SearchSort::TimestampDesc,
+            first.next_cursor.clone(),
+        ))
+        .unwrap();
+    assert_eq!(page_ids(&second), [0, 3]);
+    let third = mem
+        .search(request(
+            SearchSort::TimestampDesc,
+            second.next_cursor.clone(),
+        ))
+        .unwrap();
+    assert_eq!(page_ids(&third), [1]);
+    assert!(third.next_cursor.is_none());
+
+    let mut ascending = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = mem
+            .search(request(SearchSort::TimestampAsc, cursor))
+            .unwrap();
+        ascending.extend(page_ids(&page));
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(ascending, [1, 3, 0, 5, 2, 6]);
+
+    let by_id = mem.search(request(SearchSort::FrameId, None)).unwrap();
+    assert_eq!(page_ids(&by_id), [0, 1]);
+    let by_id = mem
+        .search(request(SearchSort::FrameId, by_id.next_cursor))
+        .unwrap();
+    assert_eq!(page_ids(&by_id), [2, 3]);
+
+    // Cursors are bound to the sort order they were issued for.
+    assert!(
+        mem.search(request(SearchSort::FrameId, first.next_cursor))
+            .is_err()
+    );
+    assert!(
+        mem.search(request(SearchSort::TimestampAsc, Some("2".to_string())))
+            .is_err()
+    );
+    assert!(
+        mem.search(request(SearchSort::TimestampAsc, Some("bogus".to_string())))
+            .is_err()
+    );
+
+    // Relevance cursors are opaque too and keep the first page's frame set.
+    let ranked = mem.search(request(SearchSort::Relevance, None)).unwrap();
+    let cursor = ranked.next_cursor.clone().unwrap();
+    assert!(cursor.parse::<usize>().is_err());
+    let ranked_next = mem
+        .search(request(SearchSort::Relevance, Some(cursor)))
+        .unwrap();
+    assert_eq!(ranked_next.hits.len(), 2);
+
+    // Relevance pages resume after the last hit, so deleting an earlier hit
+    // between pages neither skips nor repeats one.
+    let mut seen = page_ids(&ranked);
+    mem.delete_frame(seen[0]).unwrap();
+    mem.commit().unwrap();
+    let mut cursor = ranked.next_cursor.clone();
+    while let Some(token) = cursor {
+        let page = mem
+            .search(request(SearchSort::Relevance, Some(token)))
+            .unwrap();
+        seen.extend(page_ids(&page));
+        cursor = page.next_cursor;
+    }
+    seen.sort_unstable();
+    assert_eq!(seen, [0, 1, 2, 3, 5, 6]);
+}
+
+#[test]
+#[cfg(feature = "lex")]
+fn search_routes_frames_to_language_analyzers() {
+    use memvid_core::types::{SearchEngineKind, SearchSort};
+    use memvid_core::{LANGUAGE_METADATA_KEY, SearchResponse};
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    let docs = [
+        (
+            "mv2://de/report",
+            "Die Verkäufe im dritten Quartal sind deutlich gestiegen, vor allem bei den Kunden in Österreich und der Schweiz.",
+        ),
+        (
+            "mv2://ja/office",
+            "東京オフィスは来年三月に移転します。新しいオフィスは渋谷駅の近くにあり、会議室が増えます。",
+        ),
+        (
+            "mv2://ko/maintenance",
+            "서울 데이터센터는 다음 주 토요일에 정기 점검을 진행합니다. 점검 중에는 서비스가 잠시 중단됩니다.",
+        ),
+    ];
+    for (uri, text) in docs {
+        let opts = PutOptions {
+            uri: Some(uri.to_string()),
+            search_text: Some(text.to_string()),
+            auto_tag: false,
+            ..Default::default()
+        };
+        mem.put_bytes_with_options(text.as_bytes(), opts).unwrap();
+    }
+    // A caller-supplied language wins over detection.
+    let text = "Reunion notes for the partners";
+    let mut opts = PutOptions {
+        uri: Some("mv2://fr/override".to_string()),
+        search_text: Some(text.to_string()),
+        auto_tag: false,
+        ..Default::default()
+    };
+    opts.extra_metadata
+        .insert(LANGUAGE_METADATA_KEY.to_string(), "fr".to_string());
+    mem.put_bytes_with_options(text.as_bytes(), opts).unwrap();
+    mem.commit().unwrap();
+
+    let language = |mem: &Memvid, uri: &str| {
+        mem.frame_by_uri(uri)
+            .unwrap()
+            .extra_metadata
+            .get(LANGUAGE_METADATA_KEY)
+            .cloned()
+    };
+    assert_eq!(language(&mem, "mv2://de/report").as_deref(), Some("de"));
+    assert_eq!(language(&mem, "mv2://ja/office").as_deref(), Some("ja"));
+    assert_eq!(
+        language(&mem, "mv2://ko/maintenance").as_deref(),
+        Some("ko")
+    );
+    assert_eq!(language(&mem, "mv2://fr/override").as_deref(), Some("fr"));
+
+    let search = |mem: &mut Memvid, query: &str| -> SearchResponse {
+        mem.search(SearchRequest {
+            query: query.to_string(),
+            top_k: 5,
+            snippet_chars: 200,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: true,
+            acl_context: None,
+            acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            diversify: None,
+            highlight: None,
+            facets: Vec::new(),
+            sort: SearchSort::Relevance,
+            explain: false,
+        })
+        .unwrap()
+    };
+    for (query, uri) in [
+        ("Kunde", "mv2://de/report"),
+        ("移転", "mv2://ja/office"),
+        ("渋谷駅", "mv2://ja/office"),
+        ("점검", "mv2://ko/maintenance"),
+        ("데이터센터", "mv2://ko/maintenance"),
+        ("partners", "mv2://fr/override"),
+    ] {
+        let response = search(&mut mem, query);
+        assert_eq!(response.engine, SearchEngineKind::Tantivy, "{query}");
+        let uris: Vec<&str> = response.hits.iter().map(|hit| hit.uri.as_str()).collect();
+        assert_eq!(uris, [uri], "{query}");
+    }
+
+    // Highlights analyse German text with the German stemmer: "Kunde" and
+    // "Kunden" share a German stem but not an English one.
+    let response = mem
+        .search(SearchRequest {
+            query: "Kunde".to_string(),
+            top_k: 1,
+            snippet_chars: 200,
+            uri: Some("mv2://de/report".to_string()),
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: true,
+            acl_context: None,
+            acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            diversify: None,
+            highlight: Some(memvid_core::HighlightOptions {
+                max_fragments: 1,
+                fragment_chars: 80,
+            }),
+            facets: Vec::new(),
+            sort: SearchSort::Relevance,
+            explain: false,
+        })
+        .unwrap();
+    let hit = &response.hits[0];
+    assert!(hit.text.contains("Kunden"), "{}", hit.text);
+    let frame_text = hit.chunk_text.as_deref().unwrap();
+    let marked: Vec<&str> = hit
+        .highlights
+        .iter()
+        .flat_map(|fragment| fragment.spans.iter())
+        .map(|span| &frame_text[span.range.0..span.range.1])
+        .collect();
+    assert_eq!(marked, ["Kunden"]);
+}
+
+#[test]
+#[cfg(feature = "lex")]
+fn search_applies_persisted_synonyms() {
+    use memvid_core::types::{AskMode, AskRequest, SearchSort};
+    use memvid_core::{QueryExpansion, SearchResponse, VecEmbedder};
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    let docs = [
+        (
+            "mv2://ops/cluster",
+            "The kubernetes cluster was upgraded over the weekend.",
+        ),
+        (
+            "mv2://hr/leave",
+            "Employees accrue vacation days every month.",
+        ),
+    ];
+    for (uri, text) in docs {
+        let opts = PutOptions {
+            uri: Some(uri.to_string()),
+            search_text: Some(text.to_string()),
+            auto_tag: false,
+            ..Default::default()
+        };
+        mem.put_bytes_with_options(text.as_bytes(), opts).unwrap();
+    }
+    mem.add_synonym_group(["K8s", "kubernetes"]).unwrap();
+    mem.add_query_expansion("PTO", ["vacation"]).unwrap();
+    mem.commit().unwrap();
+    drop(mem);
+
+    let mut mem = Memvid::open(&path).unwrap();
+    assert_eq!(mem.synonyms().groups, [vec!["k8s", "kubernetes"]]);
+
+    let search = |mem: &mut Memvid, query: &str| -> SearchResponse {
+        mem.search(SearchRequest {
+            query: query.to_string(),
+            top_k: 5,
+            snippet_chars: 200,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: false,
+            acl_context: None,
+            acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            diversify: None,
+            highlight: None,
+            facets: Vec::new(),
+            sort: SearchSort::Relevance,
+            explain: false,
+        })
+        .unwrap()
+    };
+    let uris = |response: &SearchResponse| -> Vec<String> {
+        response.hits.iter().map(|hit| hit.uri.clone()).collect()
+    };
+
+    let response = search(&mut mem, "k8s upgraded");
+    assert_eq!(uris(&response), ["mv2://ops/cluster"]);
+    assert_eq!(
+        response.query_expansions,
+        [QueryExpansion {
+            term: "k8s".into(),
+            expanded_to: vec!["kubernetes".into()],
+        }]
+    );
+    assert_eq!(uris(&search(&mut mem, "pto")), ["mv2://hr/leave"]);
+    // One-way: "vacation" does not pull in "pto"
+    let response = search(&mut mem, "vacation");
+    assert!(response.query_expansions.is_empty());
+
+    let ask = mem
+        .ask(
+            AskRequest {
+                question: "When was k8s upgraded?".to_string(),
+                top_k: 5,
+                snippet_chars: 200,
+                uri: None,
+                scope: None,
+                cursor: None,
+                start: None,
+                end: None,
+                #[cfg(feature = "temporal_track")]
+                temporal: None,
+                context_only: true,
+                mode: AskMode::Lex,
+                as_of_frame: None,
+                as_of_ts: None,
+                adaptive: None,
+                acl_context: None,
+                acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                diversify: None,
+                highlight: None,
+            },
+            None::<&dyn VecEmbedder>,
+        )
+        .unwrap();
+    assert_eq!(ask.retrieval.hits[0].uri, "mv2://ops/cluster");
+    assert!(
+        ask.retrieval
+            .query_expansions
+            .iter()
+            .any(|expansion| expansion.term == "k8s")
+    );
+
+    // Index-time expansion makes the one-way target find new "pto" frames too
+    mem.set_synonym_index_expansion(true).unwrap();
+    let text = "PTO requests need two weeks notice.";
+    let opts = PutOptions {
+        uri: Some("mv2://hr/requests".to_string()),
+        search_text: Some(text.to_string()),
+        auto_tag: false,
+        ..Default::default()
+    };
+    mem.put_bytes_with_options(text.as_bytes(), opts).unwrap();
+    mem.commit().unwrap();
+    let response = search(&mut mem, "vacation");
+    let mut found = uris(&response);
+    found.sort();
+    assert_eq!(found, ["mv2://hr/leave", "mv2://hr/requests"]);
+    // Recorded synonyms are indexed but never shown as frame text
+    let frame = mem.frame_by_uri("mv2://hr/requests").unwrap();
+    assert_eq!(
+        frame
+            .extra_metadata
+            .get(memvid_core::SYNONYMS_METADATA_KEY)
+            .map(String::as_str),
+        Some("vacation")
+    );
+    assert!(
+        response
+            .hits
+            .iter()
+            .all(|hit| !hit.text.contains("synonyms"))
+    );
+    assert!(
+        frame
+            .search_text
+            .as_deref()
+            .is_some_and(|text| !text.contains("vacation"))
+    );
+
+    assert!(mem.remove_synonym_group("kubernetes").unwrap());
+    assert!(search(&mut mem, "k8s").hits.is_empty());
+}
+
+/// Test search explanations for returned, paged-out and unmatched frames.
+#[test]
+fn explain_search_traces_stages_and_frames() {
+    use memvid_core::types::{AclContext, AclEnforcementMode, AdaptiveConfig, SearchSort};
+    use memvid_core::{FrameVerdict, SearchStage};
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    mem.enable_vec().unwrap();
+    let docs = [
+        ("mv2://a", "rust rust rust borrow checker", [1.0, 0.0, 0.0]),
+        ("mv2://b", "rust compiler notes", [0.0, 1.0, 0.0]),
+        ("mv2://c", "gardening in spring", [0.0, 0.0, 1.0]),
+    ];
+    for (uri, text, embedding) in docs {
+        let opts = PutOptions {
+            uri: Some(uri.to_string()),
+            search_text: Some(text.to_string()),
+            auto_tag: false,
+            ..Default::default()
+        };
+        mem.put_with_embedding_and_options(text.as_bytes(), embedding.to_vec(), opts)
+            .unwrap();
+    }
+    mem.commit().unwrap();
+
+    let request = |top_k: usize, explain: bool| SearchRequest {
+        query: "rust".to_string(),
+        top_k,
+        snippet_chars: 120,
+        uri: None,
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch: true,
+        acl_context: None,
+        acl_enforcement_mode: AclEnforcementMode::Audit,
+        diversify: None,
+        highlight: None,
+        facets: Vec::new(),
+        sort: SearchSort::Relevance,
+        explain,
+    };
+
+    assert!(mem.search(request(5, false)).unwrap().explanation.is_none());
+    let response = mem.search(request(5, true)).unwrap();
+    let explanation = response.explanation.unwrap();
+    assert_eq!(explanation.query_tree, "word:rust");
+    let stages: Vec<SearchStage> = explanation.stages.iter().map(|s| s.stage).collect();
+    assert_eq!(stages, [SearchStage::Engine, SearchStage::Pagination]);
+    assert_eq!(explanation.stages[0].candidates, Some(2));
+
+    let top = mem.explain_search(request(5, false), 0).unwrap();
+    let frame = top.frame.unwrap();
+    assert_eq!(frame.verdict, FrameVerdict::Returned { rank: 1 });
+    let bm25 = frame.bm25.unwrap();
+    assert!(bm25.value > 0.0);
+    assert!(!bm25.details.is_empty());
+
+    let paged = mem.explain_search(request(1, false), 1).unwrap();
+    let frame = paged.frame.unwrap();
+    assert!(matches!(
+        frame.verdict,
+        FrameVerdict::Dropped {
+            stage: SearchStage::Pagination,
+            ..
+        }
+    ));
+    assert_eq!(frame.lexical_rank, Some(2));
+
+    let unmatched = mem.explain_search(request(5, false), 2).unwrap();
+    let frame = unmatched.frame.unwrap();
+    assert!(matches!(
+        frame.verdict,
+        FrameVerdict::Dropped {
+            stage: SearchStage::Engine,
+            ..
+        }
+    ));
+    assert!(frame.bm25.is_none());
+
+    let hybrid = mem
+        .explain_hybrid_search(request(5, false), 1, &[0.0, 1.0, 0.0])
+        .unwrap();
+    let frame = hybrid.frame.unwrap();
+    assert_eq!(frame.vector_rank, Some(1));
+    assert!(frame.vector_distance.unwrap() < 1e-6);
+    let lists: Vec<(&str, usize)> = frame
+        .rrf
+        .iter()
+        .map(|entry| (entry.list.as_str(), entry.rank))
+        .collect();
+    assert_eq!(lists, [("lexical", 2), ("vector", 1)]);
+
+    // Adaptive retrieval keeps only the nearest vector, so the unmatched
+    // frame has no way into `ask` and no vector fusion term.
+    let config = AdaptiveConfig::with_relative_threshold(0.5);
+    let adaptive = mem
+        .explain_adaptive_search(request(5, false), 2, &[1.0, 0.0, 0.0], &config)
+        .unwrap();
+    assert_eq!(
+        adaptive.stages.last().map(|s| (s.stage, s.candidates)),
+        Some((SearchStage::AdaptiveCutoff, Some(1)))
+    );
+    let frame = adaptive.frame.unwrap();
+    assert!(frame.vector_rank.is_some());
+    assert!(frame.rrf.is_empty());
+    assert!(matches!(
+        frame.verdict,
+        FrameVerdict::Dropped {
+            stage: SearchStage::AdaptiveCutoff,
+            ..
+        }
+    ));
+
+    // Audit mode keeps denied hits but still reports the ACL's decisions.
+    let mut audited = request(5, true);
+    audited.acl_context = Some(AclContext {
+        tenant_id: Some("acme".to_string()),
+        ..Default::default()
+    });
+    let explanation = mem.search(audited).unwrap().explanation.unwrap();
+    let stages: Vec<(SearchStage, Option<usize>)> = explanation
+        .stages
+        .iter()
+        .map(|s| (s.stage, s.candidates))
+        .collect();
+    assert_eq!(
+        stages,
+        [
+            (SearchStage::Engine, Some(2)),
+            (SearchStage::AclAudit, Some(0)),
+            (SearchStage::Pagination, Some(2)),
+        ]
+    );
+
+    assert!(mem.explain_search(request(5, false), 99).is_err());
+}
+
+/// Test that hits on recognized text report the page and box of the match.
+#[test]
+#[cfg(feature = "lex")]
+fn search_reports_ocr_regions() {
+    use memvid_core::{OCR_WORDS_METADATA_KEY, OcrBox, OcrWord, encode_ocr_words};
+
+    let line = ["Either", "party", "may", "request", "termination", "notice"];
+    let words: Vec<OcrWord> = line
+        .iter()
+        .enumerate()
+        .map(|(i, text)| OcrWord {
+            text: (*text).to_string(),
+            page: 3,
+            bbox: OcrBox {
+                x: 0.1 + 0.12 * i as f32,
+                y: 0.4,
+                width: 0.1,
+                height: 0.02,
+            },
+            confidence: 0.95,
+        })
+        .collect();
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    let mut options = PutOptions::builder()
+        .uri("mv2://contracts/scan.pdf")
+        .search_text(line.join(" "))
+        .build();
+    options
+        .extra_metadata
+        .insert(OCR_WORDS_METADATA_KEY.to_string(), encode_ocr_words(&words));
+    mem.put_bytes_with_options(b"Scanned service agreement.", options)
+        .unwrap();
+    mem.commit().unwrap();
+
+    let request = || SearchRequest {
+        query: "termination notice".to_string(),
+        top_k: 5,
+        snippet_chars: 200,
+        uri: None,
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch: false,
+        acl_context: None,
+        acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        diversify: None,
+        highlight: None,
+        facets: Vec::new(),
+        sort: memvid_core::types::SearchSort::Relevance,
+        explain: false,
+    };
+    let results = mem.search(request()).unwrap();
+
+    let metadata = results.hits[0].metadata.as_ref().unwrap();
+    assert!(!metadata.extra_metadata.contains_key(OCR_WORDS_METADATA_KEY));
+    let region = metadata.region.as_ref().unwrap();
+    assert_eq!(region.page, 3);
+    assert_eq!(region.text, "termination notice");
+    assert!((region.x - 0.58).abs() < 1e-3);
+    assert!((region.width - 0.22).abs() < 1e-3);
+    let frame = mem.frame_by_id(results.hits[0].frame_id).unwrap();
+    assert!(!frame.extra_metadata.contains_key(OCR_WORDS_METADATA_KEY));
+
+    // The layout is persisted with the commit
+    drop(mem);
+    let mut mem = Memvid::open(&path).unwrap();
+    let results = mem.search(request()).unwrap();
+    let region = results.hits[0]
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.region.as_ref())
+        .unwrap();
+    assert_eq!(region.page, 3);
+    assert_eq!(region.text, "termination notice");
+}
+
+#[test]
+#[cfg(feature = "lex")]
+fn search_sorted_total_hits_respects_scope() {
+    use memvid_core::types::SearchSort;
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    for (i, uri) in [
+        "mv2://a/1",
+        "mv2://b/1",
+        "mv2://a/2",
+        "mv2://b/2",
+        "mv2://a/3",
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let opts = PutOptions {
+            uri: Some(uri.to_string()),
+            timestamp: Some(1_700_000_000 + i64::try_from(i).unwrap()),
+            auto_tag: false,
+            ..Default::default()
+        };
+        mem.put_bytes_with_options(b"weekly report", opts).unwrap();
+    }
+    mem.commit().unwrap();
+
+    let response = mem
+        .search(SearchRequest {
+            query: "report".to_string(),
+            top_k: 2,
+            snippet_chars: 200,
+            uri: None,
+            scope: Some("mv2://a/".to_string()),
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: true,
+            acl_context: None,
+            acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            diversify: None,
+            highlight: None,
+            facets: Vec::new(),
+            sort: SearchSort::TimestampAsc,
+            explain: false,
+        })
+        .unwrap();
+    let uris: Vec<&str> = response.hits.iter().map(|hit| hit.uri.as_str()).collect();
+    assert_eq!(uris, ["mv2://a/1", "mv2://a/2"]);
+    assert_eq!(response.total_hits, 3);
+    assert!(response.next_cursor.is_some());
+}
@@ -0,0 +1,253 @@
+//! Integration tests for directory and archive sync.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use memvid_core::{FrameStatus, Memvid, MemvidError, SYNC_HASH_METADATA_KEY, SyncOptions};
+use tempfile::TempDir;
+
+fn write(root: &Path, relative: &str, contents: &str) {
+    let path = root.join(relative);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+/// Test that a re-sync adds, supersedes and deletes only what changed.
+#[test]
+fn sync_dir_tracks_added_changed_and_deleted_files() {
+    let dir = TempDir::new().unwrap();
+    let tree = dir.path().join("notes");
+    write(&tree, "a.md", "Alpha notes about the garden.");
+    write(&tree, "sub/b.txt", "Beta notes about the kitchen.");
+    write(&tree, ".cache/c.md", "Hidden notes.");
+    write(&tree, "build.log", "Log output.");
+    let path = dir.path().join("test.mv2");
+    let options = SyncOptions {
+        exclude: vec!["*.log".to_string()],
+        ..SyncOptions::default()
+    };
+
+    {
+        let mut mem = Memvid::create(&path).unwrap();
+        let report = mem.sync_dir(&tree, options.clone()).unwrap();
+        assert_eq!(
+            report.added,
+            vec![
+                "mv2://notes/a.md".to_string(),
+                "mv2://notes/sub/b.txt".to_string()
+            ]
+        );
+        mem.commit().unwrap();
+    }
+
+    // Nothing changed: the second sync reads no frames back and writes nothing.
+    {
+        let before = fs::read(&path).unwrap();
+        let mut mem = Memvid::open(&path).unwrap();
+        let report = mem.sync_dir(&tree, options.clone()).unwrap();
+        assert!(report.is_unchanged());
+        assert_eq!(report.unchanged, 2);
+        drop(mem);
+        assert_eq!(fs::read(&path).unwrap(), before);
+    }
+
+    write(&tree, "a.md", "Alpha notes about the greenhouse, revised.");
+    fs::remove_file(tree.join("sub/b.txt")).unwrap();
+    write(&tree, "c.md", "Gamma notes.");
+    {
+        let mut mem = Memvid::open(&path).unwrap();
+        let report = mem.sync_dir(&tree, options.clone()).unwrap();
+        assert_eq!(report.added, vec!["mv2://notes/c.md".to_string()]);
+        assert_eq!(report.updated, vec!["mv2://notes/a.md".to_string()]);
+        assert_eq!(report.deleted, vec!["mv2://notes/sub/b.txt".to_string()]);
+        mem.commit().unwrap();
+    }
+
+    let mut mem = Memvid::open(&path).unwrap();
+    let frame = mem.frame_by_uri("mv2://notes/a.md").unwrap();
+    assert!(frame.extra_metadata.contains_key(SYNC_HASH_METADATA_KEY));
+    assert!(
+        frame
+            .source_path
+            .as_deref()
+            .is_some_and(|source| source.ends_with("a.md"))
+    );
+    let search_text = frame.search_text.clone().unwrap_or_default();
+    assert!(search_text.contains("greenhouse"));
+    assert!(!search_text.contains("garden"));
+    assert!(mem.frame_text_by_id(frame.id).unwrap().contains("revised"));
+    assert_eq!(
+        mem.frame_by_uri("mv2://notes/sub/b.txt").unwrap().status,
+        FrameStatus::Deleted
+    );
+    assert!(mem.sync_dir(&tree, options).unwrap().is_unchanged());
+}
+
+/// Test that a touched file is hashed once and then recognised by its new time.
+#[test]
+fn sync_dir_records_new_time_of_unchanged_files() {
+    let dir = TempDir::new().unwrap();
+    let tree = dir.path().join("notes");
+    write(&tree, "a.md", "Alpha notes about the garden.");
+    write(&tree, "b.md", "Beta notes about the kitchen.");
+    let path = dir.path().join("test.mv2");
+
+    {
+        let mut mem = Memvid::create(&path).unwrap();
+        mem.sync_dir(&tree, SyncOptions::default()).unwrap();
+        mem.commit().unwrap();
+    }
+
+    let touched = SystemTime::now() + Duration::from_secs(3600);
+    fs::File::options()
+        .write(true)
+        .open(tree.join("a.md"))
+        .unwrap()
+        .set_modified(touched)
+        .unwrap();
+    {
+        let mut mem = Memvid::open(&path).unwrap();
+        let report = mem.sync_dir(&tree, SyncOptions::default()).unwrap();
+        assert!(report.is_unchanged());
+        assert_eq!(report.unchanged, 2);
+        assert_eq!(report.hashed, 1);
+        mem.commit().unwrap();
+    }
+
+    let mut mem = Memvid::open(&path).unwrap();
+    let report = mem.sync_dir(&tree, SyncOptions::default()).unwrap();
+    assert_eq!(report.unchanged, 2);
+    assert_eq!(report.hashed, 0);
+}
+
+/// Test that file names differing only in delimiters keep distinct URIs.
+#[test]
+fn sync_dir_percent_encodes_path_segments() {
+    let dir = TempDir::new().unwrap();
+    let tree = dir.path().join("notes");
+    write(&tree, "a b.md", "Spaced notes.");
+    write(&tree, "a-b.md", "Dashed notes.");
+    write(&tree, "50%/c#1.md", "Marked notes.");
+    let mut mem = Memvid::create(dir.path().join("test.mv2")).unwrap();
+    let report = mem.sync_dir(&tree, SyncOptions::default()).unwrap();
+    assert_eq!(
+        report.added,
+        vec![
+            "mv2://notes/50%25/c%231.md".to_string(),
+            "mv2://notes/a%20b.md".to_string(),
+            "mv2://notes/a-b.md".to_string(),
+        ]
+    );
+    mem.commit().unwrap();
+
+    let spaced = mem.frame_by_uri("mv2://notes/a%20b.md").unwrap();
+    assert!(mem.frame_text_by_id(spaced.id).unwrap().contains("Spaced"));
+    let dashed = mem.frame_by_uri("mv2://notes/a-b.md").unwrap();
+    assert!(mem.frame_text_by_id(dashed.id).unwrap().contains("Dashed"));
+}
+
+/// Test that zip members sync under the archive name and honour include globs.
+#[test]
+fn sync_dir_reads_zip_archives() {
+    let dir = TempDir::new().unwrap();
+    let archive = dir.path().join("handbook.zip");
+    {
+        let mut zip = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+        for (name, body) in [
+            ("intro.md", "Welcome to the handbook."),
+            ("policies/leave.md", "Leave policy details."),
+            ("logo.txt", "Not markdown."),
+        ] {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(body.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    let mut mem = Memvid::create(dir.path().join("test.mv2")).unwrap();
+    let options = SyncOptions {
+        include: vec!["*.md".to_string()],
+        ..SyncOptions::default()
+    };
+    let report = mem.sync_dir(&archive, options.clone()).unwrap();
+    assert_eq!(
+        report.added,
+        vec![
+            "mv2://handbook/intro.md".to_string(),
+            "mv2://handbook/policies/leave.md".to_string()
+        ]
+    );
+    mem.commit().unwrap();
+
+    let frame = mem
+        .frame_by_uri("mv2://handbook/policies/leave.md")
+        .unwrap();
+    assert!(
+        frame
+            .source_path
+            .as_deref()
+            .is_some_and(|source| source.ends_with("handbook.zip!/policies/leave.md"))
+    );
+    assert!(mem.sync_dir(&archive, options).unwrap().is_unchanged());
+}
+
+/// Test that a tar path stored twice syncs its last copy and that members
+/// over the size limit are skipped.
+#[test]
+fn sync_dir_reads_tar_archives() {
+    let dir = TempDir::new().unwrap();
+    let archive = dir.path().join("notes.tar");
+    {
+        let mut tar = tar::Builder::new(fs::File::create(&archive).unwrap());
+        for (name, body) in [
+            ("todo.md", "First draft."),
+            ("big.md", "A member well over the configured size limit."),
+            ("todo.md", "Final list."),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(body.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar.append_data(&mut header, name, body.as_bytes()).unwrap();
+        }
+        tar.finish().unwrap();
+    }
+
+    let mut mem = Memvid::create(dir.path().join("test.mv2")).unwrap();
+    let options = SyncOptions {
+        max_file_bytes: Some(20),
+        ..SyncOptions::default()
+    };
+    let report = mem.sync_dir(&archive, options).unwrap();
+    assert_eq!(report.added, vec!["mv2://notes/todo.md".to_string()]);
+    assert_eq!(report.skipped, vec!["mv2://notes/big.md".to_string()]);
+    mem.commit().unwrap();
+
+    let frame = mem.frame_by_uri("mv2://notes/todo.md").unwrap();
+    assert!(
+        mem.frame_text_by_id(frame.id)
+            .unwrap()
+            .starts_with("Final list.")
+    );
+}
+
+/// Test that a malformed glob is reported before anything is written.
+#[test]
+fn sync_dir_rejects_invalid_globs() {
+    let dir = TempDir::new().unwrap();
+    let mut mem = Memvid::create(dir.path().join("test.mv2")).unwrap();
+    let err = mem
+        .sync_dir(
+            dir.path(),
+            SyncOptions {
+                include: vec!["[".to_string()],
+                ..SyncOptions::default()
+            },
+        )
+        .unwrap_err();
+    assert!(matches!(err, MemvidError::Sync { .. }));
+}
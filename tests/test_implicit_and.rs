@@ -52,6 +52,11 @@ fn test_implicit_and_precision() -> memvid_core::Result<()> {
         no_sketch: false,
         acl_context: None,
         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        diversify: None,
+        highlight: None,
+        facets: Vec::new(),
+        sort: memvid_core::types::SearchSort::Relevance,
+        explain: false,
     })?;
 
     assert_eq!(
@@ -107,6 +112,11 @@ fn test_explicit_operators_still_work() -> memvid_core::Result<()> {
         no_sketch: false,
         acl_context: None,
         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        diversify: None,
+        highlight: None,
+        facets: Vec::new(),
+        sort: memvid_core::types::SearchSort::Relevance,
+        explain: false,
     })?;
 
     assert_eq!(results.hits.len(), 1, "Explicit AND should work");
@@ -126,6 +136,11 @@ fn test_explicit_operators_still_work() -> memvid_core::Result<()> {
         no_sketch: false,
         acl_context: None,
         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        diversify: None,
+        highlight: None,
+        facets: Vec::new(),
+        sort: memvid_core::types::SearchSort::Relevance,
+        explain: false,
     })?;
 
     assert!(results.hits.len() >= 2, "Explicit OR should work");
@@ -293,6 +293,11 @@ fn search_arden(mem: &mut Memvid, query: &str, top_k: usize) -> Vec<memvid_core:
         no_sketch: false,
         acl_context: None,
         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        diversify: None,
+        highlight: None,
+        facets: Vec::new(),
+        sort: memvid_core::types::SearchSort::Relevance,
+        explain: false,
     })
     .unwrap()
     .hits